@@ -69,6 +69,7 @@
 //! - **`trig`**: Trigonometric functions with clamping (sin, cos, tan, atan2)
 //! - **`linear`**: Linear algebra (dot product, magnitude, distance, normalize)
 //! - **`ops`**: WofLang interpreter integration (opcodes 7000-7999)
+//! - **`stream`**: Oscillators, envelopes and ring-buffered streaming to WAV
 //!
 //! ## Example: Neural Network Activation
 //!
@@ -95,6 +96,7 @@ pub mod linear;
 pub mod math;
 pub mod mode;
 pub mod ops;
+pub mod stream;
 pub mod test_suite;
 pub mod trig;
 
@@ -21,6 +21,8 @@
 //! assert_eq!(config.sub(-80.0, 50.0), -100.0); // Saturates at -100
 //! ```
 
+use wide::f64x4;
+
 use crate::mode::{clamp_analog, get_analog_config, AnalogConfig};
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -267,41 +269,129 @@ pub fn analog_fma(a: f64, b: f64, c: f64) -> f64 {
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
-// BATCH OPERATIONS (for future SIMD optimization)
+// BATCH OPERATIONS (SIMD)
 // ═══════════════════════════════════════════════════════════════════════════
+//
+// The kernels below run four lanes at a time as `wide::f64x4` vectors,
+// clamping with lane-wise min/max (NaN stays NaN, as in the scalar ops).
+// The range is read once per batch rather than once per element.
+
+/// Number of `f64` values processed per SIMD step (one AVX register).
+pub const BATCH_LANES: usize = 4;
+
+/// Load up to [`BATCH_LANES`] values, padding the rest with zeros.
+#[inline]
+fn load(values: &[f64]) -> f64x4 {
+    let mut lanes = [0.0; BATCH_LANES];
+    lanes[..values.len()].copy_from_slice(values);
+    f64x4::new(lanes)
+}
+
+/// Clamp every lane of `x` to `[lo, hi]` as [`f64::clamp`] does.
+#[inline]
+fn clamp_lanes(x: f64x4, lo: f64x4, hi: f64x4) -> f64x4 {
+    x.is_nan().blend(x, x.max(lo).min(hi))
+}
+
+/// Apply `f` to `a` and `b` four lanes at a time, storing the results,
+/// clamped to `[lo, hi]`, in `a`. Only the overlap of the slices is
+/// touched.
+#[inline]
+fn lanes_binary_in_place(a: &mut [f64], b: &[f64], lo: f64, hi: f64, f: impl Fn(f64x4, f64x4) -> f64x4) {
+    let n = a.len().min(b.len());
+    let (lo, hi) = (f64x4::splat(lo), f64x4::splat(hi));
+    for (x, y) in a[..n].chunks_mut(BATCH_LANES).zip(b[..n].chunks(BATCH_LANES)) {
+        let result = clamp_lanes(f(load(x), load(y)), lo, hi);
+        x.copy_from_slice(&result.to_array()[..x.len()]);
+    }
+}
+
+/// Apply `f` to `a` four lanes at a time, storing the clamped results in
+/// place.
+#[inline]
+fn lanes_unary_in_place(a: &mut [f64], lo: f64, hi: f64, f: impl Fn(f64x4) -> f64x4) {
+    let (lo, hi) = (f64x4::splat(lo), f64x4::splat(hi));
+    for x in a.chunks_mut(BATCH_LANES) {
+        let result = clamp_lanes(f(load(x)), lo, hi);
+        x.copy_from_slice(&result.to_array()[..x.len()]);
+    }
+}
 
 /// Batch add: adds corresponding elements, clamping each result.
 #[must_use]
 pub fn batch_add(a: &[f64], b: &[f64]) -> Vec<f64> {
     let config = get_analog_config();
-    a.iter()
-        .zip(b.iter())
-        .map(|(x, y)| config.add(*x, *y))
-        .collect()
+    let mut out = a[..a.len().min(b.len())].to_vec();
+    lanes_binary_in_place(&mut out, b, config.min(), config.max(), |x, y| x + y);
+    out
 }
 
 /// Batch multiply: multiplies corresponding elements, clamping each result.
 #[must_use]
 pub fn batch_mul(a: &[f64], b: &[f64]) -> Vec<f64> {
     let config = get_analog_config();
-    a.iter()
-        .zip(b.iter())
-        .map(|(x, y)| config.mul(*x, *y))
-        .collect()
+    let mut out = a[..a.len().min(b.len())].to_vec();
+    lanes_binary_in_place(&mut out, b, config.min(), config.max(), |x, y| x * y);
+    out
 }
 
 /// Batch clamp: clamps all values in a slice.
 #[must_use]
 pub fn batch_clamp(values: &[f64]) -> Vec<f64> {
     let config = get_analog_config();
-    values.iter().map(|x| config.clamp(*x)).collect()
+    let mut out = values.to_vec();
+    lanes_unary_in_place(&mut out, config.min(), config.max(), |x| x);
+    out
 }
 
 /// Batch scale: multiplies all values by a scalar, clamping each.
 #[must_use]
 pub fn batch_scale(values: &[f64], scalar: f64) -> Vec<f64> {
     let config = get_analog_config();
-    values.iter().map(|x| config.mul(*x, scalar)).collect()
+    let mut out = values.to_vec();
+    lanes_unary_in_place(&mut out, config.min(), config.max(), |x| x * scalar);
+    out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BLOCK OPERATIONS (IN PLACE)
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// Slice variants of the config methods for real-time DSP: they overwrite
+// the first buffer instead of allocating, and run on the same SIMD lanes
+// as the batch kernels. Binary ops touch only the overlap of the two
+// slices.
+
+impl AnalogConfig {
+    /// `a[i] += b[i]` with clamping.
+    pub fn add_slice(&self, a: &mut [f64], b: &[f64]) {
+        lanes_binary_in_place(a, b, self.min(), self.max(), |x, y| x + y);
+    }
+
+    /// `a[i] -= b[i]` with clamping.
+    pub fn sub_slice(&self, a: &mut [f64], b: &[f64]) {
+        lanes_binary_in_place(a, b, self.min(), self.max(), |x, y| x - y);
+    }
+
+    /// `a[i] *= b[i]` with clamping.
+    pub fn mul_slice(&self, a: &mut [f64], b: &[f64]) {
+        lanes_binary_in_place(a, b, self.min(), self.max(), |x, y| x * y);
+    }
+
+    /// Multiply every sample by `gain`, clamping.
+    pub fn scale_slice(&self, a: &mut [f64], gain: f64) {
+        lanes_unary_in_place(a, self.min(), self.max(), |x| x * gain);
+    }
+
+    /// Clamp every sample to the range.
+    pub fn clamp_slice(&self, a: &mut [f64]) {
+        lanes_unary_in_place(a, self.min(), self.max(), |x| x);
+    }
+
+    /// Hyperbolic tangent of every sample (soft saturation), clamping.
+    pub fn tanh_slice(&self, a: &mut [f64]) {
+        lanes_unary_in_place(a, self.min(), self.max(), |x| f64x4::new(x.to_array().map(f64::tanh)));
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -396,6 +486,60 @@ mod tests {
         assert_eq!(scaled, vec![20.0, 40.0, 100.0]); // Last one saturates
     }
 
+    #[test]
+    fn batch_matches_scalar_across_lanes() {
+        setup();
+        set_analog_mode(AnalogMode::FloatUnit);
+        let config = get_analog_config();
+
+        // Long enough to exercise full lanes and a remainder.
+        let a: Vec<f64> = (0..11).map(|i| f64::from(i) * 0.3 - 1.5).collect();
+        let b: Vec<f64> = (0..11).map(|i| 0.8 - f64::from(i) * 0.2).collect();
+
+        let sum = batch_add(&a, &b);
+        let product = batch_mul(&a, &b);
+        for i in 0..a.len() {
+            assert_eq!(sum[i], config.add(a[i], b[i]));
+            assert_eq!(product[i], config.mul(a[i], b[i]));
+        }
+        assert_eq!(batch_clamp(&a), a.iter().map(|x| config.clamp(*x)).collect::<Vec<_>>());
+        assert_eq!(batch_add(&a, &b[..3]).len(), 3);
+        assert!(batch_add(&[f64::NAN; 5], &[0.0; 5]).iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn slices_update_in_place_like_scalars() {
+        let config = AnalogConfig::new(AnalogMode::FloatUnit);
+        let a: Vec<f64> = (0..11).map(|i| f64::from(i) * 0.3 - 1.5).collect();
+        let b: Vec<f64> = (0..11).map(|i| 0.8 - f64::from(i) * 0.2).collect();
+        let each = |f: &dyn Fn(f64, f64) -> f64| a.iter().zip(&b).map(|(x, y)| f(*x, *y)).collect::<Vec<_>>();
+
+        let mut sum = a.clone();
+        config.add_slice(&mut sum, &b);
+        assert_eq!(sum, each(&|x, y| config.add(x, y)));
+        let mut diff = a.clone();
+        config.sub_slice(&mut diff, &b);
+        assert_eq!(diff, each(&|x, y| config.sub(x, y)));
+        let mut product = a.clone();
+        config.mul_slice(&mut product, &b);
+        assert_eq!(product, each(&|x, y| config.mul(x, y)));
+
+        let mut scaled = a.clone();
+        config.scale_slice(&mut scaled, 0.5);
+        assert_eq!(scaled, a.iter().map(|x| config.mul(*x, 0.5)).collect::<Vec<_>>());
+        let mut soft = a.clone();
+        config.tanh_slice(&mut soft);
+        assert_eq!(soft, a.iter().map(|x| config.tanh(*x)).collect::<Vec<_>>());
+        let mut clamped = a.clone();
+        config.clamp_slice(&mut clamped);
+        assert_eq!(clamped, a.iter().map(|x| config.clamp(*x)).collect::<Vec<_>>());
+
+        // Only the overlap changes.
+        let mut short = vec![0.5; 6];
+        config.add_slice(&mut short, &[0.25, 0.25]);
+        assert_eq!(short, [0.75, 0.75, 0.5, 0.5, 0.5, 0.5]);
+    }
+
     #[test]
     fn fma() {
         setup();
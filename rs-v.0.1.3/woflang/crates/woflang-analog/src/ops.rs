@@ -13,6 +13,8 @@
 //! - 7050-7069: Linear algebra 2D
 //! - 7070-7089: Linear algebra 3D
 //! - 7090-7099: Coordinate transforms
+//! - 7100-7109: Batch operations
+//! - 7110-7119: Buffer (block) operations
 
 use crate::linear;
 use crate::math;
@@ -422,6 +424,121 @@ pub fn op_polar_to_cartesian(r: &WofValue, theta: &WofValue) -> AnalogResult<(Wo
     Ok((analog_value(x), analog_value(y)))
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// BATCH OPERATIONS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Convert a run of stack values to `f64` for the batch kernels.
+fn to_f64_vec(values: &[WofValue]) -> AnalogResult<Vec<f64>> {
+    values.iter().map(to_f64).collect()
+}
+
+/// Wrap batch results, rounding to integers in the integer modes.
+fn batch_values(results: Vec<f64>) -> Vec<WofValue> {
+    if get_analog_config().is_integer_mode() {
+        results.into_iter().map(|v| WofValue::integer(v.round() as i64)).collect()
+    } else {
+        results.into_iter().map(WofValue::double).collect()
+    }
+}
+
+/// Opcode 7100: Element-wise analog add of two equal-length runs.
+pub fn op_batch_add(a: &[WofValue], b: &[WofValue]) -> AnalogResult<Vec<WofValue>> {
+    Ok(batch_values(math::batch_add(&to_f64_vec(a)?, &to_f64_vec(b)?)))
+}
+
+/// Opcode 7101: Element-wise analog multiply of two equal-length runs.
+pub fn op_batch_mul(a: &[WofValue], b: &[WofValue]) -> AnalogResult<Vec<WofValue>> {
+    Ok(batch_values(math::batch_mul(&to_f64_vec(a)?, &to_f64_vec(b)?)))
+}
+
+/// Opcode 7102: Clamp a run of values to the analog range.
+pub fn op_batch_clamp(values: &[WofValue]) -> AnalogResult<Vec<WofValue>> {
+    Ok(batch_values(math::batch_clamp(&to_f64_vec(values)?)))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BUFFER OPERATIONS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Apply an in-place binary kernel to two equal-length buffers.
+fn buffer_binary(
+    a: WofValue,
+    b: &WofValue,
+    kernel: fn(&AnalogConfig, &mut [f64], &[f64]),
+) -> AnalogResult<WofValue> {
+    let rhs = b.as_buffer()?;
+    let mut samples = a.into_buffer()?;
+    if samples.len() != rhs.len() {
+        return Err(WofError::runtime(format!(
+            "analog buffer: length mismatch ({} vs {})",
+            samples.len(),
+            rhs.len()
+        )));
+    }
+    kernel(&get_analog_config(), &mut samples, rhs);
+    Ok(WofValue::buffer(samples))
+}
+
+/// Opcode 7110: Build a buffer from a run of values, clamping each.
+pub fn op_buffer_from(values: &[WofValue]) -> AnalogResult<WofValue> {
+    let mut samples = to_f64_vec(values)?;
+    get_analog_config().clamp_slice(&mut samples);
+    Ok(WofValue::buffer(samples))
+}
+
+/// Opcode 7111: A buffer of `len` copies of a clamped value.
+pub fn op_buffer_fill(value: &WofValue, len: usize) -> AnalogResult<WofValue> {
+    Ok(WofValue::buffer(vec![clamp_analog(to_f64(value)?); len]))
+}
+
+/// Opcode 7112: Unpack a buffer into stack values.
+pub fn op_buffer_values(buffer: &WofValue) -> AnalogResult<Vec<WofValue>> {
+    Ok(batch_values(buffer.as_buffer()?.to_vec()))
+}
+
+/// Opcode 7113: Number of samples in a buffer.
+pub fn op_buffer_len(buffer: &WofValue) -> AnalogResult<WofValue> {
+    Ok(WofValue::integer(buffer.as_buffer()?.len() as i64))
+}
+
+/// Opcode 7114: Sample-wise add of two buffers.
+pub fn op_buffer_add(a: WofValue, b: &WofValue) -> AnalogResult<WofValue> {
+    buffer_binary(a, b, AnalogConfig::add_slice)
+}
+
+/// Opcode 7115: Sample-wise subtract of two buffers.
+pub fn op_buffer_sub(a: WofValue, b: &WofValue) -> AnalogResult<WofValue> {
+    buffer_binary(a, b, AnalogConfig::sub_slice)
+}
+
+/// Opcode 7116: Sample-wise multiply (ring modulation) of two buffers.
+pub fn op_buffer_mul(a: WofValue, b: &WofValue) -> AnalogResult<WofValue> {
+    buffer_binary(a, b, AnalogConfig::mul_slice)
+}
+
+/// Opcode 7117: Scale a buffer by a gain.
+pub fn op_buffer_scale(buffer: WofValue, gain: &WofValue) -> AnalogResult<WofValue> {
+    let gain = to_f64(gain)?;
+    let mut samples = buffer.into_buffer()?;
+    get_analog_config().scale_slice(&mut samples, gain);
+    Ok(WofValue::buffer(samples))
+}
+
+/// Opcode 7118: Soft-saturate a buffer with tanh.
+pub fn op_buffer_tanh(buffer: WofValue) -> AnalogResult<WofValue> {
+    let mut samples = buffer.into_buffer()?;
+    get_analog_config().tanh_slice(&mut samples);
+    Ok(WofValue::buffer(samples))
+}
+
+/// Opcode 7119: Clamp a buffer to the current range.
+pub fn op_buffer_clamp(buffer: WofValue) -> AnalogResult<WofValue> {
+    let mut samples = buffer.into_buffer()?;
+    get_analog_config().clamp_slice(&mut samples);
+    Ok(WofValue::buffer(samples))
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // TESTS
 // ═══════════════════════════════════════════════════════════════════════════
@@ -501,4 +618,49 @@ mod tests {
         let mag = op_magnitude_2d(&x1, &y1).unwrap();
         assert_eq!(mag.as_double().unwrap(), 5.0);
     }
+
+    #[test]
+    fn batch_operations_follow_mode() {
+        setup();
+        op_analog_mode_int201();
+
+        let a: Vec<WofValue> = [10.0, 60.0, -90.0].map(WofValue::double).to_vec();
+        let b: Vec<WofValue> = [5.5, 60.0, -30.0].map(WofValue::double).to_vec();
+
+        let sum = op_batch_add(&a, &b).unwrap();
+        let sum: Vec<i64> = sum.iter().map(|v| v.as_integer().unwrap()).collect();
+        assert_eq!(sum, vec![16, 100, -100]);
+
+        op_analog_mode_float_unit();
+        let clamped = op_batch_clamp(&a).unwrap();
+        assert_eq!(clamped[1].as_double().unwrap(), 1.0);
+        assert!(op_batch_mul(&[WofValue::string("x")], &b).is_err());
+    }
+
+    #[test]
+    fn buffer_operations_work_in_place() {
+        setup();
+        op_analog_mode_float_unit();
+
+        let a = op_buffer_from(&[0.5, 2.0, -0.25].map(WofValue::double)).unwrap();
+        assert_eq!(a.as_buffer().unwrap(), [0.5, 1.0, -0.25]);
+        let b = op_buffer_fill(&WofValue::double(0.75), 3).unwrap();
+
+        // The sum reuses `a`'s samples when it holds the only handle.
+        let ptr = a.as_buffer().unwrap().as_ptr();
+        let sum = op_buffer_add(a, &b).unwrap();
+        assert_eq!(sum.as_buffer().unwrap(), [1.0, 1.0, 0.5]);
+        assert_eq!(sum.as_buffer().unwrap().as_ptr(), ptr);
+
+        let shared = sum.clone();
+        let product = op_buffer_mul(sum, &b).unwrap();
+        assert_eq!(product.as_buffer().unwrap(), [0.75, 0.75, 0.375]);
+        assert_eq!(shared.as_buffer().unwrap(), [1.0, 1.0, 0.5]);
+
+        let quiet = op_buffer_scale(shared, &WofValue::double(-0.5)).unwrap();
+        assert_eq!(op_buffer_values(&quiet).unwrap(), [-0.5, -0.5, -0.25].map(WofValue::double));
+        assert_eq!(op_buffer_len(&quiet).unwrap(), WofValue::integer(3));
+        assert!(op_buffer_add(quiet, &WofValue::buffer(vec![0.0])).is_err());
+        assert!(op_buffer_tanh(WofValue::double(1.0)).is_err());
+    }
 }
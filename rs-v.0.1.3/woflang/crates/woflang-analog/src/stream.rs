@@ -0,0 +1,485 @@
+//! Streaming audio and control-voltage generation.
+//!
+//! A [`Patch`] is a bank of oscillator voices, each optionally shaped by
+//! an ADSR envelope. A [`Stream`] pulls samples out of a patch through a
+//! fixed-size [`RingBuffer`], generating a block at a time, so a consumer
+//! can read any number of samples without the whole signal in memory.
+//! [`write_wav`] renders samples to a 16-bit mono WAV file.
+//!
+//! The mix saturates at ±1 like the rest of the analog module, so piling
+//! up voices clips rather than wraps.
+//!
+//! # Examples
+//!
+//! ```
+//! use woflang_analog::stream::{Envelope, Oscillator, Patch, Stream, Waveform};
+//!
+//! let mut patch = Patch::new(8000);
+//! let voice = patch.add(Oscillator::new(Waveform::Sine, 440.0, 0.5));
+//! patch.set_envelope(voice, Envelope::new(0.01, 0.1, 0.7, 0.2, 0.5));
+//!
+//! let mut stream = Stream::new(patch, 256);
+//! let mut block = [0.0; 100];
+//! stream.read(&mut block);
+//! assert!(block.iter().all(|s| s.abs() <= 0.5));
+//! ```
+
+use std::f64::consts::TAU;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Default sample rate in Hz.
+pub const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// SOURCES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Oscillator waveform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Waveform {
+    /// Pure sine.
+    Sine,
+    /// Rising sawtooth.
+    Saw,
+    /// 50% duty square.
+    Square,
+    /// Symmetric triangle.
+    Triangle,
+}
+
+impl Waveform {
+    /// Parse `"sine"`, `"saw"`, `"square"` or `"tri"`/`"triangle"`.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "sine" => Self::Sine,
+            "saw" => Self::Saw,
+            "square" => Self::Square,
+            "tri" | "triangle" => Self::Triangle,
+            _ => return None,
+        })
+    }
+
+    /// Value in `[-1, 1]` at `phase` in `[0, 1)`.
+    #[must_use]
+    pub fn at(self, phase: f64) -> f64 {
+        match self {
+            Self::Sine => (TAU * phase).sin(),
+            Self::Saw => 2.0 * phase - 1.0,
+            Self::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Self::Triangle => 4.0 * ((phase - 0.25).rem_euclid(1.0) - 0.5).abs() - 1.0,
+        }
+    }
+}
+
+/// A free-running oscillator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Oscillator {
+    /// Waveform shape.
+    pub waveform: Waveform,
+    /// Frequency in Hz.
+    pub freq: f64,
+    /// Peak amplitude, as a fraction of full scale.
+    pub amp: f64,
+    phase: f64,
+}
+
+impl Oscillator {
+    /// An oscillator starting at phase zero.
+    #[must_use]
+    pub fn new(waveform: Waveform, freq: f64, amp: f64) -> Self {
+        Self { waveform, freq, amp, phase: 0.0 }
+    }
+
+    /// The current sample, then advance by one sample period.
+    pub fn next_sample(&mut self, sample_rate: u32) -> f64 {
+        let value = self.amp * self.waveform.at(self.phase);
+        self.phase = (self.phase + self.freq / f64::from(sample_rate)).rem_euclid(1.0);
+        value
+    }
+}
+
+/// An ADSR envelope for a single note of fixed length.
+///
+/// The gate opens at time zero and closes after `gate` seconds; the
+/// release then runs from whatever level the envelope had reached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Envelope {
+    /// Attack time in seconds.
+    pub attack: f64,
+    /// Decay time in seconds.
+    pub decay: f64,
+    /// Sustain level in `[0, 1]`.
+    pub sustain: f64,
+    /// Release time in seconds.
+    pub release: f64,
+    /// How long the gate is held, in seconds.
+    pub gate: f64,
+}
+
+impl Envelope {
+    /// An envelope; the sustain level is clamped to `[0, 1]`.
+    #[must_use]
+    pub fn new(attack: f64, decay: f64, sustain: f64, release: f64, gate: f64) -> Self {
+        Self { attack, decay, sustain: sustain.clamp(0.0, 1.0), release, gate }
+    }
+
+    /// Level in `[0, 1]` at `t` seconds after the note starts.
+    #[must_use]
+    pub fn level_at(&self, t: f64) -> f64 {
+        if t < 0.0 {
+            return 0.0;
+        }
+        if t >= self.gate {
+            let from = self.held_level(self.gate);
+            let into = t - self.gate;
+            return if into >= self.release { 0.0 } else { from * (1.0 - into / self.release) };
+        }
+        self.held_level(t)
+    }
+
+    /// Level while the gate is open.
+    fn held_level(&self, t: f64) -> f64 {
+        if t < self.attack {
+            t / self.attack
+        } else if t < self.attack + self.decay {
+            1.0 - (1.0 - self.sustain) * (t - self.attack) / self.decay
+        } else {
+            self.sustain
+        }
+    }
+
+    /// Time after which the envelope stays silent.
+    #[must_use]
+    pub fn length(&self) -> f64 {
+        self.gate + self.release
+    }
+}
+
+/// An oscillator, optionally shaped by an envelope.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Voice {
+    /// Signal source.
+    pub osc: Oscillator,
+    /// Amplitude envelope; `None` holds the oscillator at full level.
+    pub envelope: Option<Envelope>,
+}
+
+/// A bank of voices mixed into one signal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Patch {
+    sample_rate: u32,
+    voices: Vec<Voice>,
+    /// Samples generated so far, which is the envelopes' clock.
+    position: u64,
+}
+
+impl Default for Patch {
+    fn default() -> Self {
+        Self::new(DEFAULT_SAMPLE_RATE)
+    }
+}
+
+impl Patch {
+    /// An empty patch at `sample_rate` Hz.
+    #[must_use]
+    pub fn new(sample_rate: u32) -> Self {
+        Self { sample_rate, voices: Vec::new(), position: 0 }
+    }
+
+    /// Sample rate in Hz.
+    #[must_use]
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Add a voice and return its index.
+    pub fn add(&mut self, osc: Oscillator) -> usize {
+        self.voices.push(Voice { osc, envelope: None });
+        self.voices.len() - 1
+    }
+
+    /// Attach an envelope to voice `index`; `false` if there is no such voice.
+    pub fn set_envelope(&mut self, index: usize, envelope: Envelope) -> bool {
+        match self.voices.get_mut(index) {
+            Some(voice) => {
+                voice.envelope = Some(envelope);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The voices, in the order they were added.
+    #[must_use]
+    pub fn voices(&self) -> &[Voice] {
+        &self.voices
+    }
+
+    /// Remove every voice and rewind the clock.
+    pub fn clear(&mut self) {
+        self.voices.clear();
+        self.position = 0;
+    }
+
+    /// Restart every oscillator and envelope from time zero.
+    pub fn rewind(&mut self) {
+        for voice in &mut self.voices {
+            voice.osc.phase = 0.0;
+        }
+        self.position = 0;
+    }
+
+    /// The next mixed sample, saturated to `[-1, 1]`.
+    pub fn next_sample(&mut self) -> f64 {
+        let t = self.position as f64 / f64::from(self.sample_rate);
+        let rate = self.sample_rate;
+        let mix: f64 = self
+            .voices
+            .iter_mut()
+            .map(|voice| {
+                let level = voice.envelope.map_or(1.0, |env| env.level_at(t));
+                level * voice.osc.next_sample(rate)
+            })
+            .sum();
+        self.position += 1;
+        mix.clamp(-1.0, 1.0)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// RING BUFFER STREAM
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A fixed-capacity FIFO of samples.
+#[derive(Debug, Clone)]
+pub struct RingBuffer {
+    data: Vec<f32>,
+    head: usize,
+    len: usize,
+}
+
+impl RingBuffer {
+    /// An empty buffer holding up to `capacity` samples (at least one).
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self { data: vec![0.0; capacity.max(1)], head: 0, len: 0 }
+    }
+
+    /// Maximum number of samples held.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Number of samples waiting to be read.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there is nothing to read.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append a sample; `false` (and nothing stored) when full.
+    pub fn push(&mut self, sample: f32) -> bool {
+        if self.len == self.data.len() {
+            return false;
+        }
+        let tail = (self.head + self.len) % self.data.len();
+        self.data[tail] = sample;
+        self.len += 1;
+        true
+    }
+
+    /// Take the oldest sample.
+    pub fn pop(&mut self) -> Option<f32> {
+        if self.len == 0 {
+            return None;
+        }
+        let sample = self.data[self.head];
+        self.head = (self.head + 1) % self.data.len();
+        self.len -= 1;
+        Some(sample)
+    }
+}
+
+/// A patch read through a ring buffer, refilled one block at a time.
+#[derive(Debug, Clone)]
+pub struct Stream {
+    patch: Patch,
+    buffer: RingBuffer,
+}
+
+impl Stream {
+    /// Stream `patch` through a buffer of `block` samples.
+    #[must_use]
+    pub fn new(patch: Patch, block: usize) -> Self {
+        Self { patch, buffer: RingBuffer::new(block) }
+    }
+
+    /// The patch being played.
+    #[must_use]
+    pub fn patch(&self) -> &Patch {
+        &self.patch
+    }
+
+    /// Top the buffer up from the patch.
+    fn refill(&mut self) {
+        while self.buffer.len() < self.buffer.capacity() {
+            let sample = self.patch.next_sample() as f32;
+            self.buffer.push(sample);
+        }
+    }
+
+    /// Fill `out` with the next samples.
+    pub fn read(&mut self, out: &mut [f32]) {
+        for slot in out {
+            if self.buffer.is_empty() {
+                self.refill();
+            }
+            *slot = self.buffer.pop().unwrap_or(0.0);
+        }
+    }
+
+    /// The next `seconds` of signal.
+    #[must_use]
+    pub fn render(&mut self, seconds: f64) -> Vec<f32> {
+        let count = (seconds.max(0.0) * f64::from(self.patch.sample_rate)).round() as usize;
+        let mut samples = vec![0.0; count];
+        self.read(&mut samples);
+        samples
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// WAV OUTPUT
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Write `samples` in `[-1, 1]` as a 16-bit PCM mono WAV file.
+///
+/// # Errors
+///
+/// Returns any I/O error from creating or writing the file.
+pub fn write_wav(path: &Path, samples: &[f32], sample_rate: u32) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    encode_wav(&mut out, samples, sample_rate)?;
+    out.flush()
+}
+
+/// Write the RIFF/WAVE header and 16-bit samples to `out`.
+fn encode_wav(out: &mut impl Write, samples: &[f32], sample_rate: u32) -> io::Result<()> {
+    let data_len = u32::try_from(samples.len() * 2)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many samples for a WAV file"))?;
+    out.write_all(b"RIFF")?;
+    out.write_all(&(36 + data_len).to_le_bytes())?;
+    out.write_all(b"WAVEfmt ")?;
+    out.write_all(&16_u32.to_le_bytes())?;
+    out.write_all(&1_u16.to_le_bytes())?; // PCM
+    out.write_all(&1_u16.to_le_bytes())?; // mono
+    out.write_all(&sample_rate.to_le_bytes())?;
+    out.write_all(&(sample_rate * 2).to_le_bytes())?; // byte rate
+    out.write_all(&2_u16.to_le_bytes())?; // block align
+    out.write_all(&16_u16.to_le_bytes())?; // bits per sample
+    out.write_all(b"data")?;
+    out.write_all(&data_len.to_le_bytes())?;
+    for &sample in samples {
+        let pcm = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)).round() as i16;
+        out.write_all(&pcm.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waveforms_and_oscillators() {
+        assert!((Waveform::Sine.at(0.25) - 1.0).abs() < 1e-12);
+        assert_eq!(Waveform::Saw.at(0.75), 0.5);
+        assert_eq!(Waveform::Square.at(0.6), -1.0);
+        assert_eq!(Waveform::Triangle.at(0.5), 0.0);
+        assert_eq!(Waveform::parse("tri"), Some(Waveform::Triangle));
+        assert_eq!(Waveform::parse("noise"), None);
+
+        // 1 kHz at 4 kHz visits the four quarter points.
+        let mut osc = Oscillator::new(Waveform::Sine, 1000.0, 0.5);
+        let samples: Vec<f64> = (0..4).map(|_| osc.next_sample(4000)).collect();
+        let expected = [0.0, 0.5, 0.0, -0.5];
+        assert!(samples.iter().zip(expected).all(|(s, e)| (s - e).abs() < 1e-12), "{samples:?}");
+    }
+
+    #[test]
+    fn envelope_releases_from_where_the_gate_closed() {
+        let env = Envelope::new(0.1, 0.2, 0.5, 0.4, 1.0);
+        assert_eq!(env.level_at(0.05), 0.5);
+        assert!((env.level_at(0.2) - 0.75).abs() < 1e-12);
+        assert_eq!(env.level_at(0.9), 0.5);
+        assert!((env.level_at(1.2) - 0.25).abs() < 1e-12);
+        assert_eq!(env.level_at(1.5), 0.0);
+        assert_eq!(env.length(), 1.4);
+
+        // Released mid-attack: ramps down from 0.5, not from the sustain.
+        let short = Envelope::new(0.1, 0.2, 0.8, 0.1, 0.05);
+        assert!((short.level_at(0.1) - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn ring_buffer_wraps() {
+        let mut ring = RingBuffer::new(3);
+        assert!(ring.push(1.0) && ring.push(2.0) && ring.push(3.0));
+        assert!(!ring.push(4.0));
+        assert_eq!(ring.pop(), Some(1.0));
+        assert!(ring.push(4.0));
+        assert_eq!((ring.pop(), ring.pop(), ring.pop(), ring.pop()), (Some(2.0), Some(3.0), Some(4.0), None));
+    }
+
+    #[test]
+    fn stream_matches_the_patch_and_saturates() {
+        let mut patch = Patch::new(8000);
+        patch.add(Oscillator::new(Waveform::Saw, 100.0, 0.8));
+        patch.add(Oscillator::new(Waveform::Square, 50.0, 0.8));
+        let mut direct = patch.clone();
+        let expected: Vec<f32> = (0..800).map(|_| direct.next_sample() as f32).collect();
+
+        // A small block forces many refills across the read.
+        let mut stream = Stream::new(patch, 7);
+        let mut got = stream.render(0.1);
+        assert_eq!(got, expected);
+        assert!(got.iter().all(|s| s.abs() <= 1.0));
+        assert!(got.contains(&1.0));
+
+        let next: Vec<f32> = (0..5).map(|_| direct.next_sample() as f32).collect();
+        stream.read(&mut got[..5]);
+        assert_eq!(got[..5], next);
+    }
+
+    #[test]
+    fn wav_header_and_samples() {
+        let mut bytes = Vec::new();
+        encode_wav(&mut bytes, &[0.0, 1.0, -1.0], 8000).unwrap();
+        assert_eq!(bytes.len(), 44 + 6);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()), 42);
+        assert_eq!(&bytes[8..16], b"WAVEfmt ");
+        assert_eq!(u32::from_le_bytes(bytes[24..28].try_into().unwrap()), 8000);
+        assert_eq!(&bytes[36..40], b"data");
+        assert_eq!(bytes[44..], [0, 0, 0xff, 0x7f, 0x01, 0x80]);
+    }
+}
@@ -1,9 +1,13 @@
-//! Criterion benchmarks for Woflang prime checking.
+//! Criterion benchmarks for Woflang prime checking and interpreter startup.
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use woflang_core::InterpreterContext;
 use woflang_runtime::Interpreter;
 
+#[path = "../src/image.rs"]
+#[allow(dead_code)]
+mod image;
+
 fn create_interp() -> Interpreter {
     let mut interp = Interpreter::new();
     woflang_ops::register_all(&mut interp);
@@ -63,6 +67,33 @@ fn bench_arithmetic(c: &mut Criterion) {
         });
     });
 
+    // The same loops interpreted, through `jit` (native code when built
+    // with `--features jit`, interpreted otherwise) and compiled once to
+    // bytecode.
+    for (name, body) in [("add_loop", "10000 ⨯ ⺆ 1 + ⺘"), ("mul_loop", "10000 ⨯ ⺆ 3 * 7 - ⺘")] {
+        for (mode, line) in [("interpreted", body.to_string()), ("jit", format!("jit ⺆ {body} ⺘"))] {
+            group.bench_function(BenchmarkId::new(name, mode), |b| {
+                let mut interp = create_interp();
+                b.iter(|| {
+                    interp.clear();
+                    interp.exec_line("1").unwrap();
+                    interp.exec_line(black_box(&line)).unwrap();
+                    black_box(interp.stack().peek().unwrap().as_integer().unwrap())
+                });
+            });
+        }
+        group.bench_function(BenchmarkId::new(name, "bytecode"), |b| {
+            let mut interp = create_interp();
+            let program = interp.compile_line(body).unwrap();
+            b.iter(|| {
+                interp.clear();
+                interp.exec_line("1").unwrap();
+                interp.exec_program(black_box(&program)).unwrap();
+                black_box(interp.stack().peek().unwrap().as_integer().unwrap())
+            });
+        });
+    }
+
     group.finish();
 }
 
@@ -86,5 +117,41 @@ fn bench_stack_ops(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_prime_check, bench_arithmetic, bench_stack_ops);
+// Building the interpreter the way the CLI does, one stage at a time.
+fn bench_startup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("startup");
+
+    group.bench_function("ops", |b| b.iter(|| black_box(create_interp())));
+    group.bench_function("ops_plugins", |b| {
+        b.iter(|| {
+            let mut interp = create_interp();
+            woflang_plugins::register_all(&mut interp);
+            black_box(interp)
+        });
+    });
+    group.bench_function("ops_plugins_stdlib", |b| {
+        b.iter(|| {
+            let mut interp = create_interp();
+            woflang_plugins::register_all(&mut interp);
+            woflang_ops::stdlib::load(&mut interp).unwrap();
+            black_box(interp)
+        });
+    });
+
+    // The same, cold (building the image) and warm (loading a saved one).
+    let started = |path: Option<&std::path::Path>| {
+        let image::Started { mut interp, image, .. } = image::start(path).unwrap();
+        image.define_stdlib(&mut interp).unwrap();
+        interp
+    };
+    let path = std::env::temp_dir().join(format!("woflang-bench-image-{}.json", std::process::id()));
+    group.bench_function("image_cold", |b| b.iter(|| black_box(started(None))));
+    assert!(image::start(Some(&path)).and_then(|_| image::start(Some(&path))).unwrap().warm);
+    group.bench_function("image_warm", |b| b.iter(|| black_box(started(Some(&path)))));
+    let _ = std::fs::remove_file(&path);
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_prime_check, bench_arithmetic, bench_stack_ops, bench_startup);
 criterion_main!(benches);
@@ -0,0 +1,608 @@
+//! Data-driven benchmark suite behind `--benchmark`.
+//!
+//! A suite is a list of [`Workload`]s generated from a category and a
+//! [`BenchConfig`]; running it yields one [`BenchResult`] per workload,
+//! which is rendered as a console table, CSV or JSON:
+//!
+//! ```text
+//! woflang --benchmark primes --digits 15 --count 50
+//! woflang --benchmark all --format csv > bench-10.1.1.csv
+//! ```
+//!
+//! Every workload also runs a second time with the interpreter's phase
+//! profile enabled, splitting its time into tokenization, op lookup,
+//! literal conversion, handler execution and everything else (control
+//! flow, functions, variables). The split is always exported; the console
+//! shows it with `--breakdown`.
+//!
+//! Generated inputs come from a fixed seed, so the same options produce
+//! the same workloads in every version and results can be diffed.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use woflang_core::{InterpreterContext, WofValue};
+use woflang_runtime::{Interpreter, Phase, PhaseProfile};
+
+/// A family of workloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Category {
+    /// Miller-Rabin primality checks.
+    Primes,
+    /// Fibonacci numbers.
+    Fib,
+    /// Sorting the whole stack.
+    Sort,
+    /// Integer matrix products expanded into stack arithmetic.
+    Matrix,
+    /// Breadth-first reachability on path graphs.
+    Graph,
+    /// Chains of Hadamard gates on a qubit.
+    Quantum,
+    /// Every category above.
+    All,
+}
+
+impl Category {
+    const EACH: [Self; 6] =
+        [Self::Primes, Self::Fib, Self::Sort, Self::Matrix, Self::Graph, Self::Quantum];
+
+    /// Lower-case name, as used on the command line and in exports.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Primes => "primes",
+            Self::Fib => "fib",
+            Self::Sort => "sort",
+            Self::Matrix => "matrix",
+            Self::Graph => "graph",
+            Self::Quantum => "quantum",
+            Self::All => "all",
+        }
+    }
+
+    const fn title(self) -> &'static str {
+        match self {
+            Self::Primes => "🔢 WofLang Prime Benchmarking Suite",
+            Self::Fib => "🌀 WofLang Fibonacci Benchmark",
+            Self::Sort => "📶 WofLang Sort Benchmark",
+            Self::Matrix => "🧮 WofLang Matrix Benchmark",
+            Self::Graph => "🕸️ WofLang Graph Benchmark",
+            Self::Quantum => "⚛️ WofLang Quantum Benchmark",
+            Self::All => "🐺 WofLang Benchmark Suite",
+        }
+    }
+}
+
+/// Output format for results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable tables.
+    #[default]
+    Console,
+    /// One row per workload, with a header.
+    Csv,
+    /// A JSON document with the version and all results.
+    Json,
+}
+
+/// Knobs for generated workloads.
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    /// Digits per generated prime candidate; `None` runs the classic
+    /// fixed prime table.
+    pub digits: Option<u32>,
+    /// Workloads per category.
+    pub count: usize,
+    /// Seed for generated inputs.
+    pub seed: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self { digits: None, count: 10, seed: 0x5eed_f00d }
+    }
+}
+
+/// One script to time.
+#[derive(Debug, Clone)]
+pub struct Workload {
+    pub category: Category,
+    pub name: String,
+    /// Short description of the input, e.g. the number tested.
+    pub input: String,
+    pub script: String,
+    /// Expected top of stack, when it can be computed independently.
+    pub expected: Option<WofValue>,
+}
+
+/// Where a workload's time went, in microseconds, from a profiled run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Breakdown {
+    pub tokenize: f64,
+    pub lookup: f64,
+    pub convert: f64,
+    pub handler: f64,
+    /// Wall time not attributed to any phase.
+    pub other: f64,
+    /// Op handlers that ran.
+    pub ops: u64,
+}
+
+impl Breakdown {
+    fn new(profile: &PhaseProfile, wall: Duration) -> Self {
+        let us = |d: Duration| d.as_secs_f64() * 1_000_000.0;
+        Self {
+            tokenize: us(profile.get(Phase::Tokenize)),
+            lookup: us(profile.get(Phase::Lookup)),
+            convert: us(profile.get(Phase::Convert)),
+            handler: us(profile.get(Phase::Handler)),
+            other: us(wall.saturating_sub(profile.attributed())),
+            ops: profile.ops(),
+        }
+    }
+
+    const COLUMNS: [&'static str; 6] = ["tokenize_us", "lookup_us", "convert_us", "handler_us", "other_us", "ops"];
+
+    fn values(&self) -> [String; 6] {
+        [
+            format!("{:.3}", self.tokenize),
+            format!("{:.3}", self.lookup),
+            format!("{:.3}", self.convert),
+            format!("{:.3}", self.handler),
+            format!("{:.3}", self.other),
+            self.ops.to_string(),
+        ]
+    }
+}
+
+/// The outcome of running one workload.
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub workload: Workload,
+    pub actual: Result<Option<WofValue>, String>,
+    /// Unprofiled wall time.
+    pub micros: f64,
+    pub breakdown: Breakdown,
+}
+
+impl BenchResult {
+    /// `Some(true)` if the result matched the expectation, `None` if
+    /// there was nothing to check against.
+    pub fn ok(&self) -> Option<bool> {
+        match &self.actual {
+            Err(_) => Some(false),
+            Ok(actual) => self.workload.expected.as_ref().map(|e| actual.as_ref() == Some(e)),
+        }
+    }
+}
+
+/// Small deterministic generator so workloads are stable across runs.
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+/// Build the workloads for one category.
+pub fn workloads(category: Category, config: &BenchConfig) -> Result<Vec<Workload>, String> {
+    let mut rng = SplitMix(config.seed ^ category as u64);
+    let count = config.count;
+    let make = |name: String, input: String, script: String, expected: Option<WofValue>| {
+        Workload { category, name, input, script, expected }
+    };
+
+    Ok(match category {
+        Category::All => {
+            let mut all = Vec::new();
+            for each in Category::EACH {
+                all.extend(workloads(each, config)?);
+            }
+            all
+        }
+        Category::Primes => match config.digits {
+            None => CLASSIC_PRIMES
+                .iter()
+                .map(|&(name, n, prime)| {
+                    make(name.into(), n.to_string(), format!("{n} prime_check"), Some(WofValue::boolean(prime)))
+                })
+                .collect(),
+            Some(digits @ 1..=18) => {
+                let low = 10_u64.pow(digits - 1);
+                let span = 10_u64.pow(digits) - low;
+                (0..count)
+                    .map(|i| {
+                        let n = (low + rng.below(span)) | 1;
+                        let expected = (digits <= TRIAL_DIVISION_DIGITS).then(|| WofValue::boolean(is_prime(n)));
+                        make(format!("{digits}-digit #{}", i + 1), n.to_string(), format!("{n} prime_check"), expected)
+                    })
+                    .collect()
+            }
+            Some(digits) => return Err(format!("--digits must be between 1 and 18, got {digits}")),
+        },
+        Category::Fib => (0..count)
+            .map(|i| {
+                let n = 1 + (i * 91 / count.max(1)) as u64;
+                make(format!("fib({n})"), n.to_string(), format!("{n} fib"), Some(WofValue::integer(fib(n))))
+            })
+            .collect(),
+        Category::Sort => (0..count)
+            .map(|i| {
+                let len = 100 * (i + 1);
+                let values: Vec<i64> = (0..len).map(|_| rng.below(1_000_000) as i64).collect();
+                let mut script = join(&values);
+                script.push_str(" sort_asc");
+                let max = values.iter().copied().max().map(WofValue::integer);
+                make(format!("sort {len}"), format!("{len} ints"), script, max)
+            })
+            .collect(),
+        Category::Matrix => (0..count)
+            .map(|i| {
+                let size = 2 + i;
+                let a: Vec<i64> = (0..size * size).map(|_| rng.below(10) as i64).collect();
+                let b: Vec<i64> = (0..size * size).map(|_| rng.below(10) as i64).collect();
+                let (script, total) = matrix_product_sum(size, &a, &b);
+                make(format!("matmul {size}x{size}"), format!("{size}x{size}"), script, Some(WofValue::integer(total)))
+            })
+            .collect(),
+        Category::Graph => (0..count)
+            .map(|i| {
+                let nodes = 50 * (i + 1);
+                let mut script = format!(r#"{nodes} "bench" graph_new"#);
+                for v in 1..nodes {
+                    let _ = write!(script, r#" {} {v} "bench" graph_add_edge"#, v - 1);
+                }
+                script.push_str(r#" 0 "bench" graph_bfs_reach"#);
+                let expected = WofValue::integer(nodes as i64);
+                make(format!("bfs {nodes}"), format!("{nodes} nodes"), script, Some(expected))
+            })
+            .collect(),
+        Category::Quantum => (0..count)
+            .map(|i| {
+                // An even number of Hadamards is the identity, so |0⟩ stays |0⟩.
+                let gates = 20 * (i + 1);
+                let script = format!("|0⟩{} measure", " H".repeat(gates));
+                make(format!("H^{gates}"), format!("{gates} gates"), script, Some(WofValue::integer(0)))
+            })
+            .collect(),
+    })
+}
+
+/// Numbers up to this many digits are verified by trial division.
+const TRIAL_DIVISION_DIGITS: u32 = 12;
+
+const CLASSIC_PRIMES: &[(&str, u64, bool)] = &[
+    ("Small Prime 1", 97, true),
+    ("Small Prime 2", 997, true),
+    ("Small Prime 3", 9973, true),
+    ("Medium Prime 1", 982_451_653, true),
+    ("Medium Prime 2", 2_147_483_647, true),
+    ("Large Prime 1", 1_000_000_007, true),
+    ("Large Prime 2", 1_000_000_009, true),
+    ("Large Prime 3", 10_000_000_019, true),
+    ("Composite 1", 1_000_000_000, false),
+    ("Composite 2", 999_999_999_999, false),
+    ("Composite 3", 1_000_000_000_001, false),
+    ("13-digit Prime", 1_000_000_000_039, true),
+    ("12-digit Prime", 100_000_000_003, true),
+    ("Carmichael 1", 561, false),
+    ("Carmichael 2", 1105, false),
+    ("Carmichael 3", 1729, false),
+    ("Pseudoprime", 2047, false),
+];
+
+fn is_prime(n: u64) -> bool {
+    if n < 4 {
+        return n >= 2;
+    }
+    if n % 2 == 0 {
+        return false;
+    }
+    (3..).step_by(2).take_while(|d| d * d <= n).all(|d| n % d != 0)
+}
+
+fn fib(n: u64) -> i64 {
+    let (mut a, mut b) = (0_i64, 1_i64);
+    for _ in 0..n {
+        (a, b) = (b, a + b);
+    }
+    a
+}
+
+fn join(values: &[i64]) -> String {
+    values.iter().map(ToString::to_string).collect::<Vec<_>>().join(" ")
+}
+
+/// Script computing the sum of all entries of `a × b` with stack
+/// arithmetic, together with the expected sum.
+fn matrix_product_sum(size: usize, a: &[i64], b: &[i64]) -> (String, i64) {
+    let mut script = String::from("0");
+    let mut total = 0;
+    for a_row in a.chunks_exact(size) {
+        for col in 0..size {
+            for (&x, &y) in a_row.iter().zip(b.iter().skip(col).step_by(size)) {
+                let _ = write!(script, " {x} {y} * +");
+                total += x * y;
+            }
+        }
+    }
+    (script, total)
+}
+
+/// An interpreter with every op the workloads use.
+///
+/// The quantum plugin's gates are probabilistic and print as they run,
+/// so the amplitude simulator from `woflang-ops` is registered last and
+/// takes precedence.
+pub fn interpreter() -> Interpreter {
+    let mut interp = Interpreter::new();
+    woflang_ops::register_all(&mut interp);
+    woflang_plugins::register_all(&mut interp);
+    woflang_ops::quantum::register(&mut interp);
+    interp
+}
+
+/// Time every workload on `interp`: once plain for the reported time,
+/// then once profiled for the breakdown, so profiling overhead never
+/// leaks into the headline numbers.
+pub fn run(interp: &mut Interpreter, workloads: Vec<Workload>) -> Vec<BenchResult> {
+    workloads
+        .into_iter()
+        .map(|workload| {
+            interp.clear();
+            let start = Instant::now();
+            let result = interp.exec_line(&workload.script);
+            let micros = start.elapsed().as_secs_f64() * 1_000_000.0;
+            let actual = result.map(|()| interp.stack().peek().ok().cloned()).map_err(|e| e.to_string());
+
+            interp.clear();
+            interp.enable_phase_profile();
+            let start = Instant::now();
+            // Failures were already reported by the plain run.
+            let _ = interp.exec_line(&workload.script);
+            let wall = start.elapsed();
+            let breakdown = interp
+                .take_phase_profile()
+                .map(|profile| Breakdown::new(&profile, wall))
+                .unwrap_or_default();
+
+            BenchResult { workload, actual, micros, breakdown }
+        })
+        .collect()
+}
+
+/// Write `results` in the chosen format. `breakdown` adds the per-phase
+/// table to console output; CSV and JSON always include it.
+pub fn render(results: &[BenchResult], format: Format, breakdown: bool, out: &mut impl Write) -> io::Result<()> {
+    match format {
+        Format::Console => render_console(results, breakdown, out),
+        Format::Csv => render_csv(results, out),
+        Format::Json => render_json(results, out),
+    }
+}
+
+/// Show a value the way the console table expects: primality results
+/// read PRIME/COMPOSITE.
+fn describe(category: Category, value: Option<&WofValue>) -> String {
+    match (category, value) {
+        (_, None) => "?".into(),
+        (Category::Primes, Some(v)) if v.is_truthy() => "PRIME".into(),
+        (Category::Primes, Some(_)) => "COMPOSITE".into(),
+        (_, Some(v)) => v.to_string(),
+    }
+}
+
+fn render_console(results: &[BenchResult], breakdown: bool, out: &mut impl Write) -> io::Result<()> {
+    let mut rest = results;
+    while let Some(first) = rest.first() {
+        let category = first.workload.category;
+        let len = rest.iter().take_while(|r| r.workload.category == category).count();
+        let (group, tail) = rest.split_at(len);
+        rest = tail;
+
+        let title = category.title();
+        writeln!(out, "{title}")?;
+        writeln!(out, "{}\n", "=".repeat(title.chars().count()))?;
+        writeln!(
+            out,
+            "{:<20} {:<18} {:<12} {:<12} {:<12} {:<5}",
+            "Test Name", "Input", "Expected", "Result", "Time (µs)", "OK"
+        )?;
+        writeln!(out, "{}", "-".repeat(80))?;
+
+        let mut total_time = 0.0;
+        let mut correct = 0;
+        let mut checked = 0;
+        for result in group {
+            let w = &result.workload;
+            let expected = describe(category, w.expected.as_ref());
+            let (actual, mark) = match (&result.actual, result.ok()) {
+                (Err(_), _) => ("ERROR".to_string(), "✗"),
+                (Ok(v), ok) => (describe(category, v.as_ref()), match ok {
+                    Some(true) => "✓",
+                    Some(false) => "✗",
+                    None => "-",
+                }),
+            };
+            writeln!(
+                out,
+                "{:<20} {:<18} {:<12} {:<12} {:<12.2} {mark}",
+                w.name, w.input, expected, actual, result.micros
+            )?;
+            if let Err(e) = &result.actual {
+                writeln!(out, "    Error: {e}")?;
+            }
+            total_time += result.micros;
+            if let Some(ok) = result.ok() {
+                checked += 1;
+                correct += usize::from(ok);
+            }
+        }
+
+        writeln!(out, "{}", "-".repeat(80))?;
+        writeln!(out, "Total time: {total_time:.2} µs")?;
+        writeln!(out, "Average time: {:.2} µs", total_time / group.len() as f64)?;
+        writeln!(out, "Correct results: {correct}/{checked}")?;
+        if checked > 0 {
+            writeln!(out, "Success rate: {:.1}%", 100.0 * correct as f64 / checked as f64)?;
+        }
+        writeln!(out)?;
+        if breakdown {
+            render_breakdown(group, out)?;
+        }
+    }
+    writeln!(out, "🐺 Benchmark complete! 🐺\n")
+}
+
+fn render_breakdown(group: &[BenchResult], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "Dispatch breakdown (µs, profiled run):")?;
+    writeln!(
+        out,
+        "{:<20} {:>10} {:>10} {:>10} {:>10} {:>10} {:>7}",
+        "Test Name", "tokenize", "lookup", "convert", "handler", "other", "ops"
+    )?;
+    writeln!(out, "{}", "-".repeat(83))?;
+    let mut sum = Breakdown::default();
+    for result in group {
+        let b = &result.breakdown;
+        writeln!(
+            out,
+            "{:<20} {:>10.2} {:>10.2} {:>10.2} {:>10.2} {:>10.2} {:>7}",
+            result.workload.name, b.tokenize, b.lookup, b.convert, b.handler, b.other, b.ops
+        )?;
+        sum.tokenize += b.tokenize;
+        sum.lookup += b.lookup;
+        sum.convert += b.convert;
+        sum.handler += b.handler;
+        sum.other += b.other;
+        sum.ops += b.ops;
+    }
+    writeln!(out, "{}", "-".repeat(83))?;
+    writeln!(
+        out,
+        "{:<20} {:>10.2} {:>10.2} {:>10.2} {:>10.2} {:>10.2} {:>7}\n",
+        "Total", sum.tokenize, sum.lookup, sum.convert, sum.handler, sum.other, sum.ops
+    )
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn render_csv(results: &[BenchResult], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "version,category,name,input,expected,actual,ok,micros,{}", Breakdown::COLUMNS.join(","))?;
+    for result in results {
+        let w = &result.workload;
+        let actual = match &result.actual {
+            Ok(v) => v.as_ref().map(ToString::to_string).unwrap_or_default(),
+            Err(e) => format!("error: {e}"),
+        };
+        let ok = result.ok().map(|ok| ok.to_string()).unwrap_or_default();
+        let fields = [
+            env!("CARGO_PKG_VERSION").to_string(),
+            w.category.name().to_string(),
+            w.name.clone(),
+            w.input.clone(),
+            w.expected.as_ref().map(ToString::to_string).unwrap_or_default(),
+            actual,
+            ok,
+            format!("{:.3}", result.micros),
+        ];
+        let mut row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        row.extend(result.breakdown.values());
+        writeln!(out, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+fn render_json(results: &[BenchResult], out: &mut impl Write) -> io::Result<()> {
+    let rows: Vec<serde_json::Value> = results
+        .iter()
+        .map(|result| {
+            let w = &result.workload;
+            let (actual, error) = match &result.actual {
+                Ok(v) => (v.as_ref().map(ToString::to_string), None),
+                Err(e) => (None, Some(e.clone())),
+            };
+            serde_json::json!({
+                "category": w.category.name(),
+                "name": w.name,
+                "input": w.input,
+                "expected": w.expected.as_ref().map(ToString::to_string),
+                "actual": actual,
+                "error": error,
+                "ok": result.ok(),
+                "micros": result.micros,
+                "breakdown": {
+                    "tokenize_us": result.breakdown.tokenize,
+                    "lookup_us": result.breakdown.lookup,
+                    "convert_us": result.breakdown.convert,
+                    "handler_us": result.breakdown.handler,
+                    "other_us": result.breakdown.other,
+                    "ops": result.breakdown.ops,
+                },
+            })
+        })
+        .collect();
+    let doc = serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "results": rows,
+    });
+    serde_json::to_writer_pretty(&mut *out, &doc)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_workloads_are_deterministic_and_correct() {
+        let config = BenchConfig { digits: Some(6), count: 3, ..BenchConfig::default() };
+        let first = workloads(Category::All, &config).unwrap();
+        let again = workloads(Category::All, &config).unwrap();
+        let scripts = |ws: &[Workload]| ws.iter().map(|w| w.script.clone()).collect::<Vec<_>>();
+        assert_eq!(scripts(&first), scripts(&again));
+        assert_eq!(first.len(), 3 * Category::EACH.len());
+
+        let results = run(&mut interpreter(), first);
+        for result in &results {
+            assert_eq!(result.ok(), Some(true), "{result:?}");
+        }
+        assert!(workloads(Category::Primes, &BenchConfig { digits: Some(19), ..config }).is_err());
+    }
+
+    #[test]
+    fn renders_csv_and_json() {
+        let config = BenchConfig { count: 2, ..BenchConfig::default() };
+        let results = run(&mut interpreter(), workloads(Category::Fib, &config).unwrap());
+
+        let mut csv = Vec::new();
+        render(&results, Format::Csv, false, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.lines().nth(1).unwrap().contains(",fib,fib(1),1,1,1,true,"));
+        assert!(csv.lines().next().unwrap().ends_with(",handler_us,other_us,ops"));
+        assert!(csv.lines().nth(1).unwrap().ends_with(",1"), "one op per fib workload");
+
+        let mut json = Vec::new();
+        render(&results, Format::Json, false, &mut json).unwrap();
+        let doc: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(doc["results"][1]["expected"], "1836311903");
+    }
+}
@@ -0,0 +1,186 @@
+//! Interactive debugger (`--debugger`, and `:break` and friends in the REPL).
+//!
+//! Breakpoints go on op names or source lines. When one is reached, the
+//! token about to run and the stack are shown, and debugger commands are
+//! read until execution goes on:
+//!
+//! ```text
+//! wof> ⊕ sq ⺆ dup * ⺘
+//! wof> :break *
+//! Breakpoint set at op '*'
+//! wof> 3 sq
+//! ⏸  breakpoint at op '*': before '*' at 1:12
+//!    Stack[2]: 3, 3
+//! (debug) :frames
+//!   #0 call sq
+//! (debug) :continue
+//! → 9
+//! ```
+//!
+//! `:step` runs one token and pauses again. A script run with
+//! `--debugger` pauses before its first token.
+
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+
+use woflang_core::InterpreterContext;
+use woflang_runtime::{Breakpoint, DebugAction, DebugFrontend, Interpreter, Pause, PauseReason};
+
+/// Commands accepted while paused.
+const PAUSED_HELP: &str = "\
+  :step, s        Run the next token and pause again
+  :continue, c    Run to the next breakpoint
+  :frames, f      Show calls, loops and variables in scope
+  .s              Show the stack
+  :break [X]      Pause at op or line X (no X: list breakpoints)
+  :unbreak [X]    Remove breakpoint X, or all breakpoints
+  :abort, q       Stop the running line
+";
+
+/// A debugger frontend reading commands from `input` (stdin in the CLI).
+pub struct Terminal<R> {
+    input: R,
+}
+
+impl Terminal<io::BufReader<io::Stdin>> {
+    /// A frontend reading from stdin.
+    pub fn stdin() -> Self {
+        Self { input: io::BufReader::new(io::stdin()) }
+    }
+}
+
+impl<R: BufRead + Send> DebugFrontend for Terminal<R> {
+    fn paused(&mut self, interp: &mut Interpreter, pause: &Pause) -> DebugAction {
+        let reason = match &pause.reason {
+            PauseReason::Breakpoint(b) => format!("breakpoint at {b}"),
+            PauseReason::Step => "step".to_string(),
+        };
+        println!("⏸  {reason}: before '{}' at {}", pause.token, pause.span);
+        println!("   {}", interp.stack());
+        loop {
+            print!("(debug) ");
+            io::stdout().flush().ok();
+            let mut line = String::new();
+            // Without input there is nobody to ask, so run on
+            if self.input.read_line(&mut line).unwrap_or(0) == 0 {
+                println!();
+                return DebugAction::Continue;
+            }
+            match line.trim() {
+                ":step" | "s" | "" => return DebugAction::Step,
+                ":continue" | "c" => return DebugAction::Continue,
+                ":abort" | "q" => return DebugAction::Abort,
+                ".s" => println!("   {}", interp.stack()),
+                ":frames" | "f" => print!("{}", frames(interp)),
+                cmd => match command(interp, cmd) {
+                    Some(out) => print!("{out}"),
+                    None => print!("{PAUSED_HELP}"),
+                },
+            }
+        }
+    }
+}
+
+/// Attach a stdin frontend unless some debugger is attached already.
+pub fn ensure_attached(interp: &mut Interpreter) {
+    if !interp.has_debugger() {
+        interp.attach_debugger(Terminal::stdin());
+    }
+}
+
+/// Run a breakpoint or inspection command typed at the REPL or while
+/// paused, returning its output, or `None` if `line` is not one.
+///
+/// Breakpoints only pause once a frontend is attached (see
+/// [`ensure_attached`]).
+pub fn command(interp: &mut Interpreter, line: &str) -> Option<String> {
+    let (cmd, arg) = line.split_once(' ').map_or((line, ""), |(c, a)| (c, a.trim()));
+    let out = match (cmd, arg) {
+        (":break", "") => {
+            let set = interp.breakpoints();
+            if set.is_empty() {
+                "No breakpoints. Add one with :break <op|line>\n".to_string()
+            } else {
+                set.iter().map(|b| format!("  {b}\n")).collect()
+            }
+        }
+        (":break", arg) => {
+            let breakpoint = Breakpoint::parse(arg);
+            let out = format!("Breakpoint set at {breakpoint}\n");
+            interp.add_breakpoint(breakpoint);
+            out
+        }
+        (":unbreak", "") => {
+            interp.clear_breakpoints();
+            "All breakpoints removed\n".to_string()
+        }
+        (":unbreak", arg) => {
+            let breakpoint = Breakpoint::parse(arg);
+            if interp.remove_breakpoint(&breakpoint) {
+                format!("Breakpoint at {breakpoint} removed\n")
+            } else {
+                format!("No breakpoint at {breakpoint}\n")
+            }
+        }
+        (":frames", "") => frames(interp),
+        _ => return None,
+    };
+    Some(out)
+}
+
+/// The running calls and loops, outermost first, then the variables of
+/// each scope from the innermost out.
+pub fn frames(interp: &Interpreter) -> String {
+    let mut out: String = interp.frames().iter().enumerate().map(|(i, f)| format!("  #{i} {f}\n")).collect();
+    if out.is_empty() {
+        out.push_str("  (top level)\n");
+    }
+    let scopes = interp.scopes();
+    let mut scope = Some(scopes.current());
+    while let Some(s) = scope {
+        if !s.is_empty() {
+            let vars: Vec<String> = s
+                .names()
+                .filter_map(|name| s.get_local(name).map(|v| format!("{name} = {v}")))
+                .collect();
+            let label = if s.parent.is_some() { "local" } else { "global" };
+            let _ = writeln!(out, "  {label}: {}", vars.join(", "));
+        }
+        scope = s.parent.and_then(|id| scopes.get(id));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pauses_read_commands_until_execution_goes_on() {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        let input = ":frames\n:break 9\nbogus\n:step\n.s\nc\n";
+        interp.attach_debugger(Terminal { input: io::Cursor::new(input) });
+
+        assert_eq!(command(&mut interp, ":break"), Some("No breakpoints. Add one with :break <op|line>\n".into()));
+        assert_eq!(command(&mut interp, ":break *").as_deref(), Some("Breakpoint set at op '*'\n"));
+        interp.exec_line("⊕ sq ⺆ dup * ⺘ 3 sq 4 sq").unwrap();
+        // The second * pauses too, but input has run out, so it runs on
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 16);
+        assert_eq!(command(&mut interp, ":break").as_deref(), Some("  op '*'\n  line 9\n"));
+
+        assert_eq!(command(&mut interp, ":unbreak 9").as_deref(), Some("Breakpoint at line 9 removed\n"));
+        assert_eq!(command(&mut interp, ":unbreak 9").as_deref(), Some("No breakpoint at line 9\n"));
+        assert_eq!(command(&mut interp, ":unbreak").as_deref(), Some("All breakpoints removed\n"));
+        assert_eq!(command(&mut interp, ":watch x"), None);
+    }
+
+    #[test]
+    fn frames_show_variables_by_scope() {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        assert_eq!(frames(&interp), "  (top level)\n");
+        interp.exec_line("7 字 x").unwrap();
+        assert_eq!(frames(&interp), "  (top level)\n  global: x = 7\n");
+    }
+}
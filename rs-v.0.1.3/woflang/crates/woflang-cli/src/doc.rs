@@ -0,0 +1,317 @@
+//! Operation reference generated from registry metadata (`woflang doc`,
+//! and `help <op>` in the REPL).
+//!
+//! Every op that carries [`OpMeta`] is grouped with the other names bound
+//! to the same handler and listed under its category with its stack
+//! effect, help text, example and source crate. Registered ops without
+//! metadata are still listed, by name only, so nothing is silently left
+//! out of the reference:
+//!
+//! ```text
+//! woflang doc --out docs/
+//! ```
+//!
+//! writes `docs/ops.md` and `docs/ops.html`. In the REPL, `help sqrt` (or
+//! `:doc sqrt`) prints the same metadata for one op and `help run sqrt`
+//! executes its example in a scratch interpreter. `help` lists the
+//! categories from the same entries, so it cannot drift from the ops
+//! actually registered, and `:doc math` lists one category.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use woflang_core::InterpreterContext;
+use woflang_runtime::{Interpreter, OpMeta};
+
+/// Category heading for registered ops without metadata.
+const UNDOCUMENTED: &str = "undocumented";
+
+/// Line width of the category [`summary`].
+const SUMMARY_WIDTH: usize = 78;
+
+/// One reference entry: a handler and every name bound to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// All names, ASCII names first.
+    pub names: Vec<String>,
+    /// The shared metadata, if any.
+    pub meta: Option<OpMeta>,
+}
+
+/// Collect the reference entries of `interp`, keyed by category.
+pub fn entries(interp: &Interpreter) -> BTreeMap<String, Vec<Entry>> {
+    let registry = interp.registry();
+    let mut seen = BTreeSet::new();
+    let mut by_category: BTreeMap<String, Vec<Entry>> = BTreeMap::new();
+
+    for (name, meta) in registry.described() {
+        let mut names: Vec<String> =
+            registry.aliases_of(name).into_iter().map(str::to_string).collect();
+        names.sort_by(|a, b| (!a.is_ascii(), a).cmp(&(!b.is_ascii(), b)));
+        if !seen.insert(names.clone()) {
+            continue;
+        }
+        by_category
+            .entry(meta.category.clone())
+            .or_default()
+            .push(Entry { names, meta: Some(meta.clone()) });
+    }
+
+    let mut rest: Vec<&str> = registry.names().filter(|n| registry.meta(n).is_none()).collect();
+    rest.sort_unstable();
+    if !rest.is_empty() {
+        by_category.insert(
+            UNDOCUMENTED.to_string(),
+            rest.into_iter().map(|n| Entry { names: vec![n.to_string()], meta: None }).collect(),
+        );
+    }
+
+    for list in by_category.values_mut() {
+        list.sort_by(|a, b| a.names.cmp(&b.names));
+    }
+    by_category
+}
+
+/// Render the reference as Markdown.
+pub fn markdown(entries: &BTreeMap<String, Vec<Entry>>) -> String {
+    let code = |s: &str| format!("`{}`", s.replace('|', "\\|"));
+    let cell = |s: &str| s.replace('|', "\\|");
+
+    let mut out = String::from("# Woflang Operation Reference\n\n");
+    let _ = writeln!(out, "Generated by `woflang doc` from woflang {}.\n", env!("CARGO_PKG_VERSION"));
+    for (category, list) in entries {
+        let _ = writeln!(out, "- [{category}](#{category}) ({})", list.len());
+    }
+
+    for (category, list) in entries {
+        let _ = writeln!(out, "\n## {category}\n");
+        out.push_str("| Names | Stack Effect | Description | Example | Source |\n");
+        out.push_str("|-------|--------------|-------------|---------|--------|\n");
+        for entry in list {
+            let names: Vec<String> = entry.names.iter().map(|n| code(n)).collect();
+            let meta = entry.meta.clone().unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                names.join(" "),
+                cell(&meta.stack_effect),
+                cell(&meta.help),
+                meta.example.as_deref().map(code).unwrap_or_default(),
+                meta.source.as_deref().unwrap_or_default(),
+            );
+        }
+    }
+    out
+}
+
+/// Render the reference as a standalone HTML page.
+pub fn html(entries: &BTreeMap<String, Vec<Entry>>) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Woflang Operation Reference</title>\n<style>\n\
+         body { font-family: sans-serif; max-width: 60em; margin: auto; }\n\
+         table { border-collapse: collapse; width: 100%; }\n\
+         th, td { border: 1px solid #ccc; padding: 0.2em 0.5em; text-align: left; }\n\
+         </style>\n</head>\n<body>\n<h1>Woflang Operation Reference</h1>\n",
+    );
+    let _ = writeln!(
+        out,
+        "<p>Generated by <code>woflang doc</code> from woflang {}.</p>\n<ul>",
+        env!("CARGO_PKG_VERSION")
+    );
+    for (category, list) in entries {
+        let _ = writeln!(out, "<li><a href=\"#{0}\">{0}</a> ({1})</li>", escape(category), list.len());
+    }
+    out.push_str("</ul>\n");
+
+    for (category, list) in entries {
+        let _ = writeln!(out, "<h2 id=\"{0}\">{0}</h2>", escape(category));
+        out.push_str(
+            "<table>\n<tr><th>Names</th><th>Stack Effect</th><th>Description</th>\
+             <th>Example</th><th>Source</th></tr>\n",
+        );
+        for entry in list {
+            let names: Vec<String> =
+                entry.names.iter().map(|n| format!("<code>{}</code>", escape(n))).collect();
+            let meta = entry.meta.clone().unwrap_or_default();
+            let example = meta
+                .example
+                .as_deref()
+                .map(|e| format!("<code>{}</code>", escape(e)))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                names.join(" "),
+                escape(&meta.stack_effect),
+                escape(&meta.help),
+                example,
+                escape(meta.source.as_deref().unwrap_or_default()),
+            );
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Write `ops.md` and `ops.html` for `interp` into `dir`, creating it if
+/// needed, and return the written paths.
+pub fn write(interp: &Interpreter, dir: &Path) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let entries = entries(interp);
+    let md = dir.join("ops.md");
+    let page = dir.join("ops.html");
+    fs::write(&md, markdown(&entries))?;
+    fs::write(&page, html(&entries))?;
+    Ok(vec![md, page])
+}
+
+/// The `help <op>` card for `name`, pointing at `help run` when the op
+/// has an example, or `None` if it is neither a registered op nor a user
+/// function.
+pub fn help_card(interp: &Interpreter, name: &str) -> Option<String> {
+    let mut card = interp.help_card(name)?;
+    if interp.registry().meta(name).is_some_and(|meta| meta.example.is_some()) {
+        let _ = writeln!(card, "  Try it:  help run {name}");
+    }
+    Some(card)
+}
+
+/// One line per category: its op count and as many names as fit.
+pub fn summary(entries: &BTreeMap<String, Vec<Entry>>) -> String {
+    let width = entries.keys().map(String::len).max().unwrap_or(0);
+    let mut out = String::new();
+    for (category, list) in entries {
+        let mut line = format!("  {category:<width$} {:>4}  ", list.len());
+        for (i, entry) in list.iter().enumerate() {
+            let sep = if i == 0 { "" } else { ", " };
+            let name = &entry.names[0];
+            if line.chars().count() + sep.len() + name.chars().count() + 3 > SUMMARY_WIDTH {
+                line.push_str(sep);
+                line.push('…');
+                break;
+            }
+            line.push_str(sep);
+            line.push_str(name);
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// The ops of one category with their stack effects, or `None` if there
+/// is no such category.
+pub fn category(entries: &BTreeMap<String, Vec<Entry>>, name: &str) -> Option<String> {
+    let list = entries.get(name)?;
+    let names: Vec<String> = list.iter().map(|e| e.names.join(" ")).collect();
+    let width = names.iter().map(|n| n.chars().count()).max().unwrap_or(0);
+    let effects = list.iter().filter_map(|e| e.meta.as_ref()).map(|m| m.stack_effect.chars().count());
+    let effect_width = effects.max().unwrap_or(0);
+    let mut out = format!("{name} ({} ops)\n", list.len());
+    for (entry, names) in list.iter().zip(&names) {
+        let pad = width - names.chars().count();
+        match &entry.meta {
+            Some(meta) => {
+                let effect = &meta.stack_effect;
+                let effect_pad = effect_width - effect.chars().count();
+                let _ = writeln!(out, "  {names}{:pad$}  {effect}{:effect_pad$}  {}", "", "", meta.help);
+            }
+            None => {
+                let _ = writeln!(out, "  {names}");
+            }
+        }
+    }
+    Some(out)
+}
+
+/// Run the example for `name` in `scratch`, returning the example and
+/// the stack it leaves behind.
+pub fn run_example(
+    interp: &Interpreter,
+    name: &str,
+    scratch: &mut Interpreter,
+) -> Result<String, String> {
+    let example = interp
+        .registry()
+        .meta(name)
+        .and_then(|meta| meta.example.clone())
+        .ok_or_else(|| format!("{name} has no example"))?;
+    scratch.exec_line(&example).map_err(|e| format!("{example}\n  error: {e}"))?;
+    Ok(format!("{example}\n  {}\n", scratch.stack()))
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interpreter() -> Interpreter {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        interp.register("mystery", |_| Ok(()));
+        interp
+    }
+
+    #[test]
+    fn groups_aliases_and_lists_undocumented_ops() {
+        let entries = entries(&interpreter());
+        let math = &entries["math"];
+        let sqrt = math.iter().find(|e| e.names.contains(&"sqrt".to_string())).unwrap();
+        assert_eq!(sqrt.names, ["sqrt", "√"]);
+        assert_eq!(math.iter().filter(|e| e.names.contains(&"√".to_string())).count(), 1);
+        assert_eq!(entries[UNDOCUMENTED][0].names, ["mystery"]);
+    }
+
+    #[test]
+    fn renders_markdown_and_html() {
+        let entries = entries(&interpreter());
+        let md = markdown(&entries);
+        assert!(md.contains("| `sqrt` `√` | ( a -- b ) | Square root | `16 sqrt` | woflang-ops |"));
+        assert!(md.contains("- [undocumented](#undocumented) (1)"));
+
+        let page = html(&entries);
+        assert!(page.contains("<h2 id=\"stack\">stack</h2>"));
+        assert!(page.contains("<code>&lt;=</code>"));
+    }
+
+    #[test]
+    fn summarises_and_lists_categories() {
+        let entries = entries(&interpreter());
+        let summary = summary(&entries);
+        let jobs = summary.lines().find(|l| l.trim_start().starts_with("jobs")).unwrap();
+        assert!(jobs.ends_with("3  job_done?, jobs, join"), "{jobs}");
+        assert!(summary.lines().all(|l| l.chars().count() <= SUMMARY_WIDTH), "{summary}");
+        assert!(summary.lines().any(|l| l.contains("math") && l.ends_with('…')), "{summary}");
+
+        let list = category(&entries, "reflect").unwrap();
+        assert!(list.starts_with(&format!("reflect ({} ops)\n", entries["reflect"].len())), "{list}");
+        let help = list.lines().find(|l| l.starts_with("  help ")).unwrap();
+        let aliases = list.lines().find(|l| l.starts_with("  aliases ")).unwrap();
+        assert!(help.ends_with("  Print an op's help card"), "{list}");
+        assert_eq!(help.find("( name"), aliases.find("( name"), "{list}");
+        assert!(category(&entries, "sqrt").is_none());
+    }
+
+    #[test]
+    fn help_cards_and_examples() {
+        let interp = interpreter();
+        let card = help_card(&interp, "sqrt").unwrap();
+        assert!(card.starts_with("sqrt  (also √)  [math, woflang-ops]\n"));
+        assert!(card.contains("( a -- b )  Square root"));
+        assert!(card.contains("help run sqrt"));
+        assert!(help_card(&interp, "mystery").unwrap().contains("No description"));
+        assert!(help_card(&interp, "nonexistent").is_none());
+
+        let mut scratch = interpreter();
+        assert_eq!(run_example(&interp, "√", &mut scratch).unwrap(), "16 sqrt\n  Stack[1]: 4.0\n");
+        assert!(run_example(&interp, "mystery", &mut scratch).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! What the REPL prints after each line (`:echo` and `--echo`).
+//!
+//! | Mode      | Prints |
+//! |-----------|--------|
+//! | `off`     | Nothing; scripts print their own output |
+//! | `top`     | `→ top` whenever the stack is non-empty (the default) |
+//! | `stack`   | The whole stack |
+//! | `changed` | `→ top` only when the line changed the stack |
+
+use clap::ValueEnum;
+use woflang_core::{WofStack, WofValue};
+
+/// REPL result echo policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Echo {
+    /// Never echo.
+    Off,
+    /// Echo the top of the stack.
+    #[default]
+    Top,
+    /// Echo the whole stack.
+    Stack,
+    /// Echo the top of the stack only if the line changed the stack.
+    Changed,
+}
+
+impl Echo {
+    /// Parse a mode name as typed after `:echo`.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::from_str(name, true).ok()
+    }
+
+    /// Lower-case name, as accepted by [`Echo::parse`].
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Top => "top",
+            Self::Stack => "stack",
+            Self::Changed => "changed",
+        }
+    }
+
+    /// Whether the stack must be snapshotted before each line.
+    pub const fn needs_snapshot(self) -> bool {
+        matches!(self, Self::Changed)
+    }
+
+    /// The line to print after executing a line, given the stack
+    /// `before` it ran (when [`needs_snapshot`](Self::needs_snapshot)).
+    pub fn render(self, before: Option<&[WofValue]>, stack: &WofStack) -> Option<String> {
+        let top = || stack.peek().ok().map(|top| format!("→ {top}"));
+        match self {
+            Self::Off => None,
+            Self::Top => top(),
+            Self::Stack => Some(stack.to_string()),
+            Self::Changed => match before {
+                Some(before) if before == stack.as_slice() => None,
+                _ => top(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_each_mode() {
+        let mut stack = WofStack::new();
+        stack.push(WofValue::integer(1));
+        stack.push(WofValue::integer(2));
+        let same = stack.as_slice().to_vec();
+
+        assert_eq!(Echo::Off.render(None, &stack), None);
+        assert_eq!(Echo::Top.render(None, &stack).as_deref(), Some("→ 2"));
+        assert_eq!(Echo::Stack.render(None, &stack).as_deref(), Some("Stack[2]: 1, 2"));
+        assert_eq!(Echo::Changed.render(Some(&same), &stack), None);
+        assert_eq!(Echo::Changed.render(Some(&same[..1]), &stack).as_deref(), Some("→ 2"));
+
+        stack.clear();
+        assert_eq!(Echo::Top.render(None, &stack), None);
+        assert_eq!(Echo::Changed.render(Some(&same), &stack), None);
+        assert_eq!(Echo::parse("CHANGED"), Some(Echo::Changed));
+        assert_eq!(Echo::parse("loud"), None);
+    }
+}
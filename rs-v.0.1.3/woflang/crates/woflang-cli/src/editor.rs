@@ -0,0 +1,234 @@
+//! Line editing in the REPL: tab completion, highlighting and hints.
+//!
+//! Tab completes the word under the cursor from the registered ops,
+//! user functions and variables, listing each op with its stack effect.
+//! As a line is typed, numbers, strings, glyphs, labels and comments are
+//! coloured, and a known op at the end of the line hints its stack
+//! effect:
+//!
+//! ```text
+//! wof> 3 4 sw⇥
+//! wof> 3 4 swap  ( a b -- b a )
+//! ```
+//!
+//! The names are refreshed from the interpreter before each prompt, so
+//! new functions and variables complete on the next line.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use rustyline::completion::{Completer, Pair};
+use rustyline::highlight::{CmdKind, Highlighter};
+use rustyline::hint::{Hint, Hinter};
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper};
+use woflang_runtime::{Interpreter, TokenKind, Tokenizer};
+
+const RESET: &str = "\x1b[0m";
+const NUMBER: &str = "\x1b[33m";
+const STRING: &str = "\x1b[32m";
+const GLYPH: &str = "\x1b[36m";
+const LABEL: &str = "\x1b[34m";
+const DIM: &str = "\x1b[90m";
+
+/// What a completable name is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Word {
+    /// An op, with its stack effect if it is documented.
+    Op(Option<String>),
+    Function,
+    Variable,
+}
+
+/// The REPL's rustyline helper.
+#[derive(Debug, Default)]
+pub struct WofHelper {
+    words: BTreeMap<String, Word>,
+}
+
+/// A stack-effect hint, shown but never inserted.
+#[derive(Debug)]
+pub struct EffectHint(String);
+
+impl Hint for EffectHint {
+    fn display(&self) -> &str {
+        &self.0
+    }
+
+    fn completion(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl WofHelper {
+    /// Take the current ops, functions and variables from `interp`.
+    pub fn refresh(&mut self, interp: &Interpreter) {
+        let registry = interp.registry();
+        self.words = registry
+            .completions("")
+            .into_iter()
+            .map(|name| {
+                let effect = registry.meta(name).map(|meta| meta.stack_effect.clone()).filter(|e| !e.is_empty());
+                (name.to_string(), Word::Op(effect))
+            })
+            .collect();
+        for name in interp.function_names() {
+            self.words.insert(name.to_string(), Word::Function);
+        }
+        for name in interp.scopes().all_visible_names() {
+            self.words.entry(name).or_insert(Word::Variable);
+        }
+    }
+
+    /// Start of the word ending at `pos`, and the word so far.
+    fn word_at(line: &str, pos: usize) -> (usize, &str) {
+        let before = &line[..pos];
+        let start = before.char_indices().rev().find(|(_, c)| c.is_whitespace()).map_or(0, |(i, c)| i + c.len_utf8());
+        (start, &before[start..])
+    }
+
+    /// Names completing the word before `pos`; nothing inside a string.
+    fn candidates(&self, line: &str, pos: usize) -> (usize, Vec<Pair>) {
+        let (start, word) = Self::word_at(line, pos);
+        if word.is_empty() || word.starts_with('"') || line[..start].matches('"').count() % 2 == 1 {
+            return (start, Vec::new());
+        }
+        let pairs = self
+            .words
+            .range(word.to_string()..)
+            .take_while(|(name, _)| name.starts_with(word))
+            .map(|(name, kind)| Pair {
+                display: match kind {
+                    Word::Op(Some(effect)) => format!("{name}  {effect}"),
+                    Word::Op(None) => name.clone(),
+                    Word::Function => format!("{name}  (function)"),
+                    Word::Variable => format!("{name}  (variable)"),
+                },
+                replacement: name.clone(),
+            })
+            .collect();
+        (start, pairs)
+    }
+
+    /// The stack effect of the op just typed, with the cursor at the end.
+    fn effect_hint(&self, line: &str, pos: usize) -> Option<EffectHint> {
+        if pos < line.len() {
+            return None;
+        }
+        let (_, word) = Self::word_at(line, pos);
+        match self.words.get(word)? {
+            Word::Op(Some(effect)) => Some(EffectHint(format!("  {effect}"))),
+            _ => None,
+        }
+    }
+}
+
+impl Completer for WofHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        Ok(self.candidates(line, pos))
+    }
+}
+
+impl Hinter for WofHelper {
+    type Hint = EffectHint;
+
+    fn hint(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Option<EffectHint> {
+        self.effect_hint(line, pos)
+    }
+}
+
+impl Highlighter for WofHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        let mut out = String::with_capacity(line.len() * 2);
+        let mut last = 0;
+        // Text between tokens is whitespace or a comment.
+        let gap = |out: &mut String, text: &str| match text.find('#') {
+            Some(i) => {
+                out.push_str(&text[..i]);
+                out.push_str(DIM);
+                out.push_str(&text[i..]);
+                out.push_str(RESET);
+            }
+            None => out.push_str(text),
+        };
+        for token in Tokenizer::new(line) {
+            let start = token.span.offset() as usize;
+            gap(&mut out, &line[last..start]);
+            let colour = match token.kind {
+                TokenKind::Integer | TokenKind::Float => Some(NUMBER),
+                TokenKind::String | TokenKind::Interpolated => Some(STRING),
+                TokenKind::Label | TokenKind::LabelRef => Some(LABEL),
+                TokenKind::Symbol if !token.text.is_ascii() => Some(GLYPH),
+                _ => None,
+            };
+            match colour {
+                Some(colour) => {
+                    out.push_str(colour);
+                    out.push_str(token.text);
+                    out.push_str(RESET);
+                }
+                None => out.push_str(token.text),
+            }
+            last = start + token.text.len();
+        }
+        gap(&mut out, &line[last..]);
+        Cow::Owned(out)
+    }
+
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        Cow::Owned(format!("{DIM}{hint}{RESET}"))
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize, _kind: CmdKind) -> bool {
+        true
+    }
+}
+
+impl Validator for WofHelper {}
+
+impl Helper for WofHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helper() -> WofHelper {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        interp.exec_line("⊕ swirl ⺆ dup ⺘ 5 字 swatch").unwrap();
+        let mut helper = WofHelper::default();
+        helper.refresh(&interp);
+        helper
+    }
+
+    #[test]
+    fn completes_ops_functions_and_variables() {
+        let helper = helper();
+        let (start, pairs) = helper.candidates("3 4 sw", 6);
+        assert_eq!(start, 4);
+        let shown: Vec<&str> = pairs.iter().map(|p| p.display.as_str()).collect();
+        assert!(shown.contains(&"swap  ( a b -- b a )"), "{shown:?}");
+        assert!(shown.contains(&"swirl  (function)"), "{shown:?}");
+        assert!(shown.contains(&"swatch  (variable)"), "{shown:?}");
+        assert!(pairs.iter().all(|p| p.replacement.starts_with("sw")));
+
+        assert!(helper.candidates(r#""sw"#, 3).1.is_empty());
+        assert!(helper.candidates("1 ", 2).1.is_empty());
+        assert_eq!(helper.effect_hint("3 4 swap", 8).unwrap().display(), "  ( a b -- b a )");
+        assert!(helper.effect_hint("3 4 swap", 3).is_none());
+    }
+
+    #[test]
+    fn highlights_literals_glyphs_and_comments() {
+        let helper = helper();
+        let line = r#"3.5 "hi" ⊕ dup # note"#;
+        let shown = helper.highlight(line, 0);
+        assert_eq!(
+            shown,
+            format!("{NUMBER}3.5{RESET} {STRING}\"hi\"{RESET} {GLYPH}⊕{RESET} dup {DIM}# note{RESET}")
+        );
+        assert_eq!(helper.highlight("", 0), "");
+    }
+}
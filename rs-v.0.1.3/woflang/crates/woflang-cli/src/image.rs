@@ -0,0 +1,363 @@
+//! The warm-start image: what building an interpreter works out, saved so
+//! later runs can skip it.
+//!
+//! Registering the ops is quick; attaching metadata to every plugin op
+//! and defining the standard library is most of the startup time. The
+//! image holds the result of that work: every registered op name in
+//! order with its metadata, and the stdlib functions as tokens. A warm
+//! start registers the op handlers (closures, which cannot be saved),
+//! checks that they register exactly the names the image lists, and
+//! restores the rest from the image.
+//!
+//! An image is used only if it was written by this format, this woflang
+//! version and the same set of enabled plugins, its fingerprint of the
+//! stdlib source and the standard ops' metadata is current, and its op
+//! names still match. Editing a stdlib function or an op's help thus
+//! invalidates the image even without a version bump. Anything else (no
+//! image, a corrupt or stale one) means a cold start, which writes a
+//! fresh image for next time. The constants
+//! database is not part of the image: it is parsed the first time a
+//! constant is looked up, not at startup.
+//!
+//! The image is read into memory rather than mapped, since mapping a
+//! file needs `unsafe`, which the workspace denies.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use woflang_core::{Result, Span, WofError};
+use woflang_runtime::{is_special_form, FunctionDef, Interpreter, OpMeta, OwnedToken, TokenKind};
+
+/// Layout version of the image; bump it when the saved fields change.
+const FORMAT: u32 = 2;
+
+/// Token kinds with their names in the image.
+const KINDS: [(TokenKind, &str); 9] = [
+    (TokenKind::Integer, "integer"),
+    (TokenKind::Float, "float"),
+    (TokenKind::String, "string"),
+    (TokenKind::Interpolated, "interpolated"),
+    (TokenKind::Symbol, "symbol"),
+    (TokenKind::Label, "label"),
+    (TokenKind::LabelRef, "label_ref"),
+    (TokenKind::Infix, "infix"),
+    (TokenKind::Eof, "eof"),
+];
+
+/// An interpreter built by [`start`] and the image it was built with.
+pub struct Started {
+    /// The interpreter, with every op and plugin registered.
+    pub interp: Interpreter,
+    /// The image, for [`Image::define_stdlib`].
+    pub image: Image,
+    /// Whether the image was loaded rather than built.
+    pub warm: bool,
+}
+
+/// A saved interpreter build.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Image {
+    format: u32,
+    version: String,
+    plugins: Vec<String>,
+    /// See [`fingerprint`].
+    fingerprint: u64,
+    /// Every registered op, in registration order, with its metadata;
+    /// emptied once restored onto an interpreter.
+    ops: Vec<(String, Option<Meta>)>,
+    functions: Vec<Function>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Meta {
+    category: String,
+    stack_effect: String,
+    help: String,
+    example: Option<String>,
+    source: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Function {
+    name: String,
+    arity: usize,
+    span: [u32; 4],
+    body: Vec<Token>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Token {
+    kind: String,
+    text: String,
+    span: [u32; 4],
+}
+
+/// Build an interpreter with the standard ops and all enabled plugins,
+/// warm from the image at `path` if it is current, else cold.
+///
+/// A cold start saves its image to `path`. Failing to save is not an
+/// error: the next run simply starts cold again.
+pub fn start(path: Option<&Path>) -> Result<Started> {
+    let mut interp = Interpreter::new();
+    woflang_ops::register_all(&mut interp);
+    let fingerprint = fingerprint(&interp);
+    if let Some(mut image) = path.and_then(|path| Image::load(path, fingerprint)) {
+        woflang_plugins::register_handlers(&mut interp);
+        if image.restore(&mut interp) {
+            return Ok(Started { interp, image, warm: true });
+        }
+        interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+    }
+
+    woflang_plugins::register_all(&mut interp);
+    let image = Image::capture(&interp, fingerprint)?;
+    if let Some(path) = path {
+        let _ = image.save(path);
+    }
+    Ok(Started { interp, image, warm: false })
+}
+
+impl Image {
+    /// Record the ops registered in `interp` and the stdlib functions.
+    fn capture(interp: &Interpreter, fingerprint: u64) -> Result<Self> {
+        let mut stdlib = Interpreter::new();
+        woflang_ops::stdlib::load(&mut stdlib)?;
+        let functions = stdlib
+            .function_names()
+            .into_iter()
+            .filter_map(|name| stdlib.get_function(name))
+            .map(Function::from)
+            .collect();
+        let ops = interp
+            .registry()
+            .names()
+            .map(|name| (name.to_string(), interp.registry().meta(name).map(Meta::from)))
+            .collect();
+        Ok(Self {
+            format: FORMAT,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            plugins: plugins(),
+            fingerprint,
+            ops,
+            functions,
+        })
+    }
+
+    /// Read the image at `path`, if there is one built by this binary
+    /// from the sources `fingerprint` describes.
+    fn load(path: &Path, fingerprint: u64) -> Option<Self> {
+        let bytes = fs::read(path).ok()?;
+        let image: Self = serde_json::from_slice(&bytes).ok()?;
+        (image.format == FORMAT
+            && image.version == env!("CARGO_PKG_VERSION")
+            && image.plugins == plugins()
+            && image.fingerprint == fingerprint)
+            .then_some(image)
+    }
+
+    /// Write the image to `path`, replacing any older one in one step so
+    /// a concurrent start never reads half an image.
+    fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&partial, serde_json::to_vec(self)?)?;
+        fs::rename(&partial, path)
+    }
+
+    /// Move the saved metadata onto the ops of `interp`, whose handlers
+    /// are registered but untagged. Returns false, leaving `interp` and
+    /// the image untouched, if its ops are not the ones the image lists.
+    fn restore(&mut self, interp: &mut Interpreter) -> bool {
+        if !interp.registry().names().eq(self.ops.iter().map(|(name, _)| name.as_str())) {
+            return false;
+        }
+        for (name, meta) in std::mem::take(&mut self.ops) {
+            if let Some(meta) = meta {
+                interp.describe(name, meta.into());
+            }
+        }
+        true
+    }
+
+    /// Define the standard library functions in `interp`, binding their
+    /// symbols to its ops.
+    pub fn define_stdlib(&self, interp: &mut Interpreter) -> Result<()> {
+        for function in &self.functions {
+            let body = function
+                .body
+                .iter()
+                .map(|token| token.to_owned_token(interp))
+                .collect::<Result<Vec<_>>>()?;
+            interp.define_function(
+                FunctionDef::new(function.name.clone(), body, span_from(function.span)).with_arity(function.arity),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A hash of what the image caches: the stdlib source it tokenizes and
+/// the standard ops registered in `interp`, names and metadata in order.
+/// Plugins never describe their own ops, so their metadata follows from
+/// these and the plugin list.
+fn fingerprint(interp: &Interpreter) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    woflang_ops::stdlib::SOURCES.hash(&mut hasher);
+    for name in interp.registry().names() {
+        name.hash(&mut hasher);
+        if let Some(meta) = interp.registry().meta(name) {
+            (&meta.category, &meta.stack_effect, &meta.help, &meta.example, &meta.source).hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Names of the enabled plugins, in registration order.
+fn plugins() -> Vec<String> {
+    woflang_plugins::modules().into_iter().map(|(name, _)| name.to_string()).collect()
+}
+
+impl From<&OpMeta> for Meta {
+    fn from(meta: &OpMeta) -> Self {
+        Self {
+            category: meta.category.clone(),
+            stack_effect: meta.stack_effect.clone(),
+            help: meta.help.clone(),
+            example: meta.example.clone(),
+            source: meta.source.clone(),
+        }
+    }
+}
+
+impl From<Meta> for OpMeta {
+    fn from(meta: Meta) -> Self {
+        Self {
+            category: meta.category,
+            stack_effect: meta.stack_effect,
+            help: meta.help,
+            example: meta.example,
+            source: meta.source,
+        }
+    }
+}
+
+impl From<&FunctionDef> for Function {
+    fn from(function: &FunctionDef) -> Self {
+        Self {
+            name: function.name.clone(),
+            arity: function.arity,
+            span: span_to(function.span),
+            body: function
+                .body
+                .iter()
+                .map(|token| Token {
+                    kind: KINDS.iter().find(|(kind, _)| *kind == token.kind).map_or("eof", |(_, name)| name).to_string(),
+                    text: token.text.clone(),
+                    span: span_to(token.span),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Token {
+    /// The token, bound to the op of `interp` it names, as
+    /// [`exec_source`](Interpreter::exec_source) would bind it.
+    fn to_owned_token(&self, interp: &Interpreter) -> Result<OwnedToken> {
+        let kind = KINDS
+            .iter()
+            .find(|(_, name)| *name == self.kind)
+            .map(|(kind, _)| *kind)
+            .ok_or_else(|| WofError::Runtime(format!("warm-start image: unknown token kind '{}'", self.kind)))?;
+        let op = (kind == TokenKind::Symbol && !is_special_form(&self.text))
+            .then(|| interp.registry().id_of(&self.text))
+            .flatten();
+        Ok(OwnedToken { kind, text: self.text.clone(), span: span_from(self.span), op })
+    }
+}
+
+const fn span_to(span: Span) -> [u32; 4] {
+    [span.line(), span.column(), span.offset(), span.length()]
+}
+
+const fn span_from([line, column, offset, length]: [u32; 4]) -> Span {
+    Span::with_length(line, column, offset, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use woflang_core::InterpreterContext;
+
+    fn run(started: Started, code: &str) -> Interpreter {
+        let mut interp = started.interp;
+        started.image.define_stdlib(&mut interp).unwrap();
+        interp.exec_line(code).unwrap();
+        interp
+    }
+
+    #[test]
+    fn warm_start_matches_a_cold_one() {
+        let dir = std::env::temp_dir().join(format!("woflang-image-{}", std::process::id()));
+        let path = dir.join("image.json");
+
+        let cold = start(Some(&path)).unwrap();
+        assert!(!cold.warm);
+        assert!(path.exists());
+        let warm = start(Some(&path)).unwrap();
+        assert!(warm.warm);
+
+        let names: Vec<&str> = cold.interp.registry().names().collect();
+        assert!(warm.interp.registry().names().eq(names.iter().copied()));
+        for name in &names {
+            assert_eq!(warm.interp.registry().meta(name), cold.interp.registry().meta(name), "{name}");
+        }
+        let cold = run(cold, "3 sq 0 0 3 4 dist2d");
+        let warm = run(warm, "3 sq 0 0 3 4 dist2d");
+        assert_eq!(warm.stack().as_slice(), cold.stack().as_slice());
+        assert!(warm.get_function("sq").unwrap().body.iter().any(|t| t.op.is_some()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn stale_or_corrupt_images_start_cold() {
+        let dir = std::env::temp_dir().join(format!("woflang-stale-image-{}", std::process::id()));
+        let path = dir.join("image.json");
+        let mut image = start(None).unwrap().image;
+
+        image.version = "0.0.0".to_string();
+        image.save(&path).unwrap();
+        assert!(!start(Some(&path)).unwrap().warm);
+        assert!(start(Some(&path)).unwrap().warm, "the cold start rewrote the image");
+
+        image.version = env!("CARGO_PKG_VERSION").to_string();
+        image.plugins.pop();
+        image.save(&path).unwrap();
+        assert!(!start(Some(&path)).unwrap().warm);
+
+        image.plugins = plugins();
+        image.fingerprint ^= 1;
+        image.save(&path).unwrap();
+        assert!(!start(Some(&path)).unwrap().warm, "edited stdlib or op docs");
+
+        image.fingerprint ^= 1;
+        image.ops.swap(0, 1);
+        image.save(&path).unwrap();
+        assert!(!start(Some(&path)).unwrap().warm);
+
+        std::fs::write(&path, "{ not an image").unwrap();
+        let started = start(Some(&path)).unwrap();
+        assert!(!started.warm);
+        assert_eq!(run(started, "3 sq").stack().as_slice(), [woflang_core::WofValue::integer(9)]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,25 +4,60 @@
 //!
 //! ```text
 //! woflang [OPTIONS] [SCRIPT]
+//! woflang doc [--out <DIR>]   Generate the op reference (Markdown and HTML)
+//! woflang trace <SCRIPT> [--html <FILE>]   Show the stack before and after
+//!                              each token; --html writes an animated page
 //!
 //! Options:
 //!   -h, --help       Show help
 //!   -v, --version    Show version
 //!   --test           Run test suite
-//!   --benchmark      Run prime benchmarking suite
+//!   --run-tests <DIR>  Run the test_case blocks in every *_test.wof file
+//!                    under DIR; exit with 1 if any fail
+//!   --benchmark [SUITE]  Run a benchmark suite (primes, fib, sort, matrix,
+//!                    graph, quantum or all; default primes)
+//!   --digits <N>     Digits per generated prime (with --benchmark primes)
+//!   --count <N>      Workloads per benchmark category
+//!   --format <FMT>   Benchmark output: console, csv or json
+//!   --breakdown      Show per-phase dispatch timings in console output
 //!   --test-analog    Run analog computing test suite
 //!   --debug          Enable debug output
+//!   --echo <MODE>    REPL echo after each line: off, top, stack or changed
+//!   --no-stdlib      Skip loading the Woflang standard library
+//!   --allow-path <DIR>  Confine script file access to DIR (repeatable)
+//!   --read-only      Refuse file writes from scripts
+//!   --coverage       Print op/function/line coverage after the run
+//!   --lcov <FILE>    Also write line coverage in lcov format
+//!   --debugger       Pause before a script's first token; stop at breakpoints
+//!   --profile-out <FILE>  Time each op and function; write a JSON report
+//!   --watch-plugins  Reload plugins in the REPL when their libraries change
 //! ```
 
-use clap::Parser;
+mod bench;
+mod debug;
+mod doc;
+mod echo;
+mod editor;
+mod image;
+mod profile;
+mod progress;
+mod reload;
+mod testrun;
+mod trace;
+mod undo;
+mod watch;
+mod workspace;
+
+use clap::{Parser, Subcommand};
 use color_eyre::eyre::{Result, WrapErr};
 use rustyline::error::ReadlineError;
-use rustyline::DefaultEditor;
+use rustyline::config::{CompletionType, Config};
+use rustyline::history::DefaultHistory;
+use rustyline::Editor;
 use std::io::IsTerminal;
-use std::path::PathBuf;
-use std::time::Instant;
-use woflang_core::InterpreterContext;
-use woflang_runtime::{Interpreter, PluginLoader};
+use std::path::{Path, PathBuf};
+use woflang_core::{InterpreterContext, WofError};
+use woflang_runtime::{Interpreter, PluginLoader, Sandbox};
 
 const BANNER: &str = r#"
 ╦ ╦┌─┐┌─┐┬  ┌─┐┌┐┌┌─┐
@@ -35,6 +70,9 @@ A Unicode-native stack language (Rust Edition)
 #[command(name = "woflang")]
 #[command(author, version, about = "A Unicode-native stack-based programming language")]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Script file to execute
     #[arg(value_name = "SCRIPT")]
     script: Option<PathBuf>,
@@ -43,9 +81,30 @@ struct Args {
     #[arg(long)]
     test: bool,
 
-    /// Run prime benchmarking suite
-    #[arg(long)]
-    benchmark: bool,
+    /// Run the test cases in every *_test.wof file under DIR and print a
+    /// pass/fail summary; exits with 1 if any fail
+    #[arg(long, value_name = "DIR")]
+    run_tests: Option<PathBuf>,
+
+    /// Run a benchmark suite
+    #[arg(long, value_enum, value_name = "SUITE", num_args = 0..=1, default_missing_value = "primes")]
+    benchmark: Option<bench::Category>,
+
+    /// Digits per generated prime candidate (default: the classic prime table)
+    #[arg(long, value_name = "N", requires = "benchmark")]
+    digits: Option<u32>,
+
+    /// Workloads per benchmark category
+    #[arg(long, value_name = "N", requires = "benchmark")]
+    count: Option<usize>,
+
+    /// Benchmark output format
+    #[arg(long, value_enum, default_value_t, requires = "benchmark")]
+    format: bench::Format,
+
+    /// Show where benchmark time goes (tokenize, lookup, convert, handler)
+    #[arg(long, requires = "benchmark")]
+    breakdown: bool,
 
     /// Run analog computing test suite
     #[arg(long)]
@@ -55,9 +114,80 @@ struct Args {
     #[arg(long, short)]
     debug: bool,
 
+    /// What the REPL prints after each line (change with :echo)
+    #[arg(long, value_enum, value_name = "MODE", default_value_t)]
+    echo: echo::Echo,
+
+    /// Do not load the standard library functions written in Woflang
+    #[arg(long)]
+    no_stdlib: bool,
+
+    /// Reject lines using symbols that name no op, function or variable
+    #[arg(long)]
+    strict: bool,
+
+    /// Ignore the saved warm-start image and build a fresh one
+    #[arg(long)]
+    cold_start: bool,
+
+    /// Let scripts use files only under DIR (repeatable; default: anywhere)
+    #[arg(long, value_name = "DIR")]
+    allow_path: Vec<PathBuf>,
+
+    /// Refuse file writes from scripts
+    #[arg(long)]
+    read_only: bool,
+
     /// Plugin directory path
     #[arg(long, default_value = "plugins")]
     plugins: PathBuf,
+
+    /// Load the plugins in the plugin directory, list them and exit
+    #[arg(long)]
+    list_plugins: bool,
+
+    /// In the REPL, reload plugins whose libraries change in the plugin
+    /// directory before running each line
+    #[arg(long)]
+    watch_plugins: bool,
+
+    /// Print op, function and line coverage after running
+    #[arg(long)]
+    coverage: bool,
+
+    /// Write line coverage of executed scripts to FILE in lcov format
+    #[arg(long, value_name = "FILE")]
+    lcov: Option<PathBuf>,
+
+    /// Run under the debugger: scripts pause before their first token
+    #[arg(long)]
+    debugger: bool,
+
+    /// Time each op and user function and write a JSON report to FILE
+    /// when the script or REPL session ends
+    #[arg(long, value_name = "FILE")]
+    profile_out: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Generate the operation reference from registry metadata
+    Doc {
+        /// Directory to write ops.md and ops.html into
+        #[arg(long, default_value = "docs")]
+        out: PathBuf,
+    },
+    /// Run a script token by token, showing the stack after each one
+    Trace {
+        /// Script file to trace
+        script: PathBuf,
+        /// Write an animated HTML page to FILE instead of printing the steps
+        #[arg(long, value_name = "FILE")]
+        html: Option<PathBuf>,
+        /// Stop the script after N tokens
+        #[arg(long, value_name = "N", default_value_t = trace::DEFAULT_MAX_STEPS)]
+        max_steps: usize,
+    },
 }
 
 fn main() -> Result<()> {
@@ -65,13 +195,60 @@ fn main() -> Result<()> {
 
     let args = Args::parse();
 
+    // Declared before any interpreter so plugin code outlives their ops.
+    let mut plugins = PluginLoader::new();
+
+    if let Some(Command::Doc { out }) = &args.command {
+        let interp = create_interpreter(&args, &mut plugins)?;
+        for path in doc::write(&interp, out)
+            .wrap_err_with(|| format!("failed to write docs to {}", out.display()))?
+        {
+            println!("Wrote {}", path.display());
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Trace { script, html, max_steps }) = &args.command {
+        let mut interp = create_interpreter(&args, &mut plugins)?;
+        let trace = trace::record_file(&mut interp, script, *max_steps)
+            .wrap_err_with(|| format!("failed to read script: {}", script.display()))?;
+        match html {
+            Some(path) => {
+                std::fs::write(path, trace::html(&trace))
+                    .wrap_err_with(|| format!("failed to write trace: {}", path.display()))?;
+                println!("Wrote {} ({} steps)", path.display(), trace.steps.len());
+            }
+            None => print!("{}", trace::text(&trace)),
+        }
+        return Ok(());
+    }
+
     if args.test {
-        run_tests()?;
+        run_tests(args.coverage)?;
         return Ok(());
     }
 
-    if args.benchmark {
-        run_benchmark()?;
+    if let Some(dir) = &args.run_tests {
+        let files = testrun::discover(dir)
+            .wrap_err_with(|| format!("failed to read test directory: {}", dir.display()))?;
+        let mut results = Vec::with_capacity(files.len());
+        for path in &files {
+            let mut interp = create_interpreter(&args, &mut plugins)?;
+            results.push(testrun::run_file(&mut interp, path));
+        }
+        print!("{}", testrun::report(&results));
+        if !results.iter().all(testrun::FileResult::passed) {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(suite) = args.benchmark {
+        let mut config = bench::BenchConfig { digits: args.digits, ..bench::BenchConfig::default() };
+        if let Some(count) = args.count {
+            config.count = count;
+        }
+        run_benchmark(suite, &config, args.format, args.breakdown)?;
         return Ok(());
     }
 
@@ -80,48 +257,191 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if args.list_plugins {
+        create_interpreter(&args, &mut plugins)?;
+        list_plugins(&plugins, &args.plugins);
+        return Ok(());
+    }
+
     // Create and configure interpreter
-    let mut interp = create_interpreter(&args)?;
+    if args.script.is_none() {
+        plugins.enable_hot_reload();
+    }
+    let mut interp = create_interpreter(&args, &mut plugins)?;
+    install_interrupt_handler(&interp)?;
+    if let Some(bar) = progress::Bar::for_stderr() {
+        interp.set_progress_sink(bar);
+    }
+
+    if args.debugger {
+        debug::ensure_attached(&mut interp);
+    }
+    if args.profile_out.is_some() {
+        interp.enable_op_profile();
+    }
 
     // Execute script or start REPL
     if let Some(script_path) = &args.script {
-        interp
+        if args.coverage || args.lcov.is_some() {
+            interp.enable_coverage();
+        }
+        if args.debugger {
+            interp.step();
+        }
+        let result = interp
             .exec_file(script_path)
-            .wrap_err_with(|| format!("failed to execute script: {}", script_path.display()))?;
+            .wrap_err_with(|| format!("failed to execute script: {}", script_path.display()));
+        report_coverage(&interp, &args)?;
+        report_profile(&interp, &args)?;
+        result?;
     } else {
-        run_repl(&mut interp)?;
+        let watch = reload::PluginWatch::new(&args.plugins, args.watch_plugins);
+        run_repl(&mut interp, args.echo, &mut plugins, watch)?;
+        report_profile(&interp, &args)?;
     }
 
     Ok(())
 }
 
-fn create_interpreter(args: &Args) -> Result<Interpreter> {
-    let mut interp = Interpreter::new();
+fn create_interpreter(args: &Args, plugins: &mut PluginLoader) -> Result<Interpreter> {
+    // Register standard and plugin operations, restoring their metadata
+    // from the warm-start image when it is current
+    let image_path = dirs::data_local_dir().map(|d| d.join("woflang").join("image.json"));
+    if args.cold_start {
+        if let Some(path) = &image_path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+    let image::Started { mut interp, image, warm } = image::start(image_path.as_deref())?;
     interp.debug = args.debug;
-
-    // Register standard operations
-    woflang_ops::register_all(&mut interp);
-    
-    // Register plugin operations (math, util, crypto, logic, sigils)
-    woflang_plugins::register_all(&mut interp);
+    if args.debug {
+        eprintln!("[debug] {} start", if warm { "warm" } else { "cold" });
+    }
 
     // Load dynamic plugins if directory exists
     if args.plugins.exists() {
-        let mut loader = PluginLoader::new();
-        let loaded = loader.load_plugins_from_dir(&args.plugins, &mut interp)?;
-        if !loaded.is_empty() {
+        let loaded = plugins.load_plugins_from_dir(&args.plugins, &mut interp)?;
+        if !loaded.is_empty() && !args.list_plugins {
             eprintln!("Loaded {} dynamic plugin(s)", loaded.len());
         }
     }
 
+    // Define the self-hosted standard library functions
+    if !args.no_stdlib {
+        image.define_stdlib(&mut interp)?;
+    }
+    interp.strict = args.strict;
+
+    let mut sandbox = Sandbox::default();
+    for root in &args.allow_path {
+        sandbox.allow(root).wrap_err("Invalid --allow-path")?;
+    }
+    sandbox.read_only = args.read_only;
+    interp.set_sandbox(sandbox);
+
     Ok(interp)
 }
 
-fn run_repl(interp: &mut Interpreter) -> Result<()> {
+/// Print the plugins loaded from `dir` for `--list-plugins`.
+fn list_plugins(plugins: &PluginLoader, dir: &Path) {
+    if plugins.count() == 0 {
+        println!("No plugins loaded from {}.", dir.display());
+        return;
+    }
+    for plugin in plugins.plugins() {
+        println!(
+            "{} {}  (core {}, {} ops)  {}",
+            plugin.name,
+            plugin.version,
+            plugin.core_version,
+            plugin.ops.len(),
+            plugin.path.display()
+        );
+        if !plugin.ops.is_empty() {
+            println!("  {}", plugin.ops.join(" "));
+        }
+    }
+}
+
+/// Print what loading or reloading plugins in the REPL did.
+fn report_reloads(results: Vec<std::result::Result<String, String>>) {
+    for result in results {
+        match result {
+            Ok(message) => println!("{message}"),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+}
+
+/// Make Ctrl-C stop the running computation instead of the process. A
+/// second Ctrl-C before the first is noticed exits as usual.
+fn install_interrupt_handler(interp: &Interpreter) -> Result<()> {
+    let cancel = interp.cancel_token();
+    ctrlc::set_handler(move || {
+        if cancel.is_cancelled() {
+            std::process::exit(130);
+        }
+        cancel.cancel();
+    })
+    .wrap_err("failed to install Ctrl-C handler")
+}
+
+/// Print and/or export the coverage collected by `interp`, if any.
+fn report_coverage(interp: &Interpreter, args: &Args) -> Result<()> {
+    let Some(coverage) = interp.coverage() else {
+        return Ok(());
+    };
+    if args.coverage {
+        print!("{}", coverage.summary(interp.registry().names(), interp.function_names()));
+    }
+    if let Some(path) = &args.lcov {
+        std::fs::write(path, coverage.to_lcov())
+            .wrap_err_with(|| format!("failed to write lcov report: {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Write the op profile collected by `interp` to `--profile-out`, if both
+/// are there.
+fn report_profile(interp: &Interpreter, args: &Args) -> Result<()> {
+    if let (Some(profile), Some(path)) = (interp.op_profile(), &args.profile_out) {
+        profile::write_json(profile, path)
+            .wrap_err_with(|| format!("failed to write profile: {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// `help <op>` prints an op's metadata; `help run <op>` runs its example
+/// in a fresh interpreter so the session's stack is left alone.
+fn show_op_help(interp: &Interpreter, query: &str) {
+    if let Some(name) = query.strip_prefix("run ") {
+        let mut scratch = Interpreter::new();
+        woflang_ops::register_all(&mut scratch);
+        woflang_plugins::register_all(&mut scratch);
+        match doc::run_example(interp, name.trim(), &mut scratch) {
+            Ok(result) => print!("{result}"),
+            Err(e) => eprintln!("{e}"),
+        }
+        return;
+    }
+    match doc::help_card(interp, query) {
+        Some(card) => print!("{card}"),
+        None => eprintln!("Unknown op: {query}"),
+    }
+}
+
+fn run_repl(
+    interp: &mut Interpreter,
+    mut echo: echo::Echo,
+    plugins: &mut PluginLoader,
+    mut plugin_watch: reload::PluginWatch,
+) -> Result<()> {
     println!("{BANNER}");
     println!("Type 'help' for commands, 'quit' to exit.");
 
-    let mut rl = DefaultEditor::new()?;
+    let config = Config::builder().completion_type(CompletionType::List).build();
+    let mut rl: Editor<editor::WofHelper, DefaultHistory> = Editor::with_config(config)?;
+    rl.set_helper(Some(editor::WofHelper::default()));
     let history_path = dirs::data_local_dir()
         .map(|d| d.join("woflang").join("history.txt"))
         .unwrap_or_else(|| PathBuf::from(".woflang_history"));
@@ -132,11 +452,33 @@ fn run_repl(interp: &mut Interpreter) -> Result<()> {
     }
 
     let _ = rl.load_history(&history_path);
+    let mut watches = watch::Watches::default();
+    let mut workspaces = workspace::Workspaces::new(interp);
+    let mut history = undo::History::default();
+    // Lines of a block or string still open, waiting for the rest
+    let mut pending = String::new();
 
     loop {
-        match rl.readline("wof> ") {
-            Ok(line) => {
-                let line = line.trim();
+        let prompt = match workspaces.current() {
+            _ if !pending.is_empty() => "...> ".to_string(),
+            workspace::MAIN => "wof> ".to_string(),
+            name => format!("wof:{name}> "),
+        };
+        if let Some(helper) = rl.helper_mut() {
+            helper.refresh(interp);
+        }
+        match rl.readline(&prompt) {
+            Ok(input) => {
+                let command = pending.is_empty() && (input.starts_with(':') || input.trim_start().starts_with("help "));
+                if !pending.is_empty() {
+                    pending.push('\n');
+                }
+                pending.push_str(&input);
+                if !command && woflang_runtime::is_incomplete(&pending) {
+                    continue;
+                }
+                let statement = std::mem::take(&mut pending);
+                let line = statement.trim();
 
                 if line.is_empty() {
                     continue;
@@ -144,23 +486,133 @@ fn run_repl(interp: &mut Interpreter) -> Result<()> {
 
                 let _ = rl.add_history_entry(line);
 
+                if plugin_watch.enabled && line != ":reload-plugins" {
+                    report_reloads(reload::apply(plugins, interp, &plugin_watch.changed()));
+                }
+
                 match line {
                     "quit" | "exit" => {
                         println!("Goodbye from woflang! 🐺");
                         break;
                     }
                     "help" => {
-                        show_help();
+                        show_help(interp);
+                        continue;
+                    }
+                    ":echo" => {
+                        println!("echo: {}", echo.name());
+                        continue;
+                    }
+                    _ if line.starts_with(":echo ") => {
+                        match echo::Echo::parse(line[":echo ".len()..].trim()) {
+                            Some(mode) => echo = mode,
+                            None => eprintln!("Usage: :echo off|top|stack|changed"),
+                        }
+                        continue;
+                    }
+                    ":workspace" | ":workspace list" => {
+                        print!("{}", workspaces.list(interp));
+                        continue;
+                    }
+                    _ if line.starts_with(":workspace ") => {
+                        let result = match line[":workspace ".len()..].split_whitespace().collect::<Vec<_>>()[..] {
+                            ["new", name] => workspaces.create(interp, name),
+                            ["switch", name] => workspaces.switch(interp, name),
+                            _ => Err("Usage: :workspace new|switch NAME, or :workspace list".into()),
+                        };
+                        match result {
+                            Ok(()) => history.clear(),
+                            Err(e) => eprintln!("{e}"),
+                        }
+                        continue;
+                    }
+                    ":reload-plugins" => {
+                        let libraries = plugin_watch.all();
+                        if libraries.is_empty() {
+                            println!("No plugins in {}", plugin_watch.dir().display());
+                        }
+                        report_reloads(reload::apply(plugins, interp, &libraries));
+                        continue;
+                    }
+                    "undo" | ":undo" => {
+                        print!("{}", history.undo(interp));
+                        continue;
+                    }
+                    ":watch" => {
+                        print!("{}", watches.list());
+                        continue;
+                    }
+                    _ if line.starts_with(":watch ") => {
+                        watches.add(line[":watch ".len()..].trim());
+                        if let Some(status) = watches.status(interp) {
+                            println!("{status}");
+                        }
+                        continue;
+                    }
+                    ":unwatch" => {
+                        watches.clear();
+                        continue;
+                    }
+                    _ if line.starts_with(":unwatch ") => {
+                        let arg = line[":unwatch ".len()..].trim();
+                        if arg.parse().ok().and_then(|n| watches.remove(n)).is_none() {
+                            eprintln!("Usage: :unwatch [N]  (see :watch for numbers)");
+                        }
+                        continue;
+                    }
+                    ":step" => {
+                        debug::ensure_attached(interp);
+                        interp.step();
+                        println!("The next line pauses before its first token");
+                        continue;
+                    }
+                    ":continue" => {
+                        println!("Not paused; :continue applies at a (debug) prompt");
+                        continue;
+                    }
+                    _ if line.starts_with(":break") || line.starts_with(":unbreak") || line == ":frames" => {
+                        debug::ensure_attached(interp);
+                        match debug::command(interp, line) {
+                            Some(out) => print!("{out}"),
+                            None => eprintln!("Usage: :break [op|line], :unbreak [op|line]"),
+                        }
+                        continue;
+                    }
+                    _ if line == ":profile" || line.starts_with(":profile ") => {
+                        match profile::command(interp, line[":profile".len()..].trim()) {
+                            Some(out) => print!("{out}"),
+                            None => eprintln!("Usage: :profile [off|reset]"),
+                        }
+                        continue;
+                    }
+                    _ if line.starts_with("help ") => {
+                        show_op_help(interp, line["help ".len()..].trim());
+                        continue;
+                    }
+                    ":doc" => {
+                        print!("{}", doc::summary(&doc::entries(interp)));
+                        continue;
+                    }
+                    _ if line.starts_with(":doc ") => {
+                        let query = line[":doc ".len()..].trim();
+                        match doc::category(&doc::entries(interp), query) {
+                            Some(list) => print!("{list}"),
+                            None => match doc::help_card(interp, query) {
+                                Some(card) => print!("{card}"),
+                                None => eprintln!("Unknown op or category: {query}"),
+                            },
+                        }
                         continue;
                     }
                     "benchmark" => {
-                        if let Err(e) = run_benchmark() {
+                        let config = bench::BenchConfig::default();
+                        if let Err(e) = run_benchmark(bench::Category::Primes, &config, bench::Format::Console, false) {
                             eprintln!("Benchmark error: {e}");
                         }
                         continue;
                     }
                     "test" => {
-                        if let Err(e) = run_tests() {
+                        if let Err(e) = run_tests(false) {
                             eprintln!("Test error: {e}");
                         }
                         continue;
@@ -172,13 +624,22 @@ fn run_repl(interp: &mut Interpreter) -> Result<()> {
                     _ => {}
                 }
 
+                history.record(interp);
+                let before = echo.needs_snapshot().then(|| interp.stack().as_slice().to_vec());
+                // A Ctrl-C while idle must not abort the next line
+                interp.cancel_token().reset();
                 match interp.exec_line(line) {
                     Ok(()) => {
-                        if !interp.stack().is_empty() {
-                            if let Ok(top) = interp.stack().peek() {
-                                println!("→ {top}");
-                            }
+                        if let Some(out) = echo.render(before.as_deref(), interp.stack()) {
+                            println!("{out}");
+                        }
+                    }
+                    Err(e @ WofError::Interrupted(_)) => {
+                        if std::io::stderr().is_terminal() {
+                            progress::clear_line();
                         }
+                        eprintln!("⏹  {e}");
+                        eprintln!("   {}", interp.stack());
                     }
                     Err(e) => {
                         // Render pretty diagnostic with source context
@@ -187,9 +648,13 @@ fn run_repl(interp: &mut Interpreter) -> Result<()> {
                         eprint!("{}", diag.render(use_color));
                     }
                 }
+                if let Some(status) = watches.status(interp) {
+                    println!("{status}");
+                }
             }
             Err(ReadlineError::Interrupted) => {
                 println!("^C");
+                pending.clear();
                 continue;
             }
             Err(ReadlineError::Eof) => {
@@ -207,14 +672,17 @@ fn run_repl(interp: &mut Interpreter) -> Result<()> {
     Ok(())
 }
 
-fn show_help() {
-    println!(
+fn show_help(interp: &Interpreter) {
+    print!(
         r#"
 WofLang - Stack-based Programming Language
 
 Interactive Commands:
   exit, quit     Exit the interpreter
   help           Show this help
+  help <op>      Show an op's stack effect, description and example
+  help run <op>  Run the op's example in a scratch interpreter
+  :doc <op>      Same as help <op>; :doc <category> lists a category's ops
   .s, .          Show stack contents
   :vars          Show variables
   :funcs         Show functions
@@ -222,173 +690,119 @@ Interactive Commands:
   :labels        Show defined labels
   :bind a g      Bind alias 'a' to glyph 'g'
   :help          Extended REPL help
-  benchmark      Run benchmarking suite
+  :echo MODE     After each line print: off, top, stack or changed
+  :workspace new NAME     Start a workspace with its own stack, variables
+                          and functions (also: switch NAME, list)
+  :watch EXPR    Show EXPR's result (run on a copy) after every line
+  :watch         List watches
+  :unwatch [N]   Remove watch N, or all watches
+  :break X       Pause before op X or at line X (no X: list breakpoints)
+  :unbreak [X]   Remove breakpoint X, or all breakpoints
+  :step          Pause before the next line's first token
+  :frames        Show running calls, loops and variables
+                 (while paused: :step, :continue, :frames, .s, :abort)
+  :profile       Start timing ops and functions, then show the report
+  :profile off   Show the report and stop; :profile reset starts over
+  :reload-plugins  Reload the plugin directory's libraries and load new ones
+                 (--watch-plugins does this whenever one changes)
+  undo, :undo    Put back the stack and variables from before the last line
+  Tab            Complete an op, function or variable name
+  ...>           A block or string is still open: keep typing (Ctrl-C drops it)
+  Ctrl-C         Stop the running line (the stack is kept)
+  benchmark      Run benchmarking suite (more: woflang --benchmark all)
+  bench ⺆...⺘ N  Time a block over N runs (pushes mean ms)
   test           Run test suite
   test_analog    Run analog computing test suite
 
-Stack Operations:
+Literals:
   <number>       Push number onto stack
-  dup            Duplicate top
-  drop           Remove top
-  swap           Swap top two
-  rot            Rotate top three
 
 Variables:
   字 x  /  let x     Define x (from stack)
   読 x  /  get x     Load x onto stack
   支 x  /  set x     Update x (from stack)
+  $"x is {{x}}"      String with {{expr}} filled in (stack left unchanged)
+  """..."""        Raw string: spans lines in scripts, no escapes
 
 Control Flow:
   若 ⺆...⺘ 或 ⺆...⺘    if/else
   ⟳ ⺆...⺘              infinite loop
   N ⨯ ⺆...⺘            repeat N times
+  当 ⺆c⺘ ⺆...⺘         while c leaves a true value
+  jit ⺆...⺘            run a numeric block as native code (--features jit)
   🛑 / break            exit loop
   ↻ / continue          restart iteration
 
 Functions:
-  ⊕ name ⺆...⺘         define function
+  ⊕ name [n] ⺆...⺘     define function (taking n args)
   巡 name / call name    call function
   至 / return            return early
+  macro name ⺆...⺘     rewrite the tokens after name as lines are read
+
+Standard Library (skip with --no-stdlib):
+  -rot, spin, dupd, swapd, nip2, dup3, drop3   Stack combinators
+  sq, recip, avg2, between?, clamp01, dist2d   Math helpers
+  N range, sum_n, product_n, max_n, min_n      Counted lists (x1..xn n)
 
 Labels & Jumps:
   :label                define a label
   goto @label           jump to label
 
-Arithmetic:
-  +, -, *, /     Basic arithmetic
-  pow, sqrt      Power and root
-  sin, cos, tan  Trigonometry
-
-Logic:
-  and, or, not   Boolean logic
-  =, <, >        Comparison
-
-Analog Mode:
-  a.201           Set mode: [-100, +100]
-  a.unit          Set mode: [-1.0, +1.0]
-  a.custom        Set custom range (min max --)
-  ≋ / a.status    Show current mode
-  ⊞ / a+          Analog add (saturates)
-  ⊟ / a-          Analog subtract
-  ⊠ / a*          Analog multiply
-  ⊘ / a/          Analog divide
-  a.sin, a.cos    Bounded trig
-  a.sigmoid       Neural activation
-  a.lerp          Linear interpolation
-  a.dot2, a.mag2  2D vector ops
+Expressions & Scoped Blocks:
+  =( 3*(2+4)^2 )                  Infix expression (also "..." evaluate)
+  record "P" "x"                  Define P_new, P?, .x and with_x
+  enum "S" "A/1"                  Define tagged constructors A, S.A and S?
+  schema "R" "k:int(1..)" ...     Define a map shape for v "R" validate
+  match_tag ⺆ A ⺆..⺘ _ ⺆..⺘ ⺘    Dispatch on a tagged value's variant
+  x ok / "e" err / x some / none  Results and options (unwrap_or to default)
+  map_ok ⺆...⺘ / and_then ⺆...⺘   Run a block on the Ok/Some value
+  try_prop                        Unwrap Ok/Some, or return Err/None early
+  parameterize "precision" 2 ⺆...⺘   Run a block with a parameter overridden
+  with_file "p" "a" ⺆...⺘          Open a handle for a block, closed after it
+  with_graph "g" 5 ⺆...⺘           Scratch graph deleted after the block
+  ns "lib" ⺆...⺘                  Keep the block's named graphs, FSMs and LFOs
+                                  apart ("::g" or global ⺆...⺘ for top-level)
+  spawn ⺆ ... ⺘                   Run a block on a worker thread (pushes job id)
+
+Files are confined with --allow-path DIR and --read-only.
+
+Operations (help <op> or :doc <op> for one, :doc <category> for a list):
 "#
     );
+    print!("{}", doc::summary(&doc::entries(interp)));
 }
 
 // ═══════════════════════════════════════════════════════════════════════
 // BENCHMARK SUITE
 // ═══════════════════════════════════════════════════════════════════════
 
-fn run_benchmark() -> Result<()> {
-    println!("🔢 WofLang Prime Benchmarking Suite");
-    println!("===================================\n");
-
-    let mut interp = Interpreter::new();
-    woflang_ops::register_all(&mut interp);
-
-    struct BenchTest {
-        name: &'static str,
-        number: u64,
-        expected_prime: bool,
-    }
-
-    let tests = [
-        BenchTest { name: "Small Prime 1", number: 97, expected_prime: true },
-        BenchTest { name: "Small Prime 2", number: 997, expected_prime: true },
-        BenchTest { name: "Small Prime 3", number: 9973, expected_prime: true },
-        BenchTest { name: "Medium Prime 1", number: 982_451_653, expected_prime: true },
-        BenchTest { name: "Medium Prime 2", number: 2_147_483_647, expected_prime: true },
-        BenchTest { name: "Large Prime 1", number: 1_000_000_007, expected_prime: true },
-        BenchTest { name: "Large Prime 2", number: 1_000_000_009, expected_prime: true },
-        BenchTest { name: "Large Prime 3", number: 10_000_000_019, expected_prime: true },
-        BenchTest { name: "Composite 1", number: 1_000_000_000, expected_prime: false },
-        BenchTest { name: "Composite 2", number: 999_999_999_999, expected_prime: false },
-        BenchTest { name: "Composite 3", number: 1_000_000_000_001, expected_prime: false },
-        BenchTest { name: "13-digit Prime", number: 1_000_000_000_039, expected_prime: true },
-        BenchTest { name: "12-digit Prime", number: 100_000_000_003, expected_prime: true },
-        BenchTest { name: "Carmichael 1", number: 561, expected_prime: false },
-        BenchTest { name: "Carmichael 2", number: 1105, expected_prime: false },
-        BenchTest { name: "Carmichael 3", number: 1729, expected_prime: false },
-        BenchTest { name: "Pseudoprime", number: 2047, expected_prime: false },
-    ];
-
-    println!(
-        "{:<20} {:<18} {:<12} {:<12} {:<12} {:<5}",
-        "Test Name", "Number", "Expected", "Result", "Time (µs)", "OK"
-    );
-    println!("{}", "-".repeat(80));
-
-    let mut total_time = 0.0;
-    let mut correct = 0;
-
-    for test in &tests {
-        print!("{:<20} {:<18} {:<12}", test.name, test.number, 
-               if test.expected_prime { "PRIME" } else { "COMPOSITE" });
-        std::io::Write::flush(&mut std::io::stdout()).ok();
-
-        interp.clear();
-        let command = format!("{} prime_check", test.number);
-
-        let start = Instant::now();
-        let exec_result = interp.exec_line(&command);
-        let duration = start.elapsed().as_secs_f64() * 1_000_000.0;
-
-        match exec_result {
-            Ok(()) => {
-                let result = interp
-                    .stack()
-                    .peek()
-                    .map(|v| v.as_bool())
-                    .unwrap_or(false);
-
-                let is_correct = result == test.expected_prime;
-                if is_correct {
-                    correct += 1;
-                }
-
-                println!(
-                    "{:<12} {:<12.2} {}",
-                    if result { "PRIME" } else { "COMPOSITE" },
-                    duration,
-                    if is_correct { "✓" } else { "✗" }
-                );
-            }
-            Err(e) => {
-                println!("{:<12} {:<12.2} ✗", "ERROR", 0.0);
-                eprintln!("    Error: {e}");
-            }
-        }
-
-        total_time += duration;
+fn run_benchmark(
+    suite: bench::Category,
+    config: &bench::BenchConfig,
+    format: bench::Format,
+    breakdown: bool,
+) -> Result<()> {
+    let workloads = bench::workloads(suite, config).map_err(|e| eyre::eyre!(e))?;
+    let results = bench::run(&mut bench::interpreter(), workloads);
+    match bench::render(&results, format, breakdown, &mut std::io::stdout().lock()) {
+        // Piping into `head` and the like closes stdout early.
+        Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
     }
-
-    println!("{}", "-".repeat(80));
-    println!("Total time: {:.2} µs", total_time);
-    println!("Average time: {:.2} µs", total_time / tests.len() as f64);
-    println!("Correct results: {}/{}", correct, tests.len());
-    println!(
-        "Success rate: {:.1}%",
-        100.0 * correct as f64 / tests.len() as f64
-    );
-    println!("\n🐺 Benchmark complete! 🐺\n");
-
-    Ok(())
 }
 
 // ═══════════════════════════════════════════════════════════════════════
 // TEST SUITE
 // ═══════════════════════════════════════════════════════════════════════
 
-fn run_tests() -> Result<()> {
+fn run_tests(coverage: bool) -> Result<()> {
     println!("🧪 Running COMPREHENSIVE WofLang Test Suite...\n");
 
     let mut interp = Interpreter::new();
     woflang_ops::register_all(&mut interp);
+    if coverage {
+        interp.enable_coverage();
+    }
 
     let mut passed = 0;
     let mut total = 0;
@@ -462,6 +876,7 @@ fn run_tests() -> Result<()> {
         test("Hash function", "42 hash", true);
         test("Base64 encode", "123 base64_encode", true);
         test("Diffie-Hellman demo", "diffie_hellman", true);
+        test("Constant-time compare", "\"abc\" \"abc\" ct_eq", true);
     }
 
     println!("\n=== 🧮 LOGIC OPERATIONS ===");
@@ -494,6 +909,11 @@ fn run_tests() -> Result<()> {
     }
     println!("\nSystem Status: 🟢 FULLY OPERATIONAL 🟢");
 
+    if let Some(cov) = interp.coverage() {
+        println!();
+        print!("{}", cov.summary(interp.registry().names(), interp.function_names()));
+    }
+
     Ok(())
 }
 
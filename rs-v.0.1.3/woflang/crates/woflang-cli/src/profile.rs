@@ -0,0 +1,107 @@
+//! Op profiling (`--profile-out`, and `:profile` in the REPL).
+//!
+//! The interpreter's op profile counts calls and wall time per registered
+//! operation and per user function. In the REPL, `:profile` starts it and
+//! then shows the report so far:
+//!
+//! ```text
+//! wof> :profile
+//! Profiling ops and functions; :profile again for the report
+//! wof> ⊕ sq ⺆ dup * ⺘ 3 sq 4 sq
+//! wof> :profile
+//! Operations:
+//!        calls      total ms     mean µs  name
+//!            2         0.004        1.80  *
+//!            2         0.002        1.10  dup
+//! Functions:
+//!        calls      total ms     mean µs  name
+//!            2         0.031       15.40  sq
+//! ```
+//!
+//! `--profile-out report.json` profiles a script (or a whole REPL
+//! session) and writes the same tables as JSON when it ends.
+
+use std::io;
+use std::path::Path;
+
+use woflang_runtime::{CallStats, Interpreter, OpProfile};
+
+/// Run `:profile` with `arg` (empty, `off` or `reset`), returning what to
+/// print.
+pub fn command(interp: &mut Interpreter, arg: &str) -> Option<String> {
+    let out = match arg {
+        "" => match interp.op_profile() {
+            Some(profile) => profile.to_string(),
+            None => {
+                interp.enable_op_profile();
+                "Profiling ops and functions; :profile again for the report\n".to_string()
+            }
+        },
+        "off" => match interp.take_op_profile() {
+            Some(profile) => format!("{profile}Profiling off\n"),
+            None => "Not profiling\n".to_string(),
+        },
+        "reset" => {
+            interp.enable_op_profile();
+            "Profile cleared\n".to_string()
+        }
+        _ => return None,
+    };
+    Some(out)
+}
+
+/// The profile as JSON: ops and functions, most total time first.
+pub fn to_json(profile: &OpProfile) -> serde_json::Value {
+    let rows = |entries: Vec<(&str, CallStats)>| -> Vec<serde_json::Value> {
+        entries
+            .into_iter()
+            .map(|(name, stats)| {
+                serde_json::json!({
+                    "name": name,
+                    "calls": stats.calls,
+                    "total_ms": stats.total.as_secs_f64() * 1e3,
+                    "mean_us": stats.mean().as_secs_f64() * 1e6,
+                })
+            })
+            .collect()
+    };
+    serde_json::json!({
+        "ops": rows(profile.ops()),
+        "functions": rows(profile.functions()),
+    })
+}
+
+/// Write the profile to `path` as pretty-printed JSON.
+pub fn write_json(profile: &OpProfile, path: &Path) -> io::Result<()> {
+    let file = std::fs::File::create(path)?;
+    serde_json::to_writer_pretty(file, &to_json(profile))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_command_starts_reports_and_stops() {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        interp.exec_line("⊕ sq ⺆ dup * ⺘").unwrap();
+
+        assert_eq!(command(&mut interp, "off").as_deref(), Some("Not profiling\n"));
+        assert!(command(&mut interp, "").unwrap().starts_with("Profiling ops"));
+        assert_eq!(command(&mut interp, "").as_deref(), Some("Nothing profiled yet\n"));
+        interp.exec_line("3 sq 4 sq").unwrap();
+        let report = command(&mut interp, "").unwrap();
+        assert!(report.contains("Functions:\n"), "{report}");
+
+        let doc = to_json(interp.op_profile().unwrap());
+        assert_eq!(doc["functions"][0]["name"], "sq");
+        assert_eq!(doc["functions"][0]["calls"], 2);
+        assert_eq!(doc["ops"].as_array().unwrap().len(), 2);
+
+        assert!(command(&mut interp, "off").unwrap().ends_with("Profiling off\n"));
+        assert!(interp.op_profile().is_none());
+        assert_eq!(command(&mut interp, "bogus"), None);
+    }
+}
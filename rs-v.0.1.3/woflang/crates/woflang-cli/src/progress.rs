@@ -0,0 +1,89 @@
+//! Terminal progress bar for long-running ops (chess training, sieving,
+//! fractal rendering, graph generation).
+//!
+//! The bar is drawn on stderr, redrawn at most every 100 ms, and erased
+//! when the work finishes, so op output on stdout is left untouched.
+//! Nothing is drawn when stderr is not a terminal.
+
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use woflang_runtime::{Progress, ProgressSink};
+
+/// Width of the bar itself, in cells.
+const WIDTH: usize = 30;
+
+/// Minimum time between redraws.
+const REDRAW: Duration = Duration::from_millis(100);
+
+/// A [`ProgressSink`] that draws a single-line progress bar.
+#[derive(Debug, Default)]
+pub struct Bar {
+    last_draw: Option<Instant>,
+}
+
+impl Bar {
+    /// A bar that draws only if stderr is a terminal.
+    pub fn for_stderr() -> Option<Self> {
+        io::stderr().is_terminal().then(Self::default)
+    }
+}
+
+impl ProgressSink for Bar {
+    fn report(&mut self, progress: &Progress) {
+        if progress.is_finished() {
+            if self.last_draw.take().is_some() {
+                clear_line();
+            }
+            return;
+        }
+        if self.last_draw.is_some_and(|t| t.elapsed() < REDRAW) {
+            return;
+        }
+        self.last_draw = Some(Instant::now());
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[K{}", render(progress));
+        let _ = stderr.flush();
+    }
+}
+
+/// Erase a partly drawn bar, e.g. before reporting an interrupted line.
+pub fn clear_line() {
+    let mut stderr = io::stderr().lock();
+    let _ = write!(stderr, "\r\x1b[K");
+    let _ = stderr.flush();
+}
+
+/// `[██████░░░░]  45% 450/1000 games`, or just the count when the total
+/// is unknown.
+fn render(progress: &Progress) -> String {
+    let Progress { done, total, message } = progress;
+    if *total == 0 {
+        return format!("{done} {message}");
+    }
+    let filled = (progress.fraction() * WIDTH as f64).round() as usize;
+    format!(
+        "[{}{}] {:>3.0}% {done}/{total} {message}",
+        "█".repeat(filled),
+        "░".repeat(WIDTH - filled),
+        progress.fraction() * 100.0
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(done: usize, total: usize) -> Progress {
+        Progress { done, total, message: "games".into() }
+    }
+
+    #[test]
+    fn renders_fraction_and_counts() {
+        let half = render(&report(15, 30));
+        assert!(half.starts_with(&format!("[{}{}]", "█".repeat(15), "░".repeat(15))));
+        assert!(half.ends_with(" 50% 15/30 games"));
+        assert_eq!(render(&report(7, 0)), "7 games");
+        assert!(render(&report(40, 30)).contains("100% 40/30"));
+    }
+}
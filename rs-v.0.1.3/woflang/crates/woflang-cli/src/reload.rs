@@ -0,0 +1,130 @@
+//! Reloading dynamic plugins in the REPL (`:reload-plugins` and
+//! `--watch-plugins`).
+//!
+//! `:reload-plugins` reloads every library in the plugin directory and
+//! loads any new ones. With `--watch-plugins`, the REPL looks at the
+//! directory before running each line and does the same for libraries
+//! that appeared or changed since it last looked, so rebuilding a plugin
+//! is enough:
+//!
+//! ```text
+//! $ woflang --plugins target/debug --watch-plugins
+//! wof> answer
+//! → 42
+//!                        # cargo build the plugin in another terminal
+//! wof> answer
+//! Reloaded answer 0.2.1 (1 ops)
+//! → 43
+//! ```
+//!
+//! A reload registers the new library's ops after removing the old
+//! one's; a library that fails to load leaves the old ops in place.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use woflang_runtime::{plugin_libraries, Interpreter, PluginLoader};
+
+/// The plugin directory as last seen: each library's modification time
+/// and size.
+pub struct PluginWatch {
+    dir: PathBuf,
+    seen: HashMap<PathBuf, (SystemTime, u64)>,
+    /// Whether the REPL checks for changes before each line.
+    pub enabled: bool,
+}
+
+impl PluginWatch {
+    /// Watch `dir`, taking the libraries there now as loaded.
+    pub fn new(dir: &Path, enabled: bool) -> Self {
+        let mut watch = Self { dir: dir.to_path_buf(), seen: HashMap::new(), enabled };
+        watch.changed();
+        watch
+    }
+
+    /// The directory being watched.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The libraries that are new or modified since the last look.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let Ok(libraries) = plugin_libraries(&self.dir) else {
+            return Vec::new();
+        };
+        let mut seen = HashMap::new();
+        let mut changed = Vec::new();
+        for path in libraries {
+            let Ok(meta) = fs::metadata(&path) else { continue };
+            let stamp = (meta.modified().unwrap_or(SystemTime::UNIX_EPOCH), meta.len());
+            if self.seen.get(&path) != Some(&stamp) {
+                changed.push(path.clone());
+            }
+            seen.insert(path, stamp);
+        }
+        self.seen = seen;
+        changed
+    }
+
+    /// Every library in the directory, taken as loaded from now on.
+    pub fn all(&mut self) -> Vec<PathBuf> {
+        self.changed();
+        let mut all: Vec<PathBuf> = self.seen.keys().cloned().collect();
+        all.sort();
+        all
+    }
+}
+
+/// Load or reload each library in `paths`: one message per library,
+/// `Err` for those that failed.
+pub fn apply(plugins: &mut PluginLoader, interp: &mut Interpreter, paths: &[PathBuf]) -> Vec<Result<String, String>> {
+    paths
+        .iter()
+        .map(|path| {
+            let reloading = plugins.plugins().any(|p| p.path == *path);
+            let verb = if reloading { "Reloaded" } else { "Loaded" };
+            plugins
+                .load_or_reload(path, interp)
+                .map(|info| format!("{verb} {} {} ({} ops)", info.name, info.version, info.ops.len()))
+                .map_err(|e| e.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn reports_new_and_modified_libraries() {
+        let dir = std::env::temp_dir().join(format!("woflang-reload-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let library = |name: &str| dir.join(format!("{name}{}", std::env::consts::DLL_SUFFIX));
+        fs::write(library("a"), "v1").unwrap();
+
+        let mut watch = PluginWatch::new(&dir, true);
+        assert!(watch.changed().is_empty());
+
+        fs::write(library("b"), "v1").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+        assert_eq!(watch.changed(), [library("b")]);
+
+        // Same size, later modification time
+        let file = fs::File::options().write(true).open(library("a")).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+        assert_eq!(watch.changed(), [library("a")]);
+        assert!(watch.changed().is_empty());
+
+        fs::remove_file(library("b")).unwrap();
+        assert_eq!(watch.all(), [library("a")]);
+
+        let mut plugins = PluginLoader::new();
+        let mut interp = Interpreter::new();
+        let results = apply(&mut plugins, &mut interp, &watch.all());
+        assert!(matches!(&results[..], [Err(e)] if e.contains("failed to load")), "{results:?}");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
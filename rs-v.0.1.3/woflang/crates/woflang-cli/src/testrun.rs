@@ -0,0 +1,153 @@
+//! Running test scripts (`woflang --run-tests <DIR>`).
+//!
+//! Every `*_test.wof` file under the directory runs in a fresh
+//! interpreter. Each `test_case "name" ⺆ ... ⺘` in it is reported as a
+//! line of its own, followed by its failed assertions; an error outside
+//! any test case fails the file. What a failing file printed is shown
+//! after its cases:
+//!
+//! ```text
+//! tests/math_test.wof
+//!   ok      addition
+//!   FAILED  halving
+//!           assertion failed: 0.4 is not within 0.001 of 0.5
+//!
+//! test result: FAILED. 1 passed; 1 failed (1 file)
+//! ```
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use woflang_core::Buffer;
+use woflang_runtime::{Interpreter, TestCase};
+
+/// Suffix of the files `--run-tests` runs.
+pub const SUFFIX: &str = "_test.wof";
+
+/// The outcome of one test file.
+#[derive(Debug, Clone)]
+pub struct FileResult {
+    /// The file run.
+    pub path: PathBuf,
+    /// Its test cases, in order.
+    pub cases: Vec<TestCase>,
+    /// The error that stopped the file outside a test case, if any.
+    pub error: Option<String>,
+    /// Everything the file printed.
+    pub output: String,
+}
+
+impl FileResult {
+    /// Whether the file ran to the end and all its cases passed.
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.cases.iter().all(TestCase::passed)
+    }
+}
+
+/// The test files under `dir`, recursively, sorted by path.
+pub fn discover(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.ends_with(SUFFIX)) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Run the test file at `path` in `interp`.
+pub fn run_file(interp: &mut Interpreter, path: &Path) -> FileResult {
+    let printed = Buffer::new();
+    interp.set_output(printed.clone());
+    let error = interp.exec_file(path).err().map(|e| e.to_string());
+    FileResult {
+        path: path.to_path_buf(),
+        cases: interp.take_test_report().cases().to_vec(),
+        error,
+        output: printed.contents(),
+    }
+}
+
+/// The report for `results`: each file's cases, then a summary line.
+pub fn report(results: &[FileResult]) -> String {
+    let mut out = String::new();
+    let (mut passed, mut failed) = (0, 0);
+    for file in results {
+        let _ = writeln!(out, "{}", file.path.display());
+        for case in &file.cases {
+            if case.passed() {
+                passed += 1;
+                let _ = writeln!(out, "  ok      {}", case.name);
+                continue;
+            }
+            failed += 1;
+            let _ = writeln!(out, "  FAILED  {}", case.name);
+            for failure in case.failures.iter().chain(&case.error) {
+                let _ = writeln!(out, "          {failure}");
+            }
+        }
+        if let Some(error) = &file.error {
+            failed += 1;
+            let _ = writeln!(out, "  FAILED  (file)\n          {error}");
+        }
+        if !file.passed() && !file.output.is_empty() {
+            let _ = writeln!(out, "  output:");
+            for line in file.output.lines() {
+                let _ = writeln!(out, "    {line}");
+            }
+        }
+    }
+    let status = if failed == 0 { "ok" } else { "FAILED" };
+    let files = if results.len() == 1 { "file" } else { "files" };
+    let _ = writeln!(out, "\ntest result: {status}. {passed} passed; {failed} failed ({} {files})", results.len());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interpreter() -> Interpreter {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        woflang_plugins::register_all(&mut interp);
+        interp
+    }
+
+    #[test]
+    fn runs_test_files_and_reports_each_case() {
+        let dir = std::env::temp_dir().join(format!("woflang-run-tests-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(
+            dir.join("math_test.wof"),
+            "test_case \"addition\" ⺆ 2 3 + 5 assert_eq ⺘\n\
+             test_case \"halving\" ⺆ \"halving\" print 0.4 0.5 0.001 assert_near 1 assert_stack_depth ⺘\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("nested/broken_test.wof"), "test_case \"fine\" ⺆ 1 assert ⺘\n1 0 /\n").unwrap();
+        std::fs::write(dir.join("helper.wof"), "1 0 /\n").unwrap();
+
+        let files = discover(&dir).unwrap();
+        assert_eq!(files, [dir.join("math_test.wof"), dir.join("nested/broken_test.wof")]);
+
+        let results: Vec<FileResult> = files.iter().map(|path| run_file(&mut interpreter(), path)).collect();
+        assert!(!results[0].passed() && !results[1].passed());
+        assert_eq!(results[0].cases[1].failures.len(), 2);
+        assert_eq!(results[1].cases.len(), 1);
+
+        let report = report(&results);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(report.contains("  ok      addition\n  FAILED  halving\n"), "{report}");
+        assert!(report.contains("          assertion failed: 0.4 is not within 0.001 of 0.5\n"), "{report}");
+        assert!(report.contains("  output:\n    halving\n"), "{report}");
+        assert!(report.contains("  ok      fine\n  FAILED  (file)\n          error at 2:5: division by zero"), "{report}");
+        assert!(report.ends_with("\ntest result: FAILED. 2 passed; 2 failed (2 files)\n"), "{report}");
+    }
+}
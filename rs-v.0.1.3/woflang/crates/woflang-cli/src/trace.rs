@@ -0,0 +1,437 @@
+//! Token-by-token execution traces (`woflang trace`).
+//!
+//! The script runs under a debugger frontend that steps through every
+//! token and records the token, where it is, the stack and the running
+//! calls and loops before it runs. The trace is printed as one line per
+//! token, or with `--html` written as a standalone page that animates
+//! the stack as the highlighted token moves through the source:
+//!
+//! ```text
+//! woflang trace fib.wof --html fib.html
+//! ```
+//!
+//! Tokens run from outside the script, such as the bodies of standard
+//! library functions, are traced but not highlighted. Recording stops
+//! the script after `--max-steps` tokens, so a runaway loop still
+//! yields a trace.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use serde_json::{json, Value};
+use woflang_core::{Buffer, InterpreterContext, WofValue};
+use woflang_runtime::{DebugAction, DebugFrontend, Interpreter, Pause};
+
+/// Tokens recorded before the script is stopped, by default.
+pub const DEFAULT_MAX_STEPS: usize = 10_000;
+
+/// The state before one token ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// The token about to run.
+    pub token: String,
+    /// Where the token is in the script, as a line and a 1-based column,
+    /// or `None` if it comes from elsewhere.
+    pub at: Option<(u32, u32)>,
+    /// The stack, bottom first.
+    pub stack: Vec<Slot>,
+    /// The running calls and loops, outermost first.
+    pub frames: Vec<String>,
+    /// How many bytes the script had printed.
+    pub printed: usize,
+}
+
+/// A stack value as shown in a trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slot {
+    /// The value as printed.
+    pub text: String,
+    /// Its type name.
+    pub kind: String,
+}
+
+impl Slot {
+    fn new(value: &WofValue) -> Self {
+        Self { text: value.to_string(), kind: value.value_type().to_string() }
+    }
+}
+
+/// A recorded run of a script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    /// The script's name, for headings.
+    pub name: String,
+    /// The script's text.
+    pub source: String,
+    /// One step per token run.
+    pub steps: Vec<Step>,
+    /// The stack when the script ended.
+    pub stack: Vec<Slot>,
+    /// Everything the script printed.
+    pub output: String,
+    /// The error the script ended with, if any.
+    pub error: Option<String>,
+    /// Whether recording stopped the script at the step limit.
+    pub truncated: bool,
+}
+
+/// The debugger frontend recording steps into a shared list.
+struct Recorder {
+    source: Vec<Vec<char>>,
+    steps: Arc<Mutex<Vec<Step>>>,
+    printed: Buffer,
+    max_steps: usize,
+}
+
+impl Recorder {
+    /// Where `pause`'s token is in the script, if its text is there.
+    fn locate(&self, pause: &Pause) -> Option<(u32, u32)> {
+        let (line, column) = (pause.span.line(), pause.span.column());
+        let chars = self.source.get(usize::try_from(line).ok()?.checked_sub(1)?)?;
+        let start = usize::try_from(column).ok()?.checked_sub(1)?;
+        let len = pause.token.chars().count();
+        let found = chars.get(start..start + len)?;
+        found.iter().copied().eq(pause.token.chars()).then_some((line, column))
+    }
+}
+
+impl DebugFrontend for Recorder {
+    fn paused(&mut self, interp: &mut Interpreter, pause: &Pause) -> DebugAction {
+        let mut steps = self.steps.lock().unwrap_or_else(PoisonError::into_inner);
+        if steps.len() >= self.max_steps {
+            return DebugAction::Abort;
+        }
+        steps.push(Step {
+            token: pause.token.clone(),
+            at: self.locate(pause),
+            stack: interp.stack().as_slice().iter().map(Slot::new).collect(),
+            frames: interp.frames().iter().map(ToString::to_string).collect(),
+            printed: self.printed.contents().len(),
+        });
+        DebugAction::Step
+    }
+}
+
+/// Run `source` in `interp`, recording at most `max_steps` tokens. The
+/// interpreter's debugger and output are replaced.
+pub fn record(interp: &mut Interpreter, name: &str, source: &str, max_steps: usize) -> Trace {
+    let steps = Arc::new(Mutex::new(Vec::new()));
+    let printed = Buffer::new();
+    interp.set_output(printed.clone());
+    interp.attach_debugger(Recorder {
+        source: source.lines().map(|line| line.chars().collect()).collect(),
+        steps: Arc::clone(&steps),
+        printed: printed.clone(),
+        max_steps,
+    });
+    interp.step();
+    let result = interp.exec_source(source);
+    interp.detach_debugger();
+
+    let steps = std::mem::take(&mut *steps.lock().unwrap_or_else(PoisonError::into_inner));
+    Trace {
+        name: name.to_string(),
+        source: source.to_string(),
+        truncated: result.is_err() && steps.len() >= max_steps,
+        steps,
+        stack: interp.stack().as_slice().iter().map(Slot::new).collect(),
+        output: printed.contents(),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+/// Run the script at `path` in `interp` and record it.
+pub fn record_file(interp: &mut Interpreter, path: &Path, max_steps: usize) -> std::io::Result<Trace> {
+    let source = std::fs::read_to_string(path)?;
+    Ok(record(interp, &path.display().to_string(), &source, max_steps))
+}
+
+/// The stack as one line, bottom first.
+fn stack_line(stack: &[Slot]) -> String {
+    let slots: Vec<String> = stack
+        .iter()
+        .map(|slot| if slot.kind == "string" { format!("{:?}", slot.text) } else { slot.text.clone() })
+        .collect();
+    format!("[{}]", slots.join(" "))
+}
+
+/// One line per token: where it is, the token, and the stack after it.
+pub fn text(trace: &Trace) -> String {
+    let mut out = String::new();
+    for (i, step) in trace.steps.iter().enumerate() {
+        let after = trace.steps.get(i + 1).map_or(&trace.stack, |next| &next.stack);
+        let at = step.at.map_or_else(|| "-".to_string(), |(line, column)| format!("{line}:{column}"));
+        let _ = writeln!(out, "{:>5}  {at:<7} {:<12} {}", i + 1, step.token, stack_line(after));
+    }
+    if !trace.output.is_empty() {
+        let _ = writeln!(out, "Output:\n{}", trace.output.trim_end());
+    }
+    if trace.truncated {
+        let _ = writeln!(out, "Stopped after {} steps", trace.steps.len());
+    } else if let Some(error) = &trace.error {
+        let _ = writeln!(out, "Error: {error}");
+    }
+    out
+}
+
+/// The trace as the JSON the page reads.
+fn data(trace: &Trace) -> Value {
+    let slots = |stack: &[Slot]| -> Vec<Value> { stack.iter().map(|s| json!([s.text, s.kind])).collect() };
+    let steps: Vec<Value> = trace
+        .steps
+        .iter()
+        .map(|step| {
+            json!({
+                "token": step.token,
+                "at": step.at.map(<[u32; 2]>::from),
+                "stack": slots(&step.stack),
+                "frames": step.frames,
+                "printed": step.printed,
+            })
+        })
+        .collect();
+    json!({
+        "name": trace.name,
+        "source": trace.source,
+        "steps": steps,
+        "stack": slots(&trace.stack),
+        "output": trace.output,
+        "error": trace.error,
+        "truncated": trace.truncated,
+    })
+}
+
+/// Render the trace as a standalone HTML page.
+pub fn html(trace: &Trace) -> String {
+    // Inside <script>, "</" could end the element early.
+    let data = data(trace).to_string().replace("</", "<\\/");
+    PAGE.replace("{{title}}", &escape(&trace.name)).replace("{{data}}", &data)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// The page template: `{{title}}` and `{{data}}` are filled in.
+const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{{title}} — woflang trace</title>
+<style>
+body { font-family: sans-serif; margin: 1em 2em; color: #222; }
+h1 { font-size: 1.3em; margin: 0 0 0.5em; }
+#controls { display: flex; gap: 0.4em; align-items: center; margin-bottom: 0.8em; }
+#controls button { font-size: 1em; min-width: 2.4em; }
+#slider { flex: 1; }
+#main { display: flex; gap: 1.5em; align-items: flex-start; }
+#source { flex: 3; font-family: monospace; white-space: pre; border: 1px solid #ccc; padding: 0.5em 0; overflow-x: auto; }
+.line { padding: 0 0.8em 0 0; }
+.line .no { display: inline-block; width: 3em; text-align: right; padding-right: 0.8em; color: #999; user-select: none; }
+.line.current { background: #fff6d5; }
+mark { background: #ffd54f; border-radius: 2px; }
+#side { flex: 2; min-width: 16em; }
+#stack { display: flex; flex-direction: column-reverse; gap: 3px; min-height: 2em; }
+.slot { font-family: monospace; border: 1px solid #999; border-radius: 3px; padding: 0.25em 0.6em; background: #f4f6fb; display: flex; justify-content: space-between; transition: background 0.3s; }
+.slot.new { background: #c8e6c9; border-color: #43a047; }
+.slot .kind { color: #888; font-size: 0.8em; padding-left: 1em; }
+.empty { color: #999; font-style: italic; }
+h2 { font-size: 1em; margin: 1em 0 0.3em; }
+#token { font-family: monospace; font-size: 1.2em; }
+#frames, #output { font-family: monospace; white-space: pre-wrap; }
+#output { border: 1px solid #ccc; padding: 0.4em; min-height: 1.5em; max-height: 12em; overflow-y: auto; }
+#error { color: #b71c1c; }
+</style>
+</head>
+<body>
+<h1>{{title}}</h1>
+<div id="controls">
+<button id="first" title="Start (Home)">⏮</button>
+<button id="prev" title="Back (←)">◀</button>
+<button id="play" title="Play (space)">▶</button>
+<button id="next" title="Forward (→)">▶▶</button>
+<button id="last" title="End (End)">⏭</button>
+<input id="slider" type="range" min="0" value="0">
+<select id="speed" title="Steps per second">
+<option value="1000">1/s</option><option value="400" selected>2.5/s</option>
+<option value="150">7/s</option><option value="40">25/s</option>
+</select>
+<span id="counter"></span>
+</div>
+<div id="main">
+<div id="source"></div>
+<div id="side">
+<div><span id="caption"></span> <span id="token"></span></div>
+<h2>Stack (top first)</h2>
+<div id="stack"></div>
+<h2>Running</h2>
+<div id="frames"></div>
+<h2>Output</h2>
+<div id="output"></div>
+<p id="error"></p>
+</div>
+</div>
+<script>
+const trace = {{data}};
+const lines = trace.source.split("\n");
+const count = trace.steps.length;
+const el = id => document.getElementById(id);
+let state = 0, timer = null;
+
+// State 0 is before the first token; state i is just after token i - 1.
+function stackAt(i) { return i < count ? trace.steps[i].stack : trace.stack; }
+function printedAt(i) { return i < count ? trace.output.slice(0, byteToChar(trace.steps[i].printed)) : trace.output; }
+function byteToChar(bytes) { return new TextDecoder().decode(new TextEncoder().encode(trace.output).slice(0, bytes)).length; }
+function show(value) { return value[1] === "string" ? JSON.stringify(value[0]) : value[0]; }
+
+function renderSource(at, token) {
+  const src = el("source");
+  src.textContent = "";
+  lines.forEach((text, i) => {
+    const div = document.createElement("div");
+    div.className = "line";
+    const no = document.createElement("span");
+    no.className = "no";
+    no.textContent = i + 1;
+    div.appendChild(no);
+    const chars = Array.from(text);
+    if (at && at[0] === i + 1) {
+      div.className += " current";
+      const start = at[1] - 1, end = start + Array.from(token).length;
+      div.appendChild(document.createTextNode(chars.slice(0, start).join("")));
+      const mark = document.createElement("mark");
+      mark.textContent = chars.slice(start, end).join("");
+      div.appendChild(mark);
+      div.appendChild(document.createTextNode(chars.slice(end).join("")));
+    } else {
+      div.appendChild(document.createTextNode(text));
+    }
+    src.appendChild(div);
+  });
+  const current = src.querySelector(".current");
+  if (current) current.scrollIntoView({ block: "nearest" });
+}
+
+function render() {
+  const step = state > 0 ? trace.steps[state - 1] : null;
+  renderSource(step && step.at, step ? step.token : "");
+  el("caption").textContent = step ? "Ran" : (count ? "Start" : "Nothing ran");
+  el("token").textContent = step ? step.token + (step.at ? "" : "  (outside the script)") : "";
+
+  const before = state > 0 ? stackAt(state - 1) : [];
+  const stack = stackAt(state);
+  let same = 0;
+  while (same < before.length && same < stack.length && before[same][0] === stack[same][0] && before[same][1] === stack[same][1]) same++;
+  const box = el("stack");
+  box.textContent = "";
+  if (!stack.length) {
+    box.innerHTML = '<span class="empty">empty</span>';
+  }
+  stack.forEach((value, i) => {
+    const slot = document.createElement("div");
+    slot.className = i >= same && state > 0 ? "slot new" : "slot";
+    const text = document.createElement("span");
+    text.textContent = show(value);
+    const kind = document.createElement("span");
+    kind.className = "kind";
+    kind.textContent = value[1];
+    slot.append(text, kind);
+    box.appendChild(slot);
+  });
+
+  const frames = state < count ? trace.steps[state].frames : [];
+  el("frames").textContent = frames.length ? frames.join("\n") : "(top level)";
+  el("output").textContent = printedAt(state);
+  let ending = "";
+  if (state === count) {
+    if (trace.truncated) ending = "Stopped after " + count + " steps.";
+    else if (trace.error) ending = "Error: " + trace.error;
+  }
+  el("error").textContent = ending;
+  el("slider").value = state;
+  el("counter").textContent = "step " + state + " / " + count;
+}
+
+function go(i) { state = Math.max(0, Math.min(count, i)); render(); if (state === count) pause(); }
+function pause() { clearInterval(timer); timer = null; el("play").textContent = "▶"; }
+function play() {
+  if (timer) return pause();
+  if (state === count) state = 0;
+  el("play").textContent = "⏸";
+  timer = setInterval(() => go(state + 1), Number(el("speed").value));
+}
+
+el("slider").max = count;
+el("first").onclick = () => go(0);
+el("prev").onclick = () => go(state - 1);
+el("next").onclick = () => go(state + 1);
+el("last").onclick = () => go(count);
+el("play").onclick = play;
+el("slider").oninput = e => go(Number(e.target.value));
+el("speed").onchange = () => { if (timer) { pause(); play(); } };
+document.addEventListener("keydown", e => {
+  if (e.target.tagName === "INPUT" || e.target.tagName === "SELECT") return;
+  if (e.key === "ArrowRight") go(state + 1);
+  else if (e.key === "ArrowLeft") go(state - 1);
+  else if (e.key === "Home") go(0);
+  else if (e.key === "End") go(count);
+  else if (e.key === " ") { e.preventDefault(); play(); }
+});
+render();
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interpreter() -> Interpreter {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        interp
+    }
+
+    #[test]
+    fn records_each_token_with_the_stack_before_it() {
+        let mut interp = interpreter();
+        let source = "⊕ sq ⺆ dup * ⺘\n\"hi\" print\n3 sq\n";
+        let trace = record(&mut interp, "sq.wof", source, DEFAULT_MAX_STEPS);
+        let tokens: Vec<&str> = trace.steps.iter().map(|s| s.token.as_str()).collect();
+        assert_eq!(tokens, ["⊕", "\"hi\"", "print", "3", "sq", "dup", "*", "至"]);
+        assert_eq!(trace.steps[5].at, Some((1, 8)));
+        assert_eq!(trace.steps[5].frames, ["call sq"]);
+        assert_eq!(trace.steps[6].stack, vec![Slot::new(&WofValue::integer(3)); 2]);
+        // The return at the end of the body is not written in the script.
+        assert_eq!(trace.steps[7].at, None);
+        assert_eq!(trace.steps[3].printed, 3);
+        assert_eq!(trace.stack, [Slot::new(&WofValue::integer(9))]);
+        assert_eq!((trace.output.as_str(), &trace.error, trace.truncated), ("hi\n", &None, false));
+
+        let text = text(&trace);
+        assert!(text.contains("    2  2:1     \"hi\"         [\"hi\"]"), "{text}");
+        assert!(text.contains("    8  -       至            [9]"), "{text}");
+
+        let page = html(&trace);
+        assert!(page.contains("<title>sq.wof — woflang trace</title>"));
+        assert!(page.contains(r#""token":"dup""#));
+        assert!(!page.contains("{{"));
+    }
+
+    #[test]
+    fn stops_at_the_step_limit_and_keeps_errors() {
+        let mut interp = interpreter();
+        let trace = record(&mut interp, "loop.wof", "0 ⟳ ⺆ 1 + ⺘", 50);
+        assert_eq!(trace.steps.len(), 50);
+        assert!(trace.truncated);
+        assert!(text(&trace).ends_with("Stopped after 50 steps\n"));
+
+        let mut interp = interpreter();
+        let trace = record(&mut interp, "bad.wof", "1 drop drop\n\"</script>\" 2", DEFAULT_MAX_STEPS);
+        assert!(!trace.truncated);
+        assert!(trace.error.is_some());
+        assert!(!html(&trace).contains("</script>\""));
+    }
+}
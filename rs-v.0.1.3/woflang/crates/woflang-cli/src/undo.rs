@@ -0,0 +1,80 @@
+//! Undo in the REPL (`undo`, or `:undo`).
+//!
+//! Before each line runs, the stack and variables are saved; `undo`
+//! puts back the ones from before the last line, and again for the line
+//! before that, up to [`LIMIT`] lines back:
+//!
+//! ```text
+//! wof> 3 4
+//! → 4
+//! wof> * 2 /
+//! → 6.0
+//! wof> undo
+//! Undone: Stack[2]: 3, 4
+//! ```
+//!
+//! Functions defined by an undone line stay defined. Switching workspace
+//! forgets the history.
+
+use woflang_runtime::{Interpreter, Snapshot};
+
+/// Lines that can be undone.
+pub const LIMIT: usize = 100;
+
+/// Snapshots from before each recent line, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    snapshots: Vec<Snapshot>,
+}
+
+impl History {
+    /// Save the state before running a line.
+    pub fn record(&mut self, interp: &Interpreter) {
+        if self.snapshots.len() == LIMIT {
+            self.snapshots.remove(0);
+        }
+        self.snapshots.push(interp.snapshot());
+    }
+
+    /// Go back to before the last recorded line, returning what to print.
+    pub fn undo(&mut self, interp: &mut Interpreter) -> String {
+        match self.snapshots.pop() {
+            Some(snapshot) => {
+                interp.restore(&snapshot);
+                format!("Undone: {}\n", snapshot.stack())
+            }
+            None => "Nothing to undo\n".to_string(),
+        }
+    }
+
+    /// Forget every saved line.
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use woflang_core::InterpreterContext;
+
+    #[test]
+    fn undo_walks_back_line_by_line() {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        let mut history = History::default();
+        for line in ["3 4", "* 字 x", "x 2 /"] {
+            history.record(&interp);
+            interp.exec_line(line).unwrap();
+        }
+
+        assert_eq!(history.undo(&mut interp), "Undone: Stack[0]: (empty)\n");
+        assert_eq!(interp.get_var("x").unwrap().as_integer().unwrap(), 12);
+        history.undo(&mut interp);
+        assert!(!interp.has_var("x"));
+        assert_eq!(interp.stack().len(), 2);
+        history.undo(&mut interp);
+        assert!(interp.stack().is_empty());
+        assert_eq!(history.undo(&mut interp), "Nothing to undo\n");
+    }
+}
@@ -0,0 +1,105 @@
+//! Watch expressions (`:watch <expr>` in the REPL).
+//!
+//! After every executed line, each watched expression runs against a
+//! [fork](Interpreter::fork) of the session, so it sees the current
+//! stack, variables and functions without changing them. The results are
+//! shown in one status line:
+//!
+//! ```text
+//! wof> :watch depth
+//! wof> :watch dup entropy
+//! wof> 1 2 2 3
+//! → 3
+//! [watch] depth = 4 | dup entropy = 1.5
+//! ```
+
+use woflang_core::InterpreterContext;
+use woflang_runtime::Interpreter;
+
+/// The REPL's watched expressions, in the order they were added.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Watches {
+    exprs: Vec<String>,
+}
+
+impl Watches {
+    /// Watch `expr`, ignoring duplicates.
+    pub fn add(&mut self, expr: &str) {
+        if !self.exprs.iter().any(|e| e == expr) {
+            self.exprs.push(expr.to_string());
+        }
+    }
+
+    /// Stop watching the `n`th expression (1-based), returning it.
+    pub fn remove(&mut self, n: usize) -> Option<String> {
+        (1..=self.exprs.len()).contains(&n).then(|| self.exprs.remove(n - 1))
+    }
+
+    /// Stop watching everything.
+    pub fn clear(&mut self) {
+        self.exprs.clear();
+    }
+
+    /// The watched expressions, numbered from 1 as `remove` expects.
+    pub fn list(&self) -> String {
+        if self.exprs.is_empty() {
+            return "No watches. Add one with :watch <expr>\n".to_string();
+        }
+        self.exprs.iter().enumerate().map(|(i, e)| format!("  {}. {e}\n", i + 1)).collect()
+    }
+
+    /// Evaluate every watch against a fork of `interp` and format the
+    /// status line, or `None` if nothing is watched.
+    pub fn status(&self, interp: &Interpreter) -> Option<String> {
+        if self.exprs.is_empty() {
+            return None;
+        }
+        let results: Vec<String> = self
+            .exprs
+            .iter()
+            .map(|expr| format!("{expr} = {}", evaluate(interp, expr)))
+            .collect();
+        Some(format!("[watch] {}", results.join(" | ")))
+    }
+}
+
+/// The top of the stack after running `expr` in a fork of `interp`.
+fn evaluate(interp: &Interpreter, expr: &str) -> String {
+    let mut fork = interp.fork();
+    fork.debug = false;
+    match fork.exec_line(expr) {
+        Ok(()) => fork.stack().peek().map_or_else(|_| "(empty)".to_string(), ToString::to_string),
+        Err(e) => format!("error: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_against_a_fork() {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        interp.exec_line("3 4").unwrap();
+
+        let mut watches = Watches::default();
+        assert_eq!(watches.status(&interp), None);
+        watches.add("depth");
+        watches.add("dup *");
+        watches.add("depth");
+        watches.add("drop drop drop");
+        assert_eq!(
+            watches.status(&interp).unwrap(),
+            "[watch] depth = 2 | dup * = 16 | drop drop drop = error: \
+             error at 1:11: stack underflow: expected at least 1 value(s), found 0"
+        );
+        assert_eq!(interp.stack().len(), 2);
+
+        assert_eq!(watches.remove(3).as_deref(), Some("drop drop drop"));
+        assert_eq!(watches.remove(3), None);
+        assert_eq!(watches.list(), "  1. depth\n  2. dup *\n");
+        watches.clear();
+        assert!(watches.list().starts_with("No watches"));
+    }
+}
@@ -0,0 +1,115 @@
+//! Named workspaces in the REPL (`:workspace new|switch|list`).
+//!
+//! Each workspace has its own stack, variables and functions; all of
+//! them share the session's registry, keybindings and settings. Parked
+//! workspaces are kept as interpreter forks whose program state is
+//! swapped with the live interpreter on `switch`:
+//!
+//! ```text
+//! wof> 1 2 3
+//! wof> :workspace new physics     # fresh stack, stdlib functions only
+//! wof> :workspace switch main     # back to 1 2 3
+//! ```
+
+use std::collections::BTreeMap;
+
+use woflang_core::InterpreterContext;
+use woflang_runtime::Interpreter;
+
+/// Name of the workspace the REPL starts in.
+pub const MAIN: &str = "main";
+
+/// The REPL's workspaces. The live one's state is in the interpreter.
+pub struct Workspaces {
+    current: String,
+    parked: BTreeMap<String, Interpreter>,
+    /// Program state new workspaces start from.
+    template: Interpreter,
+}
+
+impl Workspaces {
+    /// Start in [`MAIN`]; new workspaces begin with the functions and
+    /// variables `interp` has now and an empty stack.
+    pub fn new(interp: &Interpreter) -> Self {
+        let mut template = interp.fork();
+        template.stack_mut().clear();
+        Self { current: MAIN.to_string(), parked: BTreeMap::new(), template }
+    }
+
+    /// The live workspace's name.
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    /// Create workspace `name` and switch `interp` to it.
+    pub fn create(&mut self, interp: &mut Interpreter, name: &str) -> Result<(), String> {
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(format!("invalid workspace name '{name}'"));
+        }
+        if name == self.current || self.parked.contains_key(name) {
+            return Err(format!("workspace '{name}' already exists"));
+        }
+        self.parked.insert(name.to_string(), self.template.fork());
+        self.switch(interp, name)
+    }
+
+    /// Park the live workspace and make `name` live in `interp`.
+    pub fn switch(&mut self, interp: &mut Interpreter, name: &str) -> Result<(), String> {
+        if name == self.current {
+            return Ok(());
+        }
+        let mut incoming = self
+            .parked
+            .remove(name)
+            .ok_or_else(|| format!("no workspace '{name}' (see :workspace list)"))?;
+        interp.swap_program_state(&mut incoming);
+        let previous = std::mem::replace(&mut self.current, name.to_string());
+        self.parked.insert(previous, incoming);
+        Ok(())
+    }
+
+    /// One line per workspace, the live one marked with `*`.
+    pub fn list(&self, interp: &Interpreter) -> String {
+        let mut all: Vec<(&str, usize)> =
+            self.parked.iter().map(|(name, ws)| (name.as_str(), ws.stack().len())).collect();
+        all.push((&self.current, interp.stack().len()));
+        all.sort_unstable();
+        all.into_iter()
+            .map(|(name, depth)| {
+                let mark = if name == self.current { '*' } else { ' ' };
+                format!("{mark} {name}  ({depth} on stack)\n")
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspaces_keep_separate_state() {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        interp.exec_line("⊕ sq ⺆ dup * ⺘").unwrap();
+        let mut spaces = Workspaces::new(&interp);
+        interp.exec_line("1 2 3 7 字 x ⊕ cube ⺆ dup sq * ⺘").unwrap();
+
+        spaces.create(&mut interp, "physics").unwrap();
+        assert_eq!(spaces.current(), "physics");
+        assert!(interp.stack().is_empty());
+        interp.exec_line("4 sq").unwrap();
+        assert!(interp.get_var("x").is_err());
+        assert!(!interp.has_function("cube"));
+
+        spaces.switch(&mut interp, MAIN).unwrap();
+        assert_eq!(interp.stack().len(), 3);
+        interp.exec_line("x cube").unwrap();
+        assert_eq!(interp.stack().peek().unwrap().as_integer().unwrap(), 343);
+        assert_eq!(spaces.list(&interp), "* main  (4 on stack)\n  physics  (1 on stack)\n");
+
+        assert!(spaces.create(&mut interp, "physics").is_err());
+        assert!(spaces.switch(&mut interp, "music").is_err());
+        assert_eq!(spaces.current(), MAIN);
+    }
+}
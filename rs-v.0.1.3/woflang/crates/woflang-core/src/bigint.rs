@@ -0,0 +1,360 @@
+//! Arbitrary-precision integers.
+//!
+//! [`BigInt`] wraps [`num_bigint::BigInt`] with the operations number
+//! theory on the stack needs, in the shape the ops want them: division
+//! and modular arithmetic return `None` for a zero divisor or modulus
+//! rather than panicking, and [`BigInt::is_probable_prime`] adds the
+//! Miller–Rabin test `num-bigint` leaves out.
+//!
+//! ```
+//! use woflang_core::BigInt;
+//!
+//! let p: BigInt = "170141183460469231731687303715884105727".parse().unwrap();
+//! assert!(p.is_probable_prime());
+//! let q = &p * &p;
+//! assert_eq!(q.to_string(), "28948022309329048855892746252171976962977213799489202546401021394546514198529");
+//! ```
+
+use core::fmt;
+use core::ops::{Add, Mul, Neg, Sub};
+use core::str::FromStr;
+
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::WofError;
+
+/// An arbitrary-precision signed integer.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BigInt(num_bigint::BigInt);
+
+/// Witnesses making Miller–Rabin exact below 3.3·10²⁴; larger numbers
+/// also get [`EXTRA_WITNESSES`].
+const WITNESSES: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Further witnesses for numbers beyond the deterministic range.
+const EXTRA_WITNESSES: [u32; 8] = [41, 43, 47, 53, 59, 61, 67, 71];
+
+impl BigInt {
+    /// Zero.
+    #[must_use]
+    pub const fn zero() -> Self {
+        Self(num_bigint::BigInt::ZERO)
+    }
+
+    /// One.
+    #[must_use]
+    pub fn one() -> Self {
+        Self(num_bigint::BigInt::one())
+    }
+
+    /// True for zero.
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// True below zero.
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.0.is_negative()
+    }
+
+    /// True for even numbers, including zero.
+    #[must_use]
+    pub fn is_even(&self) -> bool {
+        self.0.is_even()
+    }
+
+    /// The absolute value.
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Number of bits in the magnitude.
+    #[must_use]
+    pub fn bits(&self) -> u64 {
+        self.0.bits()
+    }
+
+    /// Bit `i` of the magnitude.
+    #[must_use]
+    pub fn bit(&self, i: u64) -> bool {
+        self.0.magnitude().bit(i)
+    }
+
+    /// The value as an `i64`, if it fits.
+    #[must_use]
+    pub fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+
+    /// The nearest `f64`, or an infinity past its range.
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        let overflow = if self.is_negative() { f64::NEG_INFINITY } else { f64::INFINITY };
+        self.0.to_f64().unwrap_or(overflow)
+    }
+
+    /// Truncating division: the quotient rounds toward zero and the
+    /// remainder takes the dividend's sign. `None` for a zero divisor.
+    #[must_use]
+    pub fn div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let (q, r) = self.0.div_rem(&divisor.0);
+        Some((Self(q), Self(r)))
+    }
+
+    /// The remainder in `0..|m|`. `None` for a zero modulus.
+    #[must_use]
+    pub fn rem_euclid(&self, m: &Self) -> Option<Self> {
+        (!m.is_zero()).then(|| Self(self.0.mod_floor(&m.0.abs())))
+    }
+
+    /// `self^exp mod m`, in `0..m`. `None` unless `exp ≥ 0` and `m > 0`.
+    #[must_use]
+    pub fn pow_mod(&self, exp: &Self, m: &Self) -> Option<Self> {
+        (!exp.is_negative() && m.0.is_positive()).then(|| Self(self.0.modpow(&exp.0, &m.0)))
+    }
+
+    /// The non-negative greatest common divisor.
+    #[must_use]
+    pub fn gcd(&self, other: &Self) -> Self {
+        Self(self.0.gcd(&other.0))
+    }
+
+    /// The non-negative least common multiple; zero if either is zero.
+    #[must_use]
+    pub fn lcm(&self, other: &Self) -> Self {
+        Self(self.0.lcm(&other.0))
+    }
+
+    /// `(g, x, y)` with `g = gcd(a, b) = a·x + b·y`.
+    #[must_use]
+    pub fn extended_gcd(&self, other: &Self) -> (Self, Self, Self) {
+        let egcd = self.0.extended_gcd(&other.0);
+        (Self(egcd.gcd), Self(egcd.x), Self(egcd.y))
+    }
+
+    /// The inverse of `self` modulo `m > 0`, in `0..m`, if they are coprime.
+    #[must_use]
+    pub fn mod_inv(&self, m: &Self) -> Option<Self> {
+        if !m.0.is_positive() {
+            return None;
+        }
+        self.0.modinv(&m.0).map(Self)
+    }
+
+    /// Miller–Rabin primality. Exact below 3.3·10²⁴; above that the
+    /// twenty fixed witnesses make a mistake vanishingly unlikely for
+    /// numbers not built to fool them.
+    #[must_use]
+    pub fn is_probable_prime(&self) -> bool {
+        let n = &self.0;
+        if !n.is_positive() || n.is_one() {
+            return false;
+        }
+        for &p in WITNESSES.iter().chain(&EXTRA_WITNESSES) {
+            let p = num_bigint::BigInt::from(p);
+            if *n == p {
+                return true;
+            }
+            if (n % &p).is_zero() {
+                return false;
+            }
+        }
+
+        let n_minus_1 = n - 1_u32;
+        let r = n_minus_1.trailing_zeros().unwrap_or(0);
+        let d = &n_minus_1 >> r;
+        let extra = if n.bits() > 81 { &EXTRA_WITNESSES[..] } else { &[] };
+        'witness: for &a in WITNESSES.iter().chain(extra) {
+            let mut x = num_bigint::BigInt::from(a).modpow(&d, n);
+            if x.is_one() || x == n_minus_1 {
+                continue;
+            }
+            for _ in 1..r {
+                x = &x * &x % n;
+                if x == n_minus_1 {
+                    continue 'witness;
+                }
+            }
+            return false;
+        }
+        true
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> Self {
+        Self(n.into())
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(n: u64) -> Self {
+        Self(n.into())
+    }
+}
+
+impl From<num_bigint::BigInt> for BigInt {
+    fn from(n: num_bigint::BigInt) -> Self {
+        Self(n)
+    }
+}
+
+impl From<BigInt> for num_bigint::BigInt {
+    fn from(n: BigInt) -> Self {
+        n.0
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = WofError;
+
+    /// Parse decimal digits with an optional leading sign.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(WofError::InvalidArgument(format!("not an integer: '{s}'")));
+        }
+        s.parse().map(Self).map_err(|_| WofError::InvalidArgument(format!("not an integer: '{s}'")))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Neg for BigInt {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        BigInt(&self.0 + &other.0)
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        BigInt(&self.0 - &other.0)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt(&self.0 * &other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> Result<BigInt, WofError> {
+        s.parse()
+    }
+
+    #[test]
+    fn parses_and_prints() -> Result<(), WofError> {
+        for s in ["0", "7", "-42", "4294967296", "18446744073709551616", "-123456789012345678901234567890"] {
+            assert_eq!(big(s)?.to_string(), s);
+        }
+        assert_eq!(big("+0012")?.to_string(), "12");
+        assert_eq!(big("-0")?, BigInt::zero());
+        for bad in ["12a", "", "-", "1_000", "+-1"] {
+            assert!(big(bad).is_err(), "{bad}");
+        }
+        assert_eq!(BigInt::from(i64::MIN).to_string(), i64::MIN.to_string());
+        assert_eq!(BigInt::from(i64::MIN).to_i64(), Some(i64::MIN));
+        assert_eq!(big("9223372036854775808")?.to_i64(), None);
+        assert_eq!(big("-9223372036854775809")?.to_i64(), None);
+        assert_eq!(big("1099511627776")?.to_f64(), 1_099_511_627_776.0);
+        Ok(())
+    }
+
+    #[test]
+    fn arithmetic_matches_i128() -> Result<(), WofError> {
+        let samples: [i128; 8] = [0, 1, -1, 97, -4_294_967_297, 1 << 40, -(1 << 62) + 12_345, 9_007_199_254_740_993];
+        for &a in &samples {
+            for &b in &samples {
+                let (x, y) = (big(&a.to_string())?, big(&b.to_string())?);
+                assert_eq!((&x + &y).to_string(), (a + b).to_string());
+                assert_eq!((&x - &y).to_string(), (a - b).to_string());
+                assert_eq!((&x * &y).to_string(), (a * b).to_string());
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                if b != 0 {
+                    let (q, r) = x.div_rem(&y).ok_or(WofError::DivisionByZero)?;
+                    assert_eq!((q.to_string(), r.to_string()), ((a / b).to_string(), (a % b).to_string()));
+                    let m = x.rem_euclid(&y).ok_or(WofError::DivisionByZero)?;
+                    assert_eq!(m.to_string(), a.rem_euclid(b.abs()).to_string());
+                }
+            }
+        }
+        assert!(big("5")?.div_rem(&BigInt::zero()).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn long_division_round_trips() -> Result<(), WofError> {
+        let a = big("123456789012345678901234567890123456789012345678901234567890")?;
+        for b in ["98765432109876543210987654321", "340282366920938463463374607431768211455"] {
+            let b = big(b)?;
+            let (q, r) = a.div_rem(&b).ok_or(WofError::DivisionByZero)?;
+            assert_eq!(&(&q * &b) + &r, a);
+            assert!(r < b && !r.is_negative());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn number_theory() -> Result<(), WofError> {
+        assert_eq!(big("4")?.pow_mod(&big("13")?, &big("497")?), Some(big("445")?));
+        let m = big("1000000000000000000000000000057")?;
+        let e = &m - &BigInt::one();
+        assert_eq!(big("3")?.pow_mod(&e, &m), Some(BigInt::one()));
+        assert_eq!(big("-2")?.pow_mod(&big("3")?, &big("5")?), Some(big("2")?));
+        assert_eq!(big("2")?.pow_mod(&big("-1")?, &big("5")?), None);
+        assert_eq!(big("2")?.pow_mod(&big("3")?, &BigInt::zero()), None);
+
+        assert_eq!(big("-12")?.gcd(&big("18")?), big("6")?);
+        assert_eq!(big("4")?.lcm(&big("-6")?), big("12")?);
+        let (g, x, y) = big("240")?.extended_gcd(&big("46")?);
+        assert_eq!(g, big("2")?);
+        assert_eq!(&(&big("240")? * &x) + &(&big("46")? * &y), g);
+        assert_eq!(big("3")?.mod_inv(&big("11")?), Some(big("4")?));
+        assert_eq!(big("-3")?.mod_inv(&big("11")?), Some(big("7")?));
+        assert_eq!(big("6")?.mod_inv(&big("9")?), None);
+        assert_eq!(big("3")?.mod_inv(&big("-11")?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn primality() -> Result<(), WofError> {
+        let primes = ["2", "97", "1000000007", "18446744073709551557", "170141183460469231731687303715884105727"];
+        for p in primes {
+            assert!(big(p)?.is_probable_prime(), "{p}");
+        }
+        let composites = ["0", "1", "-7", "561", "3215031751", "18446744073709551617", "3317044064679887385961981"];
+        for c in composites {
+            assert!(!big(c)?.is_probable_prime(), "{c}");
+        }
+        Ok(())
+    }
+}
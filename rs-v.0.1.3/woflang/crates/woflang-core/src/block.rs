@@ -231,15 +231,29 @@ impl BlockRegistry {
         }
     }
 
+    /// Remove a block that will not be looked up again, such as one an
+    /// interpreter has finished running, and return it. The global block
+    /// is never removed.
+    ///
+    /// The search starts from the newest block, so removing the innermost
+    /// open block is cheap however many blocks came before it.
+    pub fn remove(&mut self, id: BlockId) -> Option<BlockInfo> {
+        if id == BlockId::ROOT {
+            return None;
+        }
+        let index = self.blocks.iter().rposition(|b| b.id == id)?;
+        Some(self.blocks.remove(index))
+    }
+
     /// Get a block by ID.
     #[must_use]
     pub fn get(&self, id: BlockId) -> Option<&BlockInfo> {
-        self.blocks.iter().find(|b| b.id == id)
+        self.blocks.iter().rev().find(|b| b.id == id)
     }
 
     /// Get a mutable reference to a block by ID.
     pub fn get_mut(&mut self, id: BlockId) -> Option<&mut BlockInfo> {
-        self.blocks.iter_mut().find(|b| b.id == id)
+        self.blocks.iter_mut().rev().find(|b| b.id == id)
     }
 
     /// Find the innermost block containing an instruction pointer.
@@ -444,4 +458,18 @@ mod tests {
         let found = registry.enclosing_loop(10).unwrap();
         assert_eq!(found.id, loop_id);
     }
+
+    #[test]
+    fn removed_blocks_are_gone() {
+        let mut registry = BlockRegistry::new();
+        let outer = registry.register(BlockType::Loop, 0, Some(BlockId::ROOT), Span::synthetic());
+        let inner = registry.register(BlockType::Then, 1, Some(outer), Span::synthetic());
+
+        assert_eq!(registry.remove(inner).map(|b| b.block_type), Some(BlockType::Then));
+        assert!(registry.get(inner).is_none());
+        assert!(registry.remove(inner).is_none());
+        assert!(registry.get(outer).is_some());
+        assert!(registry.remove(BlockId::ROOT).is_none());
+        assert_eq!(registry.len(), 2);
+    }
 }
@@ -0,0 +1,119 @@
+//! Source compatibility with 0.0.x plugins.
+//!
+//! In 0.0.x `WofValue` was an enum, and plugins both built values from its
+//! variants and matched on them. Construction still compiles through the
+//! deprecated shims on [`WofValue`] (`WofValue::Float(x)`, `v.as_int()`);
+//! matching needs a real enum, which is what [`Legacy`] is:
+//!
+//! ```
+//! use woflang_core::compat::Legacy;
+//! use woflang_core::WofValue;
+//!
+//! fn describe(v: &WofValue) -> String {
+//!     match Legacy::from(v) {
+//!         Legacy::Int(n) => format!("int {n}"),
+//!         Legacy::Float(x) => format!("float {x}"),
+//!         Legacy::Str(s) | Legacy::Symbol(s) => s,
+//!         Legacy::Nil => "nil".into(),
+//!         Legacy::Other(v) => v.to_string(),
+//!     }
+//! }
+//!
+//! assert_eq!(describe(&WofValue::double(1.5)), "float 1.5");
+//! ```
+//!
+//! New code should use the [`WofValue`] constructors and accessors directly.
+
+use crate::{WofType, WofValue};
+
+/// A [`WofValue`] in the shape of the 0.0.x enum.
+///
+/// Units are not part of the old shape: they are dropped on the way in,
+/// and values built from a `Legacy` have none.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Legacy {
+    /// Nil/unknown.
+    Nil,
+    /// 64-bit signed integer.
+    Int(i64),
+    /// 64-bit float.
+    Float(f64),
+    /// UTF-8 string.
+    Str(String),
+    /// Symbol (identifier).
+    Symbol(String),
+    /// Queues, records and tagged values, which 0.0.x did not have.
+    Other(WofValue),
+}
+
+impl From<&WofValue> for Legacy {
+    fn from(v: &WofValue) -> Self {
+        match v.value_type() {
+            WofType::Unknown => Self::Nil,
+            WofType::Integer => v.try_integer().map_or(Self::Nil, Self::Int),
+            WofType::Double => v.try_double().map_or(Self::Nil, Self::Float),
+            WofType::String => Self::Str(v.try_str().unwrap_or_default().to_owned()),
+            WofType::Symbol => Self::Symbol(v.try_str().unwrap_or_default().to_owned()),
+            _ => Self::Other(v.clone()),
+        }
+    }
+}
+
+impl From<WofValue> for Legacy {
+    fn from(v: WofValue) -> Self {
+        Self::from(&v)
+    }
+}
+
+impl From<Legacy> for WofValue {
+    fn from(v: Legacy) -> Self {
+        match v {
+            Legacy::Nil => Self::nil(),
+            Legacy::Int(n) => Self::integer(n),
+            Legacy::Float(x) => Self::double(x),
+            Legacy::Str(s) => Self::string(s),
+            Legacy::Symbol(s) => Self::symbol(s),
+            Legacy::Other(v) => v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UnitInfo;
+
+    #[test]
+    #[allow(deprecated)]
+    fn old_and_new_spellings_agree() {
+        assert_eq!(WofValue::Int(7), WofValue::integer(7));
+        assert_eq!(WofValue::Float(0.5), WofValue::double(0.5));
+        assert_eq!(WofValue::Str("a"), WofValue::string("a"));
+        assert_eq!(WofValue::Symbol("a"), WofValue::symbol("a"));
+        assert_eq!(WofValue::Bool(true), WofValue::boolean(true));
+
+        let v = WofValue::double(2.0);
+        assert_eq!(v.as_int().unwrap(), v.as_integer().unwrap());
+        assert_eq!(v.as_f64().unwrap(), 2.0);
+        assert_eq!(v.as_float().unwrap(), 2.0);
+        assert!(WofValue::string("x").as_int().is_err());
+    }
+
+    #[test]
+    fn legacy_roundtrip() {
+        let values = [
+            WofValue::nil(),
+            WofValue::integer(-3),
+            WofValue::double(1.25),
+            WofValue::string("wolf"),
+            WofValue::symbol("sq"),
+        ];
+        for v in values {
+            assert_eq!(WofValue::from(Legacy::from(&v)), v);
+        }
+        assert_eq!(Legacy::from(WofValue::symbol("sq")), Legacy::Symbol("sq".into()));
+
+        let metres = WofValue::double(3.0).with_unit(UnitInfo::new("m", 1.0));
+        assert_eq!(Legacy::from(&metres), Legacy::Float(3.0));
+    }
+}
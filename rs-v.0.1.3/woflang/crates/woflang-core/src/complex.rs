@@ -0,0 +1,212 @@
+//! Complex numbers.
+//!
+//! [`Complex`] is a plain pair of `f64` parts with the arithmetic, polar
+//! form and exponential that quantum amplitudes and escape-time fractals
+//! need.
+//!
+//! ```
+//! use woflang_core::Complex;
+//!
+//! let z = Complex::new(3.0, 4.0);
+//! assert_eq!(z.abs(), 5.0);
+//! assert_eq!((z * z.conj()).re, 25.0);
+//! assert_eq!(z.to_string(), "3+4i");
+//! ```
+
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A complex number `re + im·i`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    /// Real part.
+    pub re: f64,
+    /// Imaginary part.
+    pub im: f64,
+}
+
+impl Complex {
+    /// Zero.
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+    /// One.
+    pub const ONE: Self = Self::new(1.0, 0.0);
+    /// The imaginary unit.
+    pub const I: Self = Self::new(0.0, 1.0);
+
+    /// Create `re + im·i`.
+    #[must_use]
+    pub const fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// Create the number with magnitude `r` at angle `theta` radians.
+    #[must_use]
+    pub fn from_polar(r: f64, theta: f64) -> Self {
+        let (sin, cos) = theta.sin_cos();
+        Self::new(r * cos, r * sin)
+    }
+
+    /// Magnitude and angle in radians, the inverse of [`from_polar`](Self::from_polar).
+    #[must_use]
+    pub fn to_polar(self) -> (f64, f64) {
+        (self.abs(), self.arg())
+    }
+
+    /// Magnitude `|z|`.
+    #[must_use]
+    pub fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Squared magnitude `|z|²`, the probability of an amplitude.
+    #[must_use]
+    pub fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Angle from the positive real axis, in (-π, π].
+    #[must_use]
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Complex conjugate.
+    #[must_use]
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// `e^z`.
+    #[must_use]
+    pub fn exp(self) -> Self {
+        Self::from_polar(self.re.exp(), self.im)
+    }
+
+    /// Principal square root, with a non-negative real part. Exact for
+    /// the roots of negative reals: the root of `-1` is `0+1i`.
+    #[must_use]
+    pub fn sqrt(self) -> Self {
+        let t = ((self.abs() + self.re.abs()) / 2.0).sqrt();
+        if t == 0.0 {
+            Self::ZERO
+        } else if self.re >= 0.0 {
+            Self::new(t, self.im / (2.0 * t))
+        } else {
+            Self::new(self.im.abs() / (2.0 * t), t.copysign(self.im))
+        }
+    }
+
+    /// Multiply both parts by a real factor.
+    #[must_use]
+    pub fn scale(self, k: f64) -> Self {
+        Self::new(self.re * k, self.im * k)
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    /// Division; dividing by zero gives non-finite parts, as with `f64`.
+    fn div(self, rhs: Self) -> Self {
+        (self * rhs.conj()).scale(1.0 / rhs.norm_sqr())
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl fmt::Display for Complex {
+    /// `a+bi`, `a-bi`; a negative zero imaginary part prints as `-0i`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.im.is_sign_negative() { '-' } else { '+' };
+        write!(f, "{}{sign}{}i", self.re, self.im.abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::PI;
+
+    fn close(a: Complex, b: Complex) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -1.0);
+        assert_eq!(a + b, Complex::new(4.0, 1.0));
+        assert_eq!(a - b, Complex::new(-2.0, 3.0));
+        assert_eq!(a * b, Complex::new(5.0, 5.0));
+        assert!(close(a * b / b, a));
+        assert_eq!(Complex::I * Complex::I, -Complex::ONE);
+        assert!(!(Complex::ONE / Complex::ZERO).re.is_finite());
+    }
+
+    #[test]
+    fn polar_and_exp() {
+        let z = Complex::from_polar(2.0, PI / 3.0);
+        let (r, theta) = z.to_polar();
+        assert!((r - 2.0).abs() < 1e-12 && (theta - PI / 3.0).abs() < 1e-12);
+        assert_eq!(Complex::new(-1.0, 0.0).arg(), PI);
+        // Euler's identity
+        assert!(close(Complex::new(0.0, PI).exp(), -Complex::ONE));
+        assert!(close(Complex::new(1.0, 0.0).exp(), Complex::from(core::f64::consts::E)));
+    }
+
+    #[test]
+    fn sqrt() {
+        assert_eq!(Complex::from(-1.0).sqrt(), Complex::I);
+        assert_eq!(Complex::from(-4.0).sqrt(), Complex::new(0.0, 2.0));
+        assert_eq!(Complex::new(3.0, 4.0).sqrt(), Complex::new(2.0, 1.0));
+        assert_eq!(Complex::new(3.0, -4.0).sqrt(), Complex::new(2.0, -1.0));
+        assert_eq!(Complex::ZERO.sqrt(), Complex::ZERO);
+        let z = Complex::new(-2.5, 1.5);
+        assert!(close(z.sqrt() * z.sqrt(), z));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Complex::new(1.5, -2.0).to_string(), "1.5-2i");
+        assert_eq!(Complex::I.to_string(), "0+1i");
+    }
+}
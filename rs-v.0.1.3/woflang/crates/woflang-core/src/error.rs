@@ -73,6 +73,19 @@ pub enum WofError {
         span: Span,
     },
 
+    /// Another error, located where it happened.
+    ///
+    /// Errors from running a line come wrapped this way, so match on
+    /// [`root`](Self::root) rather than the error itself:
+    /// `matches!(e.root(), WofError::DivisionByZero)`.
+    #[error("error at {span}: {error}")]
+    Located {
+        /// The error as raised.
+        error: Box<Self>,
+        /// Source location.
+        span: Span,
+    },
+
     /// Numeric overflow.
     #[error("numeric overflow: {0}")]
     Overflow(String),
@@ -145,6 +158,10 @@ pub enum WofError {
         /// Where the return occurred.
         span: Span,
     },
+
+    /// Computation cancelled (Ctrl-C), with how far it got.
+    #[error("interrupted {0}")]
+    Interrupted(String),
 }
 
 impl WofError {
@@ -237,12 +254,21 @@ impl WofError {
         Self::UndefinedLabel { name: name.into() }
     }
 
+    /// Create an interruption error; `progress` says where work stopped,
+    /// e.g. `"after 12 of 100 games"`.
+    #[inline]
+    #[must_use]
+    pub fn interrupted(progress: impl Into<String>) -> Self {
+        Self::Interrupted(progress.into())
+    }
+
     /// Get the span associated with this error, if any.
     #[must_use]
     pub fn span(&self) -> Option<Span> {
         match self {
             Self::Parse { span, .. }
             | Self::RuntimeAt { span, .. }
+            | Self::Located { span, .. }
             | Self::UnclosedBlock { span }
             | Self::UnexpectedBlockClose { span }
             | Self::BreakOutsideLoop { span }
@@ -258,6 +284,55 @@ impl WofError {
         }
     }
 
+    /// The error as raised, without any [`Located`](Self::Located)
+    /// wrapping.
+    #[must_use]
+    pub fn root(&self) -> &Self {
+        match self {
+            Self::Located { error, .. } => error.root(),
+            other => other,
+        }
+    }
+
+    /// A short `snake_case` name for the kind of error, e.g.
+    /// `"stack_underflow"`, as scripts see it in a `catch` handler.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Located { error, .. } => error.kind(),
+            Self::StackUnderflow { .. } => "stack_underflow",
+            Self::TypeMismatch { .. } => "type_mismatch",
+            Self::DivisionByZero => "division_by_zero",
+            Self::UnknownOperation(_) => "unknown_operation",
+            Self::Parse { .. } => "parse",
+            Self::Io(_) => "io",
+            Self::Plugin(_) => "plugin",
+            Self::Runtime(_) | Self::RuntimeAt { .. } => "runtime",
+            Self::Overflow(_) => "overflow",
+            Self::IndexOutOfBounds { .. } => "index_out_of_bounds",
+            Self::InvalidArgument(_) => "invalid_argument",
+            Self::UndefinedVariable { .. } => "undefined_variable",
+            Self::UndefinedFunction { .. } => "undefined_function",
+            Self::UndefinedLabel { .. } => "undefined_label",
+            Self::UnclosedBlock { .. } => "unclosed_block",
+            Self::UnexpectedBlockClose { .. } => "unexpected_block_close",
+            Self::BreakOutsideLoop { .. } => "break_outside_loop",
+            Self::ContinueOutsideLoop { .. } => "continue_outside_loop",
+            Self::ReturnOutsideFunction { .. } => "return_outside_function",
+            Self::Interrupted(_) => "interrupted",
+        }
+    }
+
+    /// What went wrong, without the location.
+    #[must_use]
+    pub fn message(&self) -> String {
+        match self {
+            Self::Runtime(message) | Self::RuntimeAt { message, .. } | Self::Parse { message, .. } => message.clone(),
+            Self::Located { error, .. } => error.message(),
+            other => other.to_string(),
+        }
+    }
+
     /// Check if this is a recoverable error.
     #[must_use]
     pub const fn is_recoverable(&self) -> bool {
@@ -344,6 +419,16 @@ mod tests {
         assert_eq!(err.span(), Some(span));
     }
 
+    #[test]
+    fn located_errors_keep_their_kind() {
+        let err = WofError::Located { error: Box::new(WofError::DivisionByZero), span: Span::new(3, 7, 40) };
+        assert_eq!(err.to_string(), "error at 3:7: division by zero");
+        assert_eq!(err.kind(), "division_by_zero");
+        assert_eq!(err.message(), "division by zero");
+        assert!(matches!(err.root(), WofError::DivisionByZero));
+        assert_eq!(WofError::runtime("boom").message(), "boom");
+    }
+
     #[test]
     fn recoverability() {
         assert!(WofError::DivisionByZero.is_recoverable());
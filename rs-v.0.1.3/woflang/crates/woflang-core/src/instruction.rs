@@ -317,7 +317,7 @@ mod tests {
         
         let push = Instruction::push_value(WofValue::integer(42), span);
         assert!(!push.is_simple());
-        assert_eq!(push.value_operand().map(|v| v.as_integer()), Some(Some(42)));
+        assert_eq!(push.value_operand().map(|v| v.try_integer()), Some(Some(42)));
         
         let call = Instruction::call("test".to_string(), span);
         assert_eq!(call.symbol_operand(), Some("test"));
@@ -10,6 +10,8 @@
 //! - [`Opcode`]: The complete set of language operations
 //! - [`BlockRegistry`]: Block tracking for structured control flow
 //! - [`ScopeStack`]: Lexical scoping with variable bindings
+//! - [`Out`]: Where ops print, redirectable by embedders
+//! - [`compat`]: Source compatibility with 0.0.x plugins
 //!
 //! ## Memory Layout
 //!
@@ -21,25 +23,40 @@
 #![warn(missing_docs)]
 #![allow(clippy::module_name_repetitions)]
 
+mod bigint;
 mod block;
+mod complex;
+pub mod compat;
 mod diagnostic;
 mod error;
 mod instruction;
+mod map;
 mod opcode;
+mod output;
+mod queue;
+mod record;
 mod scope;
 mod span;
 mod stack;
+mod tagged;
 mod unit;
 mod value;
 
+pub use bigint::BigInt;
+pub use complex::Complex;
 pub use block::{BlockId, BlockInfo, BlockRegistry, BlockStack, BlockType};
 pub use diagnostic::{Diagnostic, IntoDiagnostic};
 pub use error::{Result, ResultExt, WofError};
 pub use instruction::{Instruction, Operand, Program};
+pub use map::WofMap;
 pub use opcode::{Opcode, OpcodeCategory};
+pub use output::{Buffer, Out, Output, Stdout};
+pub use queue::{QueueRef, WofQueue};
+pub use record::{RecordType, WofRecord};
 pub use scope::{Scope, ScopeId, ScopeStack};
 pub use span::{Span, Spanned};
 pub use stack::WofStack;
+pub use tagged::WofTagged;
 pub use unit::UnitInfo;
 pub use value::{WofType, WofValue};
 
@@ -78,6 +95,14 @@ pub trait InterpreterContext {
     /// Clear the stack.
     fn clear(&mut self);
 
+    /// Where ops print: standard output unless the context redirects it.
+    ///
+    /// Ops write here rather than with `println!`, so embedders and tests
+    /// can capture the text.
+    fn out(&self) -> Out {
+        Out::stdout()
+    }
+
     /// Signal an error with a message.
     fn error(&self, msg: impl Into<String>) -> WofError {
         WofError::Runtime(msg.into())
@@ -0,0 +1,172 @@
+//! Map values: dictionaries keyed by any value.
+//!
+//! Maps remember insertion order, so listing keys or printing a map is
+//! deterministic. Like records and lists they are immutable values:
+//! setting a key makes a new map.
+
+use core::fmt;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::WofValue;
+
+/// An insertion-ordered map from values to values.
+#[derive(Debug, Clone, Default)]
+pub struct WofMap {
+    entries: Vec<(WofValue, WofValue)>,
+    index: HashMap<WofValue, usize>,
+}
+
+impl WofMap {
+    /// Create an empty map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True if the map has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The value stored under `key`.
+    #[must_use]
+    pub fn get(&self, key: &WofValue) -> Option<&WofValue> {
+        self.index.get(key).map(|&i| &self.entries[i].1)
+    }
+
+    /// True if `key` has a value.
+    #[must_use]
+    pub fn contains_key(&self, key: &WofValue) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Store `value` under `key`, returning the previous value. A new key
+    /// goes last; replacing a value keeps the key's position.
+    pub fn insert(&mut self, key: WofValue, value: WofValue) -> Option<WofValue> {
+        if let Some(&i) = self.index.get(&key) {
+            return Some(std::mem::replace(&mut self.entries[i].1, value));
+        }
+        self.index.insert(key.clone(), self.entries.len());
+        self.entries.push((key, value));
+        None
+    }
+
+    /// Remove `key`, returning its value. Later keys keep their order.
+    pub fn remove(&mut self, key: &WofValue) -> Option<WofValue> {
+        let i = self.index.remove(key)?;
+        let (_, value) = self.entries.remove(i);
+        for (k, _) in &self.entries[i..] {
+            if let Some(slot) = self.index.get_mut(k) {
+                *slot -= 1;
+            }
+        }
+        Some(value)
+    }
+
+    /// Keys in insertion order.
+    pub fn keys(&self) -> impl Iterator<Item = &WofValue> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Values in key insertion order.
+    pub fn values(&self) -> impl Iterator<Item = &WofValue> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    /// Entries in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&WofValue, &WofValue)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl FromIterator<(WofValue, WofValue)> for WofMap {
+    fn from_iter<I: IntoIterator<Item = (WofValue, WofValue)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+/// Maps are equal when they hold the same entries, in any order.
+impl PartialEq for WofMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl Eq for WofMap {}
+
+impl Hash for WofMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Combine entry hashes without regard to order, matching `eq`.
+        let combined = self.iter().fold(0u64, |acc, entry| {
+            let mut hasher = DefaultHasher::new();
+            entry.hash(&mut hasher);
+            acc ^ hasher.finish()
+        });
+        self.len().hash(state);
+        combined.hash(state);
+    }
+}
+
+impl fmt::Display for WofMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key}: {value}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> WofValue {
+        WofValue::string(s)
+    }
+
+    #[test]
+    fn insertion_order_survives_updates_and_removal() {
+        let mut map: WofMap = [("b", 1), ("a", 2), ("c", 3)]
+            .into_iter()
+            .map(|(k, v)| (key(k), WofValue::integer(v)))
+            .collect();
+        assert_eq!(map.insert(key("a"), WofValue::integer(20)), Some(WofValue::integer(2)));
+        assert_eq!(map.remove(&key("b")), Some(WofValue::integer(1)));
+        assert_eq!(map.remove(&key("b")), None);
+        map.insert(key("b"), WofValue::integer(4));
+        assert_eq!(map.to_string(), "{a: 20, c: 3, b: 4}");
+        assert_eq!(map.get(&key("c")), Some(&WofValue::integer(3)));
+        assert!(!map.contains_key(&WofValue::symbol("c")));
+    }
+
+    #[test]
+    fn equality_ignores_order() {
+        let ab: WofMap = [(key("a"), key("x")), (key("b"), key("y"))].into_iter().collect();
+        let ba: WofMap = [(key("b"), key("y")), (key("a"), key("x"))].into_iter().collect();
+        assert_eq!(ab, ba);
+        let hash = |m: &WofMap| {
+            let mut h = DefaultHasher::new();
+            m.hash(&mut h);
+            h.finish()
+        };
+        assert_eq!(hash(&ab), hash(&ba));
+        assert_ne!(ab, WofMap::new());
+    }
+}
@@ -0,0 +1,156 @@
+//! Where ops write their text.
+//!
+//! Ops print through their context's [`Out`] handle instead of
+//! `println!`, so an embedder decides where the text goes: standard
+//! output by default, a [`Buffer`] to read back, or a callback.
+//!
+//! ```
+//! use woflang_core::{Buffer, Out};
+//!
+//! let buffer = Buffer::new();
+//! let out = Out::new(buffer.clone());
+//! out.print("2 + 3 = ");
+//! writeln!(out, "{}", 2 + 3);
+//! assert_eq!(buffer.take(), "2 + 3 = 5\n");
+//!
+//! let (tx, rx) = std::sync::mpsc::channel();
+//! let out = Out::new(move |text: &str| {
+//!     let _ = tx.send(text.to_string());
+//! });
+//! out.println("hello");
+//! assert_eq!(rx.try_iter().collect::<String>(), "hello\n");
+//! ```
+//!
+//! An `Out` is a cheap shared handle: clones write to the same place,
+//! and holding one does not borrow the interpreter, so an op can take it
+//! first and print while it works on its state.
+
+use std::fmt;
+use std::io::Write as _;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// A destination for printed text.
+///
+/// Implemented for closures taking `&str`, which are called with each
+/// piece of text as it is printed.
+pub trait Output: Send {
+    /// Write `text` as it is; any newlines are part of it.
+    fn print(&mut self, text: &str);
+
+    /// Make sure everything printed so far has reached its destination.
+    fn flush(&mut self) {}
+}
+
+impl<F: FnMut(&str) + Send> Output for F {
+    fn print(&mut self, text: &str) {
+        self(text);
+    }
+}
+
+/// Standard output, the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stdout;
+
+impl Output for Stdout {
+    fn print(&mut self, text: &str) {
+        print!("{text}");
+    }
+
+    fn flush(&mut self) {
+        let _ = std::io::stdout().flush();
+    }
+}
+
+/// Text kept in memory; clones share it, so keep one to read back what
+/// was printed through another.
+#[derive(Debug, Clone, Default)]
+pub struct Buffer(Arc<Mutex<String>>);
+
+impl Buffer {
+    /// Create an empty buffer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, String> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Everything printed so far.
+    #[must_use]
+    pub fn contents(&self) -> String {
+        self.lock().clone()
+    }
+
+    /// Everything printed so far, leaving the buffer empty.
+    #[must_use]
+    pub fn take(&self) -> String {
+        std::mem::take(&mut *self.lock())
+    }
+}
+
+impl Output for Buffer {
+    fn print(&mut self, text: &str) {
+        self.lock().push_str(text);
+    }
+}
+
+/// A shared handle to an [`Output`], as returned by
+/// [`InterpreterContext::out`](crate::InterpreterContext::out).
+///
+/// Works with `write!` and `writeln!`, which never fail on it.
+#[derive(Clone)]
+pub struct Out(Arc<Mutex<Box<dyn Output>>>);
+
+impl Out {
+    /// A handle writing to `output`.
+    pub fn new(output: impl Output + 'static) -> Self {
+        Self(Arc::new(Mutex::new(Box::new(output))))
+    }
+
+    /// A handle writing to standard output.
+    #[must_use]
+    pub fn stdout() -> Self {
+        Self::new(Stdout)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Box<dyn Output>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Write `text` as it is.
+    pub fn print(&self, text: &str) {
+        self.lock().print(text);
+    }
+
+    /// Write `text` and a newline.
+    pub fn println(&self, text: &str) {
+        self.print(&format!("{text}\n"));
+    }
+
+    /// Write formatted text, for `write!` and `writeln!`.
+    pub fn write_fmt(&self, args: fmt::Arguments<'_>) {
+        match args.as_str() {
+            Some(text) => self.print(text),
+            None => self.print(&args.to_string()),
+        }
+    }
+
+    /// Flush the underlying output.
+    pub fn flush(&self) {
+        self.lock().flush();
+    }
+}
+
+impl Default for Out {
+    fn default() -> Self {
+        Self::stdout()
+    }
+}
+
+impl fmt::Debug for Out {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Out")
+    }
+}
@@ -0,0 +1,181 @@
+//! Queue values: double-ended queues and priority queues.
+//!
+//! Queues are reference values. Copying a queue value (with `dup` or by
+//! storing it in a variable) shares the same underlying queue, so ops such
+//! as `push_back` can mutate it in place without handing it back.
+
+use core::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::WofValue;
+
+/// A priority-queue entry. Lower priorities pop first; equal priorities
+/// pop in insertion order.
+#[derive(Debug, Clone)]
+struct PqEntry {
+    priority: f64,
+    seq: u64,
+    value: WofValue,
+}
+
+impl PartialEq for PqEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PqEntry {}
+
+impl PartialOrd for PqEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PqEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so the max-heap yields the smallest priority first.
+        other
+            .priority
+            .total_cmp(&self.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// The contents of a queue value.
+#[derive(Debug, Clone)]
+pub struct WofQueue(Kind);
+
+#[derive(Debug, Clone)]
+enum Kind {
+    /// A double-ended FIFO queue.
+    Deque(VecDeque<WofValue>),
+    /// A min-priority queue with an insertion counter for stable ordering
+    /// among equal priorities.
+    Priority { heap: BinaryHeap<PqEntry>, next_seq: u64 },
+}
+
+impl WofQueue {
+    /// Create an empty double-ended queue.
+    #[must_use]
+    pub const fn deque() -> Self {
+        Self(Kind::Deque(VecDeque::new()))
+    }
+
+    /// Create an empty priority queue.
+    #[must_use]
+    pub fn priority() -> Self {
+        Self(Kind::Priority { heap: BinaryHeap::new(), next_seq: 0 })
+    }
+
+    /// Returns `true` for a priority queue.
+    #[must_use]
+    pub const fn is_priority(&self) -> bool {
+        matches!(self.0, Kind::Priority { .. })
+    }
+
+    /// Number of queued items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match &self.0 {
+            Kind::Deque(items) => items.len(),
+            Kind::Priority { heap, .. } => heap.len(),
+        }
+    }
+
+    /// Returns `true` if nothing is queued.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append to the back of a deque. Returns `false` for a priority queue.
+    pub fn push_back(&mut self, value: WofValue) -> bool {
+        match &mut self.0 {
+            Kind::Deque(items) => {
+                items.push_back(value);
+                true
+            }
+            Kind::Priority { .. } => false,
+        }
+    }
+
+    /// Prepend to the front of a deque. Returns `false` for a priority queue.
+    pub fn push_front(&mut self, value: WofValue) -> bool {
+        match &mut self.0 {
+            Kind::Deque(items) => {
+                items.push_front(value);
+                true
+            }
+            Kind::Priority { .. } => false,
+        }
+    }
+
+    /// Remove from the front of a deque.
+    pub fn pop_front(&mut self) -> Option<WofValue> {
+        match &mut self.0 {
+            Kind::Deque(items) => items.pop_front(),
+            Kind::Priority { .. } => None,
+        }
+    }
+
+    /// Remove from the back of a deque.
+    pub fn pop_back(&mut self) -> Option<WofValue> {
+        match &mut self.0 {
+            Kind::Deque(items) => items.pop_back(),
+            Kind::Priority { .. } => None,
+        }
+    }
+
+    /// Insert into a priority queue. Returns `false` for a deque.
+    pub fn push_priority(&mut self, value: WofValue, priority: f64) -> bool {
+        match &mut self.0 {
+            Kind::Priority { heap, next_seq } => {
+                heap.push(PqEntry { priority, seq: *next_seq, value });
+                *next_seq += 1;
+                true
+            }
+            Kind::Deque(_) => false,
+        }
+    }
+
+    /// Remove the lowest-priority item with its priority.
+    pub fn pop_priority(&mut self) -> Option<(WofValue, f64)> {
+        match &mut self.0 {
+            Kind::Priority { heap, .. } => heap.pop().map(|e| (e.value, e.priority)),
+            Kind::Deque(_) => None,
+        }
+    }
+}
+
+/// Shared handle to a queue, as stored in a [`WofValue`].
+#[derive(Debug, Clone)]
+pub struct QueueRef(Arc<Mutex<WofQueue>>);
+
+impl QueueRef {
+    /// Wrap a queue in a new shared handle.
+    #[must_use]
+    pub fn new(queue: WofQueue) -> Self {
+        Self(Arc::new(Mutex::new(queue)))
+    }
+
+    /// Lock the queue for reading or mutation.
+    ///
+    /// A panic while the lock was held cannot leave a queue half-updated,
+    /// so a poisoned lock is recovered rather than reported.
+    pub fn lock(&self) -> MutexGuard<'_, WofQueue> {
+        self.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Returns `true` if both handles refer to the same queue.
+    #[must_use]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Address of the shared queue, for identity hashing.
+    pub(crate) fn addr(&self) -> usize {
+        Arc::as_ptr(&self.0) as usize
+    }
+}
@@ -0,0 +1,107 @@
+//! Record values: lightweight typed aggregates with named fields.
+//!
+//! A record type is a name and an ordered list of field names, shared by
+//! every instance. Records are immutable values; updating a field makes a
+//! new record.
+
+use core::fmt;
+use std::sync::Arc;
+
+use crate::WofValue;
+
+/// The shape of a record: its type name and field names.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct RecordType {
+    name: Arc<str>,
+    fields: Box<[Arc<str>]>,
+}
+
+impl RecordType {
+    /// Define a record type.
+    #[must_use]
+    pub fn new(name: impl AsRef<str>, fields: impl IntoIterator<Item = impl AsRef<str>>) -> Arc<Self> {
+        Arc::new(Self {
+            name: Arc::from(name.as_ref()),
+            fields: fields.into_iter().map(|f| Arc::from(f.as_ref())).collect(),
+        })
+    }
+
+    /// The type name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Field names in declaration order.
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(AsRef::as_ref)
+    }
+
+    /// Number of fields.
+    #[must_use]
+    pub fn arity(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Position of `field`, if the type has it.
+    #[must_use]
+    pub fn index_of(&self, field: &str) -> Option<usize> {
+        self.fields.iter().position(|f| f.as_ref() == field)
+    }
+}
+
+/// An instance of a [`RecordType`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WofRecord {
+    ty: Arc<RecordType>,
+    values: Box<[WofValue]>,
+}
+
+impl WofRecord {
+    /// Build a record from values in field order. Returns `None` if the
+    /// number of values does not match the type.
+    #[must_use]
+    pub fn new(ty: Arc<RecordType>, values: Vec<WofValue>) -> Option<Self> {
+        (values.len() == ty.arity()).then(|| Self { ty, values: values.into_boxed_slice() })
+    }
+
+    /// The record's type.
+    #[must_use]
+    pub fn record_type(&self) -> &Arc<RecordType> {
+        &self.ty
+    }
+
+    /// The value of `field`, if the type has it.
+    #[must_use]
+    pub fn get(&self, field: &str) -> Option<&WofValue> {
+        self.values.get(self.ty.index_of(field)?)
+    }
+
+    /// A copy with `field` set to `value`, if the type has it.
+    #[must_use]
+    pub fn with(&self, field: &str, value: WofValue) -> Option<Self> {
+        let index = self.ty.index_of(field)?;
+        let mut values = self.values.clone();
+        *values.get_mut(index)? = value;
+        Some(Self { ty: Arc::clone(&self.ty), values })
+    }
+
+    /// Field values in declaration order.
+    #[must_use]
+    pub fn values(&self) -> &[WofValue] {
+        &self.values
+    }
+}
+
+impl fmt::Display for WofRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{{", self.ty.name)?;
+        for (i, (name, value)) in self.ty.fields.iter().zip(self.values.iter()).enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{name}: {value}")?;
+        }
+        write!(f, "}}")
+    }
+}
@@ -5,7 +5,7 @@
 //! to outermost scope.
 
 use crate::{BlockId, WofValue, WofError, Result};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// A unique identifier for a scope.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -26,7 +26,7 @@ pub struct Scope {
     /// Parent scope (for lookup chain).
     pub parent: Option<ScopeId>,
     /// Variable bindings in this scope.
-    variables: HashMap<String, WofValue>,
+    variables: BTreeMap<String, WofValue>,
 }
 
 impl Scope {
@@ -37,7 +37,7 @@ impl Scope {
             id,
             block_id,
             parent,
-            variables: HashMap::new(),
+            variables: BTreeMap::new(),
         }
     }
 
@@ -68,7 +68,7 @@ impl Scope {
         self.variables.remove(name)
     }
 
-    /// Get all variable names in this scope.
+    /// Get all variable names in this scope, sorted.
     #[must_use]
     pub fn names(&self) -> impl Iterator<Item = &str> {
         self.variables.keys().map(String::as_str)
@@ -92,7 +92,7 @@ impl Scope {
 /// The `ScopeStack` maintains the chain of active scopes during execution.
 /// Variable lookups traverse from the innermost scope outward until a
 /// binding is found.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ScopeStack {
     /// All scopes, indexed by ID.
     scopes: Vec<Scope>,
@@ -291,7 +291,20 @@ impl ScopeStack {
         self.next_id = 1;
     }
 
-    /// Get all variable names visible from the current scope.
+    /// Put back the variables of every scope `earlier` (a clone of this
+    /// stack) also has, as they were there. Scopes opened since keep
+    /// their variables and the active chain stays, so this is safe
+    /// inside a block.
+    pub fn restore_variables(&mut self, earlier: &Self) {
+        for scope in &mut self.scopes {
+            if let Some(old) = earlier.get(scope.id) {
+                scope.variables.clone_from(&old.variables);
+            }
+        }
+    }
+
+    /// Get all variable names visible from the current scope: the
+    /// innermost scope's names first, each scope's sorted.
     pub fn all_visible_names(&self) -> Vec<String> {
         let mut names = Vec::new();
         let mut scope_id = Some(self.current_id());
@@ -323,21 +336,21 @@ mod tests {
         
         // Define in global scope
         scopes.define("x", WofValue::integer(42));
-        assert_eq!(scopes.lookup("x").map(|v| v.as_integer()), Some(Some(42)));
+        assert_eq!(scopes.lookup("x").map(|v| v.try_integer()), Some(Some(42)));
         
         // Push a new scope
         scopes.push(BlockId::new(1));
         
         // Can still see x from parent
-        assert_eq!(scopes.lookup("x").map(|v| v.as_integer()), Some(Some(42)));
+        assert_eq!(scopes.lookup("x").map(|v| v.try_integer()), Some(Some(42)));
         
         // Shadow x in inner scope
         scopes.define("x", WofValue::integer(100));
-        assert_eq!(scopes.lookup("x").map(|v| v.as_integer()), Some(Some(100)));
+        assert_eq!(scopes.lookup("x").map(|v| v.try_integer()), Some(Some(100)));
         
         // Pop scope, x reverts to original
         scopes.pop();
-        assert_eq!(scopes.lookup("x").map(|v| v.as_integer()), Some(Some(42)));
+        assert_eq!(scopes.lookup("x").map(|v| v.try_integer()), Some(Some(42)));
     }
 
     #[test]
@@ -352,7 +365,39 @@ mod tests {
         
         // Check it's modified in the outer scope
         scopes.pop();
-        assert_eq!(scopes.lookup("counter").map(|v| v.as_integer()), Some(Some(5)));
+        assert_eq!(scopes.lookup("counter").map(|v| v.try_integer()), Some(Some(5)));
+    }
+
+    #[test]
+    fn visible_names_are_sorted_innermost_first() {
+        let mut scopes = ScopeStack::new();
+        for name in ["y", "x", "b"] {
+            scopes.define(name, WofValue::integer(0));
+        }
+        scopes.push(BlockId::new(1));
+        scopes.define("z", WofValue::integer(1));
+        scopes.define("a", WofValue::integer(1));
+        scopes.define("x", WofValue::integer(1));
+
+        assert_eq!(scopes.all_visible_names(), ["a", "x", "z", "b", "y"]);
+    }
+
+    #[test]
+    fn restore_variables_keeps_newer_scopes() {
+        let mut scopes = ScopeStack::new();
+        scopes.define("x", WofValue::integer(1));
+        let earlier = scopes.clone();
+
+        scopes.define("x", WofValue::integer(2));
+        scopes.define("y", WofValue::integer(3));
+        scopes.push(BlockId::new(1));
+        scopes.define("local", WofValue::integer(4));
+        scopes.restore_variables(&earlier);
+
+        assert_eq!(scopes.lookup("x").map(|v| v.try_integer()), Some(Some(1)));
+        assert!(scopes.lookup("y").is_none());
+        assert_eq!(scopes.lookup("local").map(|v| v.try_integer()), Some(Some(4)));
+        assert_eq!(scopes.depth(), 2);
     }
 
     #[test]
@@ -0,0 +1,106 @@
+//! Tagged values: instances of an enum variant with an optional payload.
+
+use core::fmt;
+use std::sync::Arc;
+
+use crate::{Result, WofError, WofValue};
+
+/// A value of a sum type, e.g. `Shape.Circle(2)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WofTagged {
+    enum_name: Arc<str>,
+    tag: Arc<str>,
+    payload: Box<[WofValue]>,
+}
+
+impl WofTagged {
+    /// Create a tagged value of variant `tag` of `enum_name`.
+    #[must_use]
+    pub fn new(enum_name: Arc<str>, tag: Arc<str>, payload: Vec<WofValue>) -> Self {
+        Self { enum_name, tag, payload: payload.into_boxed_slice() }
+    }
+
+    /// Name of the enum this value belongs to.
+    #[must_use]
+    pub fn enum_name(&self) -> &str {
+        &self.enum_name
+    }
+
+    /// Variant name.
+    #[must_use]
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Payload values in constructor order.
+    #[must_use]
+    pub fn payload(&self) -> &[WofValue] {
+        &self.payload
+    }
+
+    /// `Option.Some(value)`.
+    #[must_use]
+    pub fn some(value: WofValue) -> Self {
+        Self::new(Arc::from("Option"), Arc::from("Some"), vec![value])
+    }
+
+    /// `Option.None`.
+    #[must_use]
+    pub fn none() -> Self {
+        Self::new(Arc::from("Option"), Arc::from("None"), Vec::new())
+    }
+
+    /// `Result.Ok(value)`.
+    #[must_use]
+    pub fn ok(value: WofValue) -> Self {
+        Self::new(Arc::from("Result"), Arc::from("Ok"), vec![value])
+    }
+
+    /// `Result.Err(error)`.
+    #[must_use]
+    pub fn err(error: WofValue) -> Self {
+        Self::new(Arc::from("Result"), Arc::from("Err"), vec![error])
+    }
+
+    /// The value held by an `Option.Some` or `Result.Ok`, or `None` for
+    /// `Option.None` and `Result.Err`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this is not an `Option` or `Result`.
+    pub fn success(&self) -> Result<Option<&WofValue>> {
+        match (&*self.enum_name, &*self.tag, &*self.payload) {
+            ("Option", "Some", [value]) | ("Result", "Ok", [value]) => Ok(Some(value)),
+            ("Option", "None", []) | ("Result", "Err", [_]) => Ok(None),
+            _ => Err(WofError::InvalidArgument(format!("expected an Option or Result, got {self}"))),
+        }
+    }
+
+    /// Returns `true` if `pattern` names this variant, either bare
+    /// (`Circle`) or qualified (`Shape.Circle`).
+    #[must_use]
+    pub fn matches(&self, pattern: &str) -> bool {
+        pattern == &*self.tag
+            || pattern
+                .strip_prefix(&*self.enum_name)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .is_some_and(|rest| rest == &*self.tag)
+    }
+}
+
+impl fmt::Display for WofTagged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.enum_name, self.tag)?;
+        if !self.payload.is_empty() {
+            write!(f, "(")?;
+            for (i, value) in self.payload.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{value}")?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
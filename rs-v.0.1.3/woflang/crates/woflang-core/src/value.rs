@@ -7,6 +7,12 @@
 //! - Compact discriminant encoding
 //! - Cache-friendly access patterns
 
+use crate::bigint::BigInt;
+use crate::complex::Complex;
+use crate::map::WofMap;
+use crate::queue::{QueueRef, WofQueue};
+use crate::record::WofRecord;
+use crate::tagged::WofTagged;
 use crate::{Result, UnitInfo, WofError};
 use core::fmt;
 use num_traits::{ToPrimitive, Zero};
@@ -30,6 +36,22 @@ pub enum WofType {
     String = 3,
     /// Interned symbol (identifier).
     Symbol = 4,
+    /// Shared deque or priority queue.
+    Queue = 5,
+    /// Record with named fields.
+    Record = 6,
+    /// Enum variant with payload.
+    Tagged = 7,
+    /// Ordered list of values.
+    List = 8,
+    /// Dictionary from values to values.
+    Map = 9,
+    /// Contiguous block of samples.
+    Buffer = 10,
+    /// Arbitrary-precision integer.
+    BigInt = 11,
+    /// Complex number with `f64` parts.
+    Complex = 12,
 }
 
 impl WofType {
@@ -37,7 +59,7 @@ impl WofType {
     #[inline]
     #[must_use]
     pub const fn is_numeric(self) -> bool {
-        matches!(self, Self::Integer | Self::Double)
+        matches!(self, Self::Integer | Self::Double | Self::BigInt)
     }
 
     /// Returns `true` if this type represents a string-like value.
@@ -56,6 +78,14 @@ impl fmt::Display for WofType {
             Self::Double => write!(f, "double"),
             Self::String => write!(f, "string"),
             Self::Symbol => write!(f, "symbol"),
+            Self::Queue => write!(f, "queue"),
+            Self::Record => write!(f, "record"),
+            Self::Tagged => write!(f, "tagged"),
+            Self::List => write!(f, "list"),
+            Self::Map => write!(f, "map"),
+            Self::Buffer => write!(f, "buffer"),
+            Self::BigInt => write!(f, "bigint"),
+            Self::Complex => write!(f, "complex"),
         }
     }
 }
@@ -70,6 +100,14 @@ enum ValueStorage {
     Integer(i64),
     Double(f64),
     String(Arc<str>),
+    Queue(QueueRef),
+    Record(Arc<WofRecord>),
+    Tagged(Arc<WofTagged>),
+    List(Arc<Vec<WofValue>>),
+    Map(Arc<WofMap>),
+    Buffer(Arc<Vec<f64>>),
+    BigInt(Arc<BigInt>),
+    Complex(Arc<Complex>),
 }
 
 impl Default for ValueStorage {
@@ -163,6 +201,105 @@ impl WofValue {
         }
     }
 
+    /// Create a queue value holding `queue`.
+    ///
+    /// Queue values are references: clones share the same queue.
+    #[must_use]
+    pub fn queue(queue: WofQueue) -> Self {
+        Self {
+            storage: ValueStorage::Queue(QueueRef::new(queue)),
+            typ: WofType::Queue,
+            unit: None,
+        }
+    }
+
+    /// Create a record value.
+    #[must_use]
+    pub fn record(record: WofRecord) -> Self {
+        Self {
+            storage: ValueStorage::Record(Arc::new(record)),
+            typ: WofType::Record,
+            unit: None,
+        }
+    }
+
+    /// Create a tagged (enum variant) value.
+    #[must_use]
+    pub fn tagged(tagged: WofTagged) -> Self {
+        Self {
+            storage: ValueStorage::Tagged(Arc::new(tagged)),
+            typ: WofType::Tagged,
+            unit: None,
+        }
+    }
+
+    /// Create a list value.
+    ///
+    /// Lists are immutable values like records; ops that change a list
+    /// return a new one, copying the elements only when the old list is
+    /// still in use.
+    #[must_use]
+    pub fn list(items: Vec<WofValue>) -> Self {
+        Self {
+            storage: ValueStorage::List(Arc::new(items)),
+            typ: WofType::List,
+            unit: None,
+        }
+    }
+
+    /// Create a map value.
+    ///
+    /// Maps are immutable values like lists; ops that change a map return
+    /// a new one.
+    #[must_use]
+    pub fn map(map: WofMap) -> Self {
+        Self {
+            storage: ValueStorage::Map(Arc::new(map)),
+            typ: WofType::Map,
+            unit: None,
+        }
+    }
+
+    /// Create a sample buffer value.
+    ///
+    /// Buffers hold raw `f64` samples contiguously for block processing;
+    /// like lists they are shared on copy, and an op that owns the only
+    /// handle can change the samples in place.
+    #[must_use]
+    pub fn buffer(samples: Vec<f64>) -> Self {
+        Self {
+            storage: ValueStorage::Buffer(Arc::new(samples)),
+            typ: WofType::Buffer,
+            unit: None,
+        }
+    }
+
+    /// Create an arbitrary-precision integer value.
+    ///
+    /// The value stays a bigint even when it would fit an `i64`, so ops
+    /// can tell that big arithmetic was asked for.
+    #[must_use]
+    pub fn bigint(n: BigInt) -> Self {
+        Self {
+            storage: ValueStorage::BigInt(Arc::new(n)),
+            typ: WofType::BigInt,
+            unit: None,
+        }
+    }
+
+    /// Create a complex number value.
+    ///
+    /// The parts live behind a shared pointer so that primitives keep
+    /// their 8-byte storage slot.
+    #[must_use]
+    pub fn complex(z: Complex) -> Self {
+        Self {
+            storage: ValueStorage::Complex(Arc::new(z)),
+            typ: WofType::Complex,
+            unit: None,
+        }
+    }
+
     /// Create a boolean value (stored as integer 0 or 1).
     #[inline]
     #[must_use]
@@ -230,6 +367,13 @@ impl WofValue {
             ValueStorage::Integer(n) => !n.is_zero(),
             ValueStorage::Double(n) => !n.is_zero() && !n.is_nan(),
             ValueStorage::String(s) => !s.is_empty() && s.as_ref() != "false",
+            ValueStorage::Queue(q) => !q.lock().is_empty(),
+            ValueStorage::Record(_) | ValueStorage::Tagged(_) => true,
+            ValueStorage::List(items) => !items.is_empty(),
+            ValueStorage::Map(map) => !map.is_empty(),
+            ValueStorage::Buffer(samples) => !samples.is_empty(),
+            ValueStorage::BigInt(n) => !n.is_zero(),
+            ValueStorage::Complex(z) => **z != Complex::ZERO,
         }
     }
 
@@ -252,6 +396,9 @@ impl WofValue {
             ValueStorage::Double(n) => n
                 .to_i64()
                 .ok_or_else(|| WofError::type_mismatch("integer", self.typ)),
+            ValueStorage::BigInt(n) => n
+                .to_i64()
+                .ok_or_else(|| WofError::Overflow(format!("{n} does not fit a 64-bit integer"))),
             _ => Err(WofError::type_mismatch("integer", self.typ)),
         }
     }
@@ -264,6 +411,7 @@ impl WofValue {
                 .to_f64()
                 .ok_or_else(|| WofError::type_mismatch("double", self.typ)),
             ValueStorage::Double(n) => Ok(*n),
+            ValueStorage::BigInt(n) => Ok(n.to_f64()),
             _ => Err(WofError::type_mismatch("double", self.typ)),
         }
     }
@@ -283,6 +431,102 @@ impl WofValue {
         }
     }
 
+    /// Extract as a shared queue handle.
+    #[inline]
+    pub fn as_queue(&self) -> Result<&QueueRef> {
+        match &self.storage {
+            ValueStorage::Queue(q) => Ok(q),
+            _ => Err(WofError::type_mismatch("queue", self.typ)),
+        }
+    }
+
+    /// Extract as a record.
+    #[inline]
+    pub fn as_record(&self) -> Result<&WofRecord> {
+        match &self.storage {
+            ValueStorage::Record(r) => Ok(r),
+            _ => Err(WofError::type_mismatch("record", self.typ)),
+        }
+    }
+
+    /// Extract as a tagged value.
+    #[inline]
+    pub fn as_tagged(&self) -> Result<&WofTagged> {
+        match &self.storage {
+            ValueStorage::Tagged(t) => Ok(t),
+            _ => Err(WofError::type_mismatch("tagged", self.typ)),
+        }
+    }
+
+    /// Extract as a list's elements.
+    #[inline]
+    pub fn as_list(&self) -> Result<&[WofValue]> {
+        match &self.storage {
+            ValueStorage::List(items) => Ok(items),
+            _ => Err(WofError::type_mismatch("list", self.typ)),
+        }
+    }
+
+    /// Take a list's elements, copying them only if the list is shared.
+    pub fn into_list(self) -> Result<Vec<WofValue>> {
+        match self.storage {
+            ValueStorage::List(items) => Ok(Arc::try_unwrap(items).unwrap_or_else(|shared| (*shared).clone())),
+            _ => Err(WofError::type_mismatch("list", self.typ)),
+        }
+    }
+
+    /// Extract as a map.
+    #[inline]
+    pub fn as_map(&self) -> Result<&WofMap> {
+        match &self.storage {
+            ValueStorage::Map(map) => Ok(map),
+            _ => Err(WofError::type_mismatch("map", self.typ)),
+        }
+    }
+
+    /// Take a map, copying it only if it is shared.
+    pub fn into_map(self) -> Result<WofMap> {
+        match self.storage {
+            ValueStorage::Map(map) => Ok(Arc::try_unwrap(map).unwrap_or_else(|shared| (*shared).clone())),
+            _ => Err(WofError::type_mismatch("map", self.typ)),
+        }
+    }
+
+    /// Extract as a buffer's samples.
+    #[inline]
+    pub fn as_buffer(&self) -> Result<&[f64]> {
+        match &self.storage {
+            ValueStorage::Buffer(samples) => Ok(samples),
+            _ => Err(WofError::type_mismatch("buffer", self.typ)),
+        }
+    }
+
+    /// Take a buffer's samples, copying them only if the buffer is shared.
+    pub fn into_buffer(self) -> Result<Vec<f64>> {
+        match self.storage {
+            ValueStorage::Buffer(samples) => Ok(Arc::try_unwrap(samples).unwrap_or_else(|shared| (*shared).clone())),
+            _ => Err(WofError::type_mismatch("buffer", self.typ)),
+        }
+    }
+
+    /// Extract as an arbitrary-precision integer, widening plain integers.
+    pub fn as_bigint(&self) -> Result<BigInt> {
+        match &self.storage {
+            ValueStorage::Integer(n) => Ok(BigInt::from(*n)),
+            ValueStorage::BigInt(n) => Ok((**n).clone()),
+            _ => Err(WofError::type_mismatch("integer", self.typ)),
+        }
+    }
+
+    /// Extract as a complex number, widening real numbers.
+    pub fn as_complex(&self) -> Result<Complex> {
+        match &self.storage {
+            ValueStorage::Complex(z) => Ok(**z),
+            _ if self.is_numeric() => Ok(Complex::from(self.as_double()?)),
+            _ => Err(WofError::type_mismatch("complex", self.typ)),
+        }
+    }
+
     /// Extract as boolean.
     #[inline]
     #[must_use]
@@ -332,6 +576,7 @@ impl WofValue {
 
     /// Extract as f64. Alias for [`as_double`](Self::as_double).
     #[inline]
+    #[deprecated(since = "0.1.0", note = "use `as_double`")]
     pub fn as_float(&self) -> Result<f64> {
         self.as_double()
     }
@@ -356,6 +601,73 @@ impl WofValue {
     pub const fn is_symbol(&self) -> bool {
         matches!(self.typ, WofType::Symbol)
     }
+
+    // ═══════════════════════════════════════════════════════════════
+    // DEPRECATED 0.0.x NAMES
+    // ═══════════════════════════════════════════════════════════════
+    //
+    // In 0.0.x `WofValue` was an enum, so plugins wrote `WofValue::Float(x)`
+    // and `v.as_int()`. These shims keep that code compiling; pattern
+    // matching on the old variants goes through [`crate::compat::Legacy`].
+
+    /// 0.0.x spelling of [`integer`](Self::integer).
+    #[allow(non_snake_case)]
+    #[inline]
+    #[must_use]
+    #[deprecated(since = "0.1.0", note = "use `WofValue::integer`")]
+    pub const fn Int(v: i64) -> Self {
+        Self::integer(v)
+    }
+
+    /// 0.0.x spelling of [`double`](Self::double).
+    #[allow(non_snake_case)]
+    #[inline]
+    #[must_use]
+    #[deprecated(since = "0.1.0", note = "use `WofValue::double`")]
+    pub const fn Float(v: f64) -> Self {
+        Self::double(v)
+    }
+
+    /// 0.0.x spelling of [`string`](Self::string).
+    #[allow(non_snake_case)]
+    #[inline]
+    #[must_use]
+    #[deprecated(since = "0.1.0", note = "use `WofValue::string`")]
+    pub fn Str(s: impl AsRef<str>) -> Self {
+        Self::string(s)
+    }
+
+    /// 0.0.x spelling of [`symbol`](Self::symbol).
+    #[allow(non_snake_case)]
+    #[inline]
+    #[must_use]
+    #[deprecated(since = "0.1.0", note = "use `WofValue::symbol`")]
+    pub fn Symbol(s: impl AsRef<str>) -> Self {
+        Self::symbol(s)
+    }
+
+    /// 0.0.x spelling of [`boolean`](Self::boolean).
+    #[allow(non_snake_case)]
+    #[inline]
+    #[must_use]
+    #[deprecated(since = "0.1.0", note = "use `WofValue::boolean`")]
+    pub const fn Bool(b: bool) -> Self {
+        Self::boolean(b)
+    }
+
+    /// 0.0.x spelling of [`as_integer`](Self::as_integer).
+    #[inline]
+    #[deprecated(since = "0.1.0", note = "use `as_integer`")]
+    pub fn as_int(&self) -> Result<i64> {
+        self.as_integer()
+    }
+
+    /// 0.0.x spelling of [`as_double`](Self::as_double).
+    #[inline]
+    #[deprecated(since = "0.1.0", note = "use `as_double`")]
+    pub fn as_f64(&self) -> Result<f64> {
+        self.as_double()
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -372,6 +684,14 @@ impl fmt::Debug for WofValue {
                 write!(f, "WofValue::symbol({s:?})")
             }
             ValueStorage::String(s) => write!(f, "WofValue::string({s:?})"),
+            ValueStorage::Queue(q) => write!(f, "WofValue::queue(len={})", q.lock().len()),
+            ValueStorage::Record(r) => write!(f, "WofValue::record({r})"),
+            ValueStorage::Tagged(t) => write!(f, "WofValue::tagged({t})"),
+            ValueStorage::List(items) => write!(f, "WofValue::list({items:?})"),
+            ValueStorage::Map(map) => write!(f, "WofValue::map({map})"),
+            ValueStorage::Buffer(samples) => write!(f, "WofValue::buffer(len={})", samples.len()),
+            ValueStorage::BigInt(n) => write!(f, "WofValue::bigint({n})"),
+            ValueStorage::Complex(z) => write!(f, "WofValue::complex({z})"),
         }
     }
 }
@@ -389,6 +709,27 @@ impl fmt::Display for WofValue {
                 }
             }
             ValueStorage::String(s) => write!(f, "{s}"),
+            ValueStorage::Queue(q) => {
+                let q = q.lock();
+                let kind = if q.is_priority() { "pq" } else { "queue" };
+                write!(f, "<{kind} len={}>", q.len())
+            }
+            ValueStorage::Record(r) => write!(f, "{r}"),
+            ValueStorage::Tagged(t) => write!(f, "{t}"),
+            ValueStorage::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            ValueStorage::Map(map) => write!(f, "{map}"),
+            ValueStorage::Buffer(samples) => write!(f, "<buffer len={}>", samples.len()),
+            ValueStorage::BigInt(n) => write!(f, "{n}"),
+            ValueStorage::Complex(z) => write!(f, "{z}"),
         }?;
         if let Some(unit) = &self.unit {
             write!(f, " {}", unit.name)?;
@@ -410,6 +751,19 @@ impl PartialEq for WofValue {
                 (a.is_nan() && b.is_nan()) || a == b
             }
             (ValueStorage::String(a), ValueStorage::String(b)) => a == b,
+            (ValueStorage::Queue(a), ValueStorage::Queue(b)) => a.ptr_eq(b),
+            (ValueStorage::Record(a), ValueStorage::Record(b)) => a == b,
+            (ValueStorage::Tagged(a), ValueStorage::Tagged(b)) => a == b,
+            (ValueStorage::List(a), ValueStorage::List(b)) => a == b,
+            (ValueStorage::Map(a), ValueStorage::Map(b)) => a == b,
+            (ValueStorage::Buffer(a), ValueStorage::Buffer(b)) => {
+                a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| (x.is_nan() && y.is_nan()) || x == y)
+            }
+            (ValueStorage::BigInt(a), ValueStorage::BigInt(b)) => a == b,
+            (ValueStorage::Complex(a), ValueStorage::Complex(b)) => {
+                let same = |x: f64, y: f64| (x.is_nan() && y.is_nan()) || x == y;
+                same(a.re, b.re) && same(a.im, b.im)
+            }
             _ => false,
         }
     }
@@ -425,6 +779,22 @@ impl std::hash::Hash for WofValue {
             ValueStorage::Integer(n) => n.hash(state),
             ValueStorage::Double(n) => n.to_bits().hash(state),
             ValueStorage::String(s) => s.hash(state),
+            ValueStorage::Queue(q) => q.addr().hash(state),
+            ValueStorage::Record(r) => r.hash(state),
+            ValueStorage::Tagged(t) => t.hash(state),
+            ValueStorage::List(items) => items.hash(state),
+            ValueStorage::Map(map) => map.hash(state),
+            ValueStorage::Buffer(samples) => {
+                samples.len().hash(state);
+                for x in samples.iter() {
+                    x.to_bits().hash(state);
+                }
+            }
+            ValueStorage::BigInt(n) => n.hash(state),
+            ValueStorage::Complex(z) => {
+                z.re.to_bits().hash(state);
+                z.im.to_bits().hash(state);
+            }
         }
     }
 }
@@ -529,4 +899,59 @@ mod tests {
         assert_eq!(format!("{}", WofValue::double(3.0)), "3.0");
         assert_eq!(format!("{}", WofValue::string("test")), "test");
     }
+
+    #[test]
+    fn lists_are_values() {
+        let list = WofValue::list(vec![WofValue::integer(1), WofValue::string("a")]);
+        assert_eq!(list.value_type(), WofType::List);
+        assert_eq!(format!("{list}"), "[1, a]");
+        assert!(list.is_truthy() && !WofValue::list(Vec::new()).is_truthy());
+
+        let copy = list.clone();
+        let mut items = copy.into_list().unwrap();
+        items.push(WofValue::nil());
+        assert_eq!(list.as_list().unwrap().len(), 2);
+        assert_ne!(list, WofValue::list(items));
+        assert!(WofValue::integer(1).as_list().is_err());
+    }
+
+    #[test]
+    fn buffers_are_values() {
+        let buf = WofValue::buffer(vec![0.5, f64::NAN, -1.0]);
+        assert_eq!(buf.value_type(), WofType::Buffer);
+        assert_eq!(format!("{buf}"), "<buffer len=3>");
+        assert_eq!(buf, WofValue::buffer(vec![0.5, f64::NAN, -1.0]));
+        assert!(buf.is_truthy() && !WofValue::buffer(Vec::new()).is_truthy());
+
+        let mut samples = buf.clone().into_buffer().unwrap();
+        samples[0] = 0.0;
+        assert_eq!(buf.as_buffer().unwrap()[0], 0.5);
+        assert!(WofValue::list(Vec::new()).as_buffer().is_err());
+    }
+
+    #[test]
+    fn bigints_are_values() {
+        let n = WofValue::bigint("-123456789012345678901234567890".parse().unwrap());
+        assert_eq!(n.value_type(), WofType::BigInt);
+        assert_eq!(n.to_string(), "-123456789012345678901234567890");
+        assert!(n.is_numeric() && n.is_truthy());
+        assert!(n.as_integer().is_err());
+        assert!((n.as_double().unwrap() + 1.234_567_890_123_456_8e29).abs() < 1e15);
+        assert_eq!(WofValue::integer(-7).as_bigint().unwrap(), BigInt::from(-7_i64));
+        assert_eq!(WofValue::bigint(BigInt::from(42_i64)).as_integer().unwrap(), 42);
+        assert_ne!(WofValue::bigint(BigInt::from(42_i64)), WofValue::integer(42));
+        assert!(!WofValue::bigint(BigInt::zero()).is_truthy());
+    }
+
+    #[test]
+    fn complexes_are_values() {
+        let z = WofValue::complex(Complex::new(3.0, -4.0));
+        assert_eq!(z.value_type(), WofType::Complex);
+        assert_eq!(format!("{z}"), "3-4i");
+        assert_eq!(z.as_complex().unwrap().abs(), 5.0);
+        assert_eq!(WofValue::integer(2).as_complex().unwrap(), Complex::new(2.0, 0.0));
+        assert!(z.as_double().is_err() && WofValue::string("i").as_complex().is_err());
+        assert_eq!(z, WofValue::complex(Complex::new(3.0, -4.0)));
+        assert!(!WofValue::complex(Complex::ZERO).is_truthy());
+    }
 }
@@ -13,8 +13,19 @@
 //! | `abs`     | (a -- b)     | Absolute value |
 //! | `min`     | (a b -- c)   | Minimum |
 //! | `max`     | (a b -- c)   | Maximum |
+//! | `evaluate`| (s -- ...)   | Evaluate infix string (alias `calc`) |
+//!
+//! Infix can also be written inline as `=( 3 * (2 + 4) )`.
+//!
+//! Integer literals too large for 64 bits are bigints; `+`, `-`, `*`,
+//! `%`, `neg` and `abs` keep them exact, widening any plain integer they
+//! meet.
+//!
+//! Complex numbers work the same way: `+`, `-`, `*`, `/` and `neg` give
+//! a complex result, widening any real number they meet, and `abs` gives
+//! the magnitude.
 
-use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_core::{BigInt, Complex, InterpreterContext, Result, WofError, WofType, WofValue};
 use woflang_runtime::Interpreter;
 
 /// Register all arithmetic operations.
@@ -31,15 +42,59 @@ pub fn register(interp: &mut Interpreter) {
     interp.register("max", op_max);
     interp.register("inc", op_inc);
     interp.register("dec", op_dec);
+    interp.register("evaluate", op_evaluate);
+    interp.register("calc", op_evaluate);
 
     // Unicode aliases
     interp.register("×", op_mul);
     interp.register("÷", op_div);
+
+    crate::describe(interp, "arithmetic", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["+"], "( a b -- a+b )", "Addition", "2 3 +"),
+    (&["-"], "( a b -- a-b )", "Subtraction", "10 4 -"),
+    (&["*", "×"], "( a b -- a*b )", "Multiplication", "6 7 *"),
+    (&["/", "÷"], "( a b -- a/b )", "Division", "7 2 /"),
+    (&["%", "mod"], "( a b -- a%b )", "Modulo", "17 5 %"),
+    (&["neg"], "( a -- -a )", "Negation", "5 neg"),
+    (&["abs"], "( a -- |a| )", "Absolute value", "-3 abs"),
+    (&["min"], "( a b -- c )", "Minimum", "3 8 min"),
+    (&["max"], "( a b -- c )", "Maximum", "3 8 max"),
+    (&["inc"], "( a -- a+1 )", "Increment", "41 inc"),
+    (&["dec"], "( a -- a-1 )", "Decrement", "43 dec"),
+    (&["evaluate", "calc"], "( s -- ... )", "Evaluate an infix expression string", "\"3 * (2 + 4)\" evaluate"),
+];
+
+/// Both operands as bigints, when either is one and neither is a double.
+pub(crate) fn big_pair(a: &WofValue, b: &WofValue) -> Option<(BigInt, BigInt)> {
+    if a.value_type() != WofType::BigInt && b.value_type() != WofType::BigInt {
+        return None;
+    }
+    Some((a.as_bigint().ok()?, b.as_bigint().ok()?))
+}
+
+/// Both operands as complex numbers, when either is one.
+pub(crate) fn complex_pair(a: &WofValue, b: &WofValue) -> Option<(Complex, Complex)> {
+    if a.value_type() != WofType::Complex && b.value_type() != WofType::Complex {
+        return None;
+    }
+    Some((a.as_complex().ok()?, b.as_complex().ok()?))
 }
 
 fn op_add(interp: &mut Interpreter) -> Result<()> {
     let b = interp.stack_mut().pop()?;
     let a = interp.stack_mut().pop()?;
+    if let Some((a, b)) = complex_pair(&a, &b) {
+        interp.push(WofValue::complex(a + b));
+        return Ok(());
+    }
+    if let Some((a, b)) = big_pair(&a, &b) {
+        interp.push(WofValue::bigint(&a + &b));
+        return Ok(());
+    }
 
     // Integer arithmetic if both are integers
     let result = match (a.try_integer(), b.try_integer()) {
@@ -54,6 +109,14 @@ fn op_add(interp: &mut Interpreter) -> Result<()> {
 fn op_sub(interp: &mut Interpreter) -> Result<()> {
     let b = interp.stack_mut().pop()?;
     let a = interp.stack_mut().pop()?;
+    if let Some((a, b)) = complex_pair(&a, &b) {
+        interp.push(WofValue::complex(a - b));
+        return Ok(());
+    }
+    if let Some((a, b)) = big_pair(&a, &b) {
+        interp.push(WofValue::bigint(&a - &b));
+        return Ok(());
+    }
 
     let result = match (a.try_integer(), b.try_integer()) {
         (Some(a), Some(b)) => WofValue::integer(a.wrapping_sub(b)),
@@ -67,6 +130,14 @@ fn op_sub(interp: &mut Interpreter) -> Result<()> {
 fn op_mul(interp: &mut Interpreter) -> Result<()> {
     let b = interp.stack_mut().pop()?;
     let a = interp.stack_mut().pop()?;
+    if let Some((a, b)) = complex_pair(&a, &b) {
+        interp.push(WofValue::complex(a * b));
+        return Ok(());
+    }
+    if let Some((a, b)) = big_pair(&a, &b) {
+        interp.push(WofValue::bigint(&a * &b));
+        return Ok(());
+    }
 
     let result = match (a.try_integer(), b.try_integer()) {
         (Some(a), Some(b)) => WofValue::integer(a.wrapping_mul(b)),
@@ -78,8 +149,16 @@ fn op_mul(interp: &mut Interpreter) -> Result<()> {
 }
 
 fn op_div(interp: &mut Interpreter) -> Result<()> {
-    let b = interp.stack_mut().pop_numeric()?;
-    let a = interp.stack_mut().pop_numeric()?;
+    let b = interp.stack_mut().pop()?;
+    let a = interp.stack_mut().pop()?;
+    if let Some((a, b)) = complex_pair(&a, &b) {
+        if b == Complex::ZERO {
+            return Err(WofError::DivisionByZero);
+        }
+        interp.push(WofValue::complex(a / b));
+        return Ok(());
+    }
+    let (a, b) = (a.as_numeric()?, b.as_numeric()?);
 
     if b == 0.0 {
         return Err(WofError::DivisionByZero);
@@ -92,6 +171,11 @@ fn op_div(interp: &mut Interpreter) -> Result<()> {
 fn op_mod(interp: &mut Interpreter) -> Result<()> {
     let b = interp.stack_mut().pop()?;
     let a = interp.stack_mut().pop()?;
+    if let Some((a, b)) = big_pair(&a, &b) {
+        let (_, r) = a.div_rem(&b).ok_or(WofError::DivisionByZero)?;
+        interp.push(WofValue::bigint(r));
+        return Ok(());
+    }
 
     let result = match (a.try_integer(), b.try_integer()) {
         (Some(a), Some(b)) => {
@@ -115,6 +199,14 @@ fn op_mod(interp: &mut Interpreter) -> Result<()> {
 
 fn op_neg(interp: &mut Interpreter) -> Result<()> {
     let a = interp.stack_mut().pop()?;
+    if a.value_type() == WofType::Complex {
+        interp.push(WofValue::complex(-a.as_complex()?));
+        return Ok(());
+    }
+    if a.value_type() == WofType::BigInt {
+        interp.push(WofValue::bigint(-a.as_bigint()?));
+        return Ok(());
+    }
 
     let result = match a.try_integer() {
         Some(n) => WofValue::integer(-n),
@@ -127,6 +219,14 @@ fn op_neg(interp: &mut Interpreter) -> Result<()> {
 
 fn op_abs(interp: &mut Interpreter) -> Result<()> {
     let a = interp.stack_mut().pop()?;
+    if a.value_type() == WofType::Complex {
+        interp.push(WofValue::double(a.as_complex()?.abs()));
+        return Ok(());
+    }
+    if a.value_type() == WofType::BigInt {
+        interp.push(WofValue::bigint(a.as_bigint()?.abs()));
+        return Ok(());
+    }
 
     let result = match a.try_integer() {
         Some(n) => WofValue::integer(n.abs()),
@@ -175,6 +275,11 @@ fn op_dec(interp: &mut Interpreter) -> Result<()> {
     Ok(())
 }
 
+fn op_evaluate(interp: &mut Interpreter) -> Result<()> {
+    let expr = interp.stack_mut().pop()?.as_string()?;
+    interp.exec_infix(&expr)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,6 +298,40 @@ mod tests {
         assert_eq!(interp.stack().peek().unwrap().as_integer().unwrap(), 8);
     }
 
+    #[test]
+    fn test_bigint_arithmetic() {
+        let mut interp = make_interp();
+        interp.exec_line("9223372036854775808 2 * 1 -").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "18446744073709551615");
+        interp.exec_line("100000000000000000000 7 % 100000000000000000000 neg abs").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "100000000000000000000");
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "2");
+        assert!(interp.exec_line("100000000000000000000 0 %").is_err());
+    }
+
+    #[test]
+    fn test_complex_arithmetic() {
+        let mut interp = make_interp();
+        interp.push(WofValue::complex(Complex::new(1.0, 2.0)));
+        interp.exec_line("3 + 2 * 1 - neg").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().as_complex().unwrap(), Complex::new(-7.0, -4.0));
+
+        interp.push(WofValue::complex(Complex::new(3.0, 4.0)));
+        interp.exec_line("abs").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::double(5.0));
+
+        interp.push(WofValue::integer(5));
+        interp.push(WofValue::complex(Complex::new(1.0, 2.0)));
+        interp.exec_line("/").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().as_complex().unwrap(), Complex::new(1.0, -2.0));
+
+        interp.push(WofValue::complex(Complex::I));
+        let err = interp.exec_line("0 /").unwrap_err();
+        assert!(matches!(err.root(), WofError::DivisionByZero), "{err}");
+        interp.push(WofValue::complex(Complex::I));
+        assert!(interp.exec_line("\"z\" +").is_err());
+    }
+
     #[test]
     fn test_sub() {
         let mut interp = make_interp();
@@ -218,8 +357,8 @@ mod tests {
     #[test]
     fn test_div_by_zero() {
         let mut interp = make_interp();
-        let result = interp.exec_line("10 0 /");
-        assert!(matches!(result, Err(WofError::DivisionByZero)));
+        let err = interp.exec_line("10 0 /").unwrap_err();
+        assert!(matches!(err.root(), WofError::DivisionByZero), "{err}");
     }
 
     #[test]
@@ -257,4 +396,14 @@ mod tests {
         interp.exec_line("6 7 ×").unwrap();
         assert_eq!(interp.stack().peek().unwrap().as_integer().unwrap(), 42);
     }
+
+    #[test]
+    fn test_evaluate_infix() {
+        let mut interp = make_interp();
+        interp.exec_line("\"2 * (3 + 4) - -1\" evaluate").unwrap();
+        assert_eq!(interp.stack().peek().unwrap().as_integer().unwrap(), 15);
+
+        interp.exec_line("10 =(max(2, 5) * 3) +").unwrap();
+        assert_eq!(interp.stack().peek().unwrap().as_integer().unwrap(), 25);
+    }
 }
@@ -129,8 +129,30 @@ pub fn register(interp: &mut Interpreter) {
         i.push(WofValue::integer(i64::MIN));
         Ok(())
     });
+
+    crate::describe(interp, "constants", DOCS);
 }
 
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["pi", "π"], "( -- π )", "Circle ratio", "pi"),
+    (&["e"], "( -- e )", "Euler's number", "e"),
+    (&["tau", "τ"], "( -- τ )", "2π", "tau"),
+    (&["phi", "φ"], "( -- φ )", "Golden ratio", "phi"),
+    (&["sqrt2", "√2"], "( -- √2 )", "Square root of 2", "sqrt2"),
+    (&["ln2"], "( -- ln 2 )", "Natural log of 2", "ln2"),
+    (&["ln10"], "( -- ln 10 )", "Natural log of 10", "ln10"),
+    (&["avogadro"], "( -- Nₐ )", "Avogadro's constant", "avogadro"),
+    (&["c"], "( -- c )", "Speed of light in m/s", "c"),
+    (&["planck"], "( -- h )", "Planck's constant in J⋅s", "planck"),
+    (&["boltzmann"], "( -- k )", "Boltzmann constant in J/K", "boltzmann"),
+    (&["inf", "∞"], "( -- ∞ )", "Positive infinity", "inf"),
+    (&["-inf"], "( -- -∞ )", "Negative infinity", "-inf"),
+    (&["nan"], "( -- NaN )", "Not a number", "nan"),
+    (&["max_int"], "( -- n )", "Largest integer", "max_int"),
+    (&["min_int"], "( -- n )", "Smallest integer", "min_int"),
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
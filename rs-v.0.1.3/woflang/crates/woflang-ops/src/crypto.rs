@@ -8,9 +8,32 @@
 //! | `gcd`           | (a b -- c)   | Greatest common divisor |
 //! | `mod_exp`       | (b e m -- r) | Modular exponentiation |
 //! | `diffie_hellman`| ( -- )       | Demonstrate DH key exchange |
+//! | `ct_eq`         | (a b -- b)   | Constant-time string/byte equality |
+//! | `timing_demo`   | ( -- )       | Time naive vs constant-time comparison |
+//!
+//! `prime_check`, `mod_exp` and `mod_inv` also take bigints (integer
+//! literals past 64 bits), returning a bigint when any operand is one,
+//! so RSA-sized numbers work.
+//!
+//! `random` and `rand` draw from the system generator unless the `seed`
+//! parameter holds an integer, which makes them reproducible:
+//!
+//! ```text
+//! parameterize "seed" 42 ⺆ 1 6 random 1 6 random ⺘   # same pair every run
+//! "seed" 7 param_set                                 # reproducible from here on
+//! ```
+//!
+//! `ct_eq` examines every byte of the longer input and never branches
+//! on the data, so its running time reveals only the input lengths. A
+//! naive comparison stops at the first mismatch, which lets an attacker
+//! recover a secret one byte at a time; `timing_demo` shows the gap.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
 
-use rand::Rng;
-use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use woflang_core::{BigInt, InterpreterContext, Result, WofError, WofType, WofValue};
 use woflang_runtime::Interpreter;
 
 /// Register all cryptographic operations.
@@ -36,8 +59,64 @@ pub fn register(interp: &mut Interpreter) {
 
     // Encoding
     interp.register("base64_encode", op_base64_encode);
+
+    // Timing-safe comparison
+    interp.register("ct_eq", op_ct_eq);
+    interp.register("timing_demo", op_timing_demo);
+
+    interp.define_param("seed", seed, set_seed);
+
+    crate::describe(interp, "crypto", DOCS);
 }
 
+/// The generator behind the random ops while the `seed` parameter is set,
+/// with the seed it started from.
+#[derive(Default)]
+struct Seeded(Option<(i64, ChaCha8Rng)>);
+
+fn seed(interp: &Interpreter) -> WofValue {
+    match interp.state::<Seeded>().and_then(|s| s.0.as_ref()) {
+        Some(&(seed, _)) => WofValue::integer(seed),
+        None => WofValue::nil(),
+    }
+}
+
+/// Seed the random ops with an integer, or return them to the system
+/// generator with nil.
+fn set_seed(interp: &mut Interpreter, value: WofValue) -> Result<()> {
+    let seeded = if value.is_nil() {
+        None
+    } else {
+        let seed = value.as_integer()?;
+        Some((seed, ChaCha8Rng::seed_from_u64(seed as u64)))
+    };
+    interp.state_mut::<Seeded>().0 = seeded;
+    Ok(())
+}
+
+/// Run `f` on the seeded generator if there is one, else the system's.
+fn with_rng<T>(interp: &mut Interpreter, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    match &mut interp.state_mut::<Seeded>().0 {
+        Some((_, rng)) => f(rng),
+        None => f(&mut rand::thread_rng()),
+    }
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["prime_check", "is_prime"], "( n -- bool )", "Miller-Rabin primality test", "97 prime_check"),
+    (&["next_prime"], "( n -- p )", "Smallest prime greater than n", "100 next_prime"),
+    (&["random"], "( lo hi -- n )", "Random integer in range", "1 6 random"),
+    (&["rand"], "( -- x )", "Random float in [0, 1)", "rand"),
+    (&["hash"], "( n -- h )", "FNV-1a hash of an integer", "42 hash"),
+    (&["mod_exp"], "( b e m -- r )", "Modular exponentiation", "4 13 497 mod_exp"),
+    (&["mod_inv"], "( a m -- x )", "Modular inverse", "3 11 mod_inv"),
+    (&["diffie_hellman"], "( -- )", "Demonstrate DH key exchange", "diffie_hellman"),
+    (&["base64_encode"], "( n -- s )", "Base64 of an integer's bytes", "42 base64_encode"),
+    (&["ct_eq"], "( a b -- bool )", "Constant-time string equality", "\"secret\" \"secret\" ct_eq"),
+    (&["timing_demo"], "( -- )", "Time naive vs constant-time comparison", ""),
+];
+
 /// Miller-Rabin primality test with deterministic witnesses for 64-bit integers.
 fn is_prime_miller_rabin(n: u64) -> bool {
     if n < 2 {
@@ -132,7 +211,23 @@ fn mod_pow(mut base: u64, mut exp: u64, modulus: u64) -> u64 {
     result as u64
 }
 
+/// Pop the top `n` values as bigints, deepest first, if any of them is
+/// one; otherwise leave the stack alone for the 64-bit path.
+fn pop_bigints(interp: &mut Interpreter, n: usize) -> Result<Option<Vec<BigInt>>> {
+    let stack = interp.stack().as_slice();
+    let top = &stack[stack.len().saturating_sub(n)..];
+    if !top.iter().any(|v| v.value_type() == WofType::BigInt) {
+        return Ok(None);
+    }
+    let values = interp.stack_mut().pop_n(n)?;
+    values.iter().rev().map(WofValue::as_bigint).collect::<Result<Vec<_>>>().map(Some)
+}
+
 fn op_prime_check(interp: &mut Interpreter) -> Result<()> {
+    if let Some(n) = pop_bigints(interp, 1)? {
+        interp.push(WofValue::boolean(n[0].is_probable_prime()));
+        return Ok(());
+    }
     let n = interp.stack_mut().pop_integer()?;
     if n < 0 {
         interp.push(WofValue::boolean(false));
@@ -179,14 +274,13 @@ fn op_random(interp: &mut Interpreter) -> Result<()> {
         )));
     }
 
-    let mut rng = rand::thread_rng();
-    let value = rng.gen_range(lo..=hi);
+    let value = with_rng(interp, |rng| rng.gen_range(lo..=hi));
     interp.push(WofValue::integer(value));
     Ok(())
 }
 
 fn op_rand(interp: &mut Interpreter) -> Result<()> {
-    let value: f64 = rand::random();
+    let value: f64 = with_rng(interp, |rng| rng.gen());
     interp.push(WofValue::double(value));
     Ok(())
 }
@@ -207,6 +301,17 @@ fn op_hash(interp: &mut Interpreter) -> Result<()> {
 }
 
 fn op_mod_exp(interp: &mut Interpreter) -> Result<()> {
+    if let Some(args) = pop_bigints(interp, 3)? {
+        let [b, e, m] = &args[..] else { unreachable!("three operands") };
+        if m.is_zero() {
+            return Err(WofError::DivisionByZero);
+        }
+        let r = b.pow_mod(e, m).ok_or_else(|| {
+            WofError::InvalidArgument("mod_exp: need a non-negative exponent and positive modulus".into())
+        })?;
+        interp.push(WofValue::bigint(r));
+        return Ok(());
+    }
     let m = interp.stack_mut().pop_integer()? as u64;
     let e = interp.stack_mut().pop_integer()? as u64;
     let b = interp.stack_mut().pop_integer()? as u64;
@@ -221,6 +326,17 @@ fn op_mod_exp(interp: &mut Interpreter) -> Result<()> {
 }
 
 fn op_mod_inv(interp: &mut Interpreter) -> Result<()> {
+    if let Some(args) = pop_bigints(interp, 2)? {
+        let [a, m] = &args[..] else { unreachable!("two operands") };
+        if m.is_negative() || m.is_zero() {
+            return Err(WofError::InvalidArgument("modulus must be positive".into()));
+        }
+        let inv = a.mod_inv(m).ok_or_else(|| {
+            WofError::InvalidArgument(format!("no modular inverse: gcd({a}, {m}) = {}", a.gcd(m)))
+        })?;
+        interp.push(WofValue::bigint(inv));
+        return Ok(());
+    }
     let m = interp.stack_mut().pop_integer()?;
     let a = interp.stack_mut().pop_integer()?;
 
@@ -253,16 +369,13 @@ fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
 
 fn op_diffie_hellman(interp: &mut Interpreter) -> Result<()> {
     // Demonstrate Diffie-Hellman with small parameters
-    let mut rng = rand::thread_rng();
 
     // Small safe prime for demonstration
     let p: u64 = 23;
     let g: u64 = 5;
 
-    // Alice's private key
-    let a: u64 = rng.gen_range(2..p - 1);
-    // Bob's private key
-    let b: u64 = rng.gen_range(2..p - 1);
+    // Alice's and Bob's private keys
+    let (a, b): (u64, u64) = with_rng(interp, |rng| (rng.gen_range(2..p - 1), rng.gen_range(2..p - 1)));
 
     // Public values
     let big_a = mod_pow(g, a, p); // g^a mod p
@@ -272,10 +385,10 @@ fn op_diffie_hellman(interp: &mut Interpreter) -> Result<()> {
     let secret_a = mod_pow(big_b, a, p); // B^a mod p = g^(ab) mod p
     let secret_b = mod_pow(big_a, b, p); // A^b mod p = g^(ab) mod p
 
-    println!("Diffie-Hellman Demo (p={p}, g={g}):");
-    println!("  Alice: private={a}, public={big_a}");
-    println!("  Bob:   private={b}, public={big_b}");
-    println!("  Shared secret: {secret_a} (verified: {})", secret_a == secret_b);
+    writeln!(interp.out(), "Diffie-Hellman Demo (p={p}, g={g}):");
+    writeln!(interp.out(), "  Alice: private={a}, public={big_a}");
+    writeln!(interp.out(), "  Bob:   private={b}, public={big_b}");
+    writeln!(interp.out(), "  Shared secret: {secret_a} (verified: {})", secret_a == secret_b);
 
     interp.push(WofValue::integer(secret_a as i64));
     Ok(())
@@ -308,6 +421,78 @@ fn op_base64_encode(interp: &mut Interpreter) -> Result<()> {
     Ok(())
 }
 
+/// Compare two byte strings in time that depends only on their lengths.
+///
+/// Differences are OR-ed into an accumulator instead of returning early,
+/// and the accumulator goes through [`black_box`] so the optimiser cannot
+/// turn the loop back into an early-exit comparison.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let mut diff = u8::from(a.len() != b.len());
+    for i in 0..a.len().max(b.len()) {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        diff = black_box(diff | (x ^ y));
+    }
+    diff == 0
+}
+
+/// Byte-by-byte comparison that stops at the first mismatch.
+fn naive_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    for (x, y) in a.iter().zip(b) {
+        if x != y {
+            return false;
+        }
+    }
+    true
+}
+
+fn op_ct_eq(interp: &mut Interpreter) -> Result<()> {
+    let b = interp.stack_mut().pop_string()?;
+    let a = interp.stack_mut().pop_string()?;
+    interp.push(WofValue::boolean(constant_time_eq(a.as_bytes(), b.as_bytes())));
+    Ok(())
+}
+
+/// Average time of `runs` calls to `eq(secret, guess)`.
+fn time_compare(eq: fn(&[u8], &[u8]) -> bool, secret: &[u8], guess: &[u8], runs: u32) -> Duration {
+    let start = Instant::now();
+    for _ in 0..runs {
+        black_box(eq(black_box(secret), black_box(guess)));
+    }
+    start.elapsed() / runs
+}
+
+fn op_timing_demo(interp: &mut Interpreter) -> Result<()> {
+    const LEN: usize = 4096;
+    const RUNS: u32 = 1000;
+
+    let out = interp.out();
+    let secret = vec![0x5a_u8; LEN];
+    let wrong_at = |pos: usize| -> Vec<u8> {
+        secret.iter().enumerate().map(|(i, &b)| if i == pos { b ^ 1 } else { b }).collect()
+    };
+    let (early, late) = (wrong_at(0), wrong_at(LEN - 1));
+
+    writeln!(out, "Timing attack demo ({LEN}-byte secret, {RUNS} runs each):");
+    writeln!(out, "  {:<8} {:>16} {:>16} {:>8}", "compare", "wrong @ first", "wrong @ last", "ratio");
+    for (name, eq) in [("naive", naive_eq as fn(&[u8], &[u8]) -> bool), ("ct_eq", constant_time_eq)] {
+        let t_early = time_compare(eq, &secret, &early, RUNS);
+        let t_late = time_compare(eq, &secret, &late, RUNS);
+        let ratio = t_late.as_secs_f64() / t_early.as_secs_f64().max(1e-12);
+        writeln!(
+            out,
+            "  {name:<8} {:>13} ns {:>13} ns {ratio:>7.1}x",
+            t_early.as_nanos(),
+            t_late.as_nanos()
+        );
+    }
+    writeln!(out, "  The naive time grows with the matching prefix; ct_eq's does not.");
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -379,6 +564,36 @@ mod tests {
         assert_eq!(interp.stack().peek().unwrap().as_integer().unwrap(), 24);
     }
 
+    #[test]
+    fn test_bigint_rsa_round_trip() {
+        let mut interp = make_interp();
+        let p = "170141183460469231731687303715884105727";
+        let q = "618970019642690137449562111";
+        interp.exec_line(&format!("{p} prime_check {q} prime_check")).unwrap();
+        assert!(interp.stack_mut().pop_bool().unwrap());
+        assert!(interp.stack_mut().pop_bool().unwrap());
+        interp.exec_line("340282366920938463463374607431768211457 prime_check").unwrap();
+        assert!(!interp.stack_mut().pop_bool().unwrap());
+
+        // n = p·q and φ(n) = (p-1)(q-1), worked out with BigInt
+        let (p, q): (BigInt, BigInt) = (p.parse().unwrap(), q.parse().unwrap());
+        let one = BigInt::one();
+        let n = &p * &q;
+        let phi = &(&p - &one) * &(&q - &one);
+
+        interp.exec_line(&format!("65537 {phi} mod_inv")).unwrap();
+        let d = interp.stack_mut().pop().unwrap().to_string();
+        interp.exec_line(&format!("42424242 65537 {n} mod_exp {d} {n} mod_exp")).unwrap();
+        let message = interp.stack_mut().pop().unwrap();
+        assert_eq!(message.value_type(), WofType::BigInt);
+        assert_eq!(message.as_integer().unwrap(), 42_424_242);
+
+        interp.exec_line("6 123456789012345678901234 mod_inv").unwrap_err();
+        // Small operands keep the 64-bit path and integer results
+        interp.exec_line("4 13 497 mod_exp").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().value_type(), WofType::Integer);
+    }
+
     #[test]
     fn test_hash_deterministic() {
         let mut interp = make_interp();
@@ -391,6 +606,20 @@ mod tests {
         assert_eq!(h1, h2);
     }
 
+    #[test]
+    fn test_ct_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret!"));
+        assert!(!constant_time_eq(b"", b"\0"));
+        assert!(constant_time_eq(b"", b""));
+
+        let mut interp = make_interp();
+        interp.exec_line(r#""hunter2" "hunter2" ct_eq "hunter2" "hunter3" ct_eq"#).unwrap();
+        assert!(!interp.stack_mut().pop_bool().unwrap());
+        assert!(interp.stack_mut().pop_bool().unwrap());
+    }
+
     #[test]
     fn test_next_prime() {
         let mut interp = make_interp();
@@ -400,4 +629,20 @@ mod tests {
         interp.exec_line("11 next_prime").unwrap();
         assert_eq!(interp.stack().peek().unwrap().as_integer().unwrap(), 13);
     }
+
+    #[test]
+    fn test_seed_makes_random_reproducible() {
+        let mut interp = make_interp();
+        let draw = "parameterize \"seed\" 42 ⺆ 1 1000000 random rand ⺘";
+        interp.exec_line(draw).unwrap();
+        let first = interp.stack().as_slice().to_vec();
+        interp.stack_mut().clear();
+        interp.exec_line(draw).unwrap();
+        assert_eq!(interp.stack().as_slice(), first.as_slice());
+        assert!(interp.param("seed").unwrap().is_nil());
+
+        interp.set_param("seed", WofValue::integer(7)).unwrap();
+        assert_eq!(interp.param("seed").unwrap().as_integer().unwrap(), 7);
+        assert!(interp.set_param("seed", WofValue::string("x")).is_err());
+    }
 }
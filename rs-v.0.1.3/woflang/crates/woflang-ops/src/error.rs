@@ -0,0 +1,113 @@
+//! Error values, as `try ⺆ ... ⺘ catch ⺆ ... ⺘` hands them to the catch
+//! block.
+//!
+//! A catch block starts with an `Error` record on the stack. Its fields
+//! are read with the record accessors registered here, and `throw`
+//! raises an error of the script's own:
+//!
+//! | Operation  | Stack Effect    | Description |
+//! |------------|-----------------|-------------|
+//! | `.message` | ( e -- s )      | What went wrong |
+//! | `.kind`    | ( e -- s )      | Kind of error, e.g. `division_by_zero` |
+//! | `.line`    | ( e -- n )      | Line of the failing token (0 if unknown) |
+//! | `.column`  | ( e -- n )      | Its column (0 if unknown) |
+//! | `Error?`   | ( v -- bool )   | Is `v` an `Error`? |
+//! | `throw`    | ( msg -- )      | Raise a runtime error, or re-raise an `Error` |
+//!
+//! `Error_new` ( msg kind line column -- e ) and `with_message`,
+//! `with_kind`, `with_line` and `with_column` build and update `Error`
+//! records like those of any other record type.
+//!
+//! ```text
+//! ⊕ safe_div ⺆ try ⺆ / ⺘ catch ⺆ .message print 0 ⺘ ⺘
+//! 6 3 safe_div                          # → 2
+//! 1 0 safe_div                          # prints "division by zero", → 0
+//! try ⺆ "bad input" throw ⺘ catch ⺆ .message ⺘    # → "bad input"
+//! ```
+//!
+//! A re-raised `Error` keeps its message and location but is reported as
+//! a `runtime` error.
+
+use woflang_core::{InterpreterContext, Result, Span, WofError, WofValue};
+use woflang_runtime::{Interpreter, ERROR_FIELDS};
+
+/// Register the error operations.
+pub fn register(interp: &mut Interpreter) {
+    interp
+        .define_record("Error", &ERROR_FIELDS.map(String::from))
+        .expect("the Error record is well formed");
+    interp.register("throw", op_throw);
+
+    crate::describe(interp, "error", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&[".message"], "( e -- s )", "What went wrong", "try ⺆ 1 0 / ⺘ catch ⺆ .message ⺘"),
+    (&[".kind"], "( e -- s )", "Kind of error, e.g. division_by_zero", "try ⺆ 1 0 / ⺘ catch ⺆ .kind ⺘"),
+    (&[".line"], "( e -- n )", "Line of the failing token, 0 if unknown", ""),
+    (&[".column"], "( e -- n )", "Column of the failing token, 0 if unknown", ""),
+    (&["Error?"], "( v -- bool )", "Is the value an Error record?", ""),
+    (
+        &["Error_new"],
+        "( msg kind line column -- e )",
+        "Build an Error record",
+        "\"oops\" \"runtime\" 0 0 Error_new .kind",
+    ),
+    (&["with_message"], "( rec s -- rec' )", "Copy a record with its message replaced", ""),
+    (&["with_kind"], "( rec s -- rec' )", "Copy a record with its kind replaced", ""),
+    (&["with_line"], "( rec n -- rec' )", "Copy a record with its line replaced", ""),
+    (&["with_column"], "( rec n -- rec' )", "Copy a record with its column replaced", ""),
+    (
+        &["throw"],
+        "( msg -- )",
+        "Raise a runtime error with the message, or re-raise an Error",
+        "try ⺆ \"bad input\" throw ⺘ catch ⺆ .message ⺘",
+    ),
+];
+
+fn op_throw(interp: &mut Interpreter) -> Result<()> {
+    let value = interp.stack_mut().pop()?;
+    let Ok(error) = value.as_record() else {
+        return Err(WofError::Runtime(value.as_string()?));
+    };
+    if error.record_type().name() != "Error" {
+        return Err(WofError::type_mismatch("string or Error", value.value_type()));
+    }
+    let field = |name: &str| error.get(name).cloned().unwrap_or_else(WofValue::nil);
+    let message = field("message").as_string()?;
+    let line = field("line").as_integer().unwrap_or(0);
+    let column = field("column").as_integer().unwrap_or(0);
+    match (u32::try_from(line), u32::try_from(column)) {
+        (Ok(line @ 1..), Ok(column)) => Err(WofError::runtime_at(message, Span::new(line, column, 0))),
+        _ => Err(WofError::Runtime(message)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        crate::register_all(&mut interp);
+        interp
+    }
+
+    #[test]
+    fn test_catch_reads_and_rethrows_errors() {
+        let mut interp = make_interp();
+        interp.exec_line("try ⺆ 1 0 / ⺘ catch ⺆ dup Error? swap .kind ⺘").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::string("division_by_zero"));
+        assert!(interp.stack_mut().pop_bool().unwrap());
+
+        interp.exec_line(r#"try ⺆ "bad input" throw ⺘ catch ⺆ .message ⺘"#).unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::string("bad input"));
+
+        let err = interp.exec_source("\ntry ⺆ 1 0 / ⺘ catch ⺆ throw ⺘").unwrap_err();
+        assert_eq!(err.span().map(|s| (s.line(), s.column())), Some((2, 11)));
+
+        assert!(interp.exec_line("5 throw").is_err());
+        assert!(interp.exec_line(r#"record "P" "x" 1 P_new throw"#).is_err());
+    }
+}
@@ -0,0 +1,301 @@
+//! File operations.
+//!
+//! | Operation        | Stack Effect      | Description |
+//! |------------------|-------------------|-------------|
+//! | `file_read`      | (path -- s)       | Read a whole text file |
+//! | `file_write`     | (s path -- )      | Create or replace a file |
+//! | `file_append`    | (s path -- )      | Append to a file, creating it if needed |
+//! | `file_exists`    | (path -- bool)    | Whether a file or directory exists |
+//! | `dir_list`       | (path -- list)    | Sorted entry names of a directory |
+//! | `file_open`      | (path mode -- fh) | Open a file to read ("r"), write ("w") or append ("a") |
+//! | `file_read_line` | (fh -- s)         | Next line without its newline, nil at the end |
+//! | `file_put`       | (s fh -- )        | Write to an open file |
+//! | `file_close`     | (fh -- )          | Close an open file |
+//!
+//! File ops go through the interpreter's [`Sandbox`](woflang_runtime::Sandbox):
+//! paths outside its allowed roots fail, as do writes in read-only mode.
+//! Relative paths are relative to the working directory.
+//!
+//! An open file is an integer handle. `with_file` closes it when its
+//! block ends, even on error; the mode defaults to reading:
+//!
+//! ```text
+//! with_file "log.txt" "a" ⺆ "started" swap file_put ⺘
+//! with_file "log.txt" ⺆ file_read_line print ⺘
+//! ```
+//!
+//! Built with the `file-ops` feature (on by default); leave it out for
+//! targets without a filesystem, such as the browser.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead as _, BufReader, Write as _};
+
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Register all file operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("file_read", op_file_read);
+    interp.register("file_write", op_file_write);
+    interp.register("file_append", op_file_append);
+    interp.register("file_exists", op_file_exists);
+    interp.register("dir_list", op_dir_list);
+    interp.register("file_open", op_file_open);
+    interp.register("file_read_line", op_file_read_line);
+    interp.register("file_put", op_file_put);
+    interp.register("file_close", op_file_close);
+
+    interp.define_resource("file", open_file_resource, close_file_resource);
+
+    crate::describe(interp, "file", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["file_read"], "( path -- s )", "Read a whole text file", ""),
+    (&["file_write"], "( s path -- )", "Create or replace a file", ""),
+    (&["file_append"], "( s path -- )", "Append to a file, creating it if needed", ""),
+    (&["file_exists"], "( path -- bool )", "Whether a file or directory exists", "\".\" file_exists"),
+    (&["dir_list"], "( path -- list )", "Sorted entry names of a directory", "\".\" dir_list"),
+    (&["file_open"], "( path mode -- fh )", "Open a file to read (\"r\"), write (\"w\") or append (\"a\")", ""),
+    (&["file_read_line"], "( fh -- s )", "Next line without its newline, nil at the end", ""),
+    (&["file_put"], "( s fh -- )", "Write to an open file", ""),
+    (&["file_close"], "( fh -- )", "Close an open file", ""),
+];
+
+fn op_file_read(interp: &mut Interpreter) -> Result<()> {
+    let path = interp.stack_mut().pop_string()?;
+    let resolved = interp.sandbox().check_read(&path)?;
+    let text = fs::read_to_string(resolved).map_err(|e| WofError::Io(format!("file_read: {path}: {e}")))?;
+    interp.push(WofValue::string(text));
+    Ok(())
+}
+
+/// Write or append the string under a path, as `op` names.
+fn write_file(interp: &mut Interpreter, op: &str, append: bool) -> Result<()> {
+    let path = interp.stack_mut().pop_string()?;
+    let text = interp.stack_mut().pop()?.to_string();
+    let resolved = interp.sandbox().check_write(&path)?;
+    OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(resolved)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .map_err(|e| WofError::Io(format!("{op}: {path}: {e}")))
+}
+
+fn op_file_write(interp: &mut Interpreter) -> Result<()> {
+    write_file(interp, "file_write", false)
+}
+
+fn op_file_append(interp: &mut Interpreter) -> Result<()> {
+    write_file(interp, "file_append", true)
+}
+
+fn op_file_exists(interp: &mut Interpreter) -> Result<()> {
+    let path = interp.stack_mut().pop_string()?;
+    let exists = interp.sandbox().check_read(&path)?.exists();
+    interp.push(WofValue::boolean(exists));
+    Ok(())
+}
+
+fn op_dir_list(interp: &mut Interpreter) -> Result<()> {
+    let path = interp.stack_mut().pop_string()?;
+    let resolved = interp.sandbox().check_read(&path)?;
+    let mut names = fs::read_dir(resolved)
+        .and_then(|entries| {
+            entries.map(|entry| entry.map(|e| e.file_name().to_string_lossy().into_owned())).collect::<std::io::Result<Vec<_>>>()
+        })
+        .map_err(|e| WofError::Io(format!("dir_list: {path}: {e}")))?;
+    names.sort();
+    interp.push(WofValue::list(names.into_iter().map(WofValue::string).collect()));
+    Ok(())
+}
+
+/// A file opened by `file_open`.
+enum OpenFile {
+    Read(BufReader<File>),
+    Write(File),
+}
+
+/// Open files by handle.
+#[derive(Default)]
+struct Files {
+    next: i64,
+    open: BTreeMap<i64, OpenFile>,
+}
+
+/// Open `path` in `mode` and return its handle.
+fn open_file(interp: &mut Interpreter, path: &str, mode: &str) -> Result<i64> {
+    let io_error = |e: std::io::Error| WofError::Io(format!("file_open: {path}: {e}"));
+    let file = match mode {
+        "r" => OpenFile::Read(BufReader::new(File::open(interp.sandbox().check_read(path)?).map_err(io_error)?)),
+        "w" | "a" => {
+            let resolved = interp.sandbox().check_write(path)?;
+            let mut options = OpenOptions::new();
+            options.create(true);
+            if mode == "a" {
+                options.append(true);
+            } else {
+                options.write(true).truncate(true);
+            }
+            OpenFile::Write(options.open(resolved).map_err(io_error)?)
+        }
+        _ => return Err(WofError::InvalidArgument(format!("file_open: mode must be \"r\", \"w\" or \"a\", not \"{mode}\""))),
+    };
+    let files = interp.state_mut::<Files>();
+    files.next += 1;
+    files.open.insert(files.next, file);
+    Ok(files.next)
+}
+
+/// The open file behind `handle`.
+fn open_file_mut<'a>(interp: &'a mut Interpreter, handle: i64, op: &str) -> Result<&'a mut OpenFile> {
+    interp
+        .state_mut::<Files>()
+        .open
+        .get_mut(&handle)
+        .ok_or_else(|| WofError::Runtime(format!("{op}: no open file {handle}")))
+}
+
+fn op_file_open(interp: &mut Interpreter) -> Result<()> {
+    let mode = interp.stack_mut().pop_string()?;
+    let path = interp.stack_mut().pop_string()?;
+    let handle = open_file(interp, &path, &mode)?;
+    interp.push(WofValue::integer(handle));
+    Ok(())
+}
+
+fn op_file_read_line(interp: &mut Interpreter) -> Result<()> {
+    let handle = interp.stack_mut().pop_integer()?;
+    let OpenFile::Read(reader) = open_file_mut(interp, handle, "file_read_line")? else {
+        return Err(WofError::Runtime(format!("file_read_line: file {handle} is open for writing")));
+    };
+    let mut line = String::new();
+    let read = reader.read_line(&mut line).map_err(|e| WofError::Io(format!("file_read_line: {e}")))?;
+    let value = if read == 0 {
+        WofValue::nil()
+    } else {
+        let end = line.trim_end_matches(['\n', '\r']).len();
+        line.truncate(end);
+        WofValue::string(line)
+    };
+    interp.push(value);
+    Ok(())
+}
+
+fn op_file_put(interp: &mut Interpreter) -> Result<()> {
+    let handle = interp.stack_mut().pop_integer()?;
+    let text = interp.stack_mut().pop()?.to_string();
+    let OpenFile::Write(file) = open_file_mut(interp, handle, "file_put")? else {
+        return Err(WofError::Runtime(format!("file_put: file {handle} is open for reading")));
+    };
+    file.write_all(text.as_bytes()).map_err(|e| WofError::Io(format!("file_put: {e}")))
+}
+
+fn op_file_close(interp: &mut Interpreter) -> Result<()> {
+    let handle = interp.stack_mut().pop_integer()?;
+    match interp.state_mut::<Files>().open.remove(&handle) {
+        Some(_) => Ok(()),
+        None => Err(WofError::Runtime(format!("file_close: no open file {handle}"))),
+    }
+}
+
+/// Open the file for `with_file "path" [mode]`.
+fn open_file_resource(interp: &mut Interpreter, args: Vec<WofValue>) -> Result<WofValue> {
+    let (path, mode) = match args.as_slice() {
+        [path] => (path.as_str()?, "r"),
+        [path, mode] => (path.as_str()?, mode.as_str()?),
+        _ => return Err(WofError::Runtime("expected a path and optional mode".into())),
+    };
+    Ok(WofValue::integer(open_file(interp, path, mode)?))
+}
+
+/// Close a `with_file` handle, unless the block already closed it.
+fn close_file_resource(interp: &mut Interpreter, handle: WofValue) -> Result<()> {
+    interp.state_mut::<Files>().open.remove(&handle.as_integer()?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        interp
+    }
+
+    #[test]
+    fn test_files_in_a_sandbox() {
+        let dir = std::env::temp_dir().join(format!("woflang-io-{}", std::process::id()));
+        fs::create_dir_all(dir.join("data")).unwrap();
+        let at = |name: &str| dir.join(name).display().to_string();
+
+        let mut interp = make_interp();
+        let mut sandbox = woflang_runtime::Sandbox::default();
+        sandbox.allow(dir.join("data")).unwrap();
+        interp.set_sandbox(sandbox);
+
+        let file = at("data/notes.txt");
+        interp.exec_line(&format!(r#""one" "{file}" file_write 2 "{file}" file_append "{file}" file_read"#)).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "one2");
+        interp.exec_line(&format!(r#""{file}" file_exists "{}" file_exists"#, at("data/nope"))).unwrap();
+        assert!(!interp.stack_mut().pop().unwrap().is_truthy());
+        assert!(interp.stack_mut().pop().unwrap().is_truthy());
+        interp.exec_line(&format!(r#""x" "{}" file_write "{}" dir_list"#, at("data/a.txt"), at("data"))).unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "[a.txt, notes.txt]");
+
+        assert!(interp.exec_line(&format!(r#""x" "{}" file_write"#, at("escape.txt"))).is_err());
+        assert!(interp.exec_line(&format!(r#""{}" file_read"#, at("data/../escape.txt"))).is_err());
+        assert!(interp.exec_line(&format!(r#""{}" file_read"#, at("data/nope"))).is_err());
+
+        let mut read_only = interp.sandbox().clone();
+        read_only.read_only = true;
+        interp.set_sandbox(read_only);
+        assert!(interp.exec_line(&format!(r#""x" "{file}" file_write"#)).is_err());
+        interp.exec_line(&format!(r#""{file}" file_read"#)).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "one2");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_with_file() {
+        let dir = std::env::temp_dir().join(format!("woflang-with-file-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("log.txt").display().to_string();
+        let mut interp = Interpreter::new();
+        crate::register_all(&mut interp);
+        let mut sandbox = woflang_runtime::Sandbox::default();
+        sandbox.allow(&dir).unwrap();
+        interp.set_sandbox(sandbox);
+
+        interp.exec_line(&format!(r#"with_file "{file}" "w" ⺆ "one\ntwo" swap file_put ⺘"#)).unwrap();
+        interp.exec_line(&format!(r#"with_file "{file}" ⺆ dup file_read_line swap dup file_read_line swap file_read_line ⺘"#)).unwrap();
+        assert!(interp.stack_mut().pop().unwrap().is_nil());
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "two");
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "one");
+
+        // The handle is closed after the block, even when it fails.
+        assert!(interp.exec_line(&format!(r#"with_file "{file}" "a" ⺆ dup 1 0 / ⺘"#)).is_err());
+        let handle = interp.stack_mut().pop_integer().unwrap();
+        assert!(interp.exec_line(&format!("{handle} file_close")).is_err());
+        interp.exec_line(&format!(r#"with_file "{file}" ⺆ file_close ⺘"#)).unwrap();
+
+        interp.exec_line(&format!(r#""{file}" "a" file_open"#)).unwrap();
+        interp.exec_line("\"!\" over file_put dup file_read_line").unwrap_err();
+        interp.exec_line("file_close").unwrap();
+        interp.exec_line(&format!(r#""{file}" file_read"#)).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "one\ntwo!");
+
+        assert!(interp.exec_line(&format!(r#""{file}" "x" file_open"#)).is_err());
+        assert!(interp.exec_line(r#"with_file "/etc/passwd" ⺆ ⺘"#).is_err());
+        assert!(interp.exec_line("with_file ⺆ ⺘").is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
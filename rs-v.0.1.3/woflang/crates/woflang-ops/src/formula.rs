@@ -0,0 +1,164 @@
+//! Formulas: Woflang expressions from untrusted users, evaluated safely.
+//!
+//! [`eval_expr`] lets a host application evaluate formulas its end users
+//! write, in config fields and the like, with values bound to names:
+//!
+//! ```
+//! use woflang_core::WofValue;
+//! use woflang_ops::formula::eval_expr;
+//!
+//! let vars = [("price", WofValue::double(2.5)), ("qty", WofValue::integer(4)), ("tax", WofValue::double(0.25))];
+//! let total = eval_expr("price qty * =(1 + tax) *", &vars).unwrap();
+//! assert_eq!(total.as_double().unwrap(), 12.5);
+//! ```
+//!
+//! Every token is checked before anything runs. A formula may use only
+//! number and string literals, `=( ... )` infix, the bound names, and
+//! the [`arithmetic`](crate::arithmetic), [`math`](crate::math),
+//! [`constants`](crate::constants) and [`logic`](crate::logic) ops,
+//! except `evaluate`, which runs a string as code. Anything else
+//! (blocks, definitions, variables, I/O, other ops) is a parse error at
+//! the token. Without blocks a formula cannot loop, so its work is
+//! bounded by its length, which is at most [`MAX_TOKENS`] tokens.
+//!
+//! Bound names take precedence over ops of the same name, so a host can
+//! bind `e` or `c`. Key bindings are not expanded, so `lambda` or `begin`
+//! is just a name. The formula must leave exactly one value.
+
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::{infix, is_special_form, Interpreter, OwnedToken, TokenKind, Tokenizer};
+
+/// Most tokens a formula may have, counting those of its infix parts.
+pub const MAX_TOKENS: usize = 1_000;
+
+/// Ops of the allowed modules that formulas may not use.
+const EXCLUDED: &[&str] = &["evaluate", "calc"];
+
+/// Evaluate the formula `source` with each `(name, value)` of `vars`
+/// bound, returning the value it leaves.
+///
+/// # Errors
+///
+/// Returns [`WofError::Parse`] for a token formulas may not use,
+/// [`WofError::InvalidArgument`] for a name that cannot be bound or a
+/// formula over [`MAX_TOKENS`] tokens, and the formula's own error if it
+/// fails or does not leave exactly one value.
+pub fn eval_expr(source: &str, vars: &[(&str, WofValue)]) -> Result<WofValue> {
+    let mut interp = Interpreter::new();
+    interp.expand_bindings = false;
+    crate::arithmetic::register(&mut interp);
+    crate::math::register(&mut interp);
+    crate::constants::register(&mut interp);
+    crate::logic::register(&mut interp);
+    for name in EXCLUDED {
+        interp.registry_mut().remove(name);
+    }
+    for (name, value) in vars {
+        if !is_bindable(name) {
+            return Err(WofError::InvalidArgument(format!("formula: cannot bind '{name}'")));
+        }
+        interp.registry_mut().remove(name);
+        interp.define_var(*name, value.clone());
+    }
+
+    let mut count = 0;
+    for token in Tokenizer::new(source).tokenize_all() {
+        let token = OwnedToken::from(token);
+        for token in expand(token)? {
+            check(&interp, &token)?;
+            count += 1;
+        }
+    }
+    if count > MAX_TOKENS {
+        return Err(WofError::InvalidArgument(format!(
+            "formula: {count} tokens, more than the {MAX_TOKENS} allowed"
+        )));
+    }
+
+    interp.exec_source(source)?;
+    match interp.stack().len() {
+        1 => interp.stack_mut().pop(),
+        n => Err(WofError::Runtime(format!("formula: expected one value, left {n}"))),
+    }
+}
+
+/// Whether `name` reads back as a single name in a formula.
+fn is_bindable(name: &str) -> bool {
+    matches!(
+        Tokenizer::new(name).tokenize_all().as_slice(),
+        [token] if token.kind == TokenKind::Symbol && token.text == name && !is_special_form(name)
+    )
+}
+
+/// The tokens `token` runs as: an infix token's compiled tokens, or the
+/// token itself.
+fn expand(token: OwnedToken) -> Result<Vec<OwnedToken>> {
+    if token.kind != TokenKind::Infix {
+        return Ok(vec![token]);
+    }
+    let inner = token
+        .text
+        .strip_prefix("=(")
+        .and_then(|t| t.strip_suffix(')'))
+        .ok_or_else(|| WofError::parse("unclosed infix expression", token.span))?;
+    infix::compile(inner, token.span)
+}
+
+/// Refuse a token formulas may not use.
+fn check(interp: &Interpreter, token: &OwnedToken) -> Result<()> {
+    let allowed = match token.kind {
+        TokenKind::Integer | TokenKind::Float | TokenKind::String | TokenKind::Eof => true,
+        TokenKind::Symbol => {
+            let name = token.text.as_str();
+            !is_special_form(name) && (interp.has_var(name) || interp.registry().contains(name))
+        }
+        TokenKind::Interpolated | TokenKind::Label | TokenKind::LabelRef | TokenKind::Infix => false,
+    };
+    if allowed {
+        Ok(())
+    } else {
+        Err(WofError::parse(format!("'{}' is not allowed in a formula", token.text), token.span))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval_expr_allows_only_math_on_bound_names() {
+        let vars = [("x", WofValue::integer(3)), ("e", WofValue::integer(10))];
+        assert_eq!(eval_expr("x x * e +", &vars).unwrap(), WofValue::integer(19));
+        assert_eq!(eval_expr("=(max(x, 2) * e) x >", &vars).unwrap(), WofValue::boolean(true));
+        assert_eq!(eval_expr("pi floor", &[]).unwrap().as_integer().unwrap(), 3);
+
+        for (source, token) in [
+            ("x print", "print"),
+            ("⊕ f ⺆ x ⺘ x", "⊕"),
+            ("1 ⟳ ⺆ x ⺘", "⟳"),
+            ("x 字 y", "字"),
+            ("\"1 1 +\" evaluate", "evaluate"),
+            ("=(x + y)", "y"),
+            ("$\"{x}\"", "$\"{x}\""),
+        ] {
+            let err = eval_expr(source, &vars).unwrap_err();
+            assert_eq!(err.kind(), "parse", "{source}");
+            assert!(err.to_string().contains(&format!("'{token}' is not allowed")), "{source}: {err}");
+        }
+
+        assert!(eval_expr("1 0 /", &vars).is_err());
+        assert!(eval_expr("1 2", &vars).unwrap_err().to_string().contains("left 2"));
+        assert!(eval_expr(&"1 ".repeat(MAX_TOKENS + 1), &vars).is_err());
+        // Key bindings are not expanded: these are plain names.
+        let vars = [("lambda", WofValue::integer(1)), ("alpha", WofValue::integer(2)), ("sum", WofValue::integer(4))];
+        assert_eq!(eval_expr("lambda alpha + sum *", &vars).unwrap(), WofValue::integer(12));
+        let vars = [("begin", WofValue::integer(1)), ("end", WofValue::integer(2))];
+        assert_eq!(eval_expr("begin end -", &vars).unwrap(), WofValue::integer(-1));
+        let err = eval_expr("begin 1 end", &[]).unwrap_err();
+        assert!(err.to_string().contains("'begin' is not allowed"), "{err}");
+
+        for name in ["if", "two words", "", "\"s\""] {
+            assert!(eval_expr("1", &[(name, WofValue::integer(1))]).is_err(), "{name}");
+        }
+    }
+}
@@ -8,8 +8,18 @@
 //! | `show`     | (a -- a)     | Print without consuming |
 //! | `cr`       | ( -- )       | Print newline |
 //! | `emit`     | (n -- )      | Print char by codepoint |
+//! | `explain`  | (a -- a)     | Print the infix expression behind the top value |
+//!
+//! `print`, `show` and `.` write doubles with as many decimal places as
+//! the `precision` parameter asks for, or in full when it is nil:
+//!
+//! ```text
+//! parameterize "precision" 2 ⺆ π print ⺘   # 3.14
+//! ```
+//!
+//! File operations are in [`file`](crate::file).
 
-use woflang_core::{InterpreterContext, Result, WofValue};
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
 use woflang_runtime::Interpreter;
 
 /// Register all I/O operations.
@@ -24,54 +34,111 @@ pub fn register(interp: &mut Interpreter) {
     interp.register("spaces", op_spaces);
     interp.register("type", op_type);
     interp.register("typeof", op_typeof);
+    interp.register("explain", op_explain);
+
+    interp.define_param("precision", precision, set_precision);
+
+    crate::describe(interp, "io", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["print"], "( a -- )", "Print top value", "42 print"),
+    (&[".", ".s"], "( -- )", "Display stack", "1 2 3 ."),
+    (&["show"], "( a -- a )", "Print without consuming", "42 show"),
+    (&["cr"], "( -- )", "Print newline", "cr"),
+    (&["emit"], "( n -- )", "Print char by codepoint", "65 emit"),
+    (&["space"], "( -- )", "Print a space", "space"),
+    (&["spaces"], "( n -- )", "Print n spaces", "3 spaces"),
+    (&["type"], "( s -- )", "Print a string without a newline", "\"wolf\" type"),
+    (&["typeof"], "( a -- a type )", "Push the type name of the top value", "42 typeof"),
+    (&["explain"], "( a -- a )", "Print the infix expression behind the top value", "2 3 + 4 * explain"),
+];
+
+/// Decimal places for printed doubles; `None` prints them in full.
+#[derive(Default)]
+struct Precision(Option<usize>);
+
+fn precision(interp: &Interpreter) -> WofValue {
+    match interp.state::<Precision>().and_then(|p| p.0) {
+        Some(places) => WofValue::integer(places as i64),
+        None => WofValue::nil(),
+    }
+}
+
+/// Set the decimal places for printed doubles, or nil for full output.
+fn set_precision(interp: &mut Interpreter, value: WofValue) -> Result<()> {
+    let places = if value.is_nil() {
+        None
+    } else {
+        let places = value.as_integer()?;
+        Some(usize::try_from(places).ok().filter(|&p| p <= 100).ok_or_else(|| {
+            WofError::InvalidArgument(format!("precision must be 0 to 100 or nil, not {places}"))
+        })?)
+    };
+    interp.state_mut::<Precision>().0 = places;
+    Ok(())
 }
 
 fn op_print(interp: &mut Interpreter) -> Result<()> {
     let val = interp.stack_mut().pop()?;
-    println!("{val}");
+    writeln!(interp.out(), "{}", interp.display(&val));
     Ok(())
 }
 
 fn op_show_stack(interp: &mut Interpreter) -> Result<()> {
-    println!("{}", interp.stack());
+    let values = interp.stack().as_slice();
+    if values.is_empty() {
+        interp.out().println("Stack[0]: (empty)");
+    } else {
+        let shown: Vec<String> = values.iter().map(|v| interp.display(v)).collect();
+        writeln!(interp.out(), "Stack[{}]: {}", values.len(), shown.join(", "));
+    }
     Ok(())
 }
 
 fn op_show(interp: &mut Interpreter) -> Result<()> {
     let val = interp.stack().peek()?;
-    println!("{val}");
+    writeln!(interp.out(), "{}", interp.display(val));
+    Ok(())
+}
+
+fn op_explain(interp: &mut Interpreter) -> Result<()> {
+    let top = interp.stack().peek()?.to_string();
+    match interp.explain() {
+        Some(expr) if expr != top => writeln!(interp.out(), "{expr} = {top}"),
+        _ => writeln!(interp.out(), "{top}"),
+    }
     Ok(())
 }
 
-fn op_cr(_interp: &mut Interpreter) -> Result<()> {
-    println!();
+fn op_cr(interp: &mut Interpreter) -> Result<()> {
+    writeln!(interp.out());
     Ok(())
 }
 
 fn op_emit(interp: &mut Interpreter) -> Result<()> {
     let code = interp.stack_mut().pop_integer()? as u32;
     if let Some(c) = char::from_u32(code) {
-        print!("{c}");
+        write!(interp.out(), "{c}");
     }
     Ok(())
 }
 
-fn op_space(_interp: &mut Interpreter) -> Result<()> {
-    print!(" ");
+fn op_space(interp: &mut Interpreter) -> Result<()> {
+    interp.out().print(" ");
     Ok(())
 }
 
 fn op_spaces(interp: &mut Interpreter) -> Result<()> {
     let n = interp.stack_mut().pop_integer()?;
-    for _ in 0..n {
-        print!(" ");
-    }
+    interp.out().print(&" ".repeat(usize::try_from(n).unwrap_or(0)));
     Ok(())
 }
 
 fn op_type(interp: &mut Interpreter) -> Result<()> {
     let s = interp.stack_mut().pop_string()?;
-    print!("{s}");
+    interp.out().print(&s);
     Ok(())
 }
 
@@ -101,4 +168,19 @@ mod tests {
         interp.exec_line("3.14 typeof").unwrap();
         assert_eq!(interp.stack_mut().pop_string().unwrap(), "double");
     }
+
+    #[test]
+    fn test_precision() {
+        let mut interp = make_interp();
+        let pi = WofValue::double(std::f64::consts::PI);
+        assert_eq!(interp.display(&pi), std::f64::consts::PI.to_string());
+        interp.exec_line("parameterize \"precision\" 2 ⺆ 1 print ⺘").unwrap();
+        assert!(interp.param("precision").unwrap().is_nil());
+        interp.set_param("precision", WofValue::integer(3)).unwrap();
+        assert_eq!(interp.display(&pi), "3.142");
+        assert_eq!(interp.display(&WofValue::integer(7)), "7");
+        assert!(interp.set_param("precision", WofValue::integer(-1)).is_err());
+        interp.set_param("precision", WofValue::nil()).unwrap();
+        assert!(interp.param("precision").unwrap().is_nil());
+    }
 }
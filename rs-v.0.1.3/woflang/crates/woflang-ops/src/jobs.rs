@@ -0,0 +1,106 @@
+//! Job control for background work started with `spawn ⺆ body ⺘`.
+//!
+//! | Operation   | Stack Effect       | Description |
+//! |-------------|--------------------|-------------|
+//! | `jobs`      | ( -- )             | List jobs that have not been joined |
+//! | `join`      | ( id -- ... )      | Wait for a job and push its final stack |
+//! | `job_done?` | ( id -- bool )     | True once the job has finished |
+//!
+//! ```text
+//! spawn ⺆ 0 1000000 ⨯ ⺆ 1 + ⺘ ⺘   # → 1
+//! 1 job_done?                     # false while it runs
+//! 1 join                          # → 1000000
+//! ```
+
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Register the job control operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("jobs", op_jobs);
+    interp.register("join", op_join);
+    interp.register("job_done?", op_job_done);
+
+    crate::describe(interp, "jobs", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["jobs"], "( -- )", "List jobs that have not been joined", "jobs"),
+    (&["join"], "( id -- ... )", "Wait for a job and push its final stack", "spawn ⺆ 2 3 + ⺘ join"),
+    (&["job_done?"], "( id -- bool )", "True once the job has finished", "spawn ⺆ 1 ⺘ job_done?"),
+];
+
+fn pop_id(interp: &mut Interpreter, op: &str) -> Result<usize> {
+    let id = interp.stack_mut().pop_integer()?;
+    usize::try_from(id).map_err(|_| WofError::InvalidArgument(format!("{op}: bad job id {id}")))
+}
+
+fn op_jobs(interp: &mut Interpreter) -> Result<()> {
+    let out = interp.out();
+    let jobs = interp.jobs();
+    if jobs.is_empty() {
+        out.println("No jobs.");
+    }
+    for job in jobs {
+        let state = if job.finished { "done" } else { "running" };
+        writeln!(out, "[{}] {state:<7} {:>8.1?}  {}", job.id, job.elapsed, job.source);
+    }
+    Ok(())
+}
+
+fn op_join(interp: &mut Interpreter) -> Result<()> {
+    let id = pop_id(interp, "join")?;
+    let stack = interp.join_job(id)?;
+    interp.stack_mut().push_all(stack);
+    Ok(())
+}
+
+fn op_job_done(interp: &mut Interpreter) -> Result<()> {
+    let id = pop_id(interp, "job_done?")?;
+    let job = interp
+        .jobs()
+        .into_iter()
+        .find(|job| job.id == id)
+        .ok_or_else(|| WofError::Runtime(format!("job_done?: no job {id}")))?;
+    interp.push(WofValue::boolean(job.finished));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        crate::register_all(&mut interp);
+        interp
+    }
+
+    #[test]
+    fn test_spawn_and_join() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ sq ⺆ dup * ⺘").unwrap();
+        interp.exec_line("9 spawn ⺆ 4 sq 1 ⺘ spawn ⺆ 7 ⺘").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 2);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1);
+        assert_eq!(interp.jobs().len(), 2);
+
+        interp.exec_line("2 join 1 join").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 16);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 7);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 9);
+        assert!(interp.jobs().is_empty());
+        assert!(interp.exec_line("1 join").is_err());
+    }
+
+    #[test]
+    fn test_failed_job() {
+        let mut interp = make_interp();
+        interp.exec_line("spawn ⺆ drop ⺘").unwrap();
+        interp.exec_line("join").unwrap_err();
+        assert!(interp.stack().is_empty());
+        assert!(interp.exec_line("spawn ⺆ 1").is_err());
+    }
+}
@@ -10,6 +10,23 @@
 //! - [`quantum`]: Quantum computing simulation
 //! - [`crypto`]: Cryptographic primitives
 //! - [`io`]: Input/output operations
+//! - [`file`]: Files and directories, in the sandbox
+//! - [`jobs`]: Job control for `spawn`ed background work
+//! - [`queue`]: Deques and priority queues
+//! - [`list`]: List values and element operations
+//! - [`map`]: Map (dictionary) values
+//! - [`option`]: Option and result values
+//! - [`error`]: Error records from `try`/`catch`, and `throw`
+//! - [`formula`]: Evaluating untrusted formulas with [`formula::eval_expr`]
+//! - [`reflect`]: Registry introspection (aliases, glyph/ASCII names)
+//! - [`schema`]: Validating map and list data against schemas
+//! - [`serialize`]: JSON parsing and encoding
+//! - [`stdlib`]: Standard library functions written in Woflang
+//! - [`trace`]: Educational trace of math operations
+//!
+//! Each module also attaches [`OpMeta`] to its ops (category, stack
+//! effect, help text and a runnable example), which drives the CLI's
+//! `help <op>` and generated reference docs.
 //!
 //! ## Usage
 //!
@@ -25,6 +42,12 @@
 //!
 //! interp.exec_line("2 3 +").unwrap();
 //! ```
+//!
+//! ## Features
+//!
+//! `file-ops` (default) adds the file operations; build without it, and
+//! without `crypto-ops` and `quantum-ops`, which need an OS random
+//! source, for `wasm32-unknown-unknown`.
 
 #![deny(unsafe_code)]
 #![warn(missing_docs)]
@@ -34,14 +57,28 @@ pub mod arithmetic;
 pub mod constants;
 #[cfg(feature = "crypto-ops")]
 pub mod crypto;
+pub mod error;
+#[cfg(feature = "file-ops")]
+pub mod file;
+pub mod formula;
 pub mod io;
+pub mod jobs;
+pub mod list;
 pub mod logic;
+pub mod map;
 pub mod math;
+pub mod option;
 #[cfg(feature = "quantum-ops")]
 pub mod quantum;
+pub mod queue;
+pub mod reflect;
+pub mod schema;
+pub mod serialize;
 pub mod stack;
+pub mod stdlib;
+pub mod trace;
 
-use woflang_runtime::Interpreter;
+use woflang_runtime::{Interpreter, OpMeta};
 
 /// Register all standard library operations with an interpreter.
 ///
@@ -54,6 +91,18 @@ pub fn register_all(interp: &mut Interpreter) {
     math::register(interp);
     logic::register(interp);
     io::register(interp);
+    #[cfg(feature = "file-ops")]
+    file::register(interp);
+    queue::register(interp);
+    list::register(interp);
+    map::register(interp);
+    option::register(interp);
+    error::register(interp);
+    jobs::register(interp);
+    reflect::register(interp);
+    serialize::register(interp);
+    schema::register(interp);
+    trace::register(interp);
 
     #[cfg(feature = "quantum-ops")]
     quantum::register(interp);
@@ -62,6 +111,26 @@ pub fn register_all(interp: &mut Interpreter) {
     crypto::register(interp);
 }
 
+/// Reference metadata for one op: every name bound to the handler
+/// (canonical first), stack effect, help text, and an example (empty
+/// for ops without a useful one).
+type OpDoc = (&'static [&'static str], &'static str, &'static str, &'static str);
+
+/// Attach [`OpMeta`] for each entry of `docs` to all of its names and
+/// record the names as one alias group.
+fn describe(interp: &mut Interpreter, category: &str, docs: &[OpDoc]) {
+    for &(names, effect, help, example) in docs {
+        interp.registry_mut().group(names);
+        for &name in names {
+            let mut meta = OpMeta::new(category, effect, help).with_source(env!("CARGO_PKG_NAME"));
+            if !example.is_empty() {
+                meta = meta.with_example(example);
+            }
+            interp.describe(name, meta);
+        }
+    }
+}
+
 /// Register only the minimal core operations.
 ///
 /// Includes arithmetic, stack operations, and basic I/O.
@@ -74,6 +143,7 @@ pub fn register_core(interp: &mut Interpreter) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use woflang_core::InterpreterContext;
 
     #[test]
     fn register_all_works() {
@@ -95,4 +165,50 @@ mod tests {
         let result = interp.stack().peek().unwrap().as_integer().unwrap();
         assert_eq!(result, 84);
     }
+
+    #[test]
+    fn explain_reconstructs_infix() {
+        let mut interp = Interpreter::new();
+        register_all(&mut interp);
+
+        interp.exec_line("3 2 4 + 2 ^ *").unwrap();
+        assert_eq!(interp.explain().unwrap(), "3 * (2 + 4) ^ 2");
+
+        interp.exec_line("clear 5 字 x 1 2 swap - x dup * + neg").unwrap();
+        assert_eq!(interp.explain().unwrap(), "-(2 - 1 + x * x)");
+
+        interp.exec_line("clear 2 sqrt 10 max").unwrap();
+        assert_eq!(interp.explain().unwrap(), "max(sqrt(2), 10)");
+
+        // Values that are not numbers are only shown as themselves.
+        interp.exec_line("clear list_new 7 list_push 0 list_get 2 * list_new swap list_push").unwrap();
+        assert_eq!(interp.explain().unwrap(), "[14]");
+        interp.exec_line("list_new 5 list_push list_len 3 +").unwrap();
+        assert_eq!(interp.explain().unwrap(), "1 + 3");
+    }
+
+    #[test]
+    fn every_op_is_described_with_a_working_example() {
+        let mut interp = Interpreter::new();
+        register_all(&mut interp);
+
+        let mut undocumented: Vec<&str> = interp
+            .registry()
+            .names()
+            .filter(|name| interp.registry().meta(name).is_none())
+            .collect();
+        undocumented.sort_unstable();
+        assert!(undocumented.is_empty(), "undocumented ops: {undocumented:?}");
+
+        let examples: Vec<(String, String)> = interp
+            .registry()
+            .described()
+            .filter_map(|(name, meta)| Some((name.to_string(), meta.example.clone()?)))
+            .collect();
+        for (name, example) in examples {
+            let mut scratch = Interpreter::new();
+            register_all(&mut scratch);
+            assert!(scratch.exec_line(&example).is_ok(), "example for {name} failed: {example}");
+        }
+    }
 }
@@ -0,0 +1,266 @@
+//! List operations.
+//!
+//! | Operation     | Stack Effect             | Description |
+//! |---------------|--------------------------|-------------|
+//! | `list_new`    | ( -- list )              | New empty list |
+//! | `list_from`   | ( x1 .. xn n -- list )   | Collect a counted run into a list |
+//! | `list_unpack` | ( list -- x1 .. xn n )   | Spread a list as a counted run |
+//! | `list_push`   | ( list v -- list )       | Append to the end |
+//! | `list_pop`    | ( list -- list v )       | Remove from the end |
+//! | `list_get`    | ( list i -- v )          | Element `i` (negative counts from the end) |
+//! | `list_set`    | ( list i v -- list )     | Replace element `i` |
+//! | `list_len`    | ( list -- n )            | Number of elements |
+//! | `list_map`    | ( list "f" -- list )     | Apply function `f` to each element |
+//! | `list_filter` | ( list "f" -- list )     | Keep elements for which `f` is truthy |
+//! | `list_fold`   | ( list init "f" -- acc ) | Combine elements left to right with `f` |
+//!
+//! Lists are immutable values like records: ops that change a list push
+//! a new one, and a copy kept with `dup` is unaffected. `list_from` and
+//! `list_unpack` convert to and from the counted-run convention used by
+//! `range`, `sum_n` and friends.
+//!
+//! `list_map`, `list_filter` and `list_fold` take the name of a function
+//! defined with `⊕`. It is called with the element (for `list_fold`, the
+//! accumulator and then the element) on top of the stack and must leave
+//! one value in their place:
+//!
+//! ```text
+//! ⊕ sq ⺆ dup * ⺘
+//! 1 2 3 3 list_from "sq" list_map   # [1, 4, 9]
+//! ```
+
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Register the list operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("list_new", op_list_new);
+    interp.register("list_from", op_list_from);
+    interp.register("list_unpack", op_list_unpack);
+    interp.register("list_push", op_list_push);
+    interp.register("list_pop", op_list_pop);
+    interp.register("list_get", op_list_get);
+    interp.register("list_set", op_list_set);
+    interp.register("list_len", op_list_len);
+    interp.register("list_map", op_list_map);
+    interp.register("list_filter", op_list_filter);
+    interp.register("list_fold", op_list_fold);
+
+    crate::describe(interp, "list", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["list_new"], "( -- list )", "New empty list", "list_new"),
+    (&["list_from"], "( x1 .. xn n -- list )", "Collect a counted run into a list", "1 2 3 3 list_from"),
+    (&["list_unpack"], "( list -- x1 .. xn n )", "Spread a list as a counted run", "1 2 2 list_from list_unpack"),
+    (&["list_push"], "( list v -- list )", "Append to the end", "list_new 1 list_push"),
+    (&["list_pop"], "( list -- list v )", "Remove from the end", "1 2 2 list_from list_pop"),
+    (&["list_get"], "( list i -- v )", "Element i (negative counts from the end)", "10 20 30 3 list_from -1 list_get"),
+    (&["list_set"], "( list i v -- list )", "Replace element i", "1 2 2 list_from 0 9 list_set"),
+    (&["list_len"], "( list -- n )", "Number of elements", "1 2 2 list_from list_len"),
+    (
+        &["list_map"],
+        "( list \"f\" -- list )",
+        "Apply function f to each element",
+        "⊕ sq ⺆ dup * ⺘ 1 2 3 3 list_from \"sq\" list_map",
+    ),
+    (
+        &["list_filter"],
+        "( list \"f\" -- list )",
+        "Keep elements for which f is truthy",
+        "⊕ big ⺆ 1 > ⺘ 1 2 3 3 list_from \"big\" list_filter",
+    ),
+    (
+        &["list_fold"],
+        "( list init \"f\" -- acc )",
+        "Combine elements left to right with f",
+        "⊕ add ⺆ + ⺘ 1 2 3 3 list_from 0 \"add\" list_fold",
+    ),
+];
+
+fn pop_list(interp: &mut Interpreter) -> Result<Vec<WofValue>> {
+    interp.stack_mut().pop()?.into_list()
+}
+
+/// Resolve `index` against a list of `len` elements.
+fn position(op: &str, index: i64, len: usize) -> Result<usize> {
+    let resolved = if index < 0 { len.checked_sub(index.unsigned_abs() as usize) } else { Some(index as usize) };
+    resolved
+        .filter(|&i| i < len)
+        .ok_or_else(|| WofError::runtime(format!("{op}: index {index} out of range for length {len}")))
+}
+
+/// Call `function` with `args` pushed and pop the single value it leaves.
+fn apply(interp: &mut Interpreter, op: &str, function: &str, args: Vec<WofValue>) -> Result<WofValue> {
+    let depth = interp.stack().len();
+    interp.stack_mut().push_all(args);
+    interp.run_function(function)?;
+    if interp.stack().len() != depth + 1 {
+        return Err(WofError::runtime(format!("{op}: '{function}' must leave exactly one value")));
+    }
+    interp.stack_mut().pop()
+}
+
+fn op_list_new(interp: &mut Interpreter) -> Result<()> {
+    interp.push(WofValue::list(Vec::new()));
+    Ok(())
+}
+
+fn op_list_from(interp: &mut Interpreter) -> Result<()> {
+    let n = interp.stack_mut().pop_integer()?;
+    let n = usize::try_from(n)
+        .ok()
+        .filter(|&n| interp.stack().has(n))
+        .ok_or_else(|| WofError::runtime(format!("list_from: need {n} values on the stack")))?;
+    let mut items = interp.stack_mut().pop_n(n)?;
+    items.reverse();
+    interp.push(WofValue::list(items));
+    Ok(())
+}
+
+fn op_list_unpack(interp: &mut Interpreter) -> Result<()> {
+    let items = pop_list(interp)?;
+    let n = items.len();
+    interp.stack_mut().push_all(items);
+    interp.push(WofValue::integer(n as i64));
+    Ok(())
+}
+
+fn op_list_push(interp: &mut Interpreter) -> Result<()> {
+    let value = interp.stack_mut().pop()?;
+    let mut items = pop_list(interp)?;
+    items.push(value);
+    interp.push(WofValue::list(items));
+    Ok(())
+}
+
+fn op_list_pop(interp: &mut Interpreter) -> Result<()> {
+    let mut items = pop_list(interp)?;
+    let value = items.pop().ok_or_else(|| WofError::runtime("list_pop: list is empty"))?;
+    interp.push(WofValue::list(items));
+    interp.push(value);
+    Ok(())
+}
+
+fn op_list_get(interp: &mut Interpreter) -> Result<()> {
+    let index = interp.stack_mut().pop_integer()?;
+    let list = interp.stack_mut().pop()?;
+    let items = list.as_list()?;
+    let value = items[position("list_get", index, items.len())?].clone();
+    interp.push(value);
+    Ok(())
+}
+
+fn op_list_set(interp: &mut Interpreter) -> Result<()> {
+    let value = interp.stack_mut().pop()?;
+    let index = interp.stack_mut().pop_integer()?;
+    let mut items = pop_list(interp)?;
+    let i = position("list_set", index, items.len())?;
+    items[i] = value;
+    interp.push(WofValue::list(items));
+    Ok(())
+}
+
+fn op_list_len(interp: &mut Interpreter) -> Result<()> {
+    let len = interp.stack_mut().pop()?.as_list()?.len();
+    interp.push(WofValue::integer(len as i64));
+    Ok(())
+}
+
+fn op_list_map(interp: &mut Interpreter) -> Result<()> {
+    let function = interp.stack_mut().pop_string()?;
+    let items = pop_list(interp)?;
+    let mapped = items
+        .into_iter()
+        .map(|item| apply(interp, "list_map", &function, vec![item]))
+        .collect::<Result<Vec<_>>>()?;
+    interp.push(WofValue::list(mapped));
+    Ok(())
+}
+
+fn op_list_filter(interp: &mut Interpreter) -> Result<()> {
+    let function = interp.stack_mut().pop_string()?;
+    let items = pop_list(interp)?;
+    let mut kept = Vec::with_capacity(items.len());
+    for item in items {
+        if apply(interp, "list_filter", &function, vec![item.clone()])?.is_truthy() {
+            kept.push(item);
+        }
+    }
+    interp.push(WofValue::list(kept));
+    Ok(())
+}
+
+fn op_list_fold(interp: &mut Interpreter) -> Result<()> {
+    let function = interp.stack_mut().pop_string()?;
+    let init = interp.stack_mut().pop()?;
+    let items = pop_list(interp)?;
+    let acc = items
+        .into_iter()
+        .try_fold(init, |acc, item| apply(interp, "list_fold", &function, vec![acc, item]))?;
+    interp.push(acc);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        crate::arithmetic::register(&mut interp);
+        crate::logic::register(&mut interp);
+        crate::stack::register(&mut interp);
+        interp
+    }
+
+    fn ints(value: &WofValue) -> Vec<i64> {
+        value.as_list().unwrap().iter().map(|v| v.as_integer().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_build_index_and_unpack() {
+        let mut interp = make_interp();
+        interp.exec_line("1 2 3 3 list_from dup 4 list_push").unwrap();
+        let pushed = interp.stack_mut().pop().unwrap();
+        let original = interp.stack_mut().pop().unwrap();
+        assert_eq!(ints(&pushed), [1, 2, 3, 4]);
+        assert_eq!(ints(&original), [1, 2, 3]);
+
+        interp.push(pushed);
+        interp.exec_line("dup -1 list_get swap 0 10 list_set list_pop").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 4);
+        assert_eq!(ints(&interp.stack_mut().pop().unwrap()), [10, 2, 3]);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 4);
+
+        interp.exec_line("5 6 2 list_from list_unpack").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 2);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 6);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 5);
+
+        assert!(interp.exec_line("list_new 0 list_get").is_err());
+        assert!(interp.exec_line("list_new list_pop").is_err());
+        assert!(interp.exec_line("1 5 list_from").is_err());
+        assert!(interp.exec_line("3 list_len").is_err());
+    }
+
+    #[test]
+    fn test_map_filter_fold_call_functions() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ sq ⺆ dup * ⺘ ⊕ odd ⺆ 2 % 1 = ⺘ ⊕ add ⺆ + ⺘").unwrap();
+        interp.exec_line(r#"7 1 2 3 4 4 list_from "sq" list_map"#).unwrap();
+        assert_eq!(ints(interp.stack().peek().unwrap()), [1, 4, 9, 16]);
+
+        interp.exec_line(r#""odd" list_filter dup 0 "add" list_fold"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 10);
+        assert_eq!(ints(&interp.stack_mut().pop().unwrap()), [1, 9]);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 7);
+
+        assert!(interp.exec_line(r#"1 1 list_from "drop" list_map"#).is_err());
+        interp.stack_mut().clear();
+        interp.exec_line("⊕ none ⺆ drop ⺘").unwrap();
+        assert!(interp.exec_line(r#"1 1 list_from "none" list_map"#).is_err());
+    }
+}
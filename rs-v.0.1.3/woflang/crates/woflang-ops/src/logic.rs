@@ -60,8 +60,31 @@ pub fn register(interp: &mut Interpreter) {
 
     // Tautology demonstration
     interp.register("tautology", op_tautology);
+
+    crate::describe(interp, "logic", DOCS);
 }
 
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["and", "∧"], "( a b -- c )", "Logical AND", "true false and"),
+    (&["or", "∨"], "( a b -- c )", "Logical OR", "true false or"),
+    (&["xor", "⊕"], "( a b -- c )", "Logical XOR", "true true xor"),
+    (&["not", "¬"], "( a -- b )", "Logical NOT", "false not"),
+    (&["nand"], "( a b -- c )", "NOT AND", "true true nand"),
+    (&["nor"], "( a b -- c )", "NOT OR", "false false nor"),
+    (&["implies", "→"], "( a b -- c )", "Material implication (a → b)", "false true implies"),
+    (&["iff", "↔"], "( a b -- c )", "Biconditional (a ↔ b)", "true true iff"),
+    (&["=", "=="], "( a b -- bool )", "Equal", "2 2 ="),
+    (&["!=", "≠"], "( a b -- bool )", "Not equal", "2 3 !="),
+    (&["<"], "( a b -- bool )", "Less than", "2 3 <"),
+    (&[">"], "( a b -- bool )", "Greater than", "2 3 >"),
+    (&["<=", "≤"], "( a b -- bool )", "Less than or equal", "3 3 <="),
+    (&[">=", "≥"], "( a b -- bool )", "Greater than or equal", "2 3 >="),
+    (&["true"], "( -- true )", "Boolean true", "true"),
+    (&["false"], "( -- false )", "Boolean false", "false"),
+    (&["tautology"], "( -- true )", "Push the proof that (P → Q) ↔ (¬P ∨ Q)", "tautology"),
+];
+
 fn op_and(interp: &mut Interpreter) -> Result<()> {
     let b = interp.stack_mut().pop_bool()?;
     let a = interp.stack_mut().pop_bool()?;
@@ -0,0 +1,150 @@
+//! Map (dictionary) operations.
+//!
+//! | Operation    | Stack Effect         | Description |
+//! |--------------|----------------------|-------------|
+//! | `map_new`    | ( -- map )           | New empty map |
+//! | `map_set`    | ( map k v -- map )   | Store `v` under key `k` |
+//! | `map_get`    | ( map k -- v )       | Value under `k` (error if missing) |
+//! | `map_has`    | ( map k -- bool )    | True if `k` has a value |
+//! | `map_del`    | ( map k -- map )     | Remove `k` if present |
+//! | `map_keys`   | ( map -- list )      | Keys in insertion order |
+//! | `map_values` | ( map -- list )      | Values in key insertion order |
+//! | `map_len`    | ( map -- n )         | Number of entries |
+//!
+//! Any value can be a key, though strings are the usual choice; keys
+//! compare by type as well as content, so `1` and `1.0` are different
+//! keys. Maps are immutable values like lists, and remember insertion
+//! order so listings are deterministic:
+//!
+//! ```text
+//! map_new "hp" 10 map_set "mp" 4 map_set dup "hp" map_get   # 10
+//! ```
+
+use woflang_core::{InterpreterContext, Result, WofError, WofMap, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Register the map operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("map_new", op_map_new);
+    interp.register("map_set", op_map_set);
+    interp.register("map_get", op_map_get);
+    interp.register("map_has", op_map_has);
+    interp.register("map_del", op_map_del);
+    interp.register("map_keys", op_map_keys);
+    interp.register("map_values", op_map_values);
+    interp.register("map_len", op_map_len);
+
+    crate::describe(interp, "map", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["map_new"], "( -- map )", "New empty map", "map_new"),
+    (&["map_set"], "( map k v -- map )", "Store v under key k", "map_new \"hp\" 10 map_set"),
+    (&["map_get"], "( map k -- v )", "Value under k (error if missing)", "map_new \"hp\" 10 map_set \"hp\" map_get"),
+    (&["map_has"], "( map k -- bool )", "True if k has a value", "map_new \"hp\" map_has"),
+    (&["map_del"], "( map k -- map )", "Remove k if present", "map_new \"hp\" 10 map_set \"hp\" map_del"),
+    (&["map_keys"], "( map -- list )", "Keys in insertion order", "map_new \"b\" 1 map_set \"a\" 2 map_set map_keys"),
+    (&["map_values"], "( map -- list )", "Values in key insertion order", "map_new \"b\" 1 map_set map_values"),
+    (&["map_len"], "( map -- n )", "Number of entries", "map_new \"b\" 1 map_set map_len"),
+];
+
+fn op_map_new(interp: &mut Interpreter) -> Result<()> {
+    interp.push(WofValue::map(WofMap::new()));
+    Ok(())
+}
+
+fn op_map_set(interp: &mut Interpreter) -> Result<()> {
+    let value = interp.stack_mut().pop()?;
+    let key = interp.stack_mut().pop()?;
+    let mut map = interp.stack_mut().pop()?.into_map()?;
+    map.insert(key, value);
+    interp.push(WofValue::map(map));
+    Ok(())
+}
+
+fn op_map_get(interp: &mut Interpreter) -> Result<()> {
+    let key = interp.stack_mut().pop()?;
+    let map = interp.stack_mut().pop()?;
+    let value = map
+        .as_map()?
+        .get(&key)
+        .cloned()
+        .ok_or_else(|| WofError::runtime(format!("map_get: no key {key}")))?;
+    interp.push(value);
+    Ok(())
+}
+
+fn op_map_has(interp: &mut Interpreter) -> Result<()> {
+    let key = interp.stack_mut().pop()?;
+    let has = interp.stack_mut().pop()?.as_map()?.contains_key(&key);
+    interp.push(WofValue::boolean(has));
+    Ok(())
+}
+
+fn op_map_del(interp: &mut Interpreter) -> Result<()> {
+    let key = interp.stack_mut().pop()?;
+    let mut map = interp.stack_mut().pop()?.into_map()?;
+    map.remove(&key);
+    interp.push(WofValue::map(map));
+    Ok(())
+}
+
+fn op_map_keys(interp: &mut Interpreter) -> Result<()> {
+    let map = interp.stack_mut().pop()?;
+    let keys = map.as_map()?.keys().cloned().collect();
+    interp.push(WofValue::list(keys));
+    Ok(())
+}
+
+fn op_map_values(interp: &mut Interpreter) -> Result<()> {
+    let map = interp.stack_mut().pop()?;
+    let values = map.as_map()?.values().cloned().collect();
+    interp.push(WofValue::list(values));
+    Ok(())
+}
+
+fn op_map_len(interp: &mut Interpreter) -> Result<()> {
+    let len = interp.stack_mut().pop()?.as_map()?.len();
+    interp.push(WofValue::integer(len as i64));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        crate::stack::register(&mut interp);
+        interp
+    }
+
+    #[test]
+    fn test_set_get_and_delete() {
+        let mut interp = make_interp();
+        interp.exec_line(r#"map_new "hp" 10 map_set dup "mp" 4 map_set "hp" 12 map_set"#).unwrap();
+        let updated = interp.stack_mut().pop().unwrap();
+        let original = interp.stack_mut().pop().unwrap();
+        assert_eq!(original.to_string(), "{hp: 10}");
+        assert_eq!(updated.to_string(), "{hp: 12, mp: 4}");
+
+        interp.push(updated);
+        interp.exec_line(r#"dup "mp" map_get swap dup "hp" map_has swap "hp" map_del map_len"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1);
+        assert!(interp.stack_mut().pop_bool().unwrap());
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 4);
+
+        assert!(interp.exec_line(r#"map_new "x" map_get"#).is_err());
+        assert!(interp.exec_line(r#"1 "x" map_has"#).is_err());
+    }
+
+    #[test]
+    fn test_keys_and_values_keep_insertion_order() {
+        let mut interp = make_interp();
+        interp.exec_line(r#"map_new "b" 1 map_set "a" 2 map_set 7 "seven" map_set dup map_keys swap map_values"#).unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "[1, 2, seven]");
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "[b, a, 7]");
+    }
+}
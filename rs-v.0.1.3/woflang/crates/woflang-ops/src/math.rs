@@ -23,8 +23,11 @@
 //! | `round`    | (a -- b)     | Round |
 //! | `trunc`    | (a -- b)     | Truncate |
 //! | `hypot`    | (a b -- c)   | Hypotenuse |
+//!
+//! `sqrt` and `exp` also take complex numbers, and the square root of a
+//! negative number is imaginary: `-1 sqrt` gives `0+1i`.
 
-use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_core::{Complex, InterpreterContext, Result, WofError, WofType, WofValue};
 use woflang_runtime::Interpreter;
 
 /// Register all math operations.
@@ -78,7 +81,46 @@ pub fn register(interp: &mut Interpreter) {
     // Degree/radian conversion
     interp.register("deg2rad", op_deg_to_rad);
     interp.register("rad2deg", op_rad_to_deg);
-}
+
+    crate::describe(interp, "math", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["sin"], "( a -- b )", "Sine", "pi 2 / sin"),
+    (&["cos"], "( a -- b )", "Cosine", "0 cos"),
+    (&["tan"], "( a -- b )", "Tangent", "pi 4 / tan"),
+    (&["asin"], "( a -- b )", "Arc sine", "1 asin"),
+    (&["acos"], "( a -- b )", "Arc cosine", "1 acos"),
+    (&["atan"], "( a -- b )", "Arc tangent", "1 atan"),
+    (&["atan2"], "( y x -- a )", "Two-argument arc tangent", "1 1 atan2"),
+    (&["sinh"], "( a -- b )", "Hyperbolic sine", "1 sinh"),
+    (&["cosh"], "( a -- b )", "Hyperbolic cosine", "1 cosh"),
+    (&["tanh"], "( a -- b )", "Hyperbolic tangent", "1 tanh"),
+    (&["sqrt", "√"], "( a -- b )", "Square root", "16 sqrt"),
+    (&["cbrt"], "( a -- b )", "Cube root", "27 cbrt"),
+    (&["pow", "^"], "( a b -- a^b )", "Power", "2 10 pow"),
+    (&["exp"], "( a -- e^a )", "Exponential", "1 exp"),
+    (&["ln", "log"], "( a -- b )", "Natural log", "e ln"),
+    (&["log10"], "( a -- b )", "Base-10 log", "1000 log10"),
+    (&["log2"], "( a -- b )", "Base-2 log", "1024 log2"),
+    (&["floor"], "( a -- b )", "Floor", "2.7 floor"),
+    (&["ceil"], "( a -- b )", "Ceiling", "2.1 ceil"),
+    (&["round"], "( a -- b )", "Round", "2.5 round"),
+    (&["trunc"], "( a -- b )", "Truncate toward zero", "-2.7 trunc"),
+    (&["frac"], "( a -- b )", "Fractional part", "2.75 frac"),
+    (&["hypot"], "( a b -- c )", "Hypotenuse", "3 4 hypot"),
+    (&["sign"], "( a -- s )", "Sign: -1, 0 or 1", "-7 sign"),
+    (&["factorial"], "( n -- n! )", "Factorial", "5 factorial"),
+    (&["gcd"], "( a b -- c )", "Greatest common divisor", "12 18 gcd"),
+    (&["lcm"], "( a b -- c )", "Least common multiple", "4 6 lcm"),
+    (&["celsius_to_kelvin"], "( c -- k )", "Celsius to kelvin", "25 celsius_to_kelvin"),
+    (&["kelvin_to_celsius"], "( k -- c )", "Kelvin to Celsius", "300 kelvin_to_celsius"),
+    (&["celsius_to_fahrenheit"], "( c -- f )", "Celsius to Fahrenheit", "100 celsius_to_fahrenheit"),
+    (&["fahrenheit_to_celsius"], "( f -- c )", "Fahrenheit to Celsius", "212 fahrenheit_to_celsius"),
+    (&["deg2rad"], "( deg -- rad )", "Degrees to radians", "180 deg2rad"),
+    (&["rad2deg"], "( rad -- deg )", "Radians to degrees", "pi rad2deg"),
+];
 
 // ═══════════════════════════════════════════════════════════════════════
 // TRIGONOMETRIC FUNCTIONS
@@ -160,13 +202,18 @@ fn op_tanh(interp: &mut Interpreter) -> Result<()> {
 // ═══════════════════════════════════════════════════════════════════════
 
 fn op_sqrt(interp: &mut Interpreter) -> Result<()> {
-    let a = interp.stack_mut().pop_numeric()?;
+    let a = interp.stack_mut().pop()?;
+    if a.value_type() == WofType::Complex {
+        interp.push(WofValue::complex(a.as_complex()?.sqrt()));
+        return Ok(());
+    }
+
+    let a = a.as_numeric()?;
     if a < 0.0 {
-        return Err(WofError::InvalidArgument(format!(
-            "sqrt: negative argument {a}"
-        )));
+        interp.push(WofValue::complex(Complex::from(a).sqrt()));
+    } else {
+        interp.push(WofValue::double(a.sqrt()));
     }
-    interp.push(WofValue::double(a.sqrt()));
     Ok(())
 }
 
@@ -188,8 +235,13 @@ fn op_pow(interp: &mut Interpreter) -> Result<()> {
 // ═══════════════════════════════════════════════════════════════════════
 
 fn op_exp(interp: &mut Interpreter) -> Result<()> {
-    let a = interp.stack_mut().pop_numeric()?;
-    interp.push(WofValue::double(a.exp()));
+    let a = interp.stack_mut().pop()?;
+    if a.value_type() == WofType::Complex {
+        interp.push(WofValue::complex(a.as_complex()?.exp()));
+        return Ok(());
+    }
+
+    interp.push(WofValue::double(a.as_numeric()?.exp()));
     Ok(())
 }
 
@@ -304,15 +356,25 @@ fn op_factorial(interp: &mut Interpreter) -> Result<()> {
 }
 
 fn op_gcd(interp: &mut Interpreter) -> Result<()> {
-    let b = interp.stack_mut().pop_integer()?.unsigned_abs();
-    let a = interp.stack_mut().pop_integer()?.unsigned_abs();
+    let b = interp.stack_mut().pop()?;
+    let a = interp.stack_mut().pop()?;
+    if let Some((a, b)) = crate::arithmetic::big_pair(&a, &b) {
+        interp.push(WofValue::bigint(a.gcd(&b)));
+        return Ok(());
+    }
+    let (a, b) = (a.as_integer()?.unsigned_abs(), b.as_integer()?.unsigned_abs());
     interp.push(WofValue::integer(num_integer::gcd(a, b) as i64));
     Ok(())
 }
 
 fn op_lcm(interp: &mut Interpreter) -> Result<()> {
-    let b = interp.stack_mut().pop_integer()?.unsigned_abs();
-    let a = interp.stack_mut().pop_integer()?.unsigned_abs();
+    let b = interp.stack_mut().pop()?;
+    let a = interp.stack_mut().pop()?;
+    if let Some((a, b)) = crate::arithmetic::big_pair(&a, &b) {
+        interp.push(WofValue::bigint(a.lcm(&b)));
+        return Ok(());
+    }
+    let (a, b) = (a.as_integer()?.unsigned_abs(), b.as_integer()?.unsigned_abs());
     interp.push(WofValue::integer(num_integer::lcm(a, b) as i64));
     Ok(())
 }
@@ -366,6 +428,7 @@ mod tests {
         let mut interp = Interpreter::new();
         register(&mut interp);
         constants::register(&mut interp);
+        crate::arithmetic::register(&mut interp);
         interp
     }
 
@@ -431,6 +494,11 @@ mod tests {
         let mut interp = make_interp();
         interp.exec_line("48 18 gcd").unwrap();
         assert_eq!(interp.stack().peek().unwrap().as_integer().unwrap(), 6);
+
+        // 2^64 + 2 is a multiple of 6 but not of 4
+        interp.exec_line("18446744073709551618 600 gcd 18446744073709551618 4 lcm").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "36893488147419103236");
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "6");
     }
 
     #[test]
@@ -450,9 +518,20 @@ mod tests {
     }
 
     #[test]
-    fn test_sqrt_negative_error() {
+    fn test_sqrt_negative_is_imaginary() {
+        let mut interp = make_interp();
+        interp.exec_line("-1 sqrt").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "0+1i");
+        interp.exec_line("-4 sqrt sqrt").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().as_complex().unwrap(), Complex::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn test_complex_exp() {
         let mut interp = make_interp();
-        let result = interp.exec_line("-4 sqrt");
-        assert!(matches!(result, Err(WofError::InvalidArgument(_))));
+        interp.push(WofValue::complex(Complex::new(0.0, std::f64::consts::PI)));
+        interp.exec_line("exp 1 +").unwrap();
+        assert!(interp.stack_mut().pop().unwrap().as_complex().unwrap().abs() < 1e-12);
+        assert!(interp.exec_line("\"x\" exp").is_err());
     }
 }
@@ -0,0 +1,118 @@
+//! Option and result values.
+//!
+//! Options and results are tagged values of the built-in `Option`
+//! (`Some`/`None`) and `Result` (`Ok`/`Err`) enums, so `match_tag` takes
+//! them apart like any other enum.
+//!
+//! | Operation   | Stack Effect                  | Description |
+//! |-------------|-------------------------------|-------------|
+//! | `some`      | ( a -- Option.Some(a) )       | Wrap a present value |
+//! | `none`      | ( -- Option.None )            | The absent value |
+//! | `ok`        | ( a -- Result.Ok(a) )         | Wrap a successful value |
+//! | `err`       | ( e -- Result.Err(e) )        | Wrap an error |
+//! | `unwrap_or` | ( opt default -- a )          | The value in `Some`/`Ok`, else `default` |
+//!
+//! The interpreter's `map_ok ⺆ ... ⺘`, `and_then ⺆ ... ⺘` and `try_prop`
+//! forms work on both:
+//!
+//! ```text
+//! ⊕ checked_sqrt ⺆ dup 0 < 若 ⺆ drop "negative" err ⺘ 或 ⺆ sqrt ok ⺘ ⺘
+//! ⊕ quartic_root ⺆ checked_sqrt try_prop checked_sqrt ⺘
+//! 16 quartic_root                                 # → Result.Ok(2.0)
+//! -4 quartic_root                                 # → Result.Err(negative)
+//! -9 checked_sqrt map_ok ⺆ 1 + ⺘ 0 unwrap_or     # → 0
+//! ```
+
+use woflang_core::{InterpreterContext, Result, WofTagged, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Register the option and result operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("some", op_some);
+    interp.register("none", op_none);
+    interp.register("ok", op_ok);
+    interp.register("err", op_err);
+    interp.register("unwrap_or", op_unwrap_or);
+
+    crate::describe(interp, "option", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["some"], "( a -- Option.Some(a) )", "Wrap a present value", "5 some"),
+    (&["none"], "( -- Option.None )", "The absent value", "none 0 unwrap_or"),
+    (&["ok"], "( a -- Result.Ok(a) )", "Wrap a successful value", "5 ok"),
+    (&["err"], "( e -- Result.Err(e) )", "Wrap an error", "\"not found\" err"),
+    (
+        &["unwrap_or"],
+        "( opt default -- a )",
+        "The value in Some or Ok, otherwise the default",
+        "\"oops\" err 0 unwrap_or",
+    ),
+];
+
+fn op_some(interp: &mut Interpreter) -> Result<()> {
+    let value = interp.stack_mut().pop()?;
+    interp.push(WofValue::tagged(WofTagged::some(value)));
+    Ok(())
+}
+
+fn op_none(interp: &mut Interpreter) -> Result<()> {
+    interp.push(WofValue::tagged(WofTagged::none()));
+    Ok(())
+}
+
+fn op_ok(interp: &mut Interpreter) -> Result<()> {
+    let value = interp.stack_mut().pop()?;
+    interp.push(WofValue::tagged(WofTagged::ok(value)));
+    Ok(())
+}
+
+fn op_err(interp: &mut Interpreter) -> Result<()> {
+    let error = interp.stack_mut().pop()?;
+    interp.push(WofValue::tagged(WofTagged::err(error)));
+    Ok(())
+}
+
+fn op_unwrap_or(interp: &mut Interpreter) -> Result<()> {
+    let default = interp.stack_mut().pop()?;
+    let value = interp.stack_mut().pop()?;
+    let value = value.as_tagged()?.success()?.cloned().unwrap_or(default);
+    interp.push(value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        crate::register_all(&mut interp);
+        interp
+    }
+
+    fn top(interp: &mut Interpreter) -> String {
+        interp.stack_mut().pop().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_constructors_and_unwrap_or() {
+        let mut interp = make_interp();
+        interp.exec_line(r#"5 some none 7 ok "bad" err"#).unwrap();
+        assert_eq!(top(&mut interp), "Result.Err(bad)");
+        assert_eq!(top(&mut interp), "Result.Ok(7)");
+        assert_eq!(top(&mut interp), "Option.None");
+        assert_eq!(top(&mut interp), "Option.Some(5)");
+
+        interp.exec_line("5 some 0 unwrap_or none 1 unwrap_or 7 ok 0 unwrap_or \"bad\" err 2 unwrap_or").unwrap();
+        let values = interp.stack_mut().pop_n(4).unwrap();
+        assert_eq!(values, [2, 7, 1, 5].map(WofValue::integer));
+
+        interp.exec_line("3 some match_tag ⺆ Some ⺆ 1 + ⺘ None ⺆ 0 ⺘ ⺘").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 4);
+
+        assert!(interp.exec_line("5 0 unwrap_or").is_err());
+        assert!(interp.exec_line(r#"enum "Shape" "Circle/1" 1 Circle 0 unwrap_or"#).is_err());
+    }
+}
@@ -43,8 +43,25 @@ pub fn register(interp: &mut Interpreter) {
 
     // Display
     interp.register("qshow", op_qshow);
+
+    crate::describe(interp, "quantum", DOCS);
 }
 
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["|0⟩"], "( -- q )", "Create |0⟩ qubit state", "|0⟩"),
+    (&["|1⟩"], "( -- q )", "Create |1⟩ qubit state", "|1⟩"),
+    (&["|+⟩"], "( -- q )", "Create |+⟩ qubit state", "|+⟩"),
+    (&["|-⟩"], "( -- q )", "Create |-⟩ qubit state", "|-⟩"),
+    (&["H"], "( q -- q' )", "Hadamard gate", "|0⟩ H"),
+    (&["X"], "( q -- q' )", "Pauli-X (NOT) gate", "|0⟩ X"),
+    (&["Y"], "( q -- q' )", "Pauli-Y gate", "|0⟩ Y"),
+    (&["Z"], "( q -- q' )", "Pauli-Z gate", "|1⟩ Z"),
+    (&["measure"], "( q -- n )", "Collapse and measure", "|1⟩ measure"),
+    (&["bell"], "( -- q q )", "Create Bell state", "bell"),
+    (&["qshow"], "( q -- q )", "Print the qubit without consuming it", "|+⟩ qshow"),
+];
+
 /// Internal representation of a qubit state.
 /// Stores [amplitude_0, amplitude_1] as real numbers.
 #[derive(Clone, Copy, Debug)]
@@ -219,7 +236,7 @@ fn op_bell_state(interp: &mut Interpreter) -> Result<()> {
 
 fn op_qshow(interp: &mut Interpreter) -> Result<()> {
     let val = interp.stack().peek()?;
-    println!("Quantum state: {val}");
+    writeln!(interp.out(), "Quantum state: {val}");
     Ok(())
 }
 
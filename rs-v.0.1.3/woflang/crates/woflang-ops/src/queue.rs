@@ -0,0 +1,201 @@
+//! Queue, deque and priority-queue operations.
+//!
+//! | Operation     | Stack Effect           | Description |
+//! |---------------|------------------------|-------------|
+//! | `queue_new`   | ( -- q )               | New empty deque (alias `deque_new`) |
+//! | `push_back`   | ( q v -- )             | Append to the back |
+//! | `push_front`  | ( q v -- )             | Prepend to the front |
+//! | `pop_front`   | ( q -- v )             | Remove from the front |
+//! | `pop_back`    | ( q -- v )             | Remove from the back |
+//! | `pq_new`      | ( -- pq )              | New empty min-priority queue |
+//! | `pq_push`     | ( pq item prio -- )    | Insert with a numeric priority |
+//! | `pq_pop`      | ( pq -- item prio )    | Remove the lowest priority |
+//! | `queue_len`   | ( q -- n )             | Number of items (alias `pq_len`) |
+//! | `queue_empty` | ( q -- bool )          | True if nothing is queued |
+//!
+//! Queues are reference values: `dup` copies the handle, not the queue,
+//! so a script keeps one copy while ops consume the other:
+//!
+//! ```text
+//! queue_new dup 1 push_back dup 2 push_back pop_front   # 1
+//! ```
+//!
+//! Equal priorities pop in insertion order, which keeps Dijkstra and
+//! event-scheduler scripts deterministic.
+
+use woflang_core::{InterpreterContext, QueueRef, Result, WofError, WofQueue, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Register the queue operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("queue_new", op_queue_new);
+    interp.register("deque_new", op_queue_new);
+    interp.register("push_back", op_push_back);
+    interp.register("push_front", op_push_front);
+    interp.register("pop_front", op_pop_front);
+    interp.register("pop_back", op_pop_back);
+    interp.register("pq_new", op_pq_new);
+    interp.register("pq_push", op_pq_push);
+    interp.register("pq_pop", op_pq_pop);
+    interp.register("queue_len", op_queue_len);
+    interp.register("pq_len", op_queue_len);
+    interp.register("queue_empty", op_queue_empty);
+
+    crate::describe(interp, "queue", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["queue_new", "deque_new"], "( -- q )", "New empty deque", "queue_new"),
+    (&["push_back"], "( q v -- )", "Append to the back", "queue_new dup 1 push_back queue_len"),
+    (&["push_front"], "( q v -- )", "Prepend to the front", "queue_new dup 1 push_front queue_len"),
+    (&["pop_front"], "( q -- v )", "Remove from the front", "queue_new dup 1 push_back pop_front"),
+    (&["pop_back"], "( q -- v )", "Remove from the back", "queue_new dup 1 push_back pop_back"),
+    (&["pq_new"], "( -- pq )", "New empty min-priority queue", "pq_new"),
+    (&["pq_push"], "( pq item prio -- )", "Insert with a numeric priority", "pq_new dup \"job\" 3 pq_push queue_len"),
+    (&["pq_pop"], "( pq -- item prio )", "Remove the lowest priority", "pq_new dup \"job\" 3 pq_push pq_pop"),
+    (&["queue_len", "pq_len"], "( q -- n )", "Number of items", "queue_new queue_len"),
+    (&["queue_empty"], "( q -- bool )", "True if nothing is queued", "queue_new queue_empty"),
+];
+
+fn pop_queue(interp: &mut Interpreter) -> Result<QueueRef> {
+    Ok(interp.stack_mut().pop()?.as_queue()?.clone())
+}
+
+fn wrong_kind(op: &str, expected: &str) -> WofError {
+    WofError::runtime(format!("{op}: expected a {expected}"))
+}
+
+fn empty(op: &str) -> WofError {
+    WofError::runtime(format!("{op}: queue is empty"))
+}
+
+fn op_queue_new(interp: &mut Interpreter) -> Result<()> {
+    interp.push(WofValue::queue(WofQueue::deque()));
+    Ok(())
+}
+
+fn op_pq_new(interp: &mut Interpreter) -> Result<()> {
+    interp.push(WofValue::queue(WofQueue::priority()));
+    Ok(())
+}
+
+fn op_push_back(interp: &mut Interpreter) -> Result<()> {
+    let value = interp.stack_mut().pop()?;
+    let queue = pop_queue(interp)?;
+    let pushed = queue.lock().push_back(value);
+    pushed.then_some(()).ok_or_else(|| wrong_kind("push_back", "deque (use pq_push)"))
+}
+
+fn op_push_front(interp: &mut Interpreter) -> Result<()> {
+    let value = interp.stack_mut().pop()?;
+    let queue = pop_queue(interp)?;
+    let pushed = queue.lock().push_front(value);
+    pushed.then_some(()).ok_or_else(|| wrong_kind("push_front", "deque (use pq_push)"))
+}
+
+fn pop_end(interp: &mut Interpreter, op: &str, front: bool) -> Result<()> {
+    let queue = pop_queue(interp)?;
+    let mut q = queue.lock();
+    if q.is_priority() {
+        return Err(wrong_kind(op, "deque (use pq_pop)"));
+    }
+    let value = if front { q.pop_front() } else { q.pop_back() };
+    drop(q);
+    interp.push(value.ok_or_else(|| empty(op))?);
+    Ok(())
+}
+
+fn op_pop_front(interp: &mut Interpreter) -> Result<()> {
+    pop_end(interp, "pop_front", true)
+}
+
+fn op_pop_back(interp: &mut Interpreter) -> Result<()> {
+    pop_end(interp, "pop_back", false)
+}
+
+fn op_pq_push(interp: &mut Interpreter) -> Result<()> {
+    let priority = interp.stack_mut().pop_numeric()?;
+    let item = interp.stack_mut().pop()?;
+    let queue = pop_queue(interp)?;
+    let pushed = queue.lock().push_priority(item, priority);
+    pushed.then_some(()).ok_or_else(|| wrong_kind("pq_push", "priority queue"))
+}
+
+fn op_pq_pop(interp: &mut Interpreter) -> Result<()> {
+    let queue = pop_queue(interp)?;
+    let mut q = queue.lock();
+    if !q.is_priority() {
+        return Err(wrong_kind("pq_pop", "priority queue"));
+    }
+    let (item, priority) = q.pop_priority().ok_or_else(|| empty("pq_pop"))?;
+    drop(q);
+    interp.push(item);
+    interp.push(WofValue::double(priority));
+    Ok(())
+}
+
+fn op_queue_len(interp: &mut Interpreter) -> Result<()> {
+    let len = pop_queue(interp)?.lock().len();
+    interp.push(WofValue::integer(len as i64));
+    Ok(())
+}
+
+fn op_queue_empty(interp: &mut Interpreter) -> Result<()> {
+    let empty = pop_queue(interp)?.lock().is_empty();
+    interp.push(WofValue::boolean(empty));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        crate::stack::register(&mut interp);
+        interp
+    }
+
+    #[test]
+    fn test_deque_both_ends() {
+        let mut interp = make_interp();
+        interp
+            .exec_line("queue_new dup 1 push_back dup 2 push_back dup 0 push_front")
+            .unwrap();
+        interp.exec_line("dup pop_front swap dup pop_back swap queue_len").unwrap();
+
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 2);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 0);
+        assert!(interp.exec_line("queue_new pop_front").is_err());
+    }
+
+    #[test]
+    fn test_pq_orders_by_priority_then_insertion() {
+        let mut interp = make_interp();
+        interp
+            .exec_line(r#"pq_new dup "b" 2 pq_push dup "a" 1 pq_push dup "c" 2 pq_push"#)
+            .unwrap();
+
+        let mut popped = Vec::new();
+        for _ in 0..3 {
+            interp.exec_line("dup pq_pop drop swap").unwrap();
+            let pq = interp.stack_mut().pop().unwrap();
+            popped.push(interp.stack_mut().pop_string().unwrap());
+            interp.push(pq);
+        }
+        assert_eq!(popped, ["a", "b", "c"]);
+        interp.exec_line("queue_empty").unwrap();
+        assert!(interp.stack_mut().pop_bool().unwrap());
+    }
+
+    #[test]
+    fn test_queue_kinds_are_checked() {
+        let mut interp = make_interp();
+        assert!(interp.exec_line("pq_new 1 push_back").is_err());
+        assert!(interp.exec_line("queue_new 1 2 pq_push").is_err());
+        assert!(interp.exec_line("5 queue_len").is_err());
+    }
+}
@@ -0,0 +1,392 @@
+//! Introspection: the names an op goes by, and code as data.
+//!
+//! | Operation   | Stack Effect            | Description |
+//! |-------------|-------------------------|-------------|
+//! | `aliases`   | ( name -- n1 .. nk k )  | Every name bound to the same handler |
+//! | `glyph_of`  | ( name -- glyph )       | The op's first non-ASCII name |
+//! | `ascii_of`  | ( name -- ascii )       | The op's first ASCII name |
+//! | `fn_body`   | ( name -- list )        | A function's body as token strings |
+//! | `tokens`    | ( source -- list )      | Split source into token strings |
+//! | `eval_list` | ( list -- )             | Run a list of tokens |
+//! | `param_get` | ( name -- v )           | Current value of a parameter |
+//! | `param_set` | ( name v -- )           | Set a parameter until it is set again |
+//! | `params`    | ( -- list )             | Names of all parameters |
+//! | `help`      | ( name -- )             | Print an op's help card |
+//! | `ops_list`  | ( -- list )             | Every op name, sorted |
+//! | `ops_count` | ( -- n )                | How many op names there are |
+//! | `ops_search` | ( text -- list )       | Ops whose name or help mentions text |
+//! | `ops_category` | ( category -- list ) | Ops in a category, sorted |
+//!
+//! Names come from the registry's alias groups, canonical name first:
+//!
+//! ```text
+//! "a+" aliases        # "⊞" "a+" "analog_add" 3
+//! "analog_add" glyph_of   # "⊞"
+//! "⊞" ascii_of        # "a+"
+//! ```
+//!
+//! `help` prints what the registry knows about an op, the same card the
+//! REPL shows for `help <op>` or `:doc <op>`:
+//!
+//! ```text
+//! "sqrt" help
+//! # sqrt  (also √)  [math, woflang-ops]
+//! #   ( a -- b )  Square root
+//! #   Example: 16 sqrt
+//! ```
+//!
+//! The `ops_` ops read the same registry as a list of names, for tools
+//! and tests written in woflang itself. Names include aliases, and the
+//! search ignores case:
+//!
+//! ```text
+//! ops_count                   # 800 or so
+//! "square root" ops_search    # [sqrt, sqrt2, √, √2]
+//! "stack" ops_category        # [2drop, 2dup, ...]
+//! ```
+//!
+//! Tokens are kept as their source text, so string literals keep their
+//! quotes and a list from `fn_body` or `tokens` runs unchanged through
+//! `eval_list`. Other values in the list run as their printed form:
+//!
+//! ```text
+//! ⊕ sq ⺆ dup * ⺘
+//! "sq" fn_body            # ["dup", "*"]
+//! 7 swap eval_list        # 49
+//! ```
+//!
+//! The same token lists feed macros (`macro name ⺆ ... ⺘`), which
+//! rewrite the tokens following them as a line is read:
+//!
+//! ```text
+//! macro unless ⺆ 0 "not 若 ⺆" list_set ⺘
+//! 0 unless ⺆ "ran" ⺘      # "ran"
+//! ```
+//!
+//! Parameters are the dynamic variables ops consult, such as
+//! `precision`; `parameterize "name" value ⺆ ... ⺘` overrides one for a
+//! block, while `param_set` changes it for good.
+
+use woflang_core::{InterpreterContext, Result, WofError, WofType, WofValue};
+use woflang_runtime::{Interpreter, Tokenizer};
+
+/// Register the introspection operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("aliases", op_aliases);
+    interp.register("glyph_of", op_glyph_of);
+    interp.register("ascii_of", op_ascii_of);
+    interp.register("fn_body", op_fn_body);
+    interp.register("tokens", op_tokens);
+    interp.register("eval_list", op_eval_list);
+    interp.register("param_get", op_param_get);
+    interp.register("param_set", op_param_set);
+    interp.register("params", op_params);
+    interp.register("help", op_help);
+    interp.register("ops_list", op_ops_list);
+    interp.register("ops_count", op_ops_count);
+    interp.register("ops_search", op_ops_search);
+    interp.register("ops_category", op_ops_category);
+
+    crate::describe(interp, "reflect", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["aliases"], "( name -- n1 .. nk k )", "Every name bound to the same handler", "\"*\" aliases"),
+    (&["glyph_of"], "( name -- glyph )", "The op's first non-ASCII name", "\"sqrt\" glyph_of"),
+    (&["ascii_of"], "( name -- ascii )", "The op's first ASCII name", "\"√\" ascii_of"),
+    (&["fn_body"], "( name -- list )", "A function's body as token strings", "⊕ sq ⺆ dup * ⺘ \"sq\" fn_body"),
+    (&["tokens"], "( source -- list )", "Split source into token strings", "\"2 3 +\" tokens"),
+    (&["eval_list"], "( list -- )", "Run a list of tokens", "\"2 3 +\" tokens eval_list"),
+    (&["param_get"], "( name -- v )", "Current value of a parameter", "\"precision\" param_get"),
+    (&["param_set"], "( name v -- )", "Set a parameter until it is set again", "\"precision\" 3 param_set"),
+    (&["params"], "( -- list )", "Names of all parameters", "params"),
+    (&["help"], "( name -- )", "Print an op's help card", "\"sqrt\" help"),
+    (&["ops_list"], "( -- list )", "Every op name, sorted", "ops_list"),
+    (&["ops_count"], "( -- n )", "How many op names there are", "ops_count"),
+    (&["ops_search"], "( text -- list )", "Ops whose name or help mentions text", "\"root\" ops_search"),
+    (&["ops_category"], "( category -- list )", "Ops in a category, sorted", "\"stack\" ops_category"),
+];
+
+/// Pop an op name and return it with every name in its alias group.
+fn group(interp: &mut Interpreter, op: &str) -> Result<(String, Vec<String>)> {
+    let name = interp.stack_mut().pop_string()?;
+    let names: Vec<String> =
+        interp.registry().aliases_of(&name).into_iter().map(str::to_string).collect();
+    if names.is_empty() {
+        return Err(WofError::Runtime(format!("{op}: unknown op '{name}'")));
+    }
+    Ok((name, names))
+}
+
+fn op_aliases(interp: &mut Interpreter) -> Result<()> {
+    let (_, names) = group(interp, "aliases")?;
+    let count = names.len();
+    for name in names {
+        interp.push(WofValue::string(name));
+    }
+    interp.push(WofValue::integer(count as i64));
+    Ok(())
+}
+
+fn op_glyph_of(interp: &mut Interpreter) -> Result<()> {
+    let (name, names) = group(interp, "glyph_of")?;
+    let glyph = names
+        .iter()
+        .find(|n| !n.is_ascii())
+        .ok_or_else(|| WofError::Runtime(format!("glyph_of: '{name}' has no glyph name")))?;
+    interp.push(WofValue::string(glyph.clone()));
+    Ok(())
+}
+
+fn op_ascii_of(interp: &mut Interpreter) -> Result<()> {
+    let (name, names) = group(interp, "ascii_of")?;
+    let ascii = names
+        .iter()
+        .find(|n| n.is_ascii())
+        .ok_or_else(|| WofError::Runtime(format!("ascii_of: '{name}' has no ASCII name")))?;
+    interp.push(WofValue::string(ascii.clone()));
+    Ok(())
+}
+
+fn op_fn_body(interp: &mut Interpreter) -> Result<()> {
+    let name = interp.stack_mut().pop_string()?;
+    let body = interp
+        .get_function(&name)
+        .ok_or_else(|| WofError::Runtime(format!("fn_body: no function named '{name}'")))?
+        .body
+        .iter()
+        .map(|t| WofValue::string(t.text.clone()))
+        .collect();
+    interp.push(WofValue::list(body));
+    Ok(())
+}
+
+fn op_tokens(interp: &mut Interpreter) -> Result<()> {
+    let source = interp.stack_mut().pop_string()?;
+    let tokens = Tokenizer::new(&source).map(|t| WofValue::string(t.text)).collect();
+    interp.push(WofValue::list(tokens));
+    Ok(())
+}
+
+fn op_eval_list(interp: &mut Interpreter) -> Result<()> {
+    let items = interp.stack_mut().pop()?.into_list()?;
+    let mut source = String::new();
+    for item in &items {
+        if item.value_type() == WofType::List {
+            return Err(WofError::Runtime("eval_list: tokens cannot be lists".into()));
+        }
+        match item.as_str() {
+            Ok(text) => source.push_str(text),
+            Err(_) => source.push_str(&item.to_string()),
+        }
+        source.push(' ');
+    }
+    interp.run_source(&source)
+}
+
+fn op_param_get(interp: &mut Interpreter) -> Result<()> {
+    let name = interp.stack_mut().pop_string()?;
+    let value = interp.param(&name).map_err(|e| WofError::Runtime(format!("param_get: {e}")))?;
+    interp.push(value);
+    Ok(())
+}
+
+fn op_param_set(interp: &mut Interpreter) -> Result<()> {
+    let value = interp.stack_mut().pop()?;
+    let name = interp.stack_mut().pop_string()?;
+    interp.set_param(&name, value).map_err(|e| WofError::Runtime(format!("param_set: {e}")))
+}
+
+fn op_params(interp: &mut Interpreter) -> Result<()> {
+    let names = interp.param_names().into_iter().map(WofValue::string).collect();
+    interp.push(WofValue::list(names));
+    Ok(())
+}
+
+fn op_help(interp: &mut Interpreter) -> Result<()> {
+    let name = interp.stack_mut().pop_string()?;
+    let card = interp
+        .help_card(&name)
+        .ok_or_else(|| WofError::Runtime(format!("help: unknown op '{name}'")))?;
+    interp.out().print(&card);
+    Ok(())
+}
+
+/// Push `names` as a list of strings.
+fn push_names(interp: &mut Interpreter, names: Vec<String>) {
+    let list = names.into_iter().map(WofValue::string).collect();
+    interp.push(WofValue::list(list));
+}
+
+fn op_ops_list(interp: &mut Interpreter) -> Result<()> {
+    let names: Vec<String> = interp.registry().completions("").into_iter().map(str::to_string).collect();
+    push_names(interp, names);
+    Ok(())
+}
+
+fn op_ops_count(interp: &mut Interpreter) -> Result<()> {
+    let count = interp.registry().completions("").len();
+    interp.push(WofValue::integer(count as i64));
+    Ok(())
+}
+
+fn op_ops_search(interp: &mut Interpreter) -> Result<()> {
+    let text = interp.stack_mut().pop_string()?.to_lowercase();
+    let registry = interp.registry();
+    let names: Vec<String> = registry
+        .completions("")
+        .into_iter()
+        .filter(|name| {
+            name.to_lowercase().contains(&text)
+                || registry.meta(name).is_some_and(|meta| meta.help.to_lowercase().contains(&text))
+        })
+        .map(str::to_string)
+        .collect();
+    push_names(interp, names);
+    Ok(())
+}
+
+fn op_ops_category(interp: &mut Interpreter) -> Result<()> {
+    let category = interp.stack_mut().pop_string()?;
+    let registry = interp.registry();
+    let names: Vec<String> = registry
+        .completions("")
+        .into_iter()
+        .filter(|name| registry.meta(name).is_some_and(|meta| meta.category == category))
+        .map(str::to_string)
+        .collect();
+    if names.is_empty() {
+        return Err(WofError::Runtime(format!("ops_category: no ops in category '{category}'")));
+    }
+    push_names(interp, names);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        crate::register_all(&mut interp);
+        interp.register_group(&["⊞", "a+", "analog_add"], |_| Ok(()));
+        interp
+    }
+
+    fn pop_str(interp: &mut Interpreter) -> String {
+        interp.stack_mut().pop_string().unwrap()
+    }
+
+    #[test]
+    fn test_aliases() {
+        let mut interp = make_interp();
+        interp.exec_line("\"a+\" aliases").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 3);
+        assert_eq!(pop_str(&mut interp), "analog_add");
+        assert_eq!(pop_str(&mut interp), "a+");
+        assert_eq!(pop_str(&mut interp), "⊞");
+
+        interp.exec_line("\"dup\" aliases").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1);
+        assert!(interp.exec_line("\"nope\" aliases").is_err());
+    }
+
+    #[test]
+    fn test_glyph_and_ascii() {
+        let mut interp = make_interp();
+        interp.exec_line("\"analog_add\" glyph_of \"⊞\" ascii_of \"÷\" ascii_of").unwrap();
+        assert_eq!(pop_str(&mut interp), "/");
+        assert_eq!(pop_str(&mut interp), "a+");
+        assert_eq!(pop_str(&mut interp), "⊞");
+        assert!(interp.exec_line("\"dup\" glyph_of").is_err());
+    }
+
+    #[test]
+    fn test_code_as_data() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ greet ⺆ \"hi\" 2 * ⺘ \"greet\" fn_body").unwrap();
+        let body = interp.stack_mut().pop().unwrap();
+        assert_eq!(body.to_string(), "[\"hi\", 2, *]");
+
+        interp.exec_line("\"\\\"a b\\\" 1 2.5 +\" tokens").unwrap();
+        let tokens = interp.stack_mut().pop().unwrap();
+        assert_eq!(tokens.to_string(), "[\"a b\", 1, 2.5, +]");
+
+        // The rest of the line runs after the list
+        interp.push(tokens);
+        interp.exec_line("eval_list 10 *").unwrap();
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 35.0);
+        assert_eq!(pop_str(&mut interp), "a b");
+
+        interp.exec_line("⊕ sq ⺆ dup * ⺘ 7 \"sq\" fn_body eval_list").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 49);
+        interp.push(WofValue::list(vec![WofValue::integer(6), WofValue::string("sq"), WofValue::string("巡 sq")]));
+        interp.exec_line("eval_list").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1296);
+
+        assert!(interp.exec_line("\"nope\" fn_body").is_err());
+        assert!(interp.exec_line("1 eval_list").is_err());
+    }
+
+    #[test]
+    fn test_macros() {
+        let mut interp = make_interp();
+        interp.exec_line("macro unless ⺆ 0 \"not 若 ⺆\" list_set ⺘").unwrap();
+        interp.exec_line("0 unless ⺆ \"ran\" ⺘ 1 unless ⺆ \"skipped\" ⺘").unwrap();
+        assert_eq!(pop_str(&mut interp), "ran");
+        assert!(interp.stack().is_empty());
+
+        interp.exec_line("macro count ⺆ list_len list_new swap list_push ⺘ count a \"b\" 3").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_params() {
+        let mut interp = make_interp();
+        interp.exec_line("params").unwrap();
+        assert!(interp.stack_mut().pop().unwrap().to_string().contains("precision"));
+
+        interp.exec_line("\"precision\" 4 param_set parameterize \"precision\" 1 1 + ⺆ \"precision\" param_get ⺘ \"precision\" param_get").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 4);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 2);
+        assert!(interp.exec_line("\"nope\" param_get").is_err());
+        assert!(interp.exec_line("\"precision\" \"x\" param_set").is_err());
+    }
+
+    #[test]
+    fn test_help() {
+        let mut interp = make_interp();
+        let card = interp.help_card("√").unwrap();
+        assert!(card.starts_with("√  (also sqrt)  [math, woflang-ops]\n"), "{card}");
+        interp.exec_line("⊕ sq ⺆ dup * ⺘").unwrap();
+        assert_eq!(interp.help_card("sq").unwrap(), "sq is a user-defined function\n");
+
+        interp.exec_line("\"sqrt\" help").unwrap();
+        assert!(interp.stack().is_empty());
+        let err = interp.exec_line("\"nope\" help").unwrap_err();
+        assert!(err.to_string().contains("help: unknown op 'nope'"), "{err}");
+    }
+
+    #[test]
+    fn test_ops_catalog() {
+        let mut interp = make_interp();
+        interp.exec_line("ops_count ops_list").unwrap();
+        let list = interp.stack_mut().pop().unwrap().into_list().unwrap();
+        let count = interp.stack_mut().pop_integer().unwrap();
+        assert_eq!(list.len() as i64, count);
+        let names: Vec<&str> = list.iter().map(|v| v.as_str().unwrap()).collect();
+        assert!(names.windows(2).all(|w| w[0] < w[1]));
+        assert!(names.contains(&"√") && names.contains(&"a+"));
+
+        interp.exec_line("\"SQUARE ROOT\" ops_search").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "[sqrt, sqrt2, √, √2]");
+        interp.exec_line("\"glyph_\" ops_search").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "[glyph_of]");
+
+        interp.exec_line("\"reflect\" ops_category").unwrap();
+        let reflect = interp.stack_mut().pop().unwrap().into_list().unwrap();
+        assert!(reflect.iter().any(|v| v.as_str().unwrap() == "ops_category"));
+        assert!(interp.exec_line("\"nope\" ops_category").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Validating data against schemas defined with `schema "Name" ...`.
+//!
+//! | Operation  | Stack Effect                 | Description |
+//! |------------|------------------------------|-------------|
+//! | `validate` | ( v schema -- bool errors )  | Check `v` against a schema or type |
+//!
+//! `schema` is any type a schema field can have: a schema name, a
+//! built-in type such as `"map"`, or `"list<Row>"` for a whole table.
+//! `errors` is a list of strings, one per problem, each starting with
+//! the path to the offending value:
+//!
+//! ```text
+//! schema "Row" "id:int(1..)" "name:string(1..40)" "score?:number(0..100)"
+//! "rows.json" file_read json_parse "list<Row>" validate
+//! # → 0 and errors such as "[3].id: 0 is below the minimum 1"
+//! #   and "[7].name: missing required key"
+//! ```
+
+use woflang_core::{InterpreterContext, Result, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Register the validation operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("validate", op_validate);
+
+    crate::describe(interp, "schema", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[(
+    &["validate"],
+    "( v schema -- bool errors )",
+    "Check v against a schema or type, listing each problem",
+    "schema \"P\" \"x:int\" map_new \"x\" \"1\" map_set \"P\" validate",
+)];
+
+fn op_validate(interp: &mut Interpreter) -> Result<()> {
+    let schema = interp.stack_mut().pop_string()?;
+    let value = interp.stack_mut().pop()?;
+    let errors = interp.validate(&value, &schema)?;
+    interp.push(WofValue::boolean(errors.is_empty()));
+    interp.push(WofValue::list(errors.into_iter().map(WofValue::string).collect()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        crate::register_all(&mut interp);
+        interp
+    }
+
+    fn validate_json(interp: &mut Interpreter, json: &str, schema: &str) -> (bool, Vec<String>) {
+        interp.push(WofValue::string(json));
+        interp.push(WofValue::string(schema));
+        interp.exec_line("swap json_parse swap validate").unwrap();
+        let errors = interp.stack_mut().pop().unwrap();
+        let errors = errors.as_list().unwrap().iter().map(ToString::to_string).collect();
+        (interp.stack_mut().pop_bool().unwrap(), errors)
+    }
+
+    #[test]
+    fn test_validate_parsed_json() {
+        let mut interp = make_interp();
+        interp.exec_line(r#"schema "Row" "id:int(1..)" "name:string" "score?:number(0..100)""#).unwrap();
+
+        let rows = r#"[{"id": 1, "name": "a"}, {"id": 2, "name": "b", "score": 99.5}]"#;
+        assert_eq!(validate_json(&mut interp, rows, "list<Row>"), (true, Vec::new()));
+
+        let (valid, errors) = validate_json(&mut interp, r#"[{"id": 0, "score": 101}, 5]"#, "list<Row>");
+        assert!(!valid);
+        assert_eq!(
+            errors,
+            [
+                "[0].id: 0 is below the minimum 1",
+                "[0].name: missing required key",
+                "[0].score: 101 is above the maximum 100",
+                "[1]: expected Row, got integer",
+            ]
+        );
+
+        assert!(interp.exec_line(r#"map_new "Nope" validate"#).is_err());
+        assert!(interp.stack().is_empty());
+    }
+}
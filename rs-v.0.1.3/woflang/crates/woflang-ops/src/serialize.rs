@@ -0,0 +1,440 @@
+//! Serialization: values to and from text formats.
+//!
+//! | Operation        | Stack Effect   | Description |
+//! |------------------|----------------|-------------|
+//! | `json_parse`     | ( s -- v )     | Parse JSON into nested maps and lists |
+//! | `json_stringify` | ( v -- s )     | Encode a value as compact JSON |
+//!
+//! JSON objects become maps with string keys in document order, arrays
+//! become lists, `true` and `false` become `1` and `0`, and `null`
+//! becomes nil. Numbers without a fraction or exponent stay integers,
+//! growing into bigints when they do not fit in 64 bits:
+//!
+//! ```text
+//! "data.json" file_read json_parse "constants" map_get
+//! map_new "hp" 10 map_set json_stringify     # {"hp":10}
+//! ```
+//!
+//! `json_stringify` accepts nil, numbers, strings, symbols, lists, maps
+//! with string keys and sample buffers. Whole doubles keep a `.0` so they
+//! parse back as doubles; infinities and NaN have no JSON form.
+
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use woflang_core::{BigInt, InterpreterContext, Result, WofError, WofMap, WofType, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Nesting depth beyond which documents are refused rather than
+/// risking the native stack.
+const MAX_DEPTH: usize = 512;
+
+/// Register the serialization operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("json_parse", op_json_parse);
+    interp.register("json_stringify", op_json_stringify);
+
+    crate::describe(interp, "serialize", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["json_parse"], "( s -- v )", "Parse JSON into nested maps and lists", "\"{\\\"a\\\": [1, 2.5, null]}\" json_parse"),
+    (&["json_stringify"], "( v -- s )", "Encode a value as compact JSON", "map_new \"hp\" 10 map_set json_stringify"),
+];
+
+fn op_json_parse(interp: &mut Interpreter) -> Result<()> {
+    let text = interp.stack_mut().pop_string()?;
+    let value = parse(&text).map_err(|e| WofError::Runtime(format!("json_parse: {e}")))?;
+    interp.push(value);
+    Ok(())
+}
+
+fn op_json_stringify(interp: &mut Interpreter) -> Result<()> {
+    let value = interp.stack_mut().pop()?;
+    let mut out = String::new();
+    stringify(&value, &mut out).map_err(|e| WofError::Runtime(format!("json_stringify: {e}")))?;
+    interp.push(WofValue::string(out));
+    Ok(())
+}
+
+/// Parse a JSON document.
+fn parse(text: &str) -> std::result::Result<WofValue, String> {
+    let mut parser = Parser { text, pos: 0, depth: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < text.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    /// Describe a problem at the current position.
+    fn error(&self, what: &str) -> String {
+        let before = &self.text[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+        format!("{what} at line {line} column {column}")
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `byte` after any whitespace, or fail.
+    fn expect(&mut self, byte: u8) -> std::result::Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() != Some(byte) {
+            return Err(self.error(&format!("expected '{}'", byte as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn value(&mut self) -> std::result::Result<WofValue, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => self.string().map(WofValue::string),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) if self.literal("true") => Ok(WofValue::boolean(true)),
+            Some(_) if self.literal("false") => Ok(WofValue::boolean(false)),
+            Some(_) if self.literal("null") => Ok(WofValue::nil()),
+            Some(_) => Err(self.error("expected a value")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    /// Parse an object or array one level deeper.
+    fn nested(&mut self, parse: fn(&mut Self) -> std::result::Result<WofValue, String>) -> std::result::Result<WofValue, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(&format!("nested deeper than {MAX_DEPTH}")));
+        }
+        self.depth += 1;
+        self.pos += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn literal(&mut self, word: &str) -> bool {
+        let found = self.text[self.pos..].starts_with(word);
+        if found {
+            self.pos += word.len();
+        }
+        found
+    }
+
+    /// The rest of an object, after its `{`.
+    fn object(&mut self) -> std::result::Result<WofValue, String> {
+        let mut map = WofMap::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(WofValue::map(map));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected a string key"));
+            }
+            let key = self.string()?;
+            self.expect(b':')?;
+            let value = self.value()?;
+            map.insert(WofValue::string(key), value);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(WofValue::map(map));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    /// The rest of an array, after its `[`.
+    fn array(&mut self) -> std::result::Result<WofValue, String> {
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(WofValue::list(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(WofValue::list(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    /// A string literal, starting at its opening quote.
+    fn string(&mut self) -> std::result::Result<String, String> {
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.text[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, e)| e) {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            let unit = hex4(&mut chars);
+                            let code = match unit {
+                                Some(high @ 0xD800..=0xDBFF) => {
+                                    let low = (chars.next().map(|(_, c)| c) == Some('\\')
+                                        && chars.next().map(|(_, c)| c) == Some('u'))
+                                    .then(|| hex4(&mut chars))
+                                    .flatten()
+                                    .filter(|low| (0xDC00..=0xDFFF).contains(low));
+                                    low.map(|low| 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+                                }
+                                other => other,
+                            };
+                            let Some(c) = code.and_then(char::from_u32) else {
+                                self.pos += i;
+                                return Err(self.error("invalid \\u escape"));
+                            };
+                            c
+                        }
+                        _ => {
+                            self.pos += i;
+                            return Err(self.error("invalid escape"));
+                        }
+                    };
+                    out.push(escaped);
+                }
+                c if c < ' ' => {
+                    self.pos += i;
+                    return Err(self.error("unescaped control character in string"));
+                }
+                c => out.push(c),
+            }
+        }
+        self.pos = self.text.len();
+        Err(self.error("unterminated string"))
+    }
+
+    fn number(&mut self) -> std::result::Result<WofValue, String> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            let from = p.pos;
+            while matches!(p.peek(), Some(b'0'..=b'9')) {
+                p.pos += 1;
+            }
+            p.pos > from
+        };
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        let int_start = self.pos;
+        if !digits(self) || (self.text.as_bytes()[int_start] == b'0' && self.pos - int_start > 1) {
+            self.pos = start;
+            return Err(self.error("invalid number"));
+        }
+        let mut integral = true;
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            integral = false;
+            if !digits(self) {
+                return Err(self.error("expected digits after '.'"));
+            }
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            integral = false;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                return Err(self.error("expected digits in exponent"));
+            }
+        }
+
+        let text = &self.text[start..self.pos];
+        if integral {
+            if let Ok(n) = text.parse::<i64>() {
+                return Ok(WofValue::integer(n));
+            }
+            if let Ok(n) = BigInt::from_str(text) {
+                return Ok(WofValue::bigint(n));
+            }
+        }
+        text.parse::<f64>().map(WofValue::double).map_err(|_| self.error("invalid number"))
+    }
+}
+
+/// Read four hex digits of a `\u` escape.
+fn hex4(chars: &mut std::str::CharIndices<'_>) -> Option<u32> {
+    (0..4).try_fold(0, |acc, _| Some(acc * 16 + chars.next()?.1.to_digit(16)?))
+}
+
+/// Append `value` to `out` as JSON.
+fn stringify(value: &WofValue, out: &mut String) -> std::result::Result<(), String> {
+    match value.value_type() {
+        WofType::Unknown => out.push_str("null"),
+        WofType::Integer | WofType::BigInt => {
+            let _ = write!(out, "{value}");
+        }
+        WofType::Double => number(value.as_double().map_err(|e| e.to_string())?, out)?,
+        WofType::String | WofType::Symbol => quote(value.as_str().map_err(|e| e.to_string())?, out),
+        WofType::List => {
+            out.push('[');
+            for (i, item) in value.as_list().map_err(|e| e.to_string())?.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                stringify(item, out)?;
+            }
+            out.push(']');
+        }
+        WofType::Map => {
+            out.push('{');
+            for (i, (key, item)) in value.as_map().map_err(|e| e.to_string())?.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                let key = key.as_str().map_err(|_| format!("object keys must be strings, not {}", key.value_type()))?;
+                quote(key, out);
+                out.push(':');
+                stringify(item, out)?;
+            }
+            out.push('}');
+        }
+        WofType::Buffer => {
+            out.push('[');
+            for (i, &sample) in value.as_buffer().map_err(|e| e.to_string())?.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                number(sample, out)?;
+            }
+            out.push(']');
+        }
+        other => return Err(format!("{other} values have no JSON form")),
+    }
+    Ok(())
+}
+
+fn number(x: f64, out: &mut String) -> std::result::Result<(), String> {
+    if !x.is_finite() {
+        return Err(format!("{x} has no JSON form"));
+    }
+    if x.fract() == 0.0 && x.abs() < 1e16 {
+        let _ = write!(out, "{x:.1}");
+    } else {
+        let _ = write!(out, "{x:?}");
+    }
+    Ok(())
+}
+
+fn quote(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        crate::register_all(&mut interp);
+        interp
+    }
+
+    fn json(interp: &mut Interpreter, value: WofValue) -> String {
+        interp.push(value);
+        interp.exec_line("json_stringify").unwrap();
+        interp.stack_mut().pop_string().unwrap()
+    }
+
+    #[test]
+    fn test_json_parse() {
+        let doc = r#" {"name": "pi", "value": 2.5, "tags": ["math", true, null],
+                      "big": 123456789012345678901234567890, "esc": "a\"\\\n\u00e9\ud83d\udc3a", "z": -0, "e": 1E2} "#;
+        let value = parse(doc).unwrap();
+        let map = value.as_map().unwrap();
+        let get = |k: &str| map.get(&WofValue::string(k)).unwrap().clone();
+        let keys: Vec<String> = map.keys().map(ToString::to_string).collect();
+        assert_eq!(keys, ["name", "value", "tags", "big", "esc", "z", "e"]);
+        assert_eq!(get("value").as_double().unwrap(), 2.5);
+        assert_eq!(get("tags").to_string(), "[math, 1, <nil>]");
+        assert_eq!(get("big").value_type(), WofType::BigInt);
+        assert_eq!(get("esc").as_str().unwrap(), "a\"\\\né🐺");
+        assert_eq!(get("z").as_integer().unwrap(), 0);
+        assert_eq!(get("e").as_double().unwrap(), 100.0);
+
+        for bad in ["", "[1,]", "{\"a\" 1}", "01", "1.", "\"\\x\"", "\"\\ud800\"", "[1] 2", "nul", "\"a\nb\""] {
+            assert!(parse(bad).is_err(), "{bad:?}");
+        }
+        let err = parse("[1,\n  2,,]").unwrap_err();
+        assert!(err.contains("line 2 column 5"), "{err}");
+        assert!(parse(&"[".repeat(MAX_DEPTH + 1)).unwrap_err().contains("nested deeper"));
+    }
+
+    #[test]
+    fn test_json_stringify() {
+        let mut interp = make_interp();
+        interp.exec_line("map_new \"hp\" 10 map_set \"pos\" 1.5 2 2 list_from map_set \"say\" \"\\\"hi\\\"\\n\" map_set").unwrap();
+        let value = interp.stack_mut().pop().unwrap();
+        assert_eq!(json(&mut interp, value.clone()), r#"{"hp":10,"pos":[1.5,2],"say":"\"hi\"\n"}"#);
+        assert_eq!(json(&mut interp, WofValue::list(vec![WofValue::double(3.0), WofValue::nil()])), "[3.0,null]");
+
+        // Round trip
+        interp.push(value.clone());
+        interp.exec_line("json_stringify json_parse").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap(), value);
+
+        interp.push(WofValue::double(f64::NAN));
+        assert!(interp.exec_line("json_stringify").is_err());
+        assert!(interp.exec_line("map_new 1 2 map_set json_stringify").is_err());
+        assert!(interp.exec_line("\"[1, 2\" json_parse").is_err());
+    }
+}
@@ -14,9 +14,22 @@
 //! | `depth`      | ( -- n)          | Push stack depth |
 //! | `clear`      | (... -- )        | Clear entire stack |
 //! | `stack_slayer` | (... -- )      | Dramatic clear 🗡️ |
+//! | `checkpoint` | (name -- )       | Save the stack and variables as `name` |
+//! | `rollback`   | (name -- )       | Go back to checkpoint `name` |
+//!
+//! A checkpoint can be rolled back to any number of times; saving one
+//! under the same name replaces it:
+//!
+//! ```text
+//! 1 2 "base" checkpoint
+//! drop 99 *               # oops
+//! "base" rollback         # 1 2
+//! ```
+
+use std::collections::HashMap;
 
 use woflang_core::{InterpreterContext, Result, WofError, WofValue};
-use woflang_runtime::Interpreter;
+use woflang_runtime::{Interpreter, Snapshot};
 
 /// Register all stack manipulation operations.
 pub fn register(interp: &mut Interpreter) {
@@ -37,8 +50,38 @@ pub fn register(interp: &mut Interpreter) {
     // Dramatic operations 🐺
     interp.register("stack_slayer", op_stack_slayer);
     interp.register("resurrect", op_resurrect);
+
+    interp.register("checkpoint", op_checkpoint);
+    interp.register("rollback", op_rollback);
+
+    crate::describe(interp, "stack", DOCS);
 }
 
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["dup"], "( a -- a a )", "Duplicate top", "1 dup"),
+    (&["drop"], "( a -- )", "Remove top", "1 2 drop"),
+    (&["swap"], "( a b -- b a )", "Swap top two", "1 2 swap"),
+    (&["over"], "( a b -- a b a )", "Copy second to top", "1 2 over"),
+    (&["rot"], "( a b c -- b c a )", "Rotate top three", "1 2 3 rot"),
+    (&["nip"], "( a b -- b )", "Remove second", "1 2 nip"),
+    (&["tuck"], "( a b -- b a b )", "Copy top below second", "1 2 tuck"),
+    (&["2dup"], "( a b -- a b a b )", "Duplicate top pair", "1 2 2dup"),
+    (&["2drop"], "( a b -- )", "Remove top pair", "1 2 3 2drop"),
+    (&["2swap"], "( a b c d -- c d a b )", "Swap top two pairs", "1 2 3 4 2swap"),
+    (&["depth"], "( -- n )", "Push stack depth", "1 2 3 depth"),
+    (&["clear"], "( ... -- )", "Clear entire stack", "1 2 3 clear"),
+    (&["pick"], "( ... n -- ... v )", "Copy the n-th value from the top (0 = top)", "10 20 30 2 pick"),
+    (&["stack_slayer"], "( ... -- )", "Dramatic clear", "1 2 3 stack_slayer"),
+    (&["resurrect"], "( -- π e φ 42 )", "Push the mystical constants", "resurrect"),
+    (&["checkpoint"], "( name -- )", "Save the stack and variables under a name", "1 2 \"a\" checkpoint"),
+    (&["rollback"], "( name -- )", "Restore the stack and variables saved by checkpoint", "1 \"a\" checkpoint 2 3 \"a\" rollback"),
+];
+
+/// Snapshots saved by `checkpoint`, by name.
+#[derive(Default)]
+struct Checkpoints(HashMap<String, Snapshot>);
+
 fn op_dup(interp: &mut Interpreter) -> Result<()> {
     interp.stack_mut().dup()
 }
@@ -151,6 +194,22 @@ fn op_resurrect(interp: &mut Interpreter) -> Result<()> {
     Ok(())
 }
 
+fn op_checkpoint(interp: &mut Interpreter) -> Result<()> {
+    let name = interp.stack_mut().pop()?.as_string()?;
+    let snapshot = interp.snapshot();
+    interp.state_mut::<Checkpoints>().0.insert(name, snapshot);
+    Ok(())
+}
+
+fn op_rollback(interp: &mut Interpreter) -> Result<()> {
+    let name = interp.stack_mut().pop()?.as_string()?;
+    let Some(snapshot) = interp.state::<Checkpoints>().and_then(|c| c.0.get(&name)).cloned() else {
+        return Err(WofError::Runtime(format!("rollback: no checkpoint named '{name}'")));
+    };
+    interp.restore(&snapshot);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,4 +296,22 @@ mod tests {
         assert_eq!(interp.stack().len(), 4);
         assert_eq!(interp.stack_mut().pop_integer().unwrap(), 42);
     }
+
+    #[test]
+    fn test_checkpoint_and_rollback() {
+        let mut interp = make_interp();
+        interp.exec_line(r#"5 字 x 1 2 "base" checkpoint"#).unwrap();
+        interp.exec_line("drop clear 7 支 x 8 字 y").unwrap();
+        interp.exec_line(r#""base" rollback"#).unwrap();
+        assert_eq!(interp.stack().len(), 2);
+        assert_eq!(interp.get_var("x").unwrap().as_integer().unwrap(), 5);
+        assert!(!interp.has_var("y"));
+
+        // Rolling back again still works; a new checkpoint replaces it
+        interp.exec_line(r#"clear "base" rollback 3 "base" checkpoint clear "base" rollback"#).unwrap();
+        assert_eq!(interp.stack().len(), 3);
+
+        let err = interp.exec_line(r#""nope" rollback"#).unwrap_err();
+        assert!(err.to_string().contains("no checkpoint named 'nope'"), "{err}");
+    }
 }
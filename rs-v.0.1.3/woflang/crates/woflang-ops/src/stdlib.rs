@@ -0,0 +1,92 @@
+//! The self-hosted standard library, written in Woflang.
+//!
+//! The `.wof` sources under `stdlib/` are embedded at compile time and
+//! define user functions rather than native ops:
+//!
+//! | File               | Functions |
+//! |--------------------|-----------|
+//! | `combinators.wof`  | `-rot`, `spin`, `dupd`, `swapd`, `nip2`, `dup3`, `drop3` |
+//! | `math.wof`         | `sq`, `double`, `half`, `recip`, `avg2`, `percent`, `zero?`, `pos?`, `neg?`, `even?`, `odd?`, `between?`, `clamp01`, `dist2d` |
+//! | `list.wof`         | `range`, `drop_n`, `sum_n`, `product_n`, `max_n`, `min_n` |
+//!
+//! List functions work on counted stack segments: `x1 .. xn n`.
+//!
+//! Because registered ops take precedence over user functions, none of
+//! these names may collide with an op; a native op of the same name
+//! would silently win.
+
+use woflang_core::{Result, WofError};
+use woflang_runtime::Interpreter;
+
+/// The embedded library sources as `(file name, source)` pairs, in load order.
+pub const SOURCES: &[(&str, &str)] = &[
+    ("combinators.wof", include_str!("../stdlib/combinators.wof")),
+    ("math.wof", include_str!("../stdlib/math.wof")),
+    ("list.wof", include_str!("../stdlib/list.wof")),
+];
+
+/// Define every standard library function in `interp`.
+///
+/// Needs the stack, arithmetic, math and logic ops to be registered
+/// before the functions are called, though not before loading.
+pub fn load(interp: &mut Interpreter) -> Result<()> {
+    for (file, source) in SOURCES {
+        interp
+            .exec_source(source)
+            .map_err(|e| WofError::Runtime(format!("stdlib/{file}: {e}")))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use woflang_core::InterpreterContext;
+
+    fn run(code: &str) -> Vec<f64> {
+        let mut interp = Interpreter::new();
+        crate::register_all(&mut interp);
+        load(&mut interp).unwrap();
+        interp.exec_line(code).unwrap();
+        let mut values = Vec::new();
+        while let Ok(v) = interp.pop() {
+            values.push(v.as_numeric().unwrap_or_else(|_| f64::from(u8::from(v.as_bool()))));
+        }
+        values.reverse();
+        values
+    }
+
+    #[test]
+    fn test_load_leaves_stack_empty() {
+        let mut interp = Interpreter::new();
+        load(&mut interp).unwrap();
+        assert!(interp.stack().is_empty());
+        assert!(interp.has_function("sq"));
+        assert!(interp.has_function("sum_n"));
+    }
+
+    #[test]
+    fn test_combinators() {
+        assert_eq!(run("1 2 3 -rot"), [3.0, 1.0, 2.0]);
+        assert_eq!(run("1 2 3 spin"), [3.0, 2.0, 1.0]);
+        assert_eq!(run("1 2 3 swapd"), [2.0, 1.0, 3.0]);
+        assert_eq!(run("1 2 3 dup3"), [1.0, 2.0, 3.0, 1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_math() {
+        assert_eq!(run("3 sq 1 +"), [10.0]);
+        assert_eq!(run("0 0 3 4 dist2d"), [5.0]);
+        assert_eq!(run("5 1 10 between? 11 1 10 between?"), [1.0, 0.0]);
+        assert_eq!(run("4 even? 4 odd?"), [1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(run("4 range"), [1.0, 2.0, 3.0, 4.0, 4.0]);
+        assert_eq!(run("4 range sum_n"), [10.0]);
+        assert_eq!(run("4 range product_n"), [24.0]);
+        assert_eq!(run("3 1 4 3 max_n 7 1 min_n"), [4.0, 7.0]);
+        assert_eq!(run("0 sum_n 0 range"), [0.0, 0.0]);
+    }
+}
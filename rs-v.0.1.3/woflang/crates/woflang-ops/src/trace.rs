@@ -0,0 +1,161 @@
+//! Educational trace of arithmetic and math operations.
+//!
+//! | Operation        | Stack Effect | Description |
+//! |------------------|--------------|-------------|
+//! | `trace_math_on`  | ( -- )       | Explain each math op as it runs |
+//! | `trace_math_off` | ( -- )       | Stop tracing |
+//!
+//! While enabled, every arithmetic or math op prints one line showing the
+//! calculation it performed and how the stack depth changed:
+//!
+//! ```text
+//! pow: 2^8 = 256.0   [depth 2 → 1]
+//! sqrt: sqrt(16) = 4.0   [depth 1 → 1]
+//! ```
+//!
+//! Tracing is implemented as an op hook, so it costs nothing while off.
+
+use woflang_core::{Out, Result, WofStack, WofValue};
+use woflang_runtime::{Interpreter, OpHook};
+
+/// Hook name used by `trace_math_on`.
+const HOOK_NAME: &str = "trace_math";
+
+/// How an op is written in the trace.
+#[derive(Debug, Clone, Copy)]
+enum Notation {
+    /// `a op b`.
+    Infix(&'static str),
+    /// `name(a, b)`.
+    Call2,
+    /// `name(a)`.
+    Call1,
+}
+
+fn notation(op: &str) -> Option<Notation> {
+    Some(match op {
+        "+" => Notation::Infix("+"),
+        "-" => Notation::Infix("-"),
+        "*" | "×" => Notation::Infix("×"),
+        "/" | "÷" => Notation::Infix("÷"),
+        "%" | "mod" => Notation::Infix("mod"),
+        "^" | "pow" => Notation::Infix("^"),
+        "min" | "max" | "atan2" | "hypot" | "gcd" | "lcm" => Notation::Call2,
+        "neg" | "abs" | "inc" | "dec" | "sqrt" | "√" | "cbrt" | "sin" | "cos" | "tan" | "asin"
+        | "acos" | "atan" | "sinh" | "cosh" | "tanh" | "exp" | "ln" | "log" | "log10"
+        | "log2" | "floor" | "ceil" | "round" | "trunc" | "frac" | "sign" | "factorial"
+        | "deg2rad" | "rad2deg" => Notation::Call1,
+        _ => return None,
+    })
+}
+
+/// Describe one op application, e.g. `pow: 2^8 = 256`.
+fn describe(op: &str, args: &[WofValue], result: &WofValue) -> Option<String> {
+    let calc = match (notation(op)?, args) {
+        (Notation::Infix("^"), [a, b]) => format!("{a}^{b}"),
+        (Notation::Infix(sym), [a, b]) => format!("{a} {sym} {b}"),
+        (Notation::Call2, [a, b]) => format!("{op}({a}, {b})"),
+        (Notation::Call1, [a]) => format!("{op}({a})"),
+        _ => return None,
+    };
+    Some(format!("{op}: {calc} = {result}"))
+}
+
+/// Op hook printing a line per math op.
+#[derive(Debug, Default)]
+struct MathTrace {
+    /// Operands and stack depth captured before the current op.
+    pending: Option<(Vec<WofValue>, usize)>,
+}
+
+impl OpHook for MathTrace {
+    fn before_op(&mut self, name: &str, stack: &WofStack) {
+        let arity = match notation(name) {
+            Some(Notation::Infix(_) | Notation::Call2) => 2,
+            Some(Notation::Call1) => 1,
+            None => {
+                self.pending = None;
+                return;
+            }
+        };
+        let depth = stack.len();
+        self.pending = (depth >= arity)
+            .then(|| (stack.as_slice()[depth - arity..].to_vec(), depth));
+    }
+
+    fn after_op(&mut self, name: &str, stack: &WofStack, result: &Result<()>, out: &Out) {
+        let Some((args, depth)) = self.pending.take() else {
+            return;
+        };
+        if result.is_err() {
+            return;
+        }
+        let Ok(top) = stack.peek() else {
+            return;
+        };
+        if let Some(line) = describe(name, &args, top) {
+            writeln!(out, "{line}   [depth {depth} → {}]", stack.len());
+        }
+    }
+}
+
+/// Register the math trace operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("trace_math_on", |interp| {
+        interp.add_hook(HOOK_NAME, MathTrace::default());
+        Ok(())
+    });
+
+    interp.register("trace_math_off", |interp| {
+        interp.remove_hook(HOOK_NAME);
+        Ok(())
+    });
+
+    crate::describe(interp, "trace", DOCS);
+}
+
+/// Reference metadata for the ops above.
+const DOCS: &[crate::OpDoc] = &[
+    (&["trace_math_on"], "( -- )", "Explain each math op as it runs", "trace_math_on 2 3 + trace_math_off"),
+    (&["trace_math_off"], "( -- )", "Stop tracing", ""),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_common_ops() {
+        let two = WofValue::integer(2);
+        let eight = WofValue::integer(8);
+        assert_eq!(
+            describe("pow", &[two.clone(), eight.clone()], &WofValue::integer(256)).unwrap(),
+            "pow: 2^8 = 256"
+        );
+        assert_eq!(
+            describe("+", &[two.clone(), eight], &WofValue::integer(10)).unwrap(),
+            "+: 2 + 8 = 10"
+        );
+        assert_eq!(
+            describe("sqrt", &[WofValue::integer(16)], &WofValue::double(4.0)).unwrap(),
+            "sqrt: sqrt(16) = 4.0"
+        );
+        assert!(describe("dup", &[two], &WofValue::integer(2)).is_none());
+    }
+
+    #[test]
+    fn toggles_hook() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        crate::arithmetic::register(&mut interp);
+
+        let printed = woflang_core::Buffer::new();
+        interp.set_output(printed.clone());
+        interp.exec_line("trace_math_on 2 3 +").unwrap();
+        assert!(interp.has_hook(HOOK_NAME));
+        assert_eq!(printed.take(), "+: 2 + 3 = 5   [depth 2 → 1]\n");
+        interp.exec_line("trace_math_off 1 1 +").unwrap();
+        assert!(!interp.has_hook(HOOK_NAME));
+        assert_eq!(printed.contents(), "");
+    }
+}
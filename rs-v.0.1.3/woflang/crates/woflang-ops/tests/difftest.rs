@@ -0,0 +1,31 @@
+//! Differential test of the bytecode VM against the tree-walking
+//! interpreter, over the scripts in `tests/difftest` with every standard
+//! op registered.
+
+use woflang_runtime::difftest::{diff_corpus, load_corpus, Backend, BytecodeBackend, InterpreterBackend};
+use woflang_runtime::Interpreter;
+
+fn make_interp() -> Interpreter {
+    let mut interp = Interpreter::new();
+    woflang_ops::register_all(&mut interp);
+    interp
+}
+
+#[test]
+fn bytecode_matches_the_interpreter_on_the_corpus() {
+    let corpus = load_corpus(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/difftest")).unwrap();
+    assert!(!corpus.is_empty());
+
+    // The scripts named error_* stop early and no others do, so the
+    // corpus covers both paths.
+    let mut walked = InterpreterBackend::new("interpreter", make_interp);
+    for (name, source) in &corpus {
+        let outcome = walked.run(source);
+        assert_eq!(outcome.error.is_some(), name.starts_with("error_"), "{name}: {outcome}");
+    }
+
+    let mut compiled = BytecodeBackend::new("bytecode", make_interp);
+    let report = diff_corpus(&mut walked, &mut compiled, &corpus);
+    assert_eq!(report.scripts, corpus.len());
+    assert!(report.is_clean(), "{report}");
+}
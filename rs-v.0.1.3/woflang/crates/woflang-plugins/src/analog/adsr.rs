@@ -0,0 +1,208 @@
+//! ADSR envelope generators for analog control signals.
+//!
+//! | Operation   | Stack Effect             | Description |
+//! |-------------|--------------------------|-------------|
+//! | `adsr_new`  | ( name a d s r -- )      | Create or replace an envelope |
+//! | `adsr_gate` | ( name on/off -- )       | Open or close the gate |
+//! | `adsr_tick` | ( name dt -- level )     | Advance by `dt` seconds and read the level |
+//!
+//! Attack, decay and release are times in seconds; sustain is a level in
+//! `[0, 1]`. Opening the gate ramps up from the current level, so a
+//! retrigger never clicks back to zero; closing it releases from wherever
+//! the envelope is. The level is scaled onto `[0, max]` of the current
+//! analog mode, so in `a.201` a full envelope peaks at 100:
+//!
+//! ```text
+//! "env" 0.1 0.2 0.5 0.3 adsr_new
+//! "env" 1 adsr_gate
+//! "env" 0.1 adsr_tick      # 100 (end of attack)
+//! "env" 0.2 adsr_tick      # 50 (sustain)
+//! "env" "off" adsr_gate
+//! ```
+//!
+//! The gate accepts `"on"`/`"off"` or any truthy value.
+
+use std::collections::HashMap;
+
+use woflang_analog::get_analog_config;
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Envelope stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Idle,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+/// One envelope generator.
+#[derive(Debug, Clone)]
+pub(super) struct Adsr {
+    pub(super) attack: f64,
+    pub(super) decay: f64,
+    pub(super) sustain: f64,
+    pub(super) release: f64,
+    stage: Stage,
+    /// Current level in `[0, 1]`.
+    level: f64,
+    /// Level the release started from, for a fixed-length release.
+    release_from: f64,
+}
+
+impl Adsr {
+    pub(super) fn new(attack: f64, decay: f64, sustain: f64, release: f64) -> Self {
+        Self {
+            attack,
+            decay,
+            sustain: sustain.clamp(0.0, 1.0),
+            release,
+            stage: Stage::Idle,
+            level: 0.0,
+            release_from: 0.0,
+        }
+    }
+
+    pub(super) fn gate(&mut self, on: bool) {
+        if on {
+            self.stage = Stage::Attack;
+        } else if self.stage != Stage::Idle {
+            self.stage = Stage::Release;
+            self.release_from = self.level;
+        }
+    }
+
+    /// Advance by `dt` seconds, carrying leftover time across stages.
+    pub(super) fn tick(&mut self, mut dt: f64) -> f64 {
+        while dt > 0.0 {
+            let (target, rate) = match self.stage {
+                Stage::Idle | Stage::Sustain => break,
+                Stage::Attack => (1.0, 1.0 / self.attack),
+                Stage::Decay => (self.sustain, (1.0 - self.sustain) / self.decay),
+                Stage::Release => (0.0, self.release_from / self.release),
+            };
+            let distance = (target - self.level).abs();
+            let needed = if rate.is_finite() && rate > 0.0 { distance / rate } else { 0.0 };
+            if needed > dt {
+                self.level += (target - self.level).signum() * rate * dt;
+                break;
+            }
+            self.level = target;
+            dt -= needed;
+            self.stage = match self.stage {
+                Stage::Attack => Stage::Decay,
+                Stage::Decay => Stage::Sustain,
+                _ => Stage::Idle,
+            };
+        }
+        self.level
+    }
+}
+
+/// All envelopes of one interpreter, by
+/// [resource name](Interpreter::resource_name).
+#[derive(Debug, Default)]
+struct Envelopes(HashMap<String, Adsr>);
+
+fn envelope<'a>(interp: &'a mut Interpreter, op: &str, name: &str) -> Result<&'a mut Adsr> {
+    let key = interp.resource_name(name);
+    interp
+        .state_mut::<Envelopes>()
+        .0
+        .get_mut(&key)
+        .ok_or_else(|| WofError::runtime(format!("{op}: no envelope named '{name}'")))
+}
+
+/// Scale a `[0, 1]` level onto `[0, max]` of the current analog range.
+fn to_analog(level: f64) -> WofValue {
+    let config = get_analog_config();
+    let value = level * config.max();
+    if config.is_integer_mode() {
+        WofValue::integer(config.clamp_rounded(value) as i64)
+    } else {
+        WofValue::double(config.clamp(value))
+    }
+}
+
+/// Register the envelope operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("adsr_new", |interp| {
+        let release = interp.stack_mut().pop_numeric()?;
+        let sustain = interp.stack_mut().pop_numeric()?;
+        let decay = interp.stack_mut().pop_numeric()?;
+        let attack = interp.stack_mut().pop_numeric()?;
+        let name = interp.stack_mut().pop_string()?;
+        if [attack, decay, release].iter().any(|t| !t.is_finite() || *t < 0.0) {
+            return Err(WofError::runtime("adsr_new: times must be finite and non-negative"));
+        }
+        let env = Adsr::new(attack, decay, sustain, release);
+        let key = interp.resource_name(&name);
+        interp.state_mut::<Envelopes>().0.insert(key, env);
+        Ok(())
+    });
+
+    interp.register("adsr_gate", |interp| {
+        let gate = interp.stack_mut().pop()?;
+        let on = match gate.as_str() {
+            Ok("on") => true,
+            Ok("off") => false,
+            _ => gate.is_truthy(),
+        };
+        let name = interp.stack_mut().pop_string()?;
+        envelope(interp, "adsr_gate", &name)?.gate(on);
+        Ok(())
+    });
+
+    interp.register("adsr_tick", |interp| {
+        let dt = interp.stack_mut().pop_numeric()?;
+        let name = interp.stack_mut().pop_string()?;
+        let level = envelope(interp, "adsr_tick", &name)?.tick(dt);
+        interp.push(to_analog(level));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walks_through_stages() {
+        let mut env = Adsr::new(0.1, 0.2, 0.5, 0.4);
+        assert!(env.tick(1.0).abs() < 1e-12);
+
+        env.gate(true);
+        assert!((env.tick(0.05) - 0.5).abs() < 1e-9);
+        // Finishes the attack and runs half the decay in one step.
+        assert!((env.tick(0.15) - 0.75).abs() < 1e-9);
+        assert!((env.tick(1.0) - 0.5).abs() < 1e-9);
+        assert_eq!(env.stage, Stage::Sustain);
+
+        env.gate(false);
+        assert!((env.tick(0.2) - 0.25).abs() < 1e-9);
+        assert!(env.tick(1.0).abs() < 1e-12);
+        assert_eq!(env.stage, Stage::Idle);
+
+        let mut click = Adsr::new(0.0, 0.0, 1.0, 0.0);
+        click.gate(true);
+        assert!((click.tick(0.01) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn ticks_named_envelopes_in_analog_range() {
+        let mut interp = Interpreter::new();
+        crate::analog::register(&mut interp);
+
+        interp
+            .exec_line(r#"a.201 "env" 0.1 0.2 0.5 0.3 adsr_new "env" 1 adsr_gate "env" 0.1 adsr_tick"#)
+            .unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 100);
+        interp.exec_line(r#""env" "off" adsr_gate "env" 0.15 adsr_tick"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 50);
+
+        assert!(interp.exec_line(r#""nope" 1 adsr_gate"#).is_err());
+        assert!(interp.exec_line(r#""bad" -1 0 0 0 adsr_new"#).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+//! Control voltages streamed to external hardware.
+//!
+//! | Operation         | Stack Effect       | Description |
+//! |-------------------|--------------------|-------------|
+//! | `a.stream_open`   | ( target -- )      | Open the output, replacing any open one |
+//! | `a.stream_send`   | ( value|buf -- )   | Send a value, or every sample of a buffer |
+//! | `a.stream_rate`   | ( hz -- )          | Pace sends at `hz` values per second (0 = unpaced) |
+//! | `a.stream_close`  | ( -- )             | Close the output |
+//!
+//! The target is `"udp:host:port"`, `"tcp:host:port"` or the path of a
+//! serial device such as `"/dev/ttyUSB0"`, whose baud rate is set outside
+//! woflang (`stty -F /dev/ttyUSB0 115200`). Device paths go through the
+//! interpreter's sandbox like any other file.
+//!
+//! Each value is clamped to the current analog range and sent as one
+//! line of text (`"-0.25\n"`); over UDP every value is its own datagram.
+//! Text is easy to parse on a microcontroller and reads the same in
+//! every mode.
+//!
+//! With a rate set, each send waits for its slot, so a loop produces a
+//! steady control signal in real time:
+//!
+//! ```text
+//! "udp:192.168.1.40:9000" a.stream_open
+//! 100 a.stream_rate
+//! "wobble" "sine" 0.5 lfo_new
+//! 1000 ⨯ ⺆ "wobble" 0.01 lfo_tick a.stream_send ⺘   # ten seconds of LFO
+//! a.stream_close
+//! ```
+//!
+//! A late send does not try to catch up, so a stall shifts the signal
+//! rather than bursting it. Sending a buffer checks for cancellation
+//! between samples.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+use woflang_analog::ops;
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Where values go.
+#[derive(Debug)]
+enum Sink {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+    Serial(File),
+}
+
+impl Sink {
+    fn send(&mut self, line: &str) -> io::Result<()> {
+        match self {
+            Self::Udp(socket) => socket.send(line.as_bytes()).map(drop),
+            Self::Tcp(stream) => stream.write_all(line.as_bytes()),
+            Self::Serial(device) => device.write_all(line.as_bytes()),
+        }
+    }
+}
+
+/// The interpreter's output and its pacing.
+#[derive(Debug, Default)]
+struct CvOut {
+    sink: Option<Sink>,
+    /// Time between values; `None` sends as fast as asked.
+    period: Option<Duration>,
+    /// When the next paced value is due.
+    next: Option<Instant>,
+}
+
+impl CvOut {
+    /// Wait for the next slot, if paced.
+    fn pace(&mut self) {
+        let Some(period) = self.period else { return };
+        let now = Instant::now();
+        let due = self.next.filter(|&due| due > now).unwrap_or(now);
+        if let Some(wait) = due.checked_duration_since(now) {
+            std::thread::sleep(wait);
+        }
+        self.next = Some(due + period);
+    }
+
+    /// Send one value as a line, once its slot comes.
+    fn send(&mut self, value: &WofValue) -> Result<()> {
+        if self.sink.is_none() {
+            return Err(WofError::runtime("a.stream_send: no stream open (use a.stream_open)"));
+        }
+        self.pace();
+        if let Some(sink) = self.sink.as_mut() {
+            sink.send(&format!("{value}\n")).map_err(|e| WofError::runtime(format!("a.stream_send: {e}")))?;
+        }
+        Ok(())
+    }
+}
+
+/// The first address of `host:port`.
+fn address(target: &str) -> Result<SocketAddr> {
+    target
+        .to_socket_addrs()
+        .map_err(|e| WofError::runtime(format!("a.stream_open: cannot resolve '{target}': {e}")))?
+        .next()
+        .ok_or_else(|| WofError::runtime(format!("a.stream_open: '{target}' has no address")))
+}
+
+fn open(interp: &Interpreter, target: &str) -> Result<Sink> {
+    let failed = |e: io::Error| WofError::runtime(format!("a.stream_open: cannot open '{target}': {e}"));
+    if let Some(rest) = target.strip_prefix("udp:") {
+        let addr = address(rest.trim_start_matches("//"))?;
+        let local: SocketAddr = if addr.is_ipv4() { ([0, 0, 0, 0], 0).into() } else { ([0; 16], 0).into() };
+        let socket = UdpSocket::bind(local).map_err(failed)?;
+        socket.connect(addr).map_err(failed)?;
+        Ok(Sink::Udp(socket))
+    } else if let Some(rest) = target.strip_prefix("tcp:") {
+        let addr = address(rest.trim_start_matches("//"))?;
+        let stream = TcpStream::connect(addr).map_err(failed)?;
+        stream.set_nodelay(true).map_err(failed)?;
+        Ok(Sink::Tcp(stream))
+    } else {
+        let resolved = interp.sandbox().check_write(target)?;
+        let device = OpenOptions::new().append(true).create(true).open(resolved).map_err(failed)?;
+        Ok(Sink::Serial(device))
+    }
+}
+
+/// The values to send for a number or a buffer.
+fn values(value: &WofValue) -> Result<Vec<WofValue>> {
+    if value.as_numeric().is_ok() {
+        return Ok(vec![ops::op_analog_clamp(value)?]);
+    }
+    ops::op_buffer_values(value)
+        .map_err(|_| WofError::runtime(format!("a.stream_send: expected a number or a buffer, got {value}")))
+}
+
+/// Register the streaming operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("a.stream_open", |interp| {
+        let target = interp.stack_mut().pop_string()?;
+        let sink = open(interp, &target)?;
+        let out = interp.state_mut::<CvOut>();
+        out.sink = Some(sink);
+        out.next = None;
+        Ok(())
+    });
+
+    interp.register("a.stream_send", |interp| {
+        let value = interp.pop()?;
+        let values = values(&value)?;
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 && interp.is_cancelled() {
+                return Err(interp.interrupt(format!("a.stream_send: after {i} of {} values", values.len())));
+            }
+            interp.state_mut::<CvOut>().send(value)?;
+        }
+        Ok(())
+    });
+
+    interp.register("a.stream_rate", |interp| {
+        let hz = interp.stack_mut().pop_numeric()?;
+        if !hz.is_finite() || hz < 0.0 {
+            return Err(WofError::runtime("a.stream_rate: rate must be finite and non-negative"));
+        }
+        let out = interp.state_mut::<CvOut>();
+        out.period = (hz > 0.0).then(|| Duration::from_secs_f64(1.0 / hz));
+        out.next = None;
+        Ok(())
+    });
+
+    interp.register("a.stream_close", |interp| {
+        interp.state_mut::<CvOut>().sink = None;
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        crate::analog::register(&mut interp);
+        interp
+    }
+
+    #[test]
+    fn streams_clamped_values_over_udp() {
+        let mut interp = make_interp();
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let port = receiver.local_addr().unwrap().port();
+
+        interp.exec_line(&format!(r#"a.unit "udp:127.0.0.1:{port}" a.stream_open 0.5 a.stream_send"#)).unwrap();
+        interp.exec_line("3 a.stream_send -0.25 0.25 2 a.buf a.stream_send").unwrap();
+        let mut datagrams = Vec::new();
+        let mut buf = [0; 64];
+        for _ in 0..4 {
+            let n = receiver.recv(&mut buf).unwrap();
+            datagrams.push(String::from_utf8_lossy(&buf[..n]).into_owned());
+        }
+        assert_eq!(datagrams, ["0.5\n", "1.0\n", "-0.25\n", "0.25\n"]);
+
+        assert!(interp.exec_line(r#""text" a.stream_send"#).is_err());
+        interp.exec_line("a.stream_close a.reset").unwrap();
+        assert!(interp.exec_line("1 a.stream_send").is_err());
+    }
+
+    #[test]
+    fn paces_sends_over_tcp() {
+        let mut interp = make_interp();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        interp.exec_line(&format!(r#"a.201 "tcp:127.0.0.1:{port}" a.stream_open 50 a.stream_rate"#)).unwrap();
+        let (conn, _) = listener.accept().unwrap();
+        let start = Instant::now();
+        interp.exec_line("10 20 30 40 4 a.buf a.stream_send").unwrap();
+        // The first value goes at once and each later one 20 ms after it.
+        assert!(start.elapsed() >= Duration::from_millis(60));
+        interp.exec_line("a.stream_close a.reset 0 a.stream_rate").unwrap();
+
+        let lines: Vec<String> = BufReader::new(conn).lines().map(|l| l.unwrap()).collect();
+        assert_eq!(lines, ["10", "20", "30", "40"]);
+        assert!(interp.exec_line("-1 a.stream_rate").is_err());
+    }
+
+    #[test]
+    fn devices_are_opened_through_the_sandbox() {
+        let mut interp = make_interp();
+        let dir = std::env::temp_dir().join(format!("woflang-cv-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let device = dir.join("tty");
+
+        interp.exec_line(&format!(r#"a.2001 "{}" a.stream_open 1500 a.stream_send"#, device.display())).unwrap();
+        interp.exec_line("a.stream_close a.reset").unwrap();
+        assert_eq!(std::fs::read_to_string(&device).unwrap(), "1000\n");
+
+        let mut sandbox = woflang_runtime::Sandbox::default();
+        sandbox.read_only = true;
+        interp.set_sandbox(sandbox);
+        assert!(interp.exec_line(&format!(r#""{}" a.stream_open"#, device.display())).is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
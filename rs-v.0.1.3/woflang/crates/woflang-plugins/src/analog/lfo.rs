@@ -0,0 +1,216 @@
+//! Low-frequency oscillators with per-name phase state.
+//!
+//! | Operation   | Stack Effect             | Description |
+//! |-------------|--------------------------|-------------|
+//! | `lfo_new`   | ( name shape freq -- )   | Create or replace an LFO |
+//! | `lfo_tick`  | ( name dt -- value )     | Sample, then advance by `dt` seconds |
+//! | `lfo_reset` | ( name -- )              | Rewind to phase zero |
+//!
+//! Shapes are `"sine"`, `"tri"`, `"saw"`, `"square"` and `"noise"`. Each
+//! waveform swings over the full range of the current analog mode, so in
+//! `a.201` a sine LFO runs from -100 to +100:
+//!
+//! ```text
+//! "wobble" "sine" 2 lfo_new
+//! "wobble" 0.125 lfo_tick   # 0 (phase 0)
+//! "wobble" 0.125 lfo_tick   # 100 (phase 0.25)
+//! ```
+//!
+//! Phases live on the interpreter, so separate interpreters run separate
+//! oscillators.
+
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+use woflang_analog::get_analog_config;
+use woflang_core::{InterpreterContext, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Waveform of an oscillator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Shape {
+    Sine,
+    Triangle,
+    Saw,
+    Square,
+    Noise,
+}
+
+impl Shape {
+    pub(super) fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "sine" => Self::Sine,
+            "tri" | "triangle" => Self::Triangle,
+            "saw" => Self::Saw,
+            "square" => Self::Square,
+            "noise" => Self::Noise,
+            _ => return None,
+        })
+    }
+}
+
+/// One oscillator.
+#[derive(Debug, Clone)]
+pub(super) struct Lfo {
+    shape: Shape,
+    pub(super) freq: f64,
+    /// Position within the cycle, in `[0, 1)`.
+    phase: f64,
+    /// xorshift state for the noise shape.
+    seed: u64,
+}
+
+impl Lfo {
+    pub(super) fn new(name: &str, shape: Shape, freq: f64) -> Self {
+        // FNV-1a of the name, so noise is repeatable per LFO.
+        let seed = name
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| (h ^ u64::from(b)).wrapping_mul(0x100_0000_01b3));
+        Self { shape, freq, phase: 0.0, seed: seed | 1 }
+    }
+
+    /// Current output in `[-1, 1]`.
+    pub(super) fn sample(&mut self) -> f64 {
+        let p = self.phase;
+        match self.shape {
+            Shape::Sine => (TAU * p).sin(),
+            Shape::Triangle => 4.0 * ((p - 0.25).rem_euclid(1.0) - 0.5).abs() - 1.0,
+            Shape::Saw => 2.0 * p - 1.0,
+            Shape::Square => {
+                if p < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Shape::Noise => {
+                self.seed ^= self.seed << 13;
+                self.seed ^= self.seed >> 7;
+                self.seed ^= self.seed << 17;
+                (self.seed >> 11) as f64 / (1_u64 << 52) as f64 - 1.0
+            }
+        }
+    }
+
+    pub(super) fn advance(&mut self, dt: f64) {
+        self.phase = (self.phase + self.freq * dt).rem_euclid(1.0);
+    }
+}
+
+/// All oscillators of one interpreter, by
+/// [resource name](Interpreter::resource_name).
+#[derive(Debug, Default)]
+struct Lfos(HashMap<String, Lfo>);
+
+/// Map a `[-1, 1]` sample onto the current analog range.
+pub(super) fn to_range(sample: f64) -> f64 {
+    let config = get_analog_config();
+    config.min() + (sample + 1.0) * 0.5 * (config.max() - config.min())
+}
+
+/// A sample as a value of the current analog mode.
+fn to_analog(sample: f64) -> WofValue {
+    let config = get_analog_config();
+    let value = to_range(sample);
+    if config.is_integer_mode() {
+        WofValue::integer(config.clamp_rounded(value) as i64)
+    } else {
+        WofValue::double(config.clamp(value))
+    }
+}
+
+/// Register the LFO operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("lfo_new", |interp| {
+        let freq = interp.stack_mut().pop_numeric()?;
+        let shape_name = interp.stack_mut().pop_string()?;
+        let name = interp.stack_mut().pop_string()?;
+        let shape = Shape::parse(&shape_name).ok_or_else(|| {
+            WofError::runtime(format!(
+                "lfo_new: unknown shape '{shape_name}' (expected sine, tri, saw, square or noise)"
+            ))
+        })?;
+        if !freq.is_finite() {
+            return Err(WofError::runtime("lfo_new: frequency must be finite"));
+        }
+        let lfo = Lfo::new(&name, shape, freq);
+        let key = interp.resource_name(&name);
+        super::scope::forget(interp, &key);
+        interp.state_mut::<Lfos>().0.insert(key, lfo);
+        Ok(())
+    });
+
+    interp.register("lfo_tick", |interp| {
+        let dt = interp.stack_mut().pop_numeric()?;
+        let name = interp.stack_mut().pop_string()?;
+        let key = interp.resource_name(&name);
+        let lfo = interp
+            .state_mut::<Lfos>()
+            .0
+            .get_mut(&key)
+            .ok_or_else(|| WofError::runtime(format!("lfo_tick: no LFO named '{name}'")))?;
+        let sample = lfo.sample();
+        lfo.advance(dt);
+        let value = to_analog(sample);
+        super::scope::record(interp, &key, value.as_numeric()?);
+        interp.push(value);
+        Ok(())
+    });
+
+    interp.register("lfo_reset", |interp| {
+        let name = interp.stack_mut().pop_string()?;
+        let key = interp.resource_name(&name);
+        let lfo = interp
+            .state_mut::<Lfos>()
+            .0
+            .get_mut(&key)
+            .ok_or_else(|| WofError::runtime(format!("lfo_reset: no LFO named '{name}'")))?;
+        lfo.phase = 0.0;
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(shape: Shape, n: usize) -> Vec<f64> {
+        let mut lfo = Lfo::new("t", shape, 1.0);
+        (0..n)
+            .map(|_| {
+                let s = lfo.sample();
+                lfo.advance(0.25);
+                s
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shapes_hit_expected_points() {
+        let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-9);
+        assert!(close(&samples(Shape::Sine, 5), &[0.0, 1.0, 0.0, -1.0, 0.0]));
+        assert!(close(&samples(Shape::Triangle, 5), &[0.0, 1.0, 0.0, -1.0, 0.0]));
+        assert!(close(&samples(Shape::Saw, 4), &[-1.0, -0.5, 0.0, 0.5]));
+        assert!(close(&samples(Shape::Square, 4), &[1.0, 1.0, -1.0, -1.0]));
+        assert!(samples(Shape::Noise, 64).iter().all(|s| (-1.0..=1.0).contains(s)));
+        assert_eq!(samples(Shape::Noise, 8), samples(Shape::Noise, 8));
+    }
+
+    #[test]
+    fn ticks_named_lfos_in_analog_range() {
+        let mut interp = Interpreter::new();
+        crate::analog::register(&mut interp);
+
+        interp
+            .exec_line(r#"a.201 "w" sine 2 lfo_new "w" 0.125 lfo_tick "w" 0.125 lfo_tick"#)
+            .unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 100);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 0);
+
+        interp.exec_line(r#""w" lfo_reset "w" 0 lfo_tick"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 0);
+
+        assert!(interp.exec_line(r#""missing" 0.1 lfo_tick"#).is_err());
+        assert!(interp.exec_line(r#""x" wobble 1 lfo_new"#).is_err());
+    }
+}
@@ -23,9 +23,90 @@
 //! - Unary: `( a -- result )`
 //! - Binary: `( a b -- result )` where `a` is deeper, `b` on top
 //! - Ternary: `( a b c -- result )` e.g., lerp takes `(start end t)`
-
-use woflang_analog::ops;
-use woflang_core::InterpreterContext;
+//!
+//! # Batch Operations
+//!
+//! Batch ops take a count `n` on top of the stack and process a whole run
+//! of values four SIMD lanes at a time, saturating each result to the
+//! current mode:
+//!
+//! | Operation     | Stack Effect                       | Description |
+//! |---------------|------------------------------------|-------------|
+//! | `batch_add`   | ( a1..an b1..bn n -- r1..rn )      | Element-wise add |
+//! | `batch_mul`   | ( a1..an b1..bn n -- r1..rn )      | Element-wise multiply |
+//! | `batch_clamp` | ( v1..vn n -- c1..cn )             | Clamp each value |
+//!
+//! # Buffers
+//!
+//! For real-time work, `a.buf` packs a run into a single buffer value and
+//! the `a.buf_*` ops process whole buffers, updating the samples in place
+//! when nothing else holds the buffer:
+//!
+//! | Operation     | Stack Effect              | Description |
+//! |---------------|---------------------------|-------------|
+//! | `a.buf`       | ( v1..vn n -- buf )       | Pack a run into a buffer (clamped) |
+//! | `a.buf_fill`  | ( v n -- buf )            | `n` copies of `v` |
+//! | `a.unbuf`     | ( buf -- v1..vn n )       | Unpack a buffer onto the stack |
+//! | `a.buf_len`   | ( buf -- n )              | Number of samples |
+//! | `a.buf_add`   | ( a b -- a+b )            | Sample-wise add |
+//! | `a.buf_sub`   | ( a b -- a-b )            | Sample-wise subtract |
+//! | `a.buf_mul`   | ( a b -- a*b )            | Sample-wise multiply |
+//! | `a.buf_scale` | ( buf gain -- buf )       | Multiply by a gain |
+//! | `a.buf_tanh`  | ( buf -- buf )            | Soft saturation |
+//! | `a.buf_clamp` | ( buf -- buf )            | Clamp to the current range |
+//!
+//! # Control Signals
+//!
+//! Stateful generators and controllers keep per-name state on the
+//! interpreter: LFOs (`lfo_*`), ADSR envelopes (`adsr_*`) and PID
+//! controllers (`pid_*`). See the submodules for their stack effects.
+//!
+//! # Patching
+//!
+//! `module_new` adds LFO, envelope, VCA, filter, mixer and constant
+//! modules to a rack on the interpreter, `patch` runs cables between
+//! them and `patch_tick` advances the whole graph, upstream modules
+//! first; `patch_render` records one module to a WAV file offline. See
+//! the `patch` submodule for the module kinds and their ports.
+//!
+//! # Scope
+//!
+//! `a.scope` draws the latest samples of an LFO or a buffer variable as a
+//! braille waveform against the current mode's range, redrawing in place
+//! when called from a loop on a terminal.
+//!
+//! # Audio
+//!
+//! `a.osc` and `a.env` build a patch of oscillator voices on the
+//! interpreter and `a.render` streams it to a WAV file, so a patch can
+//! actually be heard.
+//!
+//! # Hardware Output
+//!
+//! `a.stream_open` connects to a UDP or TCP listener or a serial device,
+//! and `a.stream_send` streams control values to it, paced in real time
+//! by `a.stream_rate`. See the `cv` submodule for the wire format.
+//!
+//! # Scoped Modes
+//!
+//! The mode is also the `analog_mode` parameter: `"201"`, `"2001"`,
+//! `"unit"` or a `[min, max]` list for a custom range. A block can run in
+//! another mode and leave the current one untouched:
+//!
+//! ```text
+//! parameterize "analog_mode" "unit" ⺆ 0.75 0.5 ⊞ ⺘   # 1.0
+//! ```
+
+mod adsr;
+mod cv;
+mod lfo;
+mod patch;
+mod pid;
+mod scope;
+mod synth;
+
+use woflang_analog::{get_analog_config, ops, AnalogMode};
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
 use woflang_runtime::Interpreter;
 
 /// Register all analog computing operations with the interpreter.
@@ -36,6 +117,15 @@ pub fn register(interp: &mut Interpreter) {
     register_linear_2d(interp);
     register_linear_3d(interp);
     register_coordinate(interp);
+    register_batch(interp);
+    register_buffer(interp);
+    lfo::register(interp);
+    adsr::register(interp);
+    pid::register(interp);
+    patch::register(interp);
+    synth::register(interp);
+    cv::register(interp);
+    scope::register(interp);
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -44,240 +134,227 @@ pub fn register(interp: &mut Interpreter) {
 
 fn register_mode_control(interp: &mut Interpreter) {
     // 7000: Status — push mode description string
-    for name in ["≋", "a.status", "analog_status"] {
-        interp.register(name, |ctx| {
-            ctx.push(ops::op_analog_status());
-            Ok(())
-        });
-    }
+    interp.register_group(&["≋", "a.status", "analog_status"], |ctx| {
+        ctx.push(ops::op_analog_status());
+        Ok(())
+    });
 
     // 7001: Set INT_201 mode (-100 to +100)
-    for name in ["a.201", "analog_201", "analog_int201"] {
-        interp.register(name, |_ctx| {
-            ops::op_analog_mode_int201();
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.201", "analog_201", "analog_int201"], |_ctx| {
+        ops::op_analog_mode_int201();
+        Ok(())
+    });
 
     // 7002: Set INT_2001 mode (-1000 to +1000)
-    for name in ["a.2001", "analog_2001", "analog_int2001"] {
-        interp.register(name, |_ctx| {
-            ops::op_analog_mode_int2001();
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.2001", "analog_2001", "analog_int2001"], |_ctx| {
+        ops::op_analog_mode_int2001();
+        Ok(())
+    });
 
     // 7003: Set FLOAT_UNIT mode (-1.0 to +1.0)
-    for name in ["a.unit", "analog_unit", "analog_float"] {
-        interp.register(name, |_ctx| {
-            ops::op_analog_mode_float_unit();
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.unit", "analog_unit", "analog_float"], |_ctx| {
+        ops::op_analog_mode_float_unit();
+        Ok(())
+    });
 
     // 7004: Set custom mode ( min max -- )
-    for name in ["a.custom", "analog_custom"] {
-        interp.register(name, |ctx| {
-            let max = ctx.pop()?;
-            let min = ctx.pop()?;
-            ops::op_analog_mode_custom(&min, &max)?;
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.custom", "analog_custom"], |ctx| {
+        let max = ctx.pop()?;
+        let min = ctx.pop()?;
+        ops::op_analog_mode_custom(&min, &max)?;
+        Ok(())
+    });
 
     // 7005: Reset to default mode
-    for name in ["a.reset", "analog_reset"] {
-        interp.register(name, |_ctx| {
-            ops::op_analog_reset();
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.reset", "analog_reset"], |_ctx| {
+        ops::op_analog_reset();
+        Ok(())
+    });
 
     // 7006: Push current minimum ( -- min )
-    for name in ["a.min", "analog_min"] {
-        interp.register(name, |ctx| {
-            ctx.push(ops::op_analog_min());
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.min", "analog_min"], |ctx| {
+        ctx.push(ops::op_analog_min());
+        Ok(())
+    });
 
     // 7007: Push current maximum ( -- max )
-    for name in ["a.max", "analog_max"] {
-        interp.register(name, |ctx| {
-            ctx.push(ops::op_analog_max());
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.max", "analog_max"], |ctx| {
+        ctx.push(ops::op_analog_max());
+        Ok(())
+    });
 
     // 7008: Push range span ( -- range )
-    for name in ["a.range", "analog_range"] {
-        interp.register(name, |ctx| {
-            ctx.push(ops::op_analog_range());
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.range", "analog_range"], |ctx| {
+        ctx.push(ops::op_analog_range());
+        Ok(())
+    });
 
     // 7009: Check if integer mode ( -- bool )
-    for name in ["a.int?", "analog_is_int"] {
-        interp.register(name, |ctx| {
-            ctx.push(ops::op_analog_is_int());
-            Ok(())
-        });
+    interp.register_group(&["a.int?", "analog_is_int"], |ctx| {
+        ctx.push(ops::op_analog_is_int());
+        Ok(())
+    });
+
+    // The mode as a parameter, for `parameterize "analog_mode" ...`
+    interp.define_param("analog_mode", analog_mode, set_analog_mode);
+}
+
+/// The current mode: "201", "2001", "unit" or `[min, max]`.
+fn analog_mode(_: &Interpreter) -> WofValue {
+    let config = get_analog_config();
+    match config.mode {
+        AnalogMode::Int201 => WofValue::string("201"),
+        AnalogMode::Int2001 => WofValue::string("2001"),
+        AnalogMode::FloatUnit => WofValue::string("unit"),
+        AnalogMode::FloatCustom => WofValue::list(vec![
+            WofValue::double(config.custom_min),
+            WofValue::double(config.custom_max),
+        ]),
     }
 }
 
+fn set_analog_mode(_: &mut Interpreter, value: WofValue) -> Result<()> {
+    if let Ok(items) = value.as_list() {
+        return match items {
+            [min, max] => Ok(ops::op_analog_mode_custom(min, max)?),
+            _ => Err(WofError::runtime("analog_mode: a custom range is [min, max]")),
+        };
+    }
+    match value.as_str() {
+        Ok("201") => ops::op_analog_mode_int201(),
+        Ok("2001") => ops::op_analog_mode_int2001(),
+        Ok("unit") => ops::op_analog_mode_float_unit(),
+        _ => {
+            return Err(WofError::runtime(format!(
+                "analog_mode: expected \"201\", \"2001\", \"unit\" or [min, max], got {value}"
+            )))
+        }
+    }
+    Ok(())
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // BASIC MATH (7010-7029)
 // ═══════════════════════════════════════════════════════════════════════════
 
 fn register_basic_math(interp: &mut Interpreter) {
     // 7010: Clamp to analog range ( a -- clamped )
-    for name in ["⌇", "a.clamp", "analog_clamp"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_clamp(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["⌇", "a.clamp", "analog_clamp"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_clamp(&a)?);
+        Ok(())
+    });
 
     // 7011: Analog add ( a b -- a+b )
-    for name in ["⊞", "a+", "analog_add"] {
-        interp.register(name, |ctx| {
-            let b = ctx.pop()?;
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_add(&a, &b)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["⊞", "a+", "analog_add"], |ctx| {
+        let b = ctx.pop()?;
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_add(&a, &b)?);
+        Ok(())
+    });
 
     // 7012: Analog subtract ( a b -- a-b )
-    for name in ["⊟", "a-", "analog_sub"] {
-        interp.register(name, |ctx| {
-            let b = ctx.pop()?;
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_sub(&a, &b)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["⊟", "a-", "analog_sub"], |ctx| {
+        let b = ctx.pop()?;
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_sub(&a, &b)?);
+        Ok(())
+    });
 
     // 7013: Analog multiply ( a b -- a*b )
-    for name in ["⊠", "a*", "analog_mul"] {
-        interp.register(name, |ctx| {
-            let b = ctx.pop()?;
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_mul(&a, &b)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["⊠", "a*", "analog_mul"], |ctx| {
+        let b = ctx.pop()?;
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_mul(&a, &b)?);
+        Ok(())
+    });
 
     // 7014: Analog divide ( a b -- a/b )
-    for name in ["⊘", "a/", "analog_div"] {
-        interp.register(name, |ctx| {
-            let b = ctx.pop()?;
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_div(&a, &b)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["⊘", "a/", "analog_div"], |ctx| {
+        let b = ctx.pop()?;
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_div(&a, &b)?);
+        Ok(())
+    });
 
     // 7015: Analog modulo ( a b -- a%b )
-    for name in ["a%", "analog_mod"] {
-        interp.register(name, |ctx| {
-            let b = ctx.pop()?;
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_mod(&a, &b)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a%", "analog_mod"], |ctx| {
+        let b = ctx.pop()?;
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_mod(&a, &b)?);
+        Ok(())
+    });
 
     // 7016: Analog negate ( a -- -a )
-    for name in ["a.neg", "analog_neg"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_neg(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.neg", "analog_neg"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_neg(&a)?);
+        Ok(())
+    });
 
     // 7017: Analog absolute value ( a -- |a| )
-    for name in ["a.abs", "analog_abs"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_abs(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.abs", "analog_abs"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_abs(&a)?);
+        Ok(())
+    });
 
     // 7018: Analog square root ( a -- √a )
-    for name in ["a.sqrt", "analog_sqrt"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_sqrt(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.sqrt", "analog_sqrt"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_sqrt(&a)?);
+        Ok(())
+    });
 
     // 7019: Analog power ( base exp -- base^exp )
-    for name in ["a.pow", "analog_pow"] {
-        interp.register(name, |ctx| {
-            let exp = ctx.pop()?;
-            let base = ctx.pop()?;
-            ctx.push(ops::op_analog_pow(&base, &exp)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.pow", "analog_pow"], |ctx| {
+        let exp = ctx.pop()?;
+        let base = ctx.pop()?;
+        ctx.push(ops::op_analog_pow(&base, &exp)?);
+        Ok(())
+    });
 
     // 7020: Analog lerp ( start end t -- interpolated )
-    for name in ["a.lerp", "analog_lerp"] {
-        interp.register(name, |ctx| {
-            let t = ctx.pop()?;
-            let b = ctx.pop()?;
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_lerp(&a, &b, &t)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.lerp", "analog_lerp"], |ctx| {
+        let t = ctx.pop()?;
+        let b = ctx.pop()?;
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_lerp(&a, &b, &t)?);
+        Ok(())
+    });
 
     // 7021: Deadzone ( value threshold -- result )
-    for name in ["a.dead", "analog_deadzone"] {
-        interp.register(name, |ctx| {
-            let threshold = ctx.pop()?;
-            let value = ctx.pop()?;
-            ctx.push(ops::op_analog_deadzone(&value, &threshold)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.dead", "analog_deadzone"], |ctx| {
+        let threshold = ctx.pop()?;
+        let value = ctx.pop()?;
+        ctx.push(ops::op_analog_deadzone(&value, &threshold)?);
+        Ok(())
+    });
 
     // 7022: Remap ( value from_min from_max to_min to_max -- result )
-    for name in ["a.remap", "analog_remap"] {
-        interp.register(name, |ctx| {
-            let to_max = ctx.pop()?;
-            let to_min = ctx.pop()?;
-            let from_max = ctx.pop()?;
-            let from_min = ctx.pop()?;
-            let value = ctx.pop()?;
-            ctx.push(ops::op_analog_remap(&value, &from_min, &from_max, &to_min, &to_max)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.remap", "analog_remap"], |ctx| {
+        let to_max = ctx.pop()?;
+        let to_min = ctx.pop()?;
+        let from_max = ctx.pop()?;
+        let from_min = ctx.pop()?;
+        let value = ctx.pop()?;
+        ctx.push(ops::op_analog_remap(&value, &from_min, &from_max, &to_min, &to_max)?);
+        Ok(())
+    });
 
     // 7023: Normalize to [0,1] ( value -- normalized )
-    for name in ["a.norm", "analog_normalize"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_normalize(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.norm", "analog_normalize"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_normalize(&a)?);
+        Ok(())
+    });
 
     // 7024: Denormalize from [0,1] ( normalized -- value )
-    for name in ["a.denorm", "analog_denormalize"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_denormalize(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.denorm", "analog_denormalize"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_denormalize(&a)?);
+        Ok(())
+    });
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -286,149 +363,117 @@ fn register_basic_math(interp: &mut Interpreter) {
 
 fn register_trig(interp: &mut Interpreter) {
     // 7030: Analog sine ( radians -- sin )
-    for name in ["a.sin", "analog_sin"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_sin(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.sin", "analog_sin"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_sin(&a)?);
+        Ok(())
+    });
 
     // 7031: Analog cosine ( radians -- cos )
-    for name in ["a.cos", "analog_cos"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_cos(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.cos", "analog_cos"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_cos(&a)?);
+        Ok(())
+    });
 
     // 7032: Analog tangent ( radians -- tan )
-    for name in ["a.tan", "analog_tan"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_tan(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.tan", "analog_tan"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_tan(&a)?);
+        Ok(())
+    });
 
     // 7033: Analog arcsine ( value -- radians )
-    for name in ["a.asin", "analog_asin"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_asin(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.asin", "analog_asin"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_asin(&a)?);
+        Ok(())
+    });
 
     // 7034: Analog arccosine ( value -- radians )
-    for name in ["a.acos", "analog_acos"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_acos(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.acos", "analog_acos"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_acos(&a)?);
+        Ok(())
+    });
 
     // 7035: Analog arctangent ( value -- radians )
-    for name in ["a.atan", "analog_atan"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_atan(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.atan", "analog_atan"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_atan(&a)?);
+        Ok(())
+    });
 
     // 7036: Analog atan2 ( y x -- radians )
-    for name in ["a.atan2", "analog_atan2"] {
-        interp.register(name, |ctx| {
-            let x = ctx.pop()?;
-            let y = ctx.pop()?;
-            ctx.push(ops::op_analog_atan2(&y, &x)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.atan2", "analog_atan2"], |ctx| {
+        let x = ctx.pop()?;
+        let y = ctx.pop()?;
+        ctx.push(ops::op_analog_atan2(&y, &x)?);
+        Ok(())
+    });
 
     // 7037: Analog tanh ( value -- tanh )
-    for name in ["a.tanh", "analog_tanh"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_tanh(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.tanh", "analog_tanh"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_tanh(&a)?);
+        Ok(())
+    });
 
     // 7038: Analog exp ( value -- e^value )
-    for name in ["a.exp", "analog_exp"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_exp(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.exp", "analog_exp"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_exp(&a)?);
+        Ok(())
+    });
 
     // 7039: Analog ln ( value -- ln(value) )
-    for name in ["a.ln", "analog_ln"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_ln(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.ln", "analog_ln"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_ln(&a)?);
+        Ok(())
+    });
 
     // 7040: Degrees to radians ( deg -- rad )
-    for name in ["a.d2r", "deg_to_rad"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_deg_to_rad(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.d2r", "deg_to_rad"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_deg_to_rad(&a)?);
+        Ok(())
+    });
 
     // 7041: Radians to degrees ( rad -- deg )
-    for name in ["a.r2d", "rad_to_deg"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_rad_to_deg(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.r2d", "rad_to_deg"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_rad_to_deg(&a)?);
+        Ok(())
+    });
 
     // 7042: Wrap radians to [0, 2π) ( rad -- wrapped )
-    for name in ["a.wrapr", "wrap_radians"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_wrap_radians(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.wrapr", "wrap_radians"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_wrap_radians(&a)?);
+        Ok(())
+    });
 
     // 7043: Wrap degrees to [0, 360) ( deg -- wrapped )
-    for name in ["a.wrapd", "wrap_degrees"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_wrap_degrees(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.wrapd", "wrap_degrees"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_wrap_degrees(&a)?);
+        Ok(())
+    });
 
     // 7044: Sigmoid activation ( value -- sigmoid )
-    for name in ["a.sigmoid", "analog_sigmoid"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_sigmoid(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.sigmoid", "analog_sigmoid"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_sigmoid(&a)?);
+        Ok(())
+    });
 
     // 7045: ReLU activation ( value -- relu )
-    for name in ["a.relu", "analog_relu"] {
-        interp.register(name, |ctx| {
-            let a = ctx.pop()?;
-            ctx.push(ops::op_analog_relu(&a)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.relu", "analog_relu"], |ctx| {
+        let a = ctx.pop()?;
+        ctx.push(ops::op_analog_relu(&a)?);
+        Ok(())
+    });
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -437,50 +482,42 @@ fn register_trig(interp: &mut Interpreter) {
 
 fn register_linear_2d(interp: &mut Interpreter) {
     // 7050: 2D dot product ( x1 y1 x2 y2 -- dot )
-    for name in ["a.dot2", "analog_dot2d"] {
-        interp.register(name, |ctx| {
-            let y2 = ctx.pop()?;
-            let x2 = ctx.pop()?;
-            let y1 = ctx.pop()?;
-            let x1 = ctx.pop()?;
-            ctx.push(ops::op_dot_2d(&x1, &y1, &x2, &y2)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.dot2", "analog_dot2d"], |ctx| {
+        let y2 = ctx.pop()?;
+        let x2 = ctx.pop()?;
+        let y1 = ctx.pop()?;
+        let x1 = ctx.pop()?;
+        ctx.push(ops::op_dot_2d(&x1, &y1, &x2, &y2)?);
+        Ok(())
+    });
 
     // 7051: 2D magnitude ( x y -- mag )
-    for name in ["a.mag2", "analog_mag2d"] {
-        interp.register(name, |ctx| {
-            let y = ctx.pop()?;
-            let x = ctx.pop()?;
-            ctx.push(ops::op_magnitude_2d(&x, &y)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.mag2", "analog_mag2d"], |ctx| {
+        let y = ctx.pop()?;
+        let x = ctx.pop()?;
+        ctx.push(ops::op_magnitude_2d(&x, &y)?);
+        Ok(())
+    });
 
     // 7052: 2D distance ( x1 y1 x2 y2 -- dist )
-    for name in ["a.dist2", "analog_dist2d"] {
-        interp.register(name, |ctx| {
-            let y2 = ctx.pop()?;
-            let x2 = ctx.pop()?;
-            let y1 = ctx.pop()?;
-            let x1 = ctx.pop()?;
-            ctx.push(ops::op_distance_2d(&x1, &y1, &x2, &y2)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.dist2", "analog_dist2d"], |ctx| {
+        let y2 = ctx.pop()?;
+        let x2 = ctx.pop()?;
+        let y1 = ctx.pop()?;
+        let x1 = ctx.pop()?;
+        ctx.push(ops::op_distance_2d(&x1, &y1, &x2, &y2)?);
+        Ok(())
+    });
 
     // 7053: 2D normalize ( x y -- nx ny )
-    for name in ["a.norm2", "analog_norm2d"] {
-        interp.register(name, |ctx| {
-            let y = ctx.pop()?;
-            let x = ctx.pop()?;
-            let (nx, ny) = ops::op_normalize_2d(&x, &y)?;
-            ctx.push(nx);
-            ctx.push(ny);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.norm2", "analog_norm2d"], |ctx| {
+        let y = ctx.pop()?;
+        let x = ctx.pop()?;
+        let (nx, ny) = ops::op_normalize_2d(&x, &y)?;
+        ctx.push(nx);
+        ctx.push(ny);
+        Ok(())
+    });
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -489,57 +526,49 @@ fn register_linear_2d(interp: &mut Interpreter) {
 
 fn register_linear_3d(interp: &mut Interpreter) {
     // 7060: 3D dot product ( x1 y1 z1 x2 y2 z2 -- dot )
-    for name in ["a.dot3", "analog_dot3d"] {
-        interp.register(name, |ctx| {
-            let z2 = ctx.pop()?;
-            let y2 = ctx.pop()?;
-            let x2 = ctx.pop()?;
-            let z1 = ctx.pop()?;
-            let y1 = ctx.pop()?;
-            let x1 = ctx.pop()?;
-            ctx.push(ops::op_dot_3d(&x1, &y1, &z1, &x2, &y2, &z2)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.dot3", "analog_dot3d"], |ctx| {
+        let z2 = ctx.pop()?;
+        let y2 = ctx.pop()?;
+        let x2 = ctx.pop()?;
+        let z1 = ctx.pop()?;
+        let y1 = ctx.pop()?;
+        let x1 = ctx.pop()?;
+        ctx.push(ops::op_dot_3d(&x1, &y1, &z1, &x2, &y2, &z2)?);
+        Ok(())
+    });
 
     // 7061: 3D magnitude ( x y z -- mag )
-    for name in ["a.mag3", "analog_mag3d"] {
-        interp.register(name, |ctx| {
-            let z = ctx.pop()?;
-            let y = ctx.pop()?;
-            let x = ctx.pop()?;
-            ctx.push(ops::op_magnitude_3d(&x, &y, &z)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.mag3", "analog_mag3d"], |ctx| {
+        let z = ctx.pop()?;
+        let y = ctx.pop()?;
+        let x = ctx.pop()?;
+        ctx.push(ops::op_magnitude_3d(&x, &y, &z)?);
+        Ok(())
+    });
 
     // 7062: 3D distance ( x1 y1 z1 x2 y2 z2 -- dist )
-    for name in ["a.dist3", "analog_dist3d"] {
-        interp.register(name, |ctx| {
-            let z2 = ctx.pop()?;
-            let y2 = ctx.pop()?;
-            let x2 = ctx.pop()?;
-            let z1 = ctx.pop()?;
-            let y1 = ctx.pop()?;
-            let x1 = ctx.pop()?;
-            ctx.push(ops::op_distance_3d(&x1, &y1, &z1, &x2, &y2, &z2)?);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.dist3", "analog_dist3d"], |ctx| {
+        let z2 = ctx.pop()?;
+        let y2 = ctx.pop()?;
+        let x2 = ctx.pop()?;
+        let z1 = ctx.pop()?;
+        let y1 = ctx.pop()?;
+        let x1 = ctx.pop()?;
+        ctx.push(ops::op_distance_3d(&x1, &y1, &z1, &x2, &y2, &z2)?);
+        Ok(())
+    });
 
     // 7063: 3D normalize ( x y z -- nx ny nz )
-    for name in ["a.norm3", "analog_norm3d"] {
-        interp.register(name, |ctx| {
-            let z = ctx.pop()?;
-            let y = ctx.pop()?;
-            let x = ctx.pop()?;
-            let (nx, ny, nz) = ops::op_normalize_3d(&x, &y, &z)?;
-            ctx.push(nx);
-            ctx.push(ny);
-            ctx.push(nz);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.norm3", "analog_norm3d"], |ctx| {
+        let z = ctx.pop()?;
+        let y = ctx.pop()?;
+        let x = ctx.pop()?;
+        let (nx, ny, nz) = ops::op_normalize_3d(&x, &y, &z)?;
+        ctx.push(nx);
+        ctx.push(ny);
+        ctx.push(nz);
+        Ok(())
+    });
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -548,26 +577,204 @@ fn register_linear_3d(interp: &mut Interpreter) {
 
 fn register_coordinate(interp: &mut Interpreter) {
     // 7090: Cartesian to polar ( x y -- r theta )
-    for name in ["a.c2p", "analog_cart2pol"] {
-        interp.register(name, |ctx| {
-            let y = ctx.pop()?;
-            let x = ctx.pop()?;
-            let (r, theta) = ops::op_cartesian_to_polar(&x, &y)?;
-            ctx.push(r);
-            ctx.push(theta);
-            Ok(())
-        });
-    }
+    interp.register_group(&["a.c2p", "analog_cart2pol"], |ctx| {
+        let y = ctx.pop()?;
+        let x = ctx.pop()?;
+        let (r, theta) = ops::op_cartesian_to_polar(&x, &y)?;
+        ctx.push(r);
+        ctx.push(theta);
+        Ok(())
+    });
 
     // 7091: Polar to Cartesian ( r theta -- x y )
-    for name in ["a.p2c", "analog_pol2cart"] {
-        interp.register(name, |ctx| {
-            let theta = ctx.pop()?;
-            let r = ctx.pop()?;
-            let (x, y) = ops::op_polar_to_cartesian(&r, &theta)?;
-            ctx.push(x);
-            ctx.push(y);
+    interp.register_group(&["a.p2c", "analog_pol2cart"], |ctx| {
+        let theta = ctx.pop()?;
+        let r = ctx.pop()?;
+        let (x, y) = ops::op_polar_to_cartesian(&r, &theta)?;
+        ctx.push(x);
+        ctx.push(y);
+        Ok(())
+    });
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BATCH OPERATIONS (7100-7109)
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Pop the batch count and then `RUNS` runs of that many values each,
+/// returned deepest run first with each run in stack order.
+fn pop_batch<const RUNS: usize>(ctx: &mut Interpreter) -> Result<[Vec<WofValue>; RUNS]> {
+    let n = ctx.stack_mut().pop_integer()?;
+    let n = usize::try_from(n)
+        .map_err(|_| WofError::runtime(format!("batch count must be non-negative, got {n}")))?;
+    let depth = ctx.stack().len();
+    if depth < n * RUNS {
+        return Err(WofError::stack_underflow(n * RUNS, depth));
+    }
+    // Depth was checked above, so the pops cannot underflow.
+    let mut runs: [Vec<WofValue>; RUNS] = std::array::from_fn(|_| {
+        let mut run = ctx.stack_mut().pop_n(n).unwrap_or_default();
+        run.reverse();
+        run
+    });
+    runs.reverse();
+    Ok(runs)
+}
+
+fn register_batch(interp: &mut Interpreter) {
+    // 7100: Batch add ( a1..an b1..bn n -- r1..rn )
+    interp.register("batch_add", |ctx| {
+        let [a, b] = pop_batch(ctx)?;
+        ctx.stack_mut().push_all(ops::op_batch_add(&a, &b)?);
+        Ok(())
+    });
+
+    // 7101: Batch multiply ( a1..an b1..bn n -- r1..rn )
+    interp.register("batch_mul", |ctx| {
+        let [a, b] = pop_batch(ctx)?;
+        ctx.stack_mut().push_all(ops::op_batch_mul(&a, &b)?);
+        Ok(())
+    });
+
+    // 7102: Batch clamp ( v1..vn n -- c1..cn )
+    interp.register("batch_clamp", |ctx| {
+        let [values] = pop_batch(ctx)?;
+        ctx.stack_mut().push_all(ops::op_batch_clamp(&values)?);
+        Ok(())
+    });
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BUFFERS (7110-7119)
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn register_buffer(interp: &mut Interpreter) {
+    // 7110: Pack a run ( v1..vn n -- buf )
+    interp.register("a.buf", |ctx| {
+        let [values] = pop_batch(ctx)?;
+        ctx.push(ops::op_buffer_from(&values)?);
+        Ok(())
+    });
+
+    // 7111: Constant buffer ( v n -- buf )
+    interp.register("a.buf_fill", |ctx| {
+        let n = ctx.stack_mut().pop_integer()?;
+        let value = ctx.pop()?;
+        let n = usize::try_from(n)
+            .map_err(|_| WofError::runtime(format!("a.buf_fill: length must be non-negative, got {n}")))?;
+        ctx.push(ops::op_buffer_fill(&value, n)?);
+        Ok(())
+    });
+
+    // 7112: Unpack ( buf -- v1..vn n )
+    interp.register("a.unbuf", |ctx| {
+        let buffer = ctx.pop()?;
+        let values = ops::op_buffer_values(&buffer)?;
+        let n = values.len();
+        ctx.stack_mut().push_all(values);
+        ctx.push(WofValue::integer(n as i64));
+        Ok(())
+    });
+
+    // 7113: Length ( buf -- n )
+    interp.register("a.buf_len", |ctx| {
+        let buffer = ctx.pop()?;
+        ctx.push(ops::op_buffer_len(&buffer)?);
+        Ok(())
+    });
+
+    // 7114-7116: Sample-wise arithmetic ( a b -- r )
+    for (name, op) in [
+        ("a.buf_add", ops::op_buffer_add as fn(WofValue, &WofValue) -> Result<WofValue>),
+        ("a.buf_sub", ops::op_buffer_sub),
+        ("a.buf_mul", ops::op_buffer_mul),
+    ] {
+        interp.register(name, move |ctx| {
+            let b = ctx.pop()?;
+            let a = ctx.pop()?;
+            ctx.push(op(a, &b)?);
             Ok(())
         });
     }
+
+    // 7117: Gain ( buf gain -- buf )
+    interp.register("a.buf_scale", |ctx| {
+        let gain = ctx.pop()?;
+        let buffer = ctx.pop()?;
+        ctx.push(ops::op_buffer_scale(buffer, &gain)?);
+        Ok(())
+    });
+
+    // 7118: Soft saturation ( buf -- buf )
+    interp.register("a.buf_tanh", |ctx| {
+        let buffer = ctx.pop()?;
+        ctx.push(ops::op_buffer_tanh(buffer)?);
+        Ok(())
+    });
+
+    // 7119: Clamp ( buf -- buf )
+    interp.register("a.buf_clamp", |ctx| {
+        let buffer = ctx.pop()?;
+        ctx.push(ops::op_buffer_clamp(buffer)?);
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_ops_keep_stack_order() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+
+        interp.exec_line("a.201 7 1 2 3 10 20 90 3 batch_add").unwrap();
+        let values: Vec<i64> =
+            interp.stack().iter().map(|v| v.as_integer().unwrap()).collect();
+        assert_eq!(values, vec![7, 11, 22, 93]);
+
+        interp.exec_line("150 -150 2 batch_clamp").unwrap();
+        assert_eq!(interp.stack().peek().unwrap().as_integer().unwrap(), -100);
+
+        assert!(interp.exec_line("99 batch_mul").is_err());
+        assert!(interp.exec_line("-1 batch_clamp").is_err());
+    }
+
+    #[test]
+    fn buffer_ops_round_trip_through_the_stack() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+
+        interp.exec_line("a.unit 0.5 -0.5 2 a.buf 0.25 2 a.buf_fill a.buf_add a.unbuf").unwrap();
+        let values: Vec<f64> = interp.stack().iter().map(|v| v.as_numeric().unwrap()).collect();
+        assert_eq!(values, vec![0.75, -0.25, 2.0]);
+        interp.stack_mut().clear();
+
+        interp.exec_line("1 1 1 3 a.buf 2 a.buf_scale a.buf_len").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 3);
+        assert!(interp.exec_line("1 1 a.buf 0 2 a.buf_fill a.buf_mul").is_err());
+        assert!(interp.exec_line("3 a.buf_tanh").is_err());
+        interp.exec_line("a.reset").unwrap();
+    }
+
+    #[test]
+    fn analog_mode_can_be_parameterized() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+
+        interp.exec_line("a.201 parameterize \"analog_mode\" \"unit\" ⺆ 0.75 0.5 ⊞ ⺘ 90 50 ⊞").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 100);
+        assert!((interp.stack_mut().pop_numeric().unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(interp.param("analog_mode").unwrap().as_str().unwrap(), "201");
+
+        interp.set_param("analog_mode", WofValue::list(vec![WofValue::integer(0), WofValue::integer(10)])).unwrap();
+        assert_eq!(interp.param("analog_mode").unwrap().to_string(), "[0.0, 10.0]");
+        assert!(interp.exec_line("parameterize \"analog_mode\" \"2001\" ⺆ 3 a.buf_tanh ⺘").is_err());
+        assert_eq!(interp.param("analog_mode").unwrap().to_string(), "[0.0, 10.0]");
+
+        assert!(interp.set_param("analog_mode", WofValue::string("huge")).is_err());
+        assert!(interp.set_param("analog_mode", WofValue::list(vec![WofValue::integer(1)])).is_err());
+        interp.exec_line("a.reset").unwrap();
+    }
 }
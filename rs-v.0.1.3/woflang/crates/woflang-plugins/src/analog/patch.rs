@@ -0,0 +1,618 @@
+//! Modular patching: named modules wired together with cables.
+//!
+//! | Operation    | Stack Effect                | Description |
+//! |--------------|-----------------------------|-------------|
+//! | `module_new` | ( name kind -- )            | Add or replace a module |
+//! | `module_set` | ( "mod.port" value -- )     | Turn a knob or set an input |
+//! | `module_get` | ( name -- value )           | Read a module's output |
+//! | `patch`      | ( "mod.out" "mod.in" -- )   | Run a cable from an output to an input |
+//! | `unpatch`    | ( "mod.out" "mod.in" -- )   | Remove a cable |
+//! | `patch_tick` | ( dt -- )                   | Advance every module by `dt` seconds |
+//! | `patch_show` | ( -- )                      | Print the modules and their cables |
+//! | `patch_output` | ( name -- )               | Choose the module `patch_render` records |
+//! | `patch_render` | ( seconds rate path -- )  | Render the output module to a WAV file |
+//!
+//! Every module has one output, `out`, and these knobs and inputs
+//! (defaults in brackets; "full" is full scale of the current mode):
+//!
+//! | Kind                 | Knobs                                    | Inputs                      | Output |
+//! |----------------------|------------------------------------------|-----------------------------|--------|
+//! | `lfo`, `lfo:<shape>` | `freq` [1 Hz]                            | `rate` [0]                  | Waveform over the whole range; `rate` adds an octave per full scale |
+//! | `adsr`               | `attack` `decay` `sustain` `release` [0.01 0.1 0.5 0.2] | `gate` [0]   | Envelope over `[0, full]`, open while `gate` is above 0 |
+//! | `vca`                |                                          | `in` [0], `cv` [full]       | `in × cv / full` |
+//! | `vcf`                |                                          | `in` [0], `cutoff` [full]   | One-pole low-pass of `in`, 20 Hz at 0 up to 20 kHz at full |
+//! | `mix`                |                                          | `a` `b` `c` `d` [0]         | The sum |
+//! | `const`              |                                          | `value` [0]                 | `value` |
+//!
+//! LFO shapes are those of `lfo_new`. Knobs are plain numbers (hertz,
+//! seconds, a sustain level in `[0, 1]`) and take no cables. An input
+//! reads its own setting plus every output patched into it, and inputs
+//! and outputs saturate to the current analog mode like any analog value.
+//!
+//! `patch_tick` runs the modules in topological order, so a signal passes
+//! through a whole chain in one tick; a cable that would close a loop is
+//! refused. Replacing a module unplugs it.
+//!
+//! ```text
+//! a.201
+//! "osc" "lfo:saw" module_new   "osc.freq" 110 module_set
+//! "wob" "lfo" module_new       "wob.freq" 2 module_set
+//! "vcf" "vcf" module_new       "vcf.cutoff" 0 module_set
+//! "osc.out" "vcf.in" patch
+//! "wob.out" "vcf.cutoff" patch
+//! 0.001 patch_tick "vcf" module_get
+//! ```
+//!
+//! `patch_render` runs a copy of the rack at `rate` samples per second,
+//! one tick per sample, and writes the output module as a 16-bit mono
+//! WAV file, full scale of the mode mapped to full scale of the file.
+//! The rack itself is left as it was, so rendering twice gives the same
+//! file. Integer modes quantise every signal; render in `a.unit` for
+//! clean audio:
+//!
+//! ```text
+//! a.unit
+//! "osc" "lfo:saw" module_new "osc.freq" 110 module_set
+//! "env" "adsr" module_new    "gate" "const" module_new "gate.value" 1 module_set
+//! "amp" "vca" module_new     "amp.cv" 0 module_set
+//! "osc" "amp.in" patch "gate" "env.gate" patch "env" "amp.cv" patch
+//! "amp" patch_output 2 44100 "saw.wav" patch_render
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::f64::consts::TAU;
+
+use woflang_analog::stream::write_wav;
+use woflang_analog::{get_analog_config, AnalogConfig};
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+use super::adsr::Adsr;
+use super::lfo::{self, Lfo, Shape};
+
+/// Longest render accepted, in seconds.
+const MAX_SECONDS: f64 = 600.0;
+
+/// Highest sample rate accepted, in hertz.
+const MAX_RATE: i64 = 192_000;
+
+/// Samples rendered between progress reports and cancellation checks.
+const BLOCK: usize = 4096;
+
+/// What a module does, with its running state.
+#[derive(Debug, Clone)]
+enum Kind {
+    Lfo(Lfo),
+    Adsr { env: Adsr, open: bool },
+    Vca,
+    Vcf { level: f64 },
+    Mix,
+    Const,
+}
+
+/// One module in the rack.
+#[derive(Debug, Clone)]
+struct Module {
+    kind: Kind,
+    /// The kind as given to `module_new`.
+    label: String,
+    knobs: Vec<Setting>,
+    /// Inputs and their own settings, before cables.
+    inputs: Vec<Setting>,
+    out: f64,
+}
+
+/// A knob or input and its value.
+type Setting = (&'static str, f64);
+
+/// What a kind of module starts as: its state, knobs and inputs.
+type Blueprint = (Kind, &'static [Setting], Vec<Setting>);
+
+/// The largest magnitude of the current range: what "full scale" means
+/// to the modules.
+fn full_scale(config: &AnalogConfig) -> f64 {
+    config.max().abs().max(config.min().abs())
+}
+
+/// Saturate a signal to the current mode.
+fn settle(value: f64, config: &AnalogConfig) -> f64 {
+    if config.is_integer_mode() {
+        config.clamp_rounded(value)
+    } else {
+        config.clamp(value)
+    }
+}
+
+/// A number as `patch_show` prints it.
+fn number(value: f64) -> String {
+    let text = format!("{value:.3}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { "0" } else { text }.to_string()
+}
+
+impl Module {
+    /// A fresh module of `kind`, or `None` for an unknown kind.
+    fn new(name: &str, kind: &str, config: &AnalogConfig) -> Option<Self> {
+        let full = full_scale(config);
+        let (base, shape) = kind.split_once(':').map_or((kind, None), |(b, s)| (b, Some(s)));
+        let (kind_state, knobs, inputs): Blueprint = match (base, shape) {
+            ("lfo", shape) => (
+                Kind::Lfo(Lfo::new(name, Shape::parse(shape.unwrap_or("sine"))?, 1.0)),
+                &[("freq", 1.0)],
+                vec![("rate", 0.0)],
+            ),
+            ("adsr", None) => (
+                Kind::Adsr { env: Adsr::new(0.01, 0.1, 0.5, 0.2), open: false },
+                &[("attack", 0.01), ("decay", 0.1), ("sustain", 0.5), ("release", 0.2)],
+                vec![("gate", 0.0)],
+            ),
+            ("vca", None) => (Kind::Vca, &[], vec![("in", 0.0), ("cv", full)]),
+            ("vcf", None) => (Kind::Vcf { level: 0.0 }, &[], vec![("in", 0.0), ("cutoff", full)]),
+            ("mix", None) => (Kind::Mix, &[], vec![("a", 0.0), ("b", 0.0), ("c", 0.0), ("d", 0.0)]),
+            ("const", None) => (Kind::Const, &[], vec![("value", 0.0)]),
+            _ => return None,
+        };
+        Some(Self { kind: kind_state, label: kind.to_string(), knobs: knobs.to_vec(), inputs, out: 0.0 })
+    }
+
+    /// Advance by `dt` seconds with the given input values.
+    fn tick(&mut self, inputs: &[f64], dt: f64, config: &AnalogConfig) {
+        let full = full_scale(config);
+        let knob = |name: &str| self.knobs.iter().find(|(k, _)| *k == name).map_or(0.0, |(_, v)| *v);
+        let value = match &mut self.kind {
+            Kind::Lfo(osc) => {
+                osc.freq = knob("freq") * (inputs[0] / full).exp2();
+                let sample = osc.sample();
+                osc.advance(dt);
+                lfo::to_range(sample)
+            }
+            Kind::Adsr { env, open } => {
+                env.attack = knob("attack");
+                env.decay = knob("decay");
+                env.sustain = knob("sustain").clamp(0.0, 1.0);
+                env.release = knob("release");
+                let gate = inputs[0] > 0.0;
+                if gate != *open {
+                    env.gate(gate);
+                    *open = gate;
+                }
+                env.tick(dt) * full
+            }
+            Kind::Vca => inputs[0] * inputs[1] / full,
+            Kind::Vcf { level } => {
+                let cutoff = 20.0 * 1000_f64.powf((inputs[1] / full).clamp(0.0, 1.0));
+                *level += (1.0 - (-TAU * cutoff * dt).exp()) * (inputs[0] - *level);
+                *level
+            }
+            Kind::Mix => inputs.iter().sum(),
+            Kind::Const => inputs[0],
+        };
+        self.out = settle(value, config);
+    }
+}
+
+/// The modules and cables of one interpreter.
+#[derive(Debug, Default)]
+struct Rack {
+    modules: BTreeMap<String, Module>,
+    /// Source module, destination module and input.
+    cables: Vec<(String, String, &'static str)>,
+    /// The module `patch_render` records.
+    output: Option<String>,
+}
+
+impl Rack {
+    fn module(&self, op: &str, name: &str) -> Result<&Module> {
+        self.modules.get(name).ok_or_else(|| WofError::runtime(format!("{op}: no module named '{name}'")))
+    }
+
+    /// Resolve `"mod.in"` to the module name and its input.
+    fn input(&self, op: &str, port: &str) -> Result<(String, &'static str)> {
+        let (name, input) = port.split_once('.').unwrap_or((port, ""));
+        let module = self.module(op, name)?;
+        if let Some((input, _)) = module.inputs.iter().find(|(i, _)| *i == input) {
+            return Ok((name.to_string(), input));
+        }
+        if module.knobs.iter().any(|(k, _)| *k == input) {
+            return Err(WofError::runtime(format!("{op}: '{port}' is a knob; only inputs take cables")));
+        }
+        let names: Vec<&str> = module.inputs.iter().map(|(i, _)| *i).collect();
+        Err(WofError::runtime(format!("{op}: '{name}' has no input '{input}' (inputs: {})", names.join(", "))))
+    }
+
+    /// Resolve `"mod.out"` (or just `"mod"`) to the module name.
+    fn output(&self, op: &str, port: &str) -> Result<String> {
+        let (name, output) = port.split_once('.').unwrap_or((port, "out"));
+        self.module(op, name)?;
+        if output != "out" {
+            return Err(WofError::runtime(format!("{op}: a module's only output is '{name}.out'")));
+        }
+        Ok(name.to_string())
+    }
+
+    /// Whether a signal from `from` already reaches `to`.
+    fn reaches(&self, from: &str, to: &str) -> bool {
+        let mut seen = BTreeSet::new();
+        let mut todo = vec![from];
+        while let Some(name) = todo.pop() {
+            if name == to {
+                return true;
+            }
+            if seen.insert(name) {
+                todo.extend(self.cables.iter().filter(|(f, ..)| f == name).map(|(_, t, _)| t.as_str()));
+            }
+        }
+        false
+    }
+
+    /// Module names with every module after the ones feeding it, ties by name.
+    fn order(&self) -> Vec<String> {
+        let mut feeds: BTreeMap<&str, usize> = self.modules.keys().map(|n| (n.as_str(), 0)).collect();
+        for (_, to, _) in &self.cables {
+            *feeds.entry(to).or_default() += 1;
+        }
+        let mut ready: BTreeSet<&str> = feeds.iter().filter(|(_, n)| **n == 0).map(|(m, _)| *m).collect();
+        let mut order = Vec::with_capacity(self.modules.len());
+        while let Some(name) = ready.pop_first() {
+            order.push(name.to_string());
+            for (_, to, _) in self.cables.iter().filter(|(f, ..)| f == name) {
+                let n = feeds.entry(to).or_default();
+                *n -= 1;
+                if *n == 0 {
+                    ready.insert(to);
+                }
+            }
+        }
+        order
+    }
+
+    fn tick(&mut self, dt: f64, config: &AnalogConfig) {
+        let mut plan = Plan::new(self);
+        plan.tick(dt, config);
+        plan.store(self);
+    }
+
+    /// One line per module in evaluation order: kind, knobs, inputs with
+    /// their cables, and the output.
+    fn show(&self) -> String {
+        if self.modules.is_empty() {
+            return "patch: no modules\n".to_string();
+        }
+        let rows: Vec<(String, &Module, String)> = self
+            .order()
+            .into_iter()
+            .map(|name| {
+                let module = &self.modules[&name];
+                let knobs = module.knobs.iter().map(|(k, v)| format!("{k} {}", number(*v)));
+                let inputs = module.inputs.iter().map(|(input, setting)| {
+                    let from: Vec<&str> = self
+                        .cables
+                        .iter()
+                        .filter(|(_, to, i)| *to == name && i == input)
+                        .map(|(f, ..)| f.as_str())
+                        .collect();
+                    if from.is_empty() {
+                        format!("{input} {}", number(*setting))
+                    } else {
+                        format!("{input} {} ← {}", number(*setting), from.join(" + "))
+                    }
+                });
+                let ports = knobs.chain(inputs).collect::<Vec<_>>().join("  ");
+                (name, module, ports)
+            })
+            .collect();
+        let names = rows.iter().map(|r| r.0.chars().count()).max().unwrap_or(0);
+        let kinds = rows.iter().map(|r| r.1.label.chars().count()).max().unwrap_or(0);
+        let ports = rows.iter().map(|r| r.2.chars().count()).max().unwrap_or(0);
+
+        let mut out = format!("patch: {} modules, {} cables", rows.len(), self.cables.len());
+        if let Some(output) = &self.output {
+            out += &format!(", output {output}");
+        }
+        out.push('\n');
+        for (name, module, port_list) in &rows {
+            out +=
+                &format!("  {name:<names$}  {:<kinds$}  {port_list:<ports$}  → {}\n", module.label, number(module.out));
+        }
+        out
+    }
+}
+
+/// A rack laid out for running: its modules in evaluation order, with
+/// the positions of the modules patched into each input.
+struct Plan {
+    names: Vec<String>,
+    modules: Vec<Module>,
+    /// Per module, per input: the modules feeding it.
+    feeds: Vec<Vec<Vec<usize>>>,
+    /// Input values of the module being ticked.
+    inputs: Vec<f64>,
+}
+
+impl Plan {
+    fn new(rack: &Rack) -> Self {
+        let names = rack.order();
+        let position: HashMap<&str, usize> = names.iter().enumerate().map(|(i, n)| (n.as_str(), i)).collect();
+        let modules: Vec<Module> = names.iter().map(|name| rack.modules[name].clone()).collect();
+        let feeds = names
+            .iter()
+            .zip(&modules)
+            .map(|(name, module)| {
+                let patched_into = |input: &str| {
+                    rack.cables
+                        .iter()
+                        .filter(|(_, to, i)| to == name && *i == input)
+                        .map(|(from, ..)| position[from.as_str()])
+                        .collect()
+                };
+                module.inputs.iter().map(|(input, _)| patched_into(input)).collect()
+            })
+            .collect();
+        Self { names, modules, feeds, inputs: Vec::new() }
+    }
+
+    /// Advance every module by `dt` seconds. An input reads its setting
+    /// plus everything patched in.
+    fn tick(&mut self, dt: f64, config: &AnalogConfig) {
+        for i in 0..self.modules.len() {
+            self.inputs.clear();
+            for (from, (_, setting)) in self.feeds[i].iter().zip(&self.modules[i].inputs) {
+                let patched: f64 = from.iter().map(|&j| self.modules[j].out).sum();
+                self.inputs.push(config.clamp(setting + patched));
+            }
+            self.modules[i].tick(&self.inputs, dt, config);
+        }
+    }
+
+    /// Put the advanced modules back in the rack.
+    fn store(self, rack: &mut Rack) {
+        rack.modules.extend(self.names.into_iter().zip(self.modules));
+    }
+}
+
+/// Register the patching operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("module_new", |interp| {
+        let kind = interp.stack_mut().pop_string()?;
+        let name = interp.stack_mut().pop_string()?;
+        if name.is_empty() || name.contains('.') {
+            return Err(WofError::runtime(format!("module_new: '{name}' is not a module name (no dots)")));
+        }
+        let module = Module::new(&name, &kind, &get_analog_config()).ok_or_else(|| {
+            WofError::runtime(format!(
+                "module_new: unknown kind '{kind}' (expected lfo, lfo:<shape>, adsr, vca, vcf, mix or const)"
+            ))
+        })?;
+        let rack = interp.state_mut::<Rack>();
+        rack.cables.retain(|(from, to, _)| *from != name && *to != name);
+        rack.modules.insert(name, module);
+        Ok(())
+    });
+
+    interp.register("module_set", |interp| {
+        let value = interp.stack_mut().pop_numeric()?;
+        let port = interp.stack_mut().pop_string()?;
+        if !value.is_finite() {
+            return Err(WofError::runtime("module_set: value must be finite"));
+        }
+        let (name, setting) = port.split_once('.').unwrap_or((&port, ""));
+        let module = interp
+            .state_mut::<Rack>()
+            .modules
+            .get_mut(name)
+            .ok_or_else(|| WofError::runtime(format!("module_set: no module named '{name}'")))?;
+        match module.knobs.iter_mut().chain(module.inputs.iter_mut()).find(|(p, _)| *p == setting) {
+            Some((_, slot)) => *slot = value,
+            None => {
+                let names: Vec<&str> = module.knobs.iter().chain(&module.inputs).map(|(p, _)| *p).collect();
+                return Err(WofError::runtime(format!(
+                    "module_set: '{name}' has no knob or input '{setting}' (has: {})",
+                    names.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    });
+
+    interp.register("module_get", |interp| {
+        let port = interp.stack_mut().pop_string()?;
+        let rack = interp.state_mut::<Rack>();
+        let out = rack.modules[&rack.output("module_get", &port)?].out;
+        let value =
+            if get_analog_config().is_integer_mode() { WofValue::integer(out as i64) } else { WofValue::double(out) };
+        interp.push(value);
+        Ok(())
+    });
+
+    interp.register("patch", |interp| {
+        let to = interp.stack_mut().pop_string()?;
+        let from = interp.stack_mut().pop_string()?;
+        let rack = interp.state_mut::<Rack>();
+        let source = rack.output("patch", &from)?;
+        let (dest, input) = rack.input("patch", &to)?;
+        if rack.reaches(&dest, &source) {
+            return Err(WofError::runtime(format!("patch: '{from}' → '{to}' would close a loop")));
+        }
+        let cable = (source, dest, input);
+        if !rack.cables.contains(&cable) {
+            rack.cables.push(cable);
+        }
+        Ok(())
+    });
+
+    interp.register("unpatch", |interp| {
+        let to = interp.stack_mut().pop_string()?;
+        let from = interp.stack_mut().pop_string()?;
+        let rack = interp.state_mut::<Rack>();
+        let source = rack.output("unpatch", &from)?;
+        let (dest, input) = rack.input("unpatch", &to)?;
+        let cable = (source, dest, input);
+        let before = rack.cables.len();
+        rack.cables.retain(|c| *c != cable);
+        if rack.cables.len() == before {
+            return Err(WofError::runtime(format!("unpatch: no cable from '{from}' to '{to}'")));
+        }
+        Ok(())
+    });
+
+    interp.register("patch_tick", |interp| {
+        let dt = interp.stack_mut().pop_numeric()?;
+        if !dt.is_finite() || dt < 0.0 {
+            return Err(WofError::runtime("patch_tick: dt must be finite and non-negative"));
+        }
+        interp.state_mut::<Rack>().tick(dt, &get_analog_config());
+        Ok(())
+    });
+
+    interp.register("patch_output", |interp| {
+        let name = interp.stack_mut().pop_string()?;
+        let rack = interp.state_mut::<Rack>();
+        let name = rack.output("patch_output", &name)?;
+        rack.output = Some(name);
+        Ok(())
+    });
+
+    // Stack: seconds rate path → (none)
+    interp.register("patch_render", |interp| {
+        let path = interp.stack_mut().pop_string()?;
+        let rate = interp.stack_mut().pop_integer()?;
+        let seconds = interp.stack_mut().pop_numeric()?;
+        let resolved = interp.sandbox().check_write(&path)?;
+        if !(seconds > 0.0 && seconds <= MAX_SECONDS) {
+            return Err(WofError::runtime(format!("patch_render: seconds must be in (0, {MAX_SECONDS}]")));
+        }
+        if !(1..=MAX_RATE).contains(&rate) {
+            return Err(WofError::runtime(format!("patch_render: rate must be 1 to {MAX_RATE} Hz")));
+        }
+        let rack = interp.state_mut::<Rack>();
+        let output = rack
+            .output
+            .clone()
+            .ok_or_else(|| WofError::runtime("patch_render: no output module (choose one with patch_output)"))?;
+        rack.module("patch_render", &output)?;
+        let mut plan = Plan::new(rack);
+        let at = plan.names.iter().position(|name| *name == output).unwrap_or_default();
+
+        let config = get_analog_config();
+        let full = full_scale(&config);
+        let dt = 1.0 / rate as f64;
+        let total = (seconds * rate as f64).round() as usize;
+        let mut samples = Vec::with_capacity(total);
+        while samples.len() < total {
+            if interp.is_cancelled() {
+                return Err(interp.interrupt(format!("patch_render: stopped after {} samples", samples.len())));
+            }
+            for _ in 0..BLOCK.min(total - samples.len()) {
+                plan.tick(dt, &config);
+                samples.push((plan.modules[at].out / full) as f32);
+            }
+            interp.progress(samples.len(), total, "samples");
+        }
+        write_wav(&resolved, &samples, rate as u32)
+            .map_err(|e| WofError::runtime(format!("patch_render: cannot write '{path}': {e}")))?;
+        Ok(())
+    });
+
+    interp.register("patch_show", |interp| {
+        let text = interp.state::<Rack>().map_or_else(|| Rack::default().show(), Rack::show);
+        write!(interp.out(), "{text}");
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use woflang_analog::AnalogMode;
+
+    #[test]
+    fn ticks_in_topological_order() {
+        let config = AnalogConfig::new(AnalogMode::Int201);
+        let mut rack = Rack::default();
+        for (name, kind) in [("amp", "vca"), ("in", "const"), ("sum", "mix")] {
+            rack.modules.insert(name.to_string(), Module::new(name, kind, &config).unwrap());
+        }
+        rack.modules.get_mut("in").unwrap().inputs[0].1 = 80.0;
+        rack.modules.get_mut("amp").unwrap().inputs[1].1 = 50.0;
+        rack.cables.push(("amp".into(), "sum".into(), "a"));
+        rack.cables.push(("in".into(), "amp".into(), "in"));
+        rack.cables.push(("in".into(), "sum".into(), "b"));
+        assert_eq!(rack.order(), ["in", "amp", "sum"]);
+        assert!(rack.reaches("in", "sum") && !rack.reaches("sum", "in"));
+
+        // The whole chain settles in one tick, and the mix saturates.
+        rack.tick(0.01, &config);
+        assert_eq!(rack.modules["amp"].out, 40.0);
+        assert_eq!(rack.modules["sum"].out, 100.0);
+
+        let shown = rack.show();
+        assert!(shown.starts_with("patch: 3 modules, 3 cables\n"), "{shown}");
+        assert!(shown.contains("amp  vca    in 0 ← in  cv 50"), "{shown}");
+        assert!(shown.contains("a 0 ← amp  b 0 ← in  c 0  d 0  → 100"), "{shown}");
+    }
+
+    #[test]
+    fn patches_modules_from_words() {
+        let mut interp = Interpreter::new();
+        crate::analog::register(&mut interp);
+
+        interp
+            .exec_line(
+                r#"a.201 "osc" "const" module_new "osc.value" 100 module_set
+                   "vcf" "vcf" module_new "vcf.cutoff" 0 module_set "osc.out" "vcf.in" patch
+                   0.001 patch_tick "vcf" module_get"#,
+            )
+            .unwrap();
+        // 20 Hz low-pass: 1 - e^(-2π·20·0.001) of the way there
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 12);
+
+        interp
+            .exec_line(
+                r#""wob" "lfo:square" module_new "gate" "adsr" module_new "gate.sustain" 1 module_set
+                   "wob" "gate.gate" patch 0.1 patch_tick "gate.out" module_get"#,
+            )
+            .unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 100);
+
+        let mut err = |line: &str| interp.exec_line(line).unwrap_err().to_string();
+        assert!(err(r#""vcf.out" "osc.value" patch"#).contains("close a loop"));
+        assert!(err(r#""osc" "wob.freq" patch"#).contains("is a knob"));
+        assert!(err(r#""osc" "vcf.q" patch"#).contains("inputs: in, cutoff"));
+        assert!(err(r#""osc" "vcf.cutoff" unpatch"#).contains("no cable"));
+        assert!(err(r#""x" "moog" module_new"#).contains("unknown kind 'moog'"));
+        interp.exec_line(r#""osc.out" "vcf.in" unpatch"#).unwrap();
+
+        interp.exec_line(r#""osc" patch_output patch_show"#).unwrap();
+        // Replacing a module unplugs it
+        interp.exec_line(r#""wob" "lfo" module_new"#).unwrap();
+        assert!(interp.state::<Rack>().unwrap().cables.is_empty());
+    }
+
+    #[test]
+    fn renders_the_output_module() {
+        let mut interp = Interpreter::new();
+        crate::analog::register(&mut interp);
+        let path = std::env::temp_dir().join(format!("woflang-patch-{}.wav", std::process::id()));
+        let render = format!(r#"0.01 8000 "{}" patch_render"#, path.display());
+
+        interp.exec_line(r#"a.unit "sq" "lfo:square" module_new "sq.freq" 1000 module_set"#).unwrap();
+        interp.exec_line(r#""amp" "vca" module_new "amp.cv" 0.5 module_set "sq" "amp.in" patch"#).unwrap();
+        assert!(interp.exec_line(&render).unwrap_err().to_string().contains("no output module"));
+        interp.exec_line(r#""amp" patch_output"#).unwrap();
+        interp.exec_line(&render).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(bytes.len(), 44 + 2 * 80);
+        let samples: Vec<i16> = bytes[44..].chunks(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect();
+        // Half-amplitude square at 1 kHz: four samples up, four down.
+        assert_eq!(samples[..8], [16384, 16384, 16384, 16384, -16384, -16384, -16384, -16384]);
+        assert_eq!(samples[8..16], samples[..8]);
+        // The rack itself has not moved.
+        interp.exec_line(r#""amp" module_get"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 0.0);
+
+        assert!(interp.exec_line(r#"1 0 "x.wav" patch_render"#).is_err());
+        assert!(interp.exec_line(r#""nope" patch_output"#).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! PID controllers for analog control loops.
+//!
+//! | Operation    | Stack Effect                            | Description |
+//! |--------------|-----------------------------------------|-------------|
+//! | `pid_new`    | ( name kp ki kd -- )                    | Create or replace a controller |
+//! | `pid_update` | ( name setpoint measured dt -- output ) | Run one control step |
+//! | `pid_reset`  | ( name -- )                             | Clear integral and history |
+//!
+//! The output saturates at the bounds of the current analog mode, like a
+//! real actuator. To avoid integral windup the integral term is clamped
+//! to the same bounds, so a controller pinned at a rail recovers as soon
+//! as the error changes sign instead of unwinding a huge accumulated sum.
+//!
+//! ```text
+//! a.unit
+//! "heater" 0.8 0.3 0.05 pid_new
+//! "heater" 0.5 temp 0.1 pid_update   # drive value in [-1, 1]
+//! ```
+//!
+//! The derivative acts on the error and is zero on the first step after
+//! creation or reset, so a fresh controller does not kick.
+
+use std::collections::HashMap;
+
+use woflang_analog::get_analog_config;
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// One controller.
+#[derive(Debug, Clone)]
+struct Pid {
+    kp: f64,
+    ki: f64,
+    kd: f64,
+    integral: f64,
+    prev_error: Option<f64>,
+}
+
+impl Pid {
+    fn new(kp: f64, ki: f64, kd: f64) -> Self {
+        Self { kp, ki, kd, integral: 0.0, prev_error: None }
+    }
+
+    fn reset(&mut self) {
+        self.integral = 0.0;
+        self.prev_error = None;
+    }
+
+    /// One control step, with output and integral term held in `[lo, hi]`.
+    fn update(&mut self, setpoint: f64, measured: f64, dt: f64, lo: f64, hi: f64) -> f64 {
+        let error = setpoint - measured;
+
+        if self.ki != 0.0 {
+            self.integral += error * dt;
+            let (a, b) = (lo / self.ki, hi / self.ki);
+            self.integral = self.integral.clamp(a.min(b), a.max(b));
+        }
+
+        let derivative = match self.prev_error {
+            Some(prev) if dt > 0.0 => (error - prev) / dt,
+            _ => 0.0,
+        };
+        self.prev_error = Some(error);
+
+        (self.kp * error + self.ki * self.integral + self.kd * derivative).clamp(lo, hi)
+    }
+}
+
+/// All controllers of one interpreter, by
+/// [resource name](Interpreter::resource_name).
+#[derive(Debug, Default)]
+struct Controllers(HashMap<String, Pid>);
+
+fn controller<'a>(interp: &'a mut Interpreter, op: &str, name: &str) -> Result<&'a mut Pid> {
+    let key = interp.resource_name(name);
+    interp
+        .state_mut::<Controllers>()
+        .0
+        .get_mut(&key)
+        .ok_or_else(|| WofError::runtime(format!("{op}: no controller named '{name}'")))
+}
+
+/// Register the PID operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("pid_new", |interp| {
+        let kd = interp.stack_mut().pop_numeric()?;
+        let ki = interp.stack_mut().pop_numeric()?;
+        let kp = interp.stack_mut().pop_numeric()?;
+        let name = interp.stack_mut().pop_string()?;
+        if ![kp, ki, kd].iter().all(|k| k.is_finite()) {
+            return Err(WofError::runtime("pid_new: gains must be finite"));
+        }
+        let key = interp.resource_name(&name);
+        interp.state_mut::<Controllers>().0.insert(key, Pid::new(kp, ki, kd));
+        Ok(())
+    });
+
+    interp.register("pid_update", |interp| {
+        let dt = interp.stack_mut().pop_numeric()?;
+        let measured = interp.stack_mut().pop_numeric()?;
+        let setpoint = interp.stack_mut().pop_numeric()?;
+        let name = interp.stack_mut().pop_string()?;
+        if !dt.is_finite() || dt < 0.0 {
+            return Err(WofError::runtime("pid_update: dt must be finite and non-negative"));
+        }
+
+        let config = get_analog_config();
+        let pid = controller(interp, "pid_update", &name)?;
+        let output = pid.update(setpoint, measured, dt, config.min(), config.max());
+        interp.push(if config.is_integer_mode() {
+            WofValue::integer(output.round() as i64)
+        } else {
+            WofValue::double(output)
+        });
+        Ok(())
+    });
+
+    interp.register("pid_reset", |interp| {
+        let name = interp.stack_mut().pop_string()?;
+        controller(interp, "pid_reset", &name)?.reset();
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integral_is_clamped_against_windup() {
+        let mut pid = Pid::new(0.0, 1.0, 0.0);
+        // A long stretch of large error pins the integral at the rail...
+        for _ in 0..100 {
+            assert_eq!(pid.update(10.0, 0.0, 1.0, -1.0, 1.0), 1.0);
+        }
+        // ...so reversing the error pulls the output off it immediately.
+        assert!(pid.update(0.0, 0.5, 1.0, -1.0, 1.0) < 1.0);
+    }
+
+    #[test]
+    fn closed_loop_settles_on_setpoint() {
+        let mut pid = Pid::new(0.6, 0.8, 0.01);
+        let mut value = 0.0;
+        for _ in 0..400 {
+            let drive = pid.update(0.5, value, 0.05, -1.0, 1.0);
+            // First-order plant.
+            value += (drive - value) * 0.2;
+        }
+        assert!((value - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn pid_ops_respect_analog_bounds() {
+        let mut interp = Interpreter::new();
+        crate::analog::register(&mut interp);
+
+        interp.exec_line(r#"a.201 "m" 10 0 0 pid_new "m" 50 0 0.1 pid_update"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 100);
+
+        interp.exec_line(r#""m" pid_reset "m" 5 3 0.1 pid_update"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 20);
+
+        assert!(interp.exec_line(r#""none" 1 1 1 pid_update"#).is_err());
+    }
+}
@@ -0,0 +1,284 @@
+//! An oscilloscope view of analog signals.
+//!
+//! | Operation | Stack Effect | Description |
+//! |-----------|--------------|-------------|
+//! | `a.scope` | ( name -- )  | Draw a signal's latest samples |
+//!
+//! `name` is an LFO, whose latest ticks are kept as it runs, or a
+//! variable holding a buffer or a list of numbers. The newest
+//! [`SAMPLES`] samples are drawn in braille (2×4 dots per cell) against
+//! the full range of the current analog mode, newest on the right, so
+//! values pinned at a rail show as flat tops:
+//!
+//! ```text
+//! a.201 "w" "sine" 1 lfo_new
+//! 40 ⨯ ⺆ "w" 0.05 lfo_tick drop ⺘
+//! "w" a.scope
+//! ```
+//!
+//! ```text
+//! w · INT_201 [-100, +100] · range 200
+//!  100 ┤ … ⠀⢀⡞⢳⡀⠀⠀⠀⠀⠀⠀⢀⡞⢳⡀⠀⠀⠀⠀⠀
+//!      │ … ⠀⡼⠀⠀⢧⠀⠀⠀⠀⠀⠀⡼⠀⠀⢧⠀⠀⠀⠀⠀
+//!      │ … ⢀⡇⠀⠀⢸⡀⠀⠀⠀⠀⢀⡇⠀⠀⢸⡀⠀⠀⠀⠀
+//!      │ … ⢸⠀⠀⠀⠀⡇⠀⠀⠀⠀⢸⠀⠀⠀⠀⡇⠀⠀⠀⠀
+//!    0 ┤ … ⠉⠀⠁⠀⠁⢹⠁⠀⠁⠀⡏⠀⠁⠀⠁⢹⠁⠀⠁⠀
+//!      │ … ⠀⠀⠀⠀⠀⠈⡇⠀⠀⢸⠁⠀⠀⠀⠀⠈⡇⠀⠀⢸
+//!      │ … ⠀⠀⠀⠀⠀⠀⢳⠀⠀⡞⠀⠀⠀⠀⠀⠀⢳⠀⠀⡞
+//! -100 ┤ … ⠀⠀⠀⠀⠀⠀⠈⢧⡼⠁⠀⠀⠀⠀⠀⠀⠈⢧⡼⠁
+//!      └───────────────────────
+//!       40 samples · last -31 · low -100 · high 100
+//! ```
+//!
+//! On a terminal, drawing the same scope in the next iteration of a loop
+//! redraws it in place, so a loop that ticks and draws shows a live,
+//! scrolling trace. Anything else printed inside the loop is drawn over.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write as _;
+use std::io::{IsTerminal, Write as _};
+
+use woflang_analog::{get_analog_config, AnalogConfig, AnalogMode};
+use woflang_core::{InterpreterContext, WofError};
+use woflang_runtime::Interpreter;
+
+use crate::braille;
+
+/// Scope width in character cells.
+const COLS: usize = 60;
+
+/// Scope height in character cells.
+const ROWS: usize = 8;
+
+/// The scope's dots.
+type Canvas = braille::Canvas<COLS, ROWS>;
+
+/// Samples shown, one per dot column.
+pub const SAMPLES: usize = Canvas::WIDTH;
+
+/// Recent LFO output and the last scope drawn, per interpreter.
+#[derive(Debug, Default)]
+struct Scopes {
+    /// Latest samples of each LFO, oldest first.
+    traces: HashMap<String, VecDeque<f64>>,
+    /// Name, loop depth and iteration of the last drawing on a terminal,
+    /// and its height in lines.
+    last_drawn: Option<(String, usize, i64, usize)>,
+}
+
+/// Keep an LFO sample for its scope, by the LFO's resource name.
+pub(super) fn record(interp: &mut Interpreter, name: &str, value: f64) {
+    let trace = interp
+        .state_mut::<Scopes>()
+        .traces
+        .entry(name.to_string())
+        .or_default();
+    if trace.len() == SAMPLES {
+        trace.pop_front();
+    }
+    trace.push_back(value);
+}
+
+/// Forget an LFO's samples, when it is replaced.
+pub(super) fn forget(interp: &mut Interpreter, name: &str) {
+    interp.state_mut::<Scopes>().traces.remove(name);
+}
+
+/// The current mode with its bounds, e.g. `INT_201 [-100, +100]`.
+fn mode_label(config: &AnalogConfig) -> String {
+    match config.mode {
+        AnalogMode::FloatCustom => format!(
+            "FLOAT_CUSTOM [{}, {}]",
+            config.custom_min, config.custom_max
+        ),
+        mode => mode.to_string(),
+    }
+}
+
+/// A value as the mode writes it: whole numbers in the integer modes.
+fn label(value: f64, config: &AnalogConfig) -> String {
+    if config.is_integer_mode() {
+        format!("{}", value.round() as i64)
+    } else {
+        braille::label(value)
+    }
+}
+
+/// Draw the newest [`SAMPLES`] of `samples` against the range of
+/// `config`, with `name`, the mode and the range above and the sample
+/// count, last value and extremes below.
+fn render(name: &str, samples: &[f64], config: &AnalogConfig) -> String {
+    let (lo, hi) = (config.min(), config.max());
+    let shown = &samples[samples.len().saturating_sub(SAMPLES)..];
+    let to_row =
+        |v: f64| ((hi - config.clamp(v)) / (hi - lo) * (Canvas::HEIGHT - 1) as f64).round() as usize;
+
+    let mut canvas = Canvas::new();
+    let zero_row = (lo < 0.0 && hi > 0.0).then(|| to_row(0.0));
+    if let Some(row) = zero_row {
+        (0..SAMPLES).step_by(4).for_each(|x| canvas.set(x, row));
+    }
+    // Newest on the right; join each sample to the previous one so
+    // steep edges stay solid.
+    let start = SAMPLES - shown.len();
+    let mut prev: Option<usize> = None;
+    for (i, &v) in shown.iter().enumerate() {
+        let row = to_row(v);
+        let (a, b) = prev.map_or((row, row), |p| (p.min(row), p.max(row)));
+        (a..=b).for_each(|r| canvas.set(start + i, r));
+        prev = Some(row);
+    }
+
+    let mut labels = vec![None; ROWS];
+    labels[0] = Some(label(hi, config));
+    labels[ROWS - 1] = Some(label(lo, config));
+    if let Some(row) = zero_row
+        .map(|r| r / 4)
+        .filter(|r| (1..ROWS - 1).contains(r))
+    {
+        labels[row] = Some(label(0.0, config));
+    }
+    let width = labels.iter().flatten().map(String::len).max().unwrap_or(0);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{name} · {} · range {}",
+        mode_label(config),
+        label(hi - lo, config)
+    );
+    for (row, text) in labels.iter().enumerate() {
+        let (text, tick) = text.as_ref().map_or(("", '│'), |t| (t.as_str(), '┤'));
+        let _ = writeln!(out, "{text:>width$} {tick}{}", canvas.row(row));
+    }
+    let _ = writeln!(out, "{:width$} └{}", "", "─".repeat(COLS));
+    match shown.last() {
+        Some(&last) => {
+            let low = shown.iter().copied().fold(f64::INFINITY, f64::min);
+            let high = shown.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let _ = writeln!(
+                out,
+                "{:width$}  {} samples · last {} · low {} · high {}",
+                "",
+                shown.len(),
+                label(last, config),
+                label(low, config),
+                label(high, config),
+            );
+        }
+        None => {
+            let _ = writeln!(out, "{:width$}  no samples yet", "");
+        }
+    }
+    out
+}
+
+/// Register the scope operation.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("a.scope", |interp| {
+        let name = interp.stack_mut().pop_string()?;
+        let key = interp.resource_name(&name);
+        let samples: Vec<f64> = match interp.state::<Scopes>().and_then(|s| s.traces.get(&key)) {
+            Some(trace) => trace.iter().copied().collect(),
+            None => {
+                let value = interp.get_var(&name).map_err(|_| {
+                    WofError::runtime(format!("a.scope: no LFO or variable named '{name}'"))
+                })?;
+                match value.as_buffer() {
+                    Ok(samples) => samples.to_vec(),
+                    Err(_) => value
+                        .as_list()
+                        .and_then(|items| items.iter().map(|v| v.as_numeric()).collect())
+                        .map_err(|_| {
+                            WofError::runtime(format!(
+                                "a.scope: '{name}' is not a buffer or list of numbers"
+                            ))
+                        })?,
+                }
+            }
+        };
+        let frame = render(&name, &samples, &get_analog_config());
+        let lines = frame.lines().count();
+
+        let mut stdout = std::io::stdout();
+        if !stdout.is_terminal() {
+            write!(interp.out(), "{frame}");
+            return Ok(());
+        }
+        // The next iteration of the same loop draws over this frame.
+        let (depth, iteration) = (interp.loop_depth(), interp.current_iteration().unwrap_or(0));
+        let scopes = interp.state_mut::<Scopes>();
+        if let Some((last, last_depth, last_iteration, height)) = &scopes.last_drawn {
+            if depth > 0 && *last == name && *last_depth == depth && last_iteration + 1 == iteration
+            {
+                let _ = write!(stdout, "\x1b[{height}A");
+            }
+        }
+        for line in frame.lines() {
+            let _ = writeln!(stdout, "\x1b[2K{line}");
+        }
+        let _ = stdout.flush();
+        scopes.last_drawn = Some((name, depth, iteration, lines));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_against_the_mode_range() {
+        let config = AnalogConfig::new(AnalogMode::Int201);
+        let frame = render("w", &[0.0, 50.0, 100.0, 150.0], &config);
+        let lines: Vec<&str> = frame.lines().collect();
+        assert_eq!(lines[0], "w · INT_201 [-100, +100] · range 200");
+        assert!(lines[1].starts_with(" 100 ┤"), "{frame}");
+        assert!(lines[5].starts_with("   0 ┤"), "{frame}");
+        assert!(lines[8].starts_with("-100 ┤"), "{frame}");
+        assert_eq!(lines.len(), 11);
+        // 100 and the clipped 150 share the top rail, joined down to 50
+        assert!(lines[1].ends_with('⡏'), "{frame}");
+        assert_eq!(lines[10], "      4 samples · last 150 · low 0 · high 150");
+
+        let config = AnalogConfig::new(AnalogMode::FloatUnit);
+        let long: Vec<f64> = (0..500).map(|i| f64::from(i) / 500.0).collect();
+        let frame = render("buf", &long, &config);
+        assert!(
+            frame.contains("120 samples · last 1.00 · low 0.76 · high 1.00"),
+            "{frame}"
+        );
+        assert!(render("quiet", &[], &config).ends_with("no samples yet\n"));
+    }
+
+    #[test]
+    fn scopes_lfos_and_variables() {
+        let mut interp = Interpreter::new();
+        crate::analog::register(&mut interp);
+
+        interp
+            .exec_line(r#"a.201 "w" "sine" 1 lfo_new 8 ⨯ ⺆ "w" 0.125 lfo_tick drop ⺘"#)
+            .unwrap();
+        let trace = &interp.state::<Scopes>().unwrap().traces["w"];
+        assert_eq!(
+            trace.iter().map(|&v| v as i64).collect::<Vec<_>>(),
+            [0, 71, 100, 71, 0, -71, -100, -71]
+        );
+        interp.exec_line(r#""w" a.scope"#).unwrap();
+
+        // Replacing the LFO starts a fresh trace
+        interp.exec_line(r#""w" "saw" 1 lfo_new"#).unwrap();
+        assert!(!interp.state::<Scopes>().unwrap().traces.contains_key("w"));
+
+        interp
+            .exec_line(r#"1 2 3 3 a.buf 字 b "b" a.scope"#)
+            .unwrap();
+        let err = interp.exec_line(r#""nope" a.scope"#).unwrap_err();
+        assert!(
+            err.to_string().contains("no LFO or variable named 'nope'"),
+            "{err}"
+        );
+        let err = interp.exec_line(r#""s" 字 t "t" a.scope"#).unwrap_err();
+        assert!(err.to_string().contains("not a buffer or list"), "{err}");
+    }
+}
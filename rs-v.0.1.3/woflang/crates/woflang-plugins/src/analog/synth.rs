@@ -0,0 +1,132 @@
+//! Audio patches rendered to WAV files.
+//!
+//! | Operation  | Stack Effect                        | Description |
+//! |------------|-------------------------------------|-------------|
+//! | `a.osc`    | ( shape freq amp -- voice )         | Add an oscillator voice to the patch |
+//! | `a.env`    | ( voice a d s r gate -- )           | Shape a voice with an ADSR note |
+//! | `a.render` | ( path seconds -- )                 | Render the patch to a 16-bit WAV file |
+//! | `a.clear`  | ( -- )                              | Remove every voice |
+//!
+//! Shapes are `"sine"`, `"saw"`, `"square"` and `"tri"`. Amplitudes are
+//! fractions of full scale and the mix saturates at ±1. Envelope times
+//! are seconds: the note is held for `gate` seconds, then released:
+//!
+//! ```text
+//! "sine" 440 0.4 a.osc
+//! 0.01 0.1 0.6 0.5 1 a.env
+//! "a440.wav" 2 a.render
+//! ```
+//!
+//! Each render starts the patch from time zero at 44.1 kHz, streaming it
+//! through `woflang_analog::stream`, so rendering twice gives the same file.
+
+use woflang_analog::stream::{write_wav, Envelope, Oscillator, Patch, Stream, Waveform};
+use woflang_core::{InterpreterContext, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Longest render accepted, in seconds.
+const MAX_SECONDS: f64 = 600.0;
+
+/// Samples generated per refill of the render stream.
+const BLOCK: usize = 4096;
+
+/// The interpreter's patch.
+#[derive(Debug, Default)]
+struct Synth(Patch);
+
+/// Register the patch operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: shape freq amp → voice
+    interp.register("a.osc", |interp| {
+        let amp = interp.stack_mut().pop_numeric()?;
+        let freq = interp.stack_mut().pop_numeric()?;
+        let shape = interp.stack_mut().pop_string()?;
+        let waveform = Waveform::parse(&shape).ok_or_else(|| {
+            WofError::runtime(format!("a.osc: unknown shape '{shape}' (expected sine, saw, square or tri)"))
+        })?;
+        if !freq.is_finite() || freq < 0.0 || !amp.is_finite() {
+            return Err(WofError::runtime("a.osc: frequency must be finite and non-negative, amplitude finite"));
+        }
+        let voice = interp.state_mut::<Synth>().0.add(Oscillator::new(waveform, freq, amp));
+        interp.push(WofValue::integer(voice as i64));
+        Ok(())
+    });
+
+    // Stack: voice a d s r gate → (none)
+    interp.register("a.env", |interp| {
+        let gate = interp.stack_mut().pop_numeric()?;
+        let release = interp.stack_mut().pop_numeric()?;
+        let sustain = interp.stack_mut().pop_numeric()?;
+        let decay = interp.stack_mut().pop_numeric()?;
+        let attack = interp.stack_mut().pop_numeric()?;
+        let voice = interp.stack_mut().pop_integer()?;
+        if [attack, decay, release, gate].iter().any(|t| !t.is_finite() || *t < 0.0) {
+            return Err(WofError::runtime("a.env: times must be finite and non-negative"));
+        }
+        let envelope = Envelope::new(attack, decay, sustain, release, gate);
+        let found = usize::try_from(voice).is_ok_and(|v| interp.state_mut::<Synth>().0.set_envelope(v, envelope));
+        if !found {
+            return Err(WofError::runtime(format!("a.env: no voice {voice}")));
+        }
+        Ok(())
+    });
+
+    // Stack: path seconds → (none)
+    interp.register("a.render", |interp| {
+        let seconds = interp.stack_mut().pop_numeric()?;
+        let path = interp.stack_mut().pop_string()?;
+        let resolved = interp.sandbox().check_write(&path)?;
+        if !(seconds > 0.0 && seconds <= MAX_SECONDS) {
+            return Err(WofError::runtime(format!("a.render: seconds must be in (0, {MAX_SECONDS}]")));
+        }
+        let mut patch = interp.state_mut::<Synth>().0.clone();
+        patch.rewind();
+        let rate = patch.sample_rate();
+        let samples = Stream::new(patch, BLOCK).render(seconds);
+        write_wav(&resolved, &samples, rate)
+            .map_err(|e| WofError::runtime(format!("a.render: cannot write '{path}': {e}")))?;
+        Ok(())
+    });
+
+    // Stack: (none) → (none)
+    interp.register("a.clear", |interp| {
+        interp.state_mut::<Synth>().0.clear();
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_a_patch_to_wav() {
+        let mut interp = Interpreter::new();
+        crate::analog::register(&mut interp);
+        let path = std::env::temp_dir().join(format!("woflang-synth-{}.wav", std::process::id()));
+
+        interp.exec_line(r#""square" 220 0.5 a.osc "sine" 330 0.25 a.osc"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 0);
+        interp.exec_line("0 0.01 0.05 0.5 0.1 0.2 a.env").unwrap();
+        interp.exec_line(&format!(r#""{}" 0.5 a.render"#, path.display())).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(&bytes[..4], b"RIFF");
+        assert_eq!(bytes.len(), 44 + 2 * 22_050);
+        let samples: Vec<i16> = bytes[44..].chunks(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect();
+        // The square voice is released by 0.3 s, leaving only the quiet sine.
+        let peak = |s: &[i16]| s.iter().map(|x| x.unsigned_abs()).max().unwrap();
+        assert!(peak(&samples[..13_230]) > 20_000);
+        assert!(peak(&samples[13_230..]) <= 8_192);
+        assert_eq!(interp.state::<Synth>().unwrap().0.voices().len(), 2);
+
+        assert!(interp.exec_line("5 0 0 1 0 1 a.env").is_err());
+        assert!(interp.exec_line(r#""noise" 1 1 a.osc"#).is_err());
+        interp.stack_mut().clear();
+        assert!(interp.exec_line(r#""x.wav" 0 a.render"#).is_err());
+        interp.exec_line("a.clear").unwrap();
+        assert!(interp.state::<Synth>().unwrap().0.voices().is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+//! Color space conversion and palette operations for Woflang.
+//!
+//! ## Operations
+//!
+//! - `rgb_to_hsl` - RGB to HSL (r g b → h s l)
+//! - `hsl_to_rgb` - HSL to RGB (h s l → r g b)
+//! - `hex_to_rgb` - Hex color to RGB ("#ff8800" → r g b)
+//! - `rgb_to_hex` - RGB to hex color (r g b → "#ff8800")
+//! - `palette_gradient` - Evenly spaced colors (c1 c2 n → list)
+//! - `contrast_ratio` - WCAG contrast ratio (c1 c2 → ratio)
+//!
+//! RGB channels are integers 0-255, hue is in degrees and saturation and
+//! lightness are fractions. Where an op takes a whole color it is a hex
+//! string, `"#rrggbb"` or the short `"#rgb"`, so palettes are plain lists
+//! of strings:
+//!
+//! ```text
+//! "#000000" "#ffffff" 3 palette_gradient   # [#000000, #808080, #ffffff]
+//! "#777777" "#ffffff" contrast_ratio       # ≈ 4.48, just short of AA
+//! ```
+
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// An RGB color with channels in 0-255.
+type Rgb = [u8; 3];
+
+/// HSL (hue in degrees, saturation and lightness in [0, 1]) of a color.
+fn rgb_to_hsl([r, g, b]: Rgb) -> (f64, f64, f64) {
+    let [r, g, b] = [r, g, b].map(|c| f64::from(c) / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l);
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    (h * 60.0, s, l)
+}
+
+/// The color with the given hue (degrees, wrapped), saturation and
+/// lightness (clamped to [0, 1]).
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> Rgb {
+    let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+    let h = h.rem_euclid(360.0) / 60.0;
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    [r, g, b].map(|v| ((v + m) * 255.0).round() as u8)
+}
+
+/// Parse `#rrggbb` or `#rgb` (the `#` is optional).
+fn parse_hex(text: &str) -> Option<Rgb> {
+    let digits = text.strip_prefix('#').unwrap_or(text);
+    if !digits.is_ascii() {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match digits.len() {
+        6 => Some([channel(&digits[0..2])?, channel(&digits[2..4])?, channel(&digits[4..6])?]),
+        3 => {
+            let mut rgb = [0; 3];
+            for (slot, i) in rgb.iter_mut().zip(0..3) {
+                *slot = channel(&digits[i..=i])? * 17;
+            }
+            Some(rgb)
+        }
+        _ => None,
+    }
+}
+
+fn to_hex([r, g, b]: Rgb) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// `n` colors from `from` to `to` inclusive, interpolated in RGB.
+fn gradient(from: Rgb, to: Rgb, n: usize) -> Vec<Rgb> {
+    (0..n)
+        .map(|i| {
+            let t = if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 };
+            let mut rgb = [0; 3];
+            for (c, (a, b)) in rgb.iter_mut().zip(from.iter().zip(to)) {
+                *c = (f64::from(*a) + (f64::from(b) - f64::from(*a)) * t).round() as u8;
+            }
+            rgb
+        })
+        .collect()
+}
+
+/// WCAG 2 relative luminance.
+fn luminance(rgb: Rgb) -> f64 {
+    let [r, g, b] = rgb.map(|c| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.040_45 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    });
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// WCAG contrast ratio, from 1 (identical) to 21 (black on white).
+fn contrast_ratio(a: Rgb, b: Rgb) -> f64 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+fn pop_hex(interp: &mut Interpreter, op: &str) -> Result<Rgb> {
+    let text = interp.stack_mut().pop_string()?;
+    parse_hex(&text).ok_or_else(|| WofError::runtime(format!("{op}: '{text}' is not a #rrggbb or #rgb color")))
+}
+
+fn pop_rgb(interp: &mut Interpreter, op: &str) -> Result<Rgb> {
+    let mut rgb = [0; 3];
+    for slot in rgb.iter_mut().rev() {
+        let c = interp.stack_mut().pop_integer()?;
+        *slot = u8::try_from(c).map_err(|_| WofError::runtime(format!("{op}: channel {c} is outside 0-255")))?;
+    }
+    Ok(rgb)
+}
+
+fn push_rgb(interp: &mut Interpreter, rgb: Rgb) {
+    for c in rgb {
+        interp.push(WofValue::integer(i64::from(c)));
+    }
+}
+
+/// Register all color operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: r g b → h s l
+    interp.register("rgb_to_hsl", |interp| {
+        let (h, s, l) = rgb_to_hsl(pop_rgb(interp, "rgb_to_hsl")?);
+        interp.push(WofValue::double(h));
+        interp.push(WofValue::double(s));
+        interp.push(WofValue::double(l));
+        Ok(())
+    });
+
+    // Stack: h s l → r g b
+    interp.register("hsl_to_rgb", |interp| {
+        let l = interp.stack_mut().pop_numeric()?;
+        let s = interp.stack_mut().pop_numeric()?;
+        let h = interp.stack_mut().pop_numeric()?;
+        if ![h, s, l].iter().all(|v| v.is_finite()) {
+            return Err(WofError::runtime("hsl_to_rgb: components must be finite"));
+        }
+        push_rgb(interp, hsl_to_rgb(h, s, l));
+        Ok(())
+    });
+
+    // Stack: "#rrggbb" → r g b
+    interp.register("hex_to_rgb", |interp| {
+        let rgb = pop_hex(interp, "hex_to_rgb")?;
+        push_rgb(interp, rgb);
+        Ok(())
+    });
+
+    // Stack: r g b → "#rrggbb"
+    interp.register("rgb_to_hex", |interp| {
+        let rgb = pop_rgb(interp, "rgb_to_hex")?;
+        interp.push(WofValue::string(to_hex(rgb)));
+        Ok(())
+    });
+
+    // Stack: c1 c2 n → [c1 … c2]
+    interp.register("palette_gradient", |interp| {
+        let n = interp.stack_mut().pop_integer()?;
+        let to = pop_hex(interp, "palette_gradient")?;
+        let from = pop_hex(interp, "palette_gradient")?;
+        let n = usize::try_from(n)
+            .ok()
+            .filter(|n| (1..=4096).contains(n))
+            .ok_or_else(|| WofError::runtime("palette_gradient: n must be 1-4096"))?;
+        let colors = gradient(from, to, n).into_iter().map(|c| WofValue::string(to_hex(c))).collect();
+        interp.push(WofValue::list(colors));
+        Ok(())
+    });
+
+    // Stack: c1 c2 → ratio
+    interp.register("contrast_ratio", |interp| {
+        let b = pop_hex(interp, "contrast_ratio")?;
+        let a = pop_hex(interp, "contrast_ratio")?;
+        interp.push(WofValue::double(contrast_ratio(a, b)));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsl_round_trips() {
+        assert_eq!(rgb_to_hsl([255, 0, 0]), (0.0, 1.0, 0.5));
+        assert_eq!(hsl_to_rgb(120.0, 1.0, 0.25), [0, 128, 0]);
+        assert_eq!(hsl_to_rgb(-120.0, 1.0, 0.5), [0, 0, 255]);
+        for rgb in [[12, 200, 99], [255, 255, 255], [0, 0, 0], [250, 128, 114], [70, 130, 180]] {
+            let (h, s, l) = rgb_to_hsl(rgb);
+            assert_eq!(hsl_to_rgb(h, s, l), rgb);
+        }
+    }
+
+    #[test]
+    fn hex_gradients_and_contrast() {
+        assert_eq!(parse_hex("#ff8800"), Some([255, 136, 0]));
+        assert_eq!(parse_hex("f80"), Some([255, 136, 0]));
+        assert_eq!(parse_hex("#ff88"), None);
+        assert_eq!(parse_hex("#gg0000"), None);
+        assert_eq!(gradient([0, 0, 0], [255, 255, 255], 3), [[0, 0, 0], [128, 128, 128], [255, 255, 255]]);
+        assert!((contrast_ratio([0, 0, 0], [255, 255, 255]) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio([0x77; 3], [255; 3]) - 4.48).abs() < 0.01);
+    }
+
+    #[test]
+    fn ops_take_hex_colors() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        interp.exec_line(r##""#102030" hex_to_rgb rgb_to_hex"##).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "#102030");
+        interp.exec_line(r##""#000" "#fff" 3 palette_gradient"##).unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "[#000000, #808080, #ffffff]");
+        assert!(interp.exec_line("256 0 0 rgb_to_hex").is_err());
+        assert!(interp.exec_line(r##""#000" "#fff" 0 palette_gradient"##).is_err());
+    }
+}
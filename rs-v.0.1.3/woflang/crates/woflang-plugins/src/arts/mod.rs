@@ -2,7 +2,9 @@
 //!
 //! Creative and artistic tools:
 //! - **music** - Music theory (scales, chords, intervals, rhythms)
+//! - **color** - Color space conversion, gradients and contrast
 
+pub mod color;
 pub mod music;
 
 use woflang_runtime::Interpreter;
@@ -10,4 +12,5 @@ use woflang_runtime::Interpreter;
 /// Register all arts operations.
 pub fn register(interp: &mut Interpreter) {
     music::register(interp);
+    color::register(interp);
 }
@@ -460,34 +460,34 @@ pub fn register(interp: &mut Interpreter) {
     // HELP
     // ─────────────────────────────────────────────────────────────────────
     
-    interp.register("music_help", |_interp| {
-        println!("Music Theory Operations:");
-        println!();
-        println!("  Scales & Chords:");
-        println!("    \"C\" \"major\" build_scale   → scale description");
-        println!("    \"C\" \"maj7\" chord_tones    → chord tones");
-        println!();
-        println!("  Intervals:");
-        println!("    \"C\" \"E\" interval_semitones → 4");
-        println!("    60 64 interval_info        → \"4 semitones (major third)\"");
-        println!();
-        println!("  MIDI/Frequency:");
-        println!("    60 midi_name               → \"C4\"");
-        println!("    69 note_freq               → 440.0 Hz");
-        println!("    3 19 440 edo_freq          → 19-TET frequency");
-        println!();
-        println!("  Rhythm:");
-        println!("    120 bpm_ms                 → 500.0 ms (quarter note)");
-        println!("    120 \"1/8\" bpm_ms           → 250.0 ms (eighth note)");
-        println!("    3 8 euclid_pattern         → \"x--x--x-\"");
-        println!("    3 2 polyrhythm             → ASCII polyrhythm");
-        println!();
-        println!("  Scale types: major, minor, dorian, phrygian, lydian,");
-        println!("               mixolydian, locrian, pentatonic_major,");
-        println!("               pentatonic_minor, blues, whole_tone, chromatic");
-        println!();
-        println!("  Chord types: maj, min, dim, aug, sus2, sus4,");
-        println!("               maj7, min7, 7, dim7, m7b5, add9, 6");
+    interp.register("music_help", |interp| {
+        writeln!(interp.out(), "Music Theory Operations:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Scales & Chords:");
+        writeln!(interp.out(), "    \"C\" \"major\" build_scale   → scale description");
+        writeln!(interp.out(), "    \"C\" \"maj7\" chord_tones    → chord tones");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Intervals:");
+        writeln!(interp.out(), "    \"C\" \"E\" interval_semitones → 4");
+        writeln!(interp.out(), "    60 64 interval_info        → \"4 semitones (major third)\"");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  MIDI/Frequency:");
+        writeln!(interp.out(), "    60 midi_name               → \"C4\"");
+        writeln!(interp.out(), "    69 note_freq               → 440.0 Hz");
+        writeln!(interp.out(), "    3 19 440 edo_freq          → 19-TET frequency");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Rhythm:");
+        writeln!(interp.out(), "    120 bpm_ms                 → 500.0 ms (quarter note)");
+        writeln!(interp.out(), "    120 \"1/8\" bpm_ms           → 250.0 ms (eighth note)");
+        writeln!(interp.out(), "    3 8 euclid_pattern         → \"x--x--x-\"");
+        writeln!(interp.out(), "    3 2 polyrhythm             → ASCII polyrhythm");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Scale types: major, minor, dorian, phrygian, lydian,");
+        writeln!(interp.out(), "               mixolydian, locrian, pentatonic_major,");
+        writeln!(interp.out(), "               pentatonic_minor, blues, whole_tone, chromatic");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Chord types: maj, min, dim, aug, sus2, sus4,");
+        writeln!(interp.out(), "               maj7, min7, 7, dim7, m7b5, add9, 6");
         Ok(())
     });
 }
@@ -0,0 +1,64 @@
+//! Braille dot canvases for terminal charts (`plot_fn`, `a.scope`).
+//!
+//! Each character cell holds 2×4 dots, so a chart `COLS` cells wide and
+//! `ROWS` cells high has `COLS * 2` by `ROWS * 4` dots, addressed from
+//! the top left.
+
+/// Braille dot bits, indexed by `[row][column]` within a cell.
+const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// A grid of braille dots, `COLS` by `ROWS` character cells.
+pub(crate) struct Canvas<const COLS: usize, const ROWS: usize> {
+    cells: Vec<u32>,
+}
+
+impl<const COLS: usize, const ROWS: usize> Canvas<COLS, ROWS> {
+    /// Dot columns.
+    pub(crate) const WIDTH: usize = COLS * 2;
+
+    /// Dot rows.
+    pub(crate) const HEIGHT: usize = ROWS * 4;
+
+    /// A blank canvas.
+    pub(crate) fn new() -> Self {
+        Self { cells: vec![0; COLS * ROWS] }
+    }
+
+    /// Set the dot at column `x`, row `y`; dots off the canvas are ignored.
+    pub(crate) fn set(&mut self, x: usize, y: usize) {
+        if x < Self::WIDTH && y < Self::HEIGHT {
+            self.cells[(y / 4) * COLS + x / 2] |= DOTS[y % 4][x % 2];
+        }
+    }
+
+    /// Character row `row` as text.
+    pub(crate) fn row(&self, row: usize) -> String {
+        self.cells[row * COLS..(row + 1) * COLS]
+            .iter()
+            .map(|&bits| char::from_u32(0x2800 + bits).unwrap_or(' '))
+            .collect()
+    }
+}
+
+/// An axis label with two decimals, writing zero as `0.00` rather than
+/// `-0.00`.
+pub(crate) fn label(value: f64) -> String {
+    let text = format!("{value:.2}");
+    if text == "-0.00" { "0.00".to_string() } else { text }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dots_fill_cells() {
+        let mut canvas = Canvas::<2, 1>::new();
+        canvas.set(0, 0);
+        canvas.set(3, 3);
+        canvas.set(4, 0);
+        assert_eq!(canvas.row(0), "⠁⢀");
+        assert_eq!(label(-0.001), "0.00");
+        assert_eq!(label(-1.5), "-1.50");
+    }
+}
@@ -1,12 +1,35 @@
 //! Cryptographic and number theory operations for Woflang.
 //!
 //! Includes prime checking, modular arithmetic, and basic crypto primitives.
+//!
+//! The modular ops and `is_prime_mr` also take bigints (integer literals
+//! past 64 bits, or `to_big`), answering with a bigint when any operand
+//! is one, so RSA-sized keys can be worked by hand:
+//!
+//! ```text
+//! 170141183460469231731687303715884105727 is_prime_mr          # 1
+//! 2 127 170141183460469231731687303715884105727 modexp          # 1
+//! "123456789012345678901234567890" to_big 97 modinv            # ...
+//! ```
 
 mod primes;
 mod modular;
 
+use woflang_core::{BigInt, InterpreterContext, Result, WofType, WofValue};
 use woflang_runtime::Interpreter;
 
+/// Pop the top `n` values as bigints, deepest first, if any of them is
+/// one; otherwise leave the stack alone for the 64-bit path.
+fn pop_bigints(interp: &mut Interpreter, n: usize) -> Result<Option<Vec<BigInt>>> {
+    let stack = interp.stack().as_slice();
+    let top = &stack[stack.len().saturating_sub(n)..];
+    if !top.iter().any(|v| v.value_type() == WofType::BigInt) {
+        return Ok(None);
+    }
+    let values = interp.stack_mut().pop_n(n)?;
+    values.iter().rev().map(WofValue::as_bigint).collect::<Result<Vec<_>>>().map(Some)
+}
+
 /// Register all crypto operations with the interpreter.
 pub fn register(interp: &mut Interpreter) {
     primes::register(interp);
@@ -2,11 +2,29 @@
 //!
 //! Provides modular exponentiation, inverse, GCD/LCM, and related functions.
 
-use woflang_core::{WofError, WofValue, InterpreterContext};
+use woflang_core::{BigInt, WofError, WofValue, InterpreterContext};
 use woflang_runtime::Interpreter;
 
+use super::pop_bigints;
+
 /// Register modular arithmetic operations.
 pub fn register(interp: &mut Interpreter) {
+    // ═══════════════════════════════════════════════════════════════
+    // BIG INTEGERS
+    // ═══════════════════════════════════════════════════════════════
+
+    // Widen an integer, or parse a decimal string, to a bigint
+    // Stack: n|"digits" → N
+    interp.register("to_big", |interp| {
+        let value = interp.stack_mut().pop()?;
+        let n = match value.as_str() {
+            Ok(digits) => digits.trim().parse::<BigInt>()?,
+            Err(_) => value.as_bigint()?,
+        };
+        interp.stack_mut().push(WofValue::bigint(n));
+        Ok(())
+    });
+
     // ═══════════════════════════════════════════════════════════════
     // MODULAR EXPONENTIATION
     // ═══════════════════════════════════════════════════════════════
@@ -14,6 +32,14 @@ pub fn register(interp: &mut Interpreter) {
     // Modular exponentiation: base^exp mod m
     // Stack: base exp mod → result
     interp.register("modexp", |interp| {
+        if let Some(args) = pop_bigints(interp, 3)? {
+            let [base, exp, m] = &args[..] else { unreachable!("three operands") };
+            let result = base.pow_mod(exp, m).ok_or_else(|| {
+                WofError::Runtime("modexp: modulus must be positive and exponent non-negative".into())
+            })?;
+            interp.stack_mut().push(WofValue::bigint(result));
+            return Ok(());
+        }
         let m = interp.stack_mut().pop()?.as_integer()?;
         let exp = interp.stack_mut().pop()?.as_integer()?;
         let base = interp.stack_mut().pop()?.as_integer()?;
@@ -37,6 +63,17 @@ pub fn register(interp: &mut Interpreter) {
     // Modular inverse: a^(-1) mod m (where a * result ≡ 1 mod m)
     // Stack: a m → inverse (or error if not coprime)
     interp.register("modinv", |interp| {
+        if let Some(args) = pop_bigints(interp, 2)? {
+            let [a, m] = &args[..] else { unreachable!("two operands") };
+            if m.is_negative() || m.is_zero() {
+                return Err(WofError::Runtime("modinv: modulus must be positive".into()));
+            }
+            let inv = a.mod_inv(m).ok_or_else(|| {
+                WofError::Runtime(format!("modinv: {a} has no inverse mod {m} (not coprime)"))
+            })?;
+            interp.stack_mut().push(WofValue::bigint(inv));
+            return Ok(());
+        }
         let m = interp.stack_mut().pop()?.as_integer()?;
         let a = interp.stack_mut().pop()?.as_integer()?;
         
@@ -64,6 +101,13 @@ pub fn register(interp: &mut Interpreter) {
     // Extended GCD: returns (gcd, x, y) where ax + by = gcd
     // Stack: a b → gcd x y
     interp.register("extgcd", |interp| {
+        if let Some(args) = pop_bigints(interp, 2)? {
+            let (g, x, y) = args[0].extended_gcd(&args[1]);
+            for n in [g, x, y] {
+                interp.stack_mut().push(WofValue::bigint(n));
+            }
+            return Ok(());
+        }
         let b = interp.stack_mut().pop()?.as_integer()?;
         let a = interp.stack_mut().pop()?.as_integer()?;
         
@@ -81,6 +125,15 @@ pub fn register(interp: &mut Interpreter) {
     // Modular addition: (a + b) mod m
     // Stack: a b m → result
     interp.register("modadd", |interp| {
+        if let Some(args) = pop_bigints(interp, 3)? {
+            let [a, b, m] = &args[..] else { unreachable!("three operands") };
+            if m.is_negative() || m.is_zero() {
+                return Err(WofError::Runtime("modadd: modulus must be positive".into()));
+            }
+            let result = (a + b).rem_euclid(m).unwrap_or_default();
+            interp.stack_mut().push(WofValue::bigint(result));
+            return Ok(());
+        }
         let m = interp.stack_mut().pop()?.as_integer()?;
         let b = interp.stack_mut().pop()?.as_integer()?;
         let a = interp.stack_mut().pop()?.as_integer()?;
@@ -98,6 +151,15 @@ pub fn register(interp: &mut Interpreter) {
     // Modular subtraction: (a - b) mod m
     // Stack: a b m → result
     interp.register("modsub", |interp| {
+        if let Some(args) = pop_bigints(interp, 3)? {
+            let [a, b, m] = &args[..] else { unreachable!("three operands") };
+            if m.is_negative() || m.is_zero() {
+                return Err(WofError::Runtime("modsub: modulus must be positive".into()));
+            }
+            let result = (a - b).rem_euclid(m).unwrap_or_default();
+            interp.stack_mut().push(WofValue::bigint(result));
+            return Ok(());
+        }
         let m = interp.stack_mut().pop()?.as_integer()?;
         let b = interp.stack_mut().pop()?.as_integer()?;
         let a = interp.stack_mut().pop()?.as_integer()?;
@@ -115,6 +177,15 @@ pub fn register(interp: &mut Interpreter) {
     // Modular multiplication: (a * b) mod m
     // Stack: a b m → result
     interp.register("modmul", |interp| {
+        if let Some(args) = pop_bigints(interp, 3)? {
+            let [a, b, m] = &args[..] else { unreachable!("three operands") };
+            if m.is_negative() || m.is_zero() {
+                return Err(WofError::Runtime("modmul: modulus must be positive".into()));
+            }
+            let result = (a * b).rem_euclid(m).unwrap_or_default();
+            interp.stack_mut().push(WofValue::bigint(result));
+            return Ok(());
+        }
         let m = interp.stack_mut().pop()?.as_integer()?;
         let b = interp.stack_mut().pop()?.as_integer()?;
         let a = interp.stack_mut().pop()?.as_integer()?;
@@ -161,6 +232,11 @@ pub fn register(interp: &mut Interpreter) {
     
     // Check if a and b are coprime
     interp.register("coprime?", |interp| {
+        if let Some(args) = pop_bigints(interp, 2)? {
+            let coprime = args[0].gcd(&args[1]) == BigInt::one();
+            interp.stack_mut().push(WofValue::integer(i64::from(coprime)));
+            return Ok(());
+        }
         let b = interp.stack_mut().pop()?.as_integer()?.unsigned_abs();
         let a = interp.stack_mut().pop()?.as_integer()?.unsigned_abs();
         let result = if gcd(a, b) == 1 { 1 } else { 0 };
@@ -252,3 +328,41 @@ fn euler_totient(mut n: u64) -> u64 {
     
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modular_ops_take_bigints() {
+        let mut interp = Interpreter::new();
+        crate::crypto::register(&mut interp);
+        let p = "170141183460469231731687303715884105727";
+
+        interp.exec_line(&format!("{p} is_prime_mr 2 127 {p} modexp")).unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "1");
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1);
+
+        interp.exec_line(&format!("3 to_big {p} modinv")).unwrap();
+        let inv = interp.stack_mut().pop().unwrap();
+        interp.push(inv);
+        interp.exec_line(&format!("3 {p} modmul")).unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "1");
+
+        interp.exec_line(&format!("5 {p} 7 modsub {p} 6 coprime?")).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1);
+        let diff = (5 - 170_141_183_460_469_231_731_687_303_715_884_105_727_i128).rem_euclid(7);
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), diff.to_string());
+
+        interp.exec_line(r#""-18446744073709551616" to_big 6 extgcd"#).unwrap();
+        let y = interp.stack_mut().pop().unwrap().as_bigint().unwrap();
+        let x = interp.stack_mut().pop().unwrap().as_bigint().unwrap();
+        let g = interp.stack_mut().pop().unwrap().as_bigint().unwrap();
+        let a: BigInt = "-18446744073709551616".parse().unwrap();
+        assert_eq!(g, BigInt::from(2_i64));
+        assert_eq!(&(&a * &x) + &(&y * &BigInt::from(6_i64)), g);
+
+        assert!(interp.exec_line("6 510423550381407695195061911147652317181 modinv").is_err());
+        assert!(interp.exec_line(r#""12x" to_big"#).is_err());
+    }
+}
@@ -13,6 +13,10 @@ pub fn register(interp: &mut Interpreter) {
     
     // Miller-Rabin primality test (probabilistic but accurate for 64-bit)
     interp.register("is_prime_mr", |interp| {
+        if let Some(n) = super::pop_bigints(interp, 1)? {
+            interp.stack_mut().push(WofValue::integer(i64::from(n[0].is_probable_prime())));
+            return Ok(());
+        }
         let n = interp.stack_mut().pop()?.as_integer()?;
         let result = if n <= 1 { 0 } else { if miller_rabin(n as u64) { 1 } else { 0 } };
         interp.stack_mut().push(WofValue::integer(result));
@@ -97,7 +101,9 @@ pub fn register(interp: &mut Interpreter) {
             interp.stack_mut().push(WofValue::integer(0));
             return Ok(());
         }
-        let primes = sieve_of_eratosthenes(n as usize);
+        let primes = sieve_of_eratosthenes(n as usize, &mut |done, total| {
+            interp.progress(done, total, "sieve passes");
+        });
         let count = primes.len();
         for p in primes {
             interp.stack_mut().push(WofValue::integer(p as i64));
@@ -204,15 +210,20 @@ fn prime_factors(mut n: u64) -> Vec<u64> {
 }
 
 /// Sieve of Eratosthenes.
-fn sieve_of_eratosthenes(limit: usize) -> Vec<usize> {
+///
+/// `on_pass(i, root)` is called as the sieve crosses off multiples of
+/// each candidate `i` up to `root`, the square root of `limit`.
+fn sieve_of_eratosthenes(limit: usize, on_pass: &mut dyn FnMut(usize, usize)) -> Vec<usize> {
     if limit < 2 { return Vec::new(); }
     
     let mut is_prime = vec![true; limit + 1];
     is_prime[0] = false;
     is_prime[1] = false;
 
+    let root = (limit as f64).sqrt() as usize;
     let mut i = 2;
     while i * i <= limit {
+        on_pass(i, root);
         if is_prime[i] {
             let mut j = i * i;
             while j <= limit {
@@ -222,6 +233,7 @@ fn sieve_of_eratosthenes(limit: usize) -> Vec<usize> {
         }
         i += 1;
     }
+    on_pass(root, root);
 
     is_prime.iter().enumerate()
         .filter(|(_, &p)| p)
@@ -250,5 +262,5 @@ fn nth_prime(n: usize) -> u64 {
 /// Count primes up to n.
 fn prime_count(n: u64) -> usize {
     if n < 2 { return 0; }
-    sieve_of_eratosthenes(n as usize).len()
+    sieve_of_eratosthenes(n as usize, &mut |_, _| {}).len()
 }
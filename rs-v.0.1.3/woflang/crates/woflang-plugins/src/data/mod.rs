@@ -4,7 +4,7 @@
 //! Uses the COMPLETE embedded constants database from wof_constants_module.json.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::OnceLock;
 
 /// Embed the FULL constants database at compile time.
@@ -75,8 +75,8 @@ pub struct ConstantsDb {
     pub si_prefixes: Vec<Unit>,
     
     // Lookups
-    by_name: HashMap<String, Constant>,
-    by_symbol: HashMap<String, Constant>,
+    by_name: BTreeMap<String, Constant>,
+    by_symbol: BTreeMap<String, Constant>,
     by_opcode: HashMap<i32, Constant>,
     
     // Metadata
@@ -137,8 +137,8 @@ impl ConstantsDb {
             base_si_units: root.units.base_si,
             derived_si_units: root.units.derived_si,
             si_prefixes: root.units.si_prefixes,
-            by_name: HashMap::new(),
-            by_symbol: HashMap::new(),
+            by_name: BTreeMap::new(),
+            by_symbol: BTreeMap::new(),
             by_opcode: HashMap::new(),
             name: root.metadata.name,
             version: root.metadata.version,
@@ -181,12 +181,12 @@ impl ConstantsDb {
         &self.categories
     }
 
-    /// Get all constant names.
+    /// Get all constant names, sorted.
     pub fn names(&self) -> impl Iterator<Item = &str> {
         self.by_name.keys().map(|s| s.as_str())
     }
 
-    /// Get all symbols.
+    /// Get all symbols, sorted.
     pub fn symbols(&self) -> impl Iterator<Item = &str> {
         self.by_symbol.keys().map(|s| s.as_str())
     }
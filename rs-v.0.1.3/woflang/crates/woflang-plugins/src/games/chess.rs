@@ -13,7 +13,7 @@
 //! - `chess_move` - Make a move (e.g., "e2e4")
 
 use std::sync::{Mutex, OnceLock};
-use woflang_core::{WofValue, InterpreterContext};
+use woflang_core::{WofValue, InterpreterContext, Out};
 use woflang_runtime::Interpreter;
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -574,19 +574,19 @@ fn find_best_move(pos: &ChessPosition, depth: i32) -> Option<Move> {
 // DISPLAY
 // ═══════════════════════════════════════════════════════════════════════════
 
-fn print_board(pos: &ChessPosition) {
-    println!("   +------------------------+");
+fn print_board(out: &Out, pos: &ChessPosition) {
+    writeln!(out, "   +------------------------+");
     for r in (0..8).rev() {
-        print!(" {} |", r + 1);
+        write!(out, " {} |", r + 1);
         for f in 0..8 {
             let c = pos.at(r * 8 + f);
-            print!(" {}", if c == '.' { '.' } else { c });
+            write!(out, " {}", if c == '.' { '.' } else { c });
         }
-        println!(" |");
+        writeln!(out, " |");
     }
-    println!("   +------------------------+");
-    println!("     a b c d e f g h");
-    println!("Side to move: {}", if pos.white_to_move { "White" } else { "Black" });
+    writeln!(out, "   +------------------------+");
+    writeln!(out, "     a b c d e f g h");
+    writeln!(out, "Side to move: {}", if pos.white_to_move { "White" } else { "Black" });
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -596,19 +596,19 @@ fn print_board(pos: &ChessPosition) {
 /// Register all chess operations.
 pub fn register(interp: &mut Interpreter) {
     // Start a new game
-    interp.register("chess_new", |_interp| {
+    interp.register("chess_new", |interp| {
         if let Ok(mut pos) = game_state().lock() {
             pos.init_start();
-            println!("[simple_chess] New game started.");
-            print_board(&pos);
+            writeln!(interp.out(), "[simple_chess] New game started.");
+            print_board(&interp.out(), &pos);
         }
         Ok(())
     });
 
     // Show current board
-    interp.register("chess_show", |_interp| {
+    interp.register("chess_show", |interp| {
         if let Ok(pos) = game_state().lock() {
-            print_board(&pos);
+            print_board(&interp.out(), &pos);
         }
         Ok(())
     });
@@ -619,14 +619,14 @@ pub fn register(interp: &mut Interpreter) {
         let move_str = interp.stack_mut().pop()?.as_string()?;
         
         if move_str.len() < 4 {
-            println!("[simple_chess] Invalid move string: {}", move_str);
+            writeln!(interp.out(), "[simple_chess] Invalid move string: {}", move_str);
             return Ok(());
         }
         
         let from = match parse_square(&move_str[0..2]) {
             Some(sq) => sq,
             None => {
-                println!("[simple_chess] Invalid from square: {}", &move_str[0..2]);
+                writeln!(interp.out(), "[simple_chess] Invalid from square: {}", &move_str[0..2]);
                 return Ok(());
             }
         };
@@ -634,7 +634,7 @@ pub fn register(interp: &mut Interpreter) {
         let to = match parse_square(&move_str[2..4]) {
             Some(sq) => sq,
             None => {
-                println!("[simple_chess] Invalid to square: {}", &move_str[2..4]);
+                writeln!(interp.out(), "[simple_chess] Invalid to square: {}", &move_str[2..4]);
                 return Ok(());
             }
         };
@@ -648,30 +648,30 @@ pub fn register(interp: &mut Interpreter) {
         let user_move = match user_move {
             Some(m) => *m,
             None => {
-                println!("[simple_chess] Illegal move: {}", move_str);
+                writeln!(interp.out(), "[simple_chess] Illegal move: {}", move_str);
                 return Ok(());
             }
         };
         
         // Apply user move
         *pos = make_move(&pos, &user_move);
-        println!("[simple_chess] You played: {}", user_move.to_string());
-        print_board(&pos);
+        writeln!(interp.out(), "[simple_chess] You played: {}", user_move.to_string());
+        print_board(&interp.out(), &pos);
         
         // Engine reply
         if let Some(engine_move) = find_best_move(&pos, 3) {
             *pos = make_move(&pos, &engine_move);
             let eng_str = engine_move.to_string();
-            println!("[simple_chess] Engine plays: {}", eng_str);
-            print_board(&pos);
+            writeln!(interp.out(), "[simple_chess] Engine plays: {}", eng_str);
+            print_board(&interp.out(), &pos);
             interp.stack_mut().push(WofValue::string(eng_str));
         } else {
             // Game over
             if in_check(&pos, pos.white_to_move) {
-                println!("[simple_chess] Checkmate. {} is checkmated.",
+                writeln!(interp.out(), "[simple_chess] Checkmate. {} is checkmated.",
                     if pos.white_to_move { "White" } else { "Black" });
             } else {
-                println!("[simple_chess] Stalemate.");
+                writeln!(interp.out(), "[simple_chess] Stalemate.");
             }
             interp.stack_mut().push(WofValue::string(String::new()));
         }
@@ -680,33 +680,33 @@ pub fn register(interp: &mut Interpreter) {
     });
 
     // Get legal moves (for debugging)
-    interp.register("chess_moves", |_interp| {
+    interp.register("chess_moves", |interp| {
         if let Ok(pos) = game_state().lock() {
             let moves = generate_moves(&pos);
-            println!("[simple_chess] Legal moves ({}):", moves.len());
+            writeln!(interp.out(), "[simple_chess] Legal moves ({}):", moves.len());
             for m in &moves {
-                print!("{} ", m.to_string());
+                write!(interp.out(), "{} ", m.to_string());
             }
-            println!();
+            writeln!(interp.out());
         }
         Ok(())
     });
 
     // Help
-    interp.register("chess_help", |_interp| {
-        println!("Simple Chess Operations:");
-        println!();
-        println!("  chess_new           - Start a new game");
-        println!("  chess_show          - Display the board");
-        println!("  \"e2e4\" chess_move   - Make a move, engine replies");
-        println!("  chess_moves         - List all legal moves");
-        println!();
-        println!("Move format: from-square + to-square (e.g., \"e2e4\", \"g1f3\")");
-        println!();
-        println!("Notes:");
-        println!("  - Simplified rules: no castling, no en passant");
-        println!("  - Pawns auto-promote to queen");
-        println!("  - Engine uses 3-ply alpha-beta search");
+    interp.register("chess_help", |interp| {
+        writeln!(interp.out(), "Simple Chess Operations:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  chess_new           - Start a new game");
+        writeln!(interp.out(), "  chess_show          - Display the board");
+        writeln!(interp.out(), "  \"e2e4\" chess_move   - Make a move, engine replies");
+        writeln!(interp.out(), "  chess_moves         - List all legal moves");
+        writeln!(interp.out());
+        writeln!(interp.out(), "Move format: from-square + to-square (e.g., \"e2e4\", \"g1f3\")");
+        writeln!(interp.out());
+        writeln!(interp.out(), "Notes:");
+        writeln!(interp.out(), "  - Simplified rules: no castling, no en passant");
+        writeln!(interp.out(), "  - Pawns auto-promote to queen");
+        writeln!(interp.out(), "  - Engine uses 3-ply alpha-beta search");
         Ok(())
     });
 }
@@ -0,0 +1,247 @@
+//! PageRank and centrality measures.
+//!
+//! | Operation     | Stack Effect                        | Description |
+//! |---------------|-------------------------------------|-------------|
+//! | `pagerank`    | ( name damping iters -- s0 .. sn-1 n ) | PageRank by power iteration |
+//! | `betweenness` | ( name -- s0 .. sn-1 n )            | Betweenness centrality (Brandes) |
+//! | `closeness`   | ( name -- s0 .. sn-1 n )            | Closeness centrality |
+//!
+//! Scores come back as a counted list indexed by node: node `i`'s score
+//! is the `i`th entry. PageRank scores sum to 1 and nodes without
+//! out-edges share their rank with every node. Betweenness counts, for
+//! each node, the shortest paths between other nodes that pass through
+//! it (each unordered pair once in undirected graphs). Closeness uses
+//! distances along out-edges and is scaled by the fraction of the graph a
+//! node reaches, so a node in a small component does not score highly.
+//!
+//! ```text
+//! "web" 0.85 50 pagerank
+//! ```
+
+use std::collections::VecDeque;
+
+use woflang_core::{InterpreterContext, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+use super::core::{get_graph, Graph};
+
+/// Each node's distinct neighbours other than itself.
+fn simple_adjacency(graph: &Graph) -> Vec<Vec<usize>> {
+    graph
+        .adj
+        .iter()
+        .enumerate()
+        .map(|(u, neighbors)| {
+            let mut out: Vec<usize> = neighbors.iter().copied().filter(|&v| v != u).collect();
+            out.sort_unstable();
+            out.dedup();
+            out
+        })
+        .collect()
+}
+
+/// PageRank with damping factor `damping`.
+fn pagerank(graph: &Graph, damping: f64, iterations: usize) -> Vec<f64> {
+    let n = graph.node_count();
+    if n == 0 {
+        return Vec::new();
+    }
+    let adj = simple_adjacency(graph);
+    let uniform = 1.0 / n as f64;
+    let mut rank = vec![uniform; n];
+    for _ in 0..iterations {
+        let dangling: f64 = (0..n).filter(|&u| adj[u].is_empty()).map(|u| rank[u]).sum();
+        let base = (1.0 - damping) * uniform + damping * dangling * uniform;
+        let mut next = vec![base; n];
+        for (u, out) in adj.iter().enumerate() {
+            let share = damping * rank[u] / out.len().max(1) as f64;
+            for &v in out {
+                next[v] += share;
+            }
+        }
+        rank = next;
+    }
+    rank
+}
+
+/// Betweenness centrality by Brandes' algorithm, calling `progress`
+/// after each source node.
+fn betweenness(graph: &Graph, progress: &mut dyn FnMut(usize, usize)) -> Vec<f64> {
+    let n = graph.node_count();
+    let adj = simple_adjacency(graph);
+    let mut score = vec![0.0; n];
+    for s in 0..n {
+        progress(s, n);
+        let mut order = Vec::with_capacity(n);
+        let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut paths = vec![0.0; n];
+        let mut dist = vec![usize::MAX; n];
+        paths[s] = 1.0;
+        dist[s] = 0;
+        let mut queue = VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for &v in &adj[u] {
+                if dist[v] == usize::MAX {
+                    dist[v] = dist[u] + 1;
+                    queue.push_back(v);
+                }
+                if dist[v] == dist[u] + 1 {
+                    paths[v] += paths[u];
+                    preds[v].push(u);
+                }
+            }
+        }
+        let mut delta = vec![0.0; n];
+        for &w in order.iter().rev() {
+            for &u in &preds[w] {
+                delta[u] += paths[u] / paths[w] * (1.0 + delta[w]);
+            }
+            if w != s {
+                score[w] += delta[w];
+            }
+        }
+    }
+    progress(n, n);
+    if !graph.directed {
+        for x in &mut score {
+            *x /= 2.0;
+        }
+    }
+    score
+}
+
+/// Closeness centrality, scaled by the fraction of nodes reached.
+fn closeness(graph: &Graph) -> Vec<f64> {
+    let n = graph.node_count();
+    let adj = simple_adjacency(graph);
+    (0..n)
+        .map(|s| {
+            let mut dist = vec![usize::MAX; n];
+            dist[s] = 0;
+            let (mut reached, mut total) = (0usize, 0usize);
+            let mut queue = VecDeque::from([s]);
+            while let Some(u) = queue.pop_front() {
+                for &v in &adj[u] {
+                    if dist[v] == usize::MAX {
+                        dist[v] = dist[u] + 1;
+                        reached += 1;
+                        total += dist[v];
+                        queue.push_back(v);
+                    }
+                }
+            }
+            if total == 0 {
+                return 0.0;
+            }
+            let reached = reached as f64;
+            (reached / (n - 1) as f64) * (reached / total as f64)
+        })
+        .collect()
+}
+
+/// Push scores as a counted list.
+fn push_scores(interp: &mut Interpreter, scores: Vec<f64>) {
+    let n = scores.len();
+    interp.stack_mut().push_all(scores.into_iter().map(WofValue::double));
+    interp.push(WofValue::integer(n as i64));
+}
+
+/// Register centrality operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: name damping iters → s0 .. sn-1 n
+    interp.register("pagerank", |interp| {
+        let iterations = interp.stack_mut().pop_integer()?;
+        let damping = interp.stack_mut().pop_double()?;
+        let name = interp.stack_mut().pop()?.as_string()?;
+        if !(0.0..=1.0).contains(&damping) {
+            return Err(WofError::InvalidArgument("pagerank: damping must be in [0, 1]".into()));
+        }
+        let iterations = usize::try_from(iterations)
+            .map_err(|_| WofError::InvalidArgument("pagerank: iters must be >= 0".into()))?;
+        let graph = get_graph(interp, &name)?;
+        push_scores(interp, pagerank(&graph, damping, iterations));
+        Ok(())
+    });
+
+    // Stack: name → s0 .. sn-1 n
+    interp.register("betweenness", |interp| {
+        let name = interp.stack_mut().pop()?.as_string()?;
+        let graph = get_graph(interp, &name)?;
+        let scores = betweenness(&graph, &mut |done, total| interp.progress(done, total, "sources"));
+        push_scores(interp, scores);
+        Ok(())
+    });
+
+    // Stack: name → s0 .. sn-1 n
+    interp.register("closeness", |interp| {
+        let name = interp.stack_mut().pop()?.as_string()?;
+        let graph = get_graph(interp, &name)?;
+        push_scores(interp, closeness(&graph));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(n: usize, directed: bool, edges: &[(usize, usize)]) -> Graph {
+        let mut g = Graph::new(n, directed);
+        for &(u, v) in edges {
+            g.add_edge(u, v);
+        }
+        g
+    }
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-9)
+    }
+
+    #[test]
+    fn pagerank_favours_well_linked_nodes() {
+        let cycle = graph(4, true, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert!(close(&pagerank(&cycle, 0.85, 50), &[0.25; 4]));
+
+        let star = graph(4, false, &[(0, 1), (0, 2), (0, 3)]);
+        let rank = pagerank(&star, 0.85, 100);
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(rank[0] > 0.45 && rank[1..].iter().all(|&r| r < rank[0]));
+
+        // Node 1 has no out-edges; its rank is spread, not lost.
+        let sink = graph(2, true, &[(0, 1)]);
+        assert!((pagerank(&sink, 0.85, 100).iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn betweenness_and_closeness_on_a_path() {
+        let path = graph(4, false, &[(0, 1), (1, 2), (2, 3)]);
+        let mut calls = 0;
+        let b = betweenness(&path, &mut |_, _| calls += 1);
+        assert!(close(&b, &[0.0, 2.0, 2.0, 0.0]));
+        assert_eq!(calls, 5);
+        assert!(close(&closeness(&path), &[0.5, 0.75, 0.75, 0.5]));
+
+        let split = graph(4, false, &[(0, 1)]);
+        assert!(close(&closeness(&split), &[1.0 / 3.0, 1.0 / 3.0, 0.0, 0.0]));
+
+        let arcs = graph(3, true, &[(0, 1), (1, 2)]);
+        assert!(close(&betweenness(&arcs, &mut |_, _| {}), &[0.0, 1.0, 0.0]));
+    }
+
+    #[test]
+    fn ops_push_counted_scores() {
+        let mut interp = Interpreter::new();
+        super::super::core::register(&mut interp);
+        register(&mut interp);
+        interp
+            .exec_line(r#"3 "p" graph_new 0 1 "p" graph_add_edge 1 2 "p" graph_add_edge "p" betweenness"#)
+            .unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 3);
+        assert_eq!(interp.stack_mut().pop_double().unwrap(), 0.0);
+        assert_eq!(interp.stack_mut().pop_double().unwrap(), 1.0);
+
+        interp.stack_mut().clear();
+        assert!(interp.exec_line(r#""p" 1.5 10 pagerank"#).is_err());
+    }
+}
@@ -106,7 +106,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → summary_string num_colors
     interp.register("graph_color_greedy", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         let n = graph.node_count();
         
         if n == 0 {
@@ -133,7 +133,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → num_colors
     interp.register("graph_chromatic", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         
         if graph.node_count() == 0 {
             interp.stack_mut().push(WofValue::integer(0));
@@ -151,7 +151,7 @@ pub fn register(interp: &mut Interpreter) {
         let name = interp.stack_mut().pop()?.as_string()?;
         let k = interp.stack_mut().pop()?.as_integer()? as usize;
         
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         
         if graph.node_count() == 0 {
             interp.stack_mut().push(WofValue::integer(1));
@@ -168,7 +168,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → 1|0
     interp.register("graph_bipartite?", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         let n = graph.node_count();
         
         if n == 0 {
@@ -224,7 +224,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → lower_bound
     interp.register("graph_chromatic_lower", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         let bound = chromatic_lower_bound(&graph.adj);
         interp.stack_mut().push(WofValue::integer(bound as i64));
         Ok(())
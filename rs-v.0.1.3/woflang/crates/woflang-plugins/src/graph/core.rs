@@ -1,9 +1,22 @@
 //! Core graph structure and basic operations.
 //!
-//! Provides graph creation, edge addition, degree queries, and management.
+//! Provides graph creation (including random G(n, p) graphs), edge
+//! addition, degree queries, and management.
+//!
+//! `with_graph "name" [n] ⺆ ... ⺘` creates a scratch graph of `n` nodes
+//! (default 0) under an unused name, pushes the name for the block and
+//! deletes the graph afterwards, even if the block fails:
+//!
+//! ```text
+//! with_graph "tmp" 3 ⺆ 0 1 "tmp" graph_add_edge graph_edges ⺘   # 1
+//! ```
+//!
+//! Graphs belong to their interpreter, and names are resolved in the
+//! current namespace, so `ns "lib" ⺆ 3 "g" graph_new ⺘` creates
+//! `lib::g` and leaves a top-level `"g"` alone.
 
+use rand::Rng;
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
 use woflang_core::{WofError, WofValue, InterpreterContext};
 use woflang_runtime::Interpreter;
 
@@ -49,50 +62,41 @@ impl Graph {
     }
 }
 
-/// Global graph storage (thread-safe).
-type GraphStore = Arc<RwLock<HashMap<String, Graph>>>;
+/// The graphs of one interpreter, by [resource name](Interpreter::resource_name).
+#[derive(Debug, Default)]
+struct Graphs(HashMap<String, Graph>);
 
-fn get_store() -> GraphStore {
-    use std::sync::OnceLock;
-    static STORE: OnceLock<GraphStore> = OnceLock::new();
-    STORE.get_or_init(|| Arc::new(RwLock::new(HashMap::new()))).clone()
+fn unknown(name: &str) -> WofError {
+    WofError::Runtime(format!("graph: unknown graph '{name}'"))
 }
 
 /// Get a graph by name (cloned).
-pub fn get_graph(name: &str) -> Result<Graph, WofError> {
-    let store = get_store();
-    let guard = store.read().map_err(|_| WofError::Runtime("graph lock poisoned".into()))?;
-    guard.get(name).cloned().ok_or_else(|| {
-        WofError::Runtime(format!("graph: unknown graph '{}'", name))
-    })
+pub fn get_graph(interp: &Interpreter, name: &str) -> Result<Graph, WofError> {
+    let key = interp.resource_name(name);
+    interp.state::<Graphs>().and_then(|g| g.0.get(&key)).cloned().ok_or_else(|| unknown(name))
 }
 
 /// Store a graph by name.
-pub fn set_graph(name: &str, graph: Graph) -> Result<(), WofError> {
-    let store = get_store();
-    let mut guard = store.write().map_err(|_| WofError::Runtime("graph lock poisoned".into()))?;
-    guard.insert(name.to_string(), graph);
+pub fn set_graph(interp: &mut Interpreter, name: &str, graph: Graph) -> Result<(), WofError> {
+    let key = interp.resource_name(name);
+    interp.state_mut::<Graphs>().0.insert(key, graph);
     Ok(())
 }
 
 /// Remove a graph by name.
-pub fn remove_graph(name: &str) -> Result<(), WofError> {
-    let store = get_store();
-    let mut guard = store.write().map_err(|_| WofError::Runtime("graph lock poisoned".into()))?;
-    guard.remove(name);
+pub fn remove_graph(interp: &mut Interpreter, name: &str) -> Result<(), WofError> {
+    let key = interp.resource_name(name);
+    interp.state_mut::<Graphs>().0.remove(&key);
     Ok(())
 }
 
 /// Modify a graph in place.
-pub fn with_graph_mut<F, R>(name: &str, f: F) -> Result<R, WofError>
+pub fn with_graph_mut<F, R>(interp: &mut Interpreter, name: &str, f: F) -> Result<R, WofError>
 where
     F: FnOnce(&mut Graph) -> Result<R, WofError>,
 {
-    let store = get_store();
-    let mut guard = store.write().map_err(|_| WofError::Runtime("graph lock poisoned".into()))?;
-    let graph = guard.get_mut(name).ok_or_else(|| {
-        WofError::Runtime(format!("graph: unknown graph '{}'", name))
-    })?;
+    let key = interp.resource_name(name);
+    let graph = interp.state_mut::<Graphs>().0.get_mut(&key).ok_or_else(|| unknown(name))?;
     f(graph)
 }
 
@@ -113,7 +117,7 @@ pub fn register(interp: &mut Interpreter) {
         }
         
         let graph = Graph::new(n as usize, false);
-        set_graph(&name, graph)?;
+        set_graph(interp, &name, graph)?;
         Ok(())
     });
 
@@ -128,7 +132,38 @@ pub fn register(interp: &mut Interpreter) {
         }
         
         let graph = Graph::new(n as usize, true);
-        set_graph(&name, graph)?;
+        set_graph(interp, &name, graph)?;
+        Ok(())
+    });
+
+    // Generate a random undirected graph: each pair of nodes is joined
+    // with probability p (Erdős–Rényi G(n, p))
+    // Stack: num_nodes p name → ()
+    interp.register("graph_random", |interp| {
+        let name = interp.stack_mut().pop()?.as_string()?;
+        let p = interp.stack_mut().pop()?.as_double()?;
+        let n = interp.stack_mut().pop()?.as_integer()?;
+
+        if n < 0 {
+            return Err(WofError::Runtime("graph_random: num_nodes must be >= 0".into()));
+        }
+        if !(0.0..=1.0).contains(&p) {
+            return Err(WofError::Runtime("graph_random: p must be in [0, 1]".into()));
+        }
+
+        let n = n as usize;
+        let mut rng = rand::thread_rng();
+        let mut graph = Graph::new(n, false);
+        for u in 0..n {
+            interp.progress(u, n, "nodes");
+            for v in u + 1..n {
+                if rng.gen_bool(p) {
+                    graph.add_edge(u, v);
+                }
+            }
+        }
+        interp.progress(n, n, "nodes");
+        set_graph(interp, &name, graph)?;
         Ok(())
     });
 
@@ -143,7 +178,7 @@ pub fn register(interp: &mut Interpreter) {
         let v = interp.stack_mut().pop()?.as_integer()? as usize;
         let u = interp.stack_mut().pop()?.as_integer()? as usize;
         
-        with_graph_mut(&name, |g| {
+        with_graph_mut(interp, &name, |g| {
             let n = g.node_count();
             if u >= n || v >= n {
                 return Err(WofError::Runtime("graph_add_edge: node index out of range".into()));
@@ -163,7 +198,7 @@ pub fn register(interp: &mut Interpreter) {
         let name = interp.stack_mut().pop()?.as_string()?;
         let node = interp.stack_mut().pop()?.as_integer()? as usize;
         
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         if node >= graph.node_count() {
             return Err(WofError::Runtime("graph_degree: node index out of range".into()));
         }
@@ -177,7 +212,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → count
     interp.register("graph_nodes", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         interp.stack_mut().push(WofValue::integer(graph.node_count() as i64));
         Ok(())
     });
@@ -186,7 +221,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → count
     interp.register("graph_edges", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         let total: usize = graph.adj.iter().map(|v| v.len()).sum();
         let count = if graph.directed { total } else { total / 2 };
         interp.stack_mut().push(WofValue::integer(count as i64));
@@ -201,7 +236,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → ()
     interp.register("graph_clear", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        remove_graph(&name)?;
+        remove_graph(interp, &name)?;
         Ok(())
     });
 
@@ -209,8 +244,68 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → 1|0
     interp.register("graph_exists?", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        let exists = get_graph(&name).is_ok();
+        let exists = get_graph(interp, &name).is_ok();
         interp.stack_mut().push(WofValue::integer(if exists { 1 } else { 0 }));
         Ok(())
     });
+
+    // Scratch graph for a block: with_graph "name" [n] ⺆ ... ⺘
+    interp.define_resource("graph", open_scratch_graph, |interp, name| remove_graph(interp, name.as_str()?));
+}
+
+/// Create the graph for `with_graph "name" [n]`, refusing a name in use
+/// so the block cannot delete someone else's graph.
+fn open_scratch_graph(interp: &mut Interpreter, args: Vec<WofValue>) -> Result<WofValue, WofError> {
+    let (name, n) = match args.as_slice() {
+        [name] => (name.as_string()?, 0),
+        [name, n] => (name.as_string()?, n.as_integer()?),
+        _ => return Err(WofError::Runtime("expected a name and optional node count".into())),
+    };
+    let n = usize::try_from(n).map_err(|_| WofError::Runtime("num_nodes must be >= 0".into()))?;
+    if get_graph(interp, &name).is_ok() {
+        return Err(WofError::Runtime(format!("graph '{name}' already exists")));
+    }
+    set_graph(interp, &name, Graph::new(n, false))?;
+    Ok(WofValue::string(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scratch_graphs_are_removed_after_the_block() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        interp.register("fail", |_| Err(WofError::Runtime("fail".into())));
+
+        interp.exec_line(r#"with_graph "scratch" 3 ⺆ 0 1 "scratch" graph_add_edge graph_edges ⺘"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1);
+        assert!(get_graph(&interp, "scratch").is_err());
+
+        assert!(interp.exec_line(r#"with_graph "scratch" ⺆ fail ⺘"#).is_err());
+        assert!(get_graph(&interp, "scratch").is_err());
+
+        set_graph(&mut interp, "kept", Graph::new(1, false)).unwrap();
+        assert!(interp.exec_line(r#"with_graph "kept" ⺆ ⺘"#).is_err());
+        assert!(get_graph(&interp, "kept").is_ok());
+        assert!(interp.exec_line(r#"with_graph "neg" -1 ⺆ ⺘"#).is_err());
+        remove_graph(&mut interp, "kept").unwrap();
+    }
+    #[test]
+    fn graphs_are_per_interpreter_and_namespaced() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+
+        interp.exec_line(r#"2 "g" graph_new ns "lib" ⺆ 5 "g" graph_new "::g" graph_nodes ⺘ "g" graph_nodes"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 2);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 2);
+        interp.exec_line(r#""lib::g" graph_nodes ns "lib" ⺆ global ⺆ "g" graph_nodes ⺘ ⺘"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 2);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 5);
+
+        let mut other = Interpreter::new();
+        register(&mut other);
+        assert!(get_graph(&other, "g").is_err());
+    }
 }
@@ -0,0 +1,359 @@
+//! Drawing graphs in the terminal and as SVG.
+//!
+//! | Operation        | Stack Effect   | Description |
+//! |------------------|----------------|-------------|
+//! | `graph_draw`     | ( name -- )    | Print the graph with box-drawing characters |
+//! | `graph_draw_svg` | ( name file -- ) | Write the graph as an SVG image |
+//!
+//! Both use the same force-directed layout, started from a circle so the
+//! result is the same on every run. Directed edges end in an arrow.
+//!
+//! ```text
+//! 4 "sq" graph_new
+//! 0 1 "sq" graph_add_edge  1 2 "sq" graph_add_edge
+//! 2 3 "sq" graph_add_edge  3 0 "sq" graph_add_edge
+//! "sq" graph_draw
+//! "sq" "sq.svg" graph_draw_svg
+//! ```
+
+use std::f64::consts::TAU;
+use std::fmt::Write as _;
+
+use woflang_core::{InterpreterContext, WofError};
+use woflang_runtime::Interpreter;
+
+use super::core::{get_graph, Graph};
+
+/// Largest graph `graph_draw` will put in the terminal.
+const MAX_TEXT_NODES: usize = 40;
+
+/// A node position, both coordinates in `[0, 1]`.
+type Point = (f64, f64);
+
+/// Each edge once: `u < v` pairs for undirected graphs, every arc for
+/// directed ones. Self-loops are left out, as neither renderer draws them.
+fn edges(graph: &Graph) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    for (u, neighbors) in graph.adj.iter().enumerate() {
+        for &v in neighbors {
+            if u != v && (graph.directed || u < v) {
+                out.push((u, v));
+            }
+        }
+    }
+    out
+}
+
+/// Fruchterman–Reingold layout in the unit square.
+fn layout(graph: &Graph) -> Vec<Point> {
+    let n = graph.node_count();
+    let mut pos: Vec<Point> = (0..n)
+        .map(|i| {
+            let a = TAU * i as f64 / n as f64;
+            (0.5 + 0.4 * a.cos(), 0.5 + 0.4 * a.sin())
+        })
+        .collect();
+    if n < 3 {
+        return normalize(pos);
+    }
+
+    let edges = edges(graph);
+    let k = (1.0 / n as f64).sqrt();
+    let iterations = if n <= 500 { 200 } else { 50 };
+    for step in 0..iterations {
+        let mut disp = vec![(0.0, 0.0); n];
+        for i in 0..n {
+            for j in i + 1..n {
+                let (dx, dy) = (pos[i].0 - pos[j].0, pos[i].1 - pos[j].1);
+                let dist = dx.hypot(dy).max(0.01);
+                let f = k * k / dist / dist;
+                disp[i].0 += dx * f;
+                disp[i].1 += dy * f;
+                disp[j].0 -= dx * f;
+                disp[j].1 -= dy * f;
+            }
+        }
+        for &(u, v) in &edges {
+            let (dx, dy) = (pos[u].0 - pos[v].0, pos[u].1 - pos[v].1);
+            let f = dx.hypot(dy) / k;
+            disp[u].0 -= dx * f;
+            disp[u].1 -= dy * f;
+            disp[v].0 += dx * f;
+            disp[v].1 += dy * f;
+        }
+        let temperature = 0.1 * (1.0 - step as f64 / iterations as f64);
+        for (p, d) in pos.iter_mut().zip(&disp) {
+            let len = d.0.hypot(d.1);
+            if len > 0.0 {
+                let moved = len.min(temperature);
+                p.0 = (p.0 + d.0 / len * moved).clamp(0.0, 1.0);
+                p.1 = (p.1 + d.1 / len * moved).clamp(0.0, 1.0);
+            }
+        }
+    }
+    normalize(pos)
+}
+
+/// Stretch positions to fill the unit square on each axis.
+fn normalize(mut pos: Vec<Point>) -> Vec<Point> {
+    let span = |get: fn(&Point) -> f64| {
+        let lo = pos.iter().map(get).fold(f64::INFINITY, f64::min);
+        let hi = pos.iter().map(get).fold(f64::NEG_INFINITY, f64::max);
+        (lo, hi - lo)
+    };
+    let (x0, w) = span(|p| p.0);
+    let (y0, h) = span(|p| p.1);
+    for p in &mut pos {
+        p.0 = if w > 1e-9 { (p.0 - x0) / w } else { 0.5 };
+        p.1 = if h > 1e-9 { (p.1 - y0) / h } else { 0.5 };
+    }
+    pos
+}
+
+/// The line character for one step of an edge.
+const fn stroke(dc: i64, dr: i64) -> char {
+    match (dc, dr) {
+        (0, _) => '│',
+        (_, 0) => '─',
+        _ if (dc > 0) == (dr > 0) => '╲',
+        _ => '╱',
+    }
+}
+
+/// The arrowhead for an edge arriving with the given step.
+const fn arrow(dc: i64, dr: i64) -> char {
+    match (dc.signum(), dr.signum()) {
+        (1, 0) => '→',
+        (-1, 0) => '←',
+        (0, 1) => '↓',
+        (0, -1) => '↑',
+        (1, 1) => '↘',
+        (-1, 1) => '↙',
+        (1, -1) => '↗',
+        _ => '↖',
+    }
+}
+
+/// Cells from `a` to `b` (inclusive), with the step taken to reach each.
+fn line(a: (i64, i64), b: (i64, i64)) -> Vec<((i64, i64), (i64, i64))> {
+    let (dc, dr) = ((b.0 - a.0).abs(), -(b.1 - a.1).abs());
+    let (sc, sr) = ((b.0 - a.0).signum(), (b.1 - a.1).signum());
+    let (mut c, mut r, mut err) = (a.0, a.1, dc + dr);
+    let mut out = Vec::new();
+    while (c, r) != b {
+        let (mut mc, mut mr) = (0, 0);
+        let e2 = 2 * err;
+        if e2 >= dr {
+            err += dr;
+            c += sc;
+            mc = sc;
+        }
+        if e2 <= dc {
+            err += dc;
+            r += sr;
+            mr = sr;
+        }
+        out.push(((c, r), (mc, mr)));
+    }
+    out
+}
+
+/// Draw `graph` on a character grid.
+fn render_text(graph: &Graph) -> String {
+    let n = graph.node_count();
+    if n == 0 {
+        return "(empty graph)\n".to_string();
+    }
+    let rows = (2 * n).clamp(5, 21);
+    let cols = 3 * rows;
+    let mut grid = vec![vec![' '; cols]; rows];
+
+    let pos = layout(graph);
+    let labels: Vec<String> = (0..n).map(|i| format!("({i})")).collect();
+    // Top-left cell of each label, centred on the node and kept on the grid.
+    let origin: Vec<(i64, i64)> = pos
+        .iter()
+        .zip(&labels)
+        .map(|(&(x, y), label)| {
+            let width = label.chars().count();
+            let centre = (x * (cols - 1) as f64).round() as usize;
+            let col = centre.saturating_sub(width / 2).min(cols - width);
+            (col as i64, (y * (rows - 1) as f64).round() as i64)
+        })
+        .collect();
+    let centre = |i: usize| (origin[i].0 + labels[i].chars().count() as i64 / 2, origin[i].1);
+    let on_label = |i: usize, (c, r): (i64, i64)| {
+        r == origin[i].1 && (origin[i].0..origin[i].0 + labels[i].chars().count() as i64).contains(&c)
+    };
+
+    for (u, v) in edges(graph) {
+        let path: Vec<_> = line(centre(u), centre(v))
+            .into_iter()
+            .filter(|&(cell, _)| !on_label(u, cell) && !on_label(v, cell))
+            .collect();
+        for &((c, r), (dc, dr)) in &path {
+            let cell = &mut grid[r as usize][c as usize];
+            let ch = stroke(dc, dr);
+            *cell = match *cell {
+                ' ' => ch,
+                old if old == ch => ch,
+                '╱' | '╲' if matches!(ch, '╱' | '╲') => '╳',
+                _ => '┼',
+            };
+        }
+        if graph.directed {
+            if let Some(&((c, r), (dc, dr))) = path.last() {
+                grid[r as usize][c as usize] = arrow(dc, dr);
+            }
+        }
+    }
+
+    for (label, &(col, row)) in labels.iter().zip(&origin) {
+        for (k, ch) in label.chars().enumerate() {
+            grid[row as usize][col as usize + k] = ch;
+        }
+    }
+
+    let mut out = String::new();
+    for row in grid {
+        let line: String = row.into_iter().collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Draw `graph` as a standalone SVG document.
+fn render_svg(graph: &Graph) -> String {
+    let n = graph.node_count();
+    let size = (85.0 * (n as f64).sqrt()).max(400.0);
+    let radius = if n <= 100 { 10.0 } else { 4.0 };
+    let margin = radius * 3.0;
+    let pos: Vec<Point> = layout(graph)
+        .into_iter()
+        .map(|(x, y)| (margin + x * (size - 2.0 * margin), margin + y * (size - 2.0 * margin)))
+        .collect();
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size:.0}" height="{size:.0}" viewBox="0 0 {size:.0} {size:.0}">"#
+    );
+    if graph.directed {
+        svg.push_str(
+            "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+             markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+             <path d=\"M0,0 L10,5 L0,10 z\" fill=\"#888\"/></marker></defs>\n",
+        );
+    }
+    svg.push_str("<g stroke=\"#888\" stroke-width=\"1.5\">\n");
+    for (u, v) in edges(graph) {
+        let ((x1, y1), (x2, y2)) = (pos[u], pos[v]);
+        let len = (x2 - x1).hypot(y2 - y1).max(1e-9);
+        // Stop at the circle's edge so the arrowhead is visible.
+        let (ex, ey) = (x2 - (x2 - x1) / len * radius, y2 - (y2 - y1) / len * radius);
+        let marker = if graph.directed { r#" marker-end="url(#arrow)""# } else { "" };
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{x1:.1}" y1="{y1:.1}" x2="{ex:.1}" y2="{ey:.1}"{marker}/>"#
+        );
+    }
+    svg.push_str("</g>\n<g fill=\"#4a90d9\" stroke=\"#fff\">\n");
+    for &(x, y) in &pos {
+        let _ = writeln!(svg, r#"<circle cx="{x:.1}" cy="{y:.1}" r="{radius}"/>"#);
+    }
+    svg.push_str("</g>\n");
+    if n <= 200 {
+        svg.push_str(
+            "<g font-family=\"monospace\" font-size=\"10\" text-anchor=\"middle\" fill=\"#fff\">\n",
+        );
+        for (i, &(x, y)) in pos.iter().enumerate() {
+            let _ = writeln!(svg, r#"<text x="{x:.1}" y="{:.1}">{i}</text>"#, y + 3.5);
+        }
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Register graph drawing operations.
+pub fn register(interp: &mut Interpreter) {
+    // Print a graph with box-drawing characters
+    // Stack: name → ()
+    interp.register("graph_draw", |interp| {
+        let name = interp.stack_mut().pop()?.as_string()?;
+        let graph = get_graph(interp, &name)?;
+        if graph.node_count() > MAX_TEXT_NODES {
+            return Err(WofError::Runtime(format!(
+                "graph_draw: {} has {} nodes, too many for the terminal (max {MAX_TEXT_NODES}); \
+                 use graph_draw_svg",
+                name,
+                graph.node_count()
+            )));
+        }
+        write!(interp.out(), "{}", render_text(&graph));
+        Ok(())
+    });
+
+    // Write a graph as an SVG image
+    // Stack: name file → ()
+    interp.register("graph_draw_svg", |interp| {
+        let path = interp.stack_mut().pop()?.as_string()?;
+        let name = interp.stack_mut().pop()?.as_string()?;
+        let resolved = interp.sandbox().check_write(&path)?;
+        let graph = get_graph(interp, &name)?;
+        std::fs::write(resolved, render_svg(&graph))
+            .map_err(|e| WofError::Runtime(format!("graph_draw_svg: {path}: {e}")))?;
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(n: usize, directed: bool) -> Graph {
+        let mut g = Graph::new(n, directed);
+        for i in 0..n {
+            g.add_edge(i, (i + 1) % n);
+        }
+        g
+    }
+
+    #[test]
+    fn layout_is_deterministic_and_spread_out() {
+        let g = cycle(6, false);
+        let pos = layout(&g);
+        assert_eq!(pos, layout(&g));
+        for (i, a) in pos.iter().enumerate() {
+            assert!((0.0..=1.0).contains(&a.0) && (0.0..=1.0).contains(&a.1));
+            for b in &pos[i + 1..] {
+                assert!((a.0 - b.0).hypot(a.1 - b.1) > 0.1);
+            }
+        }
+    }
+
+    #[test]
+    fn text_shows_every_node_and_edge() {
+        let text = render_text(&cycle(4, false));
+        for i in 0..4 {
+            assert!(text.contains(&format!("({i})")), "{text}");
+        }
+        assert!(text.chars().any(|c| "─│╱╲┼╳".contains(c)), "{text}");
+        assert!(!text.chars().any(|c| "→←↑↓↗↘↙↖".contains(c)));
+
+        let directed = render_text(&cycle(3, true));
+        assert_eq!(directed.chars().filter(|c| "→←↑↓↗↘↙↖".contains(*c)).count(), 3, "{directed}");
+        assert_eq!(render_text(&Graph::new(0, false)), "(empty graph)\n");
+        assert_eq!(render_text(&Graph::new(1, false)).trim(), "(0)");
+    }
+
+    #[test]
+    fn svg_has_a_circle_per_node_and_a_line_per_edge() {
+        let svg = render_svg(&cycle(5, true));
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<circle ").count(), 5);
+        assert_eq!(svg.matches("<line ").count(), 5);
+        assert_eq!(svg.matches("marker-end").count(), 5);
+    }
+}
@@ -0,0 +1,113 @@
+//! Converting between graphs and adjacency matrices.
+//!
+//! | Operation         | Stack Effect      | Description |
+//! |-------------------|-------------------|-------------|
+//! | `graph_to_matrix` | ( name -- A )     | Push the adjacency matrix |
+//! | `matrix_to_graph` | ( A name -- )     | Store a graph with an edge per nonzero entry |
+//!
+//! Matrices use the stack layout of the linear algebra ops
+//! (`a11 .. ann n n`), so spectral methods compose directly:
+//!
+//! ```text
+//! "g" graph_to_matrix 100 mat_power_iter   # dominant eigenvalue last
+//! ```
+//!
+//! Entry `(i, j)` counts the edges from `i` to `j`. A symmetric matrix
+//! becomes an undirected graph and any other square matrix a directed
+//! one; entries are treated as present or absent, not as weights.
+
+use woflang_core::{InterpreterContext, WofError};
+use woflang_runtime::Interpreter;
+
+use super::core::{get_graph, set_graph, Graph};
+use crate::math::linalg::Matrix;
+
+/// The adjacency matrix of `graph`.
+fn adjacency(graph: &Graph) -> Matrix {
+    let n = graph.node_count();
+    let mut m = Matrix::zeros(n, n);
+    for (u, neighbors) in graph.adj.iter().enumerate() {
+        for &v in neighbors {
+            m.set(u, v, m.get(u, v) + 1.0);
+        }
+    }
+    m
+}
+
+/// The graph with an edge wherever `m` is nonzero.
+fn from_adjacency(m: &Matrix) -> Graph {
+    let n = m.rows;
+    let directed = m != &m.transpose();
+    let mut graph = Graph::new(n, directed);
+    for u in 0..n {
+        let from = if directed { 0 } else { u };
+        for v in from..n {
+            if m.get(u, v) != 0.0 {
+                graph.add_edge(u, v);
+            }
+        }
+    }
+    graph
+}
+
+/// Register graph/matrix conversion operations.
+pub fn register(interp: &mut Interpreter) {
+    // Push a graph's adjacency matrix
+    // Stack: name → a11 .. ann n n
+    interp.register("graph_to_matrix", |interp| {
+        let name = interp.stack_mut().pop()?.as_string()?;
+        adjacency(&get_graph(interp, &name)?).push(interp);
+        Ok(())
+    });
+
+    // Store the graph of an adjacency matrix
+    // Stack: a11 .. ann n n name → ()
+    interp.register("matrix_to_graph", |interp| {
+        let name = interp.stack_mut().pop()?.as_string()?;
+        let m = Matrix::pop_square(interp, "matrix_to_graph")?;
+        if m.data.iter().any(|x| !x.is_finite()) {
+            return Err(WofError::InvalidArgument(
+                "matrix_to_graph: entries must be finite".into(),
+            ));
+        }
+        set_graph(interp, &name, from_adjacency(&m))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjacency_round_trips() {
+        let mut path = Graph::new(3, false);
+        path.add_edge(0, 1);
+        path.add_edge(1, 2);
+        let m = adjacency(&path);
+        assert_eq!(m.data, [0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0]);
+        let back = from_adjacency(&m);
+        assert!(!back.directed);
+        assert_eq!(back.adj, path.adj);
+
+        let arc = Matrix { rows: 2, cols: 2, data: vec![0.0, 2.5, 0.0, 0.0] };
+        let g = from_adjacency(&arc);
+        assert!(g.directed);
+        assert_eq!(g.adj, [vec![1], vec![]]);
+    }
+
+    #[test]
+    fn spectral_radius_of_a_complete_graph() {
+        let mut interp = Interpreter::new();
+        crate::math::linalg::register(&mut interp);
+        register(&mut interp);
+        interp
+            .exec_line(r#"0 1 1 1  1 0 1 1  1 1 0 1  1 1 1 0  4 4 "k4" matrix_to_graph"#)
+            .unwrap();
+        interp.exec_line(r#""k4" graph_to_matrix 100 mat_power_iter"#).unwrap();
+        let lambda = interp.stack_mut().pop_double().unwrap();
+        assert!((lambda - 3.0).abs() < 1e-9);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 4);
+
+        assert!(interp.exec_line(r#"1 2 3 4 5 6 2 3 "bad" matrix_to_graph"#).is_err());
+    }
+}
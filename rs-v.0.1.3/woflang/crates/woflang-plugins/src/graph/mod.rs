@@ -1,12 +1,18 @@
 //! Graph theory operations for Woflang.
 //!
 //! Provides graph creation, manipulation, search algorithms (BFS/DFS),
-//! shortest path (Dijkstra), and graph coloring.
+//! shortest path (Dijkstra), graph coloring, PageRank and centrality,
+//! adjacency matrices, drawing in the terminal or as SVG, and saving to
+//! disk.
 
 mod core;
 mod search;
 mod weighted;
 mod coloring;
+mod centrality;
+mod draw;
+mod matrix;
+mod persist;
 
 use woflang_runtime::Interpreter;
 
@@ -16,4 +22,8 @@ pub fn register(interp: &mut Interpreter) {
     search::register(interp);
     weighted::register(interp);
     coloring::register(interp);
+    centrality::register(interp);
+    draw::register(interp);
+    matrix::register(interp);
+    persist::register(interp);
 }
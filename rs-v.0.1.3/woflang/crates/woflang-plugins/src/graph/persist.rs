@@ -0,0 +1,173 @@
+//! Saving named graphs to disk and loading them back.
+//!
+//! | Operation    | Stack Effect      | Description |
+//! |--------------|-------------------|-------------|
+//! | `graph_save` | ( name file -- )  | Write a graph to a JSON file |
+//! | `graph_load` | ( name file -- )  | Read a graph from a file, storing it as `name` |
+//!
+//! The file is a JSON edge list, each edge once:
+//!
+//! ```text
+//! {"format":"woflang-graph","version":1,"directed":false,"nodes":3,"edges":[[0,1],[1,2]]}
+//! ```
+
+use serde::{Deserialize, Serialize};
+use woflang_core::{InterpreterContext, Result, WofError};
+use woflang_runtime::Interpreter;
+
+use super::core::{get_graph, set_graph, Graph};
+
+/// Value of the `format` field.
+const FORMAT: &str = "woflang-graph";
+
+/// Newest file version this build reads and the one it writes.
+const VERSION: u32 = 1;
+
+/// On-disk form of a [`Graph`].
+#[derive(Debug, Serialize, Deserialize)]
+struct GraphFile {
+    format: String,
+    version: u32,
+    directed: bool,
+    nodes: usize,
+    edges: Vec<(usize, usize)>,
+}
+
+impl From<&Graph> for GraphFile {
+    fn from(graph: &Graph) -> Self {
+        let mut edges = Vec::new();
+        for (u, neighbors) in graph.adj.iter().enumerate() {
+            for &v in neighbors {
+                if graph.directed || u <= v {
+                    edges.push((u, v));
+                }
+            }
+        }
+        Self {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            directed: graph.directed,
+            nodes: graph.node_count(),
+            edges,
+        }
+    }
+}
+
+impl GraphFile {
+    /// Rebuild the graph, rejecting files this build cannot read.
+    fn into_graph(self, path: &str) -> Result<Graph> {
+        let bad = |why: String| WofError::Runtime(format!("graph_load: {path}: {why}"));
+        if self.format != FORMAT {
+            return Err(bad(format!("not a graph file (format '{}')", self.format)));
+        }
+        if self.version > VERSION {
+            return Err(bad(format!("file version {} is newer than {VERSION}", self.version)));
+        }
+        let mut graph = Graph::new(self.nodes, self.directed);
+        for (u, v) in self.edges {
+            if u >= self.nodes || v >= self.nodes {
+                return Err(bad(format!("edge {u}-{v} is outside {} nodes", self.nodes)));
+            }
+            graph.add_edge(u, v);
+        }
+        Ok(graph)
+    }
+}
+
+/// Register graph persistence operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: name file → ()
+    interp.register("graph_save", |interp| {
+        let path = interp.stack_mut().pop()?.as_string()?;
+        let name = interp.stack_mut().pop()?.as_string()?;
+        let resolved = interp.sandbox().check_write(&path)?;
+        let graph = get_graph(interp, &name)?;
+        let json = serde_json::to_string(&GraphFile::from(&graph))
+            .map_err(|e| WofError::Runtime(format!("graph_save: {e}")))?;
+        std::fs::write(resolved, json)
+            .map_err(|e| WofError::Runtime(format!("graph_save: {path}: {e}")))
+    });
+
+    // Stack: name file → ()
+    interp.register("graph_load", |interp| {
+        let path = interp.stack_mut().pop()?.as_string()?;
+        let name = interp.stack_mut().pop()?.as_string()?;
+        let resolved = interp.sandbox().check_read(&path)?;
+        let text = std::fs::read_to_string(resolved)
+            .map_err(|e| WofError::Runtime(format!("graph_load: {path}: {e}")))?;
+        let file: GraphFile = serde_json::from_str(&text)
+            .map_err(|e| WofError::Runtime(format!("graph_load: {path}: {e}")))?;
+        set_graph(interp, &name, file.into_graph(&path)?)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edge_lists_round_trip() {
+        for directed in [false, true] {
+            let mut g = Graph::new(4, directed);
+            for (u, v) in [(0, 1), (1, 2), (2, 2), (3, 0), (0, 1)] {
+                g.add_edge(u, v);
+            }
+            let file = GraphFile::from(&g);
+            assert_eq!(file.edges.len(), 5);
+            let back = file.into_graph("test").unwrap();
+            assert_eq!(back.directed, directed);
+            let sorted = |g: &Graph| {
+                let mut adj = g.adj.clone();
+                for n in &mut adj {
+                    n.sort_unstable();
+                }
+                adj
+            };
+            assert_eq!(sorted(&back), sorted(&g));
+        }
+    }
+
+    #[test]
+    fn ops_save_and_load_through_a_file() {
+        let path = std::env::temp_dir().join(format!("woflang-graph-{}.json", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+        let mut interp = Interpreter::new();
+        super::super::core::register(&mut interp);
+        register(&mut interp);
+
+        interp
+            .exec_line(&format!(
+                r#"3 "tri" digraph_new 0 1 "tri" graph_add_edge 1 2 "tri" graph_add_edge "tri" "{path}" graph_save"#
+            ))
+            .unwrap();
+        interp.exec_line(&format!(r#""copy" "{path}" graph_load"#)).unwrap();
+        let copy = get_graph(&interp, "copy").unwrap();
+        assert!(copy.directed);
+        assert_eq!(copy.adj, [vec![1], vec![2], vec![]]);
+
+        std::fs::write(&path, r#"{"format":"woflang-graph","version":1,"directed":false,"nodes":2,"edges":[[0,5]]}"#)
+            .unwrap();
+        let err = interp.exec_line(&format!(r#""bad" "{path}" graph_load"#)).unwrap_err();
+        assert!(err.to_string().contains("outside 2 nodes"), "{err}");
+        std::fs::write(&path, "{}").unwrap();
+        assert!(interp.exec_line(&format!(r#""bad" "{path}" graph_load"#)).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn read_only_sandbox_refuses_saves() {
+        let path = std::env::temp_dir().join(format!("woflang-graph-ro-{}.json", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+        let mut interp = Interpreter::new();
+        super::super::core::register(&mut interp);
+        register(&mut interp);
+        let mut sandbox = woflang_runtime::Sandbox::default();
+        sandbox.read_only = true;
+        interp.set_sandbox(sandbox);
+
+        interp.exec_line(r#"2 "pair" graph_new"#).unwrap();
+        let err = interp.exec_line(&format!(r#""pair" "{path}" graph_save"#)).unwrap_err();
+        assert!(err.to_string().contains("read-only"), "{err}");
+        assert!(!std::path::Path::new(&path).exists());
+    }
+}
@@ -152,7 +152,7 @@ pub fn register(interp: &mut Interpreter) {
         let name = interp.stack_mut().pop()?.as_string()?;
         let start = interp.stack_mut().pop()?.as_integer()? as usize;
         
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         if start >= graph.node_count() {
             return Err(WofError::Runtime("graph_bfs_reach: start index out of range".into()));
         }
@@ -169,7 +169,7 @@ pub fn register(interp: &mut Interpreter) {
         let start = interp.stack_mut().pop()?.as_integer()? as usize;
         let dst = interp.stack_mut().pop()?.as_integer()? as usize;
         
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         let n = graph.node_count();
         if start >= n || dst >= n {
             return Err(WofError::Runtime("graph_path_exists: node index out of range".into()));
@@ -187,7 +187,7 @@ pub fn register(interp: &mut Interpreter) {
         let start = interp.stack_mut().pop()?.as_integer()? as usize;
         let dst = interp.stack_mut().pop()?.as_integer()? as usize;
         
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         let n = graph.node_count();
         if start >= n || dst >= n {
             return Err(WofError::Runtime("graph_shortest_path_len: node index out of range".into()));
@@ -208,7 +208,7 @@ pub fn register(interp: &mut Interpreter) {
         let name = interp.stack_mut().pop()?.as_string()?;
         let start = interp.stack_mut().pop()?.as_integer()? as usize;
         
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         if start >= graph.node_count() {
             return Err(WofError::Runtime("graph_dfs_reach: start index out of range".into()));
         }
@@ -226,7 +226,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → 1|0
     interp.register("graph_connected?", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         let connected = is_connected(&graph);
         interp.stack_mut().push(WofValue::integer(if connected { 1 } else { 0 }));
         Ok(())
@@ -236,7 +236,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → count
     interp.register("graph_components", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        let graph = get_graph(&name)?;
+        let graph = get_graph(interp, &name)?;
         let components = connected_components(&graph);
         interp.stack_mut().push(WofValue::integer(components.len() as i64));
         Ok(())
@@ -4,7 +4,6 @@
 
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
-use std::sync::{Arc, RwLock};
 use woflang_core::{WofError, WofValue, InterpreterContext};
 use woflang_runtime::Interpreter;
 
@@ -124,39 +123,32 @@ fn reconstruct_path(parent: &[Option<usize>], start: usize, end: usize) -> Vec<u
     path
 }
 
-/// Global weighted graph storage.
-type WGraphStore = Arc<RwLock<HashMap<String, WeightedGraph>>>;
+/// The weighted graphs of one interpreter, by
+/// [resource name](Interpreter::resource_name).
+#[derive(Debug, Default)]
+struct WeightedGraphs(HashMap<String, WeightedGraph>);
 
-fn get_store() -> WGraphStore {
-    use std::sync::OnceLock;
-    static STORE: OnceLock<WGraphStore> = OnceLock::new();
-    STORE.get_or_init(|| Arc::new(RwLock::new(HashMap::new()))).clone()
+fn unknown(name: &str) -> WofError {
+    WofError::Runtime(format!("weighted graph: unknown graph '{name}'"))
 }
 
-fn get_wgraph(name: &str) -> Result<WeightedGraph, WofError> {
-    let store = get_store();
-    let guard = store.read().map_err(|_| WofError::Runtime("wgraph lock poisoned".into()))?;
-    guard.get(name).cloned().ok_or_else(|| {
-        WofError::Runtime(format!("weighted graph: unknown graph '{}'", name))
-    })
+fn get_wgraph(interp: &Interpreter, name: &str) -> Result<WeightedGraph, WofError> {
+    let key = interp.resource_name(name);
+    interp.state::<WeightedGraphs>().and_then(|g| g.0.get(&key)).cloned().ok_or_else(|| unknown(name))
 }
 
-fn set_wgraph(name: &str, graph: WeightedGraph) -> Result<(), WofError> {
-    let store = get_store();
-    let mut guard = store.write().map_err(|_| WofError::Runtime("wgraph lock poisoned".into()))?;
-    guard.insert(name.to_string(), graph);
+fn set_wgraph(interp: &mut Interpreter, name: &str, graph: WeightedGraph) -> Result<(), WofError> {
+    let key = interp.resource_name(name);
+    interp.state_mut::<WeightedGraphs>().0.insert(key, graph);
     Ok(())
 }
 
-fn with_wgraph_mut<F, R>(name: &str, f: F) -> Result<R, WofError>
+fn with_wgraph_mut<F, R>(interp: &mut Interpreter, name: &str, f: F) -> Result<R, WofError>
 where
     F: FnOnce(&mut WeightedGraph) -> Result<R, WofError>,
 {
-    let store = get_store();
-    let mut guard = store.write().map_err(|_| WofError::Runtime("wgraph lock poisoned".into()))?;
-    let graph = guard.get_mut(name).ok_or_else(|| {
-        WofError::Runtime(format!("weighted graph: unknown graph '{}'", name))
-    })?;
+    let key = interp.resource_name(name);
+    let graph = interp.state_mut::<WeightedGraphs>().0.get_mut(&key).ok_or_else(|| unknown(name))?;
     f(graph)
 }
 
@@ -177,7 +169,7 @@ pub fn register(interp: &mut Interpreter) {
         }
         
         let graph = WeightedGraph::new(n as usize, false);
-        set_wgraph(&name, graph)?;
+        set_wgraph(interp, &name, graph)?;
         Ok(())
     });
 
@@ -192,7 +184,7 @@ pub fn register(interp: &mut Interpreter) {
         }
         
         let graph = WeightedGraph::new(n as usize, true);
-        set_wgraph(&name, graph)?;
+        set_wgraph(interp, &name, graph)?;
         Ok(())
     });
 
@@ -212,7 +204,7 @@ pub fn register(interp: &mut Interpreter) {
             return Err(WofError::Runtime("graph_w_add_edge: negative weights not allowed for Dijkstra".into()));
         }
         
-        with_wgraph_mut(&name, |g| {
+        with_wgraph_mut(interp, &name, |g| {
             let n = g.node_count();
             if u >= n || v >= n {
                 return Err(WofError::Runtime("graph_w_add_edge: node index out of range".into()));
@@ -233,7 +225,7 @@ pub fn register(interp: &mut Interpreter) {
         let start = interp.stack_mut().pop()?.as_integer()? as usize;
         let dst = interp.stack_mut().pop()?.as_integer()? as usize;
         
-        let graph = get_wgraph(&name)?;
+        let graph = get_wgraph(interp, &name)?;
         let n = graph.node_count();
         
         if n == 0 {
@@ -275,7 +267,7 @@ pub fn register(interp: &mut Interpreter) {
         let start = interp.stack_mut().pop()?.as_integer()? as usize;
         let dst = interp.stack_mut().pop()?.as_integer()? as usize;
         
-        let graph = get_wgraph(&name)?;
+        let graph = get_wgraph(interp, &name)?;
         let n = graph.node_count();
         
         if start >= n || dst >= n {
@@ -297,9 +289,8 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: name → ()
     interp.register("graph_w_clear", |interp| {
         let name = interp.stack_mut().pop()?.as_string()?;
-        let store = get_store();
-        let mut guard = store.write().map_err(|_| WofError::Runtime("wgraph lock poisoned".into()))?;
-        guard.remove(&name);
+        let key = interp.resource_name(&name);
+        interp.state_mut::<WeightedGraphs>().0.remove(&key);
         Ok(())
     });
 }
@@ -64,60 +64,124 @@ pub mod neural_chess;
 #[cfg(feature = "analog")]
 pub mod analog;
 
-pub mod data;
-
-use woflang_runtime::Interpreter;
-use woflang_core::InterpreterContext;
-
-/// Register all enabled plugins with the interpreter.
-pub fn register_all(interp: &mut Interpreter) {
-    #[cfg(feature = "math")]
-    math::register(interp);
-
-    #[cfg(feature = "util")]
-    util::register(interp);
-
-    #[cfg(feature = "crypto")]
-    crypto::register(interp);
-
-    #[cfg(feature = "logic")]
-    logic::register(interp);
-
-    #[cfg(feature = "graph")]
-    graph::register(interp);
-
-    #[cfg(feature = "sigils")]
-    sigils::register(interp);
+#[cfg(feature = "strings")]
+pub mod strings;
 
-    #[cfg(feature = "language")]
-    language::register(interp);
+#[cfg(feature = "table")]
+pub mod table;
 
-    #[cfg(feature = "arts")]
-    arts::register(interp);
-
-    #[cfg(feature = "science")]
-    science::register(interp);
+pub mod data;
 
-    #[cfg(feature = "games")]
-    games::register(interp);
+#[cfg(any(feature = "math", feature = "analog"))]
+mod braille;
 
-    #[cfg(feature = "solver")]
-    solver::register(interp);
+use std::collections::HashMap;
+use woflang_runtime::{Interpreter, OpMeta};
+use woflang_core::InterpreterContext;
 
-    #[cfg(feature = "metaphysics")]
-    metaphysics::register(interp);
+/// A plugin's name and the function that registers its ops.
+pub type Module = (&'static str, fn(&mut Interpreter));
+
+/// The enabled plugins, in registration order.
+#[must_use]
+pub fn modules() -> Vec<Module> {
+    vec![
+        #[cfg(feature = "math")]
+        ("math", math::register),
+        #[cfg(feature = "util")]
+        ("util", util::register),
+        #[cfg(feature = "crypto")]
+        ("crypto", crypto::register),
+        #[cfg(feature = "logic")]
+        ("logic", logic::register),
+        #[cfg(feature = "graph")]
+        ("graph", graph::register),
+        #[cfg(feature = "sigils")]
+        ("sigils", sigils::register),
+        #[cfg(feature = "language")]
+        ("language", language::register),
+        #[cfg(feature = "arts")]
+        ("arts", arts::register),
+        #[cfg(feature = "science")]
+        ("science", science::register),
+        #[cfg(feature = "games")]
+        ("games", games::register),
+        #[cfg(feature = "solver")]
+        ("solver", solver::register),
+        #[cfg(feature = "metaphysics")]
+        ("metaphysics", metaphysics::register),
+        #[cfg(feature = "quantum")]
+        ("quantum", quantum::register),
+        #[cfg(feature = "markov")]
+        ("markov", markov::register),
+        #[cfg(feature = "neural_chess")]
+        ("neural_chess", neural_chess::register),
+        #[cfg(feature = "analog")]
+        ("analog", analog::register),
+        #[cfg(feature = "strings")]
+        ("strings", strings::register),
+        #[cfg(feature = "table")]
+        ("table", table::register),
+    ]
+}
 
-    #[cfg(feature = "quantum")]
-    quantum::register(interp);
+/// Register all enabled plugins with the interpreter.
+pub fn register_all(interp: &mut Interpreter) {
+    let mut known = described(interp);
+    for (plugin, register) in modules() {
+        register_module(interp, &mut known, plugin, register);
+    }
+}
 
-    #[cfg(feature = "markov")]
-    markov::register(interp);
+/// Register the ops of all enabled plugins without tagging them with
+/// metadata, for a caller that restores the metadata
+/// [`register_all`] would attach from a saved copy.
+pub fn register_handlers(interp: &mut Interpreter) {
+    for (_, register) in modules() {
+        register(interp);
+    }
+}
 
-    #[cfg(feature = "neural_chess")]
-    neural_chess::register(interp);
+/// Every described op and its metadata.
+fn described(interp: &Interpreter) -> HashMap<String, OpMeta> {
+    interp
+        .registry()
+        .described()
+        .map(|(name, meta)| (name.to_string(), meta.clone()))
+        .collect()
+}
 
-    #[cfg(feature = "analog")]
-    analog::register(interp);
+/// Run a plugin's `register` and give every op it left without metadata
+/// an entry attributing it to this crate. An op that replaces a documented
+/// one keeps that description, since plugins re-implement the same op;
+/// anything else is filed under the plugin's name.
+///
+/// `known` holds the metadata described before the plugin ran. Plugins
+/// never describe their own ops, so keeping it current only takes the
+/// entries tagged here, and registering every plugin copies the
+/// registry's metadata once rather than once per plugin.
+fn register_module(
+    interp: &mut Interpreter,
+    known: &mut HashMap<String, OpMeta>,
+    plugin: &str,
+    register: fn(&mut Interpreter),
+) {
+    register(interp);
+    let untagged: Vec<String> = interp
+        .registry()
+        .names()
+        .filter(|name| interp.registry().meta(name).is_none())
+        .map(str::to_string)
+        .collect();
+    for name in untagged {
+        let meta = known
+            .get(&name)
+            .cloned()
+            .unwrap_or_else(|| OpMeta::new(plugin, "", ""))
+            .with_source(env!("CARGO_PKG_NAME"));
+        known.insert(name.clone(), meta.clone());
+        interp.describe(name, meta);
+    }
 }
 
 /// Helper macro for registering a unary numeric operation.
@@ -0,0 +1,248 @@
+//! Finite state machines.
+//!
+//! | Operation   | Stack Effect                | Description |
+//! |-------------|-----------------------------|-------------|
+//! | `fsm_new`   | ( name -- )                 | Create a machine and make it current |
+//! | `fsm_on`    | ( from event to -- )        | Add a transition to the current machine |
+//! | `fsm_state` | ( name -- state )           | Push the machine's state |
+//! | `fsm_fire`  | ( name event -- state )     | Take a transition, push the new state |
+//! | `fsm_reset` | ( name -- )                 | Return to the initial state |
+//! | `fsm_show`  | ( name -- )                 | Print the transition table and diagram |
+//!
+//! The first `from` state given to `fsm_on` is the initial state. Firing
+//! an event with no transition from the current state is an error, which
+//! keeps typos in game or protocol logic from passing silently.
+//!
+//! ```text
+//! "door" fsm_new
+//! "closed" "open" "opened" fsm_on
+//! "opened" "close" "closed" fsm_on
+//! "door" "open" fsm_fire      # "opened"
+//! ```
+//!
+//! Quote state and event names that are also op names.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// One transition.
+#[derive(Debug, Clone)]
+struct Transition {
+    from: String,
+    event: String,
+    to: String,
+}
+
+/// A state machine.
+#[derive(Debug, Clone, Default)]
+struct Machine {
+    /// Transitions in definition order.
+    transitions: Vec<Transition>,
+    initial: Option<String>,
+    state: Option<String>,
+}
+
+impl Machine {
+    /// Add or replace the transition for `(from, event)`.
+    fn on(&mut self, from: String, event: String, to: String) {
+        if self.initial.is_none() {
+            self.initial = Some(from.clone());
+            self.state = Some(from.clone());
+        }
+        if let Some(t) = self.transitions.iter_mut().find(|t| t.from == from && t.event == event) {
+            t.to = to;
+        } else {
+            self.transitions.push(Transition { from, event, to });
+        }
+    }
+
+    fn fire(&mut self, event: &str) -> Option<&str> {
+        let state = self.state.as_deref()?;
+        let to = self.transitions.iter().find(|t| t.from == state && t.event == event)?.to.clone();
+        self.state = Some(to);
+        self.state.as_deref()
+    }
+
+    /// States in order of first appearance.
+    fn states(&self) -> Vec<&str> {
+        let mut states: Vec<&str> = Vec::new();
+        for t in &self.transitions {
+            for s in [t.from.as_str(), t.to.as_str()] {
+                if !states.contains(&s) {
+                    states.push(s);
+                }
+            }
+        }
+        states
+    }
+
+    /// Render a transition table followed by a state diagram.
+    fn render(&self, name: &str) -> String {
+        let mut out = format!("fsm {name}");
+        if let Some(state) = &self.state {
+            let _ = write!(out, " (state: {state})");
+        }
+        out.push('\n');
+        if self.transitions.is_empty() {
+            out.push_str("  (no transitions)\n");
+            return out;
+        }
+
+        let width = |f: fn(&Transition) -> &str, title: &str| {
+            self.transitions.iter().map(|t| f(t).chars().count()).max().unwrap_or(0).max(title.len())
+        };
+        let (wf, we) = (width(|t| &t.from, "from"), width(|t| &t.event, "event"));
+        let _ = writeln!(out, "  {:wf$}  {:we$}  to", "from", "event");
+        for t in &self.transitions {
+            let _ = writeln!(out, "  {:wf$}  {:we$}  {}", t.from, t.event, t.to);
+        }
+
+        out.push('\n');
+        for state in self.states() {
+            let marker = match (&self.state, &self.initial) {
+                (Some(s), _) if s == state => "●",
+                (_, Some(i)) if i == state => "○",
+                _ => " ",
+            };
+            let edges: Vec<String> = self
+                .transitions
+                .iter()
+                .filter(|t| t.from == state)
+                .map(|t| format!("--{}--> {}", t.event, t.to))
+                .collect();
+            if edges.is_empty() {
+                let _ = writeln!(out, "  {marker} [{state}]");
+            } else {
+                let _ = writeln!(out, "  {marker} [{state}] {}", edges.join(", "));
+            }
+        }
+        out
+    }
+}
+
+/// All machines of one interpreter, by
+/// [resource name](Interpreter::resource_name), and the one `fsm_on`
+/// edits.
+#[derive(Debug, Default)]
+struct Machines {
+    machines: HashMap<String, Machine>,
+    current: Option<String>,
+}
+
+fn machine<'a>(interp: &'a mut Interpreter, op: &str, name: &str) -> Result<&'a mut Machine> {
+    let key = interp.resource_name(name);
+    interp
+        .state_mut::<Machines>()
+        .machines
+        .get_mut(&key)
+        .ok_or_else(|| WofError::runtime(format!("{op}: no state machine named '{name}'")))
+}
+
+/// Register the state machine operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("fsm_new", |interp| {
+        let name = interp.stack_mut().pop_string()?;
+        let key = interp.resource_name(&name);
+        let machines = interp.state_mut::<Machines>();
+        machines.machines.insert(key.clone(), Machine::default());
+        machines.current = Some(key);
+        Ok(())
+    });
+
+    interp.register("fsm_on", |interp| {
+        let to = interp.stack_mut().pop_string()?;
+        let event = interp.stack_mut().pop_string()?;
+        let from = interp.stack_mut().pop_string()?;
+        let machines = interp.state_mut::<Machines>();
+        let current = machines
+            .current
+            .as_ref()
+            .and_then(|name| machines.machines.get_mut(name))
+            .ok_or_else(|| WofError::runtime("fsm_on: no current state machine (use fsm_new)"))?;
+        current.on(from, event, to);
+        Ok(())
+    });
+
+    interp.register("fsm_state", |interp| {
+        let name = interp.stack_mut().pop_string()?;
+        let state = machine(interp, "fsm_state", &name)?.state.clone();
+        interp.push(state.map_or_else(WofValue::nil, WofValue::string));
+        Ok(())
+    });
+
+    interp.register("fsm_fire", |interp| {
+        let event = interp.stack_mut().pop_string()?;
+        let name = interp.stack_mut().pop_string()?;
+        let m = machine(interp, "fsm_fire", &name)?;
+        let from = m.state.clone().unwrap_or_default();
+        let to = m.fire(&event).map(str::to_string).ok_or_else(|| {
+            WofError::runtime(format!(
+                "fsm_fire: '{name}' has no transition on '{event}' from '{from}'"
+            ))
+        })?;
+        interp.push(WofValue::string(to));
+        Ok(())
+    });
+
+    interp.register("fsm_reset", |interp| {
+        let name = interp.stack_mut().pop_string()?;
+        let m = machine(interp, "fsm_reset", &name)?;
+        m.state = m.initial.clone();
+        Ok(())
+    });
+
+    interp.register("fsm_show", |interp| {
+        let name = interp.stack_mut().pop_string()?;
+        write!(interp.out(), "{}", machine(interp, "fsm_show", &name)?.render(&name));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn door() -> Machine {
+        let mut m = Machine::default();
+        m.on("closed".into(), "open".into(), "opened".into());
+        m.on("opened".into(), "close".into(), "closed".into());
+        m.on("closed".into(), "lock".into(), "locked".into());
+        m
+    }
+
+    #[test]
+    fn fires_and_renders() {
+        let mut m = door();
+        assert_eq!(m.fire("open"), Some("opened"));
+        assert_eq!(m.fire("lock"), None);
+        assert_eq!(m.state.as_deref(), Some("opened"));
+
+        let text = m.render("door");
+        assert!(text.starts_with("fsm door (state: opened)\n"));
+        assert!(text.contains("  closed  lock   locked"));
+        assert!(text.contains("○ [closed] --open--> opened, --lock--> locked"));
+        assert!(text.contains("● [opened] --close--> closed"));
+        assert!(text.contains("  [locked]"));
+    }
+
+    #[test]
+    fn fsm_ops_drive_named_machines() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+
+        interp
+            .exec_line(r#""door" fsm_new "closed" "open" "opened" fsm_on "opened" "close" "closed" fsm_on"#)
+            .unwrap();
+        interp.exec_line(r#""door" "open" fsm_fire "door" fsm_state"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "opened");
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "opened");
+
+        assert!(interp.exec_line(r#""door" "open" fsm_fire"#).is_err());
+        interp.exec_line(r#""door" fsm_reset "door" fsm_state"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "closed");
+        assert!(interp.exec_line(r#""window" fsm_state"#).is_err());
+    }
+}
@@ -21,9 +21,14 @@
 //! ### Category Theory
 //! - `cat_obj`, `cat_mor`, `cat_comp` - Define categories
 //! - `cat_hom`, `cat_show`, `cat_clear` - Query and manage
+//!
+//! ### State Machines
+//! - `fsm_new`, `fsm_on` - Define a machine and its transitions
+//! - `fsm_fire`, `fsm_state`, `fsm_reset`, `fsm_show` - Run and inspect
+
+mod fsm;
 
 use std::collections::HashMap;
-use std::sync::{Mutex, OnceLock};
 use woflang_core::{WofValue, InterpreterContext, WofType};
 use woflang_runtime::Interpreter;
 
@@ -119,9 +124,15 @@ impl CategoryState {
     }
 }
 
-fn category_state() -> &'static Mutex<CategoryState> {
-    static STATE: OnceLock<Mutex<CategoryState>> = OnceLock::new();
-    STATE.get_or_init(|| Mutex::new(CategoryState::new()))
+/// The categories of one interpreter, one per
+/// [namespace](Interpreter::namespace).
+#[derive(Default)]
+struct Categories(HashMap<String, CategoryState>);
+
+/// The category of the current namespace.
+fn category(interp: &mut Interpreter) -> &mut CategoryState {
+    let ns = interp.namespace().to_string();
+    interp.state_mut::<Categories>().0.entry(ns).or_insert_with(CategoryState::new)
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -370,10 +381,8 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: "A" → ()
     interp.register("cat_obj", |interp| {
         let obj = interp.stack_mut().pop()?.as_string()?;
-        if let Ok(mut cat) = category_state().lock() {
-            cat.add_object(obj.clone());
-            println!("[category_theory] added object: {}", obj);
-        }
+        category(interp).add_object(obj.clone());
+        writeln!(interp.out(), "[category_theory] added object: {}", obj);
         Ok(())
     });
 
@@ -384,10 +393,8 @@ pub fn register(interp: &mut Interpreter) {
         let to = interp.stack_mut().pop()?.as_string()?;
         let from = interp.stack_mut().pop()?.as_string()?;
 
-        if let Ok(mut cat) = category_state().lock() {
-            cat.add_morphism(name.clone(), from.clone(), to.clone());
-            println!("[category_theory] added morphism: {} : {} -> {}", name, from, to);
-        }
+        category(interp).add_morphism(name.clone(), from.clone(), to.clone());
+        writeln!(interp.out(), "[category_theory] added morphism: {} : {} -> {}", name, from, to);
         Ok(())
     });
 
@@ -397,26 +404,27 @@ pub fn register(interp: &mut Interpreter) {
         let g_name = interp.stack_mut().pop()?.as_string()?;
         let f_name = interp.stack_mut().pop()?.as_string()?;
 
-        if let Ok(cat) = category_state().lock() {
-            let f = cat.find_morphism(&f_name);
-            let g = cat.find_morphism(&g_name);
-
-            match (f, g) {
-                (Some(f), Some(g)) => {
-                    if f.to == g.from {
-                        let comp_name = format!("{} ∘ {}", g_name, f_name);
-                        interp.stack_mut().push(WofValue::string(comp_name));
-                    } else {
-                        println!(
-                            "cat_comp: cannot compose {} ∘ {} (cod(f) = {} ≠ dom(g) = {})",
-                            g_name, f_name, f.to, g.from
-                        );
-                    }
-                }
-                _ => {
-                    println!("cat_comp: unknown morphism(s): {}, {}", f_name, g_name);
+        let out = interp.out();
+        let cat = category(interp);
+        let f = cat.find_morphism(&f_name);
+        let g = cat.find_morphism(&g_name);
+
+        match (f, g) {
+            (Some(f), Some(g)) => {
+                if f.to == g.from {
+                    let comp_name = format!("{} ∘ {}", g_name, f_name);
+                    interp.stack_mut().push(WofValue::string(comp_name));
+                } else {
+                    writeln!(
+                        out,
+                        "cat_comp: cannot compose {} ∘ {} (cod(f) = {} ≠ dom(g) = {})",
+                        g_name, f_name, f.to, g.from
+                    );
                 }
             }
+            _ => {
+                writeln!(out, "cat_comp: unknown morphism(s): {}, {}", f_name, g_name);
+            }
         }
         Ok(())
     });
@@ -427,30 +435,24 @@ pub fn register(interp: &mut Interpreter) {
         let to = interp.stack_mut().pop()?.as_string()?;
         let from = interp.stack_mut().pop()?.as_string()?;
 
-        if let Ok(cat) = category_state().lock() {
-            let homset = cat.hom(&from, &to);
-            let result = format!("Hom({},{}) = {{{}}}", from, to, homset.join(", "));
-            interp.stack_mut().push(WofValue::string(result));
-        }
+        let homset = category(interp).hom(&from, &to).join(", ");
+        let result = format!("Hom({from},{to}) = {{{homset}}}");
+        interp.stack_mut().push(WofValue::string(result));
         Ok(())
     });
 
     // Show category summary
     // Stack: () → summary-string
     interp.register("cat_show", |interp| {
-        if let Ok(cat) = category_state().lock() {
-            let summary = cat.summary();
-            interp.stack_mut().push(WofValue::string(summary));
-        }
+        let summary = category(interp).summary();
+        interp.stack_mut().push(WofValue::string(summary));
         Ok(())
     });
 
     // Clear the category
-    interp.register("cat_clear", |_interp| {
-        if let Ok(mut cat) = category_state().lock() {
-            cat.clear();
-            println!("[category_theory] category cleared");
-        }
+    interp.register("cat_clear", |interp| {
+        category(interp).clear();
+        writeln!(interp.out(), "[category_theory] category cleared");
         Ok(())
     });
 
@@ -458,36 +460,44 @@ pub fn register(interp: &mut Interpreter) {
     // HELP
     // ─────────────────────────────────────────────────────────────────────
 
-    interp.register("logic_help", |_interp| {
-        println!("Logic Operations:");
-        println!();
-        println!("  Boolean Gates:");
-        println!("    a b and       → a ∧ b");
-        println!("    a b or        → a ∨ b");
-        println!("    a b xor       → a ⊕ b");
-        println!("    a not         → ¬a");
-        println!("    a b nand/⊼    → ¬(a ∧ b)");
-        println!("    a b nor/⊽     → ¬(a ∨ b)");
-        println!("    a b xnor      → a ↔ b");
-        println!("    a b implies/⇒ → a → b");
-        println!();
-        println!("  Comparisons:");
-        println!("    a b eq        → a = b");
-        println!("    a b neq       → a ≠ b");
-        println!("    a b gt/lt     → a > b / a < b");
-        println!("    a b gte/lte   → a ≥ b / a ≤ b");
-        println!();
-        println!("  Quantifiers:");
-        println!("    v1..vn n ∀    → all true?");
-        println!("    v1..vn n ∃    → any true?");
-        println!();
-        println!("  Category Theory:");
-        println!("    \"A\" cat_obj               → add object");
-        println!("    \"A\" \"B\" \"f\" cat_mor       → add f : A → B");
-        println!("    \"f\" \"g\" cat_comp          → g ∘ f");
-        println!("    \"A\" \"B\" cat_hom           → Hom(A,B)");
-        println!("    cat_show                  → summary");
-        println!("    cat_clear                 → reset");
+    interp.register("logic_help", |interp| {
+        writeln!(interp.out(), "Logic Operations:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Boolean Gates:");
+        writeln!(interp.out(), "    a b and       → a ∧ b");
+        writeln!(interp.out(), "    a b or        → a ∨ b");
+        writeln!(interp.out(), "    a b xor       → a ⊕ b");
+        writeln!(interp.out(), "    a not         → ¬a");
+        writeln!(interp.out(), "    a b nand/⊼    → ¬(a ∧ b)");
+        writeln!(interp.out(), "    a b nor/⊽     → ¬(a ∨ b)");
+        writeln!(interp.out(), "    a b xnor      → a ↔ b");
+        writeln!(interp.out(), "    a b implies/⇒ → a → b");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Comparisons:");
+        writeln!(interp.out(), "    a b eq        → a = b");
+        writeln!(interp.out(), "    a b neq       → a ≠ b");
+        writeln!(interp.out(), "    a b gt/lt     → a > b / a < b");
+        writeln!(interp.out(), "    a b gte/lte   → a ≥ b / a ≤ b");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Quantifiers:");
+        writeln!(interp.out(), "    v1..vn n ∀    → all true?");
+        writeln!(interp.out(), "    v1..vn n ∃    → any true?");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Category Theory:");
+        writeln!(interp.out(), "    \"A\" cat_obj               → add object");
+        writeln!(interp.out(), "    \"A\" \"B\" \"f\" cat_mor       → add f : A → B");
+        writeln!(interp.out(), "    \"f\" \"g\" cat_comp          → g ∘ f");
+        writeln!(interp.out(), "    \"A\" \"B\" cat_hom           → Hom(A,B)");
+        writeln!(interp.out(), "    cat_show                  → summary");
+        writeln!(interp.out(), "    cat_clear                 → reset");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  State Machines:");
+        writeln!(interp.out(), "    \"m\" fsm_new               → create, make current");
+        writeln!(interp.out(), "    \"a\" \"ev\" \"b\" fsm_on      → add a --ev--> b");
+        writeln!(interp.out(), "    \"m\" \"ev\" fsm_fire         → new state");
+        writeln!(interp.out(), "    \"m\" fsm_state / fsm_show  → inspect");
         Ok(())
     });
+
+    fsm::register(interp);
 }
@@ -129,41 +129,41 @@ pub fn register(interp: &mut Interpreter) {
     // ─────────────────────────────────────────────────────────────────────
 
     // Random math suggestion
-    interp.register("markov_suggest", |_interp| {
+    interp.register("markov_suggest", |interp| {
         if let Some(suggestion) = random_suggestion(MATH_SUGGESTIONS) {
-            println!("[Markov Suggestion] {}", suggestion);
+            writeln!(interp.out(), "[Markov Suggestion] {}", suggestion);
         }
         Ok(())
     });
 
     // Math pattern suggestion (alias)
-    interp.register("suggest_math", |_interp| {
+    interp.register("suggest_math", |interp| {
         if let Some(suggestion) = random_suggestion(MATH_SUGGESTIONS) {
-            println!("[Suggest] {}", suggestion);
+            writeln!(interp.out(), "[Suggest] {}", suggestion);
         }
         Ok(())
     });
 
     // Stack operation suggestion
-    interp.register("suggest_stack", |_interp| {
+    interp.register("suggest_stack", |interp| {
         if let Some(suggestion) = random_suggestion(STACK_SUGGESTIONS) {
-            println!("[Suggest] {}", suggestion);
+            writeln!(interp.out(), "[Suggest] {}", suggestion);
         }
         Ok(())
     });
 
     // Control flow suggestion
-    interp.register("suggest_control", |_interp| {
+    interp.register("suggest_control", |interp| {
         if let Some(suggestion) = random_suggestion(CONTROL_SUGGESTIONS) {
-            println!("[Suggest] {}", suggestion);
+            writeln!(interp.out(), "[Suggest] {}", suggestion);
         }
         Ok(())
     });
 
     // Greek symbol suggestion
-    interp.register("suggest_greek", |_interp| {
+    interp.register("suggest_greek", |interp| {
         if let Some(suggestion) = random_suggestion(GREEK_SUGGESTIONS) {
-            println!("[Suggest] {}", suggestion);
+            writeln!(interp.out(), "[Suggest] {}", suggestion);
         }
         Ok(())
     });
@@ -176,7 +176,7 @@ pub fn register(interp: &mut Interpreter) {
     interp.register("suggest_next", |interp| {
         let suggestions = stack_based_suggestions(interp.stack().len());
         if let Some(suggestion) = random_suggestion(suggestions) {
-            println!("[Suggest] {}", suggestion);
+            writeln!(interp.out(), "[Suggest] {}", suggestion);
         }
         Ok(())
     });
@@ -208,7 +208,7 @@ pub fn register(interp: &mut Interpreter) {
             _ => "Many values! Try: clear, entropy, sum",
         };
 
-        println!("[Smart Suggest] {}", suggestion);
+        writeln!(interp.out(), "[Smart Suggest] {}", suggestion);
         Ok(())
     });
 
@@ -240,11 +240,11 @@ pub fn register(interp: &mut Interpreter) {
             .collect();
 
         if matches.is_empty() {
-            println!("[Autocomplete] No matches for '{}'", prefix);
+            writeln!(interp.out(), "[Autocomplete] No matches for '{}'", prefix);
         } else {
-            println!("[Autocomplete] Matches for '{}':", prefix);
+            writeln!(interp.out(), "[Autocomplete] Matches for '{}':", prefix);
             for m in &matches {
-                println!("  {}", m);
+                writeln!(interp.out(), "  {}", m);
             }
         }
 
@@ -265,14 +265,14 @@ pub fn register(interp: &mut Interpreter) {
         for (op, nexts) in TRANSITIONS {
             if *op == last_op_lower {
                 if let Some(next) = random_suggestion(nexts) {
-                    println!("[Markov] After '{}', try: {}", last_op, next);
+                    writeln!(interp.out(), "[Markov] After '{}', try: {}", last_op, next);
                     return Ok(());
                 }
             }
         }
 
         // Default suggestion
-        println!("[Markov] After '{}', try: print, dup, or +", last_op);
+        writeln!(interp.out(), "[Markov] After '{}', try: print, dup, or +", last_op);
         Ok(())
     });
 
@@ -281,26 +281,26 @@ pub fn register(interp: &mut Interpreter) {
     // ─────────────────────────────────────────────────────────────────────
 
     // Show all suggestion categories
-    interp.register("suggest_all", |_interp| {
-        println!("Math Suggestions:");
+    interp.register("suggest_all", |interp| {
+        writeln!(interp.out(), "Math Suggestions:");
         for s in MATH_SUGGESTIONS {
-            println!("  {}", s);
+            writeln!(interp.out(), "  {}", s);
         }
-        println!();
-        println!("Stack Suggestions:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "Stack Suggestions:");
         for s in STACK_SUGGESTIONS {
-            println!("  {}", s);
+            writeln!(interp.out(), "  {}", s);
         }
-        println!();
-        println!("Greek Suggestions:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "Greek Suggestions:");
         for s in GREEK_SUGGESTIONS {
-            println!("  {}", s);
+            writeln!(interp.out(), "  {}", s);
         }
         Ok(())
     });
 
     // Random suggestion from any category
-    interp.register("suggest", |_interp| {
+    interp.register("suggest", |interp| {
         let all_suggestions: Vec<&str> = MATH_SUGGESTIONS
             .iter()
             .chain(STACK_SUGGESTIONS.iter())
@@ -309,7 +309,7 @@ pub fn register(interp: &mut Interpreter) {
             .collect();
 
         if let Some(suggestion) = random_suggestion(&all_suggestions) {
-            println!("[Suggest] {}", suggestion);
+            writeln!(interp.out(), "[Suggest] {}", suggestion);
         }
         Ok(())
     });
@@ -318,27 +318,27 @@ pub fn register(interp: &mut Interpreter) {
     // HELP
     // ─────────────────────────────────────────────────────────────────────
 
-    interp.register("markov_help", |_interp| {
-        println!("Markov Suggestion Operations:");
-        println!();
-        println!("  Basic Suggestions:");
-        println!("    markov_suggest   # Random math suggestion");
-        println!("    suggest_math     # Math pattern");
-        println!("    suggest_stack    # Stack operations");
-        println!("    suggest_control  # Control flow");
-        println!("    suggest_greek    # Greek symbols");
-        println!();
-        println!("  Context-Aware:");
-        println!("    suggest_next     # Based on stack size");
-        println!("    suggest_smart    # Based on stack contents");
-        println!();
-        println!("  Autocomplete:");
-        println!("    \"pr\" suggest_complete  # Find matches");
-        println!("    \"+\" suggest_after       # Next operation");
-        println!();
-        println!("  Other:");
-        println!("    suggest          # Random from all");
-        println!("    suggest_all      # Show all suggestions");
+    interp.register("markov_help", |interp| {
+        writeln!(interp.out(), "Markov Suggestion Operations:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Basic Suggestions:");
+        writeln!(interp.out(), "    markov_suggest   # Random math suggestion");
+        writeln!(interp.out(), "    suggest_math     # Math pattern");
+        writeln!(interp.out(), "    suggest_stack    # Stack operations");
+        writeln!(interp.out(), "    suggest_control  # Control flow");
+        writeln!(interp.out(), "    suggest_greek    # Greek symbols");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Context-Aware:");
+        writeln!(interp.out(), "    suggest_next     # Based on stack size");
+        writeln!(interp.out(), "    suggest_smart    # Based on stack contents");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Autocomplete:");
+        writeln!(interp.out(), "    \"pr\" suggest_complete  # Find matches");
+        writeln!(interp.out(), "    \"+\" suggest_after       # Next operation");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Other:");
+        writeln!(interp.out(), "    suggest          # Random from all");
+        writeln!(interp.out(), "    suggest_all      # Show all suggestions");
         Ok(())
     });
 }
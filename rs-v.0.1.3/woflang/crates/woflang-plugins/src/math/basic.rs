@@ -1,7 +1,7 @@
 //! Basic mathematical operations for Woflang.
 //!
-//! Provides floor, ceil, round, abs, sign, min, max, clamp, and related functions.
-//! Note: Core arithmetic (+, -, *, /, %) is in woflang-ops.
+//! Provides floor, ceil, round, sign, min, max, clamp, and related functions.
+//! Note: Core arithmetic (+, -, *, /, %, abs) is in woflang-ops.
 
 use woflang_core::{WofValue, InterpreterContext};
 use woflang_runtime::Interpreter;
@@ -58,12 +58,6 @@ pub fn register(interp: &mut Interpreter) {
     // SIGN AND ABSOLUTE VALUE
     // ═══════════════════════════════════════════════════════════════
     
-    interp.register("abs", |interp| {
-        let x = interp.stack_mut().pop()?.as_double()?;
-        interp.stack_mut().push(WofValue::double(x.abs()));
-        Ok(())
-    });
-
     interp.register("sign", |interp| {
         let x = interp.stack_mut().pop()?.as_double()?;
         let s = if x > 0.0 { 1.0 } else if x < 0.0 { -1.0 } else { 0.0 };
@@ -0,0 +1,143 @@
+//! Complex number operations.
+//!
+//! ## Operations
+//!
+//! - `complex` - Build a complex number (re im → z)
+//! - `re`, `im` - Real and imaginary parts (z → x)
+//! - `conj` - Complex conjugate (z → z̄)
+//! - `carg` - Angle from the positive real axis (z → θ)
+//! - `polar` - Polar form (z → r θ)
+//! - `from_polar` - Back from polar form (r θ → z)
+//!
+//! Arithmetic uses the core ops: `+`, `-`, `*`, `/`, `neg`, `abs`,
+//! `sqrt` and `exp` take complex numbers, widening any real number they
+//! meet, so `2 1 complex 3 *` scales by three. Euler's identity:
+//!
+//! ```text
+//! 0 π complex exp            # -1+0i, up to rounding
+//! 1 π from_polar 1 + abs     # 0, up to rounding
+//! ```
+
+use woflang_core::{Complex, InterpreterContext, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Pop a complex number, widening a real one.
+fn pop_complex(interp: &mut Interpreter) -> woflang_core::Result<Complex> {
+    interp.stack_mut().pop()?.as_complex()
+}
+
+/// Register complex number operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: re im → z
+    interp.register("complex", |interp| {
+        let im = interp.stack_mut().pop_numeric()?;
+        let re = interp.stack_mut().pop_numeric()?;
+        interp.push(WofValue::complex(Complex::new(re, im)));
+        Ok(())
+    });
+
+    // Stack: z → re
+    interp.register("re", |interp| {
+        let z = pop_complex(interp)?;
+        interp.push(WofValue::double(z.re));
+        Ok(())
+    });
+
+    // Stack: z → im
+    interp.register("im", |interp| {
+        let z = pop_complex(interp)?;
+        interp.push(WofValue::double(z.im));
+        Ok(())
+    });
+
+    // Stack: z → z̄
+    interp.register("conj", |interp| {
+        let z = pop_complex(interp)?;
+        interp.push(WofValue::complex(z.conj()));
+        Ok(())
+    });
+
+    // Stack: z → θ
+    interp.register("carg", |interp| {
+        let z = pop_complex(interp)?;
+        interp.push(WofValue::double(z.arg()));
+        Ok(())
+    });
+
+    // Stack: z → r θ
+    interp.register("polar", |interp| {
+        let (r, theta) = pop_complex(interp)?.to_polar();
+        interp.push(WofValue::double(r));
+        interp.push(WofValue::double(theta));
+        Ok(())
+    });
+
+    // Stack: r θ → z
+    interp.register("from_polar", |interp| {
+        let theta = interp.stack_mut().pop_numeric()?;
+        let r = interp.stack_mut().pop_numeric()?;
+        interp.push(WofValue::complex(Complex::from_polar(r, theta)));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        woflang_ops::register_all(&mut interp);
+        crate::math::register(&mut interp);
+        interp
+    }
+
+    fn pop(interp: &mut Interpreter) -> Complex {
+        interp.stack_mut().pop().unwrap().as_complex().unwrap()
+    }
+
+    #[test]
+    fn complex_ops() {
+        let mut interp = make_interp();
+
+        interp.exec_line("1 2 complex 3 -1 complex *").unwrap();
+        assert_eq!(pop(&mut interp), Complex::new(5.0, 5.0));
+        interp.exec_line("1 2 complex 3 + 1 - 2 / conj").unwrap();
+        assert_eq!(pop(&mut interp), Complex::new(1.5, -1.0));
+
+        interp.exec_line("3 4 complex abs 3 4 complex re 3 4 complex im").unwrap();
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 4.0);
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 3.0);
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 5.0);
+
+        interp.exec_line("0 π complex exp").unwrap();
+        assert!((pop(&mut interp) - Complex::new(-1.0, 0.0)).abs() < 1e-12);
+
+        interp.exec_line("0 -2 complex polar").unwrap();
+        assert!((interp.stack_mut().pop_numeric().unwrap() + PI / 2.0).abs() < 1e-12);
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 2.0);
+
+        interp.push(WofValue::double(2.0));
+        interp.push(WofValue::double(PI / 2.0));
+        interp.exec_line("from_polar").unwrap();
+        assert!((pop(&mut interp) - Complex::new(0.0, 2.0)).abs() < 1e-12);
+
+        assert!(interp.exec_line("\"z\" re").is_err());
+    }
+
+    #[test]
+    fn core_ops_take_complex_numbers() {
+        let mut interp = make_interp();
+        interp.exec_line("1 2 complex 3 +").unwrap();
+        assert_eq!(pop(&mut interp), Complex::new(4.0, 2.0));
+        interp.exec_line("-1 sqrt").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "0+1i");
+        interp.exec_line("3 4 complex sqrt -4 √").unwrap();
+        assert_eq!(pop(&mut interp), Complex::new(0.0, 2.0));
+        assert_eq!(pop(&mut interp), Complex::new(2.0, 1.0));
+        interp.exec_line("1 π complex exp abs -3 abs").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::integer(3));
+        assert!((interp.stack_mut().pop_numeric().unwrap() - std::f64::consts::E).abs() < 1e-12);
+    }
+}
@@ -1,6 +1,8 @@
 //! Exponential and logarithmic operations for Woflang.
 //!
-//! Provides exp, ln, log, log10, log2, pow, sqrt, cbrt, and related functions.
+//! Provides ln, log, log10, log2, pow, cbrt, and related functions.
+//! `exp` and `sqrt` are the core ops in woflang-ops, which also take
+//! complex numbers.
 
 use woflang_core::{WofError, WofValue, InterpreterContext};
 use woflang_runtime::Interpreter;
@@ -11,13 +13,6 @@ pub fn register(interp: &mut Interpreter) {
     // EXPONENTIALS
     // ═══════════════════════════════════════════════════════════════
     
-    // e^x
-    interp.register("exp", |interp| {
-        let x = interp.stack_mut().pop()?.as_double()?;
-        interp.stack_mut().push(WofValue::double(x.exp()));
-        Ok(())
-    });
-
     // 2^x
     interp.register("exp2", |interp| {
         let x = interp.stack_mut().pop()?.as_double()?;
@@ -110,25 +105,6 @@ pub fn register(interp: &mut Interpreter) {
         Ok(())
     });
 
-    // Square root
-    interp.register("√", |interp| {
-        let x = interp.stack_mut().pop()?.as_double()?;
-        if x < 0.0 {
-            return Err(WofError::Runtime("√: domain error, x must be >= 0".into()));
-        }
-        interp.stack_mut().push(WofValue::double(x.sqrt()));
-        Ok(())
-    });
-
-    interp.register("sqrt", |interp| {
-        let x = interp.stack_mut().pop()?.as_double()?;
-        if x < 0.0 {
-            return Err(WofError::Runtime("sqrt: domain error, x must be >= 0".into()));
-        }
-        interp.stack_mut().push(WofValue::double(x.sqrt()));
-        Ok(())
-    });
-
     // Cube root (works for negative numbers)
     interp.register("∛", |interp| {
         let x = interp.stack_mut().pop()?.as_double()?;
@@ -169,6 +145,7 @@ mod tests {
 
     fn setup() -> Interpreter {
         let mut interp = Interpreter::new();
+        woflang_ops::math::register(&mut interp);
         register(&mut interp);
         interp
     }
@@ -0,0 +1,205 @@
+//! Fourier series as functions and buffers.
+//!
+//! ## Operations
+//!
+//! - `fourier_square` - Band-limited square wave up to harmonic n (n → "f")
+//! - `fourier_saw` - Band-limited sawtooth up to harmonic n (n → "f")
+//! - `fourier_custom` - Sum of sine harmonics ([b1, b2, ...] → "f")
+//! - `fourier_eval` - Evaluate Σ bₖ·sin(k·x) (x b1 .. bn n → y)
+//! - `fourier_buf` - One period of a function as a buffer ("f" samples → buffer)
+//!
+//! Each series becomes a function defined as if with `⊕`, whose name is
+//! pushed, so it can be called, plotted with `plot_fn`, or sampled into a
+//! buffer for the `a.buf_*` ops. The period is 2π, starting at 0:
+//!
+//! ```text
+//! 9 fourier_square                  # "square_9"
+//! dup -3.14159 3.14159 plot_fn
+//! 64 fourier_buf 0.5 a.buf_scale    # a quieter cycle of 64 samples
+//! ```
+//!
+//! The coefficients are written into the function body, so the harmonics
+//! a function uses can be read from its definition.
+
+use std::f64::consts::{PI, TAU};
+use std::fmt::Write as _;
+
+use woflang_core::{InterpreterContext, Result, Span, WofError, WofType, WofValue};
+use woflang_runtime::{FunctionDef, Interpreter, OwnedToken, Tokenizer};
+
+/// Highest harmonic a series may use.
+const MAX_HARMONICS: usize = 4096;
+
+/// Most samples `fourier_buf` takes from one period.
+const MAX_SAMPLES: usize = 1 << 20;
+
+/// Counter naming the `fourier_custom` functions.
+#[derive(Debug, Default)]
+struct Series {
+    defined: usize,
+}
+
+/// Σ bₖ·sin(k·x) for k from 1 to `b.len()`.
+fn series(b: &[f64], x: f64) -> f64 {
+    b.iter().zip(1..).map(|(bk, k)| bk * (f64::from(k) * x).sin()).sum()
+}
+
+/// Sine coefficients of the unit square wave, up to harmonic `n`.
+fn square_coeffs(n: usize) -> Vec<f64> {
+    (1..=n).map(|k| if k % 2 == 1 { 4.0 / (PI * k as f64) } else { 0.0 }).collect()
+}
+
+/// Sine coefficients of the unit sawtooth rising through zero at 0,
+/// up to harmonic `n`.
+fn saw_coeffs(n: usize) -> Vec<f64> {
+    (1..=n)
+        .map(|k| {
+            let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+            sign * 2.0 / (PI * k as f64)
+        })
+        .collect()
+}
+
+/// Define `name` as the series with coefficients `b`.
+fn define_series(interp: &mut Interpreter, name: &str, b: &[f64]) {
+    let mut source = String::new();
+    for c in b {
+        let _ = write!(source, "{c} ");
+    }
+    let _ = write!(source, "{} fourier_eval", b.len());
+    let body = Tokenizer::new(&source).tokenize_all().into_iter().map(OwnedToken::from).collect();
+    interp.define_function(FunctionDef::new(name, body, Span::default()).with_arity(1));
+}
+
+/// Pop a harmonic count for `op`.
+fn pop_harmonics(interp: &mut Interpreter, op: &str) -> Result<usize> {
+    let n = interp.stack_mut().pop_integer()?;
+    usize::try_from(n)
+        .ok()
+        .filter(|n| (1..=MAX_HARMONICS).contains(n))
+        .ok_or_else(|| WofError::runtime(format!("{op}: harmonics must be in 1..={MAX_HARMONICS}")))
+}
+
+/// Register the Fourier series operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: n → "square_n"
+    interp.register("fourier_square", |interp| {
+        let n = pop_harmonics(interp, "fourier_square")?;
+        let name = format!("square_{n}");
+        define_series(interp, &name, &square_coeffs(n));
+        interp.push(WofValue::string(name));
+        Ok(())
+    });
+
+    // Stack: n → "saw_n"
+    interp.register("fourier_saw", |interp| {
+        let n = pop_harmonics(interp, "fourier_saw")?;
+        let name = format!("saw_{n}");
+        define_series(interp, &name, &saw_coeffs(n));
+        interp.push(WofValue::string(name));
+        Ok(())
+    });
+
+    // Stack: [b1, b2, ...] → "fourier_k"
+    interp.register("fourier_custom", |interp| {
+        let coeffs = interp.stack_mut().pop()?;
+        let b = match coeffs.value_type() {
+            WofType::Buffer => coeffs.as_buffer()?.to_vec(),
+            _ => coeffs.as_list()?.iter().map(WofValue::as_double).collect::<Result<Vec<_>>>()?,
+        };
+        if b.is_empty() || b.len() > MAX_HARMONICS {
+            return Err(WofError::runtime(format!("fourier_custom: need 1..={MAX_HARMONICS} coefficients")));
+        }
+        if !b.iter().all(|c| c.is_finite()) {
+            return Err(WofError::runtime("fourier_custom: coefficients must be finite"));
+        }
+        let state = interp.state_mut::<Series>();
+        let name = format!("fourier_{}", state.defined);
+        state.defined += 1;
+        define_series(interp, &name, &b);
+        interp.push(WofValue::string(name));
+        Ok(())
+    });
+
+    // Stack: x b1 .. bn n → y
+    interp.register("fourier_eval", |interp| {
+        let n = interp.stack_mut().pop_integer()?;
+        let n = usize::try_from(n)
+            .map_err(|_| WofError::runtime("fourier_eval: harmonic count must be non-negative"))?;
+        let b = interp
+            .stack_mut()
+            .pop_n(n)?
+            .iter()
+            .rev()
+            .map(WofValue::as_double)
+            .collect::<Result<Vec<_>>>()?;
+        let x = interp.stack_mut().pop_numeric()?;
+        interp.push(WofValue::double(series(&b, x)));
+        Ok(())
+    });
+
+    // Stack: "f" samples → buffer
+    interp.register("fourier_buf", |interp| {
+        let samples = interp.stack_mut().pop_integer()?;
+        let function = interp.stack_mut().pop_string()?;
+        let samples = usize::try_from(samples)
+            .ok()
+            .filter(|n| (1..=MAX_SAMPLES).contains(n))
+            .ok_or_else(|| WofError::runtime(format!("fourier_buf: samples must be in 1..={MAX_SAMPLES}")))?;
+        if !interp.has_function(&function) {
+            return Err(WofError::runtime(format!("fourier_buf: no function named '{function}'")));
+        }
+        let mut buffer = Vec::with_capacity(samples);
+        for i in 0..samples {
+            let x = TAU * i as f64 / samples as f64;
+            buffer.push(super::plot::sample(interp, "fourier_buf", &function, &[x])?);
+        }
+        interp.push(WofValue::buffer(buffer));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn series_approach_their_waveforms() {
+        let square = square_coeffs(199);
+        assert!((series(&square, PI / 2.0) - 1.0).abs() < 0.01);
+        assert!((series(&square, 3.0 * PI / 2.0) + 1.0).abs() < 0.01);
+        assert!(series(&square, 0.0).abs() < 1e-12);
+
+        let saw = saw_coeffs(199);
+        assert!((series(&saw, PI / 2.0) - 0.5).abs() < 0.01);
+        assert!((series(&saw, -PI / 4.0) + 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn series_become_functions_and_buffers() {
+        let mut interp = Interpreter::new();
+        crate::math::register(&mut interp);
+
+        interp.exec_line("3 fourier_square").unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "square_3");
+        interp.exec_line("1.5707963267948966 巡 square_3").unwrap();
+        let want = 4.0 / PI * (1.0 - 1.0 / 3.0);
+        assert!((interp.stack_mut().pop_numeric().unwrap() - want).abs() < 1e-12);
+
+        interp.push(WofValue::list(vec![WofValue::double(0.0), WofValue::integer(2)]));
+        interp.exec_line("fourier_custom 8 fourier_buf").unwrap();
+        let buffer = interp.stack_mut().pop().unwrap().into_buffer().unwrap();
+        let want = [0.0, 2.0, 0.0, -2.0, 0.0, 2.0, 0.0, -2.0];
+        assert!(buffer.iter().zip(want).all(|(got, want)| (got - want).abs() < 1e-12), "{buffer:?}");
+        assert!(interp.has_function("fourier_0"));
+
+        interp.exec_line("1 fourier_saw 8 fourier_buf").unwrap();
+        let buffer = interp.stack_mut().pop().unwrap().into_buffer().unwrap();
+        assert!((buffer[2] - 2.0 / PI).abs() < 1e-12);
+
+        assert!(interp.exec_line("0 fourier_square").is_err());
+        interp.push(WofValue::list(Vec::new()));
+        assert!(interp.exec_line("fourier_custom").is_err());
+        assert!(interp.exec_line("\"nope\" 8 fourier_buf").is_err());
+    }
+}
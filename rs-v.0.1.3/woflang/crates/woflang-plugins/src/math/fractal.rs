@@ -6,86 +6,70 @@
 //! - `sierpinski` - ASCII Sierpinski triangle
 //! - `hausdorff_dim` - Self-similar Hausdorff dimension
 //! - `julia` - Julia set iteration count
+//!
+//! Points can be complex values or pairs of real and imaginary parts:
+//! `-1 0 complex 100 mandelbrot` and `-1 0 100 mandelbrot` agree.
 
-use woflang_core::{WofValue, InterpreterContext};
+use woflang_core::{Complex, Out, WofType, WofValue, InterpreterContext};
 use woflang_runtime::Interpreter;
 
 // ═══════════════════════════════════════════════════════════════════════════
 // FRACTAL ALGORITHMS
 // ═══════════════════════════════════════════════════════════════════════════
 
-/// Compute Mandelbrot escape iterations for c = cr + i*ci.
-fn mandelbrot_escape(cr: f64, ci: f64, max_iter: i32) -> i32 {
-    let mut zr = 0.0;
-    let mut zi = 0.0;
+/// Iterate z ↦ z² + c from `z` until |z| > 2, returning the step count.
+fn escape(mut z: Complex, c: Complex, max_iter: i32) -> i32 {
     let mut iter = 0;
-
-    while iter < max_iter {
-        let zr2 = zr * zr;
-        let zi2 = zi * zi;
-
-        if zr2 + zi2 > 4.0 {
-            break;
-        }
-
-        let new_zr = zr2 - zi2 + cr;
-        let new_zi = 2.0 * zr * zi + ci;
-
-        zr = new_zr;
-        zi = new_zi;
+    while iter < max_iter && z.norm_sqr() <= 4.0 {
+        z = z * z + c;
         iter += 1;
     }
-
     iter
 }
 
-/// Compute Julia set escape iterations for z starting at zr + i*zi.
-fn julia_escape(zr: f64, zi: f64, cr: f64, ci: f64, max_iter: i32) -> i32 {
-    let mut zr = zr;
-    let mut zi = zi;
-    let mut iter = 0;
-
-    while iter < max_iter {
-        let zr2 = zr * zr;
-        let zi2 = zi * zi;
-
-        if zr2 + zi2 > 4.0 {
-            break;
-        }
+/// Compute Mandelbrot escape iterations for `c`.
+fn mandelbrot_escape(c: Complex, max_iter: i32) -> i32 {
+    escape(Complex::ZERO, c, max_iter)
+}
 
-        let new_zr = zr2 - zi2 + cr;
-        let new_zi = 2.0 * zr * zi + ci;
+/// Compute Julia set escape iterations for `z` under parameter `c`.
+fn julia_escape(z: Complex, c: Complex, max_iter: i32) -> i32 {
+    escape(z, c, max_iter)
+}
 
-        zr = new_zr;
-        zi = new_zi;
-        iter += 1;
+/// Pop a point given either as one complex value or as `re im`.
+fn pop_point(interp: &mut Interpreter) -> woflang_core::Result<Complex> {
+    let top = interp.stack_mut().pop()?;
+    if top.value_type() == WofType::Complex {
+        return top.as_complex();
     }
-
-    iter
+    let im = top.as_double()?;
+    let re = interp.stack_mut().pop()?.as_double()?;
+    Ok(Complex::new(re, im))
 }
 
 /// Print ASCII Sierpinski triangle.
-fn print_sierpinski(depth: i32) {
+fn print_sierpinski(out: &Out, depth: i32) {
     let depth = depth.clamp(1, 8);
     let size = 1 << depth;
 
-    println!("[fractal] Sierpinski triangle (depth {})", depth);
+    writeln!(out, "[fractal] Sierpinski triangle (depth {})", depth);
 
     for y in 0..size {
         // Centering
         for _ in 0..(size - y) {
-            print!(" ");
+            out.print(" ");
         }
 
         for x in 0..size {
             if (x & y) == 0 {
-                print!("*");
+                out.print("*");
             } else {
-                print!(" ");
+                out.print(" ");
             }
         }
 
-        println!();
+        writeln!(out);
     }
 }
 
@@ -103,46 +87,45 @@ fn hausdorff_dimension(n: f64, scale: f64) -> f64 {
 
 /// Register all fractal operations.
 pub fn register(interp: &mut Interpreter) {
-    // Mandelbrot: real imag max_iter → iterations
+    // Mandelbrot: c|(real imag) max_iter → iterations
     interp.register("mandelbrot", |interp| {
         let max_iter = interp.stack_mut().pop()?.as_integer()?.clamp(1, 10000) as i32;
-        let imag = interp.stack_mut().pop()?.as_double()?;
-        let real = interp.stack_mut().pop()?.as_double()?;
+        let c = pop_point(interp)?;
 
-        let iters = mandelbrot_escape(real, imag, max_iter);
+        let iters = mandelbrot_escape(c, max_iter);
 
         let status = if iters == max_iter {
             "(likely in set)"
         } else {
             "(escaped)"
         };
-        println!(
-            "[fractal] mandelbrot({} + {}i, max_iter={}) → iters={} {}",
-            real, imag, max_iter, iters, status
+        writeln!(
+            interp.out(),
+            "[fractal] mandelbrot({}, max_iter={}) → iters={} {}",
+            c, max_iter, iters, status
         );
 
         interp.stack_mut().push(WofValue::integer(iters as i64));
         Ok(())
     });
 
-    // Julia: zr zi cr ci max_iter → iterations
+    // Julia: z|(zr zi) c|(cr ci) max_iter → iterations
     interp.register("julia", |interp| {
         let max_iter = interp.stack_mut().pop()?.as_integer()?.clamp(1, 10000) as i32;
-        let ci = interp.stack_mut().pop()?.as_double()?;
-        let cr = interp.stack_mut().pop()?.as_double()?;
-        let zi = interp.stack_mut().pop()?.as_double()?;
-        let zr = interp.stack_mut().pop()?.as_double()?;
+        let c = pop_point(interp)?;
+        let z = pop_point(interp)?;
 
-        let iters = julia_escape(zr, zi, cr, ci, max_iter);
+        let iters = julia_escape(z, c, max_iter);
 
         let status = if iters == max_iter {
             "(likely in set)"
         } else {
             "(escaped)"
         };
-        println!(
-            "[fractal] julia(z={} + {}i, c={} + {}i, max={}) → {} {}",
-            zr, zi, cr, ci, max_iter, iters, status
+        writeln!(
+            interp.out(),
+            "[fractal] julia(z={}, c={}, max={}) → {} {}",
+            z, c, max_iter, iters, status
         );
 
         interp.stack_mut().push(WofValue::integer(iters as i64));
@@ -152,7 +135,7 @@ pub fn register(interp: &mut Interpreter) {
     // Sierpinski: depth → ()
     interp.register("sierpinski", |interp| {
         let depth = interp.stack_mut().pop()?.as_integer()? as i32;
-        print_sierpinski(depth);
+        print_sierpinski(&interp.out(), depth);
         Ok(())
     });
 
@@ -164,7 +147,7 @@ pub fn register(interp: &mut Interpreter) {
 
         let d = hausdorff_dimension(n, scale);
 
-        println!("[fractal] hausdorff_dim(N={}, scale={}) = {}", n, scale, d);
+        writeln!(interp.out(), "[fractal] hausdorff_dim(N={}, scale={}) = {}", n, scale, d);
 
         interp.stack_mut().push(WofValue::double(d));
         Ok(())
@@ -220,44 +203,79 @@ pub fn register(interp: &mut Interpreter) {
         let chars = " .:-=+*#%@";
         let char_vec: Vec<char> = chars.chars().collect();
 
+        // Render into a buffer so progress reports don't interleave with the image
+        let mut image = String::new();
         for py in 0..height {
+            interp.progress(py as usize, height as usize, "rows");
             for px in 0..width {
                 let x = x_min + (x_max - x_min) * (px as f64) / (width as f64);
                 let y = y_min + (y_max - y_min) * (py as f64) / (height as f64);
 
-                let iters = mandelbrot_escape(x, y, max_iter);
+                let iters = mandelbrot_escape(Complex::new(x, y), max_iter);
                 let idx = if iters == max_iter {
                     char_vec.len() - 1
                 } else {
                     (iters as usize * char_vec.len() / max_iter as usize).min(char_vec.len() - 1)
                 };
 
-                print!("{}", char_vec[idx]);
+                image.push(char_vec[idx]);
             }
-            println!();
+            image.push('\n');
         }
+        interp.progress(height as usize, height as usize, "rows");
+        write!(interp.out(), "{image}");
 
         Ok(())
     });
 
     // Help
-    interp.register("fractal_help", |_interp| {
-        println!("Fractal Operations:");
-        println!();
-        println!("  Iteration counts:");
-        println!("    real imag max_iter mandelbrot → iterations");
-        println!("    zr zi cr ci max_iter julia    → iterations");
-        println!();
-        println!("  Visualization:");
-        println!("    depth sierpinski              → (prints triangle)");
-        println!("    x1 x2 y1 y2 w h max mandelbrot_ascii → (prints fractal)");
-        println!();
-        println!("  Dimensions:");
-        println!("    N scale hausdorff_dim         → log(N)/log(scale)");
-        println!("    dim_sierpinski                → ~1.585");
-        println!("    dim_koch                      → ~1.262");
-        println!("    dim_cantor                    → ~0.631");
-        println!("    dim_menger                    → ~2.727");
+    interp.register("fractal_help", |interp| {
+        writeln!(interp.out(), "Fractal Operations:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Iteration counts:");
+        writeln!(interp.out(), "    real imag max_iter mandelbrot → iterations");
+        writeln!(interp.out(), "    zr zi cr ci max_iter julia    → iterations");
+        writeln!(interp.out(), "    (or complex values: c max_iter mandelbrot, z c max_iter julia)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Visualization:");
+        writeln!(interp.out(), "    depth sierpinski              → (prints triangle)");
+        writeln!(interp.out(), "    x1 x2 y1 y2 w h max mandelbrot_ascii → (prints fractal)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Dimensions:");
+        writeln!(interp.out(), "    N scale hausdorff_dim         → log(N)/log(scale)");
+        writeln!(interp.out(), "    dim_sierpinski                → ~1.585");
+        writeln!(interp.out(), "    dim_koch                      → ~1.262");
+        writeln!(interp.out(), "    dim_cantor                    → ~0.631");
+        writeln!(interp.out(), "    dim_menger                    → ~2.727");
         Ok(())
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_are_complex_or_pairs() {
+        let mut interp = Interpreter::new();
+        crate::math::register(&mut interp);
+
+        interp.exec_line("-1 0 complex 50 mandelbrot 1 1 50 mandelbrot").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 2);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 50);
+
+        interp.exec_line("0 0 -1 0 complex 80 julia 0 0 complex 2 0 80 julia").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 2);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 80);
+    }
+    #[test]
+    fn sierpinski_prints_to_the_interpreter_output() {
+        let mut interp = Interpreter::new();
+        crate::math::register(&mut interp);
+        let buffer = woflang_core::Buffer::new();
+        interp.set_output(buffer.clone());
+
+        interp.exec_line("1 sierpinski").unwrap();
+        assert_eq!(buffer.take(), "[fractal] Sierpinski triangle (depth 1)\n  **\n * \n");
+    }
+}
@@ -0,0 +1,194 @@
+//! Geodesy: distances and coordinates on the Earth.
+//!
+//! ## Operations
+//!
+//! - `haversine` - Great-circle distance (lat1 lon1 lat2 lon2 → km)
+//! - `deg_min_sec_to_dd` - Degrees, minutes, seconds to decimal degrees (d m s → dd)
+//! - `utm_from_latlon` - WGS84 lat/lon to UTM (lat lon → easting northing zone)
+//!
+//! Angles are decimal degrees with north and east positive. The UTM zone
+//! is pushed as a string with its latitude band, e.g. `"31U"`; bands `N`
+//! and above are in the northern hemisphere.
+//!
+//! ```text
+//! 48.8566 2.3522 51.5074 -0.1278 haversine   # Paris–London ≈ 343.6 km
+//! 51 28 40.12 deg_min_sec_to_dd              # 51.477811
+//! ```
+
+use woflang_core::{InterpreterContext, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Mean Earth radius in kilometres (IUGG).
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// WGS84 semi-major axis in metres.
+const WGS84_A: f64 = 6_378_137.0;
+
+/// WGS84 flattening.
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+/// UTM scale factor on the central meridian.
+const UTM_K0: f64 = 0.9996;
+
+/// Latitude band letters from 80°S, 8° each (X covers 72°N–84°N).
+const UTM_BANDS: &[u8] = b"CDEFGHJKLMNPQRSTUVWX";
+
+/// Great-circle distance in km between two points given in degrees.
+fn haversine(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let dphi = phi2 - phi1;
+    let dlambda = (lon2 - lon1).to_radians();
+    let h = (dphi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (dlambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().min(1.0).asin()
+}
+
+/// Decimal degrees; the sign of `deg` applies to the whole angle.
+fn dms_to_dd(deg: f64, min: f64, sec: f64) -> f64 {
+    let magnitude = deg.abs() + min / 60.0 + sec / 3600.0;
+    if deg.is_sign_negative() { -magnitude } else { magnitude }
+}
+
+/// UTM zone number, including the Norway and Svalbard exceptions.
+fn utm_zone(lat: f64, lon: f64) -> i32 {
+    if (56.0..64.0).contains(&lat) && (3.0..12.0).contains(&lon) {
+        return 32;
+    }
+    if (72.0..=84.0).contains(&lat) && (0.0..42.0).contains(&lon) {
+        return match lon {
+            l if l < 9.0 => 31,
+            l if l < 21.0 => 33,
+            l if l < 33.0 => 35,
+            _ => 37,
+        };
+    }
+    (((lon + 180.0) / 6.0).floor() as i32 + 1).min(60)
+}
+
+/// UTM easting and northing in metres plus the zone label, using the
+/// Transverse Mercator series from Snyder's *Map Projections* (mm-level
+/// accuracy within a zone). `lat` must lie in [-80, 84].
+fn utm_from_latlon(lat: f64, lon: f64) -> (f64, f64, String) {
+    let zone = utm_zone(lat, lon);
+    let band = UTM_BANDS[(((lat + 80.0) / 8.0).floor() as usize).min(UTM_BANDS.len() - 1)] as char;
+    let lon0 = f64::from(zone * 6 - 183);
+
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let (e4, e6) = (e2 * e2, e2 * e2 * e2);
+    let ep2 = e2 / (1.0 - e2);
+
+    let phi = lat.to_radians();
+    let (sin, cos, tan) = (phi.sin(), phi.cos(), phi.tan());
+    let n = WGS84_A / (1.0 - e2 * sin * sin).sqrt();
+    let t = tan * tan;
+    let c = ep2 * cos * cos;
+    let a = cos * (lon - lon0).to_radians();
+    let m = WGS84_A
+        * ((1.0 - e2 / 4.0 - 3.0 * e4 / 64.0 - 5.0 * e6 / 256.0) * phi
+            - (3.0 * e2 / 8.0 + 3.0 * e4 / 32.0 + 45.0 * e6 / 1024.0) * (2.0 * phi).sin()
+            + (15.0 * e4 / 256.0 + 45.0 * e6 / 1024.0) * (4.0 * phi).sin()
+            - (35.0 * e6 / 3072.0) * (6.0 * phi).sin());
+
+    let easting = UTM_K0
+        * n
+        * (a + (1.0 - t + c) * a.powi(3) / 6.0
+            + (5.0 - 18.0 * t + t * t + 72.0 * c - 58.0 * ep2) * a.powi(5) / 120.0)
+        + 500_000.0;
+    let mut northing = UTM_K0
+        * (m + n
+            * tan
+            * (a * a / 2.0
+                + (5.0 - t + 9.0 * c + 4.0 * c * c) * a.powi(4) / 24.0
+                + (61.0 - 58.0 * t + t * t + 600.0 * c - 330.0 * ep2) * a.powi(6) / 720.0));
+    if lat < 0.0 {
+        northing += 10_000_000.0;
+    }
+    (easting, northing, format!("{zone}{band}"))
+}
+
+/// Register the geodesy operations.
+pub fn register(interp: &mut Interpreter) {
+    // Great-circle distance: lat1 lon1 lat2 lon2 → km
+    interp.register("haversine", |interp| {
+        let lon2 = interp.stack_mut().pop_numeric()?;
+        let lat2 = interp.stack_mut().pop_numeric()?;
+        let lon1 = interp.stack_mut().pop_numeric()?;
+        let lat1 = interp.stack_mut().pop_numeric()?;
+        interp.push(WofValue::double(haversine(lat1, lon1, lat2, lon2)));
+        Ok(())
+    });
+
+    // Degrees/minutes/seconds: d m s → dd
+    interp.register("deg_min_sec_to_dd", |interp| {
+        let sec = interp.stack_mut().pop_numeric()?;
+        let min = interp.stack_mut().pop_numeric()?;
+        let deg = interp.stack_mut().pop_numeric()?;
+        if !(0.0..60.0).contains(&min) || !(0.0..60.0).contains(&sec) {
+            return Err(WofError::runtime("deg_min_sec_to_dd: minutes and seconds must be in [0, 60)"));
+        }
+        interp.push(WofValue::double(dms_to_dd(deg, min, sec)));
+        Ok(())
+    });
+
+    // UTM: lat lon → easting northing "zone"
+    interp.register("utm_from_latlon", |interp| {
+        let lon = interp.stack_mut().pop_numeric()?;
+        let lat = interp.stack_mut().pop_numeric()?;
+        if !(-80.0..=84.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            return Err(WofError::runtime(
+                "utm_from_latlon: UTM covers latitudes -80 to 84 and longitudes -180 to 180",
+            ));
+        }
+        let (easting, northing, zone) = utm_from_latlon(lat, lon);
+        interp.push(WofValue::double(easting));
+        interp.push(WofValue::double(northing));
+        interp.push(WofValue::string(zone));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances_and_angles() {
+        let paris_london = haversine(48.8566, 2.3522, 51.5074, -0.1278);
+        assert!((paris_london - 343.6).abs() < 0.5, "{paris_london}");
+        assert_eq!(haversine(10.0, 20.0, 10.0, 20.0), 0.0);
+        // Antipodes are half the circumference apart.
+        let half = haversine(0.0, 0.0, 0.0, 180.0);
+        assert!((half - std::f64::consts::PI * EARTH_RADIUS_KM).abs() < 1e-6);
+
+        assert!((dms_to_dd(51.0, 28.0, 40.12) - 51.477_811).abs() < 1e-6);
+        assert!((dms_to_dd(-0.0, 30.0, 0.0) + 0.5).abs() < 1e-12);
+        assert!((dms_to_dd(-33.0, 52.0, 4.0) + 33.867_778).abs() < 1e-6);
+    }
+
+    #[test]
+    fn utm_matches_reference_points() {
+        // Eiffel Tower and Sydney Opera House, checked against Krüger's series.
+        let (e, n, zone) = utm_from_latlon(48.858_37, 2.294_48);
+        assert_eq!(zone, "31U");
+        assert!((e - 448_250.503).abs() < 0.01 && (n - 5_411_951.589).abs() < 0.01, "{e} {n}");
+
+        let (e, n, zone) = utm_from_latlon(-33.856_78, 151.215_30);
+        assert_eq!(zone, "56H");
+        assert!((e - 334_900.531).abs() < 0.01 && (n - 6_252_290.971).abs() < 0.01, "{e} {n}");
+
+        assert_eq!(utm_zone(60.0, 5.0), 32);
+        assert_eq!(utm_zone(78.0, 15.0), 33);
+        assert_eq!(utm_zone(0.0, 180.0), 60);
+    }
+
+    #[test]
+    fn ops_check_their_ranges() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        interp.exec_line("0 0 utm_from_latlon").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().to_string(), "31N");
+        assert!((interp.stack_mut().pop_numeric().unwrap()).abs() < 1e-6);
+        assert!((interp.stack_mut().pop_numeric().unwrap() - 166_021.4).abs() < 1.0);
+        assert!(interp.exec_line("85 0 utm_from_latlon").is_err());
+        assert!(interp.exec_line("10 61 0 deg_min_sec_to_dd").is_err());
+    }
+}
@@ -13,7 +13,6 @@
 //! - `Σ`, `sum` - Sum n values from stack
 //! - `Π`, `product` - Product of n values
 //! - `Δ`, `delta` - Difference (b - a)
-//! - `√`, `sqrt` - Square root
 
 use std::f64::consts::PI;
 use woflang_core::{WofValue, InterpreterContext};
@@ -174,13 +173,6 @@ pub fn register(interp: &mut Interpreter) {
         Ok(())
     });
 
-    // √ - Square root
-    interp.register("√", |interp| {
-        let x = interp.stack_mut().pop()?.as_double()?;
-        interp.stack_mut().push(WofValue::double(x.sqrt()));
-        Ok(())
-    });
-
     // ∛ - Cube root
     interp.register("∛", |interp| {
         let x = interp.stack_mut().pop()?.as_double()?;
@@ -306,33 +298,33 @@ pub fn register(interp: &mut Interpreter) {
     });
 
     // Help
-    interp.register("greek_help", |_interp| {
-        println!("Greek Symbol Operations:");
-        println!();
-        println!("  Constants:");
-        println!("    π/pi/PI      → 3.14159...");
-        println!("    τ/tau        → 2π");
-        println!("    ε            → e (2.71828...)");
-        println!("    φ/phi        → golden ratio");
-        println!("    ∞/inf        → infinity");
-        println!("    ∅/empty/void → nil");
-        println!();
-        println!("  Aggregation:");
-        println!("    v1..vn n Σ/sum     → sum of n values");
-        println!("    v1..vn n Π/product → product of n values");
-        println!();
-        println!("  Operations:");
-        println!("    a b Δ/delta  → b - a");
-        println!("    x √          → √x");
-        println!("    x ∛          → ∛x");
-        println!("    x ∜          → ∜x");
-        println!("    x y ±        → (x+y) (x-y)");
-        println!();
-        println!("  Comparisons:");
-        println!("    a b ≤        → a ≤ b");
-        println!("    a b ≥        → a ≥ b");
-        println!("    a b ≠        → a ≠ b");
-        println!("    a b ≈        → approximately equal");
+    interp.register("greek_help", |interp| {
+        writeln!(interp.out(), "Greek Symbol Operations:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Constants:");
+        writeln!(interp.out(), "    π/pi/PI      → 3.14159...");
+        writeln!(interp.out(), "    τ/tau        → 2π");
+        writeln!(interp.out(), "    ε            → e (2.71828...)");
+        writeln!(interp.out(), "    φ/phi        → golden ratio");
+        writeln!(interp.out(), "    ∞/inf        → infinity");
+        writeln!(interp.out(), "    ∅/empty/void → nil");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Aggregation:");
+        writeln!(interp.out(), "    v1..vn n Σ/sum     → sum of n values");
+        writeln!(interp.out(), "    v1..vn n Π/product → product of n values");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Operations:");
+        writeln!(interp.out(), "    a b Δ/delta  → b - a");
+        writeln!(interp.out(), "    x √          → √x");
+        writeln!(interp.out(), "    x ∛          → ∛x");
+        writeln!(interp.out(), "    x ∜          → ∜x");
+        writeln!(interp.out(), "    x y ±        → (x+y) (x-y)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Comparisons:");
+        writeln!(interp.out(), "    a b ≤        → a ≤ b");
+        writeln!(interp.out(), "    a b ≥        → a ≥ b");
+        writeln!(interp.out(), "    a b ≠        → a ≠ b");
+        writeln!(interp.out(), "    a b ≈        → approximately equal");
         Ok(())
     });
 }
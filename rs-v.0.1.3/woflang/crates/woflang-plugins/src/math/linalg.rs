@@ -0,0 +1,555 @@
+//! Linear algebra on stack matrices and sparse matrix values.
+//!
+//! A dense matrix lives on the stack as its entries in row-major order
+//! followed by its row and column counts, extending the counted-segment
+//! convention of lists: the 2×2 identity is `1 0 0 1 2 2`. A vector is a
+//! counted list, `x1 .. xn n`.
+//!
+//! Sparse matrices are too big to spread over the stack, so they are a
+//! single `csr` record value holding the compressed sparse row arrays
+//! (`rows`, `cols`, `indptr`, `indices`, `values`). They are built from
+//! `row col value` triplets, where repeated positions add up.
+//!
+//! | Operation          | Stack Effect                          | Description |
+//! |--------------------|---------------------------------------|-------------|
+//! | `mat_identity`     | ( n -- I )                            | n×n identity |
+//! | `mat_transpose`    | ( A -- Aᵀ )                           | Transpose |
+//! | `mat_mul`          | ( A B -- AB )                         | Matrix product |
+//! | `mat_stochastic`   | ( A -- P )                            | Scale rows to sum to 1 (zero rows become uniform) |
+//! | `mat_power_iter`   | ( A iters -- v1 .. vn n λ )           | Dominant eigenvalue and unit eigenvector |
+//! | `sp_from_triplets` | ( i1 j1 v1 .. ik jk vk k m n -- S )   | m×n sparse matrix from k triplets |
+//! | `sp_matvec`        | ( S x1 .. xn n -- y1 .. ym m )        | Sparse matrix-vector product |
+//! | `sp_solve`         | ( S b1 .. bn n -- x1 .. xn n )        | Solve Sx = b by conjugate gradient |
+//!
+//! ```text
+//! 2 1 1 2 2 2 100 mat_power_iter   # 0.707 0.707 2 3.0
+//! 0 0 4  0 1 1  1 0 1  1 1 3  4 2 2 sp_from_triplets
+//! 1 2 2 sp_solve                    # 0.0909 0.6364 2
+//! ```
+//!
+//! `sp_solve` needs a symmetric positive definite matrix, as graph
+//! Laplacians plus a diagonal shift and PDE stencils are; it fails rather
+//! than return a poor answer when the iteration does not converge.
+
+use woflang_core::{InterpreterContext, RecordType, Result, WofError, WofRecord, WofValue};
+use woflang_runtime::Interpreter;
+
+/// A dense row-major matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<f64>,
+}
+
+impl Matrix {
+    /// An all-zero `rows`×`cols` matrix.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, data: vec![0.0; rows * cols] }
+    }
+
+    /// The n×n identity.
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m.set(i, i, 1.0);
+        }
+        m
+    }
+
+    /// Entry `(i, j)`.
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        self.data[i * self.cols + j]
+    }
+
+    /// Set entry `(i, j)`.
+    pub fn set(&mut self, i: usize, j: usize, value: f64) {
+        self.data[i * self.cols + j] = value;
+    }
+
+    /// Pop `( a11 .. amn m n -- )`.
+    pub fn pop(interp: &mut Interpreter, op: &str) -> Result<Self> {
+        let cols = interp.stack_mut().pop_integer()?;
+        let rows = interp.stack_mut().pop_integer()?;
+        let (Ok(rows), Ok(cols)) = (usize::try_from(rows), usize::try_from(cols)) else {
+            return Err(WofError::InvalidArgument(format!(
+                "{op}: matrix dimensions must be >= 0, got {rows}×{cols}"
+            )));
+        };
+        let len = rows
+            .checked_mul(cols)
+            .filter(|&len| interp.stack().has(len))
+            .ok_or_else(|| {
+                WofError::InvalidArgument(format!(
+                    "{op}: a {rows}×{cols} matrix needs {} entries on the stack",
+                    rows.saturating_mul(cols)
+                ))
+            })?;
+        let mut data = interp
+            .stack_mut()
+            .pop_n(len)?
+            .iter()
+            .map(WofValue::as_double)
+            .collect::<Result<Vec<_>>>()?;
+        data.reverse();
+        Ok(Self { rows, cols, data })
+    }
+
+    /// Push `( -- a11 .. amn m n )`.
+    pub fn push(self, interp: &mut Interpreter) {
+        interp.stack_mut().push_all(self.data.into_iter().map(WofValue::double));
+        interp.push(WofValue::integer(self.rows as i64));
+        interp.push(WofValue::integer(self.cols as i64));
+    }
+
+    /// Pop a matrix that must be square.
+    pub fn pop_square(interp: &mut Interpreter, op: &str) -> Result<Self> {
+        let m = Self::pop(interp, op)?;
+        if m.rows != m.cols {
+            return Err(WofError::InvalidArgument(format!(
+                "{op}: expected a square matrix, got {}×{}",
+                m.rows, m.cols
+            )));
+        }
+        Ok(m)
+    }
+
+    /// The transpose.
+    pub fn transpose(&self) -> Self {
+        let mut t = Self::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                t.set(j, i, self.get(i, j));
+            }
+        }
+        t
+    }
+
+    /// The product `self · other`, if the shapes agree.
+    pub fn mul(&self, other: &Self) -> Option<Self> {
+        if self.cols != other.rows {
+            return None;
+        }
+        let mut out = Self::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self.get(i, k);
+                if a == 0.0 {
+                    continue;
+                }
+                for j in 0..other.cols {
+                    out.data[i * other.cols + j] += a * other.get(k, j);
+                }
+            }
+        }
+        Some(out)
+    }
+
+    /// `self · v`.
+    pub fn mul_vec(&self, v: &[f64]) -> Vec<f64> {
+        (0..self.rows)
+            .map(|i| (0..self.cols).map(|j| self.get(i, j) * v[j]).sum())
+            .collect()
+    }
+
+    /// Scale each row to sum to 1; rows summing to 0 become uniform.
+    pub fn stochastic(&self) -> Self {
+        let mut p = self.clone();
+        for i in 0..self.rows {
+            let row = &mut p.data[i * self.cols..(i + 1) * self.cols];
+            let sum: f64 = row.iter().sum();
+            for x in row.iter_mut() {
+                *x = if sum == 0.0 { 1.0 / self.cols as f64 } else { *x / sum };
+            }
+        }
+        p
+    }
+
+    /// Dominant eigenvalue and unit eigenvector of a square matrix by
+    /// power iteration from the all-ones vector.
+    pub fn power_iteration(&self, iterations: usize) -> (f64, Vec<f64>) {
+        let n = self.rows;
+        if n == 0 {
+            return (0.0, Vec::new());
+        }
+        let mut v = vec![1.0 / (n as f64).sqrt(); n];
+        let mut lambda = 0.0;
+        for _ in 0..iterations {
+            let w = self.mul_vec(&v);
+            let norm = w.iter().map(|x| x * x).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                return (0.0, v);
+            }
+            // Rayleigh quotient of the current (unit) vector.
+            lambda = v.iter().zip(&w).map(|(a, b)| a * b).sum();
+            v = w.into_iter().map(|x| x / norm).collect();
+        }
+        (lambda, v)
+    }
+}
+
+/// A sparse matrix in compressed sparse row form: the entries of row `i`
+/// are `values[indptr[i]..indptr[i + 1]]`, in columns `indices[..]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Csr {
+    pub rows: usize,
+    pub cols: usize,
+    pub indptr: Vec<usize>,
+    pub indices: Vec<usize>,
+    pub values: Vec<f64>,
+}
+
+/// Field names of the `csr` record.
+const CSR_FIELDS: [&str; 5] = ["rows", "cols", "indptr", "indices", "values"];
+
+impl Csr {
+    /// Build from `(row, col, value)` triplets, summing repeats. Entries
+    /// within a row are sorted by column. `None` if a position is out of
+    /// range.
+    pub fn from_triplets(rows: usize, cols: usize, mut triplets: Vec<(usize, usize, f64)>) -> Option<Self> {
+        if triplets.iter().any(|&(i, j, _)| i >= rows || j >= cols) {
+            return None;
+        }
+        triplets.sort_by_key(|&(i, j, _)| (i, j));
+        let mut indptr = vec![0; rows + 1];
+        let mut indices: Vec<usize> = Vec::with_capacity(triplets.len());
+        let mut values: Vec<f64> = Vec::with_capacity(triplets.len());
+        let mut last = None;
+        for (i, j, v) in triplets {
+            if last == Some((i, j)) {
+                *values.last_mut()? += v;
+                continue;
+            }
+            last = Some((i, j));
+            indptr[i + 1] += 1;
+            indices.push(j);
+            values.push(v);
+        }
+        for i in 0..rows {
+            indptr[i + 1] += indptr[i];
+        }
+        Some(Self { rows, cols, indptr, indices, values })
+    }
+
+    /// `self · v`.
+    pub fn mul_vec(&self, v: &[f64]) -> Vec<f64> {
+        (0..self.rows)
+            .map(|i| {
+                let range = self.indptr[i]..self.indptr[i + 1];
+                self.indices[range.clone()].iter().zip(&self.values[range]).map(|(&j, a)| a * v[j]).sum()
+            })
+            .collect()
+    }
+
+    /// Solve `self · x = b` by conjugate gradient from `x = 0`, stopping
+    /// when the residual norm falls below `tol · |b|`. Returns the
+    /// solution, or `None` if `max_iter` steps were not enough.
+    pub fn conjugate_gradient(&self, b: &[f64], tol: f64, max_iter: usize) -> Option<Vec<f64>> {
+        let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>();
+        let mut x = vec![0.0; b.len()];
+        let mut r = b.to_vec();
+        let mut p = r.clone();
+        let mut rr = dot(&r, &r);
+        let target = tol * tol * rr.max(f64::MIN_POSITIVE);
+        for _ in 0..=max_iter {
+            if rr <= target {
+                return Some(x);
+            }
+            let ap = self.mul_vec(&p);
+            let pap = dot(&p, &ap);
+            if pap <= 0.0 || !pap.is_finite() {
+                return None;
+            }
+            let alpha = rr / pap;
+            for k in 0..x.len() {
+                x[k] += alpha * p[k];
+                r[k] -= alpha * ap[k];
+            }
+            let next = dot(&r, &r);
+            for k in 0..p.len() {
+                p[k] = r[k] + next / rr * p[k];
+            }
+            rr = next;
+        }
+        None
+    }
+
+    /// The `csr` record value.
+    pub fn to_value(&self) -> WofValue {
+        let ints = |xs: &[usize]| WofValue::list(xs.iter().map(|&x| WofValue::integer(x as i64)).collect());
+        let fields = vec![
+            WofValue::integer(self.rows as i64),
+            WofValue::integer(self.cols as i64),
+            ints(&self.indptr),
+            ints(&self.indices),
+            WofValue::list(self.values.iter().map(|&v| WofValue::double(v)).collect()),
+        ];
+        WofValue::record(WofRecord::new(RecordType::new("csr", CSR_FIELDS), fields).expect("five csr fields"))
+    }
+
+    /// Read a `csr` record value, checking that its arrays are consistent.
+    pub fn from_value(value: &WofValue, op: &str) -> Result<Self> {
+        let bad = || WofError::InvalidArgument(format!("{op}: expected a sparse matrix (csr record)"));
+        let record = value.as_record().map_err(|_| bad())?;
+        if record.record_type().name() != "csr" || !record.record_type().fields().eq(CSR_FIELDS) {
+            return Err(bad());
+        }
+        let field = |name: &str| record.get(name).ok_or_else(bad);
+        let size = |v: &WofValue| v.as_integer().ok().and_then(|n| usize::try_from(n).ok()).ok_or_else(bad);
+        let sizes = |name: &str| field(name)?.as_list().map_err(|_| bad())?.iter().map(size).collect::<Result<Vec<_>>>();
+
+        let rows = size(field("rows")?)?;
+        let cols = size(field("cols")?)?;
+        let indptr = sizes("indptr")?;
+        let indices = sizes("indices")?;
+        let values = field("values")?
+            .as_list()
+            .map_err(|_| bad())?
+            .iter()
+            .map(WofValue::as_double)
+            .collect::<Result<Vec<_>>>()?;
+        let consistent = indptr.len() == rows + 1
+            && indptr.first() == Some(&0)
+            && indptr.windows(2).all(|w| w[0] <= w[1])
+            && indptr.last() == Some(&indices.len())
+            && indices.len() == values.len()
+            && indices.iter().all(|&j| j < cols);
+        if !consistent {
+            return Err(bad());
+        }
+        Ok(Self { rows, cols, indptr, indices, values })
+    }
+}
+
+/// Pop a counted vector `( x1 .. xn n -- )`.
+fn pop_vector(interp: &mut Interpreter, op: &str) -> Result<Vec<f64>> {
+    let n = interp.stack_mut().pop_integer()?;
+    let n = usize::try_from(n)
+        .ok()
+        .filter(|&n| interp.stack().has(n))
+        .ok_or_else(|| WofError::InvalidArgument(format!("{op}: a vector of length {n} needs {n} values on the stack")))?;
+    let mut v = interp
+        .stack_mut()
+        .pop_n(n)?
+        .iter()
+        .map(WofValue::as_double)
+        .collect::<Result<Vec<_>>>()?;
+    v.reverse();
+    Ok(v)
+}
+
+/// Push a counted vector `( -- x1 .. xn n )`.
+fn push_vector(interp: &mut Interpreter, v: Vec<f64>) {
+    let n = v.len();
+    interp.stack_mut().push_all(v.into_iter().map(WofValue::double));
+    interp.push(WofValue::integer(n as i64));
+}
+
+/// Register linear algebra operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: n → I
+    interp.register("mat_identity", |interp| {
+        let n = interp.stack_mut().pop_integer()?;
+        let n = usize::try_from(n)
+            .map_err(|_| WofError::InvalidArgument("mat_identity: n must be >= 0".into()))?;
+        Matrix::identity(n).push(interp);
+        Ok(())
+    });
+
+    // Stack: A → Aᵀ
+    interp.register("mat_transpose", |interp| {
+        Matrix::pop(interp, "mat_transpose")?.transpose().push(interp);
+        Ok(())
+    });
+
+    // Stack: A B → AB
+    interp.register("mat_mul", |interp| {
+        let b = Matrix::pop(interp, "mat_mul")?;
+        let a = Matrix::pop(interp, "mat_mul")?;
+        let product = a.mul(&b).ok_or_else(|| {
+            WofError::InvalidArgument(format!(
+                "mat_mul: cannot multiply {}×{} by {}×{}",
+                a.rows, a.cols, b.rows, b.cols
+            ))
+        })?;
+        product.push(interp);
+        Ok(())
+    });
+
+    // Stack: A → P (row-stochastic)
+    interp.register("mat_stochastic", |interp| {
+        Matrix::pop(interp, "mat_stochastic")?.stochastic().push(interp);
+        Ok(())
+    });
+
+    // Stack: A iters → v1 .. vn n λ
+    interp.register("mat_power_iter", |interp| {
+        let iterations = interp.stack_mut().pop_integer()?.max(0) as usize;
+        let m = Matrix::pop_square(interp, "mat_power_iter")?;
+        let (lambda, v) = m.power_iteration(iterations);
+        push_vector(interp, v);
+        interp.push(WofValue::double(lambda));
+        Ok(())
+    });
+
+    // Stack: i1 j1 v1 .. ik jk vk k m n → S
+    interp.register("sp_from_triplets", |interp| {
+        let cols = interp.stack_mut().pop_integer()?;
+        let rows = interp.stack_mut().pop_integer()?;
+        let count = interp.stack_mut().pop_integer()?;
+        let (Ok(rows), Ok(cols), Ok(count)) = (usize::try_from(rows), usize::try_from(cols), usize::try_from(count)) else {
+            return Err(WofError::InvalidArgument(
+                "sp_from_triplets: counts and dimensions must be >= 0".into(),
+            ));
+        };
+        if !count.checked_mul(3).is_some_and(|len| interp.stack().has(len)) {
+            return Err(WofError::InvalidArgument(format!(
+                "sp_from_triplets: {count} triplets need {} values on the stack",
+                count.saturating_mul(3)
+            )));
+        }
+        let mut triplets = Vec::with_capacity(count);
+        for _ in 0..count {
+            let v = interp.stack_mut().pop_numeric()?;
+            let j = interp.stack_mut().pop_integer()?;
+            let i = interp.stack_mut().pop_integer()?;
+            let position = |x: i64| usize::try_from(x).unwrap_or(usize::MAX);
+            triplets.push((position(i), position(j), v));
+        }
+        let csr = Csr::from_triplets(rows, cols, triplets).ok_or_else(|| {
+            WofError::InvalidArgument(format!("sp_from_triplets: a position lies outside {rows}×{cols}"))
+        })?;
+        interp.push(csr.to_value());
+        Ok(())
+    });
+
+    // Stack: S x1 .. xn n → y1 .. ym m
+    interp.register("sp_matvec", |interp| {
+        let x = pop_vector(interp, "sp_matvec")?;
+        let s = Csr::from_value(&interp.stack_mut().pop()?, "sp_matvec")?;
+        if x.len() != s.cols {
+            return Err(WofError::InvalidArgument(format!(
+                "sp_matvec: cannot multiply {}×{} by a vector of length {}",
+                s.rows,
+                s.cols,
+                x.len()
+            )));
+        }
+        push_vector(interp, s.mul_vec(&x));
+        Ok(())
+    });
+
+    // Stack: S b1 .. bn n → x1 .. xn n
+    interp.register("sp_solve", |interp| {
+        let b = pop_vector(interp, "sp_solve")?;
+        let s = Csr::from_value(&interp.stack_mut().pop()?, "sp_solve")?;
+        if s.rows != s.cols || b.len() != s.rows {
+            return Err(WofError::InvalidArgument(format!(
+                "sp_solve: need a square matrix and a matching vector, got {}×{} and length {}",
+                s.rows,
+                s.cols,
+                b.len()
+            )));
+        }
+        let max_iter = (10 * s.rows).max(100);
+        let x = s.conjugate_gradient(&b, 1e-10, max_iter).ok_or_else(|| {
+            WofError::Runtime(format!(
+                "sp_solve: conjugate gradient did not converge in {max_iter} steps \
+                 (the matrix must be symmetric positive definite)"
+            ))
+        })?;
+        push_vector(interp, x);
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(interp: &Interpreter) -> Vec<f64> {
+        interp.stack().iter().map(|v| v.as_double().unwrap()).collect()
+    }
+
+    #[test]
+    fn products_and_transposes() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        interp.exec_line("1 2 3 4 5 6 2 3  1 0 0 1 1 1 3 2 mat_mul").unwrap();
+        assert_eq!(numbers(&interp), [4.0, 5.0, 10.0, 11.0, 2.0, 2.0]);
+
+        interp.stack_mut().clear();
+        interp.exec_line("1 2 3 4 5 6 2 3 mat_transpose").unwrap();
+        assert_eq!(numbers(&interp), [1.0, 4.0, 2.0, 5.0, 3.0, 6.0, 3.0, 2.0]);
+
+        interp.stack_mut().clear();
+        assert!(interp.exec_line("1 2 2 1 1 2 3 4 2 2 mat_mul").is_err());
+        interp.stack_mut().clear();
+        assert!(interp.exec_line("1 2 3 2 2 mat_transpose").is_err());
+    }
+
+    #[test]
+    fn power_iteration_finds_the_dominant_pair() {
+        let m = Matrix { rows: 2, cols: 2, data: vec![2.0, 1.0, 1.0, 2.0] };
+        let (lambda, v) = m.power_iteration(50);
+        assert!((lambda - 3.0).abs() < 1e-9);
+        assert!(v.iter().all(|x| (x - 0.5f64.sqrt()).abs() < 1e-9));
+
+        let p = Matrix { rows: 2, cols: 2, data: vec![0.0, 0.0, 1.0, 3.0] }.stochastic();
+        assert_eq!(p.data, [0.5, 0.5, 0.25, 0.75]);
+    }
+
+    #[test]
+    fn sparse_triplets_products_and_solves() {
+        let s = Csr::from_triplets(2, 3, vec![(1, 2, 5.0), (0, 1, 2.0), (1, 0, 1.0), (0, 1, 1.0)]).unwrap();
+        assert_eq!((s.indptr.as_slice(), s.indices.as_slice()), (&[0, 1, 3][..], &[1, 0, 2][..]));
+        assert_eq!(s.values, [3.0, 1.0, 5.0]);
+        assert_eq!(s.mul_vec(&[1.0, 1.0, 2.0]), [3.0, 11.0]);
+        assert_eq!(Csr::from_value(&s.to_value(), "t").unwrap(), s);
+        assert!(Csr::from_triplets(2, 2, vec![(2, 0, 1.0)]).is_none());
+
+        // 1-D Poisson problem: tridiagonal (-1, 2, -1), a PDE-grid system.
+        let n = 200;
+        let mut triplets = Vec::new();
+        for i in 0..n {
+            triplets.push((i, i, 2.0));
+            if i > 0 {
+                triplets.push((i, i - 1, -1.0));
+                triplets.push((i - 1, i, -1.0));
+            }
+        }
+        let a = Csr::from_triplets(n, n, triplets).unwrap();
+        let b = vec![1.0; n];
+        let x = a.conjugate_gradient(&b, 1e-10, 10 * n).unwrap();
+        let residual: f64 = a.mul_vec(&x).iter().zip(&b).map(|(y, b)| (y - b).powi(2)).sum();
+        assert!(residual.sqrt() < 1e-6, "{residual}");
+
+        let indefinite = Csr::from_triplets(2, 2, vec![(0, 0, 1.0), (1, 1, -1.0)]).unwrap();
+        assert!(indefinite.conjugate_gradient(&[1.0, 1.0], 1e-10, 100).is_none());
+    }
+
+    #[test]
+    fn sparse_ops_take_csr_values() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        let spd = "0 0 4  0 1 1  1 0 1  1 1 3  4 2 2 sp_from_triplets";
+        interp.exec_line(&format!("{spd} 1 2 2 sp_solve")).unwrap();
+        let x = numbers_after(&mut interp, 3);
+        assert!((x[0] - 1.0 / 11.0).abs() < 1e-9 && (x[1] - 7.0 / 11.0).abs() < 1e-9, "{x:?}");
+        assert_eq!(x[2], 2.0);
+
+        interp.exec_line(&format!("{spd} 1 1 2 sp_matvec")).unwrap();
+        assert_eq!(numbers_after(&mut interp, 3), [5.0, 4.0, 2.0]);
+        assert!(interp.stack().is_empty());
+
+        assert!(interp.exec_line("0 0 1  1 2 2 sp_from_triplets 1 1 sp_matvec").is_err());
+        interp.stack_mut().clear();
+        assert!(interp.exec_line("1 2 3 sp_solve").is_err());
+    }
+
+    fn numbers_after(interp: &mut Interpreter, n: usize) -> Vec<f64> {
+        let mut values: Vec<f64> = (0..n).map(|_| interp.stack_mut().pop_numeric().unwrap()).collect();
+        values.reverse();
+        values
+    }
+}
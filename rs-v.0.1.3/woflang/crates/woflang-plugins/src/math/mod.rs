@@ -1,17 +1,27 @@
 //! Mathematical operations for Woflang.
 //!
-//! Includes trigonometry, exponentials, logarithms, calculus operations,
-//! geometry, fractals, and general mathematical functions.
+//! Includes trigonometry, exponentials, logarithms, complex numbers,
+//! calculus operations, geometry, geodesy, fractals, dense linear
+//! algebra, terminal plots, Fourier series, statistics and simulation,
+//! time series resampling and smoothing, and general mathematical
+//! functions.
 
 mod trig;
 mod expo_log;
 mod basic;
+mod complex;
 mod calculus;
 mod discrete;
+mod geodesy;
 mod geometry;
 mod gradient;
+mod plot;
+mod fourier;
+mod stats;
+mod series;
 mod fractal;
 mod greek;
+pub(crate) mod linalg;
 
 use woflang_runtime::Interpreter;
 
@@ -20,10 +30,17 @@ pub fn register(interp: &mut Interpreter) {
     trig::register(interp);
     expo_log::register(interp);
     basic::register(interp);
+    complex::register(interp);
     calculus::register(interp);
     discrete::register(interp);
     geometry::register(interp);
+    geodesy::register(interp);
     gradient::register(interp);
+    plot::register(interp);
+    fourier::register(interp);
+    stats::register(interp);
+    series::register(interp);
     fractal::register(interp);
     greek::register(interp);
+    linalg::register(interp);
 }
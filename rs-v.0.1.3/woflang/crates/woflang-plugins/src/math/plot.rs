@@ -0,0 +1,458 @@
+//! Plotting functions in the terminal and to images.
+//!
+//! ## Operations
+//!
+//! - `plot_fn` - Chart y = f(x) over a range ("f" xmin xmax →)
+//! - `plot_surface` - Render z = f(x, y) as a PNG ("f" xmin xmax ymin ymax "file.png" →)
+//!
+//! The function is one defined with `⊕`, called with `x` on top of the
+//! stack and leaving `y` in its place, as for `list_map`. It is sampled
+//! once per braille dot column and drawn with braille characters (2×4
+//! dots per cell), with the y range fitted to the values and the axes
+//! marked where they cross the chart. Samples that are not finite leave
+//! a gap, so asymptotes do not flatten the rest of the curve.
+//!
+//! ```text
+//! ⊕ wave ⺆ sin ⺘
+//! "wave" -3.14159 3.14159 plot_fn
+//! ```
+//!
+//! `plot_surface` calls its function with `x` and then `y` on the stack
+//! and draws the sampled grid in isometric projection, far quads first
+//! (painter's algorithm), coloured from blue at the lowest value to red
+//! at the highest and shaded by a fixed light:
+//!
+//! ```text
+//! ⊕ bowl ⺆ dup * swap dup * + ⺘
+//! "bowl" -1 1 -1 1 "bowl.png" plot_surface
+//! ```
+
+use std::fmt::Write as _;
+
+use miniz_oxide::deflate::compress_to_vec_zlib;
+
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+use crate::braille::{self, label};
+
+/// Chart width in character cells.
+const COLS: usize = 60;
+
+/// Chart height in character cells.
+const ROWS: usize = 16;
+
+/// The chart's dots.
+type Canvas = braille::Canvas<COLS, ROWS>;
+
+/// Samples [`render`] draws, one per dot column.
+pub(super) const SAMPLES: usize = Canvas::WIDTH;
+
+/// Render samples of y taken evenly across `[xmin, xmax]`, one per dot
+/// column, as an axis-labelled chart. `None` when no sample is finite.
+pub(super) fn render(samples: &[f64], xmin: f64, xmax: f64) -> Option<String> {
+    let finite = samples.iter().copied().filter(|y| y.is_finite());
+    let (mut lo, mut hi) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), y| (lo.min(y), hi.max(y)));
+    if lo > hi {
+        return None;
+    }
+    if hi - lo < 1e-12 {
+        (lo, hi) = (lo - 1.0, hi + 1.0);
+    }
+
+    let max_y = Canvas::HEIGHT - 1;
+    let to_row = |y: f64| ((hi - y) / (hi - lo) * max_y as f64).round() as usize;
+    let to_col = |x: f64| ((x - xmin) / (xmax - xmin) * (Canvas::WIDTH - 1) as f64).round() as usize;
+
+    let mut canvas = Canvas::new();
+    let zero_row = (lo..=hi).contains(&0.0).then(|| to_row(0.0));
+    if let Some(row) = zero_row {
+        (0..Canvas::WIDTH).step_by(2).for_each(|x| canvas.set(x, row));
+    }
+    if (xmin..=xmax).contains(&0.0) {
+        let col = to_col(0.0);
+        (0..Canvas::HEIGHT).step_by(2).for_each(|y| canvas.set(col, y));
+    }
+
+    // Join each sample to the previous one so steep stretches stay solid.
+    let mut prev: Option<usize> = None;
+    for (x, &y) in samples.iter().enumerate().take(Canvas::WIDTH) {
+        if !y.is_finite() {
+            prev = None;
+            continue;
+        }
+        let row = to_row(y);
+        let (a, b) = prev.map_or((row, row), |p| (p.min(row), p.max(row)));
+        (a..=b).for_each(|r| canvas.set(x, r));
+        prev = Some(row);
+    }
+
+    let mut labels = vec![None; ROWS];
+    labels[0] = Some(label(hi));
+    labels[ROWS - 1] = Some(label(lo));
+    if let Some(row) = zero_row.map(|r| r / 4).filter(|r| (1..ROWS - 1).contains(r)) {
+        labels[row] = Some(label(0.0));
+    }
+    let width = labels.iter().flatten().map(String::len).max().unwrap_or(0);
+
+    let mut out = String::new();
+    for (row, text) in labels.iter().enumerate() {
+        let (text, tick) = text.as_ref().map_or(("", '│'), |t| (t.as_str(), '┤'));
+        let _ = writeln!(out, "{text:>width$} {tick}{}", canvas.row(row));
+    }
+    let _ = writeln!(out, "{:width$} └{}", "", "─".repeat(COLS));
+    let (left, right) = (label(xmin), label(xmax));
+    let gap = (COLS + 1).saturating_sub(left.len() + right.len());
+    let _ = writeln!(out, "{:width$} {left}{:gap$}{right}", "", "");
+    Some(out)
+}
+
+/// Samples per side of a `plot_surface` grid.
+const GRID: usize = 40;
+
+/// `plot_surface` image width in pixels.
+const IMAGE_WIDTH: usize = 640;
+
+/// `plot_surface` image height in pixels.
+const IMAGE_HEIGHT: usize = 480;
+
+/// A point on the image, in pixels.
+type Pixel = (f64, f64);
+
+/// An RGB image with 8-bit channels.
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    fn new(width: usize, height: usize, background: [u8; 3]) -> Self {
+        Self { width, height, pixels: background.repeat(width * height) }
+    }
+
+    fn put(&mut self, x: i64, y: i64, rgb: [u8; 3]) {
+        if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+            if x < self.width && y < self.height {
+                let i = (y * self.width + x) * 3;
+                self.pixels[i..i + 3].copy_from_slice(&rgb);
+            }
+        }
+    }
+
+    /// Fill the pixels whose centres lie inside the triangle.
+    fn fill_triangle(&mut self, [a, b, c]: [Pixel; 3], rgb: [u8; 3]) {
+        let edge = |p: Pixel, q: Pixel, r: Pixel| (q.0 - p.0) * (r.1 - p.1) - (q.1 - p.1) * (r.0 - p.0);
+        let area = edge(a, b, c);
+        if area == 0.0 {
+            return;
+        }
+        let (x0, x1) = (a.0.min(b.0).min(c.0).floor() as i64, a.0.max(b.0).max(c.0).ceil() as i64);
+        let (y0, y1) = (a.1.min(b.1).min(c.1).floor() as i64, a.1.max(b.1).max(c.1).ceil() as i64);
+        for y in y0.max(0)..=y1.min(self.height as i64 - 1) {
+            for x in x0.max(0)..=x1.min(self.width as i64 - 1) {
+                let p = (x as f64 + 0.5, y as f64 + 0.5);
+                let (w0, w1, w2) = (edge(b, c, p), edge(c, a, p), edge(a, b, p));
+                if [w0, w1, w2].iter().all(|w| w * area >= 0.0) {
+                    self.put(x, y, rgb);
+                }
+            }
+        }
+    }
+
+    fn line(&mut self, from: Pixel, to: Pixel, rgb: [u8; 3]) {
+        let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).ceil().max(1.0);
+        for i in 0..=steps as i64 {
+            let t = i as f64 / steps;
+            let x = from.0 + (to.0 - from.0) * t;
+            let y = from.1 + (to.1 - from.1) * t;
+            self.put(x as i64, y as i64, rgb);
+        }
+    }
+
+    /// Encode as an 8-bit truecolour PNG.
+    fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width * 3) {
+            raw.push(0); // no filter
+            raw.extend_from_slice(row);
+        }
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut out, b"IHDR", &header);
+        png_chunk(&mut out, b"IDAT", &compress_to_vec_zlib(&raw, 6));
+        png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// CRC-32 as used by PNG chunks.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Blue → cyan → green → yellow → red for `t` in `[0, 1]`.
+fn heat(t: f64) -> [f64; 3] {
+    const STOPS: [[f64; 3]; 5] =
+        [[0.2, 0.3, 0.9], [0.1, 0.8, 0.9], [0.2, 0.8, 0.3], [0.95, 0.85, 0.2], [0.9, 0.2, 0.15]];
+    let x = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let i = (x as usize).min(STOPS.len() - 2);
+    let f = x - i as f64;
+    let (a, b) = (STOPS[i], STOPS[i + 1]);
+    [0, 1, 2].map(|k| a[k] + (b[k] - a[k]) * f)
+}
+
+/// Render an `n`×`n` grid of heights (`z[j * n + i]`, `i` along x) as
+/// an isometric surface. Quads with a non-finite corner are left out;
+/// `None` when no height is finite.
+fn render_surface(z: &[f64], n: usize) -> Option<Image> {
+    let finite = z.iter().copied().filter(|v| v.is_finite());
+    let (lo, hi) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    if lo > hi {
+        return None;
+    }
+    let span = if hi - lo < 1e-12 { 1.0 } else { hi - lo };
+
+    // Grid point (u, v, height), all roughly in [-1, 1], and its
+    // projection seen from 45° round and 30° up.
+    let (elev_sin, elev_cos) = 30f64.to_radians().sin_cos();
+    let point = |i: usize, j: usize| {
+        let u = i as f64 / (n - 1) as f64 * 2.0 - 1.0;
+        let v = j as f64 / (n - 1) as f64 * 2.0 - 1.0;
+        let h = (z[j * n + i] - lo) / span - 0.5;
+        (u, v, h)
+    };
+    let project = |(u, v, h): (f64, f64, f64)| {
+        let across = (u - v) * std::f64::consts::FRAC_1_SQRT_2;
+        let depth = (u + v) * std::f64::consts::FRAC_1_SQRT_2;
+        (across, h * elev_cos * 1.2 + depth * elev_sin)
+    };
+
+    // Fit the projected grid into the image with a margin.
+    let projected: Vec<(f64, f64)> = (0..n * n)
+        .filter(|&k| z[k].is_finite())
+        .map(|k| project(point(k % n, k / n)))
+        .collect();
+    let (min_x, max_x) = projected.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), p| (a.min(p.0), b.max(p.0)));
+    let (min_y, max_y) = projected.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), p| (a.min(p.1), b.max(p.1)));
+    let margin = 20.0;
+    let scale = ((IMAGE_WIDTH as f64 - 2.0 * margin) / (max_x - min_x).max(1e-9))
+        .min((IMAGE_HEIGHT as f64 - 2.0 * margin) / (max_y - min_y).max(1e-9));
+    let (off_x, off_y) = (
+        (IMAGE_WIDTH as f64 - (max_x - min_x) * scale) / 2.0,
+        (IMAGE_HEIGHT as f64 - (max_y - min_y) * scale) / 2.0,
+    );
+    let to_pixel = |p: (f64, f64, f64)| {
+        let (x, y) = project(p);
+        (off_x + (x - min_x) * scale, IMAGE_HEIGHT as f64 - off_y - (y - min_y) * scale)
+    };
+
+    // Far quads (large u + v) first, so nearer ones paint over them.
+    let mut quads: Vec<(usize, usize)> = (0..n - 1)
+        .flat_map(|j| (0..n - 1).map(move |i| (i, j)))
+        .filter(|&(i, j)| [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)].iter().all(|&(a, b)| z[b * n + a].is_finite()))
+        .collect();
+    quads.sort_by_key(|&(i, j)| std::cmp::Reverse(i + j));
+
+    let light = {
+        let (x, y, z): (f64, f64, f64) = (-0.4, -0.5, 0.8);
+        let len = (x * x + y * y + z * z).sqrt();
+        (x / len, y / len, z / len)
+    };
+    let mut image = Image::new(IMAGE_WIDTH, IMAGE_HEIGHT, [255, 255, 255]);
+    for (i, j) in quads {
+        let corners = [point(i, j), point(i + 1, j), point(i + 1, j + 1), point(i, j + 1)];
+        let height = corners.iter().map(|c| c.2).sum::<f64>() / 4.0 + 0.5;
+
+        // Normal from the diagonals, for diffuse shading.
+        let (a, b) = (
+            (corners[2].0 - corners[0].0, corners[2].1 - corners[0].1, corners[2].2 - corners[0].2),
+            (corners[3].0 - corners[1].0, corners[3].1 - corners[1].1, corners[3].2 - corners[1].2),
+        );
+        let normal = (a.1 * b.2 - a.2 * b.1, a.2 * b.0 - a.0 * b.2, a.0 * b.1 - a.1 * b.0);
+        let len = (normal.0 * normal.0 + normal.1 * normal.1 + normal.2 * normal.2).sqrt().max(1e-12);
+        let diffuse = ((normal.0 * light.0 + normal.1 * light.1 + normal.2 * light.2) / len).abs();
+        let shade = 0.45 + 0.55 * diffuse;
+
+        let fill = heat(height).map(|c| (c * shade * 255.0).round().clamp(0.0, 255.0) as u8);
+        let edge = fill.map(|c| (f64::from(c) * 0.6) as u8);
+        let px = corners.map(to_pixel);
+        image.fill_triangle([px[0], px[1], px[2]], fill);
+        image.fill_triangle([px[0], px[2], px[3]], fill);
+        for k in 0..4 {
+            image.line(px[k], px[(k + 1) % 4], edge);
+        }
+    }
+    Some(image)
+}
+
+/// Register the plotting operations.
+pub fn register(interp: &mut Interpreter) {
+    // Chart a function: "f" xmin xmax →
+    interp.register("plot_fn", |interp| {
+        let xmax = interp.stack_mut().pop_numeric()?;
+        let xmin = interp.stack_mut().pop_numeric()?;
+        let function = interp.stack_mut().pop_string()?;
+        if !interp.has_function(&function) {
+            return Err(WofError::runtime(format!("plot_fn: no function named '{function}'")));
+        }
+        if !(xmin.is_finite() && xmax.is_finite() && xmin < xmax) {
+            return Err(WofError::runtime("plot_fn: need finite xmin < xmax"));
+        }
+
+        let mut samples = Vec::with_capacity(Canvas::WIDTH);
+        for i in 0..Canvas::WIDTH {
+            let x = xmin + (xmax - xmin) * i as f64 / (Canvas::WIDTH - 1) as f64;
+            samples.push(sample(interp, "plot_fn", &function, &[x])?);
+        }
+        let chart = render(&samples, xmin, xmax).ok_or_else(|| {
+            WofError::runtime(format!("plot_fn: '{function}' has no finite values on [{xmin}, {xmax}]"))
+        })?;
+        write!(interp.out(), "{chart}");
+        Ok(())
+    });
+
+    // Render a surface: "f" xmin xmax ymin ymax "file.png" →
+    interp.register("plot_surface", |interp| {
+        let path = interp.stack_mut().pop_string()?;
+        let ymax = interp.stack_mut().pop_numeric()?;
+        let ymin = interp.stack_mut().pop_numeric()?;
+        let xmax = interp.stack_mut().pop_numeric()?;
+        let xmin = interp.stack_mut().pop_numeric()?;
+        let function = interp.stack_mut().pop_string()?;
+        let resolved = interp.sandbox().check_write(&path)?;
+        if !interp.has_function(&function) {
+            return Err(WofError::runtime(format!("plot_surface: no function named '{function}'")));
+        }
+        if ![xmin, xmax, ymin, ymax].iter().all(|v| v.is_finite()) || xmin >= xmax || ymin >= ymax {
+            return Err(WofError::runtime("plot_surface: need finite xmin < xmax and ymin < ymax"));
+        }
+
+        let step = |lo: f64, hi: f64, k: usize| lo + (hi - lo) * k as f64 / (GRID - 1) as f64;
+        let mut heights = Vec::with_capacity(GRID * GRID);
+        for j in 0..GRID {
+            for i in 0..GRID {
+                let args = [step(xmin, xmax, i), step(ymin, ymax, j)];
+                heights.push(sample(interp, "plot_surface", &function, &args)?);
+            }
+        }
+        let image = render_surface(&heights, GRID).ok_or_else(|| {
+            WofError::runtime(format!("plot_surface: '{function}' has no finite values on that range"))
+        })?;
+        std::fs::write(resolved, image.to_png())
+            .map_err(|e| WofError::Runtime(format!("plot_surface: {path}: {e}")))?;
+        Ok(())
+    });
+}
+
+/// Call `function` on `args` and take the single number it leaves.
+pub(super) fn sample(interp: &mut Interpreter, op: &str, function: &str, args: &[f64]) -> Result<f64> {
+    let depth = interp.stack().len();
+    for &arg in args {
+        interp.push(WofValue::double(arg));
+    }
+    interp.run_function(function)?;
+    if interp.stack().len() != depth + 1 {
+        return Err(WofError::runtime(format!("{op}: '{function}' must leave exactly one value")));
+    }
+    interp.stack_mut().pop_numeric()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chart_is_labelled_and_follows_the_curve() {
+        let samples: Vec<f64> = (0..Canvas::WIDTH).map(|i| i as f64 / 119.0 * 2.0 - 1.0).collect();
+        let chart = render(&samples, -1.0, 1.0).unwrap();
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines.len(), ROWS + 2);
+        assert!(lines[0].starts_with(" 1.00 ┤"), "{chart}");
+        assert!(lines[ROWS - 1].starts_with("-1.00 ┤"), "{chart}");
+        assert!(lines[ROWS + 1].trim_start().starts_with("-1.00") && lines[ROWS + 1].ends_with("1.00"));
+        // A rising line: the bottom-left and top-right cells are inked.
+        assert_ne!(lines[ROWS - 1].chars().nth(7), Some('\u{2800}'));
+        assert_ne!(lines[0].chars().last(), Some('\u{2800}'));
+
+        assert!(render(&[f64::NAN; 4], 0.0, 1.0).is_none());
+        assert!(render(&[2.0; Canvas::WIDTH], 0.0, 1.0).unwrap().contains("3.00 ┤"));
+    }
+
+    #[test]
+    fn surface_png_is_well_formed() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+
+        let n = 12;
+        let bowl: Vec<f64> = (0..n * n)
+            .map(|k| {
+                let (x, y) = ((k % n) as f64 - 5.5, (k / n) as f64 - 5.5);
+                x * x + y * y
+            })
+            .collect();
+        let image = render_surface(&bowl, n).unwrap();
+        let at = |x: usize, y: usize| &image.pixels[(y * IMAGE_WIDTH + x) * 3..][..3];
+        assert_eq!(at(0, 0), [255, 255, 255]);
+        assert_ne!(at(IMAGE_WIDTH / 2, IMAGE_HEIGHT / 2), [255, 255, 255]);
+
+        let png = image.to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR"));
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]));
+        let idat_len = u32::from_be_bytes(png[33..37].try_into().unwrap()) as usize;
+        assert_eq!(&png[37..41], b"IDAT");
+        let raw = miniz_oxide::inflate::decompress_to_vec_zlib(&png[41..41 + idat_len]).unwrap();
+        assert_eq!(raw.len(), (IMAGE_WIDTH * 3 + 1) * IMAGE_HEIGHT);
+
+        assert!(render_surface(&[f64::NAN; 4], 2).is_none());
+    }
+
+    #[test]
+    fn plot_fn_samples_a_function() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        interp.register("*", |interp| {
+            let b = interp.stack_mut().pop_numeric()?;
+            let a = interp.stack_mut().pop_numeric()?;
+            interp.push(WofValue::double(a * b));
+            Ok(())
+        });
+        interp.register("dup", |interp| interp.stack_mut().dup());
+        interp.exec_line("⊕ sq ⺆ dup * ⺘").unwrap();
+        interp.exec_line("\"sq\" -2 2 plot_fn").unwrap();
+        assert!(interp.stack().is_empty());
+        assert!(interp.exec_line("\"nope\" 0 1 plot_fn").is_err());
+        assert!(interp.exec_line("\"sq\" 1 1 plot_fn").is_err());
+        interp.exec_line("⊕ two ⺆ dup ⺘").unwrap();
+        assert!(interp.exec_line("\"two\" 0 1 plot_fn").is_err());
+
+        interp.stack_mut().clear();
+        interp.exec_line("⊕ saddle ⺆ * ⺘").unwrap();
+        let path = std::env::temp_dir().join(format!("woflang-surface-{}.png", std::process::id()));
+        interp.exec_line(&format!("\"saddle\" -1 1 -2 2 \"{}\" plot_surface", path.display())).unwrap();
+        let png = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+        assert!(interp.stack().is_empty());
+        assert!(interp.exec_line("\"saddle\" -1 1 2 2 \"x.png\" plot_surface").is_err());
+    }
+}
@@ -0,0 +1,321 @@
+//! Time series: resampling and smoothing.
+//!
+//! ## Operations
+//!
+//! - `resample` - Linear interpolation onto new x positions ([x ...] [y ...] [x' ...] → [y' ...])
+//! - `resample_cubic` - Natural cubic spline onto new x positions ([x ...] [y ...] [x' ...] → [y' ...])
+//! - `ema` - Exponential moving average ([x ...] alpha → [s ...])
+//! - `rolling_mean` - Trailing mean over a window ([x ...] window → [m ...])
+//! - `detrend` - Subtract the least-squares line ([x ...] → [r ...])
+//!
+//! Sensor readings rarely arrive on a regular clock, so the usual first
+//! step is to resample them onto evenly spaced times. The sample times
+//! must increase strictly; points where the time or the reading is nil
+//! are skipped, which fills gaps in a CSV column by interpolation. New
+//! positions before the first or after the last sample take the end
+//! values rather than extrapolating:
+//!
+//! ```text
+//! "log.csv" csv_read
+//! dup "t" table_col swap "temp" table_col
+//! 0 15 30 45 60 5 list_from resample    # a reading every 15 s
+//! 0.2 ema detrend                        # smoothed, without the drift
+//! ```
+//!
+//! `ema` starts from the first value and moves `alpha` of the way
+//! towards each new one, so alpha = 1 leaves the series unchanged.
+//! `rolling_mean` averages each value with up to `window - 1` values
+//! before it, so the result has the same length as its input and lines
+//! up with the same times. `detrend` fits the line against the position
+//! in the list, which assumes evenly spaced samples.
+
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Pop a list of numbers for `op`.
+fn pop_numbers(interp: &mut Interpreter, op: &str) -> Result<Vec<f64>> {
+    let values = interp.stack_mut().pop()?.into_list()?;
+    values
+        .iter()
+        .map(WofValue::as_numeric)
+        .collect::<Result<Vec<_>>>()
+        .map_err(|e| WofError::runtime(format!("{op}: {e}")))
+}
+
+/// Push a list of numbers.
+fn push_numbers(interp: &mut Interpreter, values: impl IntoIterator<Item = f64>) {
+    interp.push(WofValue::list(values.into_iter().map(WofValue::double).collect()));
+}
+
+/// Pop `xs ys new_xs` for `op`: the sample points, without nil pairs,
+/// and the positions to interpolate at.
+fn pop_samples(interp: &mut Interpreter, op: &str) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    let at = pop_numbers(interp, op)?;
+    let ys = interp.stack_mut().pop()?.into_list()?;
+    let xs = interp.stack_mut().pop()?.into_list()?;
+    if xs.len() != ys.len() {
+        return Err(WofError::runtime(format!("{op}: {} x values but {} y values", xs.len(), ys.len())));
+    }
+    let mut points = (Vec::with_capacity(xs.len()), Vec::with_capacity(ys.len()));
+    for (x, y) in xs.iter().zip(&ys).filter(|(x, y)| !x.is_nil() && !y.is_nil()) {
+        let (x, y) = (x.as_numeric(), y.as_numeric());
+        let (x, y) = x.and_then(|x| Ok((x, y?))).map_err(|e| WofError::runtime(format!("{op}: {e}")))?;
+        if points.0.last().is_some_and(|&last| x <= last) {
+            return Err(WofError::runtime(format!("{op}: x values must increase, but {x} follows a larger one")));
+        }
+        points.0.push(x);
+        points.1.push(y);
+    }
+    if points.0.is_empty() {
+        return Err(WofError::runtime(format!("{op}: no samples")));
+    }
+    Ok((points.0, points.1, at))
+}
+
+/// The segment of `xs` holding `x`: the index of its left end, clamped
+/// so that both ends exist.
+fn segment(xs: &[f64], x: f64) -> usize {
+    xs.partition_point(|&v| v <= x).saturating_sub(1).min(xs.len().saturating_sub(2))
+}
+
+/// Piecewise-linear interpolation through `(xs, ys)`, holding the end
+/// values outside them.
+fn linear(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+    let last = xs.len() - 1;
+    if x <= xs[0] {
+        return ys[0];
+    }
+    if x >= xs[last] {
+        return ys[last];
+    }
+    let i = segment(xs, x);
+    let t = (x - xs[i]) / (xs[i + 1] - xs[i]);
+    ys[i] + t * (ys[i + 1] - ys[i])
+}
+
+/// Second derivatives of the natural cubic spline through `(xs, ys)`,
+/// by the Thomas algorithm on its tridiagonal system.
+fn spline_moments(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    let n = xs.len();
+    let mut moments = vec![0.0; n];
+    if n < 3 {
+        return moments;
+    }
+    let h: Vec<f64> = xs.windows(2).map(|w| w[1] - w[0]).collect();
+    let slope = |i: usize| (ys[i + 1] - ys[i]) / h[i];
+    // Forward sweep over the interior points, keeping the eliminated
+    // upper diagonal and right-hand side.
+    let mut upper = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    for i in 1..n - 1 {
+        let diag = 2.0 * (h[i - 1] + h[i]) - h[i - 1] * upper[i - 1];
+        upper[i] = h[i] / diag;
+        rhs[i] = (6.0 * (slope(i) - slope(i - 1)) - h[i - 1] * rhs[i - 1]) / diag;
+    }
+    for i in (1..n - 1).rev() {
+        moments[i] = rhs[i] - upper[i] * moments[i + 1];
+    }
+    moments
+}
+
+/// The natural cubic spline with `moments` through `(xs, ys)` at `x`,
+/// holding the end values outside them.
+fn cubic(xs: &[f64], ys: &[f64], moments: &[f64], x: f64) -> f64 {
+    let last = xs.len() - 1;
+    if x <= xs[0] {
+        return ys[0];
+    }
+    if x >= xs[last] {
+        return ys[last];
+    }
+    let i = segment(xs, x);
+    let h = xs[i + 1] - xs[i];
+    let (a, b) = (xs[i + 1] - x, x - xs[i]);
+    (moments[i] * a.powi(3) + moments[i + 1] * b.powi(3)) / (6.0 * h)
+        + (ys[i] / h - moments[i] * h / 6.0) * a
+        + (ys[i + 1] / h - moments[i + 1] * h / 6.0) * b
+}
+
+/// Exponential moving average with smoothing factor `alpha`.
+fn ema(values: &[f64], alpha: f64) -> Vec<f64> {
+    let mut smoothed = Vec::with_capacity(values.len());
+    for &x in values {
+        let next = smoothed.last().map_or(x, |&s: &f64| s + alpha * (x - s));
+        smoothed.push(next);
+    }
+    smoothed
+}
+
+/// Trailing mean of each value and up to `window - 1` before it.
+fn rolling_mean(values: &[f64], window: usize) -> Vec<f64> {
+    let mut sum = 0.0;
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            sum += x;
+            if i >= window {
+                sum -= values[i - window];
+            }
+            sum / (i + 1).min(window) as f64
+        })
+        .collect()
+}
+
+/// The residuals of `values` from their least-squares line against
+/// the index.
+fn detrend(values: &[f64]) -> Vec<f64> {
+    let n = values.len() as f64;
+    let mean_i = (n - 1.0) / 2.0;
+    let mean_v = values.iter().sum::<f64>() / n;
+    let (mut sxy, mut sxx) = (0.0, 0.0);
+    for (i, v) in values.iter().enumerate() {
+        let di = i as f64 - mean_i;
+        sxy += di * (v - mean_v);
+        sxx += di * di;
+    }
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    values.iter().enumerate().map(|(i, v)| v - mean_v - slope * (i as f64 - mean_i)).collect()
+}
+
+/// Register the time series operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: [x ...] [y ...] [x' ...] → [y' ...]
+    interp.register("resample", |interp| {
+        let (xs, ys, at) = pop_samples(interp, "resample")?;
+        push_numbers(interp, at.iter().map(|&x| linear(&xs, &ys, x)));
+        Ok(())
+    });
+
+    // Stack: [x ...] [y ...] [x' ...] → [y' ...]
+    interp.register("resample_cubic", |interp| {
+        let (xs, ys, at) = pop_samples(interp, "resample_cubic")?;
+        let moments = spline_moments(&xs, &ys);
+        push_numbers(interp, at.iter().map(|&x| cubic(&xs, &ys, &moments, x)));
+        Ok(())
+    });
+
+    // Stack: [x ...] alpha → [s ...]
+    interp.register("ema", |interp| {
+        let alpha = interp.stack_mut().pop_numeric()?;
+        let values = pop_numbers(interp, "ema")?;
+        if !(alpha > 0.0 && alpha <= 1.0) {
+            return Err(WofError::runtime("ema: alpha must be in (0, 1]"));
+        }
+        push_numbers(interp, ema(&values, alpha));
+        Ok(())
+    });
+
+    // Stack: [x ...] window → [m ...]
+    interp.register("rolling_mean", |interp| {
+        let window = interp.stack_mut().pop_integer()?;
+        let values = pop_numbers(interp, "rolling_mean")?;
+        let window = usize::try_from(window)
+            .ok()
+            .filter(|&w| w >= 1)
+            .ok_or_else(|| WofError::runtime("rolling_mean: the window must be at least 1"))?;
+        push_numbers(interp, rolling_mean(&values, window));
+        Ok(())
+    });
+
+    // Stack: [x ...] → [r ...]
+    interp.register("detrend", |interp| {
+        let values = pop_numbers(interp, "detrend")?;
+        if values.is_empty() {
+            return Err(WofError::runtime("detrend: the list is empty"));
+        }
+        push_numbers(interp, detrend(&values));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-9)
+    }
+
+    #[test]
+    fn interpolation() {
+        let (xs, ys) = ([0.0, 1.0, 3.0], [0.0, 10.0, 30.0]);
+        let at = [-1.0, 0.0, 0.5, 2.0, 3.0, 4.0];
+        let linear: Vec<f64> = at.iter().map(|&x| linear(&xs, &ys, x)).collect();
+        assert!(close(&linear, &[0.0, 0.0, 5.0, 20.0, 30.0, 30.0]));
+        assert_eq!(super::linear(&[2.0], &[7.0], 5.0), 7.0);
+
+        // A natural spline reproduces straight lines and passes through
+        // its knots.
+        let moments = spline_moments(&xs, &ys);
+        assert!(close(&moments, &[0.0, 0.0, 0.0]));
+        let xs: Vec<f64> = (0..=8).map(|i| f64::from(i) * 0.5).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| x.sin()).collect();
+        let moments = spline_moments(&xs, &ys);
+        for (x, y) in xs.iter().zip(&ys) {
+            assert!((cubic(&xs, &ys, &moments, *x) - y).abs() < 1e-12);
+        }
+        // Between the knots it is much closer to sin than a straight line.
+        let x = 1.25;
+        assert!((cubic(&xs, &ys, &moments, x) - x.sin()).abs() < 2e-3);
+        assert!((super::linear(&xs, &ys, x) - x.sin()).abs() > 1e-2);
+    }
+
+    #[test]
+    fn smoothing() {
+        assert!(close(&ema(&[1.0, 3.0, 3.0], 0.5), &[1.0, 2.0, 2.5]));
+        assert!(close(&ema(&[1.0, 5.0], 1.0), &[1.0, 5.0]));
+        assert!(close(&rolling_mean(&[1.0, 2.0, 3.0, 4.0, 5.0], 3), &[1.0, 1.5, 2.0, 3.0, 4.0]));
+        assert!(close(&rolling_mean(&[4.0, 2.0], 10), &[4.0, 3.0]));
+        assert!(close(&detrend(&[1.0, 3.0, 5.0, 7.0]), &[0.0; 4]));
+        assert!(close(&detrend(&[1.0, 2.0, 1.0]), &[-1.0 / 3.0, 2.0 / 3.0, -1.0 / 3.0]));
+        assert!(close(&detrend(&[4.0]), &[0.0]));
+    }
+
+    #[test]
+    fn ops_read_lists_and_skip_nil_samples() {
+        let mut interp = Interpreter::new();
+        crate::math::register(&mut interp);
+        let list = |values: &[f64]| WofValue::list(values.iter().copied().map(WofValue::double).collect());
+        let pop = |interp: &mut Interpreter| -> Vec<f64> {
+            let list = interp.stack_mut().pop().unwrap();
+            list.as_list().unwrap().iter().map(|v| v.as_numeric().unwrap()).collect()
+        };
+
+        interp.push(list(&[0.0, 1.0, 2.0, 3.0]));
+        interp.push(WofValue::list(vec![
+            WofValue::integer(0),
+            WofValue::nil(),
+            WofValue::integer(20),
+            WofValue::integer(30),
+        ]));
+        interp.push(list(&[0.5, 1.0, 2.5]));
+        interp.exec_line("resample").unwrap();
+        assert!(close(&pop(&mut interp), &[5.0, 10.0, 25.0]));
+
+        interp.push(list(&[1.0, 3.0, 3.0]));
+        interp.exec_line("0.5 ema").unwrap();
+        assert!(close(&pop(&mut interp), &[1.0, 2.0, 2.5]));
+        interp.push(list(&[2.0, 4.0, 6.0]));
+        interp.exec_line("2 rolling_mean").unwrap();
+        assert!(close(&pop(&mut interp), &[2.0, 3.0, 5.0]));
+        interp.push(list(&[2.0, 4.0, 7.0]));
+        interp.exec_line("detrend").unwrap();
+        assert!(close(&pop(&mut interp), &[1.0 / 6.0, -1.0 / 3.0, 1.0 / 6.0]));
+
+        interp.push(list(&[0.0, 0.0]));
+        interp.push(list(&[1.0, 2.0]));
+        interp.push(list(&[0.0]));
+        assert!(interp.exec_line("resample_cubic").is_err());
+        interp.push(list(&[0.0, 1.0]));
+        interp.push(list(&[1.0]));
+        interp.push(list(&[0.0]));
+        assert!(interp.exec_line("resample").is_err());
+        interp.push(list(&[1.0]));
+        assert!(interp.exec_line("0 ema").is_err());
+        interp.push(list(&[1.0]));
+        assert!(interp.exec_line("0 rolling_mean").is_err());
+        interp.push(WofValue::list(Vec::new()));
+        assert!(interp.exec_line("detrend").is_err());
+    }
+}
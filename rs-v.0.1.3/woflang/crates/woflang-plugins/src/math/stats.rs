@@ -0,0 +1,286 @@
+//! Statistics and probability simulation.
+//!
+//! ## Operations
+//!
+//! - `monte_carlo` - Mean and spread of n runs of a function ("f" n → mean stddev)
+//! - `bootstrap` - 95% confidence interval for a list's mean ([x ...] n → low high)
+//! - `beta_update` - Update a Beta(a, b) belief with observations (a b successes failures → a' b')
+//! - `posterior_plot` - Chart the Beta(a, b) density on [0, 1] (a b →)
+//! - `bayes` - Bayes' rule P(H|E) = P(E|H)·P(H) / P(E) (prior likelihood evidence → posterior)
+//!
+//! `monte_carlo` calls a function defined with `⊕` n times on an empty
+//! argument list; each run must leave one number. The standard deviation
+//! is the sample one (n - 1), so it estimates the spread of a single run;
+//! divide by √n for the error of the mean. Estimating π from random
+//! points in the unit square:
+//!
+//! ```text
+//! ⊕ dart ⺆ rand dup * rand dup * + 1 < 4 * ⺘
+//! "dart" 100000 monte_carlo        # 3.14… 1.64…
+//! ```
+//!
+//! `bootstrap` resamples the list with replacement n times and reports
+//! the 2.5th and 97.5th percentiles of the resampled means.
+//!
+//! A Beta(a, b) distribution is a belief about an unknown rate, such as
+//! a coin's bias; it is conjugate to coin flips, so learning from data is
+//! adding the counts. Starting from the uniform Beta(1, 1):
+//!
+//! ```text
+//! 1 1 7 3 beta_update        # 8.0 4.0 after 7 heads, 3 tails
+//! posterior_plot             # density peaking at 0.7, mean 0.667
+//! 0.01 0.9 0.05 bayes        # 0.18: a positive test for a 1% condition
+//! ```
+
+use std::f64::consts::PI;
+
+use rand::Rng;
+
+use woflang_core::{InterpreterContext, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Most runs `monte_carlo` makes.
+const MAX_TRIALS: usize = 10_000_000;
+
+/// Most resamples `bootstrap` draws.
+const MAX_RESAMPLES: usize = 1_000_000;
+
+/// Runs between progress reports.
+const REPORT_EVERY: usize = 1024;
+
+/// Mean and sample standard deviation; the deviation is 0 for one value.
+fn mean_stddev(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return (mean, 0.0);
+    }
+    let var = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, var.sqrt())
+}
+
+/// Percentile bootstrap: the central 95% of `resamples` resampled means.
+fn bootstrap_ci(values: &[f64], resamples: usize, rng: &mut impl Rng) -> (f64, f64) {
+    let n = values.len();
+    let mut means: Vec<f64> = (0..resamples)
+        .map(|_| (0..n).map(|_| values[rng.gen_range(0..n)]).sum::<f64>() / n as f64)
+        .collect();
+    means.sort_by(f64::total_cmp);
+    let at = |q: f64| means[((resamples - 1) as f64 * q).round() as usize];
+    (at(0.025), at(0.975))
+}
+
+/// ln Γ(x) for x > 0, by the Lanczos approximation (g = 7, n = 9).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection: Γ(x)Γ(1 - x) = π / sin(πx)
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = COEFFS[1..].iter().zip(1..).fold(COEFFS[0], |acc, (c, i)| acc + c / (x + f64::from(i)));
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// The Beta(a, b) density at `x`.
+fn beta_pdf(x: f64, a: f64, b: f64) -> f64 {
+    let ln_norm = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b);
+    (ln_norm + (a - 1.0) * x.ln() + (b - 1.0) * (1.0 - x).ln()).exp()
+}
+
+/// Pop Beta parameters for `op`, both finite and positive.
+fn pop_beta(interp: &mut Interpreter, op: &str) -> woflang_core::Result<(f64, f64)> {
+    let b = interp.stack_mut().pop_numeric()?;
+    let a = interp.stack_mut().pop_numeric()?;
+    if !(a > 0.0 && b > 0.0 && a.is_finite() && b.is_finite()) {
+        return Err(WofError::runtime(format!("{op}: a and b must be positive")));
+    }
+    Ok((a, b))
+}
+
+/// Register the statistics operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: "f" n → mean stddev
+    interp.register("monte_carlo", |interp| {
+        let n = interp.stack_mut().pop_integer()?;
+        let function = interp.stack_mut().pop_string()?;
+        let n = usize::try_from(n)
+            .ok()
+            .filter(|n| (1..=MAX_TRIALS).contains(n))
+            .ok_or_else(|| WofError::runtime(format!("monte_carlo: runs must be in 1..={MAX_TRIALS}")))?;
+        if !interp.has_function(&function) {
+            return Err(WofError::runtime(format!("monte_carlo: no function named '{function}'")));
+        }
+        let mut results = Vec::with_capacity(n);
+        for i in 0..n {
+            if i % REPORT_EVERY == 0 {
+                interp.progress(i, n, "trials");
+            }
+            results.push(super::plot::sample(interp, "monte_carlo", &function, &[])?);
+        }
+        interp.progress(n, n, "trials");
+        let (mean, stddev) = mean_stddev(&results);
+        interp.push(WofValue::double(mean));
+        interp.push(WofValue::double(stddev));
+        Ok(())
+    });
+
+    // Stack: [x ...] n → low high
+    interp.register("bootstrap", |interp| {
+        let n = interp.stack_mut().pop_integer()?;
+        let list = interp.stack_mut().pop()?;
+        let values = list.as_list()?.iter().map(WofValue::as_double).collect::<woflang_core::Result<Vec<_>>>()?;
+        let n = usize::try_from(n)
+            .ok()
+            .filter(|n| (1..=MAX_RESAMPLES).contains(n))
+            .ok_or_else(|| WofError::runtime(format!("bootstrap: resamples must be in 1..={MAX_RESAMPLES}")))?;
+        if values.is_empty() {
+            return Err(WofError::runtime("bootstrap: the list is empty"));
+        }
+        let (low, high) = bootstrap_ci(&values, n, &mut rand::thread_rng());
+        interp.push(WofValue::double(low));
+        interp.push(WofValue::double(high));
+        Ok(())
+    });
+
+    // Stack: a b successes failures → a' b'
+    interp.register("beta_update", |interp| {
+        let failures = interp.stack_mut().pop_numeric()?;
+        let successes = interp.stack_mut().pop_numeric()?;
+        let (a, b) = pop_beta(interp, "beta_update")?;
+        if !(successes >= 0.0 && failures >= 0.0 && successes.is_finite() && failures.is_finite()) {
+            return Err(WofError::runtime("beta_update: counts must be finite and non-negative"));
+        }
+        interp.push(WofValue::double(a + successes));
+        interp.push(WofValue::double(b + failures));
+        Ok(())
+    });
+
+    // Stack: a b → (none)
+    interp.register("posterior_plot", |interp| {
+        let (a, b) = pop_beta(interp, "posterior_plot")?;
+        let samples: Vec<f64> = (0..super::plot::SAMPLES)
+            .map(|i| beta_pdf(i as f64 / (super::plot::SAMPLES - 1) as f64, a, b))
+            .collect();
+        let chart = super::plot::render(&samples, 0.0, 1.0)
+            .ok_or_else(|| WofError::runtime(format!("posterior_plot: Beta({a}, {b}) has no finite density")))?;
+        writeln!(interp.out(), "Beta({a}, {b}): mean {:.3}", a / (a + b));
+        write!(interp.out(), "{chart}");
+        Ok(())
+    });
+
+    // Stack: prior likelihood evidence → posterior
+    interp.register("bayes", |interp| {
+        let evidence = interp.stack_mut().pop_numeric()?;
+        let likelihood = interp.stack_mut().pop_numeric()?;
+        let prior = interp.stack_mut().pop_numeric()?;
+        if ![prior, likelihood, evidence].iter().all(|p| (0.0..=1.0).contains(p)) || evidence == 0.0 {
+            return Err(WofError::runtime("bayes: need probabilities in [0, 1] and evidence above 0"));
+        }
+        let posterior = prior * likelihood / evidence;
+        if posterior > 1.0 + 1e-12 {
+            return Err(WofError::runtime(format!(
+                "bayes: prior × likelihood ({}) exceeds the evidence ({evidence})",
+                prior * likelihood
+            )));
+        }
+        interp.push(WofValue::double(posterior.min(1.0)));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    #[test]
+    fn summaries() {
+        let (mean, sd) = mean_stddev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert!((mean - 5.0).abs() < 1e-12);
+        assert!((sd - (32.0_f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert_eq!(mean_stddev(&[3.0]), (3.0, 0.0));
+
+        let values: Vec<f64> = (1..=100).map(f64::from).collect();
+        let (low, high) = bootstrap_ci(&values, 2000, &mut StdRng::seed_from_u64(7));
+        // The mean is 50.5 with standard error ≈ 2.9, so the interval is ≈ ±5.7.
+        assert!(low < 50.5 && 50.5 < high);
+        assert!((high - low - 11.3).abs() < 2.0, "{low} {high}");
+        assert_eq!(bootstrap_ci(&[4.0], 10, &mut StdRng::seed_from_u64(1)), (4.0, 4.0));
+    }
+
+    #[test]
+    fn beta_densities() {
+        assert!(ln_gamma(1.0).abs() < 1e-12 && ln_gamma(2.0).abs() < 1e-12);
+        assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-12);
+        assert!((ln_gamma(0.5) - PI.sqrt().ln()).abs() < 1e-12);
+        assert!((beta_pdf(0.3, 1.0, 1.0) - 1.0).abs() < 1e-12);
+        // Beta(2, 3) is 12·x·(1 - x)²
+        assert!((beta_pdf(0.4, 2.0, 3.0) - 12.0 * 0.4 * 0.36).abs() < 1e-10);
+        let area: f64 = (0..1000).map(|i| beta_pdf((f64::from(i) + 0.5) / 1000.0, 8.0, 4.0)).sum::<f64>() / 1000.0;
+        assert!((area - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bayesian_ops() {
+        let mut interp = Interpreter::new();
+        crate::math::register(&mut interp);
+
+        interp.exec_line("1 1 7 3 beta_update").unwrap();
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 4.0);
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 8.0);
+        interp.exec_line("8 4 posterior_plot").unwrap();
+        assert!(interp.stack().is_empty());
+
+        // P(sick | positive) with a 1% base rate, 90% sensitivity and 5% positives
+        interp.exec_line("0.01 0.9 0.05 bayes").unwrap();
+        assert!((interp.stack_mut().pop_numeric().unwrap() - 0.18).abs() < 1e-12);
+
+        assert!(interp.exec_line("0 1 1 1 beta_update").is_err());
+        assert!(interp.exec_line("1 1 -1 0 beta_update").is_err());
+        assert!(interp.exec_line("0.5 0.5 0 bayes").is_err());
+        assert!(interp.exec_line("0.9 0.9 0.5 bayes").is_err());
+    }
+
+    #[test]
+    fn ops_run_functions_and_resample_lists() {
+        let mut interp = Interpreter::new();
+        crate::math::register(&mut interp);
+        let flips = AtomicI64::new(0);
+        interp.register("coin", move |interp| {
+            interp.push(WofValue::integer((flips.fetch_add(1, Ordering::Relaxed) + 1) % 2));
+            Ok(())
+        });
+        interp.exec_line("⊕ flip ⺆ coin ⺘").unwrap();
+
+        interp.exec_line(r#""flip" 10 monte_carlo"#).unwrap();
+        let sd = interp.stack_mut().pop_numeric().unwrap();
+        let mean = interp.stack_mut().pop_numeric().unwrap();
+        assert!((mean - 0.5).abs() < 1e-12);
+        assert!((sd - (10.0_f64 / 36.0).sqrt()).abs() < 1e-12);
+
+        interp.push(WofValue::list(vec![WofValue::integer(1), WofValue::double(3.0)]));
+        interp.exec_line("500 bootstrap").unwrap();
+        let high = interp.stack_mut().pop_numeric().unwrap();
+        let low = interp.stack_mut().pop_numeric().unwrap();
+        assert!((1.0..=2.0).contains(&low) && (2.0..=3.0).contains(&high));
+
+        assert!(interp.exec_line(r#""nope" 10 monte_carlo"#).is_err());
+        assert!(interp.exec_line(r#""flip" 0 monte_carlo"#).is_err());
+        interp.push(WofValue::list(Vec::new()));
+        assert!(interp.exec_line("10 bootstrap").is_err());
+    }
+}
@@ -0,0 +1,704 @@
+//! Models of computation: Turing machines, the lambda calculus and
+//! Brainfuck.
+//!
+//! ## Operations
+//!
+//! - `tm_define` - Set the Turing machine's transition rules (rules →)
+//! - `tm_run` - Run the machine on a tape until it halts (tape → tape)
+//! - `tm_trace` - Run like `tm_run`, printing every configuration (tape → tape)
+//! - `lam_parse` - Parse and pretty-print a lambda term ("src" → term)
+//! - `beta_reduce` - Reduce a lambda term to normal form (term → term)
+//! - `church` - Church numeral for n (n → term)
+//! - `bf_run` - Run a Brainfuck program on an input string ("program" "input" → "output")
+//!
+//! A rule is `state read write move next`, with moves `L`, `R` or `N`
+//! and `_` for the blank symbol. Rules are given as one string separated
+//! by `;` or newlines, or as a list of strings. The machine starts in the
+//! state of the first rule, with the head on the first tape symbol, and
+//! halts when no rule matches. A binary incrementer:
+//!
+//! ```text
+//! "r 0 0 R r; r 1 1 R r; r _ _ L c; c 1 0 L c; c 0 1 N halt; c _ 1 N halt" tm_define
+//! "1011" tm_run              # "1100"
+//! ```
+//!
+//! Lambda terms are written `λx.body` or `\x.body`, with application by
+//! juxtaposition and `λx y.body` for nested abstractions. Terms live on
+//! the stack as their printed form. Reduction is normal order, so it finds
+//! a normal form whenever one exists:
+//!
+//! ```text
+//! 2 church                                        # "λf.λx.f (f x)"
+//! "(λn f x.f (n f x)) (λf x.f (f x))" beta_reduce  # "λf.λx.f (f (f x))"
+//! ```
+//!
+//! `bf_run` compiles the program to bytecode first, folding runs of
+//! `+ - < >`, turning `[-]` into a clear and matching every bracket up
+//! front, so it makes a fair interpreter-inside-an-interpreter workload
+//! for the profiler. Cells are wrapping bytes, the tape grows to the
+//! right, and `,` reads 0 once the input runs out. Long programs stop on
+//! cancellation.
+//!
+//! ```text
+//! ",[.,]" "echo" bf_run      # "echo"
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{self, Write as _};
+
+use woflang_core::{InterpreterContext, Out, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Most steps a machine or a reduction may take.
+const MAX_STEPS: usize = 1_000_000;
+
+/// Largest lambda term, in nodes, a reduction may build.
+const MAX_TERM_SIZE: usize = 100_000;
+
+/// Largest numeral `church` builds.
+const MAX_CHURCH: i64 = 10_000;
+
+/// The blank tape symbol.
+const BLANK: char = '_';
+
+/// Most instructions a Brainfuck program may execute.
+const MAX_BF_STEPS: u64 = 1_000_000_000;
+
+/// Longest Brainfuck tape.
+const MAX_BF_TAPE: usize = 1 << 24;
+
+/// Instructions between cancellation checks.
+const CHECK_EVERY: u64 = 1 << 20;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TURING MACHINES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Where the head goes after writing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Move {
+    Left,
+    Right,
+    Stay,
+}
+
+/// What to do on reading a symbol in a state.
+#[derive(Debug, Clone)]
+struct Action {
+    write: char,
+    step: Move,
+    next: String,
+}
+
+/// A single-tape Turing machine.
+#[derive(Debug, Clone, Default)]
+struct Machine {
+    start: String,
+    rules: HashMap<(String, char), Action>,
+}
+
+/// A tape symbol given as a one-character word.
+fn symbol(word: &str) -> Option<char> {
+    let mut chars = word.chars();
+    chars.next().filter(|_| chars.next().is_none())
+}
+
+impl Machine {
+    /// Parse rules of the form `state read write move next`.
+    fn parse<'a>(rules: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut machine = Self::default();
+        for rule in rules.into_iter().map(str::trim).filter(|r| !r.is_empty()) {
+            let bad = || WofError::runtime(format!("tm_define: bad rule '{rule}' (want: state read write L|R|N next)"));
+            let [state, read, write, step, next] = rule.split_whitespace().collect::<Vec<_>>()[..] else {
+                return Err(bad());
+            };
+            let step = match step {
+                "L" => Move::Left,
+                "R" => Move::Right,
+                "N" => Move::Stay,
+                _ => return Err(bad()),
+            };
+            let (read, write) = symbol(read).zip(symbol(write)).ok_or_else(bad)?;
+            if machine.rules.is_empty() {
+                machine.start = state.to_string();
+            }
+            machine.rules.insert((state.to_string(), read), Action { write, step, next: next.to_string() });
+        }
+        if machine.rules.is_empty() {
+            return Err(WofError::runtime("tm_define: no rules"));
+        }
+        Ok(machine)
+    }
+
+    /// Run on `tape` until no rule matches, calling `watch` with each
+    /// configuration before its step. Returns the tape without the blanks
+    /// at either end.
+    fn run(&self, op: &str, tape: &str, mut watch: impl FnMut(usize, &str, &VecDeque<char>, usize)) -> Result<String> {
+        let mut tape: VecDeque<char> = tape.chars().collect();
+        if tape.is_empty() {
+            tape.push_back(BLANK);
+        }
+        let mut head = 0;
+        let mut state = self.start.as_str();
+        for steps in 0..=MAX_STEPS {
+            watch(steps, state, &tape, head);
+            let Some(action) = self.rules.get(&(state.to_string(), tape[head])) else {
+                let cells: String = tape.iter().collect();
+                return Ok(cells.trim_matches(BLANK).to_string());
+            };
+            tape[head] = action.write;
+            match action.step {
+                Move::Left if head == 0 => tape.push_front(BLANK),
+                Move::Left => head -= 1,
+                Move::Right => {
+                    head += 1;
+                    if head == tape.len() {
+                        tape.push_back(BLANK);
+                    }
+                }
+                Move::Stay => {}
+            }
+            state = &action.next;
+        }
+        Err(WofError::runtime(format!("{op}: no halt within {MAX_STEPS} steps")))
+    }
+}
+
+/// Print one machine configuration with the head's cell bracketed.
+fn print_configuration(out: &Out, step: usize, state: &str, tape: &VecDeque<char>, head: usize) {
+    let mut line = format!("{step:>6} {state:<8} ");
+    for (i, c) in tape.iter().enumerate() {
+        if i == head {
+            let _ = write!(line, "[{c}]");
+        } else {
+            line.push(*c);
+        }
+    }
+    out.println(&line);
+}
+
+/// Pop rules given as a string or a list of strings.
+fn pop_rules(interp: &mut Interpreter) -> Result<Machine> {
+    let rules = interp.stack_mut().pop()?;
+    match rules.as_list() {
+        Ok(items) => {
+            let lines = items.iter().map(WofValue::as_str).collect::<Result<Vec<_>>>()?;
+            Machine::parse(lines)
+        }
+        Err(_) => Machine::parse(rules.as_str()?.split([';', '\n'])),
+    }
+}
+
+fn machine(interp: &Interpreter, op: &str) -> Result<Machine> {
+    interp
+        .state::<Machine>()
+        .filter(|m| !m.rules.is_empty())
+        .cloned()
+        .ok_or_else(|| WofError::runtime(format!("{op}: no machine defined (use tm_define)")))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// LAMBDA CALCULUS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A lambda term with de Bruijn indices for bound variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    /// Bound variable, counting binders outward from 0.
+    Var(usize),
+    /// Free variable.
+    Free(String),
+    /// Abstraction, keeping the source name for printing.
+    Lam(String, Box<Term>),
+    App(Box<Term>, Box<Term>),
+}
+
+/// Recursive-descent parser over the source characters.
+struct Parser<'a> {
+    src: std::iter::Peekable<std::str::Chars<'a>>,
+    /// Names bound by enclosing abstractions, innermost last.
+    scope: Vec<String>,
+}
+
+fn is_name_char(c: char) -> bool {
+    (c.is_alphanumeric() && c != 'λ') || c == '_' || c == '\''
+}
+
+impl Parser<'_> {
+    fn skip_space(&mut self) {
+        while self.src.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn name(&mut self) -> Option<String> {
+        self.skip_space();
+        let mut name = String::new();
+        while let Some(c) = self.src.next_if(|&c| is_name_char(c)) {
+            name.push(c);
+        }
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// term := ('λ' | '\') name+ '.' term | atom+
+    fn term(&mut self) -> Result<Term> {
+        self.skip_space();
+        if self.src.next_if(|&c| c == 'λ' || c == '\\').is_some() {
+            let mut names = Vec::new();
+            while let Some(name) = self.name() {
+                names.push(name);
+            }
+            self.skip_space();
+            if names.is_empty() || self.src.next() != Some('.') {
+                return Err(WofError::runtime("lam_parse: expected 'λname.' before a body"));
+            }
+            self.scope.extend(names.iter().cloned());
+            let body = self.term();
+            self.scope.truncate(self.scope.len() - names.len());
+            return Ok(names.into_iter().rev().fold(body?, |body, name| Term::Lam(name, Box::new(body))));
+        }
+        let mut term = self.atom()?;
+        loop {
+            self.skip_space();
+            match self.src.peek() {
+                None | Some(')') => return Ok(term),
+                Some('λ' | '\\') => return Ok(Term::App(Box::new(term), Box::new(self.term()?))),
+                Some(_) => term = Term::App(Box::new(term), Box::new(self.atom()?)),
+            }
+        }
+    }
+
+    /// atom := name | '(' term ')'
+    fn atom(&mut self) -> Result<Term> {
+        self.skip_space();
+        if self.src.next_if_eq(&'(').is_some() {
+            let term = self.term()?;
+            self.skip_space();
+            return match self.src.next() {
+                Some(')') => Ok(term),
+                _ => Err(WofError::runtime("lam_parse: missing ')'")),
+            };
+        }
+        let name = self.name().ok_or_else(|| match self.src.peek() {
+            Some(c) => WofError::runtime(format!("lam_parse: unexpected '{c}'")),
+            None => WofError::runtime("lam_parse: unexpected end of term"),
+        })?;
+        Ok(match self.scope.iter().rev().position(|bound| *bound == name) {
+            Some(index) => Term::Var(index),
+            None => Term::Free(name),
+        })
+    }
+}
+
+impl Term {
+    fn parse(src: &str) -> Result<Self> {
+        let mut parser = Parser { src: src.chars().peekable(), scope: Vec::new() };
+        let term = parser.term()?;
+        match parser.src.next() {
+            None => Ok(term),
+            Some(c) => Err(WofError::runtime(format!("lam_parse: unexpected '{c}'"))),
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::Var(_) | Self::Free(_) => 1,
+            Self::Lam(_, body) => 1 + body.size(),
+            Self::App(f, a) => 1 + f.size() + a.size(),
+        }
+    }
+
+    /// Add `by` to the indices of variables bound outside `depth` binders.
+    fn shift(&self, by: isize, depth: usize) -> Self {
+        match self {
+            Self::Var(k) if *k >= depth => Self::Var(k.wrapping_add_signed(by)),
+            Self::Var(_) | Self::Free(_) => self.clone(),
+            Self::Lam(name, body) => Self::Lam(name.clone(), Box::new(body.shift(by, depth + 1))),
+            Self::App(f, a) => Self::App(Box::new(f.shift(by, depth)), Box::new(a.shift(by, depth))),
+        }
+    }
+
+    /// Replace variable `index` with `value`.
+    fn subst(&self, index: usize, value: &Self) -> Self {
+        match self {
+            Self::Var(k) if *k == index => value.clone(),
+            Self::Var(_) | Self::Free(_) => self.clone(),
+            Self::Lam(name, body) => Self::Lam(name.clone(), Box::new(body.subst(index + 1, &value.shift(1, 0)))),
+            Self::App(f, a) => Self::App(Box::new(f.subst(index, value)), Box::new(a.subst(index, value))),
+        }
+    }
+
+    /// One normal-order step: the leftmost outermost redex.
+    fn step(&self) -> Option<Self> {
+        match self {
+            Self::App(f, a) => match f.as_ref() {
+                Self::Lam(_, body) => Some(body.subst(0, &a.shift(1, 0)).shift(-1, 0)),
+                _ => f
+                    .step()
+                    .map(|f| Self::App(Box::new(f), a.clone()))
+                    .or_else(|| a.step().map(|a| Self::App(f.clone(), Box::new(a)))),
+            },
+            Self::Lam(name, body) => body.step().map(|body| Self::Lam(name.clone(), Box::new(body))),
+            Self::Var(_) | Self::Free(_) => None,
+        }
+    }
+
+    fn normalize(self) -> Result<Self> {
+        let mut term = self;
+        for _ in 0..MAX_STEPS {
+            match term.step() {
+                None => return Ok(term),
+                Some(next) if next.size() > MAX_TERM_SIZE => break,
+                Some(next) => term = next,
+            }
+        }
+        Err(WofError::runtime(format!(
+            "beta_reduce: no normal form within {MAX_STEPS} steps and {MAX_TERM_SIZE} nodes"
+        )))
+    }
+
+    /// λf.λx.fⁿ x
+    fn church(n: usize) -> Self {
+        let body = (0..n).fold(Self::Var(0), |x, _| Self::App(Box::new(Self::Var(1)), Box::new(x)));
+        Self::Lam("f".into(), Box::new(Self::Lam("x".into(), Box::new(body))))
+    }
+
+    fn free_names<'a>(&'a self, names: &mut HashSet<&'a str>) {
+        match self {
+            Self::Free(name) => {
+                names.insert(name);
+            }
+            Self::Var(_) => {}
+            Self::Lam(_, body) => body.free_names(names),
+            Self::App(f, a) => {
+                f.free_names(names);
+                a.free_names(names);
+            }
+        }
+    }
+
+    /// Print with binders renamed (by priming) wherever a name would
+    /// otherwise be shadowed or capture a free variable.
+    fn write(&self, out: &mut fmt::Formatter<'_>, scope: &mut Vec<String>, free: &HashSet<&str>) -> fmt::Result {
+        match self {
+            Self::Var(k) => write!(out, "{}", scope[scope.len() - 1 - k]),
+            Self::Free(name) => write!(out, "{name}"),
+            Self::Lam(hint, body) => {
+                let mut name = hint.clone();
+                while scope.contains(&name) || free.contains(name.as_str()) {
+                    name.push('\'');
+                }
+                write!(out, "λ{name}.")?;
+                scope.push(name);
+                body.write(out, scope, free)?;
+                scope.pop();
+                Ok(())
+            }
+            Self::App(f, a) => {
+                if matches!(**f, Self::Lam(..)) {
+                    write!(out, "(")?;
+                    f.write(out, scope, free)?;
+                    write!(out, ")")?;
+                } else {
+                    f.write(out, scope, free)?;
+                }
+                write!(out, " ")?;
+                if matches!(**a, Self::App(..) | Self::Lam(..)) {
+                    write!(out, "(")?;
+                    a.write(out, scope, free)?;
+                    write!(out, ")")
+                } else {
+                    a.write(out, scope, free)
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut free = HashSet::new();
+        self.free_names(&mut free);
+        self.write(f, &mut Vec::new(), &free)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BRAINFUCK
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Brainfuck bytecode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bf {
+    /// Add to the current cell, wrapping.
+    Add(u8),
+    /// Move the head by a signed distance.
+    Move(isize),
+    /// Set the current cell to zero (`[-]`).
+    Clear,
+    Output,
+    Input,
+    /// `[`: jump past the matching `]` when the cell is zero.
+    JumpIfZero(usize),
+    /// `]`: jump back past the matching `[` unless the cell is zero.
+    JumpUnlessZero(usize),
+}
+
+/// Compile a program, ignoring everything but the eight commands.
+fn bf_compile(program: &str) -> Result<Vec<Bf>> {
+    let mut code: Vec<Bf> = Vec::new();
+    let mut open = Vec::new();
+    for c in program.chars() {
+        match (c, code.last_mut()) {
+            ('+', Some(Bf::Add(n))) => *n = n.wrapping_add(1),
+            ('-', Some(Bf::Add(n))) => *n = n.wrapping_sub(1),
+            ('>', Some(Bf::Move(d))) => *d += 1,
+            ('<', Some(Bf::Move(d))) => *d -= 1,
+            ('+', _) => code.push(Bf::Add(1)),
+            ('-', _) => code.push(Bf::Add(u8::MAX)),
+            ('>', _) => code.push(Bf::Move(1)),
+            ('<', _) => code.push(Bf::Move(-1)),
+            ('.', _) => code.push(Bf::Output),
+            (',', _) => code.push(Bf::Input),
+            ('[', _) => {
+                open.push(code.len());
+                code.push(Bf::JumpIfZero(0));
+            }
+            (']', _) => {
+                let start = open.pop().ok_or_else(|| WofError::runtime("bf_run: unmatched ']'"))?;
+                if code[start + 1..] == [Bf::Add(u8::MAX)] || code[start + 1..] == [Bf::Add(1)] {
+                    code.truncate(start);
+                    code.push(Bf::Clear);
+                } else {
+                    code[start] = Bf::JumpIfZero(code.len() + 1);
+                    code.push(Bf::JumpUnlessZero(start + 1));
+                }
+            }
+            _ => {}
+        }
+    }
+    if !open.is_empty() {
+        return Err(WofError::runtime("bf_run: unmatched '['"));
+    }
+    Ok(code)
+}
+
+/// Run compiled Brainfuck, returning its output bytes. `keep_going` is
+/// asked every [`CHECK_EVERY`] instructions with the count so far.
+fn bf_execute(code: &[Bf], input: &[u8], mut keep_going: impl FnMut(u64) -> bool) -> Result<Vec<u8>> {
+    let mut tape = vec![0_u8; 30_000];
+    let mut input = input.iter().copied();
+    let mut output = Vec::new();
+    let (mut pc, mut head) = (0, 0_usize);
+    let mut steps = 0_u64;
+    while let Some(&op) = code.get(pc) {
+        pc += 1;
+        match op {
+            Bf::Add(n) => tape[head] = tape[head].wrapping_add(n),
+            Bf::Move(d) => {
+                head = head
+                    .checked_add_signed(d)
+                    .filter(|&h| h < MAX_BF_TAPE)
+                    .ok_or_else(|| WofError::runtime("bf_run: head moved off the tape"))?;
+                if head >= tape.len() {
+                    tape.resize((head + 1).next_power_of_two(), 0);
+                }
+            }
+            Bf::Clear => tape[head] = 0,
+            Bf::Output => output.push(tape[head]),
+            Bf::Input => tape[head] = input.next().unwrap_or(0),
+            Bf::JumpIfZero(target) if tape[head] == 0 => pc = target,
+            Bf::JumpUnlessZero(target) if tape[head] != 0 => pc = target,
+            Bf::JumpIfZero(_) | Bf::JumpUnlessZero(_) => {}
+        }
+        steps += 1;
+        if steps % CHECK_EVERY == 0 {
+            if steps >= MAX_BF_STEPS {
+                return Err(WofError::runtime(format!("bf_run: no halt within {MAX_BF_STEPS} instructions")));
+            }
+            if !keep_going(steps) {
+                return Err(WofError::interrupted(format!("after {steps} instructions")));
+            }
+        }
+    }
+    Ok(output)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// REGISTRATION
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Register the computation operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: rules → (none)
+    interp.register("tm_define", |interp| {
+        let machine = pop_rules(interp)?;
+        *interp.state_mut::<Machine>() = machine;
+        Ok(())
+    });
+
+    // Stack: tape → tape
+    interp.register("tm_run", |interp| {
+        let tape = interp.stack_mut().pop_string()?;
+        let result = machine(interp, "tm_run")?.run("tm_run", &tape, |_, _, _, _| {})?;
+        interp.push(WofValue::string(result));
+        Ok(())
+    });
+
+    // Stack: tape → tape
+    interp.register("tm_trace", |interp| {
+        let out = interp.out();
+        let tape = interp.stack_mut().pop_string()?;
+        let result = machine(interp, "tm_trace")?.run("tm_trace", &tape, |step, state, tape, head| {
+            print_configuration(&out, step, state, tape, head);
+        })?;
+        interp.push(WofValue::string(result));
+        Ok(())
+    });
+
+    // Stack: "src" → term
+    interp.register("lam_parse", |interp| {
+        let src = interp.stack_mut().pop_string()?;
+        let term = Term::parse(&src)?;
+        interp.push(WofValue::string(term.to_string()));
+        Ok(())
+    });
+
+    // Stack: term → normal form
+    interp.register("beta_reduce", |interp| {
+        let src = interp.stack_mut().pop_string()?;
+        let term = Term::parse(&src)?.normalize()?;
+        interp.push(WofValue::string(term.to_string()));
+        Ok(())
+    });
+
+    // Stack: n → λf.λx.fⁿ x
+    interp.register("church", |interp| {
+        let n = interp.stack_mut().pop_integer()?;
+        let n = usize::try_from(n)
+            .ok()
+            .filter(|_| (0..=MAX_CHURCH).contains(&n))
+            .ok_or_else(|| WofError::runtime(format!("church: n must be 0 to {MAX_CHURCH}")))?;
+        interp.push(WofValue::string(Term::church(n).to_string()));
+        Ok(())
+    });
+
+    // Stack: "program" "input" → "output"
+    interp.register("bf_run", |interp| {
+        let input = interp.stack_mut().pop_string()?;
+        let program = interp.stack_mut().pop_string()?;
+        let code = bf_compile(&program)?;
+        let mut stopped = None;
+        let output = bf_execute(&code, input.as_bytes(), |steps| {
+            let cancelled = interp.is_cancelled();
+            if cancelled {
+                stopped = Some(steps);
+            }
+            !cancelled
+        });
+        if let Some(steps) = stopped {
+            return Err(interp.interrupt(format!("after {steps} instructions")));
+        }
+        interp.push(WofValue::string(String::from_utf8_lossy(&output?)));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INCREMENT: &str = "r 0 0 R r; r 1 1 R r; r _ _ L c; c 1 0 L c; c 0 1 N halt; c _ 1 N halt";
+
+    fn reduce(src: &str) -> String {
+        Term::parse(src).unwrap().normalize().unwrap().to_string()
+    }
+
+    #[test]
+    fn turing_machines_increment_binary() {
+        let machine = Machine::parse(INCREMENT.split(';')).unwrap();
+        let mut steps = 0;
+        assert_eq!(machine.run("tm_run", "1011", |n, _, _, _| steps = n).unwrap(), "1100");
+        assert_eq!(steps, 8);
+        assert_eq!(machine.run("tm_run", "111", |_, _, _, _| {}).unwrap(), "1000");
+        assert_eq!(machine.run("tm_run", "", |_, _, _, _| {}).unwrap(), "1");
+
+        let forever = Machine::parse(["a _ _ R a"]).unwrap();
+        assert!(forever.run("tm_run", "", |_, _, _, _| {}).is_err());
+        assert!(Machine::parse(["a 0 1 X b"]).is_err());
+        assert!(Machine::parse(["a 00 1 R b"]).is_err());
+    }
+
+    #[test]
+    fn lambda_terms_parse_and_print() {
+        assert_eq!(Term::parse(r"\x y.x").unwrap().to_string(), "λx.λy.x");
+        assert_eq!(Term::parse("(λx.x) (a b) λz.z").unwrap().to_string(), "(λx.x) (a b) (λz.z)");
+        assert_eq!(Term::parse("f (g x) y").unwrap().to_string(), "f (g x) y");
+        assert_eq!(Term::church(2).to_string(), "λf.λx.f (f x)");
+        assert!(Term::parse("λ.x").is_err());
+        assert!(Term::parse("(x").is_err());
+        assert!(Term::parse("x)").is_err());
+    }
+
+    #[test]
+    fn beta_reduction_avoids_capture() {
+        assert_eq!(reduce("(λx.x) y"), "y");
+        // The free y must not be captured by the inner binder
+        assert_eq!(reduce("(λx.λy.x) y"), "λy'.y");
+        // Normal order skips the diverging argument
+        assert_eq!(reduce("(λx.λy.y) ((λz.z z) (λz.z z))"), "λy.y");
+        assert!(Term::parse("(λz.z z) (λz.z z)").unwrap().normalize().is_err());
+
+        let plus = "λm n f x.m f (n f x)";
+        assert_eq!(reduce(&format!("({plus}) ({}) ({})", Term::church(2), Term::church(3))), Term::church(5).to_string());
+        let times = "λm n f.m (n f)";
+        assert_eq!(reduce(&format!("({times}) ({}) ({})", Term::church(3), Term::church(4))), Term::church(12).to_string());
+    }
+
+    const HELLO: &str = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.>---.+++++++..+++.>>.<-.<.+++.------.--------.>>+.>++.";
+
+    #[test]
+    fn brainfuck_compiles_and_runs() {
+        assert_eq!(
+            bf_compile("+++--<<>[-]x[,.]").unwrap(),
+            [Bf::Add(1), Bf::Move(-1), Bf::Clear, Bf::JumpIfZero(7), Bf::Input, Bf::Output, Bf::JumpUnlessZero(4)]
+        );
+        let run = |program: &str, input: &str| {
+            bf_execute(&bf_compile(program).unwrap(), input.as_bytes(), |_| true).unwrap()
+        };
+        assert_eq!(run(HELLO, ""), b"Hello World!\n");
+        assert_eq!(run(",[.,]", "echo"), b"echo");
+        // Cells wrap, input reads 0 at its end, and the tape grows
+        assert_eq!(run("-.,.", ""), [255, 0]);
+        assert_eq!(run(&format!("{}+.", ">".repeat(40_000)), ""), [1]);
+        assert!(bf_compile("[").is_err() && bf_compile("]").is_err());
+        assert!(bf_execute(&bf_compile("<").unwrap(), b"", |_| true).is_err());
+        assert!(bf_execute(&bf_compile("+[]").unwrap(), b"", |steps| steps < CHECK_EVERY).is_err());
+    }
+
+    #[test]
+    fn computation_ops() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+
+        assert!(interp.exec_line(r#""1" tm_run"#).is_err());
+        interp.exec_line(&format!(r#""{INCREMENT}" tm_define "1011" tm_run"#)).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "1100");
+        interp.exec_line(r#""1" tm_trace"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "10");
+
+        interp.push(WofValue::list(vec![WofValue::string("s 0 1 R s"), WofValue::string("s 1 0 R s")]));
+        interp.exec_line(r#"tm_define "0110" tm_run"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "1001");
+        assert!(interp.exec_line(r#""s 0 1 R" tm_define"#).is_err());
+
+        interp.exec_line(r#""\\x.x" lam_parse 3 church"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "λf.λx.f (f (f x))");
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "λx.x");
+        interp.exec_line(r#""(λn f x.f (n f x)) (λf x.f x)" beta_reduce"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "λf.λx.f (f x)");
+        assert!(interp.exec_line("-1 church").is_err());
+
+        interp.exec_line(r#"",[.,]" "wolf" bf_run"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "wolf");
+        assert!(interp.exec_line(r#""[[]" "" bf_run"#).is_err());
+    }
+}
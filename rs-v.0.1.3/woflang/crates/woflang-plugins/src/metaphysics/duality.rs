@@ -123,39 +123,39 @@ pub fn register(interp: &mut Interpreter) {
     // ─────────────────────────────────────────────────────────────────────
 
     // Turn duality mode on
-    interp.register("duality_on", |_interp| {
+    interp.register("duality_on", |interp| {
         set_duality(true);
-        println!("☯️  Duality mode: ON");
+        writeln!(interp.out(), "☯️  Duality mode: ON");
         Ok(())
     });
 
     // Turn duality mode off
-    interp.register("duality_off", |_interp| {
+    interp.register("duality_off", |interp| {
         set_duality(false);
-        println!("☯️  Duality mode: OFF");
+        writeln!(interp.out(), "☯️  Duality mode: OFF");
         Ok(())
     });
 
     // Toggle duality mode
-    interp.register("duality_toggle", |_interp| {
+    interp.register("duality_toggle", |interp| {
         let new_state = !duality_on();
         set_duality(new_state);
-        println!("☯️  Duality mode toggled to: {}", if new_state { "ON" } else { "OFF" });
+        writeln!(interp.out(), "☯️  Duality mode toggled to: {}", if new_state { "ON" } else { "OFF" });
         Ok(())
     });
 
     // Legacy toggle name
-    interp.register("duality", |_interp| {
+    interp.register("duality", |interp| {
         let new_state = !duality_on();
         set_duality(new_state);
-        println!("☯️  duality: mode is now {}", if new_state { "ON" } else { "OFF" });
+        writeln!(interp.out(), "☯️  duality: mode is now {}", if new_state { "ON" } else { "OFF" });
         Ok(())
     });
 
     // Check current mode
     interp.register("duality?", |interp| {
         let state = duality_on();
-        println!("☯️  Duality mode is: {}", if state { "ON" } else { "OFF" });
+        writeln!(interp.out(), "☯️  Duality mode is: {}", if state { "ON" } else { "OFF" });
         interp.stack_mut().push(WofValue::double(if state { 1.0 } else { 0.0 }));
         Ok(())
     });
@@ -254,7 +254,8 @@ pub fn register(interp: &mut Interpreter) {
         
         let result = !a_bool;
         
-        println!(
+        writeln!(
+            interp.out(),
             "☯️  dual_not (duality {}): {} -> {}",
             if duality_on() { "ON" } else { "OFF" },
             if a_bool { "true" } else { "false" },
@@ -275,7 +276,7 @@ pub fn register(interp: &mut Interpreter) {
         let formula = interp.stack_mut().pop()?.as_string()?;
         let dual = dualize_formula(&formula);
         
-        println!("☯️  dual_logic: \"{}\" -> \"{}\"", formula, dual);
+        writeln!(interp.out(), "☯️  dual_logic: \"{}\" -> \"{}\"", formula, dual);
         
         interp.stack_mut().push(WofValue::string(dual));
         Ok(())
@@ -10,7 +10,7 @@
 //! - `entropy_bits` - Entropy in bits
 //! - `unique_count` - Count unique values on stack
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use woflang_core::{WofValue, InterpreterContext, WofType};
 use woflang_runtime::Interpreter;
 use rand::seq::SliceRandom;
@@ -39,8 +39,9 @@ fn to_numeric(v: &WofValue) -> f64 {
     v.as_double().unwrap_or(0.0)
 }
 
-/// Calculate Shannon entropy in bits.
-fn shannon_entropy(counts: &HashMap<String, usize>, total: usize) -> f64 {
+/// Calculate Shannon entropy in bits. Terms are summed in key order, so
+/// the result is the same to the last bit on every run.
+fn shannon_entropy(counts: &BTreeMap<String, usize>, total: usize) -> f64 {
     if total == 0 {
         return 0.0;
     }
@@ -75,13 +76,13 @@ pub fn register(interp: &mut Interpreter) {
         let n = stack.len();
         
         if n == 0 {
-            println!("[entropy] Empty stack => H = 0 bits");
+            writeln!(interp.out(), "[entropy] Empty stack => H = 0 bits");
             interp.stack_mut().push(WofValue::double(0.0));
             return Ok(());
         }
         
         // Count occurrences
-        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
         
         for value in stack.iter() {
             let key = make_entropy_key(value);
@@ -90,7 +91,8 @@ pub fn register(interp: &mut Interpreter) {
         
         let h = shannon_entropy(&counts, n);
         
-        println!(
+        writeln!(
+            interp.out(),
             "[entropy] {} values, {} unique symbols => H = {:.4} bits",
             n,
             counts.len(),
@@ -111,7 +113,7 @@ pub fn register(interp: &mut Interpreter) {
             return Ok(());
         }
         
-        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
         for value in stack.iter() {
             let key = make_entropy_key(value);
             *counts.entry(key).or_insert(0) += 1;
@@ -161,7 +163,7 @@ pub fn register(interp: &mut Interpreter) {
         let stack = interp.stack_mut();
         
         if stack.is_empty() {
-            println!("[chaos] Stack already empty, nothing to shuffle");
+            writeln!(interp.out(), "[chaos] Stack already empty, nothing to shuffle");
             return Ok(());
         }
         
@@ -171,7 +173,7 @@ pub fn register(interp: &mut Interpreter) {
         let values: &mut [WofValue] = stack.as_mut_slice();
         values.shuffle(&mut thread_rng());
         
-        println!("[chaos] Stack has been randomly permuted (size = {})", len);
+        writeln!(interp.out(), "[chaos] Stack has been randomly permuted (size = {})", len);
         Ok(())
     });
 
@@ -199,7 +201,7 @@ pub fn register(interp: &mut Interpreter) {
         let stack = interp.stack_mut();
         
         if stack.is_empty() {
-            println!("[order] Stack already empty, nothing to sort");
+            writeln!(interp.out(), "[order] Stack already empty, nothing to sort");
             return Ok(());
         }
         
@@ -224,7 +226,7 @@ pub fn register(interp: &mut Interpreter) {
             }
         });
         
-        println!("[order] Stack sorted; numeric values promoted (size = {})", len);
+        writeln!(interp.out(), "[order] Stack sorted; numeric values promoted (size = {})", len);
         Ok(())
     });
 
@@ -99,19 +99,19 @@ pub fn register(interp: &mut Interpreter) {
     // ─────────────────────────────────────────────────────────────────────
 
     // Print a random learning tip
-    interp.register("lesson", |_interp| {
+    interp.register("lesson", |interp| {
         if let Some(lesson) = LESSONS.choose(&mut thread_rng()) {
-            println!("[Learning Mode] {}", lesson);
+            writeln!(interp.out(), "[Learning Mode] {}", lesson);
         }
         Ok(())
     });
 
     // Print all lessons
-    interp.register("lessons", |_interp| {
-        println!("[Learning Mode] All Lessons:");
-        println!();
+    interp.register("lessons", |interp| {
+        writeln!(interp.out(), "[Learning Mode] All Lessons:");
+        writeln!(interp.out());
         for lesson in LESSONS {
-            println!("  {}", lesson);
+            writeln!(interp.out(), "  {}", lesson);
         }
         Ok(())
     });
@@ -123,19 +123,19 @@ pub fn register(interp: &mut Interpreter) {
     // Context-aware hint
     interp.register("hint", |interp| {
         if interp.stack().is_empty() {
-            println!("Hint: The stack is empty! Try pushing a value: 42");
+            writeln!(interp.out(), "Hint: The stack is empty! Try pushing a value: 42");
         } else if interp.stack().len() == 1 {
-            println!("Hint: You have one value. Try 'dup' to duplicate or 'print' to display.");
+            writeln!(interp.out(), "Hint: You have one value. Try 'dup' to duplicate or 'print' to display.");
         } else if interp.stack().len() >= 2 {
-            println!("Hint: You have {} values. Try an operation like '+', '*', or 'swap'.", interp.stack().len());
+            writeln!(interp.out(), "Hint: You have {} values. Try an operation like '+', '*', or 'swap'.", interp.stack().len());
         }
         Ok(())
     });
 
     // Random hint
-    interp.register("random_hint", |_interp| {
+    interp.register("random_hint", |interp| {
         if let Some(hint) = HINTS.choose(&mut thread_rng()) {
-            println!("{}", hint);
+            writeln!(interp.out(), "{}", hint);
         }
         Ok(())
     });
@@ -145,29 +145,29 @@ pub fn register(interp: &mut Interpreter) {
     // ─────────────────────────────────────────────────────────────────────
 
     // Present a random quiz question
-    interp.register("quiz", |_interp| {
+    interp.register("quiz", |interp| {
         if let Some((question, options, _answer)) = QUIZZES.choose(&mut thread_rng()) {
-            println!("[Quiz] {}", question);
+            writeln!(interp.out(), "[Quiz] {}", question);
             for option in *options {
-                println!("  {}", option);
+                writeln!(interp.out(), "  {}", option);
             }
-            println!();
-            println!("(Use 'quiz_answer A', 'quiz_answer B', or 'quiz_answer C' to check)");
+            writeln!(interp.out());
+            writeln!(interp.out(), "(Use 'quiz_answer A', 'quiz_answer B', or 'quiz_answer C' to check)");
         }
         Ok(())
     });
 
     // All quizzes
-    interp.register("quizzes", |_interp| {
-        println!("[Quiz Mode] Available Quizzes:");
-        println!();
+    interp.register("quizzes", |interp| {
+        writeln!(interp.out(), "[Quiz Mode] Available Quizzes:");
+        writeln!(interp.out());
         for (i, (question, options, _)) in QUIZZES.iter().enumerate() {
-            println!("Quiz {}:", i + 1);
-            println!("  {}", question);
+            writeln!(interp.out(), "Quiz {}:", i + 1);
+            writeln!(interp.out(), "  {}", question);
             for option in *options {
-                println!("    {}", option);
+                writeln!(interp.out(), "    {}", option);
             }
-            println!();
+            writeln!(interp.out());
         }
         Ok(())
     });
@@ -177,22 +177,22 @@ pub fn register(interp: &mut Interpreter) {
     // ─────────────────────────────────────────────────────────────────────
 
     // Show example code snippets
-    interp.register("examples", |_interp| {
-        println!("[Examples] Woflang Code Snippets:");
-        println!();
+    interp.register("examples", |interp| {
+        writeln!(interp.out(), "[Examples] Woflang Code Snippets:");
+        writeln!(interp.out());
         for (description, code) in EXAMPLES {
-            println!("  {} ", description);
-            println!("    > {}", code);
-            println!();
+            writeln!(interp.out(), "  {} ", description);
+            writeln!(interp.out(), "    > {}", code);
+            writeln!(interp.out());
         }
         Ok(())
     });
 
     // Random example
-    interp.register("example", |_interp| {
+    interp.register("example", |interp| {
         if let Some((description, code)) = EXAMPLES.choose(&mut thread_rng()) {
-            println!("[Example] {}", description);
-            println!("  > {}", code);
+            writeln!(interp.out(), "[Example] {}", description);
+            writeln!(interp.out(), "  > {}", code);
         }
         Ok(())
     });
@@ -202,46 +202,46 @@ pub fn register(interp: &mut Interpreter) {
     // ─────────────────────────────────────────────────────────────────────
 
     // Interactive tutorial intro
-    interp.register("tutorial", |_interp| {
-        println!("╔══════════════════════════════════════════════════════════╗");
-        println!("║           Welcome to the Woflang Tutorial!               ║");
-        println!("╠══════════════════════════════════════════════════════════╣");
-        println!("║                                                          ║");
-        println!("║  Woflang is a stack-based language with Unicode support. ║");
-        println!("║                                                          ║");
-        println!("║  Basic operations:                                       ║");
-        println!("║    • Push numbers: 42, 3.14, -7                          ║");
-        println!("║    • Push strings: \"hello\"                               ║");
-        println!("║    • Arithmetic: +, -, *, /                              ║");
-        println!("║    • Stack ops: dup, drop, swap, over                    ║");
-        println!("║                                                          ║");
-        println!("║  Commands to try:                                        ║");
-        println!("║    lesson   - Random learning tip                        ║");
-        println!("║    hint     - Context-aware help                         ║");
-        println!("║    quiz     - Test your knowledge                        ║");
-        println!("║    examples - See code snippets                          ║");
-        println!("║                                                          ║");
-        println!("╚══════════════════════════════════════════════════════════╝");
+    interp.register("tutorial", |interp| {
+        writeln!(interp.out(), "╔══════════════════════════════════════════════════════════╗");
+        writeln!(interp.out(), "║           Welcome to the Woflang Tutorial!               ║");
+        writeln!(interp.out(), "╠══════════════════════════════════════════════════════════╣");
+        writeln!(interp.out(), "║                                                          ║");
+        writeln!(interp.out(), "║  Woflang is a stack-based language with Unicode support. ║");
+        writeln!(interp.out(), "║                                                          ║");
+        writeln!(interp.out(), "║  Basic operations:                                       ║");
+        writeln!(interp.out(), "║    • Push numbers: 42, 3.14, -7                          ║");
+        writeln!(interp.out(), "║    • Push strings: \"hello\"                               ║");
+        writeln!(interp.out(), "║    • Arithmetic: +, -, *, /                              ║");
+        writeln!(interp.out(), "║    • Stack ops: dup, drop, swap, over                    ║");
+        writeln!(interp.out(), "║                                                          ║");
+        writeln!(interp.out(), "║  Commands to try:                                        ║");
+        writeln!(interp.out(), "║    lesson   - Random learning tip                        ║");
+        writeln!(interp.out(), "║    hint     - Context-aware help                         ║");
+        writeln!(interp.out(), "║    quiz     - Test your knowledge                        ║");
+        writeln!(interp.out(), "║    examples - See code snippets                          ║");
+        writeln!(interp.out(), "║                                                          ║");
+        writeln!(interp.out(), "╚══════════════════════════════════════════════════════════╝");
         Ok(())
     });
 
     // Quick start guide
-    interp.register("quickstart", |_interp| {
-        println!("Woflang Quick Start:");
-        println!();
-        println!("  1. Push values onto the stack:");
-        println!("     > 10 20");
-        println!();
-        println!("  2. Perform operations:");
-        println!("     > +        (adds 10 and 20, result: 30)");
-        println!();
-        println!("  3. Print the result:");
-        println!("     > print    (displays: 30)");
-        println!();
-        println!("  4. Try more:");
-        println!("     > 5 dup *  (squares 5, result: 25)");
-        println!("     > π print  (prints pi)");
-        println!();
+    interp.register("quickstart", |interp| {
+        writeln!(interp.out(), "Woflang Quick Start:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  1. Push values onto the stack:");
+        writeln!(interp.out(), "     > 10 20");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  2. Perform operations:");
+        writeln!(interp.out(), "     > +        (adds 10 and 20, result: 30)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  3. Print the result:");
+        writeln!(interp.out(), "     > print    (displays: 30)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  4. Try more:");
+        writeln!(interp.out(), "     > 5 dup *  (squares 5, result: 25)");
+        writeln!(interp.out(), "     > π print  (prints pi)");
+        writeln!(interp.out());
         Ok(())
     });
 }
@@ -5,6 +5,7 @@
 //!
 //! ## Submodules
 //!
+//! - `computation` - Turing machines, the lambda calculus and Brainfuck
 //! - `duality` - Logical and numeric duality operations
 //! - `entropy` - Information entropy, chaos, and order
 //! - `learning` - Interactive educational tools
@@ -39,6 +40,15 @@
 //! tutorial  # Welcome message
 //! ```
 //!
+//! ### Computation
+//! ```text
+//! "q 0 1 R q; q 1 0 R q" tm_define   # Rules: state read write L|R|N next
+//! "0110" tm_run                       # "1001"; tm_trace prints each step
+//! "(λx.x) y" beta_reduce              # "y"
+//! 3 church                            # "λf.λx.f (f (f x))"
+//! ",[.,]" "hi" bf_run                 # "hi"
+//! ```
+//!
 //! ### Over Unity (Easter Eggs)
 //! ```text
 //! over_unity       # The mythical free energy device
@@ -48,54 +58,65 @@
 //! heat_death       # Fast-forward to the end of the universe
 //! ```
 
+mod computation;
 mod duality;
 mod entropy;
 mod learning;
 mod over_unity;
 
+use woflang_core::InterpreterContext;
 use woflang_runtime::Interpreter;
 
 /// Register all metaphysics operations.
 pub fn register(interp: &mut Interpreter) {
+    computation::register(interp);
     duality::register(interp);
     entropy::register(interp);
     learning::register(interp);
     over_unity::register(interp);
 
     // Help command
-    interp.register("metaphysics_help", |_interp| {
-        println!("Metaphysics Operations:");
-        println!();
-        println!("  Duality (☯️):");
-        println!("    duality_on/off/toggle  # Control duality mode");
-        println!("    duality?               # Check current mode");
-        println!("    a b dual_add           # + when off, - when on");
-        println!("    a b dual_and           # AND when off, OR when on");
-        println!("    a b dual_or            # OR when off, AND when on");
-        println!("    \"formula\" dual_logic   # Textual dualization");
-        println!();
-        println!("  Entropy:");
-        println!("    entropy       # Shannon entropy of stack (bits)");
-        println!("    entropy_max   # Maximum possible entropy");
-        println!("    unique_count  # Count unique values");
-        println!("    chaos         # Shuffle stack randomly");
-        println!("    order         # Sort stack (numeric first)");
-        println!("    sort_asc/desc # Simple numeric sort");
-        println!();
-        println!("  Learning:");
-        println!("    lesson/lessons  # Learning tips");
-        println!("    hint            # Context-aware hint");
-        println!("    quiz/quizzes    # Quiz questions");
-        println!("    examples        # Code snippets");
-        println!("    tutorial        # Welcome message");
-        println!("    quickstart      # Quick start guide");
-        println!();
-        println!("  Easter Eggs:");
-        println!("    over_unity       # Free energy (doesn't work)");
-        println!("    perpetual_motion # Perpetual motion (fails)");
-        println!("    thermodynamics   # Laws of thermodynamics");
-        println!("    maxwell_demon    # Sort molecules");
-        println!("    heat_death       # End of the universe");
+    interp.register("metaphysics_help", |interp| {
+        writeln!(interp.out(), "Metaphysics Operations:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Duality (☯️):");
+        writeln!(interp.out(), "    duality_on/off/toggle  # Control duality mode");
+        writeln!(interp.out(), "    duality?               # Check current mode");
+        writeln!(interp.out(), "    a b dual_add           # + when off, - when on");
+        writeln!(interp.out(), "    a b dual_and           # AND when off, OR when on");
+        writeln!(interp.out(), "    a b dual_or            # OR when off, AND when on");
+        writeln!(interp.out(), "    \"formula\" dual_logic   # Textual dualization");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Entropy:");
+        writeln!(interp.out(), "    entropy       # Shannon entropy of stack (bits)");
+        writeln!(interp.out(), "    entropy_max   # Maximum possible entropy");
+        writeln!(interp.out(), "    unique_count  # Count unique values");
+        writeln!(interp.out(), "    chaos         # Shuffle stack randomly");
+        writeln!(interp.out(), "    order         # Sort stack (numeric first)");
+        writeln!(interp.out(), "    sort_asc/desc # Simple numeric sort");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Learning:");
+        writeln!(interp.out(), "    lesson/lessons  # Learning tips");
+        writeln!(interp.out(), "    hint            # Context-aware hint");
+        writeln!(interp.out(), "    quiz/quizzes    # Quiz questions");
+        writeln!(interp.out(), "    examples        # Code snippets");
+        writeln!(interp.out(), "    tutorial        # Welcome message");
+        writeln!(interp.out(), "    quickstart      # Quick start guide");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Computation:");
+        writeln!(interp.out(), "    \"rules\" tm_define      # Turing machine: state read write L|R|N next; ...");
+        writeln!(interp.out(), "    \"tape\" tm_run/tm_trace # Run to halt (trace prints each step)");
+        writeln!(interp.out(), "    \"λx.x\" lam_parse       # Parse and pretty-print a lambda term");
+        writeln!(interp.out(), "    term beta_reduce       # Normal-order reduction to normal form");
+        writeln!(interp.out(), "    n church               # Church numeral λf.λx.fⁿ x");
+        writeln!(interp.out(), "    \"prog\" \"in\" bf_run     # Run Brainfuck, push its output");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Easter Eggs:");
+        writeln!(interp.out(), "    over_unity       # Free energy (doesn't work)");
+        writeln!(interp.out(), "    perpetual_motion # Perpetual motion (fails)");
+        writeln!(interp.out(), "    thermodynamics   # Laws of thermodynamics");
+        writeln!(interp.out(), "    maxwell_demon    # Sort molecules");
+        writeln!(interp.out(), "    heat_death       # End of the universe");
         Ok(())
     });
 }
@@ -20,22 +20,22 @@ use rand::Rng;
 /// Register over unity (easter egg) operations.
 pub fn register(interp: &mut Interpreter) {
     // The classic over-unity easter egg
-    interp.register("over_unity", |_interp| {
-        println!("⚡  Over Unity! Energy out exceeds energy in.");
-        println!("    Next op will be disabled... (just kidding, demo only)");
-        println!();
-        println!("    Remember: The laws of thermodynamics are undefeated.");
+    interp.register("over_unity", |interp| {
+        writeln!(interp.out(), "⚡  Over Unity! Energy out exceeds energy in.");
+        writeln!(interp.out(), "    Next op will be disabled... (just kidding, demo only)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "    Remember: The laws of thermodynamics are undefeated.");
         Ok(())
     });
 
     // Perpetual motion machine
     interp.register("perpetual_motion", |interp| {
-        println!("🔄  Starting perpetual motion machine...");
-        println!("    .");
-        println!("    ..");
-        println!("    ...");
-        println!("    *click* *whirr* *slowdown*");
-        println!("    ⚠️  Machine stopped. Friction wins again.");
+        writeln!(interp.out(), "🔄  Starting perpetual motion machine...");
+        writeln!(interp.out(), "    .");
+        writeln!(interp.out(), "    ..");
+        writeln!(interp.out(), "    ...");
+        writeln!(interp.out(), "    *click* *whirr* *slowdown*");
+        writeln!(interp.out(), "    ⚠️  Machine stopped. Friction wins again.");
         
         // Push 0 (entropy always wins)
         interp.stack_mut().push(WofValue::double(0.0));
@@ -46,53 +46,53 @@ pub fn register(interp: &mut Interpreter) {
     interp.register("free_energy", |interp| {
         let mut rng = rand::thread_rng();
         
-        println!("🔋  Activating free energy generator...");
-        println!("    Tapping into zero-point energy...");
-        println!("    Accessing vacuum fluctuations...");
+        writeln!(interp.out(), "🔋  Activating free energy generator...");
+        writeln!(interp.out(), "    Tapping into zero-point energy...");
+        writeln!(interp.out(), "    Accessing vacuum fluctuations...");
         
         // Generate a tiny amount of "energy" (random noise)
         let energy: f64 = rng.gen_range(-0.0001..0.0001);
         
-        println!("    Generated: {} joules", energy);
-        println!();
-        println!("    (That's just noise. Conservation of energy is real.)");
+        writeln!(interp.out(), "    Generated: {} joules", energy);
+        writeln!(interp.out());
+        writeln!(interp.out(), "    (That's just noise. Conservation of energy is real.)");
         
         interp.stack_mut().push(WofValue::double(energy));
         Ok(())
     });
 
     // Print the laws of thermodynamics
-    interp.register("thermodynamics", |_interp| {
-        println!("═══════════════════════════════════════════════════════");
-        println!("           THE LAWS OF THERMODYNAMICS                   ");
-        println!("═══════════════════════════════════════════════════════");
-        println!();
-        println!("  0th Law: If A = B and B = C, then A = C");
-        println!("           (Thermal equilibrium is transitive)");
-        println!();
-        println!("  1st Law: Energy cannot be created or destroyed");
-        println!("           (ΔU = Q - W)");
-        println!();
-        println!("  2nd Law: Entropy of an isolated system never decreases");
-        println!("           (You can't break even)");
-        println!();
-        println!("  3rd Law: As T → 0, S → constant");
-        println!("           (You can't reach absolute zero)");
-        println!();
-        println!("  Informal: You can't win, you can't break even,");
-        println!("            and you can't quit the game.");
-        println!("═══════════════════════════════════════════════════════");
+    interp.register("thermodynamics", |interp| {
+        writeln!(interp.out(), "═══════════════════════════════════════════════════════");
+        writeln!(interp.out(), "           THE LAWS OF THERMODYNAMICS                   ");
+        writeln!(interp.out(), "═══════════════════════════════════════════════════════");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  0th Law: If A = B and B = C, then A = C");
+        writeln!(interp.out(), "           (Thermal equilibrium is transitive)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  1st Law: Energy cannot be created or destroyed");
+        writeln!(interp.out(), "           (ΔU = Q - W)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  2nd Law: Entropy of an isolated system never decreases");
+        writeln!(interp.out(), "           (You can't break even)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  3rd Law: As T → 0, S → constant");
+        writeln!(interp.out(), "           (You can't reach absolute zero)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Informal: You can't win, you can't break even,");
+        writeln!(interp.out(), "            and you can't quit the game.");
+        writeln!(interp.out(), "═══════════════════════════════════════════════════════");
         Ok(())
     });
 
     // Maxwell's demon
     interp.register("maxwell_demon", |interp| {
-        println!("😈  Maxwell's Demon awakens...");
-        println!("    Attempting to sort molecules by speed...");
-        println!();
+        writeln!(interp.out(), "😈  Maxwell's Demon awakens...");
+        writeln!(interp.out(), "    Attempting to sort molecules by speed...");
+        writeln!(interp.out());
         
         if interp.stack().is_empty() {
-            println!("    The demon finds nothing to sort.");
+            writeln!(interp.out(), "    The demon finds nothing to sort.");
             return Ok(());
         }
         
@@ -106,28 +106,28 @@ pub fn register(interp: &mut Interpreter) {
             av.partial_cmp(&bv).unwrap_or(std::cmp::Ordering::Equal)
         });
         
-        println!("    Sorting complete! But wait...");
-        println!("    The demon's information processing increased entropy.");
-        println!("    The 2nd Law remains unbroken.");
+        writeln!(interp.out(), "    Sorting complete! But wait...");
+        writeln!(interp.out(), "    The demon's information processing increased entropy.");
+        writeln!(interp.out(), "    The 2nd Law remains unbroken.");
         Ok(())
     });
 
     // Heat death of the universe
     interp.register("heat_death", |interp| {
-        println!("🌌  Fast-forwarding to the heat death of the universe...");
-        println!();
-        println!("    10^100 years later...");
-        println!();
-        println!("    All stars have burned out.");
-        println!("    All black holes have evaporated.");
-        println!("    Maximum entropy has been reached.");
-        println!("    Nothing can ever happen again.");
-        println!();
+        writeln!(interp.out(), "🌌  Fast-forwarding to the heat death of the universe...");
+        writeln!(interp.out());
+        writeln!(interp.out(), "    10^100 years later...");
+        writeln!(interp.out());
+        writeln!(interp.out(), "    All stars have burned out.");
+        writeln!(interp.out(), "    All black holes have evaporated.");
+        writeln!(interp.out(), "    Maximum entropy has been reached.");
+        writeln!(interp.out(), "    Nothing can ever happen again.");
+        writeln!(interp.out());
         
         // Clear the stack (maximum entropy = no structure)
         interp.stack_mut().clear();
         
-        println!("    Stack cleared. The universe is at peace.");
+        writeln!(interp.out(), "    Stack cleared. The universe is at peace.");
         Ok(())
     });
 
@@ -136,9 +136,9 @@ pub fn register(interp: &mut Interpreter) {
         let current_entropy = interp.stack().len() as f64;
         let new_entropy = current_entropy + 1.0;
         
-        println!("📈  Entropy always increases!");
-        println!("    Previous entropy: {}", current_entropy);
-        println!("    New entropy: {}", new_entropy);
+        writeln!(interp.out(), "📈  Entropy always increases!");
+        writeln!(interp.out(), "    Previous entropy: {}", current_entropy);
+        writeln!(interp.out(), "    New entropy: {}", new_entropy);
         
         // Push a random value to increase disorder
         interp.stack_mut().push(WofValue::double(rand::random::<f64>()));
@@ -5,14 +5,21 @@
 //! - Play chess games
 //! - Learn from games
 //! - Self-train by playing against itself
+//! - Play from an opening book and search ahead, with known endgames
+//!   (KRK, KPK) scored by hand, at a strength set by [`NeuralChessAI::set_strength`]
 
 use super::tensor::Tensor;
 use super::brain::BrainCore;
+use super::book::book_moves;
 use super::chess::{Board, Move, Square, Color, GameResult, PieceType};
 use super::cnn::board_to_planes;
 use super::ganglion::PingMeasurer;
+use super::game::{pick_by_temperature, random_unit, search_scores, GamePlayer, GameState};
 
 use std::collections::HashMap;
+use woflang_core::Out;
+
+use rayon::prelude::*;
 
 // ═══════════════════════════════════════════════════════════════════════════
 // TRAINING EXAMPLE
@@ -96,12 +103,23 @@ impl Default for GameRecord {
 // NEURAL CHESS AI
 // ═══════════════════════════════════════════════════════════════════════════
 
+/// Plies after which a self-play game is stopped.
+pub(crate) const MAX_GAME_PLIES: usize = 500;
+
+/// Moves of history the brain's RNN/LSTM sees.
+const HISTORY_LEN: usize = 50;
+
 /// Complete Neural Chess AI with learning capabilities.
+#[derive(Clone)]
 pub struct NeuralChessAI {
     /// The neural brain
     pub brain: BrainCore,
     /// Temperature for move selection (higher = more random)
     pub temperature: f32,
+    /// Play from the opening book while in book
+    pub use_book: bool,
+    /// Plies to search; 0 picks from the network's policy instead
+    pub search_depth: u32,
     /// Games played
     pub games_played: u64,
     /// Training examples buffer
@@ -154,6 +172,8 @@ impl NeuralChessAI {
         NeuralChessAI {
             brain: BrainCore::new(),
             temperature: 1.0,
+            use_book: false,
+            search_depth: 0,
             games_played: 0,
             training_buffer: Vec::new(),
             buffer_size: 10000,
@@ -182,7 +202,25 @@ impl NeuralChessAI {
         Tensor::vector(data)
     }
 
-    /// Select best legal move.
+    /// Set the playing strength from 0 (weakest) to 10. Level 2 and up
+    /// plays from the opening book, the search deepens from none at
+    /// levels 0-1 to three plies at 8-10, and the temperature falls from
+    /// 2.0 to 0.
+    pub fn set_strength(&mut self, level: u8) {
+        let level = level.min(10);
+        self.use_book = level >= 2;
+        self.search_depth = match level {
+            0..=1 => 0,
+            2..=4 => 1,
+            5..=7 => 2,
+            _ => 3,
+        };
+        self.temperature = f32::from(10 - level) * 0.2;
+    }
+
+    /// Select a legal move: from the opening book when enabled and in
+    /// book, else by searching `search_depth` plies, else from the
+    /// network's policy.
     pub fn select_move(&mut self, board: &Board) -> Option<Move> {
         let ping_start = self.ping.ping();
         
@@ -191,6 +229,44 @@ impl NeuralChessAI {
             return None;
         }
 
+        let selected_move = self
+            .book_or_search_move(board, &legal_moves)
+            .or_else(|| self.policy_move(board, &legal_moves));
+
+        let ping_time = self.ping.pong(ping_start);
+        self.stats.total_moves += 1;
+        self.stats.avg_move_time_ms = 
+            (self.stats.avg_move_time_ms * (self.stats.total_moves - 1) as f32 + 
+             ping_time as f32 / 1000.0) / self.stats.total_moves as f32;
+
+        selected_move
+    }
+
+    /// A move from the opening book when enabled and in book, else from
+    /// searching when `search_depth` is set; `None` leaves the choice to
+    /// the network.
+    fn book_or_search_move(&self, board: &Board, legal_moves: &[Move]) -> Option<Move> {
+        let book = if self.use_book { book_moves(board) } else { Vec::new() };
+        if !book.is_empty() {
+            let idx = (random_unit() * book.len() as f32) as usize;
+            book.get(idx.min(book.len() - 1)).copied()
+        } else if self.search_depth > 0 {
+            self.search_move(board, legal_moves)
+        } else {
+            None
+        }
+    }
+
+    /// Search every legal move `search_depth` plies deep and pick one by
+    /// score at the current temperature.
+    fn search_move(&self, board: &Board, legal_moves: &[Move]) -> Option<Move> {
+        let scored = search_scores(board, legal_moves, self.search_depth);
+        pick_by_temperature(&scored, self.temperature)
+    }
+
+    /// Pick the network's preferred move, or a random one with
+    /// probability epsilon.
+    fn policy_move(&mut self, board: &Board, legal_moves: &[Move]) -> Option<Move> {
         // Get neural network evaluation
         let planes = Self::board_to_input(board);
         let (value, policy) = self.brain.forward(&planes);
@@ -209,35 +285,34 @@ impl NeuralChessAI {
         seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
         let r = ((seed >> 33) as f32) / (u32::MAX as f32);
 
-        let selected_move = if r < self.epsilon {
+        if r < self.epsilon {
             // Random move
-            let idx = (r * legal_moves.len() as f32) as usize;
-            legal_moves.get(idx.min(legal_moves.len() - 1)).cloned()
+            Self::random_move(legal_moves, r)
         } else {
-            // Best move according to network
-            let mut best_move = None;
-            let mut best_score = f32::NEG_INFINITY;
+            Self::best_policy_move(&policy, legal_moves)
+        }
+    }
 
-            for m in &legal_moves {
-                let idx = Self::move_to_policy_index(m);
-                let score = policy.data[idx];
-                
-                if score > best_score {
-                    best_score = score;
-                    best_move = Some(m.clone());
-                }
-            }
+    /// The legal move at fraction `r` (in [0, 1)) of the list.
+    fn random_move(legal_moves: &[Move], r: f32) -> Option<Move> {
+        let idx = (r * legal_moves.len() as f32) as usize;
+        legal_moves.get(idx.min(legal_moves.len().saturating_sub(1))).copied()
+    }
 
-            best_move
-        };
+    /// The legal move the network's policy scores highest.
+    fn best_policy_move(policy: &Tensor, legal_moves: &[Move]) -> Option<Move> {
+        let mut best_move = None;
+        let mut best_score = f32::NEG_INFINITY;
 
-        let ping_time = self.ping.pong(ping_start);
-        self.stats.total_moves += 1;
-        self.stats.avg_move_time_ms = 
-            (self.stats.avg_move_time_ms * (self.stats.total_moves - 1) as f32 + 
-             ping_time as f32 / 1000.0) / self.stats.total_moves as f32;
+        for m in legal_moves {
+            let score = policy.data[Self::move_to_policy_index(m)];
+            if score > best_score {
+                best_score = score;
+                best_move = Some(*m);
+            }
+        }
 
-        selected_move
+        best_move
     }
 
     /// Evaluate a position.
@@ -337,9 +412,27 @@ impl NeuralChessAI {
 
     /// Self-play training: play games against itself and learn.
     pub fn self_play_train(&mut self, num_games: usize) {
-        println!("🧠 Starting self-play training: {} games", num_games);
+        self.self_play_train_until(num_games, &Out::stdout(), &mut |_, _| true);
+    }
+
+    /// Self-play training that calls `on_game(done, total)` before each
+    /// game and once after the last; training stops early if it returns
+    /// `false`. Progress is printed to `out`. Returns the number of games
+    /// completed. Learning from finished games is kept.
+    pub fn self_play_train_until(
+        &mut self,
+        num_games: usize,
+        out: &Out,
+        on_game: &mut dyn FnMut(usize, usize) -> bool,
+    ) -> usize {
+        writeln!(out, "🧠 Starting self-play training: {} games", num_games);
         
         for game_num in 0..num_games {
+            if !on_game(game_num, num_games) {
+                writeln!(out, "⏹️  Training stopped after {}/{} games", game_num, num_games);
+                return game_num;
+            }
+
             // Play a game against self
             let record = self.self_play_game();
             
@@ -351,7 +444,8 @@ impl NeuralChessAI {
             
             // Progress report
             if (game_num + 1) % 10 == 0 {
-                println!(
+                writeln!(
+                    out,
                     "  Game {}/{}: Win rate: {:.1}%, Avg move time: {:.2}ms, Buffer: {}",
                     game_num + 1,
                     num_games,
@@ -362,21 +456,22 @@ impl NeuralChessAI {
             }
         }
 
-        println!("✅ Self-play training complete!");
-        println!("{}", self.status_report());
+        on_game(num_games, num_games);
+        writeln!(out, "✅ Self-play training complete!");
+        writeln!(out, "{}", self.status_report());
+        num_games
     }
 
     /// Play a single game against itself.
-    fn self_play_game(&mut self) -> GameRecord {
+    pub(crate) fn self_play_game(&mut self) -> GameRecord {
         let mut board = Board::starting_position();
         let mut record = GameRecord::new();
         
         self.brain.reset_history();
 
-        let max_moves = 500;
         let mut move_count = 0;
 
-        while !board.is_game_over() && move_count < max_moves {
+        while !board.is_game_over() && move_count < MAX_GAME_PLIES {
             let planes = Self::board_to_input(&board);
             record.positions.push(planes);
             record.sides.push(board.side_to_move);
@@ -392,10 +487,16 @@ impl NeuralChessAI {
         }
 
         record.result = board.game_result();
-        
+        self.count_self_play_result(record.result);
+
+        record
+    }
+
+    /// Count a finished self-play game in the statistics.
+    fn count_self_play_result(&mut self, result: GameResult) {
         // Update stats based on perspective
         self.games_played += 1;
-        match record.result {
+        match result {
             GameResult::WhiteWins | GameResult::BlackWins => {
                 // In self-play, count as one win and one loss
                 self.stats.wins_as_white += 1;
@@ -404,8 +505,6 @@ impl NeuralChessAI {
             GameResult::Draw => self.stats.draws += 1,
             GameResult::Ongoing => {},
         }
-
-        record
     }
 
     /// Get status report.
@@ -420,6 +519,8 @@ impl NeuralChessAI {
              ║ Training Buffer:  {:>20}   ║\n\
              ║ Temperature:      {:>20.2}   ║\n\
              ║ Epsilon:          {:>20.2}   ║\n\
+             ║ Opening Book:     {:>20}   ║\n\
+             ║ Search Depth:     {:>20}   ║\n\
              ╠═══════════════════════════════════════════╣\n\
              ║ Wins as White:    {:>20}   ║\n\
              ║ Wins as Black:    {:>20}   ║\n\
@@ -433,6 +534,8 @@ impl NeuralChessAI {
             self.training_buffer.len(),
             self.temperature,
             self.epsilon,
+            if self.use_book { "on" } else { "off" },
+            self.search_depth,
             self.stats.wins_as_white,
             self.stats.wins_as_black,
             self.stats.losses_as_white,
@@ -460,38 +563,315 @@ impl Default for NeuralChessAI {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// PARALLEL SELF-PLAY
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A self-play game being played alongside others.
+struct LiveGame {
+    board: Board,
+    record: GameRecord,
+    /// Encoded moves so far, the context for the brain's RNN/LSTM.
+    history: Vec<Tensor>,
+}
+
+impl LiveGame {
+    fn new() -> Self {
+        LiveGame {
+            board: Board::starting_position(),
+            record: GameRecord::new(),
+            history: Vec::new(),
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.board.is_game_over() || self.record.moves_played.len() >= MAX_GAME_PLIES
+    }
+
+    /// Record the position and play `m` from it.
+    fn play(&mut self, planes: Vec<Tensor>, m: Move) {
+        self.record.positions.push(planes);
+        self.record.sides.push(self.board.side_to_move);
+        self.record.moves_played.push(NeuralChessAI::move_to_policy_target(&m));
+        self.history.push(BrainCore::encode_move(m.from.0 as usize, m.to.0 as usize));
+        if self.history.len() > HISTORY_LEN {
+            self.history.remove(0);
+        }
+        self.board.make_move_unchecked(m);
+    }
+}
+
+impl NeuralChessAI {
+    /// Self-play training with up to `threads` games played side by side
+    /// (0 = one per core), otherwise as [`Self::self_play_train_until`]:
+    /// `on_game(done, total)` is called at the start and as each game
+    /// finishes, and training stops early if it returns `false`, dropping
+    /// the games still in play. Returns the number of games completed.
+    ///
+    /// Each ply, every game's book, search and exploration moves are
+    /// worked out in parallel, then the positions left for the network
+    /// go through the brain as one batch. A finished game is learned from
+    /// at once and a new one takes its place.
+    pub fn self_play_train_parallel_until(
+        &mut self,
+        num_games: usize,
+        threads: usize,
+        out: &Out,
+        on_game: &mut dyn FnMut(usize, usize) -> bool,
+    ) -> Result<usize, rayon::ThreadPoolBuildError> {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build()?;
+        let slots = pool.current_num_threads();
+        writeln!(out, "🧠 Starting parallel self-play training: {} games on {} threads", num_games, slots);
+
+        let mut live: Vec<LiveGame> = Vec::with_capacity(slots);
+        let mut started = 0;
+        let mut done = 0;
+        if !on_game(0, num_games) {
+            writeln!(out, "⏹️  Training stopped after 0/{} games", num_games);
+            return Ok(0);
+        }
+        while done < num_games {
+            while live.len() < slots && started < num_games {
+                live.push(LiveGame::new());
+                started += 1;
+            }
+            pool.install(|| self.play_ply(&mut live));
+
+            let (finished, playing): (Vec<LiveGame>, Vec<LiveGame>) = live.into_iter().partition(LiveGame::is_over);
+            live = playing;
+            for game in finished {
+                let mut record = game.record;
+                record.result = game.board.game_result();
+                self.count_self_play_result(record.result);
+                self.add_game_examples(&record);
+                self.train_batch();
+                done += 1;
+
+                if done % 10 == 0 {
+                    writeln!(
+                        out,
+                        "  Game {}/{}: Win rate: {:.1}%, Avg move time: {:.2}ms, Buffer: {}",
+                        done,
+                        num_games,
+                        self.stats.win_rate() * 100.0,
+                        self.stats.avg_move_time_ms,
+                        self.training_buffer.len(),
+                    );
+                }
+                if !on_game(done, num_games) {
+                    writeln!(out, "⏹️  Training stopped after {}/{} games", done, num_games);
+                    return Ok(done);
+                }
+            }
+        }
+
+        writeln!(out, "✅ Self-play training complete!");
+        writeln!(out, "{}", self.status_report());
+        Ok(done)
+    }
+
+    /// Play one move in every game, on the current rayon pool.
+    fn play_ply(&mut self, live: &mut [LiveGame]) {
+        let start = std::time::Instant::now();
+
+        // Moves that need no network, and every position's planes
+        let prepared: Vec<(Vec<Tensor>, Vec<Move>, Option<Move>)> = live
+            .par_iter()
+            .map(|game| {
+                let legal_moves = game.board.generate_legal_moves();
+                let r: f32 = rand::random();
+                let chosen = self.book_or_search_move(&game.board, &legal_moves).or_else(|| {
+                    (r < self.epsilon).then(|| Self::random_move(&legal_moves, rand::random())).flatten()
+                });
+                (Self::board_to_input(&game.board), legal_moves, chosen)
+            })
+            .collect();
+
+        // The rest go through the brain together
+        let batch: Vec<(Vec<Tensor>, Vec<Tensor>)> = prepared
+            .iter()
+            .zip(live.iter())
+            .filter(|((_, _, chosen), _)| chosen.is_none())
+            .map(|((planes, _, _), game)| (planes.clone(), game.history.clone()))
+            .collect();
+        let mut policies = self.brain.evaluate_batch(&batch).into_iter().map(|(_, policy)| policy);
+
+        let mut moved = 0;
+        for (game, (planes, legal_moves, chosen)) in live.iter_mut().zip(prepared) {
+            let m = chosen.or_else(|| policies.next().and_then(|policy| Self::best_policy_move(&policy, &legal_moves)));
+            if let Some(m) = m {
+                game.play(planes, m);
+                moved += 1;
+            }
+        }
+
+        if moved > 0 {
+            // Wall time per move, so the average shows the speed-up
+            let before = self.stats.total_moves;
+            self.stats.total_moves += moved;
+            self.stats.avg_move_time_ms = (self.stats.avg_move_time_ms * before as f32
+                + start.elapsed().as_secs_f32() * 1000.0)
+                / self.stats.total_moves as f32;
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// ENDGAME HEURISTICS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// King moves between two squares.
+fn king_distance(a: Square, b: Square) -> i32 {
+    let ranks = (i32::from(a.rank()) - i32::from(b.rank())).abs();
+    let files = (i32::from(a.file()) - i32::from(b.file())).abs();
+    ranks.max(files)
+}
+
+/// Distance from the centre: 0 on d4, e4, d5 and e5, up to 6 in a corner.
+fn centre_distance(sq: Square) -> i32 {
+    let rank = i32::from(sq.rank());
+    let file = i32::from(sq.file());
+    (3 - rank).max(rank - 4) + (3 - file).max(file - 4)
+}
+
+/// Score a bare king against a king and one piece, in centipawns for
+/// White, or `None` with any other material.
+///
+/// - KRK and KQK: the mating pattern. The score grows as the lone king
+///   is driven to the edge and the attacking king closes in.
+/// - KPK: a pawn outside the defending king's reach (the rule of the
+///   square) is scored as a new queen. Otherwise the pawn is worth more
+///   with its king in front of it and nothing with a rook pawn whose
+///   queening square the defender holds.
+/// - KNK and KBK cannot be won and score 0.
+#[must_use]
+pub fn endgame_eval(board: &Board) -> Option<i32> {
+    let mut extra = None;
+    for (i, &piece) in board.squares.iter().enumerate() {
+        if piece == 0 || piece.abs() == PieceType::King as i8 {
+            continue;
+        }
+        if extra.is_some() {
+            return None;
+        }
+        extra = Some((Square(i as u8), piece));
+    }
+    let (sq, piece) = extra?;
+    let strong = if piece > 0 { Color::White } else { Color::Black };
+    let strong_king = board.find_king(strong)?;
+    let weak_king = board.find_king(strong.opposite())?;
+
+    let score = match PieceType::from_i8(piece)? {
+        pt @ (PieceType::Rook | PieceType::Queen) => {
+            pt.value() + 20 * centre_distance(weak_king) + 10 * (7 - king_distance(strong_king, weak_king))
+        }
+        PieceType::Pawn => kpk_score(board, strong, sq, strong_king, weak_king),
+        _ => 0,
+    };
+    Some(score * i32::from(strong.sign()))
+}
+
+/// Score king and pawn against king for the side with the pawn.
+fn kpk_score(board: &Board, strong: Color, pawn: Square, strong_king: Square, weak_king: Square) -> i32 {
+    // Ranks advanced from the pawn's own back rank, 1 to 6.
+    let advance = |sq: Square| match strong {
+        Color::White => i32::from(sq.rank()),
+        Color::Black => 7 - i32::from(sq.rank()),
+    };
+    let forward = advance(pawn);
+    let promotion = Square::new(if strong == Color::White { 7 } else { 0 }, pawn.file());
+    let steps = if forward == 1 { 5 } else { 7 - forward };
+
+    // The defender catches the pawn if it reaches the queening square in
+    // time, with one move in hand when it is to move.
+    let weak_to_move = board.side_to_move != strong;
+    if king_distance(weak_king, promotion) > steps + i32::from(weak_to_move) {
+        return PieceType::Queen.value() - 100 + 10 * forward;
+    }
+    if matches!(pawn.file(), 0 | 7) && king_distance(weak_king, promotion) <= 1 {
+        return 0;
+    }
+
+    let mut score = PieceType::Pawn.value() + 10 * forward;
+    let king_in_front = advance(strong_king) > forward
+        && (i32::from(strong_king.file()) - i32::from(pawn.file())).abs() <= 1;
+    if king_in_front {
+        score += 200;
+    }
+    score + 5 * (king_distance(weak_king, pawn) - king_distance(strong_king, pawn))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// EVALUATION
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Static evaluation in centipawns for White: the endgame heuristics
+/// where they apply, otherwise material plus small bonuses for advanced
+/// pawns and centralised minor pieces.
+#[must_use]
+pub fn evaluate_position(board: &Board) -> i32 {
+    if let Some(score) = endgame_eval(board) {
+        return score;
+    }
+    let mut score = board.material_balance();
+    for (i, &piece) in board.squares.iter().enumerate() {
+        let sq = Square(i as u8);
+        let bonus = match PieceType::from_i8(piece) {
+            Some(PieceType::Pawn) if piece > 0 => 5 * i32::from(sq.rank()),
+            Some(PieceType::Pawn) => 5 * (7 - i32::from(sq.rank())),
+            Some(PieceType::Knight | PieceType::Bishop) => 5 * (6 - centre_distance(sq)),
+            _ => 0,
+        };
+        score += bonus * i32::from(piece.signum());
+    }
+    score
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // INTERACTIVE GAME SESSION
 // ═══════════════════════════════════════════════════════════════════════════
 
-/// Interactive game session for playing against the AI.
-pub struct GameSession {
+impl GamePlayer<Board> for NeuralChessAI {
+    fn select_move(&mut self, board: &Board) -> Option<Move> {
+        NeuralChessAI::select_move(self, board)
+    }
+
+    fn evaluate(&mut self, board: &Board) -> f32 {
+        NeuralChessAI::evaluate(self, board)
+    }
+
+    fn new_game(&mut self) {
+        self.brain.reset_history();
+    }
+}
+
+/// Interactive game session for playing against the AI: chess against
+/// the neural AI by default, or any [`GameState`] against any
+/// [`GamePlayer`].
+pub struct GameSession<G: GameState = Board, P = NeuralChessAI> {
     /// The AI opponent
-    pub ai: NeuralChessAI,
+    pub ai: P,
     /// Current board state
-    pub board: Board,
+    pub board: G,
     /// Human plays as (true = white)
     pub human_is_white: bool,
     /// Move history
-    pub move_history: Vec<Move>,
+    pub move_history: Vec<G::Move>,
 }
 
 impl GameSession {
     /// Create new game session.
     pub fn new(human_is_white: bool) -> Self {
-        GameSession {
-            ai: NeuralChessAI::new(),
-            board: Board::starting_position(),
-            human_is_white,
-            move_history: Vec::new(),
-        }
+        GameSession::with_ai(NeuralChessAI::new(), human_is_white)
     }
+}
 
+impl<G: GameState, P: GamePlayer<G>> GameSession<G, P> {
     /// Create with existing AI.
-    pub fn with_ai(ai: NeuralChessAI, human_is_white: bool) -> Self {
+    pub fn with_ai(ai: P, human_is_white: bool) -> Self {
         GameSession {
             ai,
-            board: Board::starting_position(),
+            board: G::start(),
             human_is_white,
             move_history: Vec::new(),
         }
@@ -499,38 +879,30 @@ impl GameSession {
 
     /// Check if it's the human's turn.
     pub fn is_human_turn(&self) -> bool {
-        (self.board.side_to_move == Color::White) == self.human_is_white
+        (self.board.side_to_move() == Color::White) == self.human_is_white
     }
 
-    /// Make a human move (from algebraic notation).
+    /// Make a human move, in the game's notation.
     pub fn human_move(&mut self, move_str: &str) -> Result<(), String> {
         if !self.is_human_turn() {
             return Err("Not your turn!".to_string());
         }
 
-        let m = Move::from_uci(move_str)
-            .ok_or_else(|| "Invalid move format. Use UCI notation (e.g., e2e4)".to_string())?;
-
-        let legal_moves = self.board.generate_legal_moves();
-        let legal_move = legal_moves.iter()
-            .find(|lm| lm.from == m.from && lm.to == m.to)
-            .cloned()
-            .ok_or_else(|| "Illegal move!".to_string())?;
-
-        self.board.make_move_unchecked(legal_move);
+        let legal_move = self.board.parse_move(move_str)?;
+        self.board.apply(legal_move);
         self.move_history.push(legal_move);
 
         Ok(())
     }
 
     /// Get AI's move.
-    pub fn ai_move(&mut self) -> Option<Move> {
+    pub fn ai_move(&mut self) -> Option<G::Move> {
         if self.is_human_turn() {
             return None;
         }
 
         let m = self.ai.select_move(&self.board)?;
-        self.board.make_move_unchecked(m);
+        self.board.apply(m);
         self.move_history.push(m);
         
         Some(m)
@@ -543,15 +915,15 @@ impl GameSession {
 
     /// Get legal moves list.
     pub fn legal_moves(&self) -> Vec<String> {
-        self.board.generate_legal_moves()
+        self.board.legal_moves()
             .iter()
-            .map(|m| m.to_uci())
+            .map(|m| m.to_string())
             .collect()
     }
 
     /// Get game status.
     pub fn status(&self) -> String {
-        match self.board.game_result() {
+        match self.board.result() {
             GameResult::Ongoing => {
                 let turn = if self.is_human_turn() { "Your" } else { "AI's" };
                 format!("{} turn to move", turn)
@@ -570,7 +942,7 @@ impl GameSession {
 
     /// Check if game is over.
     pub fn is_game_over(&self) -> bool {
-        self.board.is_game_over()
+        self.board.result() != GameResult::Ongoing
     }
 
     /// Get AI evaluation of current position.
@@ -580,9 +952,9 @@ impl GameSession {
 
     /// Reset for new game.
     pub fn new_game(&mut self) {
-        self.board = Board::starting_position();
+        self.board = G::start();
         self.move_history.clear();
-        self.ai.brain.reset_history();
+        self.ai.new_game();
     }
 }
 
@@ -631,34 +1003,56 @@ impl Default for TrainingConfig {
 impl NeuralChessAI {
     /// Run full training with configuration.
     pub fn train_with_config(&mut self, config: &TrainingConfig) {
+        self.train_with_config_until(config, &Out::stdout(), &mut |_, _| true);
+    }
+
+    /// Run full training, calling `on_game(done, total)` with the counts
+    /// across all iterations as in [`Self::self_play_train_until`].
+    /// Returns the number of games completed.
+    pub fn train_with_config_until(
+        &mut self,
+        config: &TrainingConfig,
+        out: &Out,
+        on_game: &mut dyn FnMut(usize, usize) -> bool,
+    ) -> usize {
         self.temperature = config.initial_temperature;
         self.epsilon = config.initial_epsilon;
         self.buffer_size = config.buffer_size;
         self.batch_size = config.batch_size;
         self.learning_rate = config.learning_rate;
 
-        println!("🚀 Starting Neural Chess Training");
-        println!("   Iterations: {}", config.iterations);
-        println!("   Games/iteration: {}", config.games_per_iteration);
+        writeln!(out, "🚀 Starting Neural Chess Training");
+        writeln!(out, "   Iterations: {}", config.iterations);
+        writeln!(out, "   Games/iteration: {}", config.games_per_iteration);
 
+        let total = config.iterations * config.games_per_iteration;
+        let mut games = 0;
         for iteration in 0..config.iterations {
-            println!("\n📊 Iteration {}/{}", iteration + 1, config.iterations);
+            writeln!(out, "\n📊 Iteration {}/{}", iteration + 1, config.iterations);
             
-            self.self_play_train(config.games_per_iteration);
+            let played = self.self_play_train_until(config.games_per_iteration, out, &mut |done, _| {
+                on_game(games + done, total)
+            });
+            games += played;
+            if played < config.games_per_iteration {
+                return games;
+            }
             
             // Anneal hyperparameters
             self.anneal_temperature(config.temperature_decay);
             self.anneal_epsilon(config.epsilon_decay);
             
-            println!(
+            writeln!(
+                out,
                 "   Temperature: {:.3}, Epsilon: {:.3}",
                 self.temperature,
                 self.epsilon,
             );
         }
 
-        println!("\n🏆 Training Complete!");
-        println!("{}", self.status_report());
+        writeln!(out, "\n🏆 Training Complete!");
+        writeln!(out, "{}", self.status_report());
+        games
     }
 }
 
@@ -690,6 +1084,117 @@ mod tests {
         assert!(!record.positions.is_empty());
     }
 
+    #[test]
+    fn test_parallel_self_play() {
+        let mut ai = NeuralChessAI::new();
+        ai.use_book = true;
+        ai.epsilon = 1.0;  // Random after the book, for a fast test
+        ai.batch_size = 1;
+
+        let mut calls = Vec::new();
+        let played = ai
+            .self_play_train_parallel_until(3, 2, &Out::stdout(), &mut |done, total| {
+                calls.push((done, total));
+                true
+            })
+            .unwrap();
+        assert_eq!(played, 3);
+        assert_eq!(calls, [(0, 3), (1, 3), (2, 3), (3, 3)]);
+        assert_eq!(ai.games_played, 3);
+        assert!(ai.training_buffer.len() >= 3);
+
+        // Stopping keeps what was learned from finished games
+        let played = ai.self_play_train_parallel_until(5, 2, &Out::stdout(), &mut |done, _| done < 1).unwrap();
+        assert_eq!(played, 1);
+        assert_eq!(ai.games_played, 4);
+    }
+
+    #[test]
+    fn test_infer_matches_forward() {
+        let mut brain = BrainCore::new();
+        let planes = NeuralChessAI::board_to_input(&Board::starting_position());
+        let (value, policy) = brain.infer(&planes, &[]);
+        let (fwd_value, fwd_policy) = brain.forward(&planes);
+        assert!((value - fwd_value).abs() < 1e-5);
+        assert_eq!(policy.argmax(), fwd_policy.argmax());
+
+        let history = vec![BrainCore::encode_move(12, 28)];
+        let batch = vec![(planes.clone(), Vec::new()), (planes, history)];
+        let evals = brain.evaluate_batch(&batch);
+        assert_eq!(evals.len(), 2);
+        assert!((evals[0].0 - value).abs() < 1e-5);
+    }
+
+    /// A board with the given pieces, White to move.
+    fn position(pieces: &[(&str, PieceType, Color)]) -> Board {
+        let mut board = Board::empty();
+        for &(sq, piece, color) in pieces {
+            board.set_piece(Square::from_algebraic(sq).unwrap(), piece, color);
+        }
+        board
+    }
+
+    #[test]
+    fn test_search_finds_krk_mate() {
+        let board = position(&[
+            ("b6", PieceType::King, Color::White),
+            ("h1", PieceType::Rook, Color::White),
+            ("a8", PieceType::King, Color::Black),
+        ]);
+        let mut ai = NeuralChessAI::new();
+        ai.set_strength(4);
+        assert_eq!(ai.search_depth, 1);
+        assert_eq!(ai.select_move(&board).unwrap().to_uci(), "h1h8");
+    }
+
+    #[test]
+    fn test_endgame_eval() {
+        let krk = |weak_king| {
+            endgame_eval(&position(&[
+                ("c3", PieceType::King, Color::White),
+                ("h1", PieceType::Rook, Color::White),
+                (weak_king, PieceType::King, Color::Black),
+            ]))
+            .unwrap()
+        };
+        assert!(krk("a8") > krk("e5"));
+        assert!(krk("e5") > 0);
+
+        // The e-pawn outruns the king on a1; on d5 the king catches it.
+        let kpk = |weak_king, pawn| {
+            endgame_eval(&position(&[
+                ("e1", PieceType::King, Color::White),
+                (pawn, PieceType::Pawn, Color::White),
+                (weak_king, PieceType::King, Color::Black),
+            ]))
+            .unwrap()
+        };
+        assert!(kpk("a1", "e4") > PieceType::Rook.value());
+        assert!(kpk("d5", "e4") < PieceType::Rook.value());
+        assert_eq!(kpk("h8", "h5"), 0);
+
+        let knk = position(&[
+            ("e1", PieceType::King, Color::White),
+            ("e8", PieceType::King, Color::Black),
+            ("b1", PieceType::Knight, Color::Black),
+        ]);
+        assert_eq!(endgame_eval(&knk), Some(0));
+        assert_eq!(endgame_eval(&Board::starting_position()), None);
+    }
+
+    #[test]
+    fn test_strength_plays_from_book() {
+        let mut ai = NeuralChessAI::new();
+        ai.set_strength(10);
+        assert!(ai.use_book);
+        assert_eq!(ai.search_depth, 3);
+        assert!(ai.temperature <= 0.0);
+
+        let board = Board::starting_position();
+        let m = ai.select_move(&board).unwrap();
+        assert!(book_moves(&board).contains(&m));
+    }
+
     #[test]
     fn test_game_session() {
         let mut session = GameSession::new(true);
@@ -0,0 +1,96 @@
+//! Opening Book - a handful of mainline openings.
+//!
+//! Lines are stored as UCI move sequences and matched by position rather
+//! than by move order, so transpositions into a book line stay in book.
+
+use super::chess::{Board, Move};
+
+/// Book lines from the starting position, in UCI notation.
+const LINES: &[&str] = &[
+    // Ruy Lopez
+    "e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6 e1g1 f8e7",
+    // Italian Game
+    "e2e4 e7e5 g1f3 b8c6 f1c4 f8c5 c2c3 g8f6 d2d3 d7d6",
+    // Sicilian Najdorf
+    "e2e4 c7c5 g1f3 d7d6 d2d4 c5d4 f3d4 g8f6 b1c3 a7a6",
+    // French Defence
+    "e2e4 e7e6 d2d4 d7d5 b1c3 g8f6 c1g5 f8e7",
+    // Caro-Kann Defence
+    "e2e4 c7c6 d2d4 d7d5 b1c3 d5e4 c3e4 c8f5",
+    // Queen's Gambit Declined
+    "d2d4 d7d5 c2c4 e7e6 b1c3 g8f6 c1g5 f8e7 e2e3 e8g8",
+    // Slav Defence
+    "d2d4 d7d5 c2c4 c7c6 g1f3 g8f6 b1c3 d5c4",
+    // King's Indian Defence
+    "d2d4 g8f6 c2c4 g7g6 b1c3 f8g7 e2e4 d7d6 g1f3 e8g8",
+    // Nimzo-Indian Defence
+    "d2d4 g8f6 c2c4 e7e6 b1c3 f8b4 e2e3 e8g8",
+    // English Opening
+    "c2c4 e7e5 b1c3 g8f6 g1f3 b8c6 g2g3 d7d5",
+    // Réti Opening
+    "g1f3 d7d5 g2g3 g8f6 f1g2 e7e6 e1g1 f8e7",
+];
+
+/// The legal move matching a UCI string, with castling and en passant
+/// filled in.
+fn resolve(board: &Board, uci: &str) -> Option<Move> {
+    let m = Move::from_uci(uci)?;
+    board
+        .generate_legal_moves()
+        .into_iter()
+        .find(|lm| lm.from == m.from && lm.to == m.to && lm.promotion == m.promotion)
+}
+
+/// All book moves for a position, without duplicates.
+#[must_use]
+pub fn book_moves(board: &Board) -> Vec<Move> {
+    let mut moves: Vec<Move> = Vec::new();
+    for line in LINES {
+        let mut pos = Board::starting_position();
+        for uci in line.split_whitespace() {
+            let Some(m) = resolve(&pos, uci) else { break };
+            if pos.squares == board.squares && pos.side_to_move == board.side_to_move {
+                if !moves.contains(&m) {
+                    moves.push(m);
+                }
+                break;
+            }
+            pos.make_move_unchecked(m);
+        }
+    }
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_book_lines_are_legal() {
+        for line in LINES {
+            let mut board = Board::starting_position();
+            for uci in line.split_whitespace() {
+                let m = resolve(&board, uci);
+                assert!(m.is_some(), "{uci} in {line}");
+                board.make_move_unchecked(m.unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_book_follows_transpositions() {
+        let start = book_moves(&Board::starting_position());
+        let ucis: Vec<String> = start.iter().map(Move::to_uci).collect();
+        assert_eq!(ucis, ["e2e4", "d2d4", "c2c4", "g1f3"]);
+
+        // 1. Nf3 d5 2. g3 reached as 1. g3 d5 2. Nf3 is still in book.
+        let mut board = Board::starting_position();
+        for uci in ["g2g3", "d7d5", "g1f3"] {
+            board.make_move_unchecked(resolve(&board, uci).unwrap());
+        }
+        assert_eq!(book_moves(&board).iter().map(Move::to_uci).collect::<Vec<_>>(), ["g8f6"]);
+
+        board.make_move_unchecked(resolve(&board, "a7a6").unwrap());
+        assert!(book_moves(&board).is_empty());
+    }
+}
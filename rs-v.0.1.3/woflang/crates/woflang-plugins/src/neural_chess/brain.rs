@@ -18,11 +18,14 @@ use super::lstm::LSTM;
 use super::cnn::ChessCNN;
 use super::ganglion::{Ganglion, NeuralClockCoordinator};
 
+use rayon::prelude::*;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // FUSION LAYER
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// Fuses outputs from CNN, RNN, and LSTM into a unified representation.
+#[derive(Clone)]
 pub struct FusionLayer {
     /// CNN feature projection
     cnn_proj: Dense,
@@ -65,28 +68,23 @@ impl FusionLayer {
         let rnn_proj = self.rnn_proj.forward(rnn_features);
         let lstm_proj = self.lstm_proj.forward(lstm_features);
         
-        // Concatenate for attention
-        let concat_data: Vec<f32> = cnn_proj.data.iter()
-            .chain(rnn_proj.data.iter())
-            .chain(lstm_proj.data.iter())
-            .copied()
-            .collect();
-        let concat = Tensor::from_data(concat_data, &[cnn_proj.size() * 3]);
-        
         // Compute attention weights (3 weights summing to 1)
-        let attn_weights = self.attention.forward(&concat);
-        
-        // Weighted combination
-        let weighted_cnn = cnn_proj.scale(attn_weights.data[0]);
-        let weighted_rnn = rnn_proj.scale(attn_weights.data[1]);
-        let weighted_lstm = lstm_proj.scale(attn_weights.data[2]);
-        
-        let combined = weighted_cnn.add(&weighted_rnn).add(&weighted_lstm);
+        let attn_weights = self.attention.forward(&concat3(&cnn_proj, &rnn_proj, &lstm_proj));
+        let combined = weigh3(&cnn_proj, &rnn_proj, &lstm_proj, &attn_weights);
         
         // Final projection
         self.fusion_proj.forward(&combined)
     }
 
+    /// Fuse features without caching for backward.
+    pub fn infer(&self, cnn_features: &Tensor, rnn_features: &Tensor, lstm_features: &Tensor) -> Tensor {
+        let cnn_proj = self.cnn_proj.infer(cnn_features);
+        let rnn_proj = self.rnn_proj.infer(rnn_features);
+        let lstm_proj = self.lstm_proj.infer(lstm_features);
+        let attn_weights = self.attention.infer(&concat3(&cnn_proj, &rnn_proj, &lstm_proj));
+        self.fusion_proj.infer(&weigh3(&cnn_proj, &rnn_proj, &lstm_proj, &attn_weights))
+    }
+
     /// Update weights.
     pub fn update(&mut self, learning_rate: f32) {
         self.cnn_proj.update(learning_rate);
@@ -97,11 +95,23 @@ impl FusionLayer {
     }
 }
 
+/// Concatenate three projections for attention.
+fn concat3(a: &Tensor, b: &Tensor, c: &Tensor) -> Tensor {
+    let data: Vec<f32> = a.data.iter().chain(b.data.iter()).chain(c.data.iter()).copied().collect();
+    Tensor::from_data(data, &[a.size() * 3])
+}
+
+/// Combine three projections by their attention weights.
+fn weigh3(a: &Tensor, b: &Tensor, c: &Tensor, weights: &Tensor) -> Tensor {
+    a.scale(weights.data[0]).add(&b.scale(weights.data[1])).add(&c.scale(weights.data[2]))
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // MOVE GENERATOR (GAN Generator)
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// Generates candidate chess moves from fused neural features.
+#[derive(Clone)]
 pub struct MoveGenerator {
     /// Input projection
     input_proj: Dense,
@@ -159,6 +169,7 @@ impl MoveGenerator {
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// Evaluates chess positions (like GAN discriminator).
+#[derive(Clone)]
 pub struct PositionEvaluator {
     /// Input projection
     input_proj: Dense,
@@ -199,6 +210,13 @@ impl PositionEvaluator {
         (value.data[0], policy)
     }
 
+    /// Evaluate a position without caching for backward.
+    pub fn infer(&self, features: &Tensor) -> (f32, Tensor) {
+        let x = self.hidden2.infer(&self.hidden1.infer(&self.input_proj.infer(features)));
+        let value = self.value_head.infer(&x);
+        (value.data[0], softmax(&self.policy_head.infer(&x)))
+    }
+
     /// Update weights.
     pub fn update(&mut self, learning_rate: f32) {
         self.input_proj.update(learning_rate);
@@ -214,6 +232,7 @@ impl PositionEvaluator {
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// The complete 3-way GAN Brain Core combining CNN + RNN + LSTM.
+#[derive(Clone)]
 pub struct BrainCore {
     /// CNN for spatial pattern recognition
     pub cnn: ChessCNN,
@@ -324,6 +343,26 @@ impl BrainCore {
         (value, policy)
     }
 
+    /// Forward pass for a position reached after the moves in `history`,
+    /// without touching caches, the clock or the brain's own history, so
+    /// several threads can evaluate positions at once.
+    pub fn infer(&self, board_planes: &[Tensor], history: &[Tensor]) -> (f32, Tensor) {
+        let cnn_features = self.cnn.infer(board_planes);
+        let (rnn_features, lstm_features) = if history.is_empty() {
+            (Tensor::zeros(&[128]), Tensor::zeros(&[128]))
+        } else {
+            (self.rnn.infer_final_hidden(history), self.lstm.infer_final_hidden(history))
+        };
+        let fused = self.fusion.infer(&cnn_features, &rnn_features, &lstm_features);
+        self.evaluator.infer(&fused)
+    }
+
+    /// Evaluate a batch of `(planes, history)` positions in parallel on the
+    /// current rayon pool, returning `(value, policy)` for each in order.
+    pub fn evaluate_batch(&self, batch: &[(Vec<Tensor>, Vec<Tensor>)]) -> Vec<(f32, Tensor)> {
+        batch.par_iter().map(|(planes, history)| self.infer(planes, history)).collect()
+    }
+
     /// Get best move (greedy).
     pub fn get_best_move(&mut self, board_planes: &[Tensor]) -> (usize, usize, f32) {
         let (value, policy) = self.forward(board_planes);
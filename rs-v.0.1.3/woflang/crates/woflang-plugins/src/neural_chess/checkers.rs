@@ -0,0 +1,394 @@
+//! Checkers - English draughts on the shared game layer.
+//!
+//! Implements:
+//! - The 32 dark squares, numbered 1-32 from Black's side
+//! - Men moving forward, kings both ways, promotion on the far row
+//! - Compulsory captures and multi-jumps (a man crowned mid-jump stops)
+//! - A draw after 80 plies without a capture or a man moving
+//!
+//! Black moves first. Moves are written with square numbers: `11-15`,
+//! or `15x22x31` for jumps (`15x31` is enough when only one path fits).
+
+use std::fmt;
+
+use super::chess::{Color, GameResult};
+use super::game::GameState;
+
+/// Plies without a capture or a man moving before the game is drawn.
+const DRAW_PLIES: u32 = 80;
+
+/// The most squares a move can visit: a king taking all twelve pieces.
+const MAX_PATH: usize = 13;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// SQUARES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Row (0 = Black's back row) and column of a square index (0-31).
+fn coords(sq: usize) -> (i8, i8) {
+    let row = (sq / 4) as i8;
+    let col = 2 * (sq % 4) as i8 + i8::from(row % 2 == 0);
+    (row, col)
+}
+
+/// The square index at a row and column, if that is a dark square.
+fn square_at(row: i8, col: i8) -> Option<usize> {
+    let on_board = (0..8).contains(&row) && (0..8).contains(&col);
+    (on_board && (row + col) % 2 == 1).then(|| (row * 4 + col / 2) as usize)
+}
+
+/// Diagonal directions as (row, col) steps.
+const DIRECTIONS: [(i8, i8); 4] = [(1, -1), (1, 1), (-1, -1), (-1, 1)];
+
+// ═══════════════════════════════════════════════════════════════════════════
+// MOVE
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A checkers move: the squares a piece visits, jumping if `capture`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckersMove {
+    path: [u8; MAX_PATH],
+    len: u8,
+    /// Whether the move jumps pieces.
+    pub capture: bool,
+}
+
+impl CheckersMove {
+    fn new(path: &[u8], capture: bool) -> Self {
+        let mut squares = [0; MAX_PATH];
+        squares[..path.len()].copy_from_slice(path);
+        CheckersMove { path: squares, len: path.len() as u8, capture }
+    }
+
+    /// The squares visited, as indices 0-31.
+    pub fn path(&self) -> &[u8] {
+        &self.path[..self.len as usize]
+    }
+}
+
+impl fmt::Display for CheckersMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sep = if self.capture { "x" } else { "-" };
+        let squares: Vec<String> = self.path().iter().map(|sq| (sq + 1).to_string()).collect();
+        write!(f, "{}", squares.join(sep))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BOARD
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A checkers position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checkers {
+    /// Pieces on squares 1-32 (index 0-31): positive = white, negative =
+    /// black, 1 = man, 2 = king, 0 = empty
+    pub squares: [i8; 32],
+    /// Side to move
+    pub side_to_move: Color,
+    /// Plies since the last capture or man move
+    pub quiet_plies: u32,
+}
+
+impl Checkers {
+    /// Create starting position: Black on 1-12, White on 21-32.
+    pub fn starting_position() -> Self {
+        let mut squares = [0; 32];
+        for (i, sq) in squares.iter_mut().enumerate() {
+            *sq = match i {
+                0..=11 => -1,
+                20..=31 => 1,
+                _ => 0,
+            };
+        }
+        Checkers { squares, side_to_move: Color::Black, quiet_plies: 0 }
+    }
+
+    /// Whether the piece on `sq` belongs to `color`.
+    fn owns(&self, sq: usize, color: Color) -> bool {
+        self.squares[sq] * color.sign() > 0
+    }
+
+    /// Directions the piece on `sq` may move in.
+    fn directions(&self, sq: usize) -> &'static [(i8, i8)] {
+        match self.squares[sq] {
+            2 | -2 => &DIRECTIONS,
+            -1 => &DIRECTIONS[..2],
+            _ => &DIRECTIONS[2..],
+        }
+    }
+
+    /// The row where the side's men are crowned.
+    fn crowning_row(color: Color) -> i8 {
+        if color == Color::Black { 7 } else { 0 }
+    }
+
+    /// Continue every jump sequence from the end of `path`, adding the
+    /// finished ones to `moves`.
+    fn extend_jumps(&self, path: &mut Vec<u8>, jumped: &mut Vec<usize>, moves: &mut Vec<CheckersMove>) {
+        let (Some(&start), Some(&at)) = (path.first(), path.last()) else {
+            return;
+        };
+        let (start, at) = (start as usize, at as usize);
+        let (row, col) = coords(at);
+        let mut extended = false;
+        let crowned = self.squares[start].abs() == 1 && row == Self::crowning_row(self.side_to_move);
+
+        if !crowned || path.len() == 1 {
+            for &(dr, dc) in self.directions(start) {
+                let (Some(over), Some(to)) = (square_at(row + dr, col + dc), square_at(row + 2 * dr, col + 2 * dc)) else {
+                    continue;
+                };
+                let enemy = self.owns(over, self.side_to_move.opposite()) && !jumped.contains(&over);
+                if enemy && (self.squares[to] == 0 || to == start) {
+                    path.push(to as u8);
+                    jumped.push(over);
+                    self.extend_jumps(path, jumped, moves);
+                    jumped.pop();
+                    path.pop();
+                    extended = true;
+                }
+            }
+        }
+        if !extended && path.len() > 1 {
+            moves.push(CheckersMove::new(path, true));
+        }
+    }
+
+    /// Legal moves: the jumps if there are any, since capturing is
+    /// compulsory, otherwise the plain moves.
+    pub fn generate_legal_moves(&self) -> Vec<CheckersMove> {
+        let own: Vec<usize> = (0..32).filter(|&sq| self.owns(sq, self.side_to_move)).collect();
+
+        let mut jumps = Vec::new();
+        for &sq in &own {
+            self.extend_jumps(&mut vec![sq as u8], &mut Vec::new(), &mut jumps);
+        }
+        if !jumps.is_empty() {
+            return jumps;
+        }
+
+        let mut moves = Vec::new();
+        for &sq in &own {
+            let (row, col) = coords(sq);
+            for &(dr, dc) in self.directions(sq) {
+                if let Some(to) = square_at(row + dr, col + dc).filter(|&to| self.squares[to] == 0) {
+                    moves.push(CheckersMove::new(&[sq as u8, to as u8], false));
+                }
+            }
+        }
+        moves
+    }
+
+    /// Make a legal move.
+    pub fn make_move(&mut self, m: CheckersMove) {
+        let path = m.path();
+        let from = path[0] as usize;
+        let to = path[path.len() - 1] as usize;
+        let piece = self.squares[from];
+
+        if m.capture {
+            for step in path.windows(2) {
+                let (r1, c1) = coords(step[0] as usize);
+                let (r2, c2) = coords(step[1] as usize);
+                if let Some(over) = square_at((r1 + r2) / 2, (c1 + c2) / 2) {
+                    self.squares[over] = 0;
+                }
+            }
+        }
+        self.squares[from] = 0;
+        let crowned = piece.abs() == 1 && coords(to).0 == Self::crowning_row(self.side_to_move);
+        self.squares[to] = if crowned { 2 * piece } else { piece };
+
+        self.quiet_plies = if m.capture || piece.abs() == 1 { 0 } else { self.quiet_plies + 1 };
+        self.side_to_move = self.side_to_move.opposite();
+    }
+
+    /// Material for White: 100 a man, 150 a king, and a little for each
+    /// row a man has advanced.
+    pub fn material_balance(&self) -> i32 {
+        let mut score = 0;
+        for (sq, &piece) in self.squares.iter().enumerate() {
+            let row = i32::from(coords(sq).0);
+            let value = match piece {
+                1 => 100 + 2 * (7 - row),
+                -1 => 100 + 2 * row,
+                _ => 150,
+            };
+            score += value * i32::from(piece.signum());
+        }
+        score
+    }
+}
+
+impl fmt::Display for Checkers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  ┌───┬───┬───┬───┬───┬───┬───┬───┐")?;
+
+        for row in 0..8 {
+            write!(f, "  │")?;
+            for col in 0..8 {
+                match square_at(row, col) {
+                    Some(sq) => match self.squares[sq] {
+                        1 => write!(f, " w │")?,
+                        2 => write!(f, " W │")?,
+                        -1 => write!(f, " b │")?,
+                        -2 => write!(f, " B │")?,
+                        _ => write!(f, "{:>2} │", sq + 1)?,
+                    },
+                    None => write!(f, "   │")?,
+                }
+            }
+            writeln!(f)?;
+
+            if row < 7 {
+                writeln!(f, "  ├───┼───┼───┼───┼───┼───┼───┼───┤")?;
+            }
+        }
+
+        writeln!(f, "  └───┴───┴───┴───┴───┴───┴───┴───┘")?;
+        writeln!(f)?;
+        writeln!(f, "Side to move: {:?}", self.side_to_move)?;
+
+        Ok(())
+    }
+}
+
+impl Default for Checkers {
+    fn default() -> Self {
+        Self::starting_position()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// GAME STATE
+// ═══════════════════════════════════════════════════════════════════════════
+
+impl GameState for Checkers {
+    type Move = CheckersMove;
+
+    fn start() -> Self {
+        Checkers::starting_position()
+    }
+
+    fn side_to_move(&self) -> Color {
+        self.side_to_move
+    }
+
+    fn legal_moves(&self) -> Vec<CheckersMove> {
+        self.generate_legal_moves()
+    }
+
+    fn apply(&mut self, m: CheckersMove) {
+        self.make_move(m);
+    }
+
+    /// A side that cannot move loses.
+    fn outcome(&self, legal_moves: &[CheckersMove]) -> GameResult {
+        if legal_moves.is_empty() {
+            match self.side_to_move {
+                Color::White => GameResult::BlackWins,
+                Color::Black => GameResult::WhiteWins,
+            }
+        } else if self.quiet_plies >= DRAW_PLIES {
+            GameResult::Draw
+        } else {
+            GameResult::Ongoing
+        }
+    }
+
+    fn evaluate(&self) -> i32 {
+        self.material_balance()
+    }
+
+    /// Longest jumps first.
+    fn move_order(&self, m: &CheckersMove) -> i32 {
+        -i32::from(m.len) * i32::from(m.capture)
+    }
+
+    /// A move with every square given, or just the first and last when
+    /// only one legal move joins them.
+    fn parse_move(&self, text: &str) -> Result<CheckersMove, String> {
+        let squares: Option<Vec<u8>> = text
+            .split(['-', 'x', 'X'])
+            .map(|n| n.trim().parse::<u8>().ok().filter(|n| (1..=32).contains(n)).map(|n| n - 1))
+            .collect();
+        let squares = squares
+            .filter(|s| s.len() >= 2)
+            .ok_or_else(|| "Invalid move format. Use square numbers (e.g., 11-15 or 15x22)".to_string())?;
+
+        let legal = self.generate_legal_moves();
+        if let Some(m) = legal.iter().find(|m| m.path() == squares.as_slice()) {
+            return Ok(*m);
+        }
+        let ends = |m: &&CheckersMove| {
+            squares.len() == 2 && m.path()[0] == squares[0] && m.path().last() == squares.last()
+        };
+        let matching: Vec<&CheckersMove> = legal.iter().filter(ends).collect();
+        match matching.as_slice() {
+            [m] => Ok(**m),
+            [] if legal.iter().any(|m| m.capture) => Err("Illegal move! A capture is compulsory".to_string()),
+            [] => Err("Illegal move!".to_string()),
+            _ => Err("Ambiguous move, give every square (e.g., 1x10x19)".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A position with the given pieces (by square number), `side` to move.
+    fn position(pieces: &[(usize, i8)], side: Color) -> Checkers {
+        let mut board = Checkers { squares: [0; 32], side_to_move: side, quiet_plies: 0 };
+        for &(sq, piece) in pieces {
+            board.squares[sq - 1] = piece;
+        }
+        board
+    }
+
+    fn texts(board: &Checkers) -> Vec<String> {
+        board.generate_legal_moves().iter().map(CheckersMove::to_string).collect()
+    }
+
+    #[test]
+    fn test_starting_moves() {
+        let board = Checkers::starting_position();
+        assert_eq!(texts(&board), ["9-13", "9-14", "10-14", "10-15", "11-15", "11-16", "12-16"]);
+        assert_eq!(board.material_balance(), 0);
+        assert_eq!(board.result(), GameResult::Ongoing);
+    }
+
+    #[test]
+    fn test_captures_are_compulsory_and_chain() {
+        // Black man on 1 can take 6 and then 15, landing on 19.
+        let board = position(&[(1, -1), (6, 1), (15, 1), (4, -1)], Color::Black);
+        assert_eq!(texts(&board), ["1x10x19"]);
+        assert!(board.parse_move("4-8").unwrap_err().contains("compulsory"));
+
+        let mut next = board.clone();
+        next.apply(board.parse_move("1x19").unwrap());
+        assert_eq!(next.squares.iter().filter(|&&p| p > 0).count(), 0);
+        assert_eq!(next.result(), GameResult::BlackWins);
+    }
+
+    #[test]
+    fn test_men_are_crowned_and_stop() {
+        // The white man jumping 6 lands on the back row and is crowned,
+        // so it cannot go on to take 7 as a king.
+        let mut board = position(&[(9, 1), (6, -1), (7, -1)], Color::White);
+        assert_eq!(texts(&board), ["9x2"]);
+        board.apply(board.parse_move("9x2").unwrap());
+        assert_eq!(board.squares[1], 2);
+        assert_eq!(board.side_to_move, Color::Black);
+        assert!(board.parse_move("33-1").is_err());
+    }
+
+    #[test]
+    fn test_display_numbers_empty_squares() {
+        let shown = Checkers::starting_position().to_string();
+        assert!(shown.contains("│ b │"));
+        assert!(shown.contains("13 │"));
+        assert!(shown.ends_with("Side to move: Black\n"));
+    }
+}
@@ -936,8 +936,11 @@ pub enum GameResult {
 impl Board {
     /// Check game result.
     pub fn game_result(&self) -> GameResult {
-        let legal_moves = self.generate_legal_moves();
-        
+        self.result_for(&self.generate_legal_moves())
+    }
+
+    /// Game result, given the legal moves in this position.
+    pub fn result_for(&self, legal_moves: &[Move]) -> GameResult {
         if legal_moves.is_empty() {
             if self.is_in_check() {
                 // Checkmate
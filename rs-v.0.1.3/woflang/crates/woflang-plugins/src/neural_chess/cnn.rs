@@ -14,6 +14,7 @@ use super::layers::{Dense, Layer};
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// 2D Convolution layer optimized for chess board analysis.
+#[derive(Clone)]
 pub struct ChessConv2D {
     /// Kernels: [num_filters, in_channels, kernel_h, kernel_w]
     pub kernels: Vec<Vec<Tensor>>,
@@ -103,10 +104,14 @@ impl ChessConv2D {
     /// Input: Vec<Tensor> where each tensor is [height, width]
     /// Output: Vec<Tensor> of [out_height, out_width]
     pub fn forward(&mut self, input: &[Tensor]) -> Vec<Tensor> {
-        assert_eq!(input.len(), self.in_channels, "Input channels mismatch");
-        
         // Cache input for backward
         self.input_cache = Some(input.to_vec());
+        self.infer(input)
+    }
+
+    /// Forward pass without caching for backward.
+    pub fn infer(&self, input: &[Tensor]) -> Vec<Tensor> {
+        assert_eq!(input.len(), self.in_channels, "Input channels mismatch");
         
         // Pad inputs
         let padded: Vec<Tensor> = input.iter().map(|t| self.pad_input(t)).collect();
@@ -162,6 +167,7 @@ impl ChessConv2D {
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// Batch normalization for convolutional layers.
+#[derive(Clone)]
 pub struct ConvBatchNorm {
     /// Scale parameter per channel
     pub gamma: Vec<f32>,
@@ -193,23 +199,35 @@ impl ConvBatchNorm {
     }
 
     pub fn forward(&mut self, inputs: &[Tensor], training: bool) -> Vec<Tensor> {
+        // Update running stats
+        if training {
+            for (c, input) in inputs.iter().enumerate() {
+                let (mean, var) = Self::stats(input);
+                self.running_mean[c] = (1.0 - self.momentum) * self.running_mean[c] + self.momentum * mean;
+                self.running_var[c] = (1.0 - self.momentum) * self.running_var[c] + self.momentum * var;
+            }
+        }
+        self.infer(inputs, training)
+    }
+
+    /// Mean and variance of one channel.
+    fn stats(input: &Tensor) -> (f32, f32) {
+        let mean = input.mean();
+        (mean, input.add_scalar(-mean).square().mean())
+    }
+
+    /// Normalize as [`forward`](Self::forward) does, without updating the
+    /// running statistics.
+    pub fn infer(&self, inputs: &[Tensor], training: bool) -> Vec<Tensor> {
         let mut outputs = Vec::with_capacity(inputs.len());
         
         for (c, input) in inputs.iter().enumerate() {
-            let mean = if training { input.mean() } else { self.running_mean[c] };
-            let var = if training {
-                let diff = input.add_scalar(-mean);
-                diff.square().mean()
+            let (mean, var) = if training {
+                Self::stats(input)
             } else {
-                self.running_var[c]
+                (self.running_mean[c], self.running_var[c])
             };
             
-            // Update running stats
-            if training {
-                self.running_mean[c] = (1.0 - self.momentum) * self.running_mean[c] + self.momentum * mean;
-                self.running_var[c] = (1.0 - self.momentum) * self.running_var[c] + self.momentum * var;
-            }
-            
             // Normalize
             let std = (var + self.epsilon).sqrt();
             let normalized = input.add_scalar(-mean).scale(1.0 / std);
@@ -253,6 +271,7 @@ pub fn global_avg_pool(inputs: &[Tensor]) -> Tensor {
 /// Dense: 256 → 512
 /// Dense: 512 → 256
 /// Output: 256 (feature vector for combining with RNN/LSTM)
+#[derive(Clone)]
 pub struct ChessCNN {
     /// Convolutional layers
     pub conv1: ChessConv2D,
@@ -324,6 +343,16 @@ impl ChessCNN {
         self.fc2.forward(&x)
     }
 
+    /// Forward pass without caching for backward or updating batch
+    /// statistics, so one network can serve several threads.
+    pub fn infer(&self, input: &[Tensor]) -> Tensor {
+        let x = self.bn1.infer(&self.conv1.infer(input), self.training);
+        let x = self.bn2.infer(&self.conv2.infer(&x), self.training);
+        let x = self.bn3.infer(&self.conv3.infer(&x), self.training);
+        let x = global_avg_pool(&x);
+        self.fc2.infer(&self.fc1.infer(&x))
+    }
+
     /// Update all weights.
     pub fn update(&mut self, learning_rate: f32) {
         self.conv1.update(learning_rate);
@@ -0,0 +1,282 @@
+//! Board Games - what chess, checkers and Go have in common.
+//!
+//! A [`GameState`] knows its rules: the legal moves, how a move changes
+//! the position, when the game is over and roughly who is ahead. That is
+//! enough for the alpha-beta search here, for [`GameSession`] to run a
+//! game against a [`GamePlayer`], and for the board to render through
+//! its `Display`.
+//!
+//! Implemented by [`Board`] (chess), [`Checkers`](super::checkers::Checkers)
+//! and [`GoBoard`](super::go::GoBoard). Two-player games only: the sides
+//! are White and Black, whichever moves first.
+//!
+//! [`GameSession`]: super::ai::GameSession
+
+use std::fmt;
+
+use super::ai::evaluate_position;
+use super::chess::{Board, Color, GameResult, Move};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// GAME STATE
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// The rules of a two-player board game, as seen from one position.
+pub trait GameState: Clone + fmt::Display + Send + Sync {
+    /// A move, shown in the game's usual notation.
+    type Move: Copy + PartialEq + fmt::Display + Send + Sync;
+
+    /// The starting position.
+    fn start() -> Self;
+
+    /// The side to move.
+    fn side_to_move(&self) -> Color;
+
+    /// Every legal move for the side to move.
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    /// Play a move from [`legal_moves`](Self::legal_moves).
+    fn apply(&mut self, m: Self::Move);
+
+    /// The result, given this position's legal moves (the search has
+    /// them at hand already).
+    fn outcome(&self, legal_moves: &[Self::Move]) -> GameResult;
+
+    /// The result, `Ongoing` while play goes on.
+    fn result(&self) -> GameResult {
+        self.outcome(&self.legal_moves())
+    }
+
+    /// Static score for White, in hundredths of the game's unit (a pawn,
+    /// a man, a point).
+    fn evaluate(&self) -> i32;
+
+    /// Sort key for searching moves; lower goes first, so likely good
+    /// moves get earlier cutoffs.
+    fn move_order(&self, _m: &Self::Move) -> i32 {
+        0
+    }
+
+    /// The legal move written as `text`, or why there is none.
+    fn parse_move(&self, text: &str) -> Result<Self::Move, String>;
+}
+
+impl GameState for Board {
+    type Move = Move;
+
+    fn start() -> Self {
+        Board::starting_position()
+    }
+
+    fn side_to_move(&self) -> Color {
+        self.side_to_move
+    }
+
+    fn legal_moves(&self) -> Vec<Move> {
+        self.generate_legal_moves()
+    }
+
+    fn apply(&mut self, m: Move) {
+        self.make_move_unchecked(m);
+    }
+
+    fn outcome(&self, legal_moves: &[Move]) -> GameResult {
+        self.result_for(legal_moves)
+    }
+
+    fn evaluate(&self) -> i32 {
+        evaluate_position(self)
+    }
+
+    /// Captures of the biggest pieces first.
+    fn move_order(&self, m: &Move) -> i32 {
+        -i32::from(self.squares[m.to.0 as usize].abs())
+    }
+
+    fn parse_move(&self, text: &str) -> Result<Move, String> {
+        let m = Move::from_uci(text)
+            .ok_or_else(|| "Invalid move format. Use UCI notation (e.g., e2e4)".to_string())?;
+        self.generate_legal_moves()
+            .into_iter()
+            .find(|lm| lm.from == m.from && lm.to == m.to)
+            .ok_or_else(|| "Illegal move!".to_string())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// PLAYERS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Something that picks moves in a game: the neural chess AI, or a plain
+/// search for any game.
+pub trait GamePlayer<G: GameState> {
+    /// A legal move for the side to move, or `None` if there is none.
+    fn select_move(&mut self, state: &G) -> Option<G::Move>;
+
+    /// How good the position is for White; positive is better.
+    fn evaluate(&mut self, state: &G) -> f32;
+
+    /// Forget anything kept from the previous game.
+    fn new_game(&mut self) {}
+}
+
+/// Plays any game by alpha-beta search on the game's own evaluation.
+#[derive(Clone, Copy, Debug)]
+pub struct SearchPlayer {
+    /// Plies searched per move (at least 1).
+    pub depth: u32,
+    /// Randomness among good moves; 0 always plays a best move.
+    pub temperature: f32,
+}
+
+impl SearchPlayer {
+    /// A player searching `depth` plies that always plays a best move.
+    pub fn new(depth: u32) -> Self {
+        SearchPlayer { depth: depth.max(1), temperature: 0.0 }
+    }
+}
+
+impl<G: GameState> GamePlayer<G> for SearchPlayer {
+    fn select_move(&mut self, state: &G) -> Option<G::Move> {
+        let scored = search_scores(state, &state.legal_moves(), self.depth);
+        pick_by_temperature(&scored, self.temperature)
+    }
+
+    fn evaluate(&mut self, state: &G) -> f32 {
+        state.evaluate() as f32 / 100.0
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// SEARCH
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Score of losing now; losses further away score less.
+pub const MATE: i32 = 100_000;
+
+/// Alpha-beta negamax score for the side to move, `ply` moves from the
+/// root.
+pub fn negamax<G: GameState>(state: &G, depth: u32, ply: i32, mut alpha: i32, beta: i32) -> i32 {
+    let mut moves = state.legal_moves();
+    match state.outcome(&moves) {
+        GameResult::Ongoing => {}
+        GameResult::Draw => return 0,
+        won => {
+            let mover_won = (won == GameResult::WhiteWins) == (state.side_to_move() == Color::White);
+            return if mover_won { MATE - ply } else { ply - MATE };
+        }
+    }
+    if depth == 0 {
+        return state.evaluate() * i32::from(state.side_to_move().sign());
+    }
+
+    moves.sort_by_key(|m| state.move_order(m));
+    for m in moves {
+        let mut next = state.clone();
+        next.apply(m);
+        let score = -negamax(&next, depth - 1, ply + 1, -beta, -alpha);
+        if score >= beta {
+            return beta;
+        }
+        alpha = alpha.max(score);
+    }
+    alpha
+}
+
+/// Each of `legal_moves` with its score after searching `depth` plies
+/// (at least 1) from `state`.
+pub fn search_scores<G: GameState>(state: &G, legal_moves: &[G::Move], depth: u32) -> Vec<(G::Move, i32)> {
+    legal_moves
+        .iter()
+        .map(|&m| {
+            let mut next = state.clone();
+            next.apply(m);
+            (m, -negamax(&next, depth.max(1) - 1, 1, -MATE, MATE))
+        })
+        .collect()
+}
+
+/// A pseudo-random number in [0, 1), seeded from the clock.
+pub(crate) fn random_unit() -> f32 {
+    let seed = (std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64)
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1);
+    (seed >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// Pick a scored move: the best at temperature 0, otherwise sampled with
+/// weight `exp(score / (100 × temperature))`, so a pawn's difference
+/// counts for less the hotter it is.
+pub fn pick_by_temperature<M: Copy>(scored: &[(M, i32)], temperature: f32) -> Option<M> {
+    let best = scored.iter().map(|&(_, score)| score).max()?;
+    if temperature <= 0.0 {
+        let top: Vec<M> = scored.iter().filter(|&&(_, score)| score == best).map(|&(m, _)| m).collect();
+        let idx = (random_unit() * top.len() as f32) as usize;
+        return top.get(idx.min(top.len() - 1)).copied();
+    }
+
+    let weights: Vec<f32> = scored
+        .iter()
+        .map(|&(_, score)| ((score - best) as f32 / (100.0 * temperature)).exp())
+        .collect();
+    let mut r = random_unit() * weights.iter().sum::<f32>();
+    for (&(m, _), &w) in scored.iter().zip(&weights) {
+        if r < w {
+            return Some(m);
+        }
+        r -= w;
+    }
+    scored.last().map(|&(m, _)| m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::checkers::Checkers;
+    use super::super::go::GoBoard;
+
+    /// Play `player` against itself for at most `plies` plies.
+    fn self_play<G: GameState>(player: &mut impl GamePlayer<G>, plies: usize) -> G {
+        let mut state = G::start();
+        for _ in 0..plies {
+            if state.result() != GameResult::Ongoing {
+                break;
+            }
+            let m = player.select_move(&state).unwrap();
+            assert!(state.legal_moves().contains(&m));
+            state.apply(m);
+        }
+        state
+    }
+
+    #[test]
+    fn test_search_plays_every_game() {
+        let mut player = SearchPlayer::new(2);
+        player.temperature = 0.5;
+        self_play::<Board>(&mut player, 6);
+        self_play::<Checkers>(&mut player, 20);
+        let mut player = SearchPlayer::new(1);
+        self_play::<GoBoard>(&mut player, 10);
+    }
+
+    #[test]
+    fn test_chess_moves_parse_against_the_position() {
+        let board = Board::starting_position();
+        assert_eq!(board.parse_move("g1f3").unwrap().to_uci(), "g1f3");
+        assert_eq!(board.parse_move("e2e5").unwrap_err(), "Illegal move!");
+        assert!(board.parse_move("nonsense").unwrap_err().starts_with("Invalid move format"));
+        assert_eq!(GameState::result(&board), GameResult::Ongoing);
+    }
+
+    #[test]
+    fn test_pick_by_temperature() {
+        let scored = [("a", 10), ("b", 500), ("c", 500)];
+        for _ in 0..10 {
+            assert_ne!(pick_by_temperature(&scored, 0.0), Some("a"));
+        }
+        assert_eq!(pick_by_temperature::<u8>(&[], 1.0), None);
+    }
+}
@@ -299,11 +299,19 @@ pub struct NeuralClockCoordinator {
     /// The central ganglion
     pub ganglion: Ganglion,
     /// Component timings (name -> avg latency in ms)
-    component_timings: std::collections::HashMap<String, Vec<f32>>,
+    component_timings: std::collections::BTreeMap<String, Vec<f32>>,
     /// Phase of operation
     pub phase: NeuralPhase,
 }
 
+/// A copy starts with a fresh, stopped clock: timings belong to the
+/// brain they were measured on.
+impl Clone for NeuralClockCoordinator {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
 /// Phases of neural network operation.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum NeuralPhase {
@@ -319,7 +327,7 @@ impl NeuralClockCoordinator {
     pub fn new() -> Self {
         NeuralClockCoordinator {
             ganglion: Ganglion::new(1000.0),
-            component_timings: std::collections::HashMap::new(),
+            component_timings: std::collections::BTreeMap::new(),
             phase: NeuralPhase::Idle,
         }
     }
@@ -429,6 +437,7 @@ impl Default for NeuralClockCoordinator {
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// Measures round-trip latency for AI responses.
+#[derive(Clone)]
 pub struct PingMeasurer {
     /// Ping times in microseconds
     ping_times: Vec<u64>,
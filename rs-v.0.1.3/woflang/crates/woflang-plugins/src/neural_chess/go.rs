@@ -0,0 +1,369 @@
+//! Go - the 9x9 board on the shared game layer.
+//!
+//! Implements:
+//! - Captures, with suicide not allowed
+//! - Simple ko: no move may bring back the position before the
+//!   opponent's last move
+//! - Area scoring (stones plus surrounded points) with 7.5 komi
+//! - The game ends after two passes in a row, or after 243 moves
+//!
+//! Black moves first. Points are written as on a real board, columns
+//! `A`-`J` without `I` and rows 1-9 from Black's side: `E5`, or `pass`.
+
+use std::fmt;
+
+use super::chess::{Color, GameResult};
+use super::game::GameState;
+
+/// Lines on each side of the board.
+pub const SIZE: usize = 9;
+
+/// Points on the board.
+const POINTS: usize = SIZE * SIZE;
+
+/// Moves after which the game is scored as it stands.
+const MAX_MOVES: u32 = 3 * POINTS as u32;
+
+/// White's compensation for moving second, in hundredths of a point.
+const KOMI: i32 = 750;
+
+/// Column letters, skipping `I` as Go boards do.
+const COLUMNS: &[u8; SIZE] = b"ABCDEFGHJ";
+
+/// Points orthogonally next to `p`.
+fn neighbours(p: usize) -> impl Iterator<Item = usize> {
+    let (row, col) = (p / SIZE, p % SIZE);
+    [
+        (row > 0).then(|| p - SIZE),
+        (row + 1 < SIZE).then(|| p + SIZE),
+        (col > 0).then(|| p - 1),
+        (col + 1 < SIZE).then(|| p + 1),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// The connected points holding the same as `p` (stones of one colour,
+/// or empty points), and what else they touch: a group of stones
+/// touching 0 has a liberty, an empty region touches the colours
+/// around it.
+fn region(points: &[i8; POINTS], p: usize) -> (Vec<usize>, Vec<i8>) {
+    let mut seen = [false; POINTS];
+    let mut members = vec![p];
+    let mut borders = Vec::new();
+    seen[p] = true;
+    let mut i = 0;
+    while i < members.len() {
+        for n in neighbours(members[i]) {
+            if points[n] == points[p] {
+                if !seen[n] {
+                    seen[n] = true;
+                    members.push(n);
+                }
+            } else if !borders.contains(&points[n]) {
+                borders.push(points[n]);
+            }
+        }
+        i += 1;
+    }
+    (members, borders)
+}
+
+/// Whether the group on `p` has a liberty.
+fn has_liberty(points: &[i8; POINTS], p: usize) -> bool {
+    region(points, p).1.contains(&0)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// MOVE
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A Go move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GoMove {
+    /// A stone on a point (0-80, row by row from A1).
+    Place(u8),
+    /// No stone this turn.
+    Pass,
+}
+
+impl fmt::Display for GoMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoMove::Place(p) => {
+                let (row, col) = (*p as usize / SIZE, *p as usize % SIZE);
+                write!(f, "{}{}", COLUMNS[col] as char, row + 1)
+            }
+            GoMove::Pass => write!(f, "pass"),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BOARD
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A 9x9 Go position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GoBoard {
+    /// Stones by point, row by row from A1: 1 = white, -1 = black, 0 = empty
+    pub points: [i8; POINTS],
+    /// Side to move
+    pub side_to_move: Color,
+    /// The stones before the last move, which may not come back (ko)
+    pub previous: [i8; POINTS],
+    /// Passes in a row
+    pub passes: u8,
+    /// Moves played, passes included
+    pub moves_played: u32,
+    /// Stones captured by Black and by White
+    pub captures: (u32, u32),
+}
+
+impl GoBoard {
+    /// An empty board, Black to move.
+    pub fn new() -> Self {
+        GoBoard {
+            points: [0; POINTS],
+            side_to_move: Color::Black,
+            previous: [0; POINTS],
+            passes: 0,
+            moves_played: 0,
+            captures: (0, 0),
+        }
+    }
+
+    /// The stones after the side to move plays on `p`, or `None` if the
+    /// point is taken, the stone would have no liberties, or it retakes
+    /// a ko.
+    fn play_stone(&self, p: usize) -> Option<[i8; POINTS]> {
+        if self.points[p] != 0 {
+            return None;
+        }
+        let stone = self.side_to_move.sign();
+        let mut next = self.points;
+        next[p] = stone;
+        for n in neighbours(p) {
+            if next[n] == -stone && !has_liberty(&next, n) {
+                for q in region(&next, n).0 {
+                    next[q] = 0;
+                }
+            }
+        }
+        (has_liberty(&next, p) && next != self.previous).then_some(next)
+    }
+
+    /// Area score: White's stones and surrounded points less Black's,
+    /// plus komi, in hundredths of a point.
+    pub fn score(&self) -> i32 {
+        let mut score = KOMI;
+        let mut counted = [false; POINTS];
+        for p in 0..POINTS {
+            if self.points[p] != 0 {
+                score += 100 * i32::from(self.points[p]);
+            } else if !counted[p] {
+                let (members, borders) = region(&self.points, p);
+                for &q in &members {
+                    counted[q] = true;
+                }
+                if let [owner] = borders.as_slice() {
+                    score += 100 * i32::from(*owner) * members.len() as i32;
+                }
+            }
+        }
+        score
+    }
+}
+
+impl Default for GoBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for GoBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letters: Vec<String> = COLUMNS.iter().map(|&c| (c as char).to_string()).collect();
+        let letters = letters.join(" ");
+        writeln!(f, "   {letters}")?;
+
+        for row in (0..SIZE).rev() {
+            write!(f, "{} ", row + 1)?;
+            for col in 0..SIZE {
+                let star = [2, 6].contains(&row) && [2, 6].contains(&col) || (row, col) == (4, 4);
+                let c = match self.points[row * SIZE + col] {
+                    1 => 'O',
+                    -1 => 'X',
+                    _ if star => '+',
+                    _ => '.',
+                };
+                write!(f, " {c}")?;
+            }
+            writeln!(f, "  {}", row + 1)?;
+        }
+
+        writeln!(f, "   {letters}")?;
+        writeln!(f)?;
+        writeln!(f, "Side to move: {:?}", self.side_to_move)?;
+        writeln!(f, "Captures: Black {}, White {}", self.captures.0, self.captures.1)?;
+
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// GAME STATE
+// ═══════════════════════════════════════════════════════════════════════════
+
+impl GameState for GoBoard {
+    type Move = GoMove;
+
+    fn start() -> Self {
+        GoBoard::new()
+    }
+
+    fn side_to_move(&self) -> Color {
+        self.side_to_move
+    }
+
+    fn legal_moves(&self) -> Vec<GoMove> {
+        let mut moves: Vec<GoMove> = (0..POINTS)
+            .filter(|&p| self.play_stone(p).is_some())
+            .map(|p| GoMove::Place(p as u8))
+            .collect();
+        moves.push(GoMove::Pass);
+        moves
+    }
+
+    fn apply(&mut self, m: GoMove) {
+        let before = self.points;
+        match m {
+            GoMove::Place(p) => {
+                let Some(next) = self.play_stone(p as usize) else { return };
+                let taken = before.iter().zip(&next).filter(|&(a, b)| *a != 0 && *b == 0).count() as u32;
+                match self.side_to_move {
+                    Color::Black => self.captures.0 += taken,
+                    Color::White => self.captures.1 += taken,
+                }
+                self.points = next;
+                self.passes = 0;
+            }
+            GoMove::Pass => self.passes += 1,
+        }
+        self.previous = before;
+        self.moves_played += 1;
+        self.side_to_move = self.side_to_move.opposite();
+    }
+
+    /// Scored once both sides pass or the move limit is reached.
+    fn outcome(&self, _legal_moves: &[GoMove]) -> GameResult {
+        if self.passes < 2 && self.moves_played < MAX_MOVES {
+            GameResult::Ongoing
+        } else if self.score() > 0 {
+            GameResult::WhiteWins
+        } else {
+            GameResult::BlackWins
+        }
+    }
+
+    fn evaluate(&self) -> i32 {
+        self.score()
+    }
+
+    fn parse_move(&self, text: &str) -> Result<GoMove, String> {
+        let text = text.trim().to_ascii_uppercase();
+        if text == "PASS" {
+            return Ok(GoMove::Pass);
+        }
+        let mut chars = text.chars();
+        let col = chars.next().and_then(|c| COLUMNS.iter().position(|&l| l as char == c));
+        let row = chars.as_str().parse::<usize>().ok().filter(|r| (1..=SIZE).contains(r));
+        let (Some(col), Some(row)) = (col, row) else {
+            return Err("Invalid move format. Use a point (e.g., E5) or pass".to_string());
+        };
+        let p = (row - 1) * SIZE + col;
+        if self.play_stone(p).is_some() {
+            Ok(GoMove::Place(p as u8))
+        } else {
+            Err("Illegal move!".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A board with the given stones, `side` to move.
+    fn position(black: &[&str], white: &[&str], side: Color) -> GoBoard {
+        let mut board = GoBoard::new();
+        for (stones, stone) in [(black, -1), (white, 1)] {
+            for text in stones {
+                let Ok(GoMove::Place(p)) = board.parse_move(text) else { panic!("{text}") };
+                board.points[p as usize] = stone;
+            }
+        }
+        board.side_to_move = side;
+        board.previous = board.points;
+        board
+    }
+
+    fn play(board: &mut GoBoard, text: &str) {
+        let m = board.parse_move(text).unwrap();
+        board.apply(m);
+    }
+
+    #[test]
+    fn test_captures_and_suicide() {
+        let mut board = position(&["D5", "C4", "D3", "A2", "B1"], &["D4"], Color::Black);
+        play(&mut board, "E4");
+        assert_eq!(board.points[3 * SIZE + 3], 0);
+        assert_eq!(board.captures, (1, 0));
+
+        // A1 has no liberties and takes nothing.
+        assert_eq!(board.parse_move("a1").unwrap_err(), "Illegal move!");
+        assert!(board.parse_move("I5").is_err());
+        assert!(board.parse_move("E10").is_err());
+    }
+
+    #[test]
+    fn test_ko_cannot_be_retaken_at_once() {
+        let mut board = position(&["D5", "C4", "D3"], &["D4", "E5", "E3", "F4"], Color::Black);
+        play(&mut board, "E4");
+        assert!(!board.legal_moves().contains(&GoMove::Place(3 * SIZE as u8 + 3)));
+
+        play(&mut board, "A1");
+        play(&mut board, "J9");
+        play(&mut board, "D4");
+        assert_eq!(board.captures, (1, 1));
+    }
+
+    #[test]
+    fn test_two_passes_end_and_score() {
+        let mut board = GoBoard::new();
+        assert_eq!(board.legal_moves().len(), POINTS + 1);
+        play(&mut board, "E5");
+        assert_eq!(board.result(), GameResult::Ongoing);
+        play(&mut board, "pass");
+        play(&mut board, "pass");
+        // Black's one stone claims the whole board.
+        assert_eq!(board.score(), 750 - 8100);
+        assert_eq!(board.result(), GameResult::BlackWins);
+
+        // Komi decides an empty board.
+        let mut board = GoBoard::new();
+        play(&mut board, "pass");
+        play(&mut board, "pass");
+        assert_eq!(board.result(), GameResult::WhiteWins);
+    }
+
+    #[test]
+    fn test_display() {
+        let mut board = GoBoard::new();
+        play(&mut board, "C7");
+        let shown = board.to_string();
+        assert!(shown.starts_with("   A B C D E F G H J\n9  . . . . . . . . .  9\n"));
+        assert!(shown.contains("7  . . X . . . + . .  7"));
+        assert!(shown.contains("Side to move: White"));
+    }
+}
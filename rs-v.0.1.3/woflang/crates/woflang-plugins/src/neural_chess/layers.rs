@@ -37,6 +37,7 @@ pub trait Layer {
 
 /// Dense (fully connected) layer.
 /// y = activation(x @ W + b)
+#[derive(Clone)]
 pub struct Dense {
     /// Weight matrix [input_size, output_size]
     pub weights: Tensor,
@@ -85,11 +86,9 @@ impl Dense {
     }
 }
 
-impl Layer for Dense {
-    fn forward(&mut self, input: &Tensor) -> Tensor {
-        // Store input for backward pass
-        self.input_cache = Some(input.clone());
-        
+impl Dense {
+    /// Compute `input @ W + b`, before activation.
+    fn linear(&self, input: &Tensor) -> Tensor {
         // Compute linear transformation
         let linear = if input.ndim() == 1 {
             // Single sample: reshape to row vector, matmul, extract
@@ -102,12 +101,26 @@ impl Layer for Dense {
         };
         
         // Add bias (broadcasting)
-        let pre_act = if linear.ndim() == 1 {
+        if linear.ndim() == 1 {
             linear.add(&self.bias)
         } else {
             linear.add_broadcast_row(&self.bias)
-        };
+        }
+    }
+
+    /// Forward pass without caching for backward, so a shared layer can
+    /// serve several threads.
+    pub fn infer(&self, input: &Tensor) -> Tensor {
+        self.activation.apply(&self.linear(input))
+    }
+}
+
+impl Layer for Dense {
+    fn forward(&mut self, input: &Tensor) -> Tensor {
+        // Store input for backward pass
+        self.input_cache = Some(input.clone());
         
+        let pre_act = self.linear(input);
         self.pre_activation = Some(pre_act.clone());
         
         // Apply activation
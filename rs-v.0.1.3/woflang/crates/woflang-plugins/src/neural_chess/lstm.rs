@@ -20,6 +20,7 @@ use super::activation::{sigmoid, tanh};
 /// c̃_t = tanh(W_xc @ x_t + W_hc @ h_{t-1} + b_c)  # candidate cell
 /// c_t = f_t * c_{t-1} + i_t * c̃_t  # cell state
 /// h_t = o_t * tanh(c_t)  # hidden state
+#[derive(Clone)]
 pub struct LSTMCell {
     // Forget gate
     pub w_xf: Tensor,
@@ -123,6 +124,17 @@ impl LSTMCell {
 
     /// Forward pass for single timestep.
     pub fn forward_step(&mut self, x: &Tensor, h_prev: &Tensor, c_prev: &Tensor) -> (Tensor, Tensor) {
+        let step = self.step(x, h_prev, c_prev);
+        let state = (step.h.clone(), step.c.clone());
+        
+        // Cache for backward pass
+        self.cache.push(step);
+        
+        state
+    }
+
+    /// Compute one timestep, returning every intermediate value.
+    fn step(&self, x: &Tensor, h_prev: &Tensor, c_prev: &Tensor) -> LSTMStepCache {
         let x_row = x.reshape(&[1, self.input_size]);
         let h_row = h_prev.reshape(&[1, self.hidden_size]);
         
@@ -159,8 +171,7 @@ impl LSTMCell {
         let c_tanh = tanh(&c);
         let h = o.mul(&c_tanh);
         
-        // Cache for backward pass
-        self.cache.push(LSTMStepCache {
+        LSTMStepCache {
             x: x.clone(),
             h_prev: h_prev.clone(),
             c_prev: c_prev.clone(),
@@ -168,11 +179,24 @@ impl LSTMCell {
             i,
             o,
             c_candidate,
-            c: c.clone(),
-            h: h.clone(),
-        });
+            c,
+            h,
+        }
+    }
+
+    /// Forward pass over a sequence without caching for backward.
+    pub fn infer(&self, sequence: &[Tensor]) -> Vec<Tensor> {
+        let (mut h, mut c) = self.init_state();
+        let mut outputs = Vec::with_capacity(sequence.len());
         
-        (h, c)
+        for x in sequence {
+            let step = self.step(x, &h, &c);
+            outputs.push(step.h.clone());
+            h = step.h;
+            c = step.c;
+        }
+        
+        outputs
     }
 
     /// Forward pass over entire sequence.
@@ -349,6 +373,7 @@ impl LSTMCell {
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// Multi-layer LSTM network.
+#[derive(Clone)]
 pub struct LSTM {
     /// LSTM cells for each layer
     pub cells: Vec<LSTMCell>,
@@ -395,6 +420,15 @@ impl LSTM {
         outputs.last().cloned().unwrap_or_else(|| Tensor::zeros(&[self.hidden_size]))
     }
 
+    /// Final hidden state, without caching for backward.
+    pub fn infer_final_hidden(&self, sequence: &[Tensor]) -> Tensor {
+        let mut current_sequence: Vec<Tensor> = sequence.to_vec();
+        for cell in &self.cells {
+            current_sequence = cell.infer(&current_sequence);
+        }
+        current_sequence.last().cloned().unwrap_or_else(|| Tensor::zeros(&[self.hidden_size]))
+    }
+
     /// Get all layer states (for more complex architectures).
     pub fn get_all_states(&mut self, sequence: &[Tensor]) -> Vec<Vec<Tensor>> {
         let mut all_outputs = Vec::with_capacity(self.num_layers);
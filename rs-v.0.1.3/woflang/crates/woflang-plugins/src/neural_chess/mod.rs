@@ -4,8 +4,12 @@
 //! Features:
 //! - 3-Way GAN Brain Core (CNN + RNN + LSTM)
 //! - Universal Ganglion Clock for synchronization
-//! - Self-play training capabilities
+//! - Self-play training capabilities, serial or across threads
 //! - Interactive game sessions
+//! - An opening book, endgame heuristics and adjustable playing strength
+//! - Checkers and 9x9 Go on the same game trait, session and search
+//! - Round-robin tournaments between saved AI checkpoints, rated on an
+//!   Elo ladder
 //!
 //! # Architecture
 //!
@@ -45,7 +49,12 @@ pub mod cnn;
 pub mod ganglion;
 pub mod brain;
 pub mod chess;
+pub mod game;
+pub mod checkers;
+pub mod go;
+pub mod book;
 pub mod ai;
+pub mod tournament;
 
 // Re-exports for convenience
 pub use tensor::Tensor;
@@ -53,13 +62,18 @@ pub use activation::Activation;
 pub use brain::BrainCore;
 pub use chess::{Board, Move, Square, Color, GameResult, PieceType};
 pub use ai::{NeuralChessAI, GameSession, TrainingConfig};
+pub use game::{GameState, GamePlayer, SearchPlayer};
+pub use checkers::{Checkers, CheckersMove};
+pub use go::{GoBoard, GoMove};
+pub use tournament::{Crosstable, Ladder, Rating, round_robin};
+pub use book::book_moves;
 pub use ganglion::{Ganglion, NeuralClockCoordinator};
 
 use std::error::Error;
 use std::sync::Mutex;
 use std::sync::OnceLock;
 use woflang_runtime::Interpreter;
-use woflang_core::{WofValue, InterpreterContext, WofError};
+use woflang_core::{WofValue, InterpreterContext, Out, WofError};
 
 // ═══════════════════════════════════════════════════════════════════════════
 // GLOBAL AI INSTANCE
@@ -79,6 +93,11 @@ fn get_session() -> &'static Mutex<Option<GameSession>> {
     GLOBAL_SESSION.get_or_init(|| Mutex::new(None))
 }
 
+/// Copies of the AI saved by `chess_ai_checkpoint`, in saving order,
+/// kept per interpreter for `chess_tournament`.
+#[derive(Default)]
+struct Checkpoints(Vec<(String, NeuralChessAI)>);
+
 // ═══════════════════════════════════════════════════════════════════════════
 // WOFLANG INTEGRATION
 // ═══════════════════════════════════════════════════════════════════════════
@@ -92,14 +111,14 @@ pub fn register(interp: &mut Interpreter) {
     interp.register("chess_ai_new", |interp| {
         let mut ai_lock = get_ai().lock().unwrap();
         *ai_lock = NeuralChessAI::new();
-        println!("♟️  Neural Chess AI initialized!");
-        println!("{}", ai_lock.status_report());
+        writeln!(interp.out(), "♟️  Neural Chess AI initialized!");
+        writeln!(interp.out(), "{}", ai_lock.status_report());
         Ok(())
     });
 
     interp.register("chess_ai_status", |interp| {
         let ai = get_ai().lock().unwrap();
-        println!("{}", ai.status_report());
+        writeln!(interp.out(), "{}", ai.status_report());
         Ok(())
     });
 
@@ -109,7 +128,33 @@ pub fn register(interp: &mut Interpreter) {
             .unwrap_or(10) as usize;
         
         let mut ai = get_ai().lock().unwrap();
-        ai.self_play_train(games);
+        let played = ai.self_play_train_until(games, &interp.out(), &mut |done, total| {
+            interp.progress(done, total, "self-play games");
+            !interp.is_cancelled()
+        });
+        drop(ai);
+        if played < games {
+            return Err(interp.interrupt(format!("after {played} of {games} games (training kept)")));
+        }
+        Ok(())
+    });
+
+    interp.register("chess_ai_train_par", |interp| {
+        // Stack: games threads →   (0 threads = one per core)
+        let threads = interp.pop()?.as_integer()?.max(0) as usize;
+        let games = interp.pop()?.as_integer()?.max(0) as usize;
+
+        let mut ai = get_ai().lock().unwrap();
+        let played = ai
+            .self_play_train_parallel_until(games, threads, &interp.out(), &mut |done, total| {
+                interp.progress(done, total, "self-play games");
+                !interp.is_cancelled()
+            })
+            .map_err(|e| WofError::runtime(format!("chess_ai_train_par: {e}")))?;
+        drop(ai);
+        if played < games {
+            return Err(interp.interrupt(format!("after {played} of {games} games (training kept)")));
+        }
         Ok(())
     });
 
@@ -127,8 +172,37 @@ pub fn register(interp: &mut Interpreter) {
             ..TrainingConfig::default()
         };
         
+        let total = iterations * games;
         let mut ai = get_ai().lock().unwrap();
-        ai.train_with_config(&config);
+        let played = ai.train_with_config_until(&config, &interp.out(), &mut |done, total| {
+            interp.progress(done, total, "self-play games");
+            !interp.is_cancelled()
+        });
+        drop(ai);
+        if played < total {
+            return Err(interp.interrupt(format!("after {played} of {total} games (training kept)")));
+        }
+        Ok(())
+    });
+
+    interp.register("chess_ai_strength", |interp| {
+        // Stack: level(0-10) →
+        let level = interp.pop()?.as_integer()?.clamp(0, 10) as u8;
+
+        let mut ai = get_ai().lock().unwrap();
+        ai.set_strength(level);
+        let mut session_lock = get_session().lock().unwrap();
+        if let Some(ref mut session) = *session_lock {
+            session.ai.set_strength(level);
+        }
+        writeln!(
+            interp.out(),
+            "♟️  Strength {}: book {}, search depth {}, temperature {:.1}",
+            level,
+            if ai.use_book { "on" } else { "off" },
+            ai.search_depth,
+            ai.temperature,
+        );
         Ok(())
     });
 
@@ -149,14 +223,16 @@ pub fn register(interp: &mut Interpreter) {
         let mut new_ai = NeuralChessAI::new();
         new_ai.temperature = ai.temperature;
         new_ai.epsilon = ai.epsilon;
+        new_ai.use_book = ai.use_book;
+        new_ai.search_depth = ai.search_depth;
         
         *session_lock = Some(GameSession::with_ai(new_ai, human_white));
         
         let session = session_lock.as_ref().unwrap();
-        println!("♟️  New game started!");
-        println!("   You play as: {}", if human_white { "White" } else { "Black" });
-        println!("\n{}", session.display());
-        println!("{}", session.status());
+        writeln!(interp.out(), "♟️  New game started!");
+        writeln!(interp.out(), "   You play as: {}", if human_white { "White" } else { "Black" });
+        writeln!(interp.out(), "\n{}", session.display());
+        writeln!(interp.out(), "{}", session.status());
         
         Ok(())
     });
@@ -164,19 +240,19 @@ pub fn register(interp: &mut Interpreter) {
     interp.register("chess_show", |interp| {
         let session_lock = get_session().lock().unwrap();
         if let Some(ref session) = *session_lock {
-            println!("{}", session.display());
-            println!("{}", session.status());
+            writeln!(interp.out(), "{}", session.display());
+            writeln!(interp.out(), "{}", session.status());
             
             if !session.is_game_over() && session.is_human_turn() {
                 let moves = session.legal_moves();
                 if moves.len() <= 20 {
-                    println!("Legal moves: {}", moves.join(", "));
+                    writeln!(interp.out(), "Legal moves: {}", moves.join(", "));
                 } else {
-                    println!("Legal moves: {} moves available", moves.len());
+                    writeln!(interp.out(), "Legal moves: {} moves available", moves.len());
                 }
             }
         } else {
-            println!("No game in progress. Use 'chess_new_game' to start.");
+            writeln!(interp.out(), "No game in progress. Use 'chess_new_game' to start.");
         }
         Ok(())
     });
@@ -190,22 +266,22 @@ pub fn register(interp: &mut Interpreter) {
         if let Some(ref mut session) = *session_lock {
             match session.human_move(&move_str) {
                 Ok(()) => {
-                    println!("Your move: {}", move_str);
+                    writeln!(interp.out(), "Your move: {}", move_str);
                     
                     // If game not over, let AI respond
                     if !session.is_game_over() && !session.is_human_turn() {
                         if let Some(ai_move) = session.ai_move() {
-                            println!("AI plays: {}", ai_move.to_uci());
+                            writeln!(interp.out(), "AI plays: {}", ai_move.to_uci());
                         }
                     }
                     
-                    println!("\n{}", session.display());
-                    println!("{}", session.status());
+                    writeln!(interp.out(), "\n{}", session.display());
+                    writeln!(interp.out(), "{}", session.status());
                 },
-                Err(e) => println!("❌ {}", e),
+                Err(e) => writeln!(interp.out(), "❌ {}", e),
             }
         } else {
-            println!("No game in progress. Use 'chess_new_game' to start.");
+            writeln!(interp.out(), "No game in progress. Use 'chess_new_game' to start.");
         }
         Ok(())
     });
@@ -214,18 +290,18 @@ pub fn register(interp: &mut Interpreter) {
         let mut session_lock = get_session().lock().unwrap();
         if let Some(ref mut session) = *session_lock {
             if session.is_game_over() {
-                println!("Game is already over!");
+                writeln!(interp.out(), "Game is already over!");
             } else if !session.is_human_turn() {
                 if let Some(ai_move) = session.ai_move() {
-                    println!("AI plays: {}", ai_move.to_uci());
-                    println!("\n{}", session.display());
-                    println!("{}", session.status());
+                    writeln!(interp.out(), "AI plays: {}", ai_move.to_uci());
+                    writeln!(interp.out(), "\n{}", session.display());
+                    writeln!(interp.out(), "{}", session.status());
                 }
             } else {
-                println!("It's your turn!");
+                writeln!(interp.out(), "It's your turn!");
             }
         } else {
-            println!("No game in progress.");
+            writeln!(interp.out(), "No game in progress.");
         }
         Ok(())
     });
@@ -240,10 +316,10 @@ pub fn register(interp: &mut Interpreter) {
                 if eval > 0.0 { "AI's favor" } else { "your favor" }
             };
             
-            println!("Position evaluation: {:.3} (in {})", eval, perspective);
+            writeln!(interp.out(), "Position evaluation: {:.3} (in {})", eval, perspective);
             interp.push(WofValue::double(eval as f64));
         } else {
-            println!("No game in progress.");
+            writeln!(interp.out(), "No game in progress.");
         }
         Ok(())
     });
@@ -252,27 +328,46 @@ pub fn register(interp: &mut Interpreter) {
         let session_lock = get_session().lock().unwrap();
         if let Some(ref session) = *session_lock {
             let moves = session.legal_moves();
-            println!("Legal moves ({}):", moves.len());
+            writeln!(interp.out(), "Legal moves ({}):", moves.len());
             for (i, m) in moves.iter().enumerate() {
-                print!("{}", m);
+                write!(interp.out(), "{}", m);
                 if i < moves.len() - 1 {
-                    print!(", ");
+                    write!(interp.out(), ", ");
                 }
                 if (i + 1) % 10 == 0 {
-                    println!();
+                    writeln!(interp.out());
                 }
             }
-            println!();
+            writeln!(interp.out());
             
             interp.push(WofValue::integer(moves.len() as i64));
         } else {
-            println!("No game in progress.");
+            writeln!(interp.out(), "No game in progress.");
+        }
+        Ok(())
+    });
+
+    interp.register("chess_book_move", |interp| {
+        // Stack: → move | nil
+        // A book move for the current game, or the starting position
+        // when no game is in progress.
+        let session_lock = get_session().lock().unwrap();
+        let board = session_lock.as_ref().map_or_else(Board::starting_position, |s| s.board.clone());
+        drop(session_lock);
+
+        let moves = book_moves(&board);
+        let pick = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.subsec_nanos() as usize);
+        match moves.get(pick % moves.len().max(1)) {
+            Some(m) => interp.push(WofValue::string(m.to_uci())),
+            None => interp.push(WofValue::nil()),
         }
         Ok(())
     });
 
     interp.register("chess_undo", |interp| {
-        println!("⚠️  Undo not yet implemented (would require game state history)");
+        writeln!(interp.out(), "⚠️  Undo not yet implemented (would require game state history)");
         Ok(())
     });
 
@@ -282,7 +377,7 @@ pub fn register(interp: &mut Interpreter) {
 
     interp.register("chess_board_new", |interp| {
         let board = Board::starting_position();
-        println!("{}", board);
+        writeln!(interp.out(), "{}", board);
         Ok(())
     });
 
@@ -315,8 +410,8 @@ pub fn register(interp: &mut Interpreter) {
         let nodes = perft(&board, depth);
         let elapsed = start.elapsed();
         
-        println!("Perft({}): {} nodes in {:?}", depth, nodes, elapsed);
-        println!("Speed: {:.0} nps", nodes as f64 / elapsed.as_secs_f64());
+        writeln!(interp.out(), "Perft({}): {} nodes in {:?}", depth, nodes, elapsed);
+        writeln!(interp.out(), "Speed: {:.0} nps", nodes as f64 / elapsed.as_secs_f64());
         
         interp.push(WofValue::integer(nodes as i64));
         Ok(())
@@ -328,7 +423,7 @@ pub fn register(interp: &mut Interpreter) {
 
     interp.register("chess_brain_info", |interp| {
         let ai = get_ai().lock().unwrap();
-        println!("{}", ai.brain.diagnostics());
+        writeln!(interp.out(), "{}", ai.brain.diagnostics());
         Ok(())
     });
 
@@ -337,43 +432,140 @@ pub fn register(interp: &mut Interpreter) {
         let avg_ping = ai.ping.average_ping_ms();
         let jitter = ai.ping.jitter() / 1000.0;
         
-        println!("🏓 Neural Response Times:");
-        println!("   Average: {:.2}ms", avg_ping);
-        println!("   Jitter:  {:.2}ms", jitter);
+        writeln!(interp.out(), "🏓 Neural Response Times:");
+        writeln!(interp.out(), "   Average: {:.2}ms", avg_ping);
+        writeln!(interp.out(), "   Jitter:  {:.2}ms", jitter);
         
         interp.push(WofValue::double(avg_ping as f64));
         Ok(())
     });
 
+    // ─────────────────────────────────────────────────────────────────────
+    // CHECKPOINTS AND TOURNAMENTS
+    // ─────────────────────────────────────────────────────────────────────
+
+    interp.register("chess_ai_checkpoint", |interp| {
+        // Stack: name →   (a copy of the AI as it is now)
+        let name = interp.pop()?.as_string()?;
+        let ai = get_ai().lock().unwrap().clone();
+        let games = ai.games_played;
+        let checkpoints = &mut interp.state_mut::<Checkpoints>().0;
+        match checkpoints.iter_mut().find(|(n, _)| *n == name) {
+            Some(slot) => slot.1 = ai,
+            None => checkpoints.push((name.clone(), ai)),
+        }
+        writeln!(interp.out(), "♟️  Checkpoint '{}' saved after {} training games", name, games);
+        Ok(())
+    });
+
+    interp.register("chess_tournament", |interp| {
+        // Stack: n →   (n games per pair of checkpoints)
+        let games = interp.pop()?.as_integer()?.max(0) as usize;
+        let mut players = std::mem::take(&mut interp.state_mut::<Checkpoints>().0);
+        if players.len() < 2 {
+            interp.state_mut::<Checkpoints>().0 = players;
+            return Err(WofError::runtime(
+                "chess_tournament: needs two checkpoints (save them with chess_ai_checkpoint)",
+            ));
+        }
+
+        let total = players.len() * (players.len() - 1) / 2 * games;
+        let mut ladder = std::mem::take(interp.state_mut::<Ladder>());
+        let table = round_robin::<Board, _>(&mut players, games, ai::MAX_GAME_PLIES, &mut ladder, &mut |done, total| {
+            interp.progress(done, total, "tournament games");
+            !interp.is_cancelled()
+        });
+        *interp.state_mut::<Checkpoints>() = Checkpoints(players);
+        *interp.state_mut::<Ladder>() = ladder;
+
+        writeln!(interp.out(), "🏆 Crosstable ({} games):", table.games_played());
+        write!(interp.out(), "{}", table);
+        writeln!(interp.out(), "\n📈 Ladder:");
+        write!(interp.out(), "{}", interp.state_mut::<Ladder>());
+
+        let played = table.games_played() as usize;
+        if played < total {
+            return Err(interp.interrupt(format!("after {played} of {total} games (ratings kept)")));
+        }
+        Ok(())
+    });
+
+    interp.register("chess_ladder", |interp| {
+        write!(interp.out(), "{}", interp.state_mut::<Ladder>());
+        Ok(())
+    });
+
+    interp.register("chess_ladder_save", |interp| {
+        // Stack: file →
+        let path = interp.pop()?.as_string()?;
+        let resolved = interp.sandbox().check_write(&path)?;
+        let json = interp.state_mut::<Ladder>().to_json();
+        std::fs::write(resolved, json)
+            .map_err(|e| WofError::runtime(format!("chess_ladder_save: {path}: {e}")))
+    });
+
+    interp.register("chess_ladder_load", |interp| {
+        // Stack: file →   (replaces the ladder)
+        let path = interp.pop()?.as_string()?;
+        let resolved = interp.sandbox().check_read(&path)?;
+        let ladder = std::fs::read_to_string(resolved)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Ladder::from_json(&text))
+            .map_err(|e| WofError::runtime(format!("chess_ladder_load: {path}: {e}")))?;
+        *interp.state_mut::<Ladder>() = ladder;
+        Ok(())
+    });
+
+    // ─────────────────────────────────────────────────────────────────────
+    // OTHER BOARD GAMES
+    // ─────────────────────────────────────────────────────────────────────
+
+    register_board_game::<Checkers>(interp, "checkers", "Checkers", 4);
+    register_board_game::<GoBoard>(interp, "go", "9x9 Go", 1);
+
     // ─────────────────────────────────────────────────────────────────────
     // HELP
     // ─────────────────────────────────────────────────────────────────────
 
     interp.register("chess_help", |interp| {
-        println!("╔═══════════════════════════════════════════════════════════════╗");
-        println!("║              NEURAL CHESS - WOFLANG OPERATIONS                ║");
-        println!("╠═══════════════════════════════════════════════════════════════╣");
-        println!("║ AI Management:                                                ║");
-        println!("║   chess_ai_new         - Initialize new AI                    ║");
-        println!("║   chess_ai_status      - Show AI statistics                   ║");
-        println!("║   n chess_ai_train     - Train AI with n self-play games      ║");
-        println!("║   g i chess_ai_train_full - Full training (g games, i iter)   ║");
-        println!("║                                                               ║");
-        println!("║ Game Session:                                                 ║");
-        println!("║   1 chess_new_game     - Start new game (1=white, 0=black)    ║");
-        println!("║   chess_show           - Display current board                ║");
-        println!("║   \"e2e4\" chess_move    - Make a move (UCI notation)          ║");
-        println!("║   chess_ai_play        - Force AI to play                     ║");
-        println!("║   chess_eval           - Get position evaluation              ║");
-        println!("║   chess_legal_moves    - List all legal moves                 ║");
-        println!("║                                                               ║");
-        println!("║ Utilities:                                                    ║");
-        println!("║   chess_board_new      - Display starting position            ║");
-        println!("║   n chess_perft        - Performance test (depth n)           ║");
-        println!("║   chess_brain_info     - Neural network diagnostics           ║");
-        println!("║   chess_ping           - Response time statistics             ║");
-        println!("║   chess_help           - This help message                    ║");
-        println!("╚═══════════════════════════════════════════════════════════════╝");
+        writeln!(interp.out(), "╔═══════════════════════════════════════════════════════════════╗");
+        writeln!(interp.out(), "║              NEURAL CHESS - WOFLANG OPERATIONS                ║");
+        writeln!(interp.out(), "╠═══════════════════════════════════════════════════════════════╣");
+        writeln!(interp.out(), "║ AI Management:                                                ║");
+        writeln!(interp.out(), "║   chess_ai_new         - Initialize new AI                    ║");
+        writeln!(interp.out(), "║   chess_ai_status      - Show AI statistics                   ║");
+        writeln!(interp.out(), "║   n chess_ai_train     - Train AI with n self-play games      ║");
+        writeln!(interp.out(), "║   n t chess_ai_train_par - Train n games on t threads         ║");
+        writeln!(interp.out(), "║   g i chess_ai_train_full - Full training (g games, i iter)   ║");
+        writeln!(interp.out(), "║   n chess_ai_strength  - Playing strength (0 weak - 10 strong)║");
+        writeln!(interp.out(), "║                                                               ║");
+        writeln!(interp.out(), "║ Game Session:                                                 ║");
+        writeln!(interp.out(), "║   1 chess_new_game     - Start new game (1=white, 0=black)    ║");
+        writeln!(interp.out(), "║   chess_show           - Display current board                ║");
+        writeln!(interp.out(), "║   \"e2e4\" chess_move    - Make a move (UCI notation)          ║");
+        writeln!(interp.out(), "║   chess_ai_play        - Force AI to play                     ║");
+        writeln!(interp.out(), "║   chess_eval           - Get position evaluation              ║");
+        writeln!(interp.out(), "║   chess_legal_moves    - List all legal moves                 ║");
+        writeln!(interp.out(), "║   chess_book_move      - Push a book move (nil out of book)   ║");
+        writeln!(interp.out(), "║                                                               ║");
+        writeln!(interp.out(), "║ Utilities:                                                    ║");
+        writeln!(interp.out(), "║   chess_board_new      - Display starting position            ║");
+        writeln!(interp.out(), "║   n chess_perft        - Performance test (depth n)           ║");
+        writeln!(interp.out(), "║   chess_brain_info     - Neural network diagnostics           ║");
+        writeln!(interp.out(), "║   chess_ping           - Response time statistics             ║");
+        writeln!(interp.out(), "║   chess_help           - This help message                    ║");
+        writeln!(interp.out(), "║                                                               ║");
+        writeln!(interp.out(), "║ Tournaments:                                                  ║");
+        writeln!(interp.out(), "║   \"gen1\" chess_ai_checkpoint - Save a copy of the AI          ║");
+        writeln!(interp.out(), "║   n chess_tournament   - n games per pair of checkpoints      ║");
+        writeln!(interp.out(), "║   chess_ladder         - Show Elo ratings                     ║");
+        writeln!(interp.out(), "║   \"f\" chess_ladder_save / chess_ladder_load - Ladder file     ║");
+        writeln!(interp.out(), "║                                                               ║");
+        writeln!(interp.out(), "║ Checkers and 9x9 Go (prefix checkers_ or go_):                ║");
+        writeln!(interp.out(), "║   1 go_new_game        - Start new game (1=move first, 0=not) ║");
+        writeln!(interp.out(), "║   go_show, go_ai_play, go_eval, go_legal_moves - As chess_    ║");
+        writeln!(interp.out(), "║   \"E5\" go_move         - Make a move (\"11-15\" in checkers)    ║");
+        writeln!(interp.out(), "╚═══════════════════════════════════════════════════════════════╝");
         Ok(())
     });
 
@@ -382,7 +574,11 @@ pub fn register(interp: &mut Interpreter) {
     // ─────────────────────────────────────────────────────────────────────
 
     interp.register("♟", |interp| {
-        println!("{}", get_ai().lock().unwrap().status_report());
+        let report = get_ai()
+            .lock()
+            .map_err(|_| WofError::runtime("♟: the chess AI is unavailable after a failed op"))?
+            .status_report();
+        writeln!(interp.out(), "{report}");
         Ok(())
     });
 
@@ -390,9 +586,9 @@ pub fn register(interp: &mut Interpreter) {
         // Show board
         let session = get_session().lock().unwrap();
         if let Some(ref s) = *session {
-            println!("{}", s.display());
+            writeln!(interp.out(), "{}", s.display());
         } else {
-            println!("{}", Board::starting_position());
+            writeln!(interp.out(), "{}", Board::starting_position());
         }
         Ok(())
     });
@@ -401,12 +597,132 @@ pub fn register(interp: &mut Interpreter) {
         // AI status
         let ai = get_ai().lock().unwrap();
         let ping = ai.stats.avg_move_time_ms;
-        println!("♕ AI: {} games, {:.1}% win rate, {:.1}ms avg", 
+        writeln!(interp.out(), "♕ AI: {} games, {:.1}% win rate, {:.1}ms avg", 
             ai.games_played, ai.stats.win_rate() * 100.0, ping);
         Ok(())
     });
 }
 
+/// Register `{name}_new_game`, `{name}_show`, `{name}_move`,
+/// `{name}_ai_play`, `{name}_eval` and `{name}_legal_moves` for a game
+/// played against a [`SearchPlayer`] of the given depth, like their
+/// chess counterparts. The game in progress is kept per interpreter.
+fn register_board_game<G>(interp: &mut Interpreter, name: &'static str, title: &'static str, depth: u32)
+where
+    G: GameState + 'static,
+{
+    type Session<G> = Option<GameSession<G, SearchPlayer>>;
+
+    fn show<G: GameState>(out: &Out, session: &GameSession<G, SearchPlayer>) {
+        writeln!(out, "\n{}", session.display());
+        writeln!(out, "{}", session.status());
+    }
+
+    interp.register(format!("{name}_new_game"), move |interp| {
+        let out = interp.out();
+        // Stack: first(1 = you move first, 0 = the AI does) →
+        let human_first = interp.pop()
+            .and_then(|v| v.as_integer())
+            .map(|v| v != 0)
+            .unwrap_or(true);
+
+        let first = G::start().side_to_move();
+        let human = if human_first { first } else { first.opposite() };
+        let session = GameSession::with_ai(SearchPlayer::new(depth), human == Color::White);
+        writeln!(out, "New {title} game started!");
+        writeln!(out, "   You play as: {human:?}");
+        show(&out, &session);
+        *interp.state_mut::<Session<G>>() = Some(session);
+        Ok(())
+    });
+
+    interp.register(format!("{name}_show"), move |interp| {
+        let out = interp.out();
+        if let Some(session) = interp.state_mut::<Session<G>>() {
+            show(&out, session);
+            if !session.is_game_over() && session.is_human_turn() {
+                let moves = session.legal_moves();
+                if moves.len() <= 20 {
+                    writeln!(out, "Legal moves: {}", moves.join(", "));
+                } else {
+                    writeln!(out, "Legal moves: {} moves available", moves.len());
+                }
+            }
+        } else {
+            writeln!(out, "No game in progress. Use '{name}_new_game' to start.");
+        }
+        Ok(())
+    });
+
+    interp.register(format!("{name}_move"), move |interp| {
+        let out = interp.out();
+        let move_str = interp.pop()
+            .and_then(|v| v.as_string())
+            .map_err(|_| WofError::runtime(format!("{name}_move: expected a move string")))?;
+
+        if let Some(session) = interp.state_mut::<Session<G>>() {
+            match session.human_move(&move_str) {
+                Ok(()) => {
+                    writeln!(out, "Your move: {}", move_str);
+                    if !session.is_game_over() && !session.is_human_turn() {
+                        if let Some(ai_move) = session.ai_move() {
+                            writeln!(out, "AI plays: {}", ai_move);
+                        }
+                    }
+                    show(&out, session);
+                },
+                Err(e) => writeln!(out, "❌ {}", e),
+            }
+        } else {
+            writeln!(out, "No game in progress. Use '{name}_new_game' to start.");
+        }
+        Ok(())
+    });
+
+    interp.register(format!("{name}_ai_play"), move |interp| {
+        let out = interp.out();
+        if let Some(session) = interp.state_mut::<Session<G>>() {
+            if session.is_game_over() {
+                writeln!(out, "Game is already over!");
+            } else if session.is_human_turn() {
+                writeln!(out, "It's your turn!");
+            } else if let Some(ai_move) = session.ai_move() {
+                writeln!(out, "AI plays: {}", ai_move);
+                show(&out, session);
+            }
+        } else {
+            writeln!(out, "No game in progress.");
+        }
+        Ok(())
+    });
+
+    interp.register(format!("{name}_eval"), move |interp| {
+        let out = interp.out();
+        let Some(session) = interp.state_mut::<Session<G>>() else {
+            writeln!(out, "No game in progress.");
+            return Ok(());
+        };
+        let eval = session.get_evaluation();
+        let for_human = if session.human_is_white { eval } else { -eval };
+        let perspective = if for_human > 0.0 { "your favor" } else { "AI's favor" };
+        writeln!(out, "Position evaluation: {:.2} (in {})", eval, perspective);
+        interp.push(WofValue::double(f64::from(eval)));
+        Ok(())
+    });
+
+    interp.register(format!("{name}_legal_moves"), move |interp| {
+        let out = interp.out();
+        let Some(session) = interp.state_mut::<Session<G>>() else {
+            writeln!(out, "No game in progress.");
+            return Ok(());
+        };
+        let moves = session.legal_moves();
+        writeln!(out, "Legal moves ({}): {}", moves.len(), moves.join(", "));
+        interp.push(WofValue::integer(moves.len() as i64));
+        Ok(())
+    });
+}
+
 /// Quick test of the neural chess system.
 pub fn quick_test() {
     println!("🧠 Neural Chess Quick Test\n");
@@ -464,4 +780,50 @@ mod tests {
         let record = ai.self_play_game();
         assert!(record.result != GameResult::Ongoing || record.positions.len() >= 500);
     }
+
+    #[test]
+    fn test_board_game_sessions() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+
+        interp.exec_line(r#"1 checkers_new_game "11-15" checkers_move checkers_legal_moves"#).unwrap();
+        assert!(interp.stack_mut().pop_integer().unwrap() > 0);
+        let session = interp.state::<Option<GameSession<Checkers, SearchPlayer>>>().unwrap().as_ref().unwrap();
+        assert_eq!(session.move_history.len(), 2);
+        assert!(session.is_human_turn());
+
+        // Moving second: nothing happens until the AI is asked to open.
+        interp.exec_line(r#"0 go_new_game "pass" go_move"#).unwrap();
+        let session = interp.state::<Option<GameSession<GoBoard, SearchPlayer>>>().unwrap().as_ref().unwrap();
+        assert!(session.move_history.is_empty());
+        interp.exec_line(r#"go_ai_play "pass" go_move"#).unwrap();
+        let session = interp.state::<Option<GameSession<GoBoard, SearchPlayer>>>().unwrap().as_ref().unwrap();
+        assert_eq!(session.move_history.len(), 3);
+        assert!(session.human_is_white);
+    }
+
+    #[test]
+    fn test_tournament_rates_checkpoints() {
+        let path = std::env::temp_dir().join(format!("woflang-ladder-{}.json", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+
+        interp.exec_line(r#""gen1" chess_ai_checkpoint"#).unwrap();
+        let err = interp.exec_line("1 chess_tournament").unwrap_err();
+        assert!(err.to_string().contains("needs two checkpoints"), "{err}");
+
+        // Searching a ply on the static evaluation keeps the game quick
+        interp.exec_line(r#"2 chess_ai_strength "gen2" chess_ai_checkpoint 1 chess_tournament"#).unwrap();
+        let ladder = interp.state::<Ladder>().unwrap().clone();
+        assert_eq!(ladder.get("gen1").unwrap().games(), 1);
+        assert_eq!(ladder.rating("gen1") + ladder.rating("gen2"), 3000.0);
+
+        interp.exec_line(&format!(r#""{path}" chess_ladder_save"#)).unwrap();
+        *interp.state_mut::<Ladder>() = Ladder::new();
+        interp.exec_line(&format!(r#""{path}" chess_ladder_load"#)).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(interp.state::<Ladder>(), Some(&ladder));
+        assert!(interp.exec_line(r#""/nonexistent/ladder.json" chess_ladder_load"#).is_err());
+    }
 }
@@ -12,6 +12,7 @@ use super::activation::{tanh, tanh_derivative};
 
 /// A single RNN cell.
 /// h_t = tanh(W_xh @ x_t + W_hh @ h_{t-1} + b_h)
+#[derive(Clone)]
 pub struct RNNCell {
     /// Input-to-hidden weights [input_size, hidden_size]
     pub w_xh: Tensor,
@@ -65,6 +66,15 @@ impl RNNCell {
         self.inputs.push(x.clone());
         self.hiddens.push(h_prev.clone());
         
+        let pre_act = self.pre_activation(x, h_prev);
+        self.pre_activations.push(pre_act.clone());
+        
+        // tanh activation
+        tanh(&pre_act)
+    }
+
+    /// `x @ W_xh + h_prev @ W_hh + b_h`, before activation.
+    fn pre_activation(&self, x: &Tensor, h_prev: &Tensor) -> Tensor {
         // x @ W_xh
         let x_row = x.reshape(&[1, self.input_size]);
         let xh = x_row.matmul(&self.w_xh).flatten();
@@ -74,11 +84,20 @@ impl RNNCell {
         let hh = h_row.matmul(&self.w_hh).flatten();
         
         // xh + hh + b_h
-        let pre_act = xh.add(&hh).add(&self.b_h);
-        self.pre_activations.push(pre_act.clone());
+        xh.add(&hh).add(&self.b_h)
+    }
+
+    /// Forward pass over a sequence without caching for backward.
+    pub fn infer(&self, sequence: &[Tensor]) -> Vec<Tensor> {
+        let mut h = self.init_hidden();
+        let mut outputs = Vec::with_capacity(sequence.len());
         
-        // tanh activation
-        tanh(&pre_act)
+        for x in sequence {
+            h = tanh(&self.pre_activation(x, &h));
+            outputs.push(h.clone());
+        }
+        
+        outputs
     }
 
     /// Forward pass over entire sequence.
@@ -180,6 +199,7 @@ impl RNNCell {
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// Multi-layer RNN.
+#[derive(Clone)]
 pub struct RNN {
     /// RNN cells for each layer
     pub cells: Vec<RNNCell>,
@@ -262,6 +282,29 @@ impl RNN {
         outputs.last().cloned().unwrap_or_else(|| Tensor::zeros(&[self.hidden_size]))
     }
 
+    /// Final hidden state, without caching for backward.
+    pub fn infer_final_hidden(&self, sequence: &[Tensor]) -> Tensor {
+        let mut current_sequence: Vec<Tensor> = sequence.to_vec();
+        for cell in &self.cells[..self.num_layers] {
+            current_sequence = cell.infer(&current_sequence);
+        }
+        
+        if self.bidirectional {
+            let mut reversed: Vec<Tensor> = sequence.iter().rev().cloned().collect();
+            for cell in &self.cells[self.num_layers..] {
+                reversed = cell.infer(&reversed);
+            }
+            // The last output pairs the forward pass's end with the
+            // backward pass's end, which is its first output reversed
+            if let (Some(fwd), Some(bwd)) = (current_sequence.last(), reversed.first()) {
+                let combined_data: Vec<f32> = fwd.data.iter().chain(bwd.data.iter()).copied().collect();
+                return Tensor::from_data(combined_data, &[self.hidden_size * 2]);
+            }
+        }
+        
+        current_sequence.last().cloned().unwrap_or_else(|| Tensor::zeros(&[self.hidden_size]))
+    }
+
     /// Update all cells.
     pub fn update(&mut self, learning_rate: f32) {
         for cell in &mut self.cells {
@@ -0,0 +1,371 @@
+//! Tournaments - rating players by playing them against each other.
+//!
+//! Players meet in a round robin, each pair playing the same number of
+//! games with colours alternating. Every game moves both players' Elo
+//! ratings on a [`Ladder`], which outlives the tournament so ratings
+//! build up across training runs, and the points go in a [`Crosstable`].
+//!
+//! A ladder is saved as JSON:
+//!
+//! ```text
+//! {"format":"woflang-elo-ladder","version":1,"players":{"gen1":{"rating":1516.0,"wins":3,"draws":1,"losses":2}}}
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::chess::{Color, GameResult};
+use super::game::{GamePlayer, GameState};
+
+/// Rating of a player who has not played yet.
+pub const START_RATING: f64 = 1500.0;
+
+/// Most rating a single game can move.
+pub const K_FACTOR: f64 = 32.0;
+
+/// Value of the `format` field.
+const FORMAT: &str = "woflang-elo-ladder";
+
+/// Newest file version this build reads and the one it writes.
+const VERSION: u32 = 1;
+
+/// Expected score of a player rated `rating` against one rated
+/// `opponent`: 0.5 between equals, about 0.76 at 200 points ahead.
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// LADDER
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A player's rating and record.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    /// Elo rating
+    pub rating: f64,
+    /// Games won
+    pub wins: u32,
+    /// Games drawn
+    pub draws: u32,
+    /// Games lost
+    pub losses: u32,
+}
+
+impl Rating {
+    /// Games played.
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Rating { rating: START_RATING, wins: 0, draws: 0, losses: 0 }
+    }
+}
+
+/// Elo ratings of everyone who has played, kept between tournaments.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ladder {
+    players: BTreeMap<String, Rating>,
+}
+
+/// On-disk form of a [`Ladder`].
+#[derive(Debug, Serialize, Deserialize)]
+struct LadderFile {
+    format: String,
+    version: u32,
+    players: BTreeMap<String, Rating>,
+}
+
+impl Ladder {
+    /// An empty ladder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `name`'s rating and record, if they have played.
+    pub fn get(&self, name: &str) -> Option<&Rating> {
+        self.players.get(name)
+    }
+
+    /// `name`'s rating, [`START_RATING`] if they have not played.
+    pub fn rating(&self, name: &str) -> f64 {
+        self.get(name).map_or(START_RATING, |r| r.rating)
+    }
+
+    /// Rate a game between `white` and `black` in which White scored
+    /// `white_score` (1 a win, 0.5 a draw, 0 a loss).
+    pub fn record(&mut self, white: &str, black: &str, white_score: f64) {
+        let (white_rating, black_rating) = (self.rating(white), self.rating(black));
+        let change = K_FACTOR * (white_score - expected_score(white_rating, black_rating));
+        for (name, score, change) in [(white, white_score, change), (black, 1.0 - white_score, -change)] {
+            let entry = self.players.entry(name.to_string()).or_default();
+            entry.rating += change;
+            if score > 0.5 {
+                entry.wins += 1;
+            } else if score < 0.5 {
+                entry.losses += 1;
+            } else {
+                entry.draws += 1;
+            }
+        }
+    }
+
+    /// Everyone on the ladder, highest rated first.
+    pub fn standings(&self) -> Vec<(&str, &Rating)> {
+        let mut rows: Vec<(&str, &Rating)> = self.players.iter().map(|(n, r)| (n.as_str(), r)).collect();
+        rows.sort_by(|a, b| b.1.rating.total_cmp(&a.1.rating).then(a.0.cmp(b.0)));
+        rows
+    }
+
+    /// Whether nobody has played.
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+
+    /// The ladder as JSON.
+    pub fn to_json(&self) -> String {
+        let file = LadderFile { format: FORMAT.to_string(), version: VERSION, players: self.players.clone() };
+        serde_json::to_string(&file).unwrap_or_default()
+    }
+
+    /// Read a ladder written by [`to_json`](Self::to_json), rejecting
+    /// files this build cannot read.
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let file: LadderFile = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        if file.format != FORMAT {
+            return Err(format!("not a ladder file (format '{}')", file.format));
+        }
+        if file.version > VERSION {
+            return Err(format!("file version {} is newer than {VERSION}", file.version));
+        }
+        Ok(Ladder { players: file.players })
+    }
+}
+
+impl fmt::Display for Ladder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "Nobody rated yet");
+        }
+        writeln!(f, "  #  rating     W    D    L  player")?;
+        for (i, (name, r)) in self.standings().into_iter().enumerate() {
+            writeln!(f, "{:>3} {:>7.0} {:>5} {:>4} {:>4}  {name}", i + 1, r.rating, r.wins, r.draws, r.losses)?;
+        }
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CROSSTABLE
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Points each player scored against each other player.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Crosstable {
+    /// Players, in table order
+    pub names: Vec<String>,
+    /// `points[i][j]`: what player `i` scored against player `j`
+    points: Vec<Vec<f64>>,
+    /// `games[i][j]`: games between players `i` and `j`
+    games: Vec<Vec<u32>>,
+}
+
+impl Crosstable {
+    /// An empty table for `names`.
+    pub fn new(names: Vec<String>) -> Self {
+        let n = names.len();
+        Crosstable { names, points: vec![vec![0.0; n]; n], games: vec![vec![0; n]; n] }
+    }
+
+    /// Record a game in which player `i` scored `score` against player `j`.
+    pub fn record(&mut self, i: usize, j: usize, score: f64) {
+        self.points[i][j] += score;
+        self.points[j][i] += 1.0 - score;
+        self.games[i][j] += 1;
+        self.games[j][i] += 1;
+    }
+
+    /// What player `i` scored against player `j`.
+    pub fn points(&self, i: usize, j: usize) -> f64 {
+        self.points[i][j]
+    }
+
+    /// Player `i`'s points against everyone.
+    pub fn total(&self, i: usize) -> f64 {
+        self.points[i].iter().sum()
+    }
+
+    /// Games played in the table.
+    pub fn games_played(&self) -> u32 {
+        self.games.iter().flatten().sum::<u32>() / 2
+    }
+}
+
+impl fmt::Display for Crosstable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.names.iter().map(|n| n.chars().count()).max().unwrap_or(0);
+        write!(f, "{:>3} {:width$}", "", "")?;
+        for j in 0..self.names.len() {
+            write!(f, " {:>5}", j + 1)?;
+        }
+        writeln!(f, "  total")?;
+        for (i, name) in self.names.iter().enumerate() {
+            write!(f, "{:>3} {name:width$}", i + 1)?;
+            for j in 0..self.names.len() {
+                if i == j || self.games[i][j] == 0 {
+                    write!(f, " {:>5}", "-")?;
+                } else {
+                    write!(f, " {:>5.1}", self.points[i][j])?;
+                }
+            }
+            writeln!(f, "  {:>5.1}", self.total(i))?;
+        }
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// PLAY
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Play a game from the start; one still going after `max_plies` plies,
+/// or in which a player finds no move, is a draw.
+pub fn play_game<G: GameState>(
+    white: &mut dyn GamePlayer<G>,
+    black: &mut dyn GamePlayer<G>,
+    max_plies: usize,
+) -> GameResult {
+    white.new_game();
+    black.new_game();
+    let mut state = G::start();
+    for _ in 0..max_plies {
+        if state.result() != GameResult::Ongoing {
+            break;
+        }
+        let m = match state.side_to_move() {
+            Color::White => white.select_move(&state),
+            Color::Black => black.select_move(&state),
+        };
+        let Some(m) = m else { break };
+        state.apply(m);
+    }
+    match state.result() {
+        GameResult::Ongoing => GameResult::Draw,
+        result => result,
+    }
+}
+
+/// White's points for a finished game.
+fn white_score(result: GameResult) -> f64 {
+    match result {
+        GameResult::WhiteWins => 1.0,
+        GameResult::BlackWins => 0.0,
+        GameResult::Draw | GameResult::Ongoing => 0.5,
+    }
+}
+
+/// Play a round robin in which every pair of `players` meets `games`
+/// times, alternating colours, rating each game on `ladder`. Calls
+/// `on_game(done, total)` before each game and once after the last, and
+/// stops early if it returns `false`. Returns the crosstable of the games
+/// played.
+pub fn round_robin<G: GameState, P: GamePlayer<G>>(
+    players: &mut [(String, P)],
+    games: usize,
+    max_plies: usize,
+    ladder: &mut Ladder,
+    on_game: &mut dyn FnMut(usize, usize) -> bool,
+) -> Crosstable {
+    let mut table = Crosstable::new(players.iter().map(|(name, _)| name.clone()).collect());
+    let n = players.len();
+    let total = n * n.saturating_sub(1) / 2 * games;
+    let mut done = 0;
+    for j in 1..n {
+        for i in 0..j {
+            let (left, right) = players.split_at_mut(j);
+            let (a, b) = (&mut left[i], &mut right[0]);
+            for g in 0..games {
+                if !on_game(done, total) {
+                    return table;
+                }
+                // Player i has White in even games
+                let (white, black) = if g % 2 == 0 { (&mut *a, &mut *b) } else { (&mut *b, &mut *a) };
+                let score = white_score(play_game::<G>(&mut white.1, &mut black.1, max_plies));
+                ladder.record(&white.0, &black.0, score);
+                table.record(i, j, if g % 2 == 0 { score } else { 1.0 - score });
+                done += 1;
+            }
+        }
+    }
+    on_game(done, total);
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::checkers::Checkers;
+    use super::super::game::SearchPlayer;
+
+    #[test]
+    fn test_elo_updates() {
+        assert!((expected_score(1500.0, 1500.0) - 0.5).abs() < 1e-9);
+        assert!((expected_score(1700.0, 1500.0) - 0.7597).abs() < 1e-3);
+
+        let mut ladder = Ladder::new();
+        ladder.record("a", "b", 1.0);
+        assert_eq!(ladder.rating("a"), 1516.0);
+        assert_eq!(ladder.rating("b"), 1484.0);
+        ladder.record("b", "a", 0.5);
+        // The lower rated side gains from a draw
+        assert!(ladder.rating("b") > 1484.0);
+        assert_eq!(ladder.rating("a") + ladder.rating("b"), 3000.0);
+        assert_eq!(ladder.get("a").unwrap().games(), 2);
+        assert_eq!(ladder.standings()[0].0, "a");
+        assert_eq!(ladder.rating("c"), START_RATING);
+    }
+
+    #[test]
+    fn test_ladder_round_trips_through_json() {
+        let mut ladder = Ladder::new();
+        ladder.record("gen1", "gen2", 0.0);
+        let back = Ladder::from_json(&ladder.to_json()).unwrap();
+        assert_eq!(back, ladder);
+        assert!(back.to_string().contains("  1    1516     1    0    0  gen2\n"), "{back}");
+
+        let err = Ladder::from_json(r#"{"format":"woflang-graph","version":1,"players":{}}"#).unwrap_err();
+        assert!(err.contains("not a ladder file"));
+        assert!(Ladder::from_json(r#"{"format":"woflang-elo-ladder","version":9,"players":{}}"#).is_err());
+    }
+
+    #[test]
+    fn test_round_robin_fills_crosstable_and_ladder() {
+        let mut players: Vec<(String, SearchPlayer)> =
+            [("deep", 3), ("shallow", 1), ("mid", 2)].map(|(n, d)| (n.to_string(), SearchPlayer::new(d))).into();
+        let mut ladder = Ladder::new();
+        let table = round_robin::<Checkers, _>(&mut players, 2, 60, &mut ladder, &mut |_, _| true);
+
+        assert_eq!(table.games_played(), 6);
+        for i in 0..3 {
+            assert_eq!(ladder.get(&table.names[i]).unwrap().games(), 4);
+            for j in 0..3 {
+                if i != j {
+                    assert_eq!(table.points(i, j) + table.points(j, i), 2.0);
+                }
+            }
+        }
+        let total: f64 = (0..3).map(|i| table.total(i)).sum();
+        assert_eq!(total, 6.0);
+        let shown = table.to_string();
+        assert!(shown.starts_with("                1     2     3  total\n  1 deep        -"), "{shown}");
+
+        // Stopping before the first game plays nothing
+        let table = round_robin::<Checkers, _>(&mut players, 2, 60, &mut ladder, &mut |_, _| false);
+        assert_eq!(table.games_played(), 0);
+    }
+}
@@ -24,10 +24,15 @@
 //!
 //! ### Measurement
 //! - `measure` - Measure and collapse qubit
+//!
+//! ### Register
+//! - `qreg_init`, `qreg_show`, `qreg_measure` - n-qubit register
+//! - `qreg_amps` - Amplitudes of a register qubit as complex values (i → α β)
 
 use std::sync::{Mutex, OnceLock};
 use rand::Rng;
-use woflang_core::{WofValue, InterpreterContext, WofType};
+use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_4};
+use woflang_core::{Complex, InterpreterContext as _, WofError, WofValue};
 use woflang_runtime::Interpreter;
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -39,53 +44,36 @@ use woflang_runtime::Interpreter;
 #[derive(Clone, Debug)]
 pub struct Qubit {
     /// Probability amplitude for |0⟩
-    pub alpha_real: f64,
-    pub alpha_imag: f64,
+    pub alpha: Complex,
     /// Probability amplitude for |1⟩
-    pub beta_real: f64,
-    pub beta_imag: f64,
+    pub beta: Complex,
 }
 
 impl Qubit {
     /// Create |0⟩ state
     pub fn zero() -> Self {
-        Qubit {
-            alpha_real: 1.0,
-            alpha_imag: 0.0,
-            beta_real: 0.0,
-            beta_imag: 0.0,
-        }
+        Qubit { alpha: Complex::ONE, beta: Complex::ZERO }
     }
 
     /// Create |1⟩ state
     pub fn one() -> Self {
-        Qubit {
-            alpha_real: 0.0,
-            alpha_imag: 0.0,
-            beta_real: 1.0,
-            beta_imag: 0.0,
-        }
+        Qubit { alpha: Complex::ZERO, beta: Complex::ONE }
     }
 
     /// Create equal superposition: (|0⟩ + |1⟩) / √2
     pub fn superposition() -> Self {
-        let s = 1.0 / 2.0_f64.sqrt();
-        Qubit {
-            alpha_real: s,
-            alpha_imag: 0.0,
-            beta_real: s,
-            beta_imag: 0.0,
-        }
+        let s = Complex::from(FRAC_1_SQRT_2);
+        Qubit { alpha: s, beta: s }
     }
 
     /// Probability of measuring |0⟩
     pub fn prob_zero(&self) -> f64 {
-        self.alpha_real * self.alpha_real + self.alpha_imag * self.alpha_imag
+        self.alpha.norm_sqr()
     }
 
     /// Probability of measuring |1⟩
     pub fn prob_one(&self) -> f64 {
-        self.beta_real * self.beta_real + self.beta_imag * self.beta_imag
+        self.beta.norm_sqr()
     }
 
     /// Measure the qubit, collapsing to classical bit
@@ -106,59 +94,37 @@ impl Qubit {
 
     /// Apply Hadamard gate: H|0⟩ = (|0⟩+|1⟩)/√2, H|1⟩ = (|0⟩-|1⟩)/√2
     pub fn hadamard(&mut self) {
-        let s = 1.0 / 2.0_f64.sqrt();
-        let new_alpha_r = s * (self.alpha_real + self.beta_real);
-        let new_alpha_i = s * (self.alpha_imag + self.beta_imag);
-        let new_beta_r = s * (self.alpha_real - self.beta_real);
-        let new_beta_i = s * (self.alpha_imag - self.beta_imag);
-
-        self.alpha_real = new_alpha_r;
-        self.alpha_imag = new_alpha_i;
-        self.beta_real = new_beta_r;
-        self.beta_imag = new_beta_i;
+        let (a, b) = (self.alpha, self.beta);
+        self.alpha = (a + b).scale(FRAC_1_SQRT_2);
+        self.beta = (a - b).scale(FRAC_1_SQRT_2);
     }
 
     /// Apply Pauli-X gate (bit flip): X|0⟩ = |1⟩, X|1⟩ = |0⟩
     pub fn pauli_x(&mut self) {
-        std::mem::swap(&mut self.alpha_real, &mut self.beta_real);
-        std::mem::swap(&mut self.alpha_imag, &mut self.beta_imag);
+        std::mem::swap(&mut self.alpha, &mut self.beta);
     }
 
     /// Apply Pauli-Y gate
     pub fn pauli_y(&mut self) {
         // Y = [[0, -i], [i, 0]]
-        let new_alpha_r = self.beta_imag;
-        let new_alpha_i = -self.beta_real;
-        let new_beta_r = -self.alpha_imag;
-        let new_beta_i = self.alpha_real;
-
-        self.alpha_real = new_alpha_r;
-        self.alpha_imag = new_alpha_i;
-        self.beta_real = new_beta_r;
-        self.beta_imag = new_beta_i;
+        let (a, b) = (self.alpha, self.beta);
+        self.alpha = -Complex::I * b;
+        self.beta = Complex::I * a;
     }
 
     /// Apply Pauli-Z gate (phase flip): Z|0⟩ = |0⟩, Z|1⟩ = -|1⟩
     pub fn pauli_z(&mut self) {
-        self.beta_real = -self.beta_real;
-        self.beta_imag = -self.beta_imag;
+        self.beta = -self.beta;
     }
 
     /// Apply S gate (π/2 phase): S|0⟩ = |0⟩, S|1⟩ = i|1⟩
     pub fn s_gate(&mut self) {
-        let new_beta_r = -self.beta_imag;
-        let new_beta_i = self.beta_real;
-        self.beta_real = new_beta_r;
-        self.beta_imag = new_beta_i;
+        self.beta = Complex::I * self.beta;
     }
 
     /// Apply T gate (π/4 phase)
     pub fn t_gate(&mut self) {
-        let s = 1.0 / 2.0_f64.sqrt();
-        let new_beta_r = s * (self.beta_real - self.beta_imag);
-        let new_beta_i = s * (self.beta_real + self.beta_imag);
-        self.beta_real = new_beta_r;
-        self.beta_imag = new_beta_i;
+        self.beta = Complex::from_polar(1.0, FRAC_PI_4) * self.beta;
     }
 }
 
@@ -195,7 +161,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: → 0|1
     interp.register("|ψ⟩", |interp| {
         let bit = random_bit();
-        println!("[quantum] |ψ⟩ superposition → pushed qubit {}", bit);
+        writeln!(interp.out(), "[quantum] |ψ⟩ superposition → pushed qubit {}", bit);
         interp.stack_mut().push(WofValue::integer(bit));
         Ok(())
     });
@@ -203,21 +169,21 @@ pub fn register(interp: &mut Interpreter) {
     // Alternative name
     interp.register("qubit", |interp| {
         let bit = random_bit();
-        println!("[quantum] qubit superposition → {}", bit);
+        writeln!(interp.out(), "[quantum] qubit superposition → {}", bit);
         interp.stack_mut().push(WofValue::integer(bit));
         Ok(())
     });
 
     // Push |0⟩
     interp.register("|0⟩", |interp| {
-        println!("[quantum] |0⟩ → pushed 0");
+        writeln!(interp.out(), "[quantum] |0⟩ → pushed 0");
         interp.stack_mut().push(WofValue::integer(0));
         Ok(())
     });
 
     // Push |1⟩
     interp.register("|1⟩", |interp| {
-        println!("[quantum] |1⟩ → pushed 1");
+        writeln!(interp.out(), "[quantum] |1⟩ → pushed 1");
         interp.stack_mut().push(WofValue::integer(1));
         Ok(())
     });
@@ -230,13 +196,13 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: qubit → new_qubit
     interp.register("H", |interp| {
         if interp.stack().is_empty() {
-            println!("[quantum] H gate: empty stack");
+            writeln!(interp.out(), "[quantum] H gate: empty stack");
             return Ok(());
         }
 
         let _ = interp.stack_mut().pop()?;
         let bit = random_bit();
-        println!("[quantum] H gate → new qubit {}", bit);
+        writeln!(interp.out(), "[quantum] H gate → new qubit {}", bit);
         interp.stack_mut().push(WofValue::integer(bit));
         Ok(())
     });
@@ -260,7 +226,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: qubit → flipped_qubit
     interp.register("X", |interp| {
         if interp.stack().is_empty() {
-            println!("[quantum] X gate: empty stack");
+            writeln!(interp.out(), "[quantum] X gate: empty stack");
             return Ok(());
         }
 
@@ -268,7 +234,7 @@ pub fn register(interp: &mut Interpreter) {
         let v = q.try_integer().unwrap_or(0);
 
         let flipped = if v == 0 { 1 } else { 0 };
-        println!("[quantum] X gate: {} → {}", v, flipped);
+        writeln!(interp.out(), "[quantum] X gate: {} → {}", v, flipped);
         interp.stack_mut().push(WofValue::integer(flipped));
         Ok(())
     });
@@ -288,7 +254,7 @@ pub fn register(interp: &mut Interpreter) {
     // Y gate (simplified: bit flip with phase)
     interp.register("Y", |interp| {
         if interp.stack().is_empty() {
-            println!("[quantum] Y gate: empty stack");
+            writeln!(interp.out(), "[quantum] Y gate: empty stack");
             return Ok(());
         }
 
@@ -296,7 +262,7 @@ pub fn register(interp: &mut Interpreter) {
         let v = q.try_integer().unwrap_or(0);
 
         let flipped = if v == 0 { 1 } else { 0 };
-        println!("[quantum] Y gate: {} → {} (with phase)", v, flipped);
+        writeln!(interp.out(), "[quantum] Y gate: {} → {} (with phase)", v, flipped);
         interp.stack_mut().push(WofValue::integer(flipped));
         Ok(())
     });
@@ -305,14 +271,14 @@ pub fn register(interp: &mut Interpreter) {
     // In classical simulation, this is identity
     interp.register("Z", |interp| {
         if interp.stack().is_empty() {
-            println!("[quantum] Z gate: empty stack");
+            writeln!(interp.out(), "[quantum] Z gate: empty stack");
             return Ok(());
         }
 
         let q = interp.stack_mut().pop()?;
         let v = q.try_integer().unwrap_or(0);
 
-        println!("[quantum] Z gate: {} → {} (phase flip)", v, v);
+        writeln!(interp.out(), "[quantum] Z gate: {} → {} (phase flip)", v, v);
         interp.stack_mut().push(WofValue::integer(v));
         Ok(())
     });
@@ -325,7 +291,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: control target → control target'
     interp.register("CNOT", |interp| {
         if interp.stack().len() < 2 {
-            println!("[quantum] CNOT: need 2 qubits");
+            writeln!(interp.out(), "[quantum] CNOT: need 2 qubits");
             return Ok(());
         }
 
@@ -337,7 +303,7 @@ pub fn register(interp: &mut Interpreter) {
 
         let new_target = if c != 0 { if t == 0 { 1 } else { 0 } } else { t };
 
-        println!("[quantum] CNOT: control={}, target={} → target'={}", c, t, new_target);
+        writeln!(interp.out(), "[quantum] CNOT: control={}, target={} → target'={}", c, t, new_target);
         interp.stack_mut().push(control);
         interp.stack_mut().push(WofValue::integer(new_target));
         Ok(())
@@ -362,7 +328,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: a b → b a
     interp.register("SWAP", |interp| {
         if interp.stack().len() < 2 {
-            println!("[quantum] SWAP: need 2 qubits");
+            writeln!(interp.out(), "[quantum] SWAP: need 2 qubits");
             return Ok(());
         }
 
@@ -371,7 +337,7 @@ pub fn register(interp: &mut Interpreter) {
 
         interp.stack_mut().push(b);
         interp.stack_mut().push(a);
-        println!("[quantum] SWAP: qubits swapped");
+        writeln!(interp.out(), "[quantum] SWAP: qubits swapped");
         Ok(())
     });
 
@@ -383,14 +349,14 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: qubit → classical_bit
     interp.register("measure", |interp| {
         if interp.stack().is_empty() {
-            println!("[quantum] measure: empty stack");
+            writeln!(interp.out(), "[quantum] measure: empty stack");
             return Ok(());
         }
 
         let q = interp.stack_mut().pop()?;
         let v = if let Some(n) = q.try_integer() { n } else if let Some(f) = q.try_double() { if f >= 0.5 { 1 } else { 0 } } else { 0 };
 
-        println!("[quantum] measured: {}", v);
+        writeln!(interp.out(), "[quantum] measured: {}", v);
         interp.stack_mut().push(WofValue::integer(v));
         Ok(())
     });
@@ -408,19 +374,22 @@ pub fn register(interp: &mut Interpreter) {
             for _ in 0..n {
                 reg.push(Qubit::zero());
             }
-            println!("[quantum] Initialized register with {} qubits", n);
+            writeln!(interp.out(), "[quantum] Initialized register with {} qubits", n);
         }
         Ok(())
     });
 
     // Show quantum register state
-    interp.register("qreg_show", |_interp| {
+    interp.register("qreg_show", |interp| {
         if let Ok(reg) = quantum_register().lock() {
-            println!("[quantum] Register state ({} qubits):", reg.len());
+            writeln!(interp.out(), "[quantum] Register state ({} qubits):", reg.len());
             for (i, q) in reg.iter().enumerate() {
-                println!(
-                    "  q{}: P(0)={:.3}, P(1)={:.3}",
+                writeln!(
+                    interp.out(),
+                    "  q{}: α={:.3}, β={:.3}, P(0)={:.3}, P(1)={:.3}",
                     i,
+                    q.alpha,
+                    q.beta,
                     q.prob_zero(),
                     q.prob_one()
                 );
@@ -429,6 +398,20 @@ pub fn register(interp: &mut Interpreter) {
         Ok(())
     });
 
+    // Amplitudes of one register qubit as complex values
+    // Stack: index → α β
+    interp.register("qreg_amps", |interp| {
+        let i = interp.stack_mut().pop()?.as_integer()?;
+        let q = quantum_register()
+            .lock()
+            .ok()
+            .and_then(|reg| usize::try_from(i).ok().and_then(|i| reg.get(i).cloned()))
+            .ok_or_else(|| WofError::runtime(format!("qreg_amps: no qubit {i} in the register")))?;
+        interp.stack_mut().push(WofValue::complex(q.alpha));
+        interp.stack_mut().push(WofValue::complex(q.beta));
+        Ok(())
+    });
+
     // Measure all qubits in register
     interp.register("qreg_measure", |interp| {
         if let Ok(mut reg) = quantum_register().lock() {
@@ -436,7 +419,7 @@ pub fn register(interp: &mut Interpreter) {
             for q in reg.iter_mut() {
                 results.push(q.measure());
             }
-            println!("[quantum] Measured register: {:?}", results);
+            writeln!(interp.out(), "[quantum] Measured register: {:?}", results);
 
             // Push results as integers
             for r in results {
@@ -450,29 +433,72 @@ pub fn register(interp: &mut Interpreter) {
     // HELP
     // ─────────────────────────────────────────────────────────────────────
 
-    interp.register("quantum_help", |_interp| {
-        println!("Quantum Computing Operations:");
-        println!();
-        println!("  State Preparation:");
-        println!("    |ψ⟩, qubit    # Push random qubit (superposition)");
-        println!("    |0⟩, |1⟩      # Push specific basis state");
-        println!();
-        println!("  Single-Qubit Gates:");
-        println!("    H             # Hadamard (superposition)");
-        println!("    X, pauli_x    # Pauli-X (bit flip, NOT)");
-        println!("    Y, Z          # Pauli-Y, Pauli-Z");
-        println!();
-        println!("  Two-Qubit Gates:");
-        println!("    CNOT, CX      # Controlled NOT");
-        println!("    SWAP          # Swap two qubits");
-        println!();
-        println!("  Measurement:");
-        println!("    measure       # Measure and collapse");
-        println!();
-        println!("  Register Operations:");
-        println!("    n qreg_init   # Initialize n-qubit register");
-        println!("    qreg_show     # Show register state");
-        println!("    qreg_measure  # Measure all qubits");
+    interp.register("quantum_help", |interp| {
+        writeln!(interp.out(), "Quantum Computing Operations:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  State Preparation:");
+        writeln!(interp.out(), "    |ψ⟩, qubit    # Push random qubit (superposition)");
+        writeln!(interp.out(), "    |0⟩, |1⟩      # Push specific basis state");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Single-Qubit Gates:");
+        writeln!(interp.out(), "    H             # Hadamard (superposition)");
+        writeln!(interp.out(), "    X, pauli_x    # Pauli-X (bit flip, NOT)");
+        writeln!(interp.out(), "    Y, Z          # Pauli-Y, Pauli-Z");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Two-Qubit Gates:");
+        writeln!(interp.out(), "    CNOT, CX      # Controlled NOT");
+        writeln!(interp.out(), "    SWAP          # Swap two qubits");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Measurement:");
+        writeln!(interp.out(), "    measure       # Measure and collapse");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Register Operations:");
+        writeln!(interp.out(), "    n qreg_init   # Initialize n-qubit register");
+        writeln!(interp.out(), "    qreg_show     # Show register state");
+        writeln!(interp.out(), "    i qreg_amps   # Push qubit i's amplitudes α β");
+        writeln!(interp.out(), "    qreg_measure  # Measure all qubits");
         Ok(())
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use woflang_core::WofType;
+
+    fn close(a: Complex, b: Complex) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn gates_act_on_complex_amplitudes() {
+        let mut q = Qubit::zero();
+        q.hadamard();
+        assert!((q.prob_zero() - 0.5).abs() < 1e-12);
+        q.hadamard();
+        assert!(close(q.alpha, Complex::ONE) && close(q.beta, Complex::ZERO));
+
+        // Y|0⟩ = i|1⟩, then S multiplies the |1⟩ amplitude by i again
+        q.pauli_y();
+        assert!(close(q.beta, Complex::I));
+        q.s_gate();
+        assert!(close(q.beta, -Complex::ONE));
+
+        // T² = S
+        let mut t = Qubit::one();
+        t.t_gate();
+        t.t_gate();
+        assert!(close(t.beta, Complex::I));
+        assert!((t.prob_one() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn register_amplitudes_are_values() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        interp.exec_line("2 qreg_init 1 qreg_amps").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap().as_complex().unwrap(), Complex::ZERO);
+        assert_eq!(interp.stack_mut().pop().unwrap().value_type(), WofType::Complex);
+        assert!(interp.exec_line("5 qreg_amps").is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Calendar and astronomical calculations for Woflang.
+//!
+//! | Operation     | Stack Effect               | Description |
+//! |---------------|----------------------------|-------------|
+//! | `julian_day`  | ( y m d -- jd )            | Julian day at 0h UT (`d` may be fractional) |
+//! | `day_of_week` | ( y m d -- n )             | 0 = Sunday … 6 = Saturday |
+//! | `easter`      | ( y -- m d )               | Gregorian Easter Sunday |
+//! | `moon_phase`  | ( jd -- phase )            | Age in lunations: 0 new, 0.5 full |
+//! | `sunrise`     | ( lat lon jd -- hours )    | Sunrise in UT hours after midnight |
+//!
+//! Dates are proleptic Gregorian. Where an op takes a single date it is a
+//! Julian day, so `julian_day` feeds the others:
+//!
+//! ```text
+//! 2024 3 31 julian_day moon_phase     # ≈ 0.70, waning
+//! 51.5 -0.13 2024 6 21 julian_day sunrise   # ≈ 3.72 (03:43 UT)
+//! ```
+//!
+//! Longitudes are east-positive. The moon and sun use low-precision
+//! series, good to a few minutes, which is plenty for scheduling. With
+//! the `sigils` plugin also loaded, `easter` here replaces its easter-egg
+//! alias (`:egg` still prints the haiku).
+
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Julian day of J2000.0 (2000-01-01 12:00 TT).
+const J2000: f64 = 2_451_545.0;
+
+/// Mean length of a lunation in days.
+const SYNODIC_MONTH: f64 = 29.530_588_853;
+
+/// Julian day of a reference new moon (2000-01-06 18:14 UT).
+const NEW_MOON_EPOCH: f64 = 2_451_550.26;
+
+/// Julian day at 0h UT of a Gregorian date (Meeus, ch. 7). `day` may carry
+/// a fraction for the time of day.
+fn julian_day(year: i64, month: i64, day: f64) -> f64 {
+    let (y, m) = if month <= 2 { (year - 1, month + 12) } else { (year, month) };
+    let a = y.div_euclid(100);
+    let b = 2 - a + a.div_euclid(4);
+    (365.25 * (y + 4716) as f64).floor() + (30.6001 * (m + 1) as f64).floor() + day + b as f64
+        - 1524.5
+}
+
+/// Day of the week for a Julian day, 0 = Sunday.
+fn day_of_week(jd: f64) -> i64 {
+    ((jd + 1.5).floor() as i64).rem_euclid(7)
+}
+
+/// Month and day of Easter Sunday (anonymous Gregorian algorithm).
+fn easter(year: i64) -> (i64, i64) {
+    let a = year.rem_euclid(19);
+    let (b, c) = (year.div_euclid(100), year.rem_euclid(100));
+    let (d, e) = (b / 4, b % 4);
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    (month, day)
+}
+
+/// Fraction of the current lunation elapsed at `jd`, in `[0, 1)`.
+fn moon_phase(jd: f64) -> f64 {
+    ((jd - NEW_MOON_EPOCH) / SYNODIC_MONTH).rem_euclid(1.0)
+}
+
+/// Sunrise on the UT date containing `jd`, in hours after 0h UT, or `None`
+/// when the sun stays up or down all day. Uses the sunrise equation with
+/// the standard -0.833° altitude for refraction and the solar disc.
+fn sunrise(lat: f64, lon: f64, jd: f64) -> Option<f64> {
+    let day = (jd - J2000 + 0.5).floor();
+    let noon = day - lon / 360.0;
+    let anomaly = (357.5291 + 0.985_600_28 * noon).rem_euclid(360.0).to_radians();
+    let center = 1.9148 * anomaly.sin() + 0.02 * (2.0 * anomaly).sin() + 0.0003 * (3.0 * anomaly).sin();
+    let ecliptic = (anomaly.to_degrees() + center + 180.0 + 102.9372).rem_euclid(360.0).to_radians();
+    let transit = J2000 + noon + 0.0053 * anomaly.sin() - 0.0069 * (2.0 * ecliptic).sin();
+    let declination = (ecliptic.sin() * 23.4397_f64.to_radians().sin()).asin();
+
+    let phi = lat.to_radians();
+    let cos_hour = ((-0.833_f64).to_radians().sin() - phi.sin() * declination.sin())
+        / (phi.cos() * declination.cos());
+    if !(-1.0..=1.0).contains(&cos_hour) {
+        return None;
+    }
+    let rise = transit - cos_hour.acos().to_degrees() / 360.0;
+    Some(((rise - (J2000 + day - 0.5)) * 24.0).rem_euclid(24.0))
+}
+
+/// Pop `y m d`, checking the month.
+fn pop_date(interp: &mut Interpreter, op: &str) -> Result<(i64, i64, f64)> {
+    let day = interp.stack_mut().pop_numeric()?;
+    let month = interp.stack_mut().pop_numeric()?;
+    let year = interp.stack_mut().pop_numeric()?;
+    if !(1.0..=12.0).contains(&month) || month.fract() != 0.0 || year.fract() != 0.0 {
+        return Err(WofError::runtime(format!("{op}: expected a whole year and a month 1-12")));
+    }
+    Ok((year as i64, month as i64, day))
+}
+
+/// Register the calendar and astronomy operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: y m d → jd
+    interp.register("julian_day", |interp| {
+        let (year, month, day) = pop_date(interp, "julian_day")?;
+        interp.push(WofValue::double(julian_day(year, month, day)));
+        Ok(())
+    });
+
+    // Stack: y m d → n (0 = Sunday)
+    interp.register("day_of_week", |interp| {
+        let (year, month, day) = pop_date(interp, "day_of_week")?;
+        interp.push(WofValue::integer(day_of_week(julian_day(year, month, day))));
+        Ok(())
+    });
+
+    // Stack: y → m d
+    interp.register("easter", |interp| {
+        let year = interp.stack_mut().pop_integer()?;
+        if year < 1583 {
+            return Err(WofError::runtime("easter: the Gregorian rule starts in 1583"));
+        }
+        let (month, day) = easter(year);
+        interp.push(WofValue::integer(month));
+        interp.push(WofValue::integer(day));
+        Ok(())
+    });
+
+    // Stack: jd → phase
+    interp.register("moon_phase", |interp| {
+        let jd = interp.stack_mut().pop_numeric()?;
+        interp.push(WofValue::double(moon_phase(jd)));
+        Ok(())
+    });
+
+    // Stack: lat lon jd → hours
+    interp.register("sunrise", |interp| {
+        let jd = interp.stack_mut().pop_numeric()?;
+        let lon = interp.stack_mut().pop_numeric()?;
+        let lat = interp.stack_mut().pop_numeric()?;
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            return Err(WofError::runtime("sunrise: latitude must be in [-90, 90] and longitude in [-180, 180]"));
+        }
+        let hours = sunrise(lat, lon, jd)
+            .ok_or_else(|| WofError::runtime("sunrise: the sun does not rise or set that day"))?;
+        interp.push(WofValue::double(hours));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calendar_conversions() {
+        assert_eq!(julian_day(2000, 1, 1.5), J2000);
+        assert_eq!(julian_day(1987, 6, 19.5), 2_446_966.0);
+        assert_eq!(julian_day(1600, 1, 1.0), 2_305_447.5);
+        assert_eq!(day_of_week(julian_day(2000, 1, 1.0)), 6);
+        assert_eq!(day_of_week(julian_day(2026, 10, 16.0)), 5);
+        assert_eq!(easter(2024), (3, 31));
+        assert_eq!(easter(2025), (4, 20));
+        assert_eq!(easter(2038), (4, 25));
+        assert_eq!(easter(1818), (3, 22));
+    }
+
+    #[test]
+    fn moon_and_sun() {
+        // New moon 2024-04-08 18:21 UT, full moon 2024-03-25 07:00 UT.
+        let new = moon_phase(julian_day(2024, 4, 8.765));
+        assert!(!(0.02..=0.98).contains(&new), "{new}");
+        assert!((moon_phase(julian_day(2024, 3, 25.29)) - 0.5).abs() < 0.03);
+
+        // London, midsummer 2024: 03:43 UT. Quito, 1 March: 06:20 local.
+        let london = sunrise(51.5, -0.13, julian_day(2024, 6, 21.0)).unwrap();
+        assert!((london - 3.72).abs() < 0.1, "{london}");
+        let quito = sunrise(-0.18, -78.47, julian_day(2024, 3, 1.0)).unwrap();
+        assert!((quito - 11.33).abs() < 0.1, "{quito}");
+        assert_eq!(sunrise(78.2, 15.6, julian_day(2024, 6, 21.0)), None);
+    }
+
+    #[test]
+    fn ops_chain_through_julian_days() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        interp.exec_line("2024 easter").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 31);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 3);
+        interp.exec_line("2024 3 31 day_of_week").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 0);
+        interp.exec_line("2024 3 31 julian_day moon_phase").unwrap();
+        let phase = interp.stack_mut().pop_numeric().unwrap();
+        assert!((0.6..0.8).contains(&phase), "{phase}");
+        assert!(interp.exec_line("2024 13 1 julian_day").is_err());
+        assert!(interp.exec_line("89 0 2024 6 21 julian_day sunrise").is_err());
+    }
+}
@@ -355,29 +355,29 @@ pub fn register(interp: &mut Interpreter) {
     // HELP
     // ─────────────────────────────────────────────────────────────────────
     
-    interp.register("chem_help", |_interp| {
-        println!("Chemistry Operations:");
-        println!();
-        println!("  Element Data:");
-        println!("    \"Fe\" element_info       → full element description");
-        println!("    \"O\" atomic_weight       → 15.999");
-        println!("    26 element_info          → lookup by atomic number");
-        println!();
-        println!("  Molecular Weight:");
-        println!("    \"H2O\" molecular_weight  → 18.015");
-        println!("    \"C6H12O6\" molar_mass    → 180.156 (glucose)");
-        println!();
-        println!("  Temperature:");
-        println!("    100 \"C->F\" temp_convert → 212.0");
-        println!("    32 f_to_c               → 0.0");
-        println!("    0 c_to_k                → 273.15");
-        println!();
-        println!("  Conversion modes: C->K, K->C, C->F, F->C, K->F, F->K");
-        println!();
-        println!("  Constants:");
-        println!("    avogadro                → 6.02214076e23");
-        println!("    gas_constant            → 8.314 J/(mol·K)");
-        println!("    faraday                 → 96485 C/mol");
+    interp.register("chem_help", |interp| {
+        writeln!(interp.out(), "Chemistry Operations:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Element Data:");
+        writeln!(interp.out(), "    \"Fe\" element_info       → full element description");
+        writeln!(interp.out(), "    \"O\" atomic_weight       → 15.999");
+        writeln!(interp.out(), "    26 element_info          → lookup by atomic number");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Molecular Weight:");
+        writeln!(interp.out(), "    \"H2O\" molecular_weight  → 18.015");
+        writeln!(interp.out(), "    \"C6H12O6\" molar_mass    → 180.156 (glucose)");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Temperature:");
+        writeln!(interp.out(), "    100 \"C->F\" temp_convert → 212.0");
+        writeln!(interp.out(), "    32 f_to_c               → 0.0");
+        writeln!(interp.out(), "    0 c_to_k                → 273.15");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Conversion modes: C->K, K->C, C->F, F->C, K->F, F->K");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Constants:");
+        writeln!(interp.out(), "    avogadro                → 6.02214076e23");
+        writeln!(interp.out(), "    gas_constant            → 8.314 J/(mol·K)");
+        writeln!(interp.out(), "    faraday                 → 96485 C/mol");
         Ok(())
     });
 }
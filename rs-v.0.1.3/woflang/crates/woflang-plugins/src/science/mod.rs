@@ -2,7 +2,9 @@
 //!
 //! Scientific tools and calculations:
 //! - **chemistry** - Elements, molecular weights, temperature conversion
+//! - **astro** - Julian days, weekdays, Easter, moon phase, sunrise
 
+pub mod astro;
 pub mod chemistry;
 
 use woflang_runtime::Interpreter;
@@ -10,4 +12,5 @@ use woflang_runtime::Interpreter;
 /// Register all science operations.
 pub fn register(interp: &mut Interpreter) {
     chemistry::register(interp);
+    astro::register(interp);
 }
@@ -53,10 +53,10 @@ pub fn register(interp: &mut Interpreter) {
     interp.register(":unlock", |interp| {
         CHAOS_UNLOCKED.store(true, Ordering::Relaxed);
         
-        println!();
-        println!("⚡ Forbidden glyphs unlocked for this session.");
-        println!("   Use with reverence; the stack remembers.");
-        println!();
+        writeln!(interp.out());
+        writeln!(interp.out(), "⚡ Forbidden glyphs unlocked for this session.");
+        writeln!(interp.out(), "   Use with reverence; the stack remembers.");
+        writeln!(interp.out());
         
         interp.stack_mut().push(WofValue::integer(1));
         Ok(())
@@ -66,7 +66,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: → 0|1
     interp.register(":chaos?", |interp| {
         let on = CHAOS_UNLOCKED.load(Ordering::Relaxed);
-        println!("[chaos] {}", if on { "unleashed" } else { "sleeping" });
+        writeln!(interp.out(), "[chaos] {}", if on { "unleashed" } else { "sleeping" });
         interp.stack_mut().push(WofValue::integer(if on { 1 } else { 0 }));
         Ok(())
     });
@@ -77,23 +77,23 @@ pub fn register(interp: &mut Interpreter) {
     
     // Toggle glitch mode
     // Stack: →
-    interp.register(":glitchmode", |_interp| {
+    interp.register(":glitchmode", |interp| {
         let now = !GLITCH_MODE.load(Ordering::Relaxed);
         GLITCH_MODE.store(now, Ordering::Relaxed);
         
-        println!();
-        println!("⚠ Glitch mode {}.", if now { "ONLINE" } else { "OFFLINE" });
-        println!("Random glyph substitutions {}.", if now { "may occur" } else { "cease" });
-        println!();
+        writeln!(interp.out());
+        writeln!(interp.out(), "⚠ Glitch mode {}.", if now { "ONLINE" } else { "OFFLINE" });
+        writeln!(interp.out(), "Random glyph substitutions {}.", if now { "may occur" } else { "cease" });
+        writeln!(interp.out());
         
         Ok(())
     });
 
     // Echo with glitched text
     // Stack: →
-    interp.register(":glitch-echo", |_interp| {
+    interp.register(":glitch-echo", |interp| {
         if !GLITCH_MODE.load(Ordering::Relaxed) {
-            println!("(no glitches today)");
+            writeln!(interp.out(), "(no glitches today)");
             return Ok(());
         }
         
@@ -109,7 +109,7 @@ pub fn register(interp: &mut Interpreter) {
             }
         }
         
-        println!("{}", base);
+        writeln!(interp.out(), "{}", base);
         Ok(())
     });
 
@@ -123,15 +123,15 @@ pub fn register(interp: &mut Interpreter) {
         let now = !DEITY_MODE.load(Ordering::Relaxed);
         DEITY_MODE.store(now, Ordering::Relaxed);
         
-        println!();
-        println!("👁  Deity mode {}.", if now { "ENABLED" } else { "DISABLED" });
+        writeln!(interp.out());
+        writeln!(interp.out(), "👁  Deity mode {}.", if now { "ENABLED" } else { "DISABLED" });
         if now {
-            println!("    Recursion guards are ignored where possible.");
-            println!("    The call stack gazes back.");
+            writeln!(interp.out(), "    Recursion guards are ignored where possible.");
+            writeln!(interp.out(), "    The call stack gazes back.");
         } else {
-            println!("    Mortal limits restored.");
+            writeln!(interp.out(), "    Mortal limits restored.");
         }
-        println!();
+        writeln!(interp.out());
         
         interp.stack_mut().push(WofValue::integer(if now { 1 } else { 0 }));
         Ok(())
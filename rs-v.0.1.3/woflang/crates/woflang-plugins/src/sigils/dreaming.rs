@@ -32,9 +32,9 @@ pub fn register(interp: &mut Interpreter) {
     // Generate surreal dreamlog trace
     // Stack: [top_value] → 0
     interp.register(":dreaming", |interp| {
-        println!();
-        println!("☁ Surreal Dreamlog Trace");
-        println!("----------------------------------------");
+        writeln!(interp.out());
+        writeln!(interp.out(), "☁ Surreal Dreamlog Trace");
+        writeln!(interp.out(), "----------------------------------------");
         
         // Generate 4 random surreal statements
         for i in 0..4 {
@@ -42,19 +42,19 @@ pub fn register(interp: &mut Interpreter) {
             let g2 = GLYPHS[random_index(GLYPHS.len(), i + 100)];
             let v = VERBS[random_index(VERBS.len(), i + 200)];
             
-            println!("  {}  {}  {}", g1, v, g2);
+            writeln!(interp.out(), "  {}  {}  {}", g1, v, g2);
         }
         
         // If there's something on the stack, include it in the dream
         if let Ok(top) = interp.stack().peek() {
             let approx = top.as_double().unwrap_or(0.0);
             let glyph = GLYPHS[random_index(GLYPHS.len(), 999)];
-            println!();
-            println!("  top-of-stack drifts as {} ≈ {}", glyph, approx);
+            writeln!(interp.out());
+            writeln!(interp.out(), "  top-of-stack drifts as {} ≈ {}", glyph, approx);
         }
         
-        println!("----------------------------------------");
-        println!();
+        writeln!(interp.out(), "----------------------------------------");
+        writeln!(interp.out());
         
         interp.stack_mut().push(WofValue::integer(0));
         Ok(())
@@ -63,18 +63,18 @@ pub fn register(interp: &mut Interpreter) {
     // Alternative name
     interp.register(":dream", |interp| {
         // Same as :dreaming
-        println!();
-        println!("💤 The stack enters REM sleep...");
-        println!();
+        writeln!(interp.out());
+        writeln!(interp.out(), "💤 The stack enters REM sleep...");
+        writeln!(interp.out());
         
         for i in 0..3 {
             let g = GLYPHS[random_index(GLYPHS.len(), i * 42)];
-            println!("  {} floats by...", g);
+            writeln!(interp.out(), "  {} floats by...", g);
         }
         
-        println!();
-        println!("The dream fades.");
-        println!();
+        writeln!(interp.out());
+        writeln!(interp.out(), "The dream fades.");
+        writeln!(interp.out());
         
         interp.stack_mut().push(WofValue::integer(0));
         Ok(())
@@ -67,12 +67,12 @@ pub fn register(interp: &mut Interpreter) {
     interp.register(":egg", |interp| {
         let poem = random_haiku();
         
-        println!();
-        println!("🥚 Cryptic Glyph Haiku:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "🥚 Cryptic Glyph Haiku:");
         for line in poem {
-            println!("{}", line);
+            writeln!(interp.out(), "{}", line);
         }
-        println!();
+        writeln!(interp.out());
         
         // Push line count
         interp.stack_mut().push(WofValue::integer(3));
@@ -81,16 +81,16 @@ pub fn register(interp: &mut Interpreter) {
 
     // Alternative Easter egg name
     interp.register("easter", |interp| {
-        println!();
-        println!("🐰 You found the easter egg!");
-        println!("   The stack appreciates your curiosity.");
-        println!();
+        writeln!(interp.out());
+        writeln!(interp.out(), "🐰 You found the easter egg!");
+        writeln!(interp.out(), "   The stack appreciates your curiosity.");
+        writeln!(interp.out());
         
         let poem = random_haiku();
         for line in poem {
-            println!("{}", line);
+            writeln!(interp.out(), "{}", line);
         }
-        println!();
+        writeln!(interp.out());
         
         interp.stack_mut().push(WofValue::integer(1));
         Ok(())
@@ -116,9 +116,9 @@ pub fn register(interp: &mut Interpreter) {
             .as_nanos();
         let fortune = FORTUNES[(nanos as usize) % FORTUNES.len()];
         
-        println!();
-        println!("🥠 {}", fortune);
-        println!();
+        writeln!(interp.out());
+        writeln!(interp.out(), "🥠 {}", fortune);
+        writeln!(interp.out());
         
         interp.stack_mut().push(WofValue::string(fortune.to_string()));
         Ok(())
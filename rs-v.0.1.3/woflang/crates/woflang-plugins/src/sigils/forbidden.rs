@@ -33,11 +33,11 @@ pub fn register(interp: &mut Interpreter) {
             *msg = "You have peered into the void.".to_string();
         }
         
-        println!("⚠️  FORBIDDEN OPERATION DETECTED ⚠️");
-        println!("Attempting to divide by the void...");
+        writeln!(interp.out(), "⚠️  FORBIDDEN OPERATION DETECTED ⚠️");
+        writeln!(interp.out(), "Attempting to divide by the void...");
         
         if interp.stack().len() < 2 {
-            println!("The void requires a sacrifice.");
+            writeln!(interp.out(), "The void requires a sacrifice.");
             return Ok(());
         }
         
@@ -47,7 +47,7 @@ pub fn register(interp: &mut Interpreter) {
         
         let dividend_val = dividend.as_double().unwrap_or(0.0);
         
-        println!("Dividing {} by the essence of nothingness...", dividend_val);
+        writeln!(interp.out(), "Dividing {} by the essence of nothingness...", dividend_val);
         
         // The void consumes all
         interp.stack_mut().clear();
@@ -55,8 +55,8 @@ pub fn register(interp: &mut Interpreter) {
         // But leaves behind infinity
         interp.stack_mut().push(WofValue::double(f64::INFINITY));
         
-        println!("The operation succeeds. Infinity remains.");
-        println!("You have gazed into the abyss.");
+        writeln!(interp.out(), "The operation succeeds. Infinity remains.");
+        writeln!(interp.out(), "You have gazed into the abyss.");
         
         Ok(())
     });
@@ -65,14 +65,14 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: a → ∞
     interp.register("/0", |interp| {
         if interp.stack().is_empty() {
-            println!("Even the void requires something to consume.");
+            writeln!(interp.out(), "Even the void requires something to consume.");
             return Ok(());
         }
         
         let value = interp.stack_mut().pop()?;
         let numeric_val = value.as_double().unwrap_or(0.0);
         
-        println!("÷0: {} → ∞", numeric_val);
+        writeln!(interp.out(), "÷0: {} → ∞", numeric_val);
         
         interp.stack_mut().push(WofValue::double(f64::INFINITY));
         Ok(())
@@ -90,23 +90,23 @@ pub fn register(interp: &mut Interpreter) {
         }
         
         if interp.stack().is_empty() {
-            println!("⚔️  The Stack Slayer finds nothing to slay.");
+            writeln!(interp.out(), "⚔️  The Stack Slayer finds nothing to slay.");
             return Ok(());
         }
         
-        println!("⚔️  THE STACK SLAYER AWAKENS! ⚔️");
+        writeln!(interp.out(), "⚔️  THE STACK SLAYER AWAKENS! ⚔️");
         
         let victims = interp.stack().len();
         
         // Print dramatic effect
         for _ in 0..victims.min(10) {
-            print!("💀 ");
+            write!(interp.out(), "💀 ");
         }
-        println!();
+        writeln!(interp.out());
         
         interp.stack_mut().clear();
         
-        println!("⚰️  The Stack Slayer has claimed {} victims. The stack lies empty.", victims);
+        writeln!(interp.out(), "⚰️  The Stack Slayer has claimed {} victims. The stack lies empty.", victims);
         
         Ok(())
     });
@@ -117,7 +117,7 @@ pub fn register(interp: &mut Interpreter) {
             *msg = "The skull claims all.".to_string();
         }
         
-        println!("☠ The stack perishes.");
+        writeln!(interp.out(), "☠ The stack perishes.");
         interp.stack_mut().clear();
         Ok(())
     });
@@ -129,7 +129,7 @@ pub fn register(interp: &mut Interpreter) {
     // Bring back sacred constants from the void
     // Stack: → π e φ
     interp.register("resurrect", |interp| {
-        println!("✨ Attempting resurrection ritual...");
+        writeln!(interp.out(), "✨ Attempting resurrection ritual...");
         
         // Resurrect with mystical constants
         let e = std::f64::consts::E;
@@ -139,10 +139,10 @@ pub fn register(interp: &mut Interpreter) {
         interp.stack_mut().push(WofValue::double(e));    // e
         interp.stack_mut().push(WofValue::double(phi));  // φ
         
-        println!("✨ Three sacred constants have risen from the void.");
-        println!("   π ≈ {:.5}", PI);
-        println!("   e ≈ {:.5}", e);
-        println!("   φ ≈ {:.5}", phi);
+        writeln!(interp.out(), "✨ Three sacred constants have risen from the void.");
+        writeln!(interp.out(), "   π ≈ {:.5}", PI);
+        writeln!(interp.out(), "   e ≈ {:.5}", e);
+        writeln!(interp.out(), "   φ ≈ {:.5}", phi);
         
         Ok(())
     });
@@ -152,9 +152,9 @@ pub fn register(interp: &mut Interpreter) {
     // ═══════════════════════════════════════════════════════════════
     
     // The encrypted glyph prophecy (void division warning)
-    interp.register("glyph_prophecy", |_interp| {
-        println!("[Forbidden] The encrypted glyph prophecy divides the stack void.");
-        println!("            Beware division by zero!");
+    interp.register("glyph_prophecy", |interp| {
+        writeln!(interp.out(), "[Forbidden] The encrypted glyph prophecy divides the stack void.");
+        writeln!(interp.out(), "            Beware division by zero!");
         Ok(())
     });
 
@@ -164,12 +164,12 @@ pub fn register(interp: &mut Interpreter) {
     
     // Echo the last forbidden message
     // Stack: →
-    interp.register("forbidden_echo", |_interp| {
+    interp.register("forbidden_echo", |interp| {
         if let Ok(msg) = last_message().lock() {
             if msg.is_empty() {
-                println!("∅∅  No forbidden op to echo.");
+                writeln!(interp.out(), "∅∅  No forbidden op to echo.");
             } else {
-                println!("∅∅  Forbidden echo (inverted): {} (now returned to you)", *msg);
+                writeln!(interp.out(), "∅∅  Forbidden echo (inverted): {} (now returned to you)", *msg);
             }
         }
         Ok(())
@@ -183,7 +183,7 @@ pub fn register(interp: &mut Interpreter) {
         }
         
         if interp.stack().is_empty() {
-            println!("∅  You have peered into the void. (stack erased)");
+            writeln!(interp.out(), "∅  You have peered into the void. (stack erased)");
             interp.stack_mut().clear();
             return Ok(());
         }
@@ -194,10 +194,10 @@ pub fn register(interp: &mut Interpreter) {
                 else { false };
             
             if is_zero {
-                println!("∅  You have peered into the void. (stack erased)");
+                writeln!(interp.out(), "∅  You have peered into the void. (stack erased)");
                 interp.stack_mut().clear();
             } else {
-                println!("∅  Only the zero can echo the void.");
+                writeln!(interp.out(), "∅  Only the zero can echo the void.");
             }
         }
         
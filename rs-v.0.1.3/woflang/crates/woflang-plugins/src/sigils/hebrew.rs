@@ -37,24 +37,24 @@ pub fn register(interp: &mut Interpreter) {
     // ═══════════════════════════════════════════════════════════════
     
     // Turn on Hebrew (RTL) mode
-    interp.register("hebrew_mode_on", |_interp| {
+    interp.register("hebrew_mode_on", |interp| {
         HEBREW_MODE.store(true, Ordering::Relaxed);
-        println!("[hebrew_ops] Hebrew mode: ON (RTL mirroring enabled)");
+        writeln!(interp.out(), "[hebrew_ops] Hebrew mode: ON (RTL mirroring enabled)");
         Ok(())
     });
 
     // Turn off Hebrew mode
-    interp.register("hebrew_mode_off", |_interp| {
+    interp.register("hebrew_mode_off", |interp| {
         HEBREW_MODE.store(false, Ordering::Relaxed);
-        println!("[hebrew_ops] Hebrew mode: OFF");
+        writeln!(interp.out(), "[hebrew_ops] Hebrew mode: OFF");
         Ok(())
     });
 
     // Toggle Hebrew mode
-    interp.register("hebrew_toggle", |_interp| {
+    interp.register("hebrew_toggle", |interp| {
         let now = !HEBREW_MODE.load(Ordering::Relaxed);
         HEBREW_MODE.store(now, Ordering::Relaxed);
-        println!("[hebrew_ops] Hebrew mode: {}", if now { "ON" } else { "OFF" });
+        writeln!(interp.out(), "[hebrew_ops] Hebrew mode: {}", if now { "ON" } else { "OFF" });
         Ok(())
     });
 
@@ -73,7 +73,7 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: value → echoed_string
     interp.register("hebrew_echo", |interp| {
         if interp.stack().is_empty() {
-            println!("[hebrew_ops] hebrew_echo: stack is empty.");
+            writeln!(interp.out(), "[hebrew_ops] hebrew_echo: stack is empty.");
             return Ok(());
         }
         
@@ -86,7 +86,7 @@ pub fn register(interp: &mut Interpreter) {
             s
         };
         
-        println!("{}", out);
+        writeln!(interp.out(), "{}", out);
         
         interp.stack_mut().push(WofValue::string(out));
         Ok(())
@@ -107,7 +107,7 @@ pub fn register(interp: &mut Interpreter) {
             joke.to_string()
         };
         
-        println!("{}", out);
+        writeln!(interp.out(), "{}", out);
         
         interp.stack_mut().push(WofValue::string(out));
         Ok(())
@@ -116,7 +116,7 @@ pub fn register(interp: &mut Interpreter) {
     // Alternative name for the joke
     interp.register("moses_tea", |interp| {
         let joke = "☕ He brews it! 🌊";
-        println!("{}", joke);
+        writeln!(interp.out(), "{}", joke);
         interp.stack_mut().push(WofValue::string(joke.to_string()));
         Ok(())
     });
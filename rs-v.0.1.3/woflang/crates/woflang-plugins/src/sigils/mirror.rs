@@ -35,10 +35,10 @@ pub fn register(interp: &mut Interpreter) {
             interp.stack_mut().push(v);
         }
         
-        println!();
-        println!("🪞 Reverse-stack mode {}.", if now { "enabled" } else { "disabled" });
-        println!("   Top and bottom have swapped stories.");
-        println!();
+        writeln!(interp.out());
+        writeln!(interp.out(), "🪞 Reverse-stack mode {}.", if now { "enabled" } else { "disabled" });
+        writeln!(interp.out(), "   Top and bottom have swapped stories.");
+        writeln!(interp.out());
         
         interp.stack_mut().push(WofValue::integer(if now { 1 } else { 0 }));
         Ok(())
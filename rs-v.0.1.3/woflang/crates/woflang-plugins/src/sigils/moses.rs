@@ -24,38 +24,38 @@ pub fn register(interp: &mut Interpreter) {
         let len = interp.stack().len();
         
         if len == 0 {
-            println!("[moses] The sea is dry. The stack is empty.");
+            writeln!(interp.out(), "[moses] The sea is dry. The stack is empty.");
             return Ok(());
         }
         
         if len == 1 {
-            println!("[moses] Only one value in the sea; nothing to part:");
+            writeln!(interp.out(), "[moses] Only one value in the sea; nothing to part:");
             if let Ok(top) = interp.stack().peek() {
-                println!("        top → {}", describe_value(top));
+                writeln!(interp.out(), "        top → {}", describe_value(top));
             }
             return Ok(());
         }
         
         let mid = len / 2;
         
-        println!("🌊 [moses] Parting the stack-sea of {} values...", len);
-        println!("    left ({} values, bottom side):", mid);
+        writeln!(interp.out(), "🌊 [moses] Parting the stack-sea of {} values...", len);
+        writeln!(interp.out(), "    left ({} values, bottom side):", mid);
         
         // We need to peek at stack elements by index
         // The stack grows upward, so index 0 is bottom
         for i in 0..mid {
             if let Ok(val) = interp.stack().peek_at(i) {
-                println!("      [{}] {}", i, describe_value(val));
+                writeln!(interp.out(), "      [{}] {}", i, describe_value(val));
             }
         }
         
-        println!("    ───────────────  ⟡  ───────────────");
+        writeln!(interp.out(), "    ───────────────  ⟡  ───────────────");
         
-        println!("    right ({} values, including top):", len - mid);
+        writeln!(interp.out(), "    right ({} values, including top):", len - mid);
         for i in mid..len {
             let is_top = i + 1 == len;
             if let Ok(val) = interp.stack().peek_at(i) {
-                println!("      [{}] {}{}", i, describe_value(val), 
+                writeln!(interp.out(), "      [{}] {}{}", i, describe_value(val), 
                     if is_top { "   ← top" } else { "" });
             }
         }
@@ -69,7 +69,7 @@ pub fn register(interp: &mut Interpreter) {
         let len = interp.stack().len();
         
         if len < 2 {
-            println!("[moses_split] Need at least two values to part the sea.");
+            writeln!(interp.out(), "[moses_split] Need at least two values to part the sea.");
             return Ok(());
         }
         
@@ -95,10 +95,10 @@ pub fn register(interp: &mut Interpreter) {
             interp.stack_mut().push(v.clone());
         }
         
-        println!("🌊 [moses_split] The stack-sea has been parted.");
-        println!("    Left side size:  {}", mid);
-        println!("    Right side size: {}", len - mid);
-        println!("    Marker value:    \"⟡-SEA-SPLIT-⟡\" (in the middle of the stack)");
+        writeln!(interp.out(), "🌊 [moses_split] The stack-sea has been parted.");
+        writeln!(interp.out(), "    Left side size:  {}", mid);
+        writeln!(interp.out(), "    Right side size: {}", len - mid);
+        writeln!(interp.out(), "    Marker value:    \"⟡-SEA-SPLIT-⟡\" (in the middle of the stack)");
         
         Ok(())
     });
@@ -51,7 +51,7 @@ pub fn register(interp: &mut Interpreter) {
             chain.push(chosen.clone());
         }
         
-        println!("[Prophecy] {}", chosen);
+        writeln!(interp.out(), "[Prophecy] {}", chosen);
         
         interp.stack_mut().push(WofValue::string(chosen));
         Ok(())
@@ -59,15 +59,15 @@ pub fn register(interp: &mut Interpreter) {
 
     // View the prophecy chain
     // Stack: →
-    interp.register("prophecy_chain", |_interp| {
-        println!("🔗  Prophecy Chain:");
+    interp.register("prophecy_chain", |interp| {
+        writeln!(interp.out(), "🔗  Prophecy Chain:");
         
         if let Ok(chain) = prophecy_chain().lock() {
             if chain.is_empty() {
-                println!("  (no prophecies yet revealed)");
+                writeln!(interp.out(), "  (no prophecies yet revealed)");
             } else {
                 for p in chain.iter() {
-                    println!("  {}", p);
+                    writeln!(interp.out(), "  {}", p);
                 }
             }
         }
@@ -77,11 +77,11 @@ pub fn register(interp: &mut Interpreter) {
 
     // Clear the prophecy chain
     // Stack: →
-    interp.register("prophecy_clear", |_interp| {
+    interp.register("prophecy_clear", |interp| {
         if let Ok(mut chain) = prophecy_chain().lock() {
             chain.clear();
         }
-        println!("[Prophecy] The chain has been broken. All is forgotten.");
+        writeln!(interp.out(), "[Prophecy] The chain has been broken. All is forgotten.");
         Ok(())
     });
 
@@ -5,34 +5,35 @@
 //! - `:wofsigil` - Display the Woflang glyph totem
 //! - `:wolf` - Display the wolf ASCII art
 
+use woflang_core::InterpreterContext;
 use woflang_runtime::Interpreter;
 
 /// Register totem operations.
 pub fn register(interp: &mut Interpreter) {
     // Display the sigil map
-    interp.register("sigil_map", |_interp| {
-        println!("Sacred Sigils:");
-        println!("  prophecy (🔮): Cryptic stack fate message");
-        println!("  stack_slayer (☠️): Destroys the stack (forbidden)");
-        println!("  :egg (🥚): Cryptic glyph haiku");
-        println!("  :whitexmas (❄): Sigil snowstorm");
-        println!("  :dreaming (☁): Surreal debug traces");
-        println!("  :deity (👁): Divine recursion mode");
-        println!("  :unlock (⚡): Unlock forbidden glyphs");
-        println!("  :glitchmode (⚠): Random glyph substitutions");
-        println!("  :mirror (🪞): Reverse stack mode");
-        println!("  moses (🌊): Part the stack sea");
-        println!("  hebrews_it (☕): The Moses tea joke");
-        println!("  void_division (∅): Divide by the void");
-        println!("  fortune (🥠): Glyph fortune cookie");
-        println!("  :matrix (🟢): Matrix sigil rain");
-        println!("  sigil_map (🗺️): This map");
+    interp.register("sigil_map", |interp| {
+        writeln!(interp.out(), "Sacred Sigils:");
+        writeln!(interp.out(), "  prophecy (🔮): Cryptic stack fate message");
+        writeln!(interp.out(), "  stack_slayer (☠️): Destroys the stack (forbidden)");
+        writeln!(interp.out(), "  :egg (🥚): Cryptic glyph haiku");
+        writeln!(interp.out(), "  :whitexmas (❄): Sigil snowstorm");
+        writeln!(interp.out(), "  :dreaming (☁): Surreal debug traces");
+        writeln!(interp.out(), "  :deity (👁): Divine recursion mode");
+        writeln!(interp.out(), "  :unlock (⚡): Unlock forbidden glyphs");
+        writeln!(interp.out(), "  :glitchmode (⚠): Random glyph substitutions");
+        writeln!(interp.out(), "  :mirror (🪞): Reverse stack mode");
+        writeln!(interp.out(), "  moses (🌊): Part the stack sea");
+        writeln!(interp.out(), "  hebrews_it (☕): The Moses tea joke");
+        writeln!(interp.out(), "  void_division (∅): Divide by the void");
+        writeln!(interp.out(), "  fortune (🥠): Glyph fortune cookie");
+        writeln!(interp.out(), "  :matrix (🟢): Matrix sigil rain");
+        writeln!(interp.out(), "  sigil_map (🗺️): This map");
         Ok(())
     });
 
     // Display the Woflang glyph totem
-    interp.register(":wofsigil", |_interp| {
-        println!(r#"
+    interp.register(":wofsigil", |interp| {
+        writeln!(interp.out(), r#"
 
             ╭────────────────────────────╮
             │        W O F L A N G      │
@@ -46,8 +47,8 @@ pub fn register(interp: &mut Interpreter) {
     });
 
     // Display wolf ASCII art
-    interp.register(":wolf", |_interp| {
-        println!(r#"
+    interp.register(":wolf", |interp| {
+        writeln!(interp.out(), r#"
                           __
                         .d$$b
                       .' TO$;\
@@ -69,8 +70,8 @@ pub fn register(interp: &mut Interpreter) {
     });
 
     // Display a mystical glyph circle
-    interp.register(":circle", |_interp| {
-        println!(r#"
+    interp.register(":circle", |interp| {
+        writeln!(interp.out(), r#"
                ╭─────────────╮
              ╭─┤  ⟁  ◬  𓂀  ├─╮
             ╱  ╰─────────────╯  ╲
@@ -89,8 +90,8 @@ pub fn register(interp: &mut Interpreter) {
     });
 
     // Display the version banner
-    interp.register(":banner", |_interp| {
-        println!(r#"
+    interp.register(":banner", |interp| {
+        writeln!(interp.out(), r#"
   ██╗    ██╗ ██████╗ ███████╗██╗      █████╗ ███╗   ██╗ ██████╗
   ██║    ██║██╔═══██╗██╔════╝██║     ██╔══██╗████╗  ██║██╔════╝
   ██║ █╗ ██║██║   ██║█████╗  ██║     ███████║██╔██╗ ██║██║  ███╗
@@ -104,63 +105,63 @@ pub fn register(interp: &mut Interpreter) {
     });
 
     // Credits
-    interp.register(":credits", |_interp| {
-        println!();
-        println!("╔═══════════════════════════════════════════════╗");
-        println!("║              W O F L A N G                    ║");
-        println!("║     A Unicode-native stack-based language     ║");
-        println!("╠═══════════════════════════════════════════════╣");
-        println!("║  Created with 🐺 and ☕                        ║");
-        println!("║  Ported from C++ to Rust with love            ║");
-        println!("║                                               ║");
-        println!("║  Features:                                    ║");
-        println!("║  • Unicode glyph operations                   ║");
-        println!("║  • Stack-based computation                    ║");
-        println!("║  • Variables, functions, loops                ║");
-        println!("║  • Cryptic easter eggs                        ║");
-        println!("║  • The Moses tea joke                         ║");
-        println!("╚═══════════════════════════════════════════════╝");
-        println!();
+    interp.register(":credits", |interp| {
+        writeln!(interp.out());
+        writeln!(interp.out(), "╔═══════════════════════════════════════════════╗");
+        writeln!(interp.out(), "║              W O F L A N G                    ║");
+        writeln!(interp.out(), "║     A Unicode-native stack-based language     ║");
+        writeln!(interp.out(), "╠═══════════════════════════════════════════════╣");
+        writeln!(interp.out(), "║  Created with 🐺 and ☕                        ║");
+        writeln!(interp.out(), "║  Ported from C++ to Rust with love            ║");
+        writeln!(interp.out(), "║                                               ║");
+        writeln!(interp.out(), "║  Features:                                    ║");
+        writeln!(interp.out(), "║  • Unicode glyph operations                   ║");
+        writeln!(interp.out(), "║  • Stack-based computation                    ║");
+        writeln!(interp.out(), "║  • Variables, functions, loops                ║");
+        writeln!(interp.out(), "║  • Cryptic easter eggs                        ║");
+        writeln!(interp.out(), "║  • The Moses tea joke                         ║");
+        writeln!(interp.out(), "╚═══════════════════════════════════════════════╝");
+        writeln!(interp.out());
         Ok(())
     });
 
     // Display help for sigils
-    interp.register(":sigil-help", |_interp| {
-        println!("Sigil Commands:");
-        println!();
-        println!("  Mode Toggles:");
-        println!("    :unlock      - Unlock forbidden glyphs");
-        println!("    :chaos?      - Check if chaos is unlocked");
-        println!("    :glitchmode  - Toggle glyph glitching");
-        println!("    :deity       - Toggle divine recursion mode");
-        println!("    :mirror      - Toggle and reverse stack");
-        println!();
-        println!("  Easter Eggs:");
-        println!("    :egg         - Random glyph haiku");
-        println!("    :whitexmas   - Sigil snowstorm");
-        println!("    :matrix      - Matrix-style rain");
-        println!("    :snow        - Gentle snowfall");
-        println!("    :stars       - Starry sky");
-        println!("    :dreaming    - Surreal debug trace");
-        println!("    fortune      - Glyph fortune cookie");
-        println!();
-        println!("  Forbidden:");
-        println!("    void_division - Divide by void");
-        println!("    stack_slayer  - Destroy the stack");
-        println!("    /0            - Quick divide by zero");
-        println!();
-        println!("  Moses:");
-        println!("    moses         - Part the stack (view)");
-        println!("    moses_split   - Part with marker");
-        println!("    hebrews_it    - The tea joke");
-        println!();
-        println!("  Display:");
-        println!("    :wofsigil     - Glyph totem");
-        println!("    :wolf         - Wolf art");
-        println!("    :banner       - Version banner");
-        println!("    :circle       - Mystical circle");
-        println!("    :credits      - Credits");
-        println!("    sigil_map     - List all sigils");
+    interp.register(":sigil-help", |interp| {
+        writeln!(interp.out(), "Sigil Commands:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Mode Toggles:");
+        writeln!(interp.out(), "    :unlock      - Unlock forbidden glyphs");
+        writeln!(interp.out(), "    :chaos?      - Check if chaos is unlocked");
+        writeln!(interp.out(), "    :glitchmode  - Toggle glyph glitching");
+        writeln!(interp.out(), "    :deity       - Toggle divine recursion mode");
+        writeln!(interp.out(), "    :mirror      - Toggle and reverse stack");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Easter Eggs:");
+        writeln!(interp.out(), "    :egg         - Random glyph haiku");
+        writeln!(interp.out(), "    :whitexmas   - Sigil snowstorm");
+        writeln!(interp.out(), "    :matrix      - Matrix-style rain");
+        writeln!(interp.out(), "    :snow        - Gentle snowfall");
+        writeln!(interp.out(), "    :stars       - Starry sky");
+        writeln!(interp.out(), "    :dreaming    - Surreal debug trace");
+        writeln!(interp.out(), "    fortune      - Glyph fortune cookie");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Forbidden:");
+        writeln!(interp.out(), "    void_division - Divide by void");
+        writeln!(interp.out(), "    stack_slayer  - Destroy the stack");
+        writeln!(interp.out(), "    /0            - Quick divide by zero");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Moses:");
+        writeln!(interp.out(), "    moses         - Part the stack (view)");
+        writeln!(interp.out(), "    moses_split   - Part with marker");
+        writeln!(interp.out(), "    hebrews_it    - The tea joke");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Display:");
+        writeln!(interp.out(), "    :wofsigil     - Glyph totem");
+        writeln!(interp.out(), "    :wolf         - Wolf art");
+        writeln!(interp.out(), "    :banner       - Version banner");
+        writeln!(interp.out(), "    :circle       - Mystical circle");
+        writeln!(interp.out(), "    :credits      - Credits");
+        writeln!(interp.out(), "    sigil_map     - List all sigils");
         Ok(())
     });
 }
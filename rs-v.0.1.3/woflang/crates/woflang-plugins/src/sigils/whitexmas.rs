@@ -4,6 +4,7 @@
 //! - `:whitexmas` - Display animated sigil snow
 //! - `:matrix` - Matrix-style sigil rain
 
+use woflang_core::InterpreterContext;
 use woflang_runtime::Interpreter;
 
 /// Mystical sigils for the snowstorm.
@@ -33,10 +34,10 @@ fn small_delay() {
 pub fn register(interp: &mut Interpreter) {
     // Sigil snowstorm
     // Stack: →
-    interp.register(":whitexmas", |_interp| {
-        println!();
-        println!("❄ Sigil storm begins...");
-        println!();
+    interp.register(":whitexmas", |interp| {
+        writeln!(interp.out());
+        writeln!(interp.out(), "❄ Sigil storm begins...");
+        writeln!(interp.out());
         
         let width = 40;
         let height = 16;
@@ -52,23 +53,23 @@ pub fn register(interp: &mut Interpreter) {
                     line.push(' ');
                 }
             }
-            println!("{}", line);
+            writeln!(interp.out(), "{}", line);
             small_delay();
         }
         
-        println!();
-        println!("The sigils melt back into the heap.");
-        println!();
+        writeln!(interp.out());
+        writeln!(interp.out(), "The sigils melt back into the heap.");
+        writeln!(interp.out());
         
         Ok(())
     });
 
     // Matrix-style sigil rain (denser)
     // Stack: →
-    interp.register(":matrix", |_interp| {
-        println!();
-        println!("🟢 Entering the Matrix...");
-        println!();
+    interp.register(":matrix", |interp| {
+        writeln!(interp.out());
+        writeln!(interp.out(), "🟢 Entering the Matrix...");
+        writeln!(interp.out());
         
         // Matrix-style glyphs (more techy)
         static MATRIX_GLYPHS: &[&str] = &[
@@ -91,20 +92,20 @@ pub fn register(interp: &mut Interpreter) {
                     line.push(' ');
                 }
             }
-            println!("{}", line);
+            writeln!(interp.out(), "{}", line);
         }
         
-        println!();
-        println!("There is no spoon. There is only the stack.");
-        println!();
+        writeln!(interp.out());
+        writeln!(interp.out(), "There is no spoon. There is only the stack.");
+        writeln!(interp.out());
         
         Ok(())
     });
 
     // Gentle snow (sparse)
     // Stack: →
-    interp.register(":snow", |_interp| {
-        println!();
+    interp.register(":snow", |interp| {
+        writeln!(interp.out());
         static SNOWFLAKES: &[&str] = &["❄", "❅", "❆", "✦", "°", "·"];
         
         let width = 35;
@@ -121,18 +122,18 @@ pub fn register(interp: &mut Interpreter) {
                     line.push(' ');
                 }
             }
-            println!("{}", line);
+            writeln!(interp.out(), "{}", line);
         }
         
-        println!();
+        writeln!(interp.out());
         
         Ok(())
     });
 
     // Stars in the sky
     // Stack: →
-    interp.register(":stars", |_interp| {
-        println!();
+    interp.register(":stars", |interp| {
+        writeln!(interp.out());
         static STARS: &[&str] = &["✦", "✧", "⋆", "✶", "✷", "✸", "★", "☆", "°"];
         
         let width = 50;
@@ -149,11 +150,11 @@ pub fn register(interp: &mut Interpreter) {
                     line.push(' ');
                 }
             }
-            println!("{}", line);
+            writeln!(interp.out(), "{}", line);
         }
         
-        println!("          🌙");
-        println!();
+        writeln!(interp.out(), "          🌙");
+        writeln!(interp.out());
         
         Ok(())
     });
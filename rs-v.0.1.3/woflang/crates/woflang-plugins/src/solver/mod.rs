@@ -6,8 +6,10 @@
 //!
 //! - `symbolic` - Symbolic expression trees and differentiation
 //! - `numeric` - Linear and quadratic numeric solvers
-//! - `pattern` - Pattern-based string equation solver
+//! - `parser` - Pratt parser from infix strings to symbolic expressions
+//! - `pattern` - String equation solver built on the parser
 //! - `simplify` - Expression simplification rules
+//! - `poly` - Polynomial values, arithmetic and Durand–Kerner roots
 //!
 //! ## Quick Reference
 //!
@@ -22,6 +24,13 @@
 //! ```text
 //! "2x + 3 = 7" pattern_solve  # → "x = 2"
 //! "x^2 - 5x + 6 = 0" pattern_solve  # → "x = 3, x = 2"
+//! "3(x-2)/4 + 1 = x" pattern_solve  # → "x = -2"
+//! ```
+//!
+//! ### Polynomials
+//! ```text
+//! "x^3 - 6x^2 + 11x - 6" "x" expr_to_poly poly_roots  # [[1, 0], [2, 0], [3, 0]]
+//! 6 -5 1 3 poly 4 poly_eval                          # 2
 //! ```
 //!
 //! ### Symbolic Calculus
@@ -39,9 +48,12 @@
 
 mod symbolic;
 mod numeric;
+mod parser;
 mod pattern;
+mod poly;
 mod simplify;
 
+use woflang_core::InterpreterContext;
 use woflang_runtime::Interpreter;
 
 /// Register all solver operations.
@@ -49,42 +61,52 @@ pub fn register(interp: &mut Interpreter) {
     symbolic::register(interp);
     numeric::register(interp);
     pattern::register(interp);
+    poly::register(interp);
     simplify::register(interp);
 
     // Help command
-    interp.register("solver_help", |_interp| {
-        println!("Equation Solver Operations:");
-        println!();
-        println!("  Numeric Solvers:");
-        println!("    a b c solve_linear         # ax + b = c → x");
-        println!("    a b solve_linear_simple    # ax = b → x");
-        println!("    a b c solve_quadratic      # ax² + bx + c = 0");
-        println!("    ... solve_linear_2x2       # 2x2 system");
-        println!("    n newton_sqrt              # √n via Newton-Raphson");
-        println!("    n newton_cbrt              # ∛n via Newton-Raphson");
-        println!();
-        println!("  Pattern Solver (string equations):");
-        println!("    \"2x + 3 = 7\" pattern_solve     # → \"x = 2\"");
-        println!("    \"x^2 - 5x + 6 = 0\" pattern_solve");
-        println!("    \"equation\" quick_solve         # → numeric result");
-        println!();
-        println!("  Symbolic Calculus:");
-        println!("    symbolic_diff       # Demo differentiation");
-        println!("    val sym_const       # Push constant");
-        println!("    \"x\" sym_var         # Push variable");
-        println!("    sym_add sym_mul     # Combine expressions");
-        println!("    \"x\" sym_diff        # Differentiate");
-        println!("    sym_show sym_clear  # Manage expression stack");
-        println!();
-        println!("  Simplification Rules:");
-        println!("    simplify_sum        # X + X → 2 * X");
-        println!("    simplify_mul_one    # X * 1 → X");
-        println!("    simplify_mul_zero   # X * 0 → 0");
-        println!("    simplify_add_zero   # X + 0 → X");
-        println!("    simplify_power      # X^0 → 1, X^1 → X");
-        println!();
-        println!("  Logic:");
-        println!("    a b iff             # Biconditional (a ↔ b)");
+    interp.register("solver_help", |interp| {
+        writeln!(interp.out(), "Equation Solver Operations:");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Numeric Solvers:");
+        writeln!(interp.out(), "    a b c solve_linear         # ax + b = c → x");
+        writeln!(interp.out(), "    a b solve_linear_simple    # ax = b → x");
+        writeln!(interp.out(), "    a b c solve_quadratic      # ax² + bx + c = 0");
+        writeln!(interp.out(), "    ... solve_linear_2x2       # 2x2 system");
+        writeln!(interp.out(), "    n newton_sqrt              # √n via Newton-Raphson");
+        writeln!(interp.out(), "    n newton_cbrt              # ∛n via Newton-Raphson");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Pattern Solver (string equations):");
+        writeln!(interp.out(), "    \"2x + 3 = 7\" pattern_solve     # → \"x = 2\"");
+        writeln!(interp.out(), "    \"x^2 - 5x + 6 = 0\" pattern_solve");
+        writeln!(interp.out(), "    \"3(x-2)/4 + 1 = x\" pattern_solve");
+        writeln!(interp.out(), "    \"equation\" quick_solve         # → numeric result");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Polynomials (coefficients constant term first):");
+        writeln!(interp.out(), "    c0 .. cn-1 n poly       # Build a polynomial");
+        writeln!(interp.out(), "    P x poly_eval           # Evaluate");
+        writeln!(interp.out(), "    P Q poly_add / poly_mul # Combine");
+        writeln!(interp.out(), "    P poly_deriv            # Differentiate");
+        writeln!(interp.out(), "    P poly_roots            # [[re, im] ...]");
+        writeln!(interp.out(), "    \"expr\" \"x\" expr_to_poly, P \"x\" poly_to_expr");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Symbolic Calculus:");
+        writeln!(interp.out(), "    symbolic_diff       # Demo differentiation");
+        writeln!(interp.out(), "    val sym_const       # Push constant");
+        writeln!(interp.out(), "    \"x\" sym_var         # Push variable");
+        writeln!(interp.out(), "    sym_add sym_mul     # Combine expressions");
+        writeln!(interp.out(), "    \"x\" sym_diff        # Differentiate");
+        writeln!(interp.out(), "    sym_show sym_clear  # Manage expression stack");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Simplification Rules:");
+        writeln!(interp.out(), "    simplify_sum        # X + X → 2 * X");
+        writeln!(interp.out(), "    simplify_mul_one    # X * 1 → X");
+        writeln!(interp.out(), "    simplify_mul_zero   # X * 0 → 0");
+        writeln!(interp.out(), "    simplify_add_zero   # X + 0 → X");
+        writeln!(interp.out(), "    simplify_power      # X^0 → 1, X^1 → X");
+        writeln!(interp.out());
+        writeln!(interp.out(), "  Logic:");
+        writeln!(interp.out(), "    a b iff             # Biconditional (a ↔ b)");
         Ok(())
     });
 }
@@ -133,15 +133,15 @@ pub fn register(interp: &mut Interpreter) {
         let b = interp.stack_mut().pop()?.as_double()?;
         let a = interp.stack_mut().pop()?.as_double()?;
 
-        println!("[solver] Solving: {}x + {} = {}", a, b, c);
+        writeln!(interp.out(), "[solver] Solving: {}x + {} = {}", a, b, c);
 
         match solve_linear(a, b, c) {
             Some(x) => {
-                println!("[solver] Solution: x = {}", x);
+                writeln!(interp.out(), "[solver] Solution: x = {}", x);
                 interp.stack_mut().push(WofValue::double(x));
             }
             None => {
-                println!("[solver] No solution (a = 0)");
+                writeln!(interp.out(), "[solver] No solution (a = 0)");
                 interp.stack_mut().push(WofValue::nil());
             }
         }
@@ -155,11 +155,11 @@ pub fn register(interp: &mut Interpreter) {
         let a = interp.stack_mut().pop()?.as_double()?;
 
         if a.abs() < 1e-12 {
-            println!("[solver] Cannot solve: coefficient is zero");
+            writeln!(interp.out(), "[solver] Cannot solve: coefficient is zero");
             interp.stack_mut().push(WofValue::nil());
         } else {
             let x = b / a;
-            println!("[solver] {}x = {} → x = {}", a, b, x);
+            writeln!(interp.out(), "[solver] {}x = {} → x = {}", a, b, x);
             interp.stack_mut().push(WofValue::double(x));
         }
         Ok(())
@@ -177,31 +177,31 @@ pub fn register(interp: &mut Interpreter) {
         let b = interp.stack_mut().pop()?.as_double()?;
         let a = interp.stack_mut().pop()?.as_double()?;
 
-        println!("[solver] Solving: {}x² + {}x + {} = 0", a, b, c);
+        writeln!(interp.out(), "[solver] Solving: {}x² + {}x + {} = 0", a, b, c);
         let discriminant = b * b - 4.0 * a * c;
-        println!("[solver] Discriminant = {}", discriminant);
+        writeln!(interp.out(), "[solver] Discriminant = {}", discriminant);
 
         match solve_quadratic(a, b, c) {
             QuadraticResult::TwoReal(x1, x2) => {
-                println!("[solver] Two solutions:");
-                println!("  x₁ = {}", x1);
-                println!("  x₂ = {}", x2);
+                writeln!(interp.out(), "[solver] Two solutions:");
+                writeln!(interp.out(), "  x₁ = {}", x1);
+                writeln!(interp.out(), "  x₂ = {}", x2);
                 interp.stack_mut().push(WofValue::double(x1));
                 interp.stack_mut().push(WofValue::double(x2));
             }
             QuadraticResult::OneReal(x) => {
-                println!("[solver] One solution: x = {}", x);
+                writeln!(interp.out(), "[solver] One solution: x = {}", x);
                 interp.stack_mut().push(WofValue::double(x));
             }
             QuadraticResult::Complex { real, imag } => {
-                println!("[solver] Complex solutions:");
-                println!("  x = {} ± {}i", real, imag);
+                writeln!(interp.out(), "[solver] Complex solutions:");
+                writeln!(interp.out(), "  x = {} ± {}i", real, imag);
                 // Push as string representation
                 let result = format!("{} ± {}i", real, imag);
                 interp.stack_mut().push(WofValue::string(result));
             }
             QuadraticResult::Degenerate => {
-                println!("[solver] Degenerate equation (a = 0)");
+                writeln!(interp.out(), "[solver] Degenerate equation (a = 0)");
                 interp.stack_mut().push(WofValue::nil());
             }
         }
@@ -224,18 +224,18 @@ pub fn register(interp: &mut Interpreter) {
         let b1 = interp.stack_mut().pop()?.as_double()?;
         let a1 = interp.stack_mut().pop()?.as_double()?;
 
-        println!("[solver] Solving system:");
-        println!("  {}x + {}y = {}", a1, b1, c1);
-        println!("  {}x + {}y = {}", a2, b2, c2);
+        writeln!(interp.out(), "[solver] Solving system:");
+        writeln!(interp.out(), "  {}x + {}y = {}", a1, b1, c1);
+        writeln!(interp.out(), "  {}x + {}y = {}", a2, b2, c2);
 
         match solve_linear_2x2(a1, b1, c1, a2, b2, c2) {
             Some((x, y)) => {
-                println!("[solver] Solution: x = {}, y = {}", x, y);
+                writeln!(interp.out(), "[solver] Solution: x = {}, y = {}", x, y);
                 interp.stack_mut().push(WofValue::double(x));
                 interp.stack_mut().push(WofValue::double(y));
             }
             None => {
-                println!("[solver] No unique solution (parallel or coincident lines)");
+                writeln!(interp.out(), "[solver] No unique solution (parallel or coincident lines)");
                 interp.stack_mut().push(WofValue::nil());
             }
         }
@@ -252,7 +252,7 @@ pub fn register(interp: &mut Interpreter) {
         let n = interp.stack_mut().pop()?.as_double()?;
 
         if n < 0.0 {
-            println!("[solver] Cannot compute square root of negative number");
+            writeln!(interp.out(), "[solver] Cannot compute square root of negative number");
             interp.stack_mut().push(WofValue::nil());
             return Ok(());
         }
@@ -268,11 +268,11 @@ pub fn register(interp: &mut Interpreter) {
 
         match newton_raphson(f, f_prime, n / 2.0, 100, 1e-15) {
             Some(root) => {
-                println!("[solver] √{} ≈ {}", n, root);
+                writeln!(interp.out(), "[solver] √{} ≈ {}", n, root);
                 interp.stack_mut().push(WofValue::double(root));
             }
             None => {
-                println!("[solver] Newton-Raphson failed to converge");
+                writeln!(interp.out(), "[solver] Newton-Raphson failed to converge");
                 interp.stack_mut().push(WofValue::double(n.sqrt()));
             }
         }
@@ -292,11 +292,11 @@ pub fn register(interp: &mut Interpreter) {
 
         match newton_raphson(f, f_prime, x0, 100, 1e-15) {
             Some(root) => {
-                println!("[solver] ∛{} ≈ {}", n, root);
+                writeln!(interp.out(), "[solver] ∛{} ≈ {}", n, root);
                 interp.stack_mut().push(WofValue::double(root));
             }
             None => {
-                println!("[solver] Newton-Raphson failed to converge");
+                writeln!(interp.out(), "[solver] Newton-Raphson failed to converge");
                 interp.stack_mut().push(WofValue::double(n.cbrt()));
             }
         }
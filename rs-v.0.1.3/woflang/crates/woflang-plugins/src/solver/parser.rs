@@ -0,0 +1,318 @@
+//! Pratt parser for infix equations.
+//!
+//! Parses strings like `"3(x-2)/4 + 1 = x"` into the symbolic [`Expr`]
+//! tree used by the rest of the solver.
+//!
+//! ## Grammar
+//!
+//! ```text
+//! equation := expr '=' expr
+//! expr     := prefix (infix expr)*        # precedence climbing
+//! prefix   := number | ident | ident '(' expr ')' | '(' expr ')' | '-' expr | '+' expr
+//! infix    := '+' | '-' | '*' | '/' | '^' | <implicit multiplication>
+//! ```
+//!
+//! Implicit multiplication binds like `*`, so `2x`, `3(x-2)` and
+//! `(x+1)(x-1)` work as expected. `^` is right-associative and binds
+//! tighter than unary minus, so `-x^2` is `-(x^2)`.
+//!
+//! Errors carry a [`Span`] whose column is the 1-based character
+//! position inside the equation string.
+
+use super::symbolic::Expr;
+use woflang_core::{Span, WofError};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// LEXER
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Num(f64),
+    Ident(String),
+    Op(char),
+    LParen,
+    RParen,
+    Eq,
+    End,
+}
+
+#[derive(Debug, Clone)]
+struct Lexeme {
+    tok: Tok,
+    /// Character index of the first character.
+    pos: usize,
+    /// Length in characters.
+    len: usize,
+}
+
+/// A parse failure with its position inside the equation string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// What went wrong.
+    pub message: String,
+    /// Character index of the offending input.
+    pub pos: usize,
+    /// Length of the offending input in characters.
+    pub len: usize,
+}
+
+impl ParseError {
+    fn new(message: impl Into<String>, pos: usize, len: usize) -> Self {
+        Self {
+            message: message.into(),
+            pos,
+            len: len.max(1),
+        }
+    }
+
+    /// Convert to a [`WofError::Parse`], rendering a caret under the
+    /// offending part of `source`.
+    #[must_use]
+    pub fn into_wof_error(self, source: &str) -> WofError {
+        let caret = format!("{}{}", " ".repeat(self.pos), "^".repeat(self.len));
+        let byte_at = |chars: usize| {
+            source
+                .char_indices()
+                .nth(chars)
+                .map_or(source.len(), |(i, _)| i)
+        };
+        let offset = byte_at(self.pos);
+        let length = byte_at(self.pos + self.len).saturating_sub(offset).max(1);
+        let span = Span::with_length(1, self.pos as u32 + 1, offset as u32, length as u32);
+        WofError::parse(
+            format!("{}\n    {source}\n    {caret}", self.message),
+            span,
+        )
+    }
+}
+
+fn lex(src: &str) -> Result<Vec<Lexeme>, ParseError> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let tok = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let value = text
+                    .parse()
+                    .map_err(|_| ParseError::new(format!("malformed number '{text}'"), start, i - start))?;
+                out.push(Lexeme { tok: Tok::Num(value), pos: start, len: i - start });
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                out.push(Lexeme { tok: Tok::Ident(text), pos: start, len: i - start });
+                continue;
+            }
+            '+' | '-' | '/' | '^' => Tok::Op(c),
+            '*' | '×' | '·' => Tok::Op('*'),
+            '÷' => Tok::Op('/'),
+            '(' => Tok::LParen,
+            ')' => Tok::RParen,
+            '=' => Tok::Eq,
+            other => {
+                return Err(ParseError::new(format!("unexpected character '{other}'"), start, 1));
+            }
+        };
+        i += 1;
+        out.push(Lexeme { tok, pos: start, len: 1 });
+    }
+
+    out.push(Lexeme { tok: Tok::End, pos: chars.len(), len: 1 });
+    Ok(out)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// PARSER
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Binding power of `+`/`-`.
+const BP_SUM: u8 = 10;
+/// Binding power of `*`, `/` and implicit multiplication.
+const BP_PRODUCT: u8 = 20;
+/// Binding power of unary minus.
+const BP_PREFIX: u8 = 30;
+/// Binding power of `^`.
+const BP_POWER: u8 = 40;
+
+struct Parser {
+    lexemes: Vec<Lexeme>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Lexeme {
+        // The lexer always terminates the stream with `End`.
+        &self.lexemes[self.pos.min(self.lexemes.len() - 1)]
+    }
+
+    fn next(&mut self) -> Lexeme {
+        let lexeme = self.peek().clone();
+        if lexeme.tok != Tok::End {
+            self.pos += 1;
+        }
+        lexeme
+    }
+
+    fn expect(&mut self, tok: &Tok, what: &str) -> Result<(), ParseError> {
+        let lexeme = self.next();
+        if &lexeme.tok == tok {
+            Ok(())
+        } else {
+            Err(ParseError::new(format!("expected {what}"), lexeme.pos, lexeme.len))
+        }
+    }
+
+    fn parse_expr(&mut self, min_bp: u8) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_prefix()?;
+
+        loop {
+            let lexeme = self.peek().clone();
+            let (bp, right_bp, implicit) = match lexeme.tok {
+                Tok::Op('+' | '-') => (BP_SUM, BP_SUM + 1, false),
+                Tok::Op('*' | '/') => (BP_PRODUCT, BP_PRODUCT + 1, false),
+                Tok::Op('^') => (BP_POWER, BP_POWER, false),
+                Tok::Num(_) | Tok::Ident(_) | Tok::LParen => (BP_PRODUCT, BP_PRODUCT + 1, true),
+                _ => break,
+            };
+            if bp < min_bp {
+                break;
+            }
+            if !implicit {
+                self.next();
+            }
+            let rhs = self.parse_expr(right_bp)?;
+            lhs = match lexeme.tok {
+                Tok::Op('+') => Expr::Add(Box::new(lhs), Box::new(rhs)),
+                Tok::Op('-') => Expr::Sub(Box::new(lhs), Box::new(rhs)),
+                Tok::Op('/') => Expr::Div(Box::new(lhs), Box::new(rhs)),
+                Tok::Op('^') => Expr::Pow(Box::new(lhs), Box::new(rhs)),
+                _ => Expr::Mul(Box::new(lhs), Box::new(rhs)),
+            };
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_prefix(&mut self) -> Result<Expr, ParseError> {
+        let lexeme = self.next();
+        match lexeme.tok {
+            Tok::Num(v) => Ok(Expr::Const(v)),
+            Tok::Ident(name) => {
+                if self.peek().tok == Tok::LParen {
+                    if let Some(func) = function(&name) {
+                        self.next();
+                        let arg = self.parse_expr(0)?;
+                        self.expect(&Tok::RParen, "')' to close function call")?;
+                        return Ok(func(Box::new(arg)));
+                    }
+                }
+                Ok(Expr::Var(name))
+            }
+            Tok::LParen => {
+                let inner = self.parse_expr(0)?;
+                self.expect(&Tok::RParen, "')'")?;
+                Ok(inner)
+            }
+            Tok::Op('-') => Ok(Expr::Neg(Box::new(self.parse_expr(BP_PREFIX)?))),
+            Tok::Op('+') => self.parse_expr(BP_PREFIX),
+            Tok::End => Err(ParseError::new("unexpected end of input", lexeme.pos, 1)),
+            _ => Err(ParseError::new("expected a number, variable or '('", lexeme.pos, lexeme.len)),
+        }
+    }
+}
+
+/// Map a function name to its expression constructor.
+fn function(name: &str) -> Option<fn(Box<Expr>) -> Expr> {
+    match name {
+        "sin" => Some(Expr::Sin),
+        "cos" => Some(Expr::Cos),
+        "ln" | "log" => Some(Expr::Ln),
+        "exp" => Some(Expr::Exp),
+        _ => None,
+    }
+}
+
+/// Parse a single infix expression (no `=`).
+pub fn parse_expr(src: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser { lexemes: lex(src)?, pos: 0 };
+    let expr = parser.parse_expr(0)?;
+    let rest = parser.next();
+    if rest.tok != Tok::End {
+        return Err(ParseError::new("unexpected input after expression", rest.pos, rest.len));
+    }
+    Ok(expr)
+}
+
+/// Parse an equation `lhs = rhs` into its two sides.
+pub fn parse_equation(src: &str) -> Result<(Expr, Expr), ParseError> {
+    let mut parser = Parser { lexemes: lex(src)?, pos: 0 };
+    let lhs = parser.parse_expr(0)?;
+    let eq = parser.next();
+    if eq.tok != Tok::Eq {
+        return Err(ParseError::new("expected '=' in equation", eq.pos, eq.len));
+    }
+    let rhs = parser.parse_expr(0)?;
+    let rest = parser.next();
+    if rest.tok != Tok::End {
+        return Err(ParseError::new("unexpected input after equation", rest.pos, rest.len));
+    }
+    Ok((lhs, rhs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn show(src: &str) -> String {
+        parse_expr(src).unwrap().to_string()
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(show("1 + 2 * 3"), "(1 + (2 * 3))");
+        assert_eq!(show("2^3^2"), "(2^(3^2))");
+        assert_eq!(show("-x^2"), "(-(x^2))");
+        assert_eq!(show("8 / 4 / 2"), "((8 / 4) / 2)");
+    }
+
+    #[test]
+    fn implicit_multiplication() {
+        assert_eq!(show("3(x-2)"), "(3 * (x - 2))");
+        assert_eq!(show("2x"), "(2 * x)");
+        assert_eq!(show("(x+1)(x-1)"), "((x + 1) * (x - 1))");
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(show("sin(x) + 1"), "(sin(x) + 1)");
+    }
+
+    #[test]
+    fn errors_point_at_offending_input() {
+        let err = parse_equation("3(x-2/4 + 1 = x").unwrap_err();
+        assert_eq!(err.pos, 12);
+        assert!(err.message.contains("')'"));
+
+        let err = parse_equation("2x + 3").unwrap_err();
+        assert!(err.message.contains("'='"));
+
+        let err = parse_expr("2 $ 3").unwrap_err();
+        assert_eq!(err.pos, 2);
+    }
+}
@@ -1,272 +1,260 @@
-//! Pattern-based equation solver.
+//! Equation solver for infix equation strings.
 //!
-//! Solves equations given as strings using pattern matching.
+//! Equations are parsed by the Pratt parser in [`super::parser`] into a
+//! symbolic [`Expr`], then reduced to a rational function
+//! `N(x) / D(x) = 0` in the single unknown. The numerator is solved in
+//! closed form when linear or quadratic and by Durand–Kerner iteration
+//! above that, and roots that make the denominator vanish are discarded
+//! as extraneous.
 //!
 //! ## Supported Forms
 //!
-//! - Linear: `"2x + 3 = 7"`, `"x - 5 = 10"`, `"-3x = 9"`
-//! - Quadratic: `"x^2 - 5x + 6 = 0"`, `"2x^2 + 3x - 2 = 0"`
+//! - Linear: `"2x + 3 = 7"`, `"3(x-2)/4 + 1 = x"`, `"-3x = 9"`
+//! - Quadratic: `"x^2 - 5x + 6 = 0"`, `"(x+1)(x-2) = 4"`
+//! - Higher degree: `"x^3 - 6x^2 + 11x = 6"`, `"(x^2-1)(x^2-4) = 0"`
+//!   (real roots only; none at all is reported as no solution)
+//! - Rational: `"1/x + 1 = 3"`, `"(x+2)/(x-1) = 4"`
+//!
+//! Any variable name works (`"2t = 8"` → `"t = 4"`). Syntax errors are
+//! reported as parse errors pointing into the equation.
 //!
 //! ## Operations
 //!
 //! - `pattern_solve` - Parse and solve an equation string
+//! - `quick_solve` - Solve, pushing a number when there is one real root
 
-use woflang_core::{WofValue, InterpreterContext};
+use super::parser::parse_equation;
+use super::poly::Poly;
+use super::symbolic::Expr;
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
 use woflang_runtime::Interpreter;
 
 // ═══════════════════════════════════════════════════════════════════════════
-// PATTERN MATCHING SOLVER
+// RATIONAL FUNCTION REDUCTION
 // ═══════════════════════════════════════════════════════════════════════════
 
-/// Result of linear equation solve.
-struct LinearResult {
-    ok: bool,
-    x: f64,
+/// A ratio of polynomials `num / den`.
+#[derive(Debug, Clone)]
+pub(super) struct Rational {
+    pub(super) num: Poly,
+    pub(super) den: Poly,
 }
 
-/// Result of quadratic equation solve.
-struct QuadraticResult {
-    ok: bool,
-    x1: f64,
-    x2: f64,
-    complex_roots: bool,
-}
+impl Rational {
+    fn poly(p: Poly) -> Self {
+        Self { num: p, den: Poly::constant(1.0) }
+    }
 
-/// Solve linear equation: ax + b = c
-fn solve_linear(a: f64, b: f64, c: f64) -> LinearResult {
-    if a.abs() < 1e-12 {
-        LinearResult { ok: false, x: 0.0 }
-    } else {
-        LinearResult {
-            ok: true,
-            x: (c - b) / a,
+    fn add(&self, other: &Self) -> Self {
+        Self {
+            num: self.num.mul(&other.den).add(&other.num.mul(&self.den)),
+            den: self.den.mul(&other.den),
         }
     }
-}
 
-/// Solve quadratic equation: ax² + bx + c = 0
-fn solve_quadratic(a: f64, b: f64, c: f64) -> QuadraticResult {
-    if a.abs() < 1e-12 {
-        // Degenerates to linear
-        let lin = solve_linear(b, c, 0.0);
-        return QuadraticResult {
-            ok: lin.ok,
-            x1: lin.x,
-            x2: lin.x,
-            complex_roots: false,
-        };
+    fn neg(&self) -> Self {
+        Self { num: self.num.scale(-1.0), den: self.den.clone() }
     }
 
-    let disc = b * b - 4.0 * a * c;
+    fn mul(&self, other: &Self) -> Self {
+        Self { num: self.num.mul(&other.num), den: self.den.mul(&other.den) }
+    }
 
-    if disc < 0.0 {
-        let real = -b / (2.0 * a);
-        let imag = (-disc).sqrt() / (2.0 * a);
-        QuadraticResult {
-            ok: true,
-            x1: real,
-            x2: imag, // Store imag in x2 for complex case
-            complex_roots: true,
-        }
-    } else {
-        let sqrt_disc = disc.sqrt();
-        QuadraticResult {
-            ok: true,
-            x1: (-b + sqrt_disc) / (2.0 * a),
-            x2: (-b - sqrt_disc) / (2.0 * a),
-            complex_roots: false,
+    fn recip(&self) -> std::result::Result<Self, String> {
+        if self.num.is_zero() {
+            return Err("division by zero".into());
         }
+        Ok(Self { num: self.den.clone(), den: self.num.clone() })
     }
-}
 
-/// Parse a coefficient string like "2", "-3", "", "+", "-"
-fn parse_coeff(s: &str, allow_empty: bool) -> Option<f64> {
-    let trimmed: String = s.chars().filter(|c| !c.is_whitespace()).collect();
-    
-    if trimmed.is_empty() {
-        return if allow_empty { Some(1.0) } else { None };
+    fn powi(&self, n: i32) -> std::result::Result<Self, String> {
+        let base = if n < 0 { self.recip()? } else { self.clone() };
+        let mut result = Self::poly(Poly::constant(1.0));
+        for _ in 0..n.unsigned_abs() {
+            result = result.mul(&base);
+        }
+        Ok(result)
     }
-    
-    if trimmed == "+" {
-        return Some(1.0);
+
+    /// Cancel common factors of numerator and denominator.
+    fn reduced(self) -> Self {
+        if self.num.is_zero() {
+            return Self::poly(Poly::constant(0.0));
+        }
+        let g = self.num.gcd(&self.den);
+        if g.degree() == 0 {
+            return self;
+        }
+        Self { num: self.num.div_rem(&g).0, den: self.den.div_rem(&g).0 }
     }
-    
-    if trimmed == "-" {
-        return Some(-1.0);
+
+    fn as_constant(&self) -> Option<f64> {
+        (self.num.degree() == 0 && self.den.degree() == 0).then(|| self.num.0[0] / self.den.0[0])
     }
-    
-    trimmed.parse().ok()
 }
 
-/// Try to match and solve linear equation: ax + b = c
-/// Patterns:
-/// - "2x + 3 = 7"
-/// - "x + 1 = 5"
-/// - "-3x - 9 = 0"
-fn try_linear_pattern(eq: &str) -> Option<String> {
-    // Remove spaces and normalize
-    let eq = eq.replace(" ", "");
-    
-    // Split by '='
-    let parts: Vec<&str> = eq.split('=').collect();
-    if parts.len() != 2 {
-        return None;
-    }
-    
-    let lhs = parts[0];
-    let rhs = parts[1];
-    
-    // Parse RHS (should be a number)
-    let c: f64 = rhs.parse().ok()?;
-    
-    // Parse LHS: look for 'x' terms and constants
-    // Pattern: [coeff]x [+/-] [const]
-    
-    let mut a = 0.0;
-    let mut b = 0.0;
-    
-    // Simple state machine parser
-    let mut i = 0;
-    let chars: Vec<char> = lhs.chars().collect();
-    
-    while i < chars.len() {
-        let mut sign = 1.0;
-        
-        // Check for leading sign
-        if chars[i] == '+' {
-            sign = 1.0;
-            i += 1;
-        } else if chars[i] == '-' {
-            sign = -1.0;
-            i += 1;
+/// Reduce an expression to a rational function of `var`.
+pub(super) fn to_rational(expr: &Expr, var: &str) -> std::result::Result<Rational, String> {
+    Ok(match expr {
+        Expr::Const(c) => Rational::poly(Poly::constant(*c)),
+        Expr::Var(name) if name == var => Rational::poly(Poly::x()),
+        Expr::Var(name) => return Err(format!("unknown symbol '{name}'")),
+        Expr::Add(l, r) => to_rational(l, var)?.add(&to_rational(r, var)?),
+        Expr::Sub(l, r) => to_rational(l, var)?.add(&to_rational(r, var)?.neg()),
+        Expr::Mul(l, r) => to_rational(l, var)?.mul(&to_rational(r, var)?),
+        Expr::Div(l, r) => to_rational(l, var)?.mul(&to_rational(r, var)?.recip()?),
+        Expr::Neg(e) => to_rational(e, var)?.neg(),
+        Expr::Pow(b, e) => {
+            let exponent = to_rational(e, var)?
+                .as_constant()
+                .ok_or("exponents containing the unknown are not supported")?;
+            if exponent.fract() != 0.0 || exponent.abs() > 16.0 {
+                return Err(format!("exponent {exponent} must be a small integer"));
+            }
+            to_rational(b, var)?.powi(exponent as i32)?
         }
-        
-        // Collect coefficient
-        let mut coeff_str = String::new();
-        while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
-            coeff_str.push(chars[i]);
-            i += 1;
+        Expr::Sin(_) | Expr::Cos(_) | Expr::Ln(_) | Expr::Exp(_) => {
+            return Err("transcendental functions are not supported".into());
         }
-        
-        // Check if this is an x term
-        if i < chars.len() && chars[i] == 'x' {
-            let coeff = if coeff_str.is_empty() {
-                1.0
-            } else {
-                coeff_str.parse().unwrap_or(1.0)
-            };
-            a += sign * coeff;
-            i += 1;
-        } else if !coeff_str.is_empty() {
-            // Constant term
-            let val: f64 = coeff_str.parse().ok()?;
-            b += sign * val;
+    })
+}
+
+/// Collect distinct variable names in order of first appearance.
+fn variables(expr: &Expr, out: &mut Vec<String>) {
+    match expr {
+        Expr::Const(_) => {}
+        Expr::Var(name) => {
+            if !out.contains(name) {
+                out.push(name.clone());
+            }
+        }
+        Expr::Add(l, r) | Expr::Sub(l, r) | Expr::Mul(l, r) | Expr::Div(l, r) | Expr::Pow(l, r) => {
+            variables(l, out);
+            variables(r, out);
+        }
+        Expr::Neg(e) | Expr::Sin(e) | Expr::Cos(e) | Expr::Ln(e) | Expr::Exp(e) => {
+            variables(e, out);
         }
     }
-    
-    let sol = solve_linear(a, b, c);
-    if sol.ok {
-        Some(format!("x = {:.6}", sol.x))
-    } else {
-        None
-    }
 }
 
-/// Try to match and solve quadratic equation: ax² + bx + c = 0
-/// Patterns:
-/// - "x^2 - 5x + 6 = 0"
-/// - "2x^2 + 3x - 2 = 0"
-fn try_quadratic_pattern(eq: &str) -> Option<String> {
-    let eq = eq.replace(" ", "");
-    
-    // Must end with "= 0" (or "=0")
-    if !eq.ends_with("=0") {
-        return None;
+// ═══════════════════════════════════════════════════════════════════════════
+// SOLVER
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// The solution set of an equation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Solution {
+    /// Distinct real roots.
+    Real(Vec<f64>),
+    /// A complex-conjugate pair `re ± im·i`.
+    Complex(f64, f64),
+    /// The equation holds for every admissible value.
+    Identity,
+    /// No value satisfies the equation.
+    Empty,
+}
+
+/// A solved equation: the unknown's name and its solution set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solved {
+    /// Name of the unknown (`x` if the equation has none).
+    pub var: String,
+    /// The solution set.
+    pub solution: Solution,
+}
+
+impl Solved {
+    /// Render in the solver's `"x = 2.000000"` style.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let v = &self.var;
+        match &self.solution {
+            Solution::Real(roots) => roots
+                .iter()
+                .map(|r| format!("{v} = {r:.6}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+            Solution::Complex(re, im) => format!("{v} = {re} ± {im}i"),
+            Solution::Identity => format!("all {v} (identity)"),
+            Solution::Empty => "no solution".to_string(),
+        }
     }
-    
-    // Must contain "x^2"
-    if !eq.contains("x^2") {
-        return None;
+}
+
+/// Solve `lhs = rhs` for its single unknown.
+pub fn solve(eq: &str) -> Result<Solved> {
+    let (lhs, rhs) = parse_equation(eq).map_err(|e| e.into_wof_error(eq))?;
+
+    let mut vars = Vec::new();
+    variables(&lhs, &mut vars);
+    variables(&rhs, &mut vars);
+    if vars.len() > 1 {
+        return Err(WofError::InvalidArgument(format!(
+            "pattern_solve: expected one unknown, found {}",
+            vars.join(", ")
+        )));
     }
-    
-    let lhs = &eq[..eq.len() - 2]; // Remove "=0"
-    
-    let mut a = 0.0;
-    let mut b = 0.0;
-    let mut c = 0.0;
-    
-    // Simple parser for ax^2 + bx + c
-    let chars: Vec<char> = lhs.chars().collect();
-    let mut i = 0;
-    
-    while i < chars.len() {
-        let mut sign = 1.0;
-        
-        // Check for leading sign
-        if chars[i] == '+' {
-            sign = 1.0;
-            i += 1;
-        } else if chars[i] == '-' {
-            sign = -1.0;
-            i += 1;
+    let var = vars.pop().unwrap_or_else(|| "x".to_string());
+
+    let diff = Expr::Sub(Box::new(lhs), Box::new(rhs));
+    let rational = to_rational(&diff, &var)
+        .map_err(|e| WofError::InvalidArgument(format!("pattern_solve: {e}")))?;
+    // Extraneous roots are judged against the unreduced denominator.
+    let den = rational.den.clone();
+    let admissible = |x: f64| den.eval(x).abs() > 1e-9;
+    let reduced = rational.reduced();
+    let num = &reduced.num;
+
+    let solution = match num.degree() {
+        0 if num.is_zero() => Solution::Identity,
+        0 => Solution::Empty,
+        1 => {
+            let x = -num.0[0] / num.0[1];
+            if admissible(x) {
+                Solution::Real(vec![x])
+            } else {
+                Solution::Empty
+            }
         }
-        
-        // Collect coefficient
-        let mut coeff_str = String::new();
-        while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
-            coeff_str.push(chars[i]);
-            i += 1;
+        2 => {
+            let (c, b, a) = (num.0[0], num.0[1], num.0[2]);
+            let disc = b * b - 4.0 * a * c;
+            if disc < 0.0 {
+                Solution::Complex(-b / (2.0 * a), (-disc).sqrt() / (2.0 * a))
+            } else {
+                let sqrt_disc = disc.sqrt();
+                let x1 = (-b + sqrt_disc) / (2.0 * a);
+                let x2 = (-b - sqrt_disc) / (2.0 * a);
+                let mut roots = vec![x1];
+                if (x1 - x2).abs() > 1e-9 {
+                    roots.push(x2);
+                }
+                roots.retain(|&x| admissible(x));
+                if roots.is_empty() {
+                    Solution::Empty
+                } else {
+                    Solution::Real(roots)
+                }
+            }
         }
-        
-        // Check term type
-        if i + 2 < chars.len() && &lhs[i..i+3] == "x^2" {
-            // x² term
-            let coeff = if coeff_str.is_empty() { 1.0 } else { coeff_str.parse().unwrap_or(1.0) };
-            a += sign * coeff;
-            i += 3;
-        } else if i < chars.len() && chars[i] == 'x' {
-            // x term
-            let coeff = if coeff_str.is_empty() { 1.0 } else { coeff_str.parse().unwrap_or(1.0) };
-            b += sign * coeff;
-            i += 1;
-        } else if !coeff_str.is_empty() {
-            // Constant term
-            let val: f64 = coeff_str.parse().ok()?;
-            c += sign * val;
+        _ => {
+            let roots: Vec<f64> =
+                num.roots().into_iter().filter(|&(x, im)| im == 0.0 && admissible(x)).map(|(x, _)| x).collect();
+            if roots.is_empty() {
+                Solution::Empty
+            } else {
+                Solution::Real(roots)
+            }
         }
-    }
-    
-    let sol = solve_quadratic(a, b, c);
-    if !sol.ok {
-        return None;
-    }
-    
-    if sol.complex_roots {
-        Some(format!("x = {} ± {}i", sol.x1, sol.x2))
-    } else if (sol.x1 - sol.x2).abs() < 1e-9 {
-        Some(format!("x = {:.6}", sol.x1))
-    } else {
-        Some(format!("x = {:.6}, x = {:.6}", sol.x1, sol.x2))
-    }
+    };
+
+    Ok(Solved { var, solution })
 }
 
-/// Solve an equation given as a string.
-pub fn pattern_solve(eq: &str) -> String {
-    // Try linear first
-    if let Some(solution) = try_linear_pattern(eq) {
-        return solution;
-    }
-    
-    // Try quadratic
-    if let Some(solution) = try_quadratic_pattern(eq) {
-        return solution;
-    }
-    
-    format!(
-        "pattern_solve: no recognised pattern in equation '{}'. \
-         Supported forms include ax + b = c and ax^2 + bx + c = 0.",
-        eq
-    )
+/// Solve an equation given as a string, rendering the solution set.
+pub fn pattern_solve(eq: &str) -> Result<String> {
+    solve(eq).map(|s| s.render())
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -279,60 +267,88 @@ pub fn register(interp: &mut Interpreter) {
     // Stack: "equation" → "solution"
     interp.register("pattern_solve", |interp| {
         let eq = interp.stack_mut().pop()?.as_string()?;
-        let solution = pattern_solve(&eq);
+        let solution = pattern_solve(&eq)?;
         
-        println!("[pattern_solve] Input: {}", eq);
-        println!("[pattern_solve] Result: {}", solution);
+        writeln!(interp.out(), "[pattern_solve] Input: {}", eq);
+        writeln!(interp.out(), "[pattern_solve] Result: {}", solution);
         
         interp.stack_mut().push(WofValue::string(solution));
         Ok(())
     });
 
-    // Quick solve for "ax + b = c" format
-    // Stack: "equation" → x
+    // Quick solve: a single real root is pushed as a number, any other
+    // solution set as its rendered string, and nil if solving fails.
+    // Stack: "equation" → x | "solution" | nil
     interp.register("quick_solve", |interp| {
         let eq = interp.stack_mut().pop()?.as_string()?;
-        
-        if let Some(solution) = try_linear_pattern(&eq) {
-            // Extract the numeric value
-            if let Some(x_str) = solution.strip_prefix("x = ") {
-                if let Ok(x) = x_str.parse::<f64>() {
-                    interp.stack_mut().push(WofValue::double(x));
-                    return Ok(());
-                }
+
+        match solve(&eq) {
+            Ok(Solved { solution: Solution::Real(roots), .. }) if roots.len() == 1 => {
+                interp.stack_mut().push(WofValue::double(roots[0]));
+            }
+            Ok(solved) => interp.stack_mut().push(WofValue::string(solved.render())),
+            Err(e) => {
+                writeln!(interp.out(), "[quick_solve] Could not solve: {}", e);
+                interp.stack_mut().push(WofValue::nil());
             }
         }
-        
-        if let Some(solution) = try_quadratic_pattern(&eq) {
-            interp.stack_mut().push(WofValue::string(solution));
-            return Ok(());
-        }
-        
-        println!("[quick_solve] Could not solve: {}", eq);
-        interp.stack_mut().push(WofValue::nil());
         Ok(())
     });
+}
 
-    // Evaluate polynomial at a point
-    // Stack: a0 a1 ... an n x → f(x)
-    // Evaluates a0 + a1*x + a2*x² + ... + an*x^n
-    interp.register("poly_eval", |interp| {
-        let x = interp.stack_mut().pop()?.as_double()?;
-        let n = interp.stack_mut().pop()?.as_integer()? as usize;
-        
-        let mut coeffs = Vec::with_capacity(n + 1);
-        for _ in 0..=n {
-            coeffs.push(interp.stack_mut().pop()?.as_double()?);
-        }
-        coeffs.reverse();
-        
-        // Horner's method
-        let mut result = coeffs[n];
-        for i in (0..n).rev() {
-            result = result * x + coeffs[i];
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roots(eq: &str) -> Vec<f64> {
+        match solve(eq).unwrap().solution {
+            Solution::Real(r) => r,
+            other => panic!("expected real roots, got {other:?}"),
         }
-        
-        interp.stack_mut().push(WofValue::double(result));
-        Ok(())
-    });
+    }
+
+    #[test]
+    fn linear_with_parentheses_and_division() {
+        let r = roots("3(x-2)/4 + 1 = x");
+        assert_eq!(r.len(), 1);
+        assert!((r[0] + 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn quadratic_and_rational() {
+        let mut r = roots("x^2 - 5x + 6 = 0");
+        r.sort_by(f64::total_cmp);
+        assert!((r[0] - 2.0).abs() < 1e-9 && (r[1] - 3.0).abs() < 1e-9);
+
+        let r = roots("(x+2)/(x-1) = 4");
+        assert!((r[0] - 2.0).abs() < 1e-9);
+
+        // x^2 = 1 after clearing denominators, but x = 1 is excluded.
+        let r = roots("x^2/(x-1) = 1/(x-1)");
+        assert_eq!(r.len(), 1);
+        assert!((r[0] + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cubic_and_quartic() {
+        let r = roots("x^3 - 6x^2 + 11x = 6");
+        assert!(r.iter().zip([1.0, 2.0, 3.0]).all(|(x, want)| (x - want).abs() < 1e-9), "{r:?}");
+        assert_eq!(roots("(x^2-1)(x^2-4) = 0").len(), 4);
+        // x³ = x over x: the root 0 is extraneous.
+        assert_eq!(roots("x^3/x = 1/x * x").len(), 2);
+        assert_eq!(solve("x^4 + 1 = 0").unwrap().solution, Solution::Empty);
+    }
+
+    #[test]
+    fn named_unknown_and_degenerate_cases() {
+        assert_eq!(pattern_solve("2t = 8").unwrap(), "t = 4.000000");
+        assert_eq!(solve("x + 1 = x + 1").unwrap().solution, Solution::Identity);
+        assert_eq!(solve("x + 1 = x").unwrap().solution, Solution::Empty);
+    }
+
+    #[test]
+    fn parse_errors_carry_spans() {
+        let err = solve("2x + = 7").unwrap_err();
+        assert!(matches!(err, WofError::Parse { span, .. } if span.column() == 6));
+    }
 }
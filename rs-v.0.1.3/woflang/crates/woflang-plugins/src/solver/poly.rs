@@ -0,0 +1,522 @@
+//! Polynomial values with arithmetic and root finding.
+//!
+//! A polynomial is a `poly` record holding its coefficients as a list,
+//! constant term first, so `x² - 5x + 6` is `poly{coeffs: [6.0, -5.0, 1.0]}`.
+//! The same [`Poly`] type backs the equation solver, which reduces
+//! equations to polynomials before solving them.
+//!
+//! ## Operations
+//!
+//! - `poly` - Build from coefficients (c0 .. cn-1 n → P)
+//! - `poly_coeffs` - Unpack coefficients (P → c0 .. cn-1 n)
+//! - `poly_eval` - Evaluate (P x → y), or the older (a0 .. an n x → y)
+//! - `poly_add`, `poly_mul` - Sum and product (P Q → R)
+//! - `poly_deriv` - Derivative (P → P')
+//! - `poly_roots` - Distinct complex roots (P → [[re, im] ...])
+//! - `expr_to_poly` - Parse an infix polynomial ("expr" "x" → P)
+//! - `poly_to_expr` - Render as infix (P "x" → "expr")
+//!
+//! ```text
+//! "x^3 - 6x^2 + 11x - 6" "x" expr_to_poly poly_roots   # [[1, 0], [2, 0], [3, 0]]
+//! -1 0 1 3 poly poly_deriv "x" poly_to_expr            # "2*x"
+//! ```
+//!
+//! Roots come from the Durand–Kerner iteration on the square-free part
+//! of the polynomial, so a repeated root is reported once and still
+//! converges to full precision.
+
+use super::parser::parse_expr;
+use super::pattern::to_rational;
+use super::symbolic::Expr;
+use woflang_core::{InterpreterContext, RecordType, Result, WofError, WofRecord, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Coefficients below this magnitude are treated as zero.
+const EPS: f64 = 1e-12;
+
+/// Imaginary parts this small (relative to the root) are rounded to zero.
+const REAL_TOL: f64 = 1e-9;
+
+/// Iteration cap for Durand–Kerner.
+const MAX_ITER: usize = 1000;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// POLYNOMIALS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A polynomial in one unknown, lowest degree first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Poly(pub(super) Vec<f64>);
+
+impl Poly {
+    /// A polynomial from coefficients, constant term first.
+    pub fn new(coeffs: Vec<f64>) -> Self {
+        if coeffs.is_empty() {
+            return Self::constant(0.0);
+        }
+        Self(coeffs).trimmed()
+    }
+
+    pub(super) fn constant(c: f64) -> Self {
+        Self(vec![c])
+    }
+
+    pub(super) fn x() -> Self {
+        Self(vec![0.0, 1.0])
+    }
+
+    /// Coefficients, constant term first.
+    pub fn coeffs(&self) -> &[f64] {
+        &self.0
+    }
+
+    fn trimmed(mut self) -> Self {
+        while self.0.len() > 1 && self.0.last().is_some_and(|c| c.abs() < EPS) {
+            self.0.pop();
+        }
+        self
+    }
+
+    pub(super) fn degree(&self) -> usize {
+        self.0.len().saturating_sub(1)
+    }
+
+    pub(super) fn is_zero(&self) -> bool {
+        self.0.iter().all(|c| c.abs() < EPS)
+    }
+
+    /// `self + other`.
+    pub fn add(&self, other: &Self) -> Self {
+        let n = self.0.len().max(other.0.len());
+        let coeffs = (0..n)
+            .map(|i| self.0.get(i).unwrap_or(&0.0) + other.0.get(i).unwrap_or(&0.0))
+            .collect();
+        Self(coeffs).trimmed()
+    }
+
+    pub(super) fn scale(&self, k: f64) -> Self {
+        Self(self.0.iter().map(|c| c * k).collect()).trimmed()
+    }
+
+    /// `self · other`.
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut coeffs = vec![0.0; self.0.len() + other.0.len() - 1];
+        for (i, a) in self.0.iter().enumerate() {
+            for (j, b) in other.0.iter().enumerate() {
+                coeffs[i + j] += a * b;
+            }
+        }
+        Self(coeffs).trimmed()
+    }
+
+    /// The derivative.
+    pub fn deriv(&self) -> Self {
+        if self.0.len() <= 1 {
+            return Self::constant(0.0);
+        }
+        Self(self.0.iter().enumerate().skip(1).map(|(i, c)| i as f64 * c).collect()).trimmed()
+    }
+
+    /// Polynomial long division, returning `(quotient, remainder)`.
+    pub(super) fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let mut rem = self.0.clone();
+        let d = divisor.degree();
+        let lead = divisor.0[d];
+        if self.degree() < d {
+            return (Self::constant(0.0), self.clone());
+        }
+        let mut quot = vec![0.0; self.degree() - d + 1];
+        for i in (0..quot.len()).rev() {
+            let q = rem[i + d] / lead;
+            quot[i] = q;
+            for (j, c) in divisor.0.iter().enumerate() {
+                rem[i + j] -= q * c;
+            }
+        }
+        rem.truncate(d.max(1));
+        (Self(quot).trimmed(), Self(rem).trimmed())
+    }
+
+    /// Monic greatest common divisor (Euclid's algorithm).
+    pub(super) fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let (_, r) = a.div_rem(&b);
+            a = b;
+            // Drop remainders that are only rounding noise.
+            b = if r.0.iter().all(|c| c.abs() < 1e-9) { Self::constant(0.0) } else { r };
+        }
+        let lead = a.0[a.degree()];
+        a.scale(1.0 / lead)
+    }
+
+    /// Horner evaluation.
+    pub fn eval(&self, x: f64) -> f64 {
+        self.0.iter().rev().fold(0.0, |acc, c| acc * x + c)
+    }
+
+    /// Distinct roots as `(re, im)` pairs, sorted by real then imaginary
+    /// part. A constant polynomial has none.
+    pub fn roots(&self) -> Vec<(f64, f64)> {
+        if self.degree() == 0 {
+            return Vec::new();
+        }
+        let monic = self.scale(1.0 / self.0[self.degree()]);
+        let square_free = monic.div_rem(&monic.gcd(&monic.deriv())).0;
+        let mut roots: Vec<(f64, f64)> = durand_kerner(&square_free)
+            .into_iter()
+            .map(|z| if z.im.abs() <= REAL_TOL * (1.0 + z.re.abs()) { (z.re, 0.0) } else { (z.re, z.im) })
+            .collect();
+        roots.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        roots
+    }
+
+    /// Reduce an expression in `var` to a polynomial, failing if it
+    /// divides by the unknown or uses functions other than `+ - * / ^`.
+    pub fn from_expr(expr: &Expr, var: &str) -> std::result::Result<Self, String> {
+        let rational = to_rational(expr, var)?;
+        if rational.den.degree() > 0 {
+            return Err(format!("not a polynomial in {var}: it divides by {var}"));
+        }
+        Ok(rational.num.scale(1.0 / rational.den.0[0]))
+    }
+
+    /// Infix rendering, highest degree first: `x^2 - 5*x + 6`.
+    pub fn render(&self, var: &str) -> String {
+        let mut out = String::new();
+        for (i, &c) in self.0.iter().enumerate().rev() {
+            if c.abs() < EPS {
+                continue;
+            }
+            let sign = if c < 0.0 { '-' } else { '+' };
+            if out.is_empty() {
+                if c < 0.0 {
+                    out.push('-');
+                }
+            } else {
+                out.push_str(&format!(" {sign} "));
+            }
+            let magnitude = c.abs();
+            let power = match i {
+                0 => String::new(),
+                1 => var.to_string(),
+                _ => format!("{var}^{i}"),
+            };
+            match (i, magnitude == 1.0) {
+                (0, _) => out.push_str(&number(magnitude)),
+                (_, true) => out.push_str(&power),
+                (_, false) => out.push_str(&format!("{}*{power}", number(magnitude))),
+            }
+        }
+        if out.is_empty() {
+            out.push('0');
+        }
+        out
+    }
+
+    /// The `poly` record value.
+    pub fn to_value(&self) -> WofValue {
+        let coeffs = WofValue::list(self.0.iter().map(|&c| WofValue::double(c)).collect());
+        WofValue::record(WofRecord::new(RecordType::new("poly", ["coeffs"]), vec![coeffs]).expect("one poly field"))
+    }
+
+    /// Read a `poly` record value.
+    pub fn from_value(value: &WofValue, op: &str) -> Result<Self> {
+        let bad = || WofError::runtime(format!("{op}: expected a polynomial (poly record)"));
+        let record = value.as_record().map_err(|_| bad())?;
+        if record.record_type().name() != "poly" {
+            return Err(bad());
+        }
+        let coeffs = record
+            .get("coeffs")
+            .ok_or_else(bad)?
+            .as_list()
+            .map_err(|_| bad())?
+            .iter()
+            .map(WofValue::as_numeric)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(coeffs))
+    }
+}
+
+/// A coefficient without a trailing `.0` when it is whole.
+fn number(c: f64) -> String {
+    if c.fract() == 0.0 && c.abs() < 1e15 {
+        format!("{}", c as i64)
+    } else {
+        format!("{c}")
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// DURAND–KERNER
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A complex number, just enough for the root iteration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    fn add_real(self, c: f64) -> Self {
+        Self::new(self.re + c, self.im)
+    }
+
+    fn sub(self, o: Self) -> Self {
+        Self::new(self.re - o.re, self.im - o.im)
+    }
+
+    fn mul(self, o: Self) -> Self {
+        Self::new(self.re * o.re - self.im * o.im, self.re * o.im + self.im * o.re)
+    }
+
+    fn div(self, o: Self) -> Self {
+        let d = o.re * o.re + o.im * o.im;
+        Self::new((self.re * o.re + self.im * o.im) / d, (self.im * o.re - self.re * o.im) / d)
+    }
+
+    fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+}
+
+/// All roots of a monic polynomial by simultaneous Weierstrass updates.
+fn durand_kerner(p: &Poly) -> Vec<Complex> {
+    let n = p.degree();
+    let eval = |z: Complex| p.0.iter().rev().fold(Complex::new(0.0, 0.0), |acc, &c| acc.mul(z).add_real(c));
+    // Start on a spiral scaled to the Cauchy bound, avoiding symmetry.
+    let radius = 1.0 + p.0[..n].iter().fold(0.0_f64, |m, c| m.max(c.abs()));
+    let seed = Complex::new(0.4, 0.9);
+    let mut z: Vec<Complex> = (0..n)
+        .scan(Complex::new(1.0, 0.0), |w, _| {
+            *w = w.mul(seed);
+            Some(Complex::new(w.re * radius, w.im * radius))
+        })
+        .collect();
+
+    for _ in 0..MAX_ITER {
+        let mut largest_step = 0.0_f64;
+        for i in 0..n {
+            let denom = (0..n).filter(|&j| j != i).fold(Complex::new(1.0, 0.0), |acc, j| acc.mul(z[i].sub(z[j])));
+            if denom.abs() == 0.0 {
+                z[i] = Complex::new(z[i].re + 1e-8, z[i].im - 1e-8);
+                largest_step = f64::INFINITY;
+                continue;
+            }
+            let step = eval(z[i]).div(denom);
+            z[i] = z[i].sub(step);
+            largest_step = largest_step.max(step.abs() / (1.0 + z[i].abs()));
+        }
+        if largest_step < 1e-15 {
+            break;
+        }
+    }
+    z
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// REGISTRATION
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn pop_poly(interp: &mut Interpreter, op: &str) -> Result<Poly> {
+    let value = interp.stack_mut().pop()?;
+    Poly::from_value(&value, op)
+}
+
+/// Register polynomial operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: c0 .. cn-1 n → P
+    interp.register("poly", |interp| {
+        let n = interp.stack_mut().pop_integer()?;
+        let n = usize::try_from(n)
+            .ok()
+            .filter(|&n| interp.stack().has(n))
+            .ok_or_else(|| WofError::runtime(format!("poly: {n} coefficients are not on the stack")))?;
+        let mut coeffs =
+            interp.stack_mut().pop_n(n)?.iter().map(WofValue::as_numeric).collect::<Result<Vec<_>>>()?;
+        coeffs.reverse();
+        interp.push(Poly::new(coeffs).to_value());
+        Ok(())
+    });
+
+    // Stack: P → c0 .. cn-1 n
+    interp.register("poly_coeffs", |interp| {
+        let p = pop_poly(interp, "poly_coeffs")?;
+        let n = p.coeffs().len();
+        interp.stack_mut().push_all(p.coeffs().iter().map(|&c| WofValue::double(c)));
+        interp.push(WofValue::integer(n as i64));
+        Ok(())
+    });
+
+    // Stack: P x → P(x), or a0 a1 ... an n x → f(x)
+    interp.register("poly_eval", |interp| {
+        let x = interp.stack_mut().pop()?.as_double()?;
+        if interp.stack().peek().is_ok_and(|v| v.as_record().is_ok()) {
+            let p = pop_poly(interp, "poly_eval")?;
+            interp.push(WofValue::double(p.eval(x)));
+            return Ok(());
+        }
+        let n = interp.stack_mut().pop()?.as_integer()? as usize;
+
+        let mut coeffs = Vec::with_capacity(n + 1);
+        for _ in 0..=n {
+            coeffs.push(interp.stack_mut().pop()?.as_double()?);
+        }
+        coeffs.reverse();
+
+        interp.stack_mut().push(WofValue::double(Poly(coeffs).eval(x)));
+        Ok(())
+    });
+
+    // Stack: P Q → P+Q
+    interp.register("poly_add", |interp| {
+        let q = pop_poly(interp, "poly_add")?;
+        let p = pop_poly(interp, "poly_add")?;
+        interp.push(p.add(&q).to_value());
+        Ok(())
+    });
+
+    // Stack: P Q → P·Q
+    interp.register("poly_mul", |interp| {
+        let q = pop_poly(interp, "poly_mul")?;
+        let p = pop_poly(interp, "poly_mul")?;
+        interp.push(p.mul(&q).to_value());
+        Ok(())
+    });
+
+    // Stack: P → P'
+    interp.register("poly_deriv", |interp| {
+        let p = pop_poly(interp, "poly_deriv")?;
+        interp.push(p.deriv().to_value());
+        Ok(())
+    });
+
+    // Stack: P → [[re, im] ...]
+    interp.register("poly_roots", |interp| {
+        let p = pop_poly(interp, "poly_roots")?;
+        if p.is_zero() {
+            return Err(WofError::runtime("poly_roots: the zero polynomial has every number as a root"));
+        }
+        let roots = p
+            .roots()
+            .into_iter()
+            .map(|(re, im)| WofValue::list(vec![WofValue::double(re), WofValue::double(im)]))
+            .collect();
+        interp.push(WofValue::list(roots));
+        Ok(())
+    });
+
+    // Stack: "expr" "x" → P
+    interp.register("expr_to_poly", |interp| {
+        let var = interp.stack_mut().pop_string()?;
+        let src = interp.stack_mut().pop_string()?;
+        let expr = parse_expr(&src).map_err(|e| e.into_wof_error(&src))?;
+        let p = Poly::from_expr(&expr, &var).map_err(|e| WofError::runtime(format!("expr_to_poly: {e}")))?;
+        interp.push(p.to_value());
+        Ok(())
+    });
+
+    // Stack: P "x" → "expr"
+    interp.register("poly_to_expr", |interp| {
+        let var = interp.stack_mut().pop_string()?;
+        let p = pop_poly(interp, "poly_to_expr")?;
+        interp.push(WofValue::string(p.render(&var)));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(got: &[(f64, f64)], want: &[(f64, f64)]) -> bool {
+        got.len() == want.len()
+            && got.iter().zip(want).all(|(a, b)| (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9)
+    }
+
+    #[test]
+    fn arithmetic_and_derivatives() {
+        let p = Poly::new(vec![-1.0, 1.0]);
+        let q = Poly::new(vec![1.0, 1.0]);
+        assert_eq!(p.mul(&q), Poly::new(vec![-1.0, 0.0, 1.0]));
+        assert_eq!(p.add(&q.scale(-1.0)), Poly::constant(-2.0));
+        assert_eq!(Poly::new(vec![5.0, 3.0, 0.0, 2.0]).deriv(), Poly::new(vec![3.0, 0.0, 6.0]));
+        assert_eq!(Poly::constant(7.0).deriv(), Poly::constant(0.0));
+        assert_eq!(Poly::new(vec![6.0, -5.0, 1.0]).eval(4.0), 2.0);
+    }
+
+    #[test]
+    fn durand_kerner_finds_real_complex_and_repeated_roots() {
+        let cubic = Poly::new(vec![-6.0, 11.0, -6.0, 1.0]);
+        assert!(close(&cubic.roots(), &[(1.0, 0.0), (2.0, 0.0), (3.0, 0.0)]), "{:?}", cubic.roots());
+
+        let quartic = Poly::new(vec![1.0, 0.0, 0.0, 0.0, 1.0]);
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        assert!(close(&quartic.roots(), &[(-h, -h), (-h, h), (h, -h), (h, h)]), "{:?}", quartic.roots());
+
+        // (x - 1)³ (x + 2): the triple root is found once, exactly.
+        let repeated = Poly::new(vec![-1.0, 1.0]).mul(&Poly::new(vec![-1.0, 1.0])).mul(&Poly::new(vec![-1.0, 1.0]));
+        let repeated = repeated.mul(&Poly::new(vec![2.0, 1.0]));
+        assert!(close(&repeated.roots(), &[(-2.0, 0.0), (1.0, 0.0)]), "{:?}", repeated.roots());
+
+        assert!(Poly::constant(3.0).roots().is_empty());
+        assert!(close(&Poly::new(vec![3.0, 2.0]).roots(), &[(-1.5, 0.0)]));
+    }
+
+    #[test]
+    fn converts_to_and_from_expressions() {
+        let expr = parse_expr("(x+1)(x-2)/2 + 3").unwrap();
+        let p = Poly::from_expr(&expr, "x").unwrap();
+        assert_eq!(p, Poly::new(vec![2.0, -0.5, 0.5]));
+        assert_eq!(p.render("x"), "0.5*x^2 - 0.5*x + 2");
+        assert_eq!(Poly::new(vec![6.0, -5.0, 1.0]).render("t"), "t^2 - 5*t + 6");
+        assert_eq!(Poly::new(vec![0.0, -1.0]).render("x"), "-x");
+        assert_eq!(Poly::constant(0.0).render("x"), "0");
+
+        // Rendering parses back to the same polynomial.
+        let back = Poly::from_expr(&parse_expr(&p.render("x")).unwrap(), "x").unwrap();
+        assert_eq!(back, p);
+
+        assert!(Poly::from_expr(&parse_expr("1/x").unwrap(), "x").is_err());
+        assert!(Poly::from_expr(&parse_expr("sin(x)").unwrap(), "x").is_err());
+    }
+
+    #[test]
+    fn ops_work_on_poly_values() {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+
+        interp.exec_line(r#""x^3 - 6x^2 + 11x - 6" "x" expr_to_poly poly_roots"#).unwrap();
+        let roots = interp.stack_mut().pop().unwrap().into_list().unwrap();
+        let roots: Vec<(f64, f64)> = roots
+            .iter()
+            .map(|r| {
+                let pair = r.as_list().unwrap();
+                (pair[0].as_double().unwrap(), pair[1].as_double().unwrap())
+            })
+            .collect();
+        assert!(close(&roots, &[(1.0, 0.0), (2.0, 0.0), (3.0, 0.0)]), "{roots:?}");
+
+        interp.exec_line(r#"-1 0 1 3 poly poly_deriv "x" poly_to_expr"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "2*x");
+
+        interp.exec_line("1 1 2 poly -1 1 2 poly poly_mul 3 poly_eval").unwrap();
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 8.0);
+        interp.exec_line("1 2 2 poly 1 -2 2 poly poly_add poly_coeffs").unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 1);
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 2.0);
+
+        // The older counted form still evaluates a0 + a1·x + a2·x².
+        interp.exec_line("1 2 3 2 2 poly_eval").unwrap();
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 17.0);
+
+        assert!(interp.exec_line("0 1 poly poly_roots").is_err());
+        assert!(interp.exec_line(r#""1/x" "x" expr_to_poly"#).is_err());
+    }
+}
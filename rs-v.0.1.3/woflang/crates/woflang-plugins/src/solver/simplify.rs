@@ -84,7 +84,7 @@ pub fn register(interp: &mut Interpreter) {
             interp.stack_mut().push(WofValue::integer(2));
             interp.stack_mut().push(a);
             interp.stack_mut().push(WofValue::string("*".to_string()));
-            println!("[simplify] X + X → 2 * X");
+            writeln!(interp.out(), "[simplify] X + X → 2 * X");
         } else {
             // No match, restore original
             interp.stack_mut().push(a);
@@ -114,14 +114,14 @@ pub fn register(interp: &mut Interpreter) {
             // X * 1 → X
             if is_one(&b) {
                 interp.stack_mut().push(a);
-                println!("[simplify] X * 1 → X");
+                writeln!(interp.out(), "[simplify] X * 1 → X");
                 return Ok(());
             }
 
             // 1 * X → X
             if is_one(&a) {
                 interp.stack_mut().push(b);
-                println!("[simplify] 1 * X → X");
+                writeln!(interp.out(), "[simplify] 1 * X → X");
                 return Ok(());
             }
         }
@@ -150,7 +150,7 @@ pub fn register(interp: &mut Interpreter) {
 
         if is_mul && (is_zero(&a) || is_zero(&b)) {
             interp.stack_mut().push(WofValue::integer(0));
-            println!("[simplify] X * 0 → 0");
+            writeln!(interp.out(), "[simplify] X * 0 → 0");
             return Ok(());
         }
 
@@ -179,13 +179,13 @@ pub fn register(interp: &mut Interpreter) {
         if is_add {
             if is_zero(&b) {
                 interp.stack_mut().push(a);
-                println!("[simplify] X + 0 → X");
+                writeln!(interp.out(), "[simplify] X + 0 → X");
                 return Ok(());
             }
 
             if is_zero(&a) {
                 interp.stack_mut().push(b);
-                println!("[simplify] 0 + X → X");
+                writeln!(interp.out(), "[simplify] 0 + X → X");
                 return Ok(());
             }
         }
@@ -216,14 +216,14 @@ pub fn register(interp: &mut Interpreter) {
             // X^0 → 1
             if is_zero(&b) {
                 interp.stack_mut().push(WofValue::integer(1));
-                println!("[simplify] X^0 → 1");
+                writeln!(interp.out(), "[simplify] X^0 → 1");
                 return Ok(());
             }
 
             // X^1 → X
             if is_one(&b) {
                 interp.stack_mut().push(a);
-                println!("[simplify] X^1 → X");
+                writeln!(interp.out(), "[simplify] X^1 → X");
                 return Ok(());
             }
         }
@@ -240,9 +240,9 @@ pub fn register(interp: &mut Interpreter) {
     // ─────────────────────────────────────────────────────────────────────
 
     // Note: This is a placeholder - full implementation would need expression trees
-    interp.register("simplify", |_interp| {
-        println!("[simplify] Use specific rules: simplify_sum, simplify_mul_one, etc.");
-        println!("           Or use symbolic expressions with sym_* operations");
+    interp.register("simplify", |interp| {
+        writeln!(interp.out(), "[simplify] Use specific rules: simplify_sum, simplify_mul_one, etc.");
+        writeln!(interp.out(), "           Or use symbolic expressions with sym_* operations");
         Ok(())
     });
 
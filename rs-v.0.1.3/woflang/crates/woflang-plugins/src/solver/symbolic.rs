@@ -285,10 +285,10 @@ pub fn register(interp: &mut Interpreter) {
         );
         let deriv = expr.diff("x").simplify();
 
-        println!();
-        println!("[calculus] Expression: {}", expr.to_string());
-        println!("[calculus] Derivative: {}", deriv.to_string());
-        println!();
+        writeln!(interp.out());
+        writeln!(interp.out(), "[calculus] Expression: {}", expr.to_string());
+        writeln!(interp.out(), "[calculus] Derivative: {}", deriv.to_string());
+        writeln!(interp.out());
 
         // Push 1.0 as result indicator
         interp.stack_mut().push(WofValue::double(1.0));
@@ -305,7 +305,7 @@ pub fn register(interp: &mut Interpreter) {
         let val = interp.stack_mut().pop()?.as_double()?;
         if let Ok(mut stack) = expr_stack().lock() {
             stack.push(Expr::Const(val));
-            println!("[symbolic] Pushed constant: {}", val);
+            writeln!(interp.out(), "[symbolic] Pushed constant: {}", val);
         }
         Ok(())
     });
@@ -316,54 +316,54 @@ pub fn register(interp: &mut Interpreter) {
         let name = interp.stack_mut().pop()?.as_string()?;
         if let Ok(mut stack) = expr_stack().lock() {
             stack.push(Expr::Var(name.clone()));
-            println!("[symbolic] Pushed variable: {}", name);
+            writeln!(interp.out(), "[symbolic] Pushed variable: {}", name);
         }
         Ok(())
     });
 
     // Add top two expressions
     // Expr stack: a b → (a + b)
-    interp.register("sym_add", |_interp| {
+    interp.register("sym_add", |interp| {
         if let Ok(mut stack) = expr_stack().lock() {
             if stack.len() < 2 {
-                println!("[symbolic] sym_add needs 2 expressions");
+                writeln!(interp.out(), "[symbolic] sym_add needs 2 expressions");
                 return Ok(());
             }
             let b = stack.pop().unwrap();
             let a = stack.pop().unwrap();
             stack.push(Expr::Add(Box::new(a), Box::new(b)));
-            println!("[symbolic] Added expressions");
+            writeln!(interp.out(), "[symbolic] Added expressions");
         }
         Ok(())
     });
 
     // Multiply top two expressions
     // Expr stack: a b → (a * b)
-    interp.register("sym_mul", |_interp| {
+    interp.register("sym_mul", |interp| {
         if let Ok(mut stack) = expr_stack().lock() {
             if stack.len() < 2 {
-                println!("[symbolic] sym_mul needs 2 expressions");
+                writeln!(interp.out(), "[symbolic] sym_mul needs 2 expressions");
                 return Ok(());
             }
             let b = stack.pop().unwrap();
             let a = stack.pop().unwrap();
             stack.push(Expr::Mul(Box::new(a), Box::new(b)));
-            println!("[symbolic] Multiplied expressions");
+            writeln!(interp.out(), "[symbolic] Multiplied expressions");
         }
         Ok(())
     });
 
     // Power: base^exp
-    interp.register("sym_pow", |_interp| {
+    interp.register("sym_pow", |interp| {
         if let Ok(mut stack) = expr_stack().lock() {
             if stack.len() < 2 {
-                println!("[symbolic] sym_pow needs 2 expressions");
+                writeln!(interp.out(), "[symbolic] sym_pow needs 2 expressions");
                 return Ok(());
             }
             let exp = stack.pop().unwrap();
             let base = stack.pop().unwrap();
             stack.push(Expr::Pow(Box::new(base), Box::new(exp)));
-            println!("[symbolic] Power expression");
+            writeln!(interp.out(), "[symbolic] Power expression");
         }
         Ok(())
     });
@@ -375,34 +375,33 @@ pub fn register(interp: &mut Interpreter) {
         let var = interp.stack_mut().pop()?.as_string()?;
         if let Ok(mut stack) = expr_stack().lock() {
             if stack.is_empty() {
-                println!("[symbolic] sym_diff needs an expression");
+                writeln!(interp.out(), "[symbolic] sym_diff needs an expression");
                 return Ok(());
             }
             let expr = stack.pop().unwrap();
             let deriv = expr.diff(&var).simplify();
-            println!("[symbolic] d/d{} ({}) = {}", var, expr.to_string(), deriv.to_string());
+            writeln!(interp.out(), "[symbolic] d/d{} ({}) = {}", var, expr.to_string(), deriv.to_string());
             stack.push(deriv);
         }
         Ok(())
     });
 
     // Show top expression
-    interp.register("sym_show", |_interp| {
+    interp.register("sym_show", |interp| {
         if let Ok(stack) = expr_stack().lock() {
-            if stack.is_empty() {
-                println!("[symbolic] Expression stack is empty");
-            } else {
-                println!("[symbolic] Top expression: {}", stack.last().unwrap().to_string());
+            match stack.last() {
+                Some(top) => writeln!(interp.out(), "[symbolic] Top expression: {}", top.to_string()),
+                None => writeln!(interp.out(), "[symbolic] Expression stack is empty"),
             }
         }
         Ok(())
     });
 
     // Clear expression stack
-    interp.register("sym_clear", |_interp| {
+    interp.register("sym_clear", |interp| {
         if let Ok(mut stack) = expr_stack().lock() {
             stack.clear();
-            println!("[symbolic] Expression stack cleared");
+            writeln!(interp.out(), "[symbolic] Expression stack cleared");
         }
         Ok(())
     });
@@ -0,0 +1,20 @@
+//! String processing operations for Woflang.
+//!
+//! ## Submodules
+//!
+//! - `regex` - Regular expression matching, search, replace and split
+//!   (requires the `regex` feature)
+
+#[cfg(feature = "regex")]
+mod regex;
+
+use woflang_runtime::Interpreter;
+
+/// Register all string operations with the interpreter.
+pub fn register(interp: &mut Interpreter) {
+    #[cfg(feature = "regex")]
+    regex::register(interp);
+
+    #[cfg(not(feature = "regex"))]
+    let _ = interp;
+}
@@ -0,0 +1,130 @@
+//! Regular expression operations.
+//!
+//! Patterns use the syntax of the [`regex`](https://docs.rs/regex) crate.
+//! Ops that produce several results push them individually followed by
+//! their count, matching the `poly_eval` convention.
+//!
+//! | Operation    | Stack Effect                          | Description |
+//! |--------------|---------------------------------------|-------------|
+//! | `re_match?`  | ( str pattern -- bool )               | Does the pattern match anywhere? |
+//! | `re_find`    | ( str pattern -- match \| nil )       | First match |
+//! | `re_findall` | ( str pattern -- m1 .. mn n )         | All non-overlapping matches |
+//! | `re_replace` | ( str pattern replacement -- str )    | Replace all matches (`$1` refers to groups) |
+//! | `re_split`   | ( str pattern -- p1 .. pn n )         | Split on matches |
+
+use regex::Regex;
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Compile a pattern, turning syntax errors into invalid-argument errors.
+fn compile(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern)
+        .map_err(|e| WofError::InvalidArgument(format!("bad regex '{pattern}': {e}")))
+}
+
+/// Pop `( str pattern -- )` and compile the pattern.
+fn pop_subject_and_pattern(interp: &mut Interpreter) -> Result<(String, Regex)> {
+    let pattern = interp.stack_mut().pop_string()?;
+    let subject = interp.stack_mut().pop_string()?;
+    Ok((subject, compile(&pattern)?))
+}
+
+/// Push each item followed by the item count.
+fn push_counted<'a>(interp: &mut Interpreter, items: impl Iterator<Item = &'a str>) {
+    let mut count = 0;
+    for item in items {
+        interp.push(WofValue::string(item));
+        count += 1;
+    }
+    interp.push(WofValue::integer(count));
+}
+
+/// Register regular expression operations.
+pub fn register(interp: &mut Interpreter) {
+    interp.register("re_match?", |interp| {
+        let (subject, re) = pop_subject_and_pattern(interp)?;
+        interp.push(WofValue::boolean(re.is_match(&subject)));
+        Ok(())
+    });
+
+    interp.register("re_find", |interp| {
+        let (subject, re) = pop_subject_and_pattern(interp)?;
+        let found = re
+            .find(&subject)
+            .map_or_else(WofValue::nil, |m| WofValue::string(m.as_str()));
+        interp.push(found);
+        Ok(())
+    });
+
+    interp.register("re_findall", |interp| {
+        let (subject, re) = pop_subject_and_pattern(interp)?;
+        push_counted(interp, re.find_iter(&subject).map(|m| m.as_str()));
+        Ok(())
+    });
+
+    interp.register("re_replace", |interp| {
+        let replacement = interp.stack_mut().pop_string()?;
+        let (subject, re) = pop_subject_and_pattern(interp)?;
+        let replaced = re.replace_all(&subject, replacement.as_str());
+        interp.push(WofValue::string(replaced));
+        Ok(())
+    });
+
+    interp.register("re_split", |interp| {
+        let (subject, re) = pop_subject_and_pattern(interp)?;
+        push_counted(interp, re.split(&subject));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Interpreter {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        interp
+    }
+
+    #[test]
+    fn test_match_and_find() {
+        let mut interp = setup();
+        interp.exec_line(r#""x = 42" "\d+" re_match?"#).unwrap();
+        assert!(interp.stack_mut().pop_bool().unwrap());
+
+        interp.exec_line(r#""x = 42" "\d+" re_find"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "42");
+
+        interp.exec_line(r#""no digits" "\d+" re_find"#).unwrap();
+        assert!(interp.stack_mut().pop().unwrap().is_nil());
+    }
+
+    #[test]
+    fn test_findall_and_split_push_counts() {
+        let mut interp = setup();
+        interp.exec_line(r#""a1b22c333" "\d+" re_findall"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 3);
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "333");
+
+        interp.stack_mut().clear();
+        interp.exec_line(r#""a, b,c" ",\s*" re_split"#).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 3);
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "c");
+    }
+
+    #[test]
+    fn test_replace_with_groups() {
+        let mut interp = setup();
+        interp
+            .exec_line(r#""2x + 3x" "(\d)x" "$1*x" re_replace"#)
+            .unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "2*x + 3*x");
+    }
+
+    #[test]
+    fn test_bad_pattern_is_an_error() {
+        let mut interp = setup();
+        assert!(interp.exec_line(r#""abc" "(" re_match?"#).is_err());
+    }
+}
@@ -0,0 +1,448 @@
+//! Tables: CSV data and column statistics.
+//!
+//! ## Operations
+//!
+//! - `csv_read` - Load a CSV file with a header row ("file" → table)
+//! - `csv_parse` - Parse CSV text with a header row ("text" → table)
+//! - `table_cols` - Column names in file order (table → [name ...])
+//! - `table_rows` - Number of rows (table → n)
+//! - `table_col` - One column's values (table "name" → [v ...])
+//! - `table_select` - Keep the listed columns, in that order (table [name ...] → table)
+//! - `table_where` - Keep the rows where a function accepts a column's value (table "name" "f" → table)
+//! - `col_mean` - Mean of a column (list → mean)
+//! - `col_median` - Median of a column (list → median)
+//! - `col_stddev` - Sample standard deviation of a column (list → stddev)
+//! - `col_corr` - Pearson correlation of two columns (xs ys → r)
+//!
+//! A table is a map from column name to a list of that column's values,
+//! all the same length, so the map ops work on it too. Cells holding an
+//! integer or a finite decimal become numbers, empty cells become nil and
+//! anything else stays a string. Quoted cells may contain commas, line
+//! breaks and doubled quotes:
+//!
+//! ```text
+//! "planets.csv" csv_read                 # name,mass,moons
+//! ⊕ big? ⺆ 1 > ⺘
+//! "mass" "big?" table_where
+//! dup "mass" table_col col_mean
+//! swap "moons" table_col col_median
+//! ```
+//!
+//! The statistics skip nil cells; `col_corr` skips rows missing either
+//! value. `csv_read` goes through the interpreter's sandbox, like
+//! `file_read`.
+
+use woflang_core::{InterpreterContext, Result, WofError, WofMap, WofValue};
+use woflang_runtime::Interpreter;
+
+/// Register the table operations.
+pub fn register(interp: &mut Interpreter) {
+    // Stack: "file" → table
+    interp.register("csv_read", |interp| {
+        let path = interp.stack_mut().pop_string()?;
+        let resolved = interp.sandbox().check_read(&path)?;
+        let text = std::fs::read_to_string(&resolved)
+            .map_err(|e| WofError::Io(format!("csv_read: {path}: {e}")))?;
+        let table = parse_csv(&text).map_err(|e| WofError::runtime(format!("csv_read: {path}: {e}")))?;
+        interp.push(table);
+        Ok(())
+    });
+
+    // Stack: "text" → table
+    interp.register("csv_parse", |interp| {
+        let text = interp.stack_mut().pop_string()?;
+        let table = parse_csv(&text).map_err(|e| WofError::runtime(format!("csv_parse: {e}")))?;
+        interp.push(table);
+        Ok(())
+    });
+
+    // Stack: table → [name ...]
+    interp.register("table_cols", |interp| {
+        let table = pop_table(interp, "table_cols")?;
+        interp.push(WofValue::list(table.keys().cloned().collect()));
+        Ok(())
+    });
+
+    // Stack: table → n
+    interp.register("table_rows", |interp| {
+        let table = pop_table(interp, "table_rows")?;
+        interp.push(WofValue::integer(row_count(&table) as i64));
+        Ok(())
+    });
+
+    // Stack: table "name" → [v ...]
+    interp.register("table_col", |interp| {
+        let name = interp.stack_mut().pop()?;
+        let table = pop_table(interp, "table_col")?;
+        let column = column(&table, &name, "table_col")?.clone();
+        interp.push(column);
+        Ok(())
+    });
+
+    // Stack: table [name ...] → table
+    interp.register("table_select", |interp| {
+        let names = interp.stack_mut().pop()?.into_list()?;
+        let table = pop_table(interp, "table_select")?;
+        let mut selected = WofMap::new();
+        for name in names {
+            let values = column(&table, &name, "table_select")?.clone();
+            selected.insert(name, values);
+        }
+        interp.push(WofValue::map(selected));
+        Ok(())
+    });
+
+    // Stack: table "name" "f" → table
+    interp.register("table_where", |interp| {
+        let function = interp.stack_mut().pop_string()?;
+        let name = interp.stack_mut().pop()?;
+        let table = pop_table(interp, "table_where")?;
+        if !interp.has_function(&function) {
+            return Err(WofError::runtime(format!("table_where: no function named '{function}'")));
+        }
+        let mut keep = Vec::new();
+        for value in column(&table, &name, "table_where")?.as_list()? {
+            let depth = interp.stack().len();
+            interp.push(value.clone());
+            interp.run_function(&function)?;
+            if interp.stack().len() != depth + 1 {
+                return Err(WofError::runtime(format!(
+                    "table_where: '{function}' must leave exactly one value"
+                )));
+            }
+            keep.push(interp.stack_mut().pop()?.is_truthy());
+        }
+        let mut filtered = WofMap::new();
+        for (name, values) in table.iter() {
+            let rows = values.as_list()?.iter().zip(&keep).filter(|(_, k)| **k).map(|(v, _)| v.clone());
+            filtered.insert(name.clone(), WofValue::list(rows.collect()));
+        }
+        interp.push(WofValue::map(filtered));
+        Ok(())
+    });
+
+    // Stack: list → mean
+    interp.register("col_mean", |interp| {
+        let values = pop_numbers(interp, "col_mean")?;
+        interp.push(WofValue::double(mean(&values)));
+        Ok(())
+    });
+
+    // Stack: list → median
+    interp.register("col_median", |interp| {
+        let mut values = pop_numbers(interp, "col_median")?;
+        values.sort_by(f64::total_cmp);
+        let mid = values.len() / 2;
+        let median = if values.len() % 2 == 0 { (values[mid - 1] + values[mid]) / 2.0 } else { values[mid] };
+        interp.push(WofValue::double(median));
+        Ok(())
+    });
+
+    // Stack: list → stddev
+    interp.register("col_stddev", |interp| {
+        let values = pop_numbers(interp, "col_stddev")?;
+        if values.len() < 2 {
+            return Err(WofError::runtime("col_stddev: needs at least two values"));
+        }
+        let m = mean(&values);
+        let var = values.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+        interp.push(WofValue::double(var.sqrt()));
+        Ok(())
+    });
+
+    // Stack: xs ys → r
+    interp.register("col_corr", |interp| {
+        let ys = interp.stack_mut().pop()?.into_list()?;
+        let xs = interp.stack_mut().pop()?.into_list()?;
+        if xs.len() != ys.len() {
+            return Err(WofError::runtime(format!(
+                "col_corr: columns differ in length ({} and {})",
+                xs.len(),
+                ys.len()
+            )));
+        }
+        let mut pairs = Vec::new();
+        for (x, y) in xs.iter().zip(&ys) {
+            if !x.is_nil() && !y.is_nil() {
+                pairs.push((x.as_numeric()?, y.as_numeric()?));
+            }
+        }
+        let r = correlation(&pairs)
+            .ok_or_else(|| WofError::runtime("col_corr: needs two or more rows where neither column is constant"))?;
+        interp.push(WofValue::double(r));
+        Ok(())
+    });
+}
+
+/// Pop a table for `op`, checking its columns are lists of one length.
+fn pop_table(interp: &mut Interpreter, op: &str) -> Result<WofMap> {
+    let table = interp.stack_mut().pop()?.into_map()?;
+    let mut rows = None;
+    for (name, values) in table.iter() {
+        let len = values
+            .as_list()
+            .map_err(|_| WofError::runtime(format!("{op}: column {name} is not a list")))?
+            .len();
+        if *rows.get_or_insert(len) != len {
+            return Err(WofError::runtime(format!("{op}: columns differ in length")));
+        }
+    }
+    Ok(table)
+}
+
+/// Rows in a table already checked by [`pop_table`].
+fn row_count(table: &WofMap) -> usize {
+    table.values().next().and_then(|v| v.as_list().ok()).map_or(0, <[WofValue]>::len)
+}
+
+/// The column called `name`.
+fn column<'a>(table: &'a WofMap, name: &WofValue, op: &str) -> Result<&'a WofValue> {
+    table.get(name).ok_or_else(|| WofError::runtime(format!("{op}: no column {name}")))
+}
+
+/// Pop a column for `op` and return its non-nil cells as numbers.
+fn pop_numbers(interp: &mut Interpreter, op: &str) -> Result<Vec<f64>> {
+    let values = interp.stack_mut().pop()?.into_list()?;
+    let numbers = values
+        .iter()
+        .filter(|v| !v.is_nil())
+        .map(WofValue::as_numeric)
+        .collect::<Result<Vec<_>>>()
+        .map_err(|e| WofError::runtime(format!("{op}: {e}")))?;
+    if numbers.is_empty() {
+        return Err(WofError::runtime(format!("{op}: the column has no values")));
+    }
+    Ok(numbers)
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Pearson's r, or `None` with fewer than two pairs or a constant side.
+fn correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.len() < 2 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mx = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let my = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        sxy += (x - mx) * (y - my);
+        sxx += (x - mx).powi(2);
+        syy += (y - my).powi(2);
+    }
+    (sxx > 0.0 && syy > 0.0).then(|| sxy / (sxx * syy).sqrt())
+}
+
+/// Parse CSV text whose first record names the columns.
+fn parse_csv(text: &str) -> std::result::Result<WofValue, String> {
+    let mut records = records(text)?.into_iter();
+    let (_, header) = records.next().ok_or("no header row")?;
+    let mut columns: Vec<(String, Vec<WofValue>)> = Vec::with_capacity(header.len());
+    for name in header {
+        if name.is_empty() || columns.iter().any(|(n, _)| *n == name) {
+            return Err(format!("column names must be unique and non-empty, not \"{name}\""));
+        }
+        columns.push((name, Vec::new()));
+    }
+    for (line, record) in records {
+        if record.len() != columns.len() {
+            return Err(format!("line {line}: {} fields, expected {}", record.len(), columns.len()));
+        }
+        for ((_, values), field) in columns.iter_mut().zip(record) {
+            values.push(cell(field));
+        }
+    }
+    let mut table = WofMap::new();
+    for (name, values) in columns {
+        table.insert(WofValue::string(name), WofValue::list(values));
+    }
+    Ok(WofValue::map(table))
+}
+
+/// Read a cell as an integer, a finite double, nil when empty, or else
+/// the text itself.
+fn cell(field: String) -> WofValue {
+    let trimmed = field.trim();
+    if trimmed.is_empty() {
+        WofValue::nil()
+    } else if let Ok(n) = trimmed.parse::<i64>() {
+        WofValue::integer(n)
+    } else if let Some(x) = trimmed.parse::<f64>().ok().filter(|x| x.is_finite()) {
+        WofValue::double(x)
+    } else {
+        WofValue::string(field)
+    }
+}
+
+/// Split CSV text into records, each with the line it starts on. Blank
+/// lines between records are skipped.
+fn records(text: &str) -> std::result::Result<Vec<(usize, Vec<String>)>, String> {
+    let mut records = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    while chars.peek().is_some() {
+        let start = line;
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        loop {
+            match chars.next() {
+                Some('"') if quoted => {
+                    if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        quoted = false;
+                    }
+                }
+                Some('"') if field.is_empty() => quoted = true,
+                Some('\n') if quoted => {
+                    line += 1;
+                    field.push('\n');
+                }
+                Some(c) if quoted => field.push(c),
+                None if quoted => return Err(format!("line {start}: unterminated quoted field")),
+                Some(',') => record.push(std::mem::take(&mut field)),
+                Some('\r') if chars.peek() == Some(&'\n') => {}
+                Some('\n') | None => {
+                    line += 1;
+                    break;
+                }
+                Some(c) => field.push(c),
+            }
+        }
+        if record.is_empty() && field.trim().is_empty() {
+            continue;
+        }
+        record.push(field);
+        records.push((start, record));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        register(&mut interp);
+        interp.register("dup", |interp| interp.stack_mut().dup());
+        interp.register("swap", |interp| interp.stack_mut().swap());
+        interp.register(">", |interp| {
+            let b = interp.stack_mut().pop_numeric()?;
+            let a = interp.stack_mut().pop_numeric()?;
+            interp.push(WofValue::boolean(a > b));
+            Ok(())
+        });
+        interp
+    }
+
+    fn list(items: &[WofValue]) -> WofValue {
+        WofValue::list(items.to_vec())
+    }
+
+    fn numbers(xs: &[f64]) -> WofValue {
+        WofValue::list(xs.iter().map(|&x| WofValue::double(x)).collect())
+    }
+
+    fn pop_strings(interp: &mut Interpreter) -> Vec<String> {
+        let items = interp.stack_mut().pop().unwrap().into_list().unwrap();
+        items.iter().map(|v| v.as_str().unwrap().to_string()).collect()
+    }
+
+    fn pop_f64(interp: &mut Interpreter) -> f64 {
+        interp.stack_mut().pop_numeric().unwrap()
+    }
+
+    const PLANETS: &str = "name,mass,moons\r\nMercury,0.055,0\n\"Earth, home\",1,1\n\nJupiter,317.8,95\n\"Sat\"\"urn\",95.2,\n";
+
+    #[test]
+    fn csv_becomes_columns() {
+        let mut interp = make_interp();
+        interp.push(WofValue::string(PLANETS));
+        interp.exec_line("csv_parse dup table_rows swap dup table_cols swap \"name\" table_col").unwrap();
+        assert_eq!(pop_strings(&mut interp), ["Mercury", "Earth, home", "Jupiter", "Sat\"urn"]);
+        assert_eq!(pop_strings(&mut interp), ["name", "mass", "moons"]);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 4);
+
+        let table = parse_csv("a,b\n1,2\n").unwrap();
+        assert_eq!(table.to_string(), parse_csv("a,b\n1,2").unwrap().to_string());
+        assert!(parse_csv("").is_err());
+        assert!(parse_csv("a,a\n1,2").is_err());
+        assert_eq!(parse_csv("a,b\n1,2\n3").unwrap_err(), "line 3: 1 fields, expected 2");
+        assert!(parse_csv("a\n\"open").unwrap_err().contains("unterminated"));
+    }
+
+    #[test]
+    fn csv_files_are_read_through_the_sandbox() {
+        let dir = std::env::temp_dir().join(format!("woflang-table-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("planets.csv");
+        std::fs::write(&file, PLANETS).unwrap();
+
+        let mut interp = make_interp();
+        let mut sandbox = woflang_runtime::Sandbox::default();
+        sandbox.allow(&dir).unwrap();
+        interp.set_sandbox(sandbox);
+        interp.exec_line(&format!(r#""{}" csv_read table_rows"#, file.display())).unwrap();
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 4);
+        assert!(interp.exec_line(&format!(r#""{}" csv_read"#, dir.join("nope.csv").display())).is_err());
+        assert!(interp.exec_line(r#""/etc/passwd" csv_read"#).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn select_and_filter_rows() {
+        let mut interp = make_interp();
+        interp.push(WofValue::string(PLANETS));
+        interp.exec_line("⊕ big? ⺆ 1 > ⺘ csv_parse \"mass\" \"big?\" table_where").unwrap();
+        interp.exec_line("dup table_rows swap").unwrap();
+        interp.push(list(&[WofValue::string("moons"), WofValue::string("name")]));
+        interp.exec_line("table_select dup table_cols").unwrap();
+        assert_eq!(pop_strings(&mut interp), ["moons", "name"]);
+        interp.exec_line("\"name\" table_col").unwrap();
+        assert_eq!(pop_strings(&mut interp), ["Jupiter", "Sat\"urn"]);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 2);
+
+        interp.push(WofValue::string(PLANETS));
+        assert!(interp.exec_line("csv_parse \"nope\" table_col").is_err());
+        interp.push(WofValue::string(PLANETS));
+        assert!(interp.exec_line("csv_parse \"mass\" \"nope\" table_where").is_err());
+    }
+
+    #[test]
+    fn column_statistics() {
+        let mut interp = make_interp();
+        interp.push(WofValue::string(PLANETS));
+        interp.exec_line("csv_parse dup \"moons\" table_col col_mean swap \"moons\" table_col col_median").unwrap();
+        assert!((pop_f64(&mut interp) - 1.0).abs() < 1e-12);
+        assert!((pop_f64(&mut interp) - 32.0).abs() < 1e-12);
+
+        interp.push(WofValue::string("x,y,z\n1,2,5\n2,4,5\n3,6,5\n4,8.5,5\n"));
+        interp.exec_line("csv_parse dup \"x\" table_col swap \"y\" table_col col_corr").unwrap();
+        let r = pop_f64(&mut interp);
+        assert!(r > 0.99 && r < 1.0, "{r}");
+
+        interp.push(numbers(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]));
+        interp.exec_line("col_stddev").unwrap();
+        assert!((pop_f64(&mut interp) - 2.138_089_935).abs() < 1e-6);
+
+        let fails = |interp: &mut Interpreter, args: &[WofValue], op: &str| {
+            interp.stack_mut().clear();
+            for arg in args {
+                interp.push(arg.clone());
+            }
+            interp.exec_line(op).is_err()
+        };
+        assert!(fails(&mut interp, &[numbers(&[1.0])], "col_stddev"));
+        assert!(fails(&mut interp, &[list(&[WofValue::nil()])], "col_mean"));
+        assert!(fails(&mut interp, &[list(&[WofValue::string("a")])], "col_median"));
+        assert!(fails(&mut interp, &[numbers(&[1.0, 2.0]), numbers(&[5.0, 5.0])], "col_corr"));
+        assert!(fails(&mut interp, &[numbers(&[1.0, 2.0]), numbers(&[1.0])], "col_corr"));
+    }
+}
@@ -1,6 +1,11 @@
 //! Assertion and testing operations for Woflang.
 //!
 //! Provides assert, assert_eq, expect, and related testing helpers.
+//!
+//! Inside a `test_case "name" ⺆ ... ⺘` block the `assert*` ops record
+//! their result in the case's report and let the case carry on; anywhere
+//! else a failed assertion stops the script. `woflang --run-tests dir/`
+//! runs every `*_test.wof` file under `dir` and prints a summary.
 
 use woflang_core::{WofError, WofValue, InterpreterContext};
 use woflang_runtime::Interpreter;
@@ -14,82 +19,66 @@ pub fn register(interp: &mut Interpreter) {
     // Assert top is truthy (non-zero, non-empty)
     interp.register("assert", |interp| {
         let val = interp.stack_mut().pop()?;
-        if !is_truthy(&val) {
-            return Err(WofError::Runtime(format!("assertion failed: {:?}", val)));
-        }
-        Ok(())
+        let passed = is_truthy(&val);
+        interp.check(passed, || format!("assertion failed: {}", val))
     });
 
     // Assert top two values are equal
     interp.register("assert_eq", |interp| {
         let b = interp.stack_mut().pop()?;
         let a = interp.stack_mut().pop()?;
-        if !values_equal(&a, &b) {
-            return Err(WofError::Runtime(format!(
-                "assertion failed: {:?} != {:?}", a, b
-            )));
-        }
-        Ok(())
+        let passed = values_equal(&a, &b);
+        interp.check(passed, || format!("assertion failed: {} != {}", a, b))
     });
 
     // Assert top two values are not equal
     interp.register("assert_ne", |interp| {
         let b = interp.stack_mut().pop()?;
         let a = interp.stack_mut().pop()?;
-        if values_equal(&a, &b) {
-            return Err(WofError::Runtime(format!(
-                "assertion failed: {:?} == {:?}", a, b
-            )));
-        }
-        Ok(())
+        let passed = !values_equal(&a, &b);
+        interp.check(passed, || format!("assertion failed: {} == {}", a, b))
     });
 
     // Assert a < b
     interp.register("assert_lt", |interp| {
         let b = interp.stack_mut().pop()?.as_double()?;
         let a = interp.stack_mut().pop()?.as_double()?;
-        if !(a < b) {
-            return Err(WofError::Runtime(format!(
-                "assertion failed: {} < {}", a, b
-            )));
-        }
-        Ok(())
+        let passed = a < b;
+        interp.check(passed, || format!("assertion failed: {} < {}", a, b))
     });
 
     // Assert a <= b
     interp.register("assert_le", |interp| {
         let b = interp.stack_mut().pop()?.as_double()?;
         let a = interp.stack_mut().pop()?.as_double()?;
-        if !(a <= b) {
-            return Err(WofError::Runtime(format!(
-                "assertion failed: {} <= {}", a, b
-            )));
-        }
-        Ok(())
+        let passed = a <= b;
+        interp.check(passed, || format!("assertion failed: {} <= {}", a, b))
     });
 
     // Assert a > b
     interp.register("assert_gt", |interp| {
         let b = interp.stack_mut().pop()?.as_double()?;
         let a = interp.stack_mut().pop()?.as_double()?;
-        if !(a > b) {
-            return Err(WofError::Runtime(format!(
-                "assertion failed: {} > {}", a, b
-            )));
-        }
-        Ok(())
+        let passed = a > b;
+        interp.check(passed, || format!("assertion failed: {} > {}", a, b))
     });
 
     // Assert a >= b
     interp.register("assert_ge", |interp| {
         let b = interp.stack_mut().pop()?.as_double()?;
         let a = interp.stack_mut().pop()?.as_double()?;
-        if !(a >= b) {
-            return Err(WofError::Runtime(format!(
-                "assertion failed: {} >= {}", a, b
-            )));
-        }
-        Ok(())
+        let passed = a >= b;
+        interp.check(passed, || format!("assertion failed: {} >= {}", a, b))
+    });
+
+    // Assert the stack holds n values (after popping n)
+    interp.register("assert_stack_depth", |interp| {
+        let expected = interp.stack_mut().pop()?.as_integer()?;
+        let depth = interp.stack().len();
+        let passed = usize::try_from(expected).is_ok_and(|n| n == depth);
+        interp.check(passed, || format!(
+            "assertion failed: stack depth is {}, expected {}", depth, expected
+        ))
     });
 
     // ═══════════════════════════════════════════════════════════════
@@ -123,12 +112,20 @@ pub fn register(interp: &mut Interpreter) {
         let eps = interp.stack_mut().pop()?.as_double()?;
         let b = interp.stack_mut().pop()?.as_double()?;
         let a = interp.stack_mut().pop()?.as_double()?;
-        if (a - b).abs() >= eps {
-            return Err(WofError::Runtime(format!(
-                "assertion failed: |{} - {}| = {} >= {}", a, b, (a - b).abs(), eps
-            )));
-        }
-        Ok(())
+        let passed = (a - b).abs() < eps;
+        interp.check(passed, || format!("assertion failed: |{} - {}| = {} >= {}", a, b, (a - b).abs(), eps))
+    });
+
+    // Assert a value is within eps of the expected one
+    // Stack: value expected eps → (asserts |value - expected| <= eps)
+    interp.register("assert_near", |interp| {
+        let eps = interp.stack_mut().pop()?.as_double()?;
+        let expected = interp.stack_mut().pop()?.as_double()?;
+        let value = interp.stack_mut().pop()?.as_double()?;
+        let passed = (value - expected).abs() <= eps;
+        interp.check(passed, || format!(
+            "assertion failed: {} is not within {} of {}", value, eps, expected
+        ))
     });
 
     // Check if approximately equal (push result)
@@ -15,20 +15,20 @@ pub fn register(interp: &mut Interpreter) {
     // Print top of stack with newline
     interp.register("print", |interp| {
         let val = interp.stack_mut().pop()?;
-        println!("{}", val);
+        writeln!(interp.out(), "{}", interp.display(&val));
         Ok(())
     });
 
     interp.register("say", |interp| {
         let val = interp.stack_mut().pop()?;
-        println!("{}", val);
+        writeln!(interp.out(), "{}", interp.display(&val));
         Ok(())
     });
 
     // Print without newline
     interp.register("emit", |interp| {
         let val = interp.stack_mut().pop()?;
-        print!("{}", val);
+        write!(interp.out(), "{}", interp.display(&val));
         let _ = io::stdout().flush();
         Ok(())
     });
@@ -36,24 +36,24 @@ pub fn register(interp: &mut Interpreter) {
     // Print and keep on stack
     interp.register("peek_print", |interp| {
         let val = interp.stack().peek()?;
-        println!("{}", val);
+        writeln!(interp.out(), "{}", interp.display(val));
         Ok(())
     });
 
     // Print newline
-    interp.register("cr", |_interp| {
-        println!();
+    interp.register("cr", |interp| {
+        writeln!(interp.out());
         Ok(())
     });
 
-    interp.register("newline", |_interp| {
-        println!();
+    interp.register("newline", |interp| {
+        writeln!(interp.out());
         Ok(())
     });
 
     // Print space
-    interp.register("space", |_interp| {
-        print!(" ");
+    interp.register("space", |interp| {
+        write!(interp.out(), " ");
         let _ = io::stdout().flush();
         Ok(())
     });
@@ -64,26 +64,26 @@ pub fn register(interp: &mut Interpreter) {
     
     // Show entire stack
     interp.register(".s", |interp| {
-        println!("{}", interp.stack());
+        writeln!(interp.out(), "{}", interp.stack());
         Ok(())
     });
 
     interp.register("show_stack", |interp| {
-        println!("{}", interp.stack());
+        writeln!(interp.out(), "{}", interp.stack());
         Ok(())
     });
 
     // Debug print with type info
     interp.register("debug", |interp| {
         let val = interp.stack_mut().pop()?;
-        println!("[DEBUG] {:?}", val);
+        writeln!(interp.out(), "[DEBUG] {:?}", val);
         Ok(())
     });
 
     // Debug peek (don't consume)
     interp.register("debug_peek", |interp| {
         let val = interp.stack().peek()?;
-        println!("[DEBUG] {:?}", val);
+        writeln!(interp.out(), "[DEBUG] {:?}", val);
         Ok(())
     });
 
@@ -105,7 +105,7 @@ pub fn register(interp: &mut Interpreter) {
     // Read line with prompt
     interp.register("prompt", |interp| {
         let prompt = interp.stack_mut().pop()?;
-        print!("{}", prompt);
+        write!(interp.out(), "{}", prompt);
         let _ = io::stdout().flush();
         
         let mut line = String::new();
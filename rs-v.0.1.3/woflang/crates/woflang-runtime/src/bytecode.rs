@@ -0,0 +1,531 @@
+//! Bytecode compilation: lowering tokens to a [`Program`] and running it.
+//!
+//! [`Interpreter::compile_line`], [`compile_source`](Interpreter::compile_source)
+//! and [`compile_file`](Interpreter::compile_file) tokenize source exactly
+//! as the interpreter would and lower the tokens to [`Instruction`]s, with
+//! control flow resolved to jumps. [`Interpreter::exec_program`] then runs
+//! the program on a small VM loop: op names are looked up once per run
+//! rather than once per token, and loops jump back instead of collecting
+//! and re-dispatching their bodies. A program can be run any number of
+//! times.
+//!
+//! | Source                      | Bytecode |
+//! |-----------------------------|----------|
+//! | `42`, `"text"`              | `push 42`, `push text` |
+//! | `name` (op or other symbol) | `工 name` |
+//! | `字 x`, `読 x`, `支 x`, `巡 f` | `字 x`, `読 x`, `支 x`, `巡 f` |
+//! | `c 若 ⺆ A ⺘ 或 ⺆ B ⺘`       | `c 若 @else A @ @end else: B end:` |
+//! | `N ⨯ ⺆ A ⺘`                 | `N ⨯ @exit top: A 終 @top exit:` |
+//! | `⟳ ⺆ A ⺘`                   | `⟳ 1000000 top: A 終 @top` |
+//! | `当 ⺆ c ⺘ ⺆ A ⺘`            | `⟳ 0 top: c 若 @stop A 終 @top stop: 🛑 @exit exit:` |
+//! | `🛑`, `↻`                   | `🛑 @exit`, `↻ @終` |
+//!
+//! Forms that capture raw tokens (function definitions, labels and
+//! `goto`, `bench`, `spawn`, `jit`, `record`, `enum`, `schema`,
+//! `match_tag`, `map_ok`, `and_then`, `try_prop`, `try`/`catch`,
+//! `test_case`) and `$"..."` strings are not compiled: define functions
+//! with [`exec_line`](Interpreter::exec_line) and call them from
+//! compiled code. Compiled code does not record provenance for `explain`.
+//!
+//! ```
+//! use woflang_runtime::Interpreter;
+//! use woflang_core::{InterpreterContext, WofValue};
+//!
+//! let mut interp = Interpreter::new();
+//! interp.register("+", |ctx| {
+//!     let b = ctx.stack_mut().pop_integer()?;
+//!     let a = ctx.stack_mut().pop_integer()?;
+//!     ctx.push(WofValue::integer(a + b));
+//!     Ok(())
+//! });
+//!
+//! let program = interp.compile_line("0 1000 ⨯ ⺆ 1 + ⺘").unwrap();
+//! interp.exec_program(&program).unwrap();
+//! assert_eq!(interp.stack().peek().unwrap().as_integer().unwrap(), 1000);
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use woflang_core::{
+    BlockType, Instruction, InterpreterContext, Opcode, Operand, Program, Result, Span, WofError, WofValue,
+};
+
+use crate::interpreter::located;
+use crate::registry::BoxedOp;
+use crate::{Interpreter, OwnedToken, TokenKind};
+
+/// Iterations an unbounded `⟳` loop may run, matching the interpreter.
+const LOOP_LIMIT: i64 = 1_000_000;
+
+/// Jumps to patch once the end of the innermost loop is known.
+#[derive(Default)]
+struct LoopPatches {
+    breaks: Vec<usize>,
+    continues: Vec<usize>,
+}
+
+/// Lowers a token stream to a [`Program`].
+struct Compiler<'a> {
+    tokens: &'a [OwnedToken],
+    pos: usize,
+    program: Program,
+    loops: Vec<LoopPatches>,
+}
+
+impl<'a> Compiler<'a> {
+    fn new(tokens: &'a [OwnedToken], source: String) -> Self {
+        Self { tokens, pos: 0, program: Program::with_source(source), loops: Vec::new() }
+    }
+
+    /// Compile every token, checking blocks are balanced.
+    fn finish(mut self) -> Result<Program> {
+        self.block(None)?;
+        Ok(self.program)
+    }
+
+    fn next(&mut self) -> Option<&'a OwnedToken> {
+        let token = self.tokens.get(self.pos)?;
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn emit(&mut self, opcode: Opcode, operand: Operand, span: Span) -> usize {
+        let at = self.program.ip();
+        self.program.push(Instruction { opcode, operand, span });
+        at
+    }
+
+    /// Point the jump at `at` to `target`.
+    fn patch(&mut self, at: usize, target: usize) {
+        self.program.instructions[at].operand = Operand::Address(target);
+    }
+
+    /// Compile tokens up to the `⺘` closing a block opened at `open`, or
+    /// to the end of input for the top level (`None`).
+    fn block(&mut self, open: Option<Span>) -> Result<()> {
+        while let Some(token) = self.next() {
+            if token.kind == TokenKind::Symbol && token.text == "⺘" {
+                return open.map(|_| ()).ok_or_else(|| WofError::parse("⺘ without a matching ⺆", token.span));
+            }
+            self.token(token)?;
+        }
+        open.map_or(Ok(()), |span| Err(WofError::parse("⺆ is never closed", span)))
+    }
+
+    /// Consume the `⺆` that `form` requires next and compile its block.
+    fn required_block(&mut self, form: &OwnedToken, usage: &str) -> Result<()> {
+        match self.next() {
+            Some(open) if open.text == "⺆" => self.block(Some(open.span)),
+            _ => Err(WofError::parse(format!("{} requires: {usage}", form.text), form.span)),
+        }
+    }
+
+    /// The name argument of a form such as `字`.
+    fn name_after(&mut self, form: &OwnedToken, what: &str) -> Result<Operand> {
+        match self.next() {
+            Some(name) if name.kind == TokenKind::Symbol => Ok(Operand::Symbol(name.text.clone())),
+            _ => Err(WofError::parse(format!("{} requires a {what} name", form.text), form.span)),
+        }
+    }
+
+    fn token(&mut self, token: &OwnedToken) -> Result<()> {
+        let span = token.span;
+        let literal = |value| Ok((Opcode::PushLiteral, Operand::Value(value)));
+        let (opcode, operand) = match token.kind {
+            TokenKind::Integer => literal(WofValue::integer(
+                token.text.parse().map_err(|e: std::num::ParseIntError| WofError::parse(e.to_string(), span))?,
+            )),
+            TokenKind::Float => literal(WofValue::double(
+                token.text.parse().map_err(|e: std::num::ParseFloatError| WofError::parse(e.to_string(), span))?,
+            )),
+            TokenKind::String => literal(WofValue::string(crate::tokenizer::parse_string_literal(&token.text))),
+            TokenKind::LabelRef => literal(WofValue::symbol(format!("@{}", token.text.trim_start_matches('@')))),
+            TokenKind::Infix => {
+                let inner = token
+                    .text
+                    .strip_prefix("=(")
+                    .and_then(|t| t.strip_suffix(')'))
+                    .ok_or_else(|| WofError::parse("unclosed infix expression", span))?;
+                let origin = Span::with_length(span.line(), span.column() + 2, span.offset() + 2, 0);
+                for token in crate::infix::compile(inner, origin)? {
+                    self.token(&token)?;
+                }
+                return Ok(());
+            }
+            TokenKind::Label | TokenKind::Interpolated => Err(unsupported(token)),
+            TokenKind::Eof => return Ok(()),
+            TokenKind::Symbol => return self.symbol(token),
+        }?;
+        self.emit(opcode, operand, span);
+        Ok(())
+    }
+
+    fn symbol(&mut self, token: &OwnedToken) -> Result<()> {
+        let span = token.span;
+        match token.text.as_str() {
+            "⺆" => self.block(Some(span))?,
+            "若" | "if" => {
+                let branch = self.emit(Opcode::If, Operand::None, span);
+                self.required_block(token, "cond 若 ⺆ then ⺘ 或 ⺆ else ⺘")?;
+                if matches!(self.tokens.get(self.pos), Some(t) if t.text == "或" || t.text == "else") {
+                    let or_else = self.next().unwrap_or(token);
+                    let skip = self.emit(Opcode::Jump, Operand::None, or_else.span);
+                    self.patch(branch, self.program.ip());
+                    self.required_block(or_else, "cond 若 ⺆ then ⺘ 或 ⺆ else ⺘")?;
+                    self.patch(skip, self.program.ip());
+                } else {
+                    self.patch(branch, self.program.ip());
+                }
+            }
+            "或" | "else" => return Err(WofError::parse("或 (else) must follow a 若 ⺆ ... ⺘ block", span)),
+            "⟳" | "loop" => {
+                self.emit(Opcode::Loop, Operand::Count(LOOP_LIMIT), span);
+                self.counted_loop(token, None, "⟳ ⺆ body ⺘")?;
+            }
+            "⨯" | "times" | "repeat" => {
+                let enter = self.emit(Opcode::Repeat, Operand::None, span);
+                self.counted_loop(token, Some(enter), "N ⨯ ⺆ body ⺘")?;
+            }
+            "当" | "while" => self.while_loop(token)?,
+            "🛑" | "break" | "↻" | "continue" => {
+                let at = self.emit(
+                    if matches!(token.text.as_str(), "🛑" | "break") { Opcode::Break } else { Opcode::Continue },
+                    Operand::None,
+                    span,
+                );
+                let patches = self
+                    .loops
+                    .last_mut()
+                    .ok_or_else(|| WofError::parse(format!("{} outside of loop", token.text), span))?;
+                if matches!(token.text.as_str(), "🛑" | "break") {
+                    patches.breaks.push(at);
+                } else {
+                    patches.continues.push(at);
+                }
+            }
+            "字" | "define" | "let" => {
+                let name = self.name_after(token, "variable")?;
+                self.emit(Opcode::DefineVar, name, span);
+            }
+            "読" | "load" | "get" => {
+                let name = self.name_after(token, "variable")?;
+                self.emit(Opcode::ReadVar, name, span);
+            }
+            "支" | "set" | "store" => {
+                let name = self.name_after(token, "variable")?;
+                self.emit(Opcode::Set, name, span);
+            }
+            "巡" | "call" => {
+                let name = self.name_after(token, "function")?;
+                self.emit(Opcode::Call, name, span);
+            }
+            "⊕" | "fn" | "func" | "def" | "至" | "return" | "ret" | "bench" | "spawn" | "jit" | "record"
+            | "enum" | "schema" | "match_tag" | "map_ok" | "and_then" | "try_prop" | "try" | "catch" | "test_case" | "goto" | "jump" | "跳"
+            | ":labels" => return Err(unsupported(token)),
+            name => {
+                self.emit(Opcode::Op, Operand::Symbol(name.to_string()), span);
+            }
+        }
+        Ok(())
+    }
+
+    /// Compile the body of a `⟳` or `⨯` loop whose entry instruction was
+    /// just emitted; `enter` is patched to the exit for `⨯`, which skips
+    /// the loop for a count of zero.
+    fn counted_loop(&mut self, token: &OwnedToken, enter: Option<usize>, usage: &str) -> Result<()> {
+        let top = self.program.ip();
+        self.loops.push(LoopPatches::default());
+        self.required_block(token, usage)?;
+        let end = self.emit(Opcode::End, Operand::Address(top), token.span);
+        let exit = self.program.ip();
+        if let Some(enter) = enter {
+            self.patch(enter, exit);
+        }
+        self.close_loop(end, exit);
+        Ok(())
+    }
+
+    /// Compile `当 ⺆ cond ⺘ ⺆ body ⺘`.
+    fn while_loop(&mut self, token: &OwnedToken) -> Result<()> {
+        let usage = "当 ⺆ cond ⺘ ⺆ body ⺘";
+        self.emit(Opcode::Loop, Operand::Count(0), token.span);
+        let top = self.program.ip();
+        self.loops.push(LoopPatches::default());
+        self.required_block(token, usage)?;
+        let test = self.emit(Opcode::If, Operand::None, token.span);
+        self.required_block(token, usage)?;
+        let end = self.emit(Opcode::End, Operand::Address(top), token.span);
+        let stop = self.emit(Opcode::Break, Operand::None, token.span);
+        let exit = self.program.ip();
+        self.patch(test, stop);
+        self.patch(stop, exit);
+        self.close_loop(end, exit);
+        Ok(())
+    }
+
+    /// Resolve the innermost loop's `🛑` and `↻` jumps.
+    fn close_loop(&mut self, end: usize, exit: usize) {
+        let patches = self.loops.pop().unwrap_or_default();
+        for at in patches.breaks {
+            self.patch(at, exit);
+        }
+        for at in patches.continues {
+            self.patch(at, end);
+        }
+    }
+}
+
+fn unsupported(token: &OwnedToken) -> WofError {
+    WofError::parse(format!("'{}' cannot be compiled; run it with exec_line", token.text), token.span)
+}
+
+/// A loop running on the VM.
+struct Frame {
+    /// Iterations left, for `⨯`.
+    left: Option<i64>,
+    /// Iterations run, for `⟳` and `当`.
+    done: i64,
+    /// Iteration limit, 0 for none.
+    limit: i64,
+}
+
+impl Interpreter {
+    /// Compile a line to bytecode without running it. Macros are not
+    /// expanded, since that would run code; see
+    /// [`compile_source`](Self::compile_source).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the line does not parse or uses a form that is
+    /// not compiled (see the [module docs](crate::bytecode)).
+    pub fn compile_line(&self, line: &str) -> Result<Program> {
+        let tokens = self.line_tokens(line, 1)?;
+        Compiler::new(&tokens, line.to_string()).finish()
+    }
+
+    /// Compile a multi-line script to bytecode. Blocks may span lines,
+    /// and macros are defined and expanded as each line is read.
+    ///
+    /// # Errors
+    ///
+    /// As for [`compile_line`](Self::compile_line).
+    pub fn compile_source(&mut self, content: &str) -> Result<Program> {
+        let tokens = self.script_tokens(content)?;
+        Compiler::new(&tokens, content.to_string()).finish()
+    }
+
+    /// Compile a script file to bytecode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or as for
+    /// [`compile_line`](Self::compile_line).
+    pub fn compile_file(&mut self, path: impl AsRef<Path>) -> Result<Program> {
+        let content = fs::read_to_string(path).map_err(WofError::from)?;
+        self.compile_source(&content)
+    }
+
+    /// Run a compiled program against the current stack and variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error an instruction raises, located at its
+    /// source span; the stack keeps whatever the program had done.
+    pub fn exec_program(&mut self, program: &Program) -> Result<()> {
+        let ops: Vec<Option<BoxedOp<Self>>> = program
+            .instructions
+            .iter()
+            .map(|inst| match (inst.opcode, &inst.operand) {
+                (Opcode::Op, Operand::Symbol(name)) => self.registry().get_cloned(name),
+                _ => None,
+            })
+            .collect();
+        let depth = self.block_depth();
+        let result = self.run_program(program, &ops);
+        while self.block_depth() > depth {
+            self.pop_scope();
+        }
+        result
+    }
+
+    fn run_program(&mut self, program: &Program, ops: &[Option<BoxedOp<Self>>]) -> Result<()> {
+        let mut frames: Vec<Frame> = Vec::new();
+        let mut ip = 0;
+        while let Some(inst) = program.get(ip) {
+            let span = inst.span;
+            let address = || inst.address_operand().ok_or_else(|| malformed(inst));
+            let name = || inst.symbol_operand().ok_or_else(|| malformed(inst));
+            ip += 1;
+            match inst.opcode {
+                Opcode::PushLiteral => {
+                    let value = inst.value_operand().ok_or_else(|| malformed(inst))?;
+                    self.push(value.clone());
+                }
+                Opcode::Op => {
+                    let name = name()?;
+                    match &ops[ip - 1] {
+                        Some(op) => self.call_op(name, op, span, None)?,
+                        None if self.has_function(name) => self.run_function(name).map_err(|e| located(e, span))?,
+                        None => self.dispatch_symbol(name, span).map_err(|e| located(e, span))?,
+                    }
+                }
+                Opcode::DefineVar => {
+                    let value = self.pop().map_err(|e| located(e, span))?;
+                    self.define_var(name()?, value);
+                }
+                Opcode::ReadVar => {
+                    let value = self.get_var(name()?).map_err(|e| located(e, span))?;
+                    self.push(value);
+                }
+                Opcode::Set => {
+                    let value = self.pop().map_err(|e| located(e, span))?;
+                    self.set_var(name()?, value).map_err(|e| located(e, span))?;
+                }
+                Opcode::Call => self.run_function(name()?).map_err(|e| located(e, span))?,
+                Opcode::If => {
+                    if !self.pop().map_err(|e| located(e, span))?.is_truthy() {
+                        ip = address()?;
+                    }
+                }
+                Opcode::Jump | Opcode::Continue => ip = address()?,
+                Opcode::Repeat => {
+                    let count = self.stack_mut().pop_integer().map_err(|e| located(e, span))?;
+                    if count <= 0 {
+                        ip = address()?;
+                    } else {
+                        frames.push(Frame { left: Some(count), done: 0, limit: 0 });
+                        self.push_scope(BlockType::Loop);
+                    }
+                }
+                Opcode::Loop => {
+                    let limit = inst.count_operand().ok_or_else(|| malformed(inst))?;
+                    frames.push(Frame { left: None, done: 0, limit });
+                    self.push_scope(BlockType::Loop);
+                }
+                Opcode::End => {
+                    if self.cancel_token().reset() {
+                        return Err(WofError::interrupted(format!("in a loop at {span}")));
+                    }
+                    let frame = frames.last_mut().ok_or_else(|| malformed(inst))?;
+                    let again = if let Some(left) = &mut frame.left {
+                        *left -= 1;
+                        *left > 0
+                    } else {
+                        frame.done += 1;
+                        if frame.limit > 0 && frame.done > frame.limit {
+                            return Err(WofError::Runtime("infinite loop safety limit reached (1M iterations)".into()));
+                        }
+                        true
+                    };
+                    if again {
+                        ip = address()?;
+                    } else {
+                        frames.pop();
+                        self.pop_scope();
+                    }
+                }
+                Opcode::Break => {
+                    frames.pop().ok_or_else(|| malformed(inst))?;
+                    self.pop_scope();
+                    ip = address()?;
+                }
+                _ => return Err(malformed(inst)),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn malformed(inst: &Instruction) -> WofError {
+    WofError::runtime_at(format!("malformed instruction '{inst}'"), inst.span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        interp.register("+", |ctx| {
+            let b = ctx.stack_mut().pop_integer()?;
+            let a = ctx.stack_mut().pop_integer()?;
+            ctx.push(WofValue::integer(a + b));
+            Ok(())
+        });
+        interp.register("<", |ctx| {
+            let b = ctx.stack_mut().pop_integer()?;
+            let a = ctx.stack_mut().pop_integer()?;
+            ctx.push(WofValue::boolean(a < b));
+            Ok(())
+        });
+        interp.register("dup", |ctx| ctx.stack_mut().dup());
+        interp.register("drop", |ctx| ctx.stack_mut().drop());
+        interp
+    }
+
+    fn stack(interp: &Interpreter) -> Vec<String> {
+        interp.stack().iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn lowers_control_flow_to_jumps() {
+        let interp = make_interp();
+        let program = interp.compile_line("1 若 ⺆ 2 ⺘ 或 ⺆ 3 ⺘ 4 ⨯ ⺆ 🛑 ⺘").unwrap();
+        let listing: Vec<String> = program.instructions.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            listing,
+            ["push 1", "若 @4", "push 2", "@ @5", "push 3", "push 4", "⨯ @9", "🛑 @9", "終 @7"]
+        );
+    }
+
+    #[test]
+    fn programs_match_the_interpreter() {
+        let source = "0 字 i 0\n当 ⺆ 読 i 5 < ⺘ ⺆\n  読 i 1 + 支 i\n  3 ⨯ ⺆ 1 + 読 i 3 < 若 ⺆ ↻ ⺘ 🛑 ⺘\n⺘\n\
+                      読 i 2 < 若 ⺆ \"small\" ⺘ 或 ⺆ 0 若 ⺆ \"no\" ⺘ 或 ⺆ \"big\" ⺘ ⺘ =(2 + 3)";
+        let mut walked = make_interp();
+        walked.exec_source(source).unwrap();
+        let mut compiled = make_interp();
+        let program = compiled.compile_source(source).unwrap();
+        for _ in 0..2 {
+            compiled.stack_mut().clear();
+            compiled.exec_program(&program).unwrap();
+            assert_eq!(stack(&compiled), stack(&walked));
+        }
+        assert_eq!(stack(&walked), ["9", "big", "5"]);
+        assert_eq!(compiled.block_depth(), walked.block_depth());
+    }
+
+    #[test]
+    fn calls_functions_defined_by_the_interpreter() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ inc ⺆ 1 + ⺘").unwrap();
+        let program = interp.compile_line("1 巡 inc inc missing").unwrap();
+        interp.exec_program(&program).unwrap();
+        assert_eq!(stack(&interp), ["3", "missing"]);
+    }
+
+    #[test]
+    fn rejects_what_it_cannot_compile() {
+        let interp = make_interp();
+        for line in ["⊕ f ⺆ 1 ⺘", ":top 1", "1 ⺘", "1 若 ⺆ 2", "🛑", "或 ⺆ 1 ⺘", "字", "$\"{1}\""] {
+            assert!(interp.compile_line(line).is_err(), "{line}");
+        }
+    }
+
+    #[test]
+    fn runtime_errors_and_limits() {
+        let mut interp = make_interp();
+        let err = interp.exec_program(&interp.compile_line("1 2 drop drop +").unwrap()).unwrap_err();
+        assert!(err.to_string().contains("1:15"), "{err}");
+
+        let depth = interp.block_depth();
+        let err = interp.exec_program(&interp.compile_line("⟳ ⺆ ⺘").unwrap()).unwrap_err();
+        assert!(err.to_string().contains("safety limit"), "{err}");
+        assert_eq!(interp.block_depth(), depth);
+
+        interp.cancel_token().cancel();
+        let err = interp.exec_program(&interp.compile_line("2 ⨯ ⺆ ⺘").unwrap()).unwrap_err();
+        assert!(matches!(err, WofError::Interrupted(_)), "{err}");
+    }
+}
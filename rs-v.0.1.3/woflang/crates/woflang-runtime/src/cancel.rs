@@ -0,0 +1,58 @@
+//! Cooperative cancellation (Ctrl-C).
+//!
+//! Every interpreter owns a [`CancelToken`]. A signal handler (or any
+//! other thread) holding a clone calls [`CancelToken::cancel`]; the
+//! interpreter notices before dispatching its next token and fails the
+//! current line with [`WofError::Interrupted`], leaving the stack as it
+//! was at that point. Long-running ops check
+//! [`Interpreter::is_cancelled`](crate::Interpreter::is_cancelled)
+//! between units of work and report how far they got:
+//!
+//! ```
+//! use woflang_runtime::Interpreter;
+//!
+//! let mut interp = Interpreter::new();
+//! interp.cancel_token().cancel();
+//! assert!(interp.exec_line("1 2").is_err());
+//! assert!(interp.exec_line("1 2").is_ok());
+//! ```
+//!
+//! [`WofError::Interrupted`]: woflang_core::WofError::Interrupted
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A shared flag requesting that the current computation stop.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Create a token that is not cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation. Safe to call from a signal handler thread.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether cancellation has been requested and not yet handled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// The underlying flag, for native code that polls it.
+    #[cfg(feature = "jit")]
+    #[must_use]
+    pub(crate) fn flag(&self) -> &AtomicBool {
+        &self.0
+    }
+
+    /// Withdraw a pending request, returning whether there was one.
+    pub fn reset(&self) -> bool {
+        self.0.swap(false, Ordering::SeqCst)
+    }
+}
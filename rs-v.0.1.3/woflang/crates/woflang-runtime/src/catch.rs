@@ -0,0 +1,124 @@
+//! Catching errors in scripts: `try ⺆ ... ⺘ catch ⺆ ... ⺘`.
+//!
+//! When the try block fails, the stack goes back to how it was before
+//! the block, an `Error` record describing the failure is pushed, and the
+//! catch block runs. When it succeeds, the catch block is skipped.
+//!
+//! | Field      | Value |
+//! |------------|-------|
+//! | `message`  | What went wrong, e.g. `"division by zero"` |
+//! | `kind`     | The kind of error, e.g. `"division_by_zero"` or `"runtime"` |
+//! | `line`     | Line of the token that failed (0 if unknown) |
+//! | `column`   | Its column (0 if unknown) |
+//!
+//! ```text
+//! try ⺆ 1 0 / ⺘ catch ⺆ .kind print 0 ⺘     # prints division_by_zero, leaves 0
+//! ```
+//!
+//! The standard ops register the `Error` record's accessors (`.message`,
+//! `.kind`, `.line`, `.column`) and `throw`. Interruptions (Ctrl-C) are
+//! never caught.
+
+use woflang_core::{InterpreterContext, RecordType, Result, Span, WofError, WofRecord, WofValue};
+
+use crate::{Interpreter, OwnedToken};
+
+/// The fields of the `Error` records a catch block receives, in order.
+pub const ERROR_FIELDS: [&str; 4] = ["message", "kind", "line", "column"];
+
+impl Interpreter {
+    /// Run `body`; if it fails, restore the stack, push the error as an
+    /// `Error` record and run `handler`.
+    pub(crate) fn try_catch(&mut self, body: Vec<OwnedToken>, handler: Vec<OwnedToken>) -> Result<()> {
+        let snapshot = self.stack().clone();
+        let (error, at) = match self.run_guarded(body) {
+            Ok(()) => return Ok(()),
+            Err((e @ WofError::Interrupted(_), _)) => return Err(e),
+            Err(failure) => failure,
+        };
+        *self.stack_mut() = snapshot;
+        self.push(error_value(&error, at));
+        self.run_nested(handler)
+    }
+}
+
+/// An `Error` record describing `error`, located at its own span or
+/// else at `at`, the token that raised it.
+fn error_value(error: &WofError, at: Span) -> WofValue {
+    let span = error.span().unwrap_or(at);
+    let (line, column) = if span.is_synthetic() { (0, 0) } else { (span.line(), span.column()) };
+    let values = vec![
+        WofValue::string(error.message()),
+        WofValue::string(error.kind()),
+        WofValue::integer(i64::from(line)),
+        WofValue::integer(i64::from(column)),
+    ];
+    let record = WofRecord::new(RecordType::new("Error", ERROR_FIELDS), values).expect("one value per field");
+    WofValue::record(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interpreter;
+    use woflang_core::{InterpreterContext, WofError, WofValue};
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        interp.register("/", |interp| {
+            let b = interp.stack_mut().pop_numeric()?;
+            let a = interp.stack_mut().pop_numeric()?;
+            if b == 0.0 {
+                return Err(WofError::DivisionByZero);
+            }
+            interp.push(WofValue::double(a / b));
+            Ok(())
+        });
+        interp.register("fail", |_| Err(WofError::Runtime("boom".into())));
+        interp.define_record("Error", &super::ERROR_FIELDS.map(String::from)).unwrap();
+        interp
+    }
+
+    #[test]
+    fn catches_errors_with_the_stack_restored() {
+        let mut interp = make_interp();
+        interp.exec_line("5 try ⺆ drop 1 0 / ⺘ catch ⺆ .kind ⺘ 7").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::integer(7));
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::string("division_by_zero"));
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::integer(5));
+
+        interp.exec_line("try ⺆ 6 2 / ⺘ catch ⺆ drop 0 ⺘").unwrap();
+        assert_eq!(interp.stack_mut().pop_numeric().unwrap(), 3.0);
+        assert!(interp.stack().is_empty());
+    }
+
+    #[test]
+    fn error_records_carry_message_and_location() {
+        let mut interp = make_interp();
+        interp.exec_source("1\n2 try ⺆ 3 fail ⺘ catch ⺆ 字 e e .message 字 m e .line 字 l e .column ⺘").unwrap();
+        assert_eq!(interp.get_var("m").unwrap(), WofValue::string("boom"));
+        assert_eq!(interp.get_var("l").unwrap(), WofValue::integer(2));
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::integer(11));
+
+        // Nested: the inner handler's error reaches the outer one
+        interp.exec_line("try ⺆ try ⺆ fail ⺘ catch ⺆ .message fail ⺘ ⺘ catch ⺆ .kind ⺘").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::string("runtime"));
+
+        assert!(interp.exec_line("try ⺆ 1 ⺘").is_err());
+        assert!(interp.exec_line("catch ⺆ 1 ⺘").is_err());
+    }
+
+    #[test]
+    fn returns_leave_the_function_from_either_block() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ f ⺆ try ⺆ 1 至 ⺘ catch ⺆ .kind ⺘ 99 ⺘").unwrap();
+        interp.exec_line("⊕ g ⺆ try ⺆ fail ⺘ catch ⺆ .message 至 ⺘ 99 ⺘").unwrap();
+        interp.exec_line("f 5 6 g 7").unwrap();
+        let values: Vec<_> = interp.stack().as_slice().iter().map(ToString::to_string).collect();
+        assert_eq!(values, ["1", "5", "6", "boom", "7"]);
+
+        // The caller's errors are not caught by the function's handler.
+        interp.stack_mut().clear();
+        assert!(interp.exec_line("f fail").is_err());
+        assert!(interp.exec_line("g 1 0 /").is_err());
+    }
+}
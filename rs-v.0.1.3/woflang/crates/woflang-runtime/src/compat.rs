@@ -0,0 +1,129 @@
+//! Script language versions: the `@wof-version` directive and the ops
+//! renamed since older versions.
+//!
+//! A script names the language version it was written for on a line of
+//! its own:
+//!
+//! ```text
+//! @wof-version 0.0.3
+//! 2 stack_dup *
+//! ```
+//!
+//! Ops renamed after the declared version run under their new names,
+//! with a deprecation note on stderr for each old name the script uses.
+//! A script declaring a version newer than [`LANGUAGE_VERSION`] is
+//! refused before any of it runs. Scripts without the directive run as
+//! current ones, with no renames.
+
+use woflang_core::{Result, Span, WofError};
+
+/// The script language version this interpreter implements.
+pub const LANGUAGE_VERSION: &str = "0.1.3";
+
+/// The directive declaring a script's language version.
+const DIRECTIVE: &str = "@wof-version";
+
+/// An op renamed between language versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rename {
+    /// The name scripts written before [`since`](Self::since) use.
+    pub old: &'static str,
+    /// The op's current name.
+    pub new: &'static str,
+    /// The language version that introduced the new name.
+    pub since: &'static str,
+}
+
+/// Every renamed op, oldest rename first.
+pub const RENAMED_OPS: &[Rename] = &[
+    Rename { old: "stack_dup", new: "dup", since: "0.1.0" },
+    Rename { old: "stack_swap", new: "swap", since: "0.1.0" },
+    Rename { old: "stack_drop", new: "drop", since: "0.1.0" },
+    Rename { old: "assert_true", new: "assert", since: "0.1.0" },
+    Rename { old: "graph_col_new", new: "graph_new", since: "0.1.0" },
+    Rename { old: "graph_col_add_edge", new: "graph_add_edge", since: "0.1.0" },
+    Rename { old: "graph_col_clear", new: "graph_clear", since: "0.1.0" },
+];
+
+/// The version a `@wof-version` line declares, if `line` is one. The
+/// version is empty when the line gives none.
+pub(crate) fn directive(line: &str) -> Option<&str> {
+    let rest = line.trim().strip_prefix(DIRECTIVE)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(rest.split_whitespace().next().unwrap_or(""))
+}
+
+/// The renames a script written for language version `declared` needs,
+/// or an error at `span` if `declared` is malformed or newer than
+/// [`LANGUAGE_VERSION`].
+pub(crate) fn renames_for(declared: &str, span: Span) -> Result<Vec<&'static Rename>> {
+    let Some(version) = parse(declared) else {
+        return Err(WofError::parse(
+            format!("invalid {DIRECTIVE} '{declared}': expected a version such as 0.1.3"),
+            span,
+        ));
+    };
+    if version > current() {
+        return Err(WofError::parse(
+            format!(
+                "this script requires woflang {declared}, but this interpreter only supports \
+                 scripts up to {LANGUAGE_VERSION}; upgrade woflang to run it"
+            ),
+            span,
+        ));
+    }
+    Ok(RENAMED_OPS
+        .iter()
+        .filter(|rename| parse(rename.since).is_some_and(|since| version < since))
+        .collect())
+}
+
+/// The deprecation note for a use of `rename.old` on line `line`.
+pub(crate) fn note(rename: &Rename, line: u32) -> String {
+    format!(
+        "note: line {line}: '{}' was renamed to '{}' in woflang {}; running it as '{}'",
+        rename.old, rename.new, rename.since, rename.new
+    )
+}
+
+fn current() -> [u64; 3] {
+    parse(LANGUAGE_VERSION).unwrap_or_default()
+}
+
+/// `major[.minor[.patch]]`, missing parts taken as zero.
+fn parse(version: &str) -> Option<[u64; 3]> {
+    let mut parts = [0; 3];
+    for (i, part) in version.split('.').enumerate() {
+        *parts.get_mut(i)? = part.parse().ok()?;
+    }
+    Some(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_directives() {
+        assert_eq!(directive("@wof-version 0.0.3"), Some("0.0.3"));
+        assert_eq!(directive("  @wof-version 0.1   # old"), Some("0.1"));
+        assert_eq!(directive("@wof-version"), Some(""));
+        assert_eq!(directive("@wof-versions 1"), None);
+        assert_eq!(directive("@top 1 2"), None);
+    }
+
+    #[test]
+    fn selects_renames_by_version() {
+        let span = Span::default();
+        assert_eq!(renames_for("0.0.3", span).unwrap().len(), RENAMED_OPS.len());
+        assert!(renames_for("0.1", span).unwrap().is_empty());
+        assert!(renames_for(LANGUAGE_VERSION, span).unwrap().is_empty());
+
+        let newer = renames_for("0.2.0", span).unwrap_err().to_string();
+        assert!(newer.contains("requires woflang 0.2.0"), "{newer}");
+        assert!(renames_for("", span).is_err());
+        assert!(renames_for("0.1.2.3", span).is_err());
+    }
+}
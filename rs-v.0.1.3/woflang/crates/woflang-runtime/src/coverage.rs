@@ -0,0 +1,218 @@
+//! Coverage tracking for Woflang scripts and test suites.
+//!
+//! When enabled on an [`Interpreter`](crate::Interpreter), coverage
+//! records how often each registered op and user-defined function ran,
+//! and which lines of each executed script file were reached. The result
+//! can be printed as a short summary or exported in lcov format for
+//! standard coverage tooling.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// How many unexercised ops the summary lists by name.
+const SUMMARY_OP_LIMIT: usize = 20;
+
+/// Per-file line hit counts.
+#[derive(Debug, Clone, Default)]
+struct FileCoverage {
+    /// Hits per 1-based line; executable lines start at zero.
+    lines: BTreeMap<u32, u64>,
+    /// User functions defined in this file, by definition line.
+    functions: BTreeMap<String, u32>,
+}
+
+/// Collected coverage data.
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    ops: BTreeMap<String, u64>,
+    functions: BTreeMap<String, u64>,
+    files: BTreeMap<String, FileCoverage>,
+    /// Last line recorded, so consecutive tokens on one line count once.
+    last_line: Option<u32>,
+}
+
+impl Coverage {
+    /// Create an empty coverage record.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record_op(&mut self, name: &str) {
+        *self.ops.entry(name.to_string()).or_default() += 1;
+    }
+
+    pub(crate) fn record_function(&mut self, name: &str) {
+        *self.functions.entry(name.to_string()).or_default() += 1;
+    }
+
+    /// Record that execution reached `line` of `file`. Consecutive
+    /// tokens on the same line count as one hit.
+    pub(crate) fn record_line(&mut self, file: &str, line: u32) {
+        if self.last_line == Some(line) {
+            return;
+        }
+        self.last_line = Some(line);
+        if let Some(f) = self.files.get_mut(file) {
+            *f.lines.entry(line).or_default() += 1;
+        }
+    }
+
+    /// Forget the last recorded line (at the start of a new file).
+    pub(crate) fn reset_line(&mut self) {
+        self.last_line = None;
+    }
+
+    /// Register the executable lines of `file` so unreached lines are
+    /// reported with zero hits.
+    pub(crate) fn register_file(&mut self, file: &str, lines: impl IntoIterator<Item = u32>) {
+        let entry = self.files.entry(file.to_string()).or_default();
+        for line in lines {
+            entry.lines.entry(line).or_default();
+        }
+    }
+
+    pub(crate) fn register_function(&mut self, file: &str, name: &str, line: u32) {
+        if let Some(f) = self.files.get_mut(file) {
+            f.functions.insert(name.to_string(), line);
+        }
+    }
+
+    /// Number of times the registered op `name` ran.
+    #[must_use]
+    pub fn op_hits(&self, name: &str) -> u64 {
+        self.ops.get(name).copied().unwrap_or(0)
+    }
+
+    /// Number of times the user function `name` was called.
+    #[must_use]
+    pub fn function_hits(&self, name: &str) -> u64 {
+        self.functions.get(name).copied().unwrap_or(0)
+    }
+
+    /// Hit count for `line` of `file`, or `None` if it is not an
+    /// executable line of a tracked file.
+    #[must_use]
+    pub fn line_hits(&self, file: &str, line: u32) -> Option<u64> {
+        self.files.get(file)?.lines.get(&line).copied()
+    }
+
+    /// Render a human-readable summary against the ops and functions
+    /// that were available.
+    #[must_use]
+    pub fn summary<'a>(
+        &self,
+        ops: impl IntoIterator<Item = &'a str>,
+        functions: impl IntoIterator<Item = &'a str>,
+    ) -> String {
+        let mut ops: Vec<&str> = ops.into_iter().collect();
+        let mut functions: Vec<&str> = functions.into_iter().collect();
+        ops.sort_unstable();
+        functions.sort_unstable();
+
+        let missed_ops: Vec<&str> = ops.iter().copied().filter(|o| self.op_hits(o) == 0).collect();
+        let missed_fns: Vec<&str> =
+            functions.iter().copied().filter(|f| self.function_hits(f) == 0).collect();
+
+        let mut out = String::from("Coverage summary:\n");
+        let _ = writeln!(out, "  ops:       {}", ratio(ops.len() - missed_ops.len(), ops.len()));
+        let _ = writeln!(
+            out,
+            "  functions: {}",
+            ratio(functions.len() - missed_fns.len(), functions.len())
+        );
+        for (file, f) in &self.files {
+            let hit = f.lines.values().filter(|&&h| h > 0).count();
+            let _ = writeln!(out, "  {file}: lines {}", ratio(hit, f.lines.len()));
+        }
+
+        if !missed_fns.is_empty() {
+            let _ = writeln!(out, "  unexercised functions: {}", missed_fns.join(", "));
+        }
+        if !missed_ops.is_empty() {
+            let shown = &missed_ops[..missed_ops.len().min(SUMMARY_OP_LIMIT)];
+            let _ = write!(out, "  unexercised ops: {}", shown.join(", "));
+            if missed_ops.len() > shown.len() {
+                let _ = write!(out, " … and {} more", missed_ops.len() - shown.len());
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Export line and function coverage of tracked files in lcov format.
+    #[must_use]
+    pub fn to_lcov(&self) -> String {
+        let mut out = String::new();
+        for (file, f) in &self.files {
+            let _ = writeln!(out, "TN:\nSF:{file}");
+            for (name, line) in &f.functions {
+                let _ = writeln!(out, "FN:{line},{name}");
+            }
+            for name in f.functions.keys() {
+                let _ = writeln!(out, "FNDA:{},{name}", self.function_hits(name));
+            }
+            let fn_hit = f.functions.keys().filter(|n| self.function_hits(n) > 0).count();
+            let _ = writeln!(out, "FNF:{}\nFNH:{fn_hit}", f.functions.len());
+            for (line, hits) in &f.lines {
+                let _ = writeln!(out, "DA:{line},{hits}");
+            }
+            let hit = f.lines.values().filter(|&&h| h > 0).count();
+            let _ = writeln!(out, "LF:{}\nLH:{hit}\nend_of_record", f.lines.len());
+        }
+        out
+    }
+}
+
+fn ratio(hit: usize, total: usize) -> String {
+    if total == 0 {
+        return "0/0".to_string();
+    }
+    format!("{hit}/{total} ({:.1}%)", 100.0 * hit as f64 / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interpreter;
+    use woflang_core::{InterpreterContext, WofValue};
+
+    #[test]
+    fn tracks_ops_functions_and_lines() {
+        let dir = std::env::temp_dir().join(format!("wof_cov_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("suite.wof");
+        std::fs::write(&path, "# suite\n⊕ sq ⺆ dup * ⺘\n⊕ unused ⺆ 1 ⺘\n3 sq\n\n4 sq\n").unwrap();
+
+        let mut interp = Interpreter::new();
+        interp.register("dup", |ctx| ctx.stack_mut().dup());
+        interp.register("*", |ctx| {
+            let b = ctx.stack_mut().pop_numeric()?;
+            let a = ctx.stack_mut().pop_numeric()?;
+            ctx.push(WofValue::double(a * b));
+            Ok(())
+        });
+        interp.register("never", |_| Ok(()));
+        interp.enable_coverage();
+        interp.exec_file(&path).unwrap();
+
+        let cov = interp.coverage().unwrap();
+        let file = path.display().to_string();
+        assert_eq!(cov.op_hits("dup"), 2);
+        assert_eq!(cov.function_hits("sq"), 2);
+        assert_eq!(cov.line_hits(&file, 1), None);
+        assert_eq!(cov.line_hits(&file, 2), Some(3));
+        assert_eq!(cov.line_hits(&file, 3), Some(1));
+        assert_eq!(cov.line_hits(&file, 4), Some(1));
+        assert_eq!(cov.line_hits(&file, 6), Some(1));
+
+        let summary = cov.summary(interp.registry().names(), interp.function_names());
+        assert!(summary.contains("ops:       2/3"));
+        assert!(summary.contains("unexercised functions: unused"));
+
+        let lcov = cov.to_lcov();
+        assert!(lcov.contains("FN:2,sq") && lcov.contains("FNDA:0,unused"));
+        assert!(lcov.contains("DA:4,1") && lcov.contains("end_of_record"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,343 @@
+//! Debugger: breakpoints, single-stepping and frame inspection.
+//!
+//! A debugger is a frontend that the interpreter calls whenever execution
+//! pauses, before the token about to run. The frontend can look at the
+//! stack, scopes and [`frames`](crate::Interpreter::frames), then says how
+//! to go on:
+//!
+//! ```
+//! use woflang_runtime::{Breakpoint, DebugAction, DebugFrontend, Interpreter, Pause};
+//!
+//! /// Steps through everything, noting each token.
+//! struct Tracer(Vec<String>);
+//!
+//! impl DebugFrontend for Tracer {
+//!     fn paused(&mut self, _interp: &mut Interpreter, pause: &Pause) -> DebugAction {
+//!         self.0.push(pause.token.clone());
+//!         DebugAction::Step
+//!     }
+//! }
+//!
+//! let mut interp = Interpreter::new();
+//! interp.attach_debugger(Tracer(Vec::new()));
+//! interp.add_breakpoint(Breakpoint::Op("+".into()));
+//! interp.exec_line("1 2 + 3 +").unwrap();
+//! assert!(interp.has_debugger());
+//! ```
+//!
+//! Execution pauses at a breakpoint, or at every token while stepping.
+//! An op breakpoint matches any symbol of that name, including user
+//! functions and control words; a line breakpoint pauses at the first
+//! token run on that line after any other line. Tokens being collected
+//! into a function or loop body, or skipped by a branch, never pause.
+//!
+//! With no debugger attached, or one with nothing to stop at, the
+//! dispatch cost is a single flag check.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use woflang_core::Span;
+
+use crate::{Interpreter, LoopType};
+
+/// Where execution should pause.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Breakpoint {
+    /// Before any symbol with this name.
+    Op(String),
+    /// At a source line.
+    Line(u32),
+}
+
+impl Breakpoint {
+    /// Read a breakpoint as written after `:break`: a line number, or
+    /// otherwise an op name.
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        text.parse().map_or_else(|_| Self::Op(text.to_string()), Self::Line)
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Op(name) => write!(f, "op '{name}'"),
+            Self::Line(line) => write!(f, "line {line}"),
+        }
+    }
+}
+
+/// Why execution paused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PauseReason {
+    /// A breakpoint was reached.
+    Breakpoint(Breakpoint),
+    /// The previous pause asked to step.
+    Step,
+}
+
+/// A pause before a token runs.
+#[derive(Debug, Clone)]
+pub struct Pause {
+    /// Why execution paused.
+    pub reason: PauseReason,
+    /// The text of the token about to run.
+    pub token: String,
+    /// Where the token is in its source.
+    pub span: Span,
+}
+
+/// How to go on after a pause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugAction {
+    /// Run the token, then pause before the next one.
+    Step,
+    /// Run until the next breakpoint.
+    Continue,
+    /// Stop the computation with an interruption error.
+    Abort,
+}
+
+/// The interactive side of a debugger.
+///
+/// While a frontend runs, its debugger is detached, so code it runs in
+/// the interpreter does not pause again.
+pub trait DebugFrontend: Send {
+    /// Called when execution pauses, before `pause.token` runs.
+    fn paused(&mut self, interp: &mut Interpreter, pause: &Pause) -> DebugAction;
+}
+
+/// One level of what is running, as listed by
+/// [`Interpreter::frames`](crate::Interpreter::frames).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebugFrame {
+    /// A call to a user-defined function.
+    Call {
+        /// The function's name.
+        function: String,
+    },
+    /// A loop being run.
+    Loop {
+        /// The kind of loop.
+        loop_type: LoopType,
+        /// The current pass, counting from 1.
+        iteration: i64,
+    },
+}
+
+impl fmt::Display for DebugFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Call { function } => write!(f, "call {function}"),
+            Self::Loop { loop_type, iteration } => match loop_type {
+                LoopType::Infinite => write!(f, "loop ⟳, iteration {iteration}"),
+                LoopType::Repeat(n) => write!(f, "loop {n} ⨯, iteration {iteration} of {n}"),
+                LoopType::While => write!(f, "loop 当, iteration {iteration}"),
+            },
+        }
+    }
+}
+
+/// Breakpoints, stepping state and the attached frontend.
+#[derive(Default)]
+pub(crate) struct Debugger {
+    breakpoints: BTreeSet<Breakpoint>,
+    stepping: bool,
+    /// Line of the last token checked, so a line breakpoint pauses once
+    /// on entering its line.
+    last_line: Option<u32>,
+    frontend: Option<Box<dyn DebugFrontend>>,
+    /// Whether a token could pause: a frontend is attached and there is
+    /// something to stop at.
+    armed: bool,
+}
+
+impl Debugger {
+    fn rearm(&mut self) {
+        self.armed = self.frontend.is_some() && (self.stepping || !self.breakpoints.is_empty());
+    }
+
+    #[inline]
+    pub(crate) fn is_armed(&self) -> bool {
+        self.armed
+    }
+
+    pub(crate) fn is_attached(&self) -> bool {
+        self.frontend.is_some()
+    }
+
+    pub(crate) fn attach(&mut self, frontend: Box<dyn DebugFrontend>) {
+        self.frontend = Some(frontend);
+        self.rearm();
+    }
+
+    pub(crate) fn detach(&mut self) -> Option<Box<dyn DebugFrontend>> {
+        let frontend = self.frontend.take();
+        self.rearm();
+        frontend
+    }
+
+    pub(crate) fn add(&mut self, breakpoint: Breakpoint) -> bool {
+        let added = self.breakpoints.insert(breakpoint);
+        self.rearm();
+        added
+    }
+
+    pub(crate) fn remove(&mut self, breakpoint: &Breakpoint) -> bool {
+        let removed = self.breakpoints.remove(breakpoint);
+        self.rearm();
+        removed
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.breakpoints.clear();
+        self.rearm();
+    }
+
+    pub(crate) fn breakpoints(&self) -> impl Iterator<Item = &Breakpoint> {
+        self.breakpoints.iter()
+    }
+
+    pub(crate) fn set_stepping(&mut self, stepping: bool) {
+        self.stepping = stepping;
+        self.rearm();
+    }
+
+    /// Why a symbol or literal `text` at `span` should pause, if it should.
+    pub(crate) fn check(&mut self, text: &str, is_symbol: bool, span: Span) -> Option<PauseReason> {
+        let line = span.line();
+        let new_line = self.last_line.replace(line) != Some(line);
+        if self.stepping {
+            return Some(PauseReason::Step);
+        }
+        if is_symbol {
+            let op = Breakpoint::Op(text.to_string());
+            if self.breakpoints.contains(&op) {
+                return Some(PauseReason::Breakpoint(op));
+            }
+        }
+        let at_line = Breakpoint::Line(line);
+        (new_line && self.breakpoints.contains(&at_line)).then_some(PauseReason::Breakpoint(at_line))
+    }
+
+    /// Take the frontend out for the length of a pause.
+    pub(crate) fn take_frontend(&mut self) -> Option<Box<dyn DebugFrontend>> {
+        let frontend = self.frontend.take();
+        self.rearm();
+        frontend
+    }
+
+    /// Put the frontend back after a pause and apply the action it chose,
+    /// unless a new one was attached meanwhile.
+    pub(crate) fn resume(&mut self, frontend: Box<dyn DebugFrontend>, action: DebugAction) {
+        if self.frontend.is_none() {
+            self.frontend = Some(frontend);
+        }
+        self.stepping = action == DebugAction::Step;
+        self.rearm();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use woflang_core::{InterpreterContext, WofError};
+
+    /// Token, line, stack depth and frames at each pause.
+    type Seen = Arc<Mutex<Vec<(String, u32, usize, Vec<DebugFrame>)>>>;
+
+    /// Answers each pause with the next scripted action (then
+    /// continues), recording what it saw.
+    struct Script {
+        actions: Vec<DebugAction>,
+        seen: Seen,
+    }
+
+    impl DebugFrontend for Script {
+        fn paused(&mut self, interp: &mut Interpreter, pause: &Pause) -> DebugAction {
+            let entry = (pause.token.clone(), pause.span.line(), interp.stack().len(), interp.frames());
+            self.seen.lock().unwrap().push(entry);
+            if self.actions.is_empty() { DebugAction::Continue } else { self.actions.remove(0) }
+        }
+    }
+
+    fn make_interp(actions: Vec<DebugAction>) -> (Interpreter, Seen) {
+        let mut interp = Interpreter::new();
+        interp.register("+", |interp| {
+            let b = interp.stack_mut().pop_integer()?;
+            let a = interp.stack_mut().pop_integer()?;
+            interp.push(woflang_core::WofValue::integer(a + b));
+            Ok(())
+        });
+        let seen = Seen::default();
+        interp.attach_debugger(Script { actions, seen: seen.clone() });
+        (interp, seen)
+    }
+
+    fn tokens(seen: &Seen) -> Vec<String> {
+        seen.lock().unwrap().iter().map(|(token, ..)| token.clone()).collect()
+    }
+
+    #[test]
+    fn breakpoints_pause_and_steps_follow() {
+        let (mut interp, seen) = make_interp(vec![DebugAction::Step, DebugAction::Step]);
+        assert!(interp.add_breakpoint(Breakpoint::parse("+")));
+        assert!(!interp.add_breakpoint(Breakpoint::Op("+".into())));
+
+        interp.exec_line("1 2 + 3 + 4 +").unwrap();
+        // Paused at the first +, stepped twice, then continued to the last +
+        assert_eq!(tokens(&seen), ["+", "3", "+", "+"]);
+        assert_eq!(seen.lock().unwrap()[1].2, 1);
+        assert_eq!(interp.stack_mut().pop_integer().unwrap(), 10);
+
+        assert!(interp.remove_breakpoint(&Breakpoint::Op("+".into())));
+        assert!(interp.breakpoints().is_empty());
+        interp.exec_line("1 2 +").unwrap();
+        assert_eq!(tokens(&seen).len(), 4);
+    }
+
+    #[test]
+    fn line_breakpoints_pause_once_per_entry() {
+        let (mut interp, seen) = make_interp(Vec::new());
+        interp.add_breakpoint(Breakpoint::parse(" 2 "));
+        interp.exec_source("1\n2 3 +\n4 +\n").unwrap();
+        assert_eq!(tokens(&seen), ["2"]);
+        assert_eq!(interp.breakpoints(), [&Breakpoint::Line(2)]);
+    }
+
+    #[test]
+    fn frames_list_calls_and_loops_outermost_first() {
+        let (mut interp, seen) = make_interp(Vec::new());
+        interp.add_breakpoint(Breakpoint::Op("+".into()));
+        interp.exec_line("⊕ inner ⺆ 1 + ⺘ ⊕ outer ⺆ 2 ⨯ ⺆ inner ⺘ ⺘ 0 outer").unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        let call = |f: &str| DebugFrame::Call { function: f.into() };
+        let pass = |i| DebugFrame::Loop { loop_type: LoopType::Repeat(2), iteration: i };
+        assert_eq!(seen[0].3, [call("outer"), pass(1), call("inner")]);
+        assert_eq!(seen[1].3, [call("outer"), pass(2), call("inner")]);
+        assert_eq!(pass(2).to_string(), "loop 2 ⨯, iteration 2 of 2");
+        drop(seen);
+        assert!(interp.frames().is_empty());
+    }
+
+    #[test]
+    fn stepping_and_aborting() {
+        let (mut interp, seen) = make_interp(vec![DebugAction::Step, DebugAction::Abort]);
+        interp.step();
+        let err = interp.exec_line("1 2 +").unwrap_err();
+        assert!(matches!(err, WofError::Interrupted(_)), "{err}");
+        assert_eq!(tokens(&seen), ["1", "2"]);
+
+        // Once detached, nothing pauses.
+        interp.step();
+        assert!(interp.detach_debugger());
+        assert!(!interp.has_debugger());
+        interp.exec_line("3 4").unwrap();
+        assert_eq!(tokens(&seen).len(), 2);
+    }
+}
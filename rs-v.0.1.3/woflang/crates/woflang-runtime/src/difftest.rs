@@ -0,0 +1,308 @@
+//! Differential testing between execution backends.
+//!
+//! A [`Backend`] runs a script and reports its [`Outcome`]: the final
+//! stack, what it printed and the error it stopped with, if any.
+//! [`diff_corpus`] runs every script of a corpus through two backends
+//! and collects the scripts where they disagree, guarding an optimised
+//! execution path against the tree-walking interpreter it must match.
+//!
+//! The corpus in `woflang-ops/tests/difftest` runs through the bytecode
+//! VM with every standard op registered as part of the test suite.
+//!
+//! ```
+//! use woflang_runtime::difftest::{diff_corpus, InterpreterBackend};
+//! use woflang_runtime::Interpreter;
+//!
+//! let corpus = vec![("push".to_string(), "1 2 3".to_string())];
+//! let mut a = InterpreterBackend::new("a", Interpreter::new);
+//! let mut b = InterpreterBackend::new("b", Interpreter::new);
+//! assert!(diff_corpus(&mut a, &mut b, &corpus).is_clean());
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use woflang_core::{Buffer, InterpreterContext, WofValue};
+
+use crate::Interpreter;
+
+/// The observable result of running one script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// Final stack, bottom first.
+    pub stack: Vec<WofValue>,
+    /// Everything the script printed.
+    pub output: String,
+    /// Error message if the script stopped early.
+    pub error: Option<String>,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, value) in self.stack.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{value}")?;
+        }
+        write!(f, "]")?;
+        if !self.output.is_empty() {
+            write!(f, " output: {:?}", self.output)?;
+        }
+        if let Some(err) = &self.error {
+            write!(f, " error: {err}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Something that can execute Woflang source.
+pub trait Backend {
+    /// Short name used in reports.
+    fn name(&self) -> &str;
+
+    /// Run `source` from a fresh state and report what happened.
+    fn run(&mut self, source: &str) -> Outcome;
+}
+
+/// The tree-walking interpreter as a [`Backend`].
+///
+/// Each script runs on a new interpreter built by the factory, so state
+/// never leaks between scripts.
+pub struct InterpreterBackend<F> {
+    name: String,
+    factory: F,
+}
+
+impl<F: FnMut() -> Interpreter> InterpreterBackend<F> {
+    /// Create a backend named `name` that builds interpreters with `factory`.
+    pub fn new(name: impl Into<String>, factory: F) -> Self {
+        Self { name: name.into(), factory }
+    }
+}
+
+impl<F: FnMut() -> Interpreter> Backend for InterpreterBackend<F> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&mut self, source: &str) -> Outcome {
+        let mut interp = (self.factory)();
+        let printed = Buffer::new();
+        interp.set_output(printed.clone());
+        let error = interp.exec_source(source).err().map(|e| e.to_string());
+        Outcome { stack: interp.stack().as_slice().to_vec(), output: printed.contents(), error }
+    }
+}
+
+/// The bytecode VM as a [`Backend`]: each script is compiled with
+/// [`compile_source`](Interpreter::compile_source) and run with
+/// [`exec_program`](Interpreter::exec_program) on a new interpreter.
+///
+/// A script that does not compile is reported with the compile error.
+pub struct BytecodeBackend<F> {
+    name: String,
+    factory: F,
+}
+
+impl<F: FnMut() -> Interpreter> BytecodeBackend<F> {
+    /// Create a backend named `name` that builds interpreters with `factory`.
+    pub fn new(name: impl Into<String>, factory: F) -> Self {
+        Self { name: name.into(), factory }
+    }
+}
+
+impl<F: FnMut() -> Interpreter> Backend for BytecodeBackend<F> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&mut self, source: &str) -> Outcome {
+        let mut interp = (self.factory)();
+        let printed = Buffer::new();
+        interp.set_output(printed.clone());
+        let error = interp
+            .compile_source(source)
+            .and_then(|program| interp.exec_program(&program))
+            .err()
+            .map(|e| e.to_string());
+        Outcome { stack: interp.stack().as_slice().to_vec(), output: printed.contents(), error }
+    }
+}
+
+/// A script on which two backends disagreed.
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    /// Script name within the corpus.
+    pub script: String,
+    /// Outcome from the first backend.
+    pub left: Outcome,
+    /// Outcome from the second backend.
+    pub right: Outcome,
+}
+
+/// Result of running a corpus through two backends.
+#[derive(Debug, Clone)]
+pub struct DiffReport {
+    /// Names of the two backends.
+    pub backends: (String, String),
+    /// Number of scripts run.
+    pub scripts: usize,
+    /// Scripts whose outcomes differed.
+    pub divergences: Vec<Divergence>,
+}
+
+impl DiffReport {
+    /// True if every script produced the same outcome on both backends.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (a, b) = &self.backends;
+        writeln!(
+            f,
+            "{a} vs {b}: {} scripts, {} divergent",
+            self.scripts,
+            self.divergences.len()
+        )?;
+        for d in &self.divergences {
+            writeln!(f, "  {}:", d.script)?;
+            writeln!(f, "    {a}: {}", d.left)?;
+            writeln!(f, "    {b}: {}", d.right)?;
+        }
+        Ok(())
+    }
+}
+
+/// Load every `.wof` file in `dir` as `(file name, source)`, sorted by name.
+pub fn load_corpus(dir: impl AsRef<Path>) -> io::Result<Vec<(String, String)>> {
+    let mut corpus = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "wof") {
+            let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+            corpus.push((name, fs::read_to_string(&path)?));
+        }
+    }
+    corpus.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(corpus)
+}
+
+/// Run every script in `corpus` through both backends and report where
+/// their outcomes differ.
+pub fn diff_corpus(
+    left: &mut dyn Backend,
+    right: &mut dyn Backend,
+    corpus: &[(String, String)],
+) -> DiffReport {
+    let divergences = corpus
+        .iter()
+        .filter_map(|(script, source)| {
+            let l = left.run(source);
+            let r = right.run(source);
+            (l != r).then(|| Divergence { script: script.clone(), left: l, right: r })
+        })
+        .collect();
+    DiffReport {
+        backends: (left.name().to_string(), right.name().to_string()),
+        scripts: corpus.len(),
+        divergences,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_add() -> Interpreter {
+        let mut interp = Interpreter::new();
+        interp.register("+", |ctx| {
+            let b = ctx.stack_mut().pop_integer()?;
+            let a = ctx.stack_mut().pop_integer()?;
+            ctx.push(WofValue::integer(a + b));
+            Ok(())
+        });
+        interp
+    }
+
+    fn with_broken_add() -> Interpreter {
+        let mut interp = Interpreter::new();
+        interp.register("+", |ctx| {
+            let b = ctx.stack_mut().pop_integer()?;
+            let a = ctx.stack_mut().pop_integer()?;
+            ctx.push(WofValue::integer(a - b));
+            Ok(())
+        });
+        interp
+    }
+
+    #[test]
+    fn flags_divergent_scripts() {
+        let corpus = vec![
+            ("plain.wof".to_string(), "1 2\n3".to_string()),
+            ("sum.wof".to_string(), "2 3 +".to_string()),
+            ("underflow.wof".to_string(), "+".to_string()),
+        ];
+        let mut good = InterpreterBackend::new("good", with_add);
+        let mut bad = InterpreterBackend::new("bad", with_broken_add);
+
+        let report = diff_corpus(&mut good, &mut bad, &corpus);
+        assert_eq!(report.scripts, 3);
+        assert_eq!(report.divergences.len(), 1);
+        let d = &report.divergences[0];
+        assert_eq!(d.script, "sum.wof");
+        assert_eq!(d.left.stack, vec![WofValue::integer(5)]);
+        assert_eq!(d.right.stack, vec![WofValue::integer(-1)]);
+        assert!(report.to_string().contains("good: [5]"));
+
+        let mut again = InterpreterBackend::new("again", with_add);
+        assert!(diff_corpus(&mut good, &mut again, &corpus).is_clean());
+    }
+
+    #[test]
+    fn flags_scripts_that_print_differently() {
+        let with_greeting = |greeting: &'static str| {
+            move || {
+                let mut interp = with_add();
+                interp.register("greet", move |ctx| {
+                    ctx.out().println(greeting);
+                    Ok(())
+                });
+                interp
+            }
+        };
+        let corpus = vec![
+            ("quiet.wof".to_string(), "2 3 +".to_string()),
+            ("greet.wof".to_string(), "greet 1".to_string()),
+        ];
+        let mut hello = InterpreterBackend::new("hello", with_greeting("hello"));
+        let mut hallo = BytecodeBackend::new("hallo", with_greeting("hallo"));
+
+        let report = diff_corpus(&mut hello, &mut hallo, &corpus);
+        assert_eq!(report.divergences.len(), 1, "{report}");
+        let d = &report.divergences[0];
+        assert_eq!((d.script.as_str(), d.left.output.as_str(), d.right.output.as_str()), ("greet.wof", "hello\n", "hallo\n"));
+        assert_eq!(d.left.stack, d.right.stack);
+        assert!(report.to_string().contains("hallo: [1] output: \"hallo\\n\""), "{report}");
+    }
+
+    #[test]
+    fn bytecode_matches_the_interpreter() {
+        let corpus = vec![
+            ("sum.wof".to_string(), "0 10 ⨯ ⺆ 2 + ⺘\n1 若 ⺆ 3 ⺘ 或 ⺆ 4 ⺘".to_string()),
+            ("vars.wof".to_string(), "5 字 x\n読 x 読 x +\n=(x + 1)".to_string()),
+            ("underflow.wof".to_string(), "1 2 +\n+ +".to_string()),
+        ];
+        let mut walked = InterpreterBackend::new("interpreter", with_add);
+        let mut compiled = BytecodeBackend::new("bytecode", with_add);
+        let report = diff_corpus(&mut walked, &mut compiled, &corpus);
+        assert!(report.is_clean(), "{report}");
+    }
+}
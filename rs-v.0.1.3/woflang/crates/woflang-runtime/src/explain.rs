@@ -0,0 +1,274 @@
+//! Expression provenance for the `explain` operation.
+//!
+//! The interpreter keeps a shadow stack of expression nodes alongside the
+//! data stack. Literals and named values become leaves; arithmetic ops
+//! with a known infix form combine their operands into new nodes. Nodes
+//! are reference counted, so `dup` shares a subexpression and the result
+//! is a small DAG rather than a tree.
+//!
+//! Ops without a known form (stack shuffles, plugins, user functions) are
+//! reconciled by value: each slot keeps its old node if the value is
+//! unchanged, reuses a node from the top of the previous stack if the
+//! value moved there, and otherwise becomes an opaque leaf showing the
+//! value itself. `explain` then renders the node under a numeric top of
+//! stack as conventional infix with minimal parentheses.
+//!
+//! Nodes remember numbers by value and anything else only by its type,
+//! so the shadow never shares a list or map with the stack (which would
+//! make every update to it a copy) or formats one.
+
+use std::sync::Arc;
+use woflang_core::{WofType, WofValue};
+
+/// Nodes deeper than this are collapsed into leaves, which keeps long
+/// loops from growing unbounded chains.
+const MAX_DEPTH: u16 = 48;
+
+/// How many slots below the untouched part of the stack are compared
+/// when reconciling an unknown op.
+const WINDOW: usize = 8;
+
+/// Infix form of an operation.
+#[derive(Debug, Clone, Copy)]
+enum Form {
+    /// `a op b` with the given precedence.
+    Binary { symbol: &'static str, prec: u8, right_assoc: bool },
+    /// `name(a, b)`.
+    Call2(&'static str),
+    /// `name(a)`.
+    Call1(&'static str),
+    /// `-a`.
+    Neg,
+}
+
+/// Precedence of unary minus; binds tighter than `*` but looser than `^`.
+const PREC_NEG: u8 = 25;
+/// Precedence of atoms (leaves and calls).
+const PREC_ATOM: u8 = u8::MAX;
+
+/// Ops rendered as `name(a, b)`.
+const CALL2: &[&str] = &["min", "max", "atan2", "hypot", "gcd", "lcm"];
+/// Ops rendered as `name(a)`.
+const CALL1: &[&str] = &[
+    "abs", "sqrt", "cbrt", "sin", "cos", "tan", "asin", "acos", "atan", "sinh", "cosh", "tanh",
+    "exp", "ln", "log", "log10", "log2", "floor", "ceil", "round", "trunc",
+];
+
+fn form_of(op: &str) -> Option<Form> {
+    let binary = |symbol, prec| Form::Binary { symbol, prec, right_assoc: false };
+    Some(match op {
+        "+" => binary("+", 10),
+        "-" => binary("-", 10),
+        "*" | "×" => binary("*", 20),
+        "/" | "÷" => binary("/", 20),
+        "%" | "mod" => binary("%", 20),
+        "^" | "pow" => Form::Binary { symbol: "^", prec: 30, right_assoc: true },
+        "neg" => Form::Neg,
+        "√" => Form::Call1("sqrt"),
+        _ => {
+            if let Some(name) = CALL2.iter().find(|n| **n == op) {
+                Form::Call2(name)
+            } else {
+                Form::Call1(CALL1.iter().find(|n| **n == op)?)
+            }
+        }
+    })
+}
+
+/// What a node remembers of its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ident {
+    Integer(i64),
+    /// A double, by its bits.
+    Double(u64),
+    /// Any other value, by type alone.
+    Other(WofType),
+}
+
+impl Ident {
+    fn of(value: &WofValue) -> Self {
+        let typ = value.value_type();
+        match typ {
+            WofType::Integer => value.as_integer().ok().map(Self::Integer),
+            WofType::Double => value.as_double().ok().map(|x| Self::Double(x.to_bits())),
+            _ => None,
+        }
+        .unwrap_or(Self::Other(typ))
+    }
+
+    const fn is_number(self) -> bool {
+        !matches!(self, Self::Other(_))
+    }
+
+    fn is_negative(self) -> bool {
+        match self {
+            Self::Integer(n) => n < 0,
+            Self::Double(bits) => f64::from_bits(bits).is_sign_negative(),
+            Self::Other(_) => false,
+        }
+    }
+
+    fn render(self) -> String {
+        match self {
+            Self::Integer(n) => n.to_string(),
+            Self::Double(bits) => WofValue::double(f64::from_bits(bits)).to_string(),
+            Self::Other(typ) => format!("<{typ}>"),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Kind {
+    Leaf(String),
+    /// A value with no better name, shown as itself.
+    Opaque,
+    Unary(Form, Arc<Node>),
+    Binary(Form, Arc<Node>, Arc<Node>),
+}
+
+/// A node in the expression DAG.
+#[derive(Debug)]
+pub(crate) struct Node {
+    ident: Ident,
+    kind: Kind,
+    depth: u16,
+}
+
+impl Node {
+    fn leaf(label: impl Into<String>, value: &WofValue) -> Arc<Self> {
+        Arc::new(Self { ident: Ident::of(value), kind: Kind::Leaf(label.into()), depth: 0 })
+    }
+
+    fn opaque(ident: Ident) -> Arc<Self> {
+        Arc::new(Self { ident, kind: Kind::Opaque, depth: 0 })
+    }
+
+    fn prec(&self) -> u8 {
+        match &self.kind {
+            Kind::Binary(Form::Binary { prec, .. }, ..) => *prec,
+            Kind::Unary(Form::Neg, _) => PREC_NEG,
+            Kind::Leaf(label) if label.starts_with('-') => PREC_NEG,
+            Kind::Opaque if self.ident.is_negative() => PREC_NEG,
+            _ => PREC_ATOM,
+        }
+    }
+
+    /// Render as infix with minimal parentheses.
+    fn render(&self) -> String {
+        let wrap = |child: &Self, needs: bool| {
+            if needs { format!("({})", child.render()) } else { child.render() }
+        };
+        match &self.kind {
+            Kind::Leaf(label) => label.clone(),
+            Kind::Opaque => self.ident.render(),
+            Kind::Unary(Form::Neg, a) => format!("-{}", wrap(a, a.prec() < PREC_NEG)),
+            Kind::Unary(Form::Call1(name), a) => format!("{name}({})", a.render()),
+            Kind::Binary(Form::Binary { symbol, prec, right_assoc }, a, b) => {
+                let left = if *right_assoc { a.prec() <= *prec } else { a.prec() < *prec };
+                let right = if *right_assoc { b.prec() < *prec } else { b.prec() <= *prec };
+                format!("{} {symbol} {}", wrap(a, left), wrap(b, right))
+            }
+            Kind::Binary(Form::Call2(name), a, b) => {
+                format!("{name}({}, {})", a.render(), b.render())
+            }
+            Kind::Unary(..) | Kind::Binary(..) => "?".to_string(),
+        }
+    }
+}
+
+/// Shadow stack of expression nodes mirroring the data stack.
+#[derive(Debug, Default)]
+pub(crate) struct ExplainTrace {
+    shadow: Vec<Arc<Node>>,
+}
+
+impl ExplainTrace {
+    /// Record a literal pushed by the token `text`.
+    pub(crate) fn literal(&mut self, text: &str, stack: &[WofValue]) {
+        if let Some(value) = stack.last() {
+            if self.shadow.len() + 1 == stack.len() {
+                self.shadow.push(Node::leaf(text, value));
+                return;
+            }
+        }
+        self.reconcile(None, self.shadow.len(), stack);
+    }
+
+    /// Record the effect of the symbol `name`, which ran with `before`
+    /// items on the stack.
+    pub(crate) fn symbol(&mut self, name: &str, before: usize, stack: &[WofValue]) {
+        if self.shadow.len() == before && self.apply_form(name, stack) {
+            return;
+        }
+        self.reconcile(Some(name), before, stack);
+    }
+
+    fn apply_form(&mut self, name: &str, stack: &[WofValue]) -> bool {
+        let Some(form) = form_of(name) else {
+            return false;
+        };
+        let arity = match form {
+            Form::Binary { .. } | Form::Call2(_) => 2,
+            Form::Call1(_) | Form::Neg => 1,
+        };
+        let before = self.shadow.len();
+        if before < arity || stack.len() + arity != before + 1 {
+            return false;
+        }
+        let Some(ident) = stack.last().map(Ident::of) else {
+            return false;
+        };
+
+        let args = self.shadow.split_off(before - arity);
+        let depth = args.iter().map(|a| a.depth).max().unwrap_or(0) + 1;
+        let node = if depth > MAX_DEPTH {
+            Node::opaque(ident)
+        } else {
+            let kind = match args.as_slice() {
+                [a] => Kind::Unary(form, Arc::clone(a)),
+                [a, b] => Kind::Binary(form, Arc::clone(a), Arc::clone(b)),
+                _ => return false,
+            };
+            Arc::new(Node { ident, kind, depth })
+        };
+        self.shadow.push(node);
+        true
+    }
+
+    /// Rebuild the shadow for the part of the stack an unknown op may
+    /// have touched.
+    fn reconcile(&mut self, name: Option<&str>, before: usize, stack: &[WofValue]) {
+        let stable = before.min(stack.len()).min(self.shadow.len()).saturating_sub(WINDOW);
+        let candidates = self.shadow.split_off(stable);
+
+        for (i, value) in stack.iter().enumerate().skip(stable) {
+            let ident = Ident::of(value);
+            let same_slot = candidates.get(i - stable).filter(|n| n.ident == ident);
+            let moved = || candidates.iter().rev().find(|n| n.ident == ident);
+            let node = match same_slot.or_else(moved) {
+                Some(node) => Arc::clone(node),
+                // A symbol that pushed exactly one new value names it
+                // (variables, constants such as `pi`).
+                None if i + 1 == stack.len() && stack.len() == before + 1 => match name {
+                    Some(n) if n.chars().all(|c| c.is_alphanumeric() || c == '_') => Node::leaf(n, value),
+                    _ => Node::opaque(ident),
+                },
+                None => Node::opaque(ident),
+            };
+            self.shadow.push(node);
+        }
+    }
+
+    /// Render the expression that produced the number on top of `stack`;
+    /// any other value is shown as itself.
+    pub(crate) fn explain(&self, stack: &[WofValue]) -> Option<String> {
+        let top = stack.last()?;
+        let ident = Ident::of(top);
+        match self.shadow.last() {
+            Some(node) if self.shadow.len() == stack.len() && ident.is_number() && node.ident == ident => {
+                Some(node.render())
+            }
+            _ => Some(top.to_string()),
+        }
+    }
+}
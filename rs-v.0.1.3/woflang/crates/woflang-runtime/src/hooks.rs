@@ -0,0 +1,90 @@
+//! Op hooks: observers called around every registered operation.
+//!
+//! Hooks let tooling (tracers, profilers, teaching aids) watch execution
+//! without touching the ops themselves. Each hook is installed under a
+//! name so it can be replaced or removed later:
+//!
+//! ```
+//! use woflang_runtime::{Interpreter, OpHook};
+//! use woflang_core::{Out, Result, WofStack};
+//!
+//! struct Counter(usize);
+//!
+//! impl OpHook for Counter {
+//!     fn after_op(&mut self, _name: &str, _stack: &WofStack, _result: &Result<()>, _out: &Out) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! let mut interp = Interpreter::new();
+//! interp.add_hook("counter", Counter(0));
+//! assert!(interp.has_hook("counter"));
+//! assert!(interp.remove_hook("counter"));
+//! ```
+//!
+//! Hooks run only for registry operations, not for literals, control
+//! flow or user-defined functions. With no hooks installed the dispatch
+//! cost is a single emptiness check.
+
+use woflang_core::{Out, Result, WofStack};
+
+/// An observer of registered operations.
+///
+/// Both methods default to doing nothing, so a hook implements only the
+/// side it cares about.
+pub trait OpHook: Send + Sync {
+    /// Called just before the op `name` runs.
+    fn before_op(&mut self, name: &str, stack: &WofStack) {
+        let _ = (name, stack);
+    }
+
+    /// Called after the op `name` has run, with its outcome. A hook that
+    /// reports what it saw writes to `out`, the interpreter's current
+    /// output, so the report goes wherever the op's own printing does.
+    fn after_op(&mut self, name: &str, stack: &WofStack, result: &Result<()>, out: &Out) {
+        let _ = (name, stack, result, out);
+    }
+}
+
+/// Named hooks in installation order.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    entries: Vec<(String, Box<dyn OpHook>)>,
+}
+
+impl Hooks {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Install `hook` under `name`, replacing any hook of the same name.
+    pub(crate) fn add(&mut self, name: String, hook: Box<dyn OpHook>) {
+        if let Some(slot) = self.entries.iter_mut().find(|(n, _)| *n == name) {
+            slot.1 = hook;
+        } else {
+            self.entries.push((name, hook));
+        }
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|(n, _)| n != name);
+        self.entries.len() != len
+    }
+
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|(n, _)| n == name)
+    }
+
+    pub(crate) fn before(&mut self, op: &str, stack: &WofStack) {
+        for (_, hook) in &mut self.entries {
+            hook.before_op(op, stack);
+        }
+    }
+
+    pub(crate) fn after(&mut self, op: &str, stack: &WofStack, result: &Result<()>, out: &Out) {
+        for (_, hook) in &mut self.entries {
+            hook.after_op(op, stack, result, out);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Infix expression compiler.
+//!
+//! Translates conventional infix arithmetic such as `3*(2+4)^2` into the
+//! equivalent postfix token stream (`3 2 4 + 2 ^ *`), which the
+//! interpreter then executes like any other Woflang code. RPN stays the
+//! execution model; infix is only a front-end.
+//!
+//! ## Grammar
+//!
+//! ```text
+//! expr    := prefix (infix-op expr)*     # precedence climbing
+//! prefix  := number | name | name '(' args ')' | '(' expr ')' | '-' prefix
+//! args    := (expr (',' expr)*)?
+//! ```
+//!
+//! | Operator      | Emits  | Precedence        |
+//! |---------------|--------|-------------------|
+//! | `+` `-`       | `+ -`  | lowest            |
+//! | `*` `/` `%`   | `* / %`|                   |
+//! | unary `-`     | `neg`  |                   |
+//! | `^`           | `^`    | highest, right    |
+//!
+//! Calls such as `max(a, b)` push their arguments left to right and then
+//! invoke the named operation, so any registered op can be used as a
+//! function. Bare names (`pi`, variables) are emitted as symbols.
+
+use crate::interpreter::OwnedToken;
+use crate::tokenizer::TokenKind;
+use woflang_core::{Result, Span, WofError};
+
+/// Binding power of `+`/`-`.
+const BP_SUM: u8 = 10;
+/// Binding power of `*`, `/` and `%`.
+const BP_PRODUCT: u8 = 20;
+/// Binding power of unary minus.
+const BP_PREFIX: u8 = 30;
+/// Binding power of `^`.
+const BP_POWER: u8 = 40;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Int,
+    Float,
+    Name,
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+    End,
+}
+
+#[derive(Debug, Clone)]
+struct Lexeme {
+    tok: Tok,
+    text: String,
+    /// Character index inside the expression.
+    pos: usize,
+}
+
+struct Compiler {
+    lexemes: Vec<Lexeme>,
+    pos: usize,
+    origin: Span,
+    out: Vec<OwnedToken>,
+}
+
+/// Compile an infix expression into postfix tokens.
+///
+/// `origin` is the span of the first character of `src`; emitted tokens
+/// and errors carry spans offset from it so diagnostics point into the
+/// original line.
+///
+/// # Errors
+///
+/// Returns [`WofError::Parse`] for malformed input.
+pub fn compile(src: &str, origin: Span) -> Result<Vec<OwnedToken>> {
+    let mut compiler = Compiler {
+        lexemes: Vec::new(),
+        pos: 0,
+        origin,
+        out: Vec::new(),
+    };
+    compiler.lexemes = compiler.lex(src)?;
+    compiler.expr(0)?;
+
+    let rest = compiler.next();
+    if rest.tok != Tok::End {
+        return Err(compiler.error("unexpected input after expression", &rest));
+    }
+    if compiler.out.is_empty() {
+        return Err(compiler.error("empty infix expression", &rest));
+    }
+    Ok(compiler.out)
+}
+
+impl Compiler {
+    fn span_at(&self, pos: usize, len: usize) -> Span {
+        let o = self.origin;
+        if o.is_synthetic() {
+            return o;
+        }
+        Span::with_length(
+            o.line(),
+            o.column() + pos as u32,
+            o.offset() + pos as u32,
+            len as u32,
+        )
+    }
+
+    fn error(&self, message: &str, at: &Lexeme) -> WofError {
+        WofError::parse(
+            format!("infix: {message}"),
+            self.span_at(at.pos, at.text.chars().count().max(1)),
+        )
+    }
+
+    fn lex(&self, src: &str) -> Result<Vec<Lexeme>> {
+        let chars: Vec<char> = src.chars().collect();
+        let mut out = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let start = i;
+            let tok = if c.is_whitespace() {
+                i += 1;
+                continue;
+            } else if c.is_ascii_digit() || c == '.' {
+                let mut is_float = false;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    is_float |= chars[i] == '.';
+                    i += 1;
+                }
+                if is_float { Tok::Float } else { Tok::Int }
+            } else if c.is_alphabetic() || c == '_' {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                Tok::Name
+            } else {
+                i += 1;
+                match c {
+                    '+' | '-' | '*' | '/' | '%' | '^' => Tok::Op(c),
+                    '×' => Tok::Op('*'),
+                    '÷' => Tok::Op('/'),
+                    '(' => Tok::LParen,
+                    ')' => Tok::RParen,
+                    ',' => Tok::Comma,
+                    _ => {
+                        return Err(WofError::parse(
+                            format!("infix: unexpected character '{c}'"),
+                            self.span_at(start, 1),
+                        ));
+                    }
+                }
+            };
+            let text: String = chars[start..i].iter().collect();
+            out.push(Lexeme { tok, text, pos: start });
+        }
+
+        out.push(Lexeme { tok: Tok::End, text: String::new(), pos: chars.len() });
+        Ok(out)
+    }
+
+    fn peek(&self) -> &Lexeme {
+        // The lexer always terminates the stream with `End`.
+        &self.lexemes[self.pos.min(self.lexemes.len() - 1)]
+    }
+
+    fn next(&mut self) -> Lexeme {
+        let lexeme = self.peek().clone();
+        if lexeme.tok != Tok::End {
+            self.pos += 1;
+        }
+        lexeme
+    }
+
+    fn expect(&mut self, tok: &Tok, what: &str) -> Result<()> {
+        let lexeme = self.next();
+        if &lexeme.tok == tok {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {what}"), &lexeme))
+        }
+    }
+
+    fn emit(&mut self, kind: TokenKind, text: impl Into<String>, at: &Lexeme) {
+        let text = text.into();
+        let span = self.span_at(at.pos, at.text.chars().count().max(1));
+        self.out.push(OwnedToken { kind, text, span, op: None });
+    }
+
+    fn expr(&mut self, min_bp: u8) -> Result<()> {
+        self.prefix()?;
+
+        loop {
+            let lexeme = self.peek().clone();
+            let (bp, right_bp) = match lexeme.tok {
+                Tok::Op('+' | '-') => (BP_SUM, BP_SUM + 1),
+                Tok::Op('*' | '/' | '%') => (BP_PRODUCT, BP_PRODUCT + 1),
+                Tok::Op('^') => (BP_POWER, BP_POWER),
+                _ => break,
+            };
+            if bp < min_bp {
+                break;
+            }
+            self.next();
+            self.expr(right_bp)?;
+            let op = lexeme.text.replace('×', "*").replace('÷', "/");
+            self.emit(TokenKind::Symbol, op, &lexeme);
+        }
+
+        Ok(())
+    }
+
+    fn prefix(&mut self) -> Result<()> {
+        let lexeme = self.next();
+        match lexeme.tok {
+            Tok::Int => self.emit(TokenKind::Integer, lexeme.text.clone(), &lexeme),
+            Tok::Float => self.emit(TokenKind::Float, lexeme.text.clone(), &lexeme),
+            Tok::Name if self.peek().tok == Tok::LParen => {
+                self.next();
+                if self.peek().tok != Tok::RParen {
+                    self.expr(0)?;
+                    while self.peek().tok == Tok::Comma {
+                        self.next();
+                        self.expr(0)?;
+                    }
+                }
+                self.expect(&Tok::RParen, "')' to close call")?;
+                self.emit(TokenKind::Symbol, lexeme.text.clone(), &lexeme);
+            }
+            Tok::Name => self.emit(TokenKind::Symbol, lexeme.text.clone(), &lexeme),
+            Tok::LParen => {
+                self.expr(0)?;
+                self.expect(&Tok::RParen, "')'")?;
+            }
+            Tok::Op('-') => {
+                self.expr(BP_PREFIX)?;
+                self.emit(TokenKind::Symbol, "neg", &lexeme);
+            }
+            Tok::Op('+') => self.expr(BP_PREFIX)?,
+            Tok::End => return Err(self.error("unexpected end of expression", &lexeme)),
+            _ => return Err(self.error("expected a number, name or '('", &lexeme)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpn(src: &str) -> String {
+        compile(src, Span::new(1, 1, 0))
+            .unwrap()
+            .iter()
+            .map(|t| t.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(rpn("3*(2+4)^2"), "3 2 4 + 2 ^ *");
+        assert_eq!(rpn("1 - 2 - 3"), "1 2 - 3 -");
+        assert_eq!(rpn("2^3^2"), "2 3 2 ^ ^");
+        assert_eq!(rpn("-x^2"), "x 2 ^ neg");
+    }
+
+    #[test]
+    fn calls_push_arguments_then_op() {
+        assert_eq!(rpn("max(1, 2*3) + sqrt(16)"), "1 2 3 * max 16 sqrt +");
+        assert_eq!(rpn("2 * pi"), "2 pi *");
+    }
+
+    #[test]
+    fn errors_point_into_source() {
+        let err = compile("1 + (2 * 3", Span::new(4, 10, 30)).unwrap_err();
+        let span = err.span().unwrap();
+        assert_eq!((span.line(), span.column()), (4, 20));
+        assert!(compile("1 +", Span::new(1, 1, 0)).is_err());
+        assert!(compile("1 $ 2", Span::new(1, 1, 0)).is_err());
+    }
+}
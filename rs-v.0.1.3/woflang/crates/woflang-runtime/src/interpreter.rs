@@ -4,14 +4,25 @@
 //! and dispatching operations through the registry. It maintains the
 //! execution state (stack, scopes) and provides the context for operation handlers.
 
-use crate::{KeyBindings, Registry, Token, TokenKind, Tokenizer};
-use std::collections::{HashMap, VecDeque};
+use crate::compat::{self, Rename};
+use crate::coverage::Coverage;
+use crate::debugger::{Breakpoint, DebugAction, DebugFrame, DebugFrontend, Debugger, Pause};
+use crate::explain::ExplainTrace;
+use crate::hooks::{Hooks, OpHook};
+use crate::pool::BufferPool;
+use crate::profile::{OpProfile, Phase, PhaseProfile};
+use crate::registry::BoxedOp;
+use crate::stream::{Event, EventSink, Stream};
+use crate::{CancelToken, KeyBindings, OpId, OpMeta, Progress, ProgressSink, Registry, Sandbox, Token, TokenKind, Tokenizer};
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fs;
 use std::io::{self, BufRead, Write};
 use std::path::Path;
+use std::time::{Duration, Instant};
 use woflang_core::{
-    BlockId, BlockRegistry, BlockStack, BlockType, Diagnostic, InterpreterContext,
-    IntoDiagnostic, Result, ScopeStack, Span, WofError, WofStack, WofValue,
+    BigInt, BlockId, BlockRegistry, BlockStack, BlockType, Diagnostic, InterpreterContext,
+    IntoDiagnostic, Out, Output, Result, ScopeStack, Span, WofError, WofStack, WofValue,
 };
 
 /// A user-defined function.
@@ -45,15 +56,41 @@ impl FunctionDef {
     }
 }
 
+/// The stack and variables at one moment, to go back to with
+/// [`Interpreter::restore`].
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    stack: WofStack,
+    scopes: ScopeStack,
+}
+
+impl Snapshot {
+    /// The stack as it was.
+    #[must_use]
+    pub fn stack(&self) -> &WofStack {
+        &self.stack
+    }
+}
+
 /// Context saved when calling a function.
 #[derive(Debug, Clone)]
 struct CallFrame {
+    /// The function being run.
+    function: String,
     /// Tokens to resume after return.
     remaining_tokens: VecDeque<OwnedToken>,
     /// Block depth at call site.
     block_depth: usize,
+    /// Loop nesting at call site; a call can only reuse this frame as a
+    /// tail call from the same loop level.
+    loop_depth: usize,
+    /// When the call started, if the op profile is enabled.
+    started: Option<Instant>,
 }
 
+/// Default limit on nested user function calls.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 10_000;
+
 /// The Woflang interpreter.
 ///
 /// Manages the execution state and operation dispatch for a Woflang
@@ -79,7 +116,7 @@ pub struct Interpreter {
     /// Operation registry.
     registry: Registry<Self>,
     /// User-defined functions.
-    functions: HashMap<String, FunctionDef>,
+    functions: BTreeMap<String, FunctionDef>,
     /// Keybinding aliases.
     keybindings: KeyBindings,
     /// Variable scopes.
@@ -92,10 +129,17 @@ pub struct Interpreter {
     token_buffer: VecDeque<OwnedToken>,
     /// Current instruction pointer (for compiled mode).
     ip: usize,
-    /// Skip mode depth (for skipping else branches etc).
+    /// Open blocks in the branch being skipped (0 when not skipping).
     skip_depth: usize,
-    /// Function definition mode: collecting body for this function name.
-    defining_function: Option<String>,
+    /// Block the next token must open after `若` or `或`, and whether to
+    /// run it rather than skip it.
+    pending_branch: Option<(BlockType, bool)>,
+    /// Set when a then-block has just closed: whether it ran, so that a
+    /// following `或` knows whether to run its block.
+    then_ran: Option<bool>,
+    /// Function definition mode: collecting body for this function name
+    /// and arity.
+    defining_function: Option<(String, usize)>,
     /// Tokens being collected for function body.
     function_body_buffer: Vec<OwnedToken>,
     /// Nesting depth inside function definition (to handle nested blocks).
@@ -106,6 +150,8 @@ pub struct Interpreter {
     loop_collect_depth: usize,
     /// Type of loop being collected (for initial dispatch).
     collecting_loop: Option<LoopType>,
+    /// Condition block of the `while` loop whose body is being collected.
+    while_condition: Option<Vec<OwnedToken>>,
     /// Active loop frames (for nested loops).
     loop_stack: Vec<LoopFrame>,
     /// Break signal (exit innermost loop).
@@ -113,15 +159,50 @@ pub struct Interpreter {
     /// Continue signal (restart innermost loop iteration).
     continue_signal: bool,
     /// Label table: maps label names to token indices in the program.
-    labels: HashMap<String, Vec<OwnedToken>>,
+    labels: BTreeMap<String, Vec<OwnedToken>>,
     /// Current source line (for diagnostic rendering).
     current_source: Option<String>,
     /// Current filename (for diagnostic rendering).
     current_filename: Option<String>,
+    /// Expression provenance of stack values (for `explain`).
+    trace: ExplainTrace,
+    /// Observers called around registered operations.
+    hooks: Hooks,
+    /// Coverage being collected, if enabled.
+    coverage: Option<Coverage>,
+    /// Dispatch-path timings being collected, if enabled.
+    profile: Option<PhaseProfile>,
+    /// Per-op and per-function timings being collected, if enabled.
+    op_profile: Option<OpProfile>,
+    /// Breakpoints and the debugger frontend, if attached.
+    debugger: Debugger,
+    /// Plugin state keyed by type (see [`Interpreter::state_mut`]).
+    state: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    /// Set to stop the running computation (see [`Interpreter::cancel_token`]).
+    cancel: CancelToken,
+    /// Receiver of progress reports from long-running ops.
+    progress_sink: Option<Box<dyn ProgressSink>>,
+    /// Receiver of the events of the line being streamed.
+    stream: Option<Stream>,
+    /// Where ops print (see [`Interpreter::set_output`]).
+    out: Out,
+    /// Where file ops may read and write (see [`Interpreter::sandbox`]).
+    sandbox: Sandbox,
+    /// Names declared anywhere in the script being run (strict mode).
+    declared: HashSet<String>,
+    /// Ops renamed since the language version the running script
+    /// declares with `@wof-version`.
+    renames: Vec<&'static Rename>,
     /// Expand keybindings in input.
     pub expand_bindings: bool,
+    /// Strict mode: reject lines containing symbols that name no op,
+    /// function or variable, instead of pushing them as symbols.
+    pub strict: bool,
     /// Debug mode: print stack after each line.
     pub debug: bool,
+    /// Deepest nesting of user function calls before a call fails.
+    /// Tail calls reuse their caller's frame and do not count.
+    pub max_call_depth: usize,
 }
 
 /// Type of loop construct.
@@ -131,7 +212,7 @@ pub enum LoopType {
     Infinite,
     /// Repeat N times (⨯).
     Repeat(i64),
-    /// While condition is true.
+    /// While a condition block leaves a truthy value (当).
     While,
 }
 
@@ -157,6 +238,9 @@ pub struct OwnedToken {
     pub text: String,
     /// Source location.
     pub span: Span,
+    /// Registry slot this symbol was bound to when its line was
+    /// compiled, if it names a registered op.
+    pub op: Option<OpId>,
 }
 
 impl<'a> From<Token<'a>> for OwnedToken {
@@ -165,8 +249,82 @@ impl<'a> From<Token<'a>> for OwnedToken {
             kind: t.kind,
             text: t.text.to_string(),
             span: t.span,
+            op: None,
+        }
+    }
+}
+
+/// Names `dispatch_symbol` handles itself before consulting the
+/// registry; these are never bound to ops.
+const SPECIAL_FORMS: &[&str] = &[
+    "⊕", "fn", "func", "def", "巡", "call", "至", "return", "ret", "⟳", "loop", "⨯", "times", "repeat",
+    "bench", "spawn", "jit", "parameterize", "ns", "global", "record", "enum", "schema", "match_tag", "map_ok",
+    "and_then", "try_prop", "try", "catch", "test_case", "🛑", "break", "↻",
+    "continue", "読", "load", "get", "字", "define", "let", "支", "set", "store", "若", "if", "或", "else", "当", "while", "⺆", "⺘",
+    "goto", "jump", "跳", ":labels",
+];
+
+/// Whether `name` is a special form, which the interpreter handles
+/// itself: such a name never reaches an op or variable of that name.
+#[must_use]
+pub fn is_special_form(name: &str) -> bool {
+    SPECIAL_FORMS.contains(&name)
+}
+
+/// Special forms whose next token is a name rather than something to run.
+const NAMING_FORMS: &[&str] = &[
+    "⊕", "fn", "func", "def", "巡", "call", "読", "load", "get", "字", "define", "let", "支", "set",
+    "store", "goto", "jump", "跳",
+];
+
+/// Split a script into the units executed one at a time, with their
+/// 1-based starting line: its lines, except that a raw string (`"""`)
+/// left open at the end of a line takes the following lines with it.
+fn statements(content: &str) -> Vec<(u32, &str)> {
+    let open_raw = |chunk: &str| {
+        Tokenizer::new(chunk)
+            .last()
+            .is_some_and(|t| t.kind == TokenKind::String && !crate::tokenizer::is_closed(&t))
+    };
+    let mut out = Vec::new();
+    let (mut start, mut end, mut line) = (0, 0, 1u32);
+    let mut first = line;
+    for text in content.split_inclusive('\n') {
+        end += text.len();
+        line += 1;
+        let chunk = &content[start..end];
+        if chunk.contains("\"\"\"") && open_raw(chunk) {
+            continue;
+        }
+        out.push((first, chunk.strip_suffix('\n').map_or(chunk, |c| c.strip_suffix('\r').unwrap_or(c))));
+        start = end;
+        first = line;
+    }
+    if start < content.len() {
+        out.push((first, &content[start..]));
+    }
+    out
+}
+
+/// True for the names of the return form.
+fn is_return(name: &str) -> bool {
+    matches!(name, "至" | "return" | "ret")
+}
+
+/// Special forms that declare the name following them.
+const DECLARING_FORMS: &[&str] = &["⊕", "fn", "func", "def", "字", "define", "let"];
+
+/// Names declared by `tokens` with `⊕ name` or `字 name`.
+fn declared_names<'t>(tokens: impl IntoIterator<Item = (TokenKind, &'t str)>) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut declaring = false;
+    for (kind, text) in tokens {
+        if declaring && kind == TokenKind::Symbol {
+            names.insert(text.to_string());
         }
+        declaring = kind == TokenKind::Symbol && DECLARING_FORMS.contains(&text);
     }
+    names
 }
 
 impl Default for Interpreter {
@@ -183,7 +341,7 @@ impl Interpreter {
             stack: WofStack::with_capacity(64),
             call_stack: Vec::with_capacity(16),
             registry: Registry::new(),
-            functions: HashMap::new(),
+            functions: BTreeMap::new(),
             keybindings: KeyBindings::with_defaults(),
             scopes: ScopeStack::new(),
             blocks: BlockRegistry::new(),
@@ -191,20 +349,39 @@ impl Interpreter {
             token_buffer: VecDeque::new(),
             ip: 0,
             skip_depth: 0,
+            pending_branch: None,
+            then_ran: None,
             defining_function: None,
             function_body_buffer: Vec::new(),
             function_def_depth: 0,
             loop_body_buffer: Vec::new(),
             loop_collect_depth: 0,
             collecting_loop: None,
+            while_condition: None,
             loop_stack: Vec::new(),
             break_signal: false,
             continue_signal: false,
-            labels: HashMap::new(),
+            labels: BTreeMap::new(),
             current_source: None,
             current_filename: None,
+            trace: ExplainTrace::default(),
+            hooks: Hooks::default(),
+            coverage: None,
+            profile: None,
+            op_profile: None,
+            debugger: Debugger::default(),
+            state: HashMap::new(),
+            cancel: CancelToken::new(),
+            progress_sink: None,
+            stream: None,
+            out: Out::stdout(),
+            sandbox: Sandbox::default(),
+            declared: HashSet::new(),
+            renames: Vec::new(),
             expand_bindings: true,
+            strict: false,
             debug: false,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
         }
     }
 
@@ -215,7 +392,7 @@ impl Interpreter {
             stack: WofStack::with_capacity(64),
             call_stack: Vec::with_capacity(16),
             registry,
-            functions: HashMap::new(),
+            functions: BTreeMap::new(),
             keybindings: KeyBindings::with_defaults(),
             scopes: ScopeStack::new(),
             blocks: BlockRegistry::new(),
@@ -223,23 +400,98 @@ impl Interpreter {
             token_buffer: VecDeque::new(),
             ip: 0,
             skip_depth: 0,
+            pending_branch: None,
+            then_ran: None,
             defining_function: None,
             function_body_buffer: Vec::new(),
             function_def_depth: 0,
             loop_body_buffer: Vec::new(),
             loop_collect_depth: 0,
             collecting_loop: None,
+            while_condition: None,
             loop_stack: Vec::new(),
             break_signal: false,
             continue_signal: false,
-            labels: HashMap::new(),
+            labels: BTreeMap::new(),
             current_source: None,
             current_filename: None,
+            trace: ExplainTrace::default(),
+            hooks: Hooks::default(),
+            coverage: None,
+            profile: None,
+            op_profile: None,
+            debugger: Debugger::default(),
+            state: HashMap::new(),
+            cancel: CancelToken::new(),
+            progress_sink: None,
+            stream: None,
+            out: Out::stdout(),
+            sandbox: Sandbox::default(),
+            declared: HashSet::new(),
+            renames: Vec::new(),
             expand_bindings: true,
+            strict: false,
             debug: false,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+        }
+    }
+
+    /// Create an independent copy of this interpreter's program state.
+    ///
+    /// The fork shares op handlers with `self` but owns copies of the
+    /// stack, user functions, variables, labels and keybindings, so
+    /// running code in it never affects the original. It keeps the file
+    /// sandbox and prints where `self` does; plugin state, op hooks,
+    /// progress sinks, coverage, profiling and the debugger are not
+    /// carried over.
+    #[must_use]
+    pub fn fork(&self) -> Self {
+        Self {
+            stack: self.stack.clone(),
+            functions: self.functions.clone(),
+            keybindings: self.keybindings.clone(),
+            scopes: self.scopes.clone(),
+            labels: self.labels.clone(),
+            sandbox: self.sandbox.clone(),
+            out: self.out.clone(),
+            expand_bindings: self.expand_bindings,
+            strict: self.strict,
+            debug: self.debug,
+            ..Self::with_registry(self.registry.clone())
         }
     }
 
+    /// Exchange program state with `other`: the stack, user functions,
+    /// variables and labels.
+    ///
+    /// Everything else stays put, including the registry, keybindings,
+    /// hooks, plugin state and the cancel token, so a REPL can keep
+    /// several explorations in parked forks and switch between them.
+    pub fn swap_program_state(&mut self, other: &mut Self) {
+        std::mem::swap(&mut self.stack, &mut other.stack);
+        std::mem::swap(&mut self.functions, &mut other.functions);
+        std::mem::swap(&mut self.scopes, &mut other.scopes);
+        std::mem::swap(&mut self.labels, &mut other.labels);
+        std::mem::swap(&mut self.trace, &mut other.trace);
+    }
+
+    /// Copy the stack and variables, for [`restore`](Self::restore).
+    #[must_use]
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot { stack: self.stack.clone(), scopes: self.scopes.clone() }
+    }
+
+    /// Go back to a [`snapshot`](Self::snapshot): the stack as it was,
+    /// and every variable that existed then with its value then.
+    ///
+    /// Variables defined since are dropped, except in scopes opened
+    /// since (the locals of a function running now). Functions, labels
+    /// and plugin state are not part of a snapshot.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.stack.clone_from(&snapshot.stack);
+        self.scopes.restore_variables(&snapshot.scopes);
+    }
+
     /// Get a reference to the registry.
     #[must_use]
     pub fn registry(&self) -> &Registry<Self> {
@@ -260,6 +512,48 @@ impl Interpreter {
         self.registry.register(name, handler);
     }
 
+    /// Register an operation handler with its metadata.
+    pub fn register_with<F>(&mut self, name: impl Into<String>, meta: OpMeta, handler: F)
+    where
+        F: Fn(&mut Self) -> Result<()> + Send + Sync + 'static,
+    {
+        self.registry.register_with(name, meta, handler);
+    }
+
+    /// Register one handler under several names as an alias group.
+    pub fn register_group<F>(&mut self, names: &[&str], handler: F)
+    where
+        F: Fn(&mut Self) -> Result<()> + Send + Sync + 'static,
+    {
+        self.registry.register_group(names, handler);
+    }
+
+    /// Attach human-facing metadata to a registered operation.
+    pub fn describe(&mut self, name: impl Into<String>, meta: OpMeta) {
+        self.registry.describe(name, meta);
+    }
+
+    /// The help card for `name`: an op's metadata (see
+    /// [`Registry::help_card`]), or a note that it is a user function.
+    #[must_use]
+    pub fn help_card(&self, name: &str) -> Option<String> {
+        self.registry.help_card(name).or_else(|| {
+            self.has_function(name).then(|| format!("{name} is a user-defined function\n"))
+        })
+    }
+
+    /// Define a record type, registering `Name_new`, `Name?`, and the
+    /// `.field` and `with_field` ops for each field.
+    pub fn define_record(&mut self, name: &str, fields: &[String]) -> Result<()> {
+        crate::record::define(self, name, fields)
+    }
+
+    /// Define an enum type, registering a constructor per variant (bare
+    /// and `Name.Variant`), `Name?`, `tag_of` and `untag`.
+    pub fn define_enum(&mut self, name: &str, variants: &[String]) -> Result<()> {
+        crate::tagged::define(self, name, variants)
+    }
+
     // ═══════════════════════════════════════════════════════════════
     // FUNCTION MANAGEMENT
     // ═══════════════════════════════════════════════════════════════
@@ -284,48 +578,126 @@ impl Interpreter {
         self.functions.get(name)
     }
 
-    /// List all defined functions.
+    /// List all defined functions, sorted by name.
     #[must_use]
     pub fn function_names(&self) -> Vec<&str> {
         self.functions.keys().map(|s| s.as_str()).collect()
     }
 
     /// Call a user-defined function by name.
+    ///
+    /// The stack must hold at least the function's arity. A call whose
+    /// next token is a return is a tail call: it replaces the current
+    /// frame instead of stacking a new one, so tail recursion runs in
+    /// constant space. Other calls fail beyond
+    /// [`max_call_depth`](Self::max_call_depth).
     pub fn call_function(&mut self, name: &str) -> Result<()> {
         // Get the function body (clone to avoid borrow issues)
         let func = self.functions.get(name)
             .ok_or_else(|| WofError::Runtime(format!("undefined function: '{name}'")))?
             .clone();
+        if self.stack.len() < func.arity {
+            return Err(WofError::Runtime(format!(
+                "'{name}' takes {} argument(s) but the stack holds {}",
+                func.arity,
+                self.stack.len()
+            )));
+        }
 
         if self.debug {
             eprintln!("[debug] calling function: {}", name);
         }
-
-        // Save current execution context
-        let frame = CallFrame {
-            remaining_tokens: std::mem::take(&mut self.token_buffer),
-            block_depth: self.block_stack.depth(),
-        };
-        self.call_stack.push(frame);
+        if let Some(coverage) = &mut self.coverage {
+            coverage.record_function(name);
+        }
+        if let Some(profile) = &mut self.op_profile {
+            profile.count_call(name);
+        }
+        let started = self.op_profile.is_some().then(Instant::now);
+
+        let tail = self.call_stack.last().is_some_and(|f| f.loop_depth == self.loop_stack.len())
+            && self.token_buffer.front().is_some_and(|t| is_return(&t.text));
+        if let Some(frame) = self.call_stack.last().filter(|_| tail) {
+            // Leave the current function now; its caller gets our result
+            let depth = frame.block_depth;
+            self.unwind_blocks(depth);
+            self.token_buffer.clear();
+            if let Some(mut frame) = self.call_stack.pop() {
+                self.profile_return(&frame.function, frame.started);
+                frame.function = name.to_string();
+                frame.started = started;
+                self.call_stack.push(frame);
+            }
+        } else {
+            if self.call_stack.len() >= self.max_call_depth {
+                return Err(WofError::Runtime(format!(
+                    "maximum call depth ({}) exceeded calling '{name}'",
+                    self.max_call_depth
+                )));
+            }
+            // Save current execution context
+            let frame = CallFrame {
+                function: name.to_string(),
+                remaining_tokens: std::mem::take(&mut self.token_buffer),
+                block_depth: self.block_stack.depth(),
+                loop_depth: self.loop_stack.len(),
+                started,
+            };
+            self.call_stack.push(frame);
+        }
 
         // Create new scope for function
         self.push_scope(BlockType::Function);
 
-        // Load function body into token buffer
+        // Load function body into token buffer, ending in an implicit
+        // return so the caller's remaining tokens resume afterwards
         for token in &func.body {
             self.token_buffer.push_back(token.clone());
         }
+        self.token_buffer.push_back(OwnedToken {
+            kind: TokenKind::Symbol,
+            text: "至".to_string(),
+            span: func.span,
+            op: None,
+        });
 
         Ok(())
     }
 
+    /// Call a user-defined function and run it to completion.
+    ///
+    /// Unlike [`call_function`](Self::call_function), which queues the
+    /// body to run after the current op, this returns once the function
+    /// has returned, so ops can apply a function to values one at a time
+    /// (`list_map`).
+    pub fn run_function(&mut self, name: &str) -> Result<()> {
+        let rest = std::mem::take(&mut self.token_buffer);
+        let depth = self.call_stack.len();
+        let mut result = self.call_function(name);
+        while result.is_ok() && self.call_stack.len() > depth {
+            let Some(token) = self.token_buffer.pop_front() else { break };
+            result = self.dispatch_owned_token(&token);
+        }
+        self.unwind_calls(depth);
+        self.token_buffer = rest;
+        result
+    }
+
+    /// Return from every call above `depth`, as when an error abandons
+    /// them.
+    fn unwind_calls(&mut self, depth: usize) {
+        while self.call_stack.len() > depth {
+            self.return_from_function().ok();
+        }
+    }
+
     /// Return from the current function.
     pub fn return_from_function(&mut self) -> Result<()> {
-        // Pop the function scope
-        self.pop_scope();
-
         // Restore caller's execution context
         if let Some(frame) = self.call_stack.pop() {
+            self.profile_return(&frame.function, frame.started);
+            // Pop the function scope and any block returned out of
+            self.unwind_blocks(frame.block_depth);
             self.token_buffer = frame.remaining_tokens;
             if self.debug {
                 eprintln!("[debug] returned from function");
@@ -333,6 +705,7 @@ impl Interpreter {
             Ok(())
         } else {
             // Return at top level - just clear tokens
+            self.pop_scope();
             self.token_buffer.clear();
             Ok(())
         }
@@ -442,13 +815,28 @@ impl Interpreter {
 
     /// Pop the current scope.
     pub fn pop_scope(&mut self) {
-        if let Some(block_id) = self.block_stack.pop() {
-            if let Some(block) = self.blocks.get(block_id) {
-                if block.block_type.creates_scope() {
-                    self.scopes.pop();
-                }
-            }
-            self.blocks.close(block_id, self.ip);
+        self.close_block();
+    }
+
+    /// Pop the current block, returning its type.
+    ///
+    /// Only open blocks are looked up, so the block is dropped from the
+    /// registry; otherwise every call, loop and branch run would leave
+    /// one behind.
+    fn close_block(&mut self) -> Option<BlockType> {
+        let block_id = self.block_stack.pop()?;
+        let block_type = self.blocks.remove(block_id).map(|block| block.block_type);
+        if block_type.is_some_and(|t| t.creates_scope()) {
+            self.scopes.pop();
+        }
+        block_type
+    }
+
+    /// Pop blocks until `depth` remain, closing any that a `break`,
+    /// `continue` or `return` left open.
+    fn unwind_blocks(&mut self, depth: usize) {
+        while self.block_stack.depth() > depth {
+            self.close_block();
         }
     }
 
@@ -485,40 +873,262 @@ impl Interpreter {
     /// The line is tokenized and each token is dispatched through the
     /// interpreter. Errors are returned immediately; partial execution
     /// may have modified the stack.
+    ///
+    /// An error raised by an op comes back as [`WofError::Located`] with
+    /// the op's position; use [`WofError::root`] to match on what went
+    /// wrong.
     pub fn exec_line(&mut self, line: &str) -> Result<()> {
+        self.exec_line_numbered(line, 1, None)
+    }
+
+    /// Execute a line, sending what it does to `sink` as it happens.
+    ///
+    /// The stack changes after each token arrive as [`Event::Pushed`] and
+    /// [`Event::Popped`] deltas, text printed to [`out`](InterpreterContext::out)
+    /// as [`Event::Printed`] instead of reaching the output, and a failure
+    /// as a final [`Event::Error`], which is also returned. See the
+    /// [`stream`](crate::stream) module.
+    pub fn exec_line_stream(&mut self, line: &str, sink: impl EventSink + 'static) -> Result<()> {
+        let stream = Stream::new(Box::new(sink), self.stack.as_slice());
+        let outer_out = std::mem::replace(&mut self.out, stream.printer());
+        let outer = self.stream.replace(stream);
+        let result = self.exec_line(line);
+        if let Some(stream) = &mut self.stream {
+            stream.stack_changed(self.stack.as_slice());
+            if let Err(e) = &result {
+                stream.send(&Event::Error(e.clone()));
+            }
+        }
+        self.stream = outer;
+        self.out = outer_out;
+        result
+    }
+
+    /// Report the stack's change since the last report, while streaming.
+    fn stream_stack(&mut self) {
+        if let Some(stream) = &mut self.stream {
+            stream.stack_changed(self.stack.as_slice());
+        }
+    }
+
+    /// Send what ops print to `output` instead of standard output.
+    ///
+    /// Forks made afterwards, such as the interpreters running `spawn`
+    /// jobs, print there too.
+    pub fn set_output(&mut self, output: impl Output + 'static) {
+        self.out = Out::new(output);
+    }
+
+    /// Execute a line, taking its token buffers from `pool`.
+    ///
+    /// Behaves exactly like [`exec_line`](Self::exec_line), but the
+    /// expanded source and token texts are drawn from and returned to the
+    /// pool, so a batch of lines reuses the same storage instead of
+    /// allocating afresh for every token.
+    pub fn exec_line_in(&mut self, pool: &BufferPool, line: &str) -> Result<()> {
+        self.exec_line_numbered(line, 1, Some(pool))
+    }
+
+    /// Execute a line whose tokens are attributed to source line `line_no`.
+    fn exec_line_numbered(
+        &mut self,
+        line: &str,
+        line_no: u32,
+        pool: Option<&BufferPool>,
+    ) -> Result<()> {
         let trimmed = line.trim();
         if trimmed.is_empty() {
             return Ok(());
         }
+        let alloc = |text: &str| pool.map_or_else(|| text.to_string(), |a| a.alloc_str(text));
 
         // Expand keybinding aliases if enabled
-        let expanded = if self.expand_bindings {
-            self.keybindings.expand_line(trimmed)
+        let mut expanded = alloc("");
+        if self.expand_bindings {
+            self.keybindings.expand_line_into(trimmed, &mut expanded);
         } else {
-            trimmed.to_string()
-        };
+            expanded.push_str(trimmed);
+        }
 
         // Store source for diagnostic rendering
-        self.current_source = Some(expanded.clone());
+        let previous = self.current_source.replace(alloc(&expanded));
+        if let (Some(pool), Some(previous)) = (pool, previous) {
+            pool.recycle(previous);
+        }
 
         // Buffer all tokens for lookahead
+        let tokenize = self.phase_start();
         let tokenizer = Tokenizer::new(&expanded);
         self.token_buffer.clear();
         for token in tokenizer {
-            self.token_buffer.push_back(token.into());
+            let s = token.span;
+            let span = Span::with_length(line_no + s.line() - 1, s.column(), s.offset(), s.length());
+            let text = alloc(token.text);
+            self.token_buffer.push_back(OwnedToken { kind: token.kind, text, span, op: None });
+        }
+        let mut tokens = std::mem::take(&mut self.token_buffer);
+        let bound = self.expand_macros(&mut tokens).and_then(|()| self.bind_tokens(tokens.make_contiguous()));
+        self.token_buffer = tokens;
+        self.phase_end(Phase::Tokenize, tokenize);
+        if let Err(e) = bound {
+            if let Some(pool) = pool {
+                for token in self.token_buffer.drain(..) {
+                    pool.recycle(token.text);
+                }
+            }
+            self.token_buffer.clear();
+            return Err(e);
         }
 
-        // Process tokens
+        // Process tokens; an error abandons the calls and blocks this
+        // line opened
+        let (calls, blocks) = (self.call_stack.len(), self.block_stack.depth());
+        let Some(pool) = pool else {
+            while let Some(token) = self.token_buffer.pop_front() {
+                let result = self.dispatch_owned_token(&token);
+                self.stream_stack();
+                if let Err(e) = result {
+                    self.abandon(calls, blocks);
+                    return Err(e);
+                }
+            }
+            self.finish_line();
+            return Ok(());
+        };
+        pool.recycle(expanded);
         while let Some(token) = self.token_buffer.pop_front() {
-            self.dispatch_owned_token(&token)?;
+            let result = self.dispatch_owned_token(&token);
+            self.stream_stack();
+            pool.recycle(token.text);
+            if let Err(e) = result {
+                self.abandon(calls, blocks);
+                for token in self.token_buffer.drain(..) {
+                    pool.recycle(token.text);
+                }
+                return Err(e);
+            }
+        }
+        self.finish_line();
+        Ok(())
+    }
+
+    /// Compile a line's tokens: bind symbols naming registered ops to
+    /// their registry slots, so running them skips name resolution, and in
+    /// [strict](Self::strict) mode reject symbols that name nothing.
+    ///
+    /// A symbol is known if it is a special form or a registered op, a
+    /// function or variable that exists now, a name this line (or the
+    /// script being run) declares with `⊕` or `字`, the name argument of a
+    /// form such as `巡` or `読`, or a `match_tag` pattern.
+    fn bind_tokens(&self, tokens: &mut [OwnedToken]) -> Result<()> {
+        let declared = if self.strict {
+            declared_names(tokens.iter().map(|t| (t.kind, t.text.as_str())))
+        } else {
+            HashSet::new()
+        };
+        let mut naming = false;
+        for i in 0..tokens.len() {
+            let next_opens_block = tokens.get(i + 1).is_some_and(|t| t.text == "⺆");
+            let Some(token) = tokens.get_mut(i) else { break };
+            let after_naming_form = std::mem::replace(&mut naming, false);
+            if token.kind != TokenKind::Symbol {
+                continue;
+            }
+            let name = token.text.as_str();
+            if SPECIAL_FORMS.contains(&name) {
+                naming = NAMING_FORMS.contains(&name);
+                continue;
+            }
+            if let Some(rename) = self.renames.iter().find(|r| r.old == name && !self.registry.contains(name)) {
+                token.text = rename.new.to_string();
+            }
+            let name = token.text.as_str();
+            if let Some(id) = self.registry.id_of(name) {
+                token.op = Some(id);
+                continue;
+            }
+            let known = after_naming_form
+                || next_opens_block
+                || self.registry.contains(name)
+                || self.has_function(name)
+                || self.has_var(name)
+                || name.strip_prefix("with_").is_some_and(|kind| self.has_resource(kind))
+                || declared.contains(name)
+                || self.declared.contains(name);
+            if self.strict && !known {
+                return Err(WofError::parse(format!("unknown symbol '{name}' (strict mode)"), token.span));
+            }
+        }
+        Ok(())
+    }
+
+    /// Tokenize a line as [`exec_line`](Self::exec_line) does, with its
+    /// tokens attributed to source line `line_no` and bound to ops.
+    pub(crate) fn line_tokens(&self, line: &str, line_no: u32) -> Result<Vec<OwnedToken>> {
+        let mut tokens = self.lex_line(line, line_no);
+        self.bind_tokens(&mut tokens)?;
+        Ok(tokens)
+    }
+
+    /// Tokenize a line with its tokens attributed to source line `line_no`.
+    fn lex_line(&self, line: &str, line_no: u32) -> Vec<OwnedToken> {
+        let trimmed = line.trim();
+        let expanded = if self.expand_bindings {
+            self.keybindings.expand_line(trimmed)
+        } else {
+            trimmed.to_string()
+        };
+        Tokenizer::new(&expanded)
+            .map(|token| {
+                let s = token.span;
+                let span = Span::with_length(line_no + s.line() - 1, s.column(), s.offset(), s.length());
+                OwnedToken { kind: token.kind, text: token.text.to_string(), span, op: None }
+            })
+            .collect()
+    }
+
+    /// Tokenize every line of a script as [`exec_source`](Self::exec_source)
+    /// would run them, expanding macros and accepting in strict mode names
+    /// declared anywhere in the script.
+    pub(crate) fn script_tokens(&mut self, content: &str) -> Result<Vec<OwnedToken>> {
+        let outer_renames = self.enter_version(content)?;
+        let outer = if self.strict {
+            let names = declared_names(Tokenizer::new(content).map(|t| (t.kind, t.text)));
+            Some(std::mem::replace(&mut self.declared, names))
+        } else {
+            None
+        };
+        let mut tokens = Vec::new();
+        let mut result = Ok(());
+        for (line_no, line) in statements(content) {
+            if compat::directive(line).is_some() {
+                continue;
+            }
+            let mut line_tokens = self.lex_line(line, line_no).into();
+            let bound = self
+                .expand_macros(&mut line_tokens)
+                .and_then(|()| self.bind_tokens(line_tokens.make_contiguous()));
+            match bound {
+                Ok(()) => tokens.extend(line_tokens),
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
         }
+        if let Some(outer) = outer {
+            self.declared = outer;
+        }
+        self.renames = outer_renames;
+        result.map(|()| tokens)
+    }
 
+    /// Per-line debug dump shared by the line executors.
+    fn finish_line(&self) {
         if self.debug {
             eprintln!("[debug] stack: {}", self.stack);
             eprintln!("[debug] scope depth: {}", self.scopes.depth());
         }
-
-        Ok(())
     }
 
     /// Execute a script from a file.
@@ -528,33 +1138,267 @@ impl Interpreter {
         self.current_filename = Some(filename);
 
         let content = fs::read_to_string(path).map_err(WofError::from)?;
+        self.exec_source(&content)?;
+
+        self.current_filename = None;
+        Ok(())
+    }
 
+    /// Execute a multi-line script held in memory.
+    ///
+    /// Lines are executed in order exactly as [`exec_file`](Self::exec_file)
+    /// would, with errors carrying the 1-based line number.
+    ///
+    /// A script may declare the language version it was written for with
+    /// a `@wof-version 0.0.3` line: ops renamed since then run under
+    /// their new names, and a script for a newer language than
+    /// [`LANGUAGE_VERSION`](crate::LANGUAGE_VERSION) is refused before
+    /// any of it runs.
+    pub fn exec_source(&mut self, content: &str) -> Result<()> {
+        let outer_renames = self.enter_version(content)?;
         // Two-pass execution: first collect labels, then execute
-        self.collect_labels(&content);
+        self.collect_labels(content);
+        if self.coverage.is_some() {
+            self.register_coverage_file(content);
+        }
+        // Strict mode accepts names declared anywhere in the script, so
+        // functions may call functions defined further down.
+        let outer = if self.strict {
+            let names = declared_names(Tokenizer::new(content).map(|t| (t.kind, t.text)));
+            Some(std::mem::replace(&mut self.declared, names))
+        } else {
+            None
+        };
 
-        for (line_num, line) in content.lines().enumerate() {
-            if let Err(e) = self.exec_line(line) {
+        let mut result = Ok(());
+        for (line_no, line) in statements(content) {
+            if compat::directive(line).is_some() {
+                continue;
+            }
+            if let Err(e) = self.exec_line_numbered(line, line_no, None) {
                 // Enrich error with file context if it doesn't already have a span
-                if e.span().is_none() {
-                    let span = Span::with_length(
-                        (line_num + 1) as u32,
-                        1,
-                        0,
-                        line.len() as u32,
-                    );
-                    return Err(WofError::runtime_at(e.to_string(), span));
+                let span = Span::with_length(line_no, 1, 0, line.len() as u32);
+                result = Err(located(e, span));
+                break;
+            }
+        }
+        if let Some(outer) = outer {
+            self.declared = outer;
+        }
+        self.renames = outer_renames;
+        result
+    }
+
+    /// Apply a script's `@wof-version` directive: refuse a script for a
+    /// newer language, and otherwise put in force the renames it needs,
+    /// noting on stderr each old name it uses. Returns the renames in
+    /// force before, for the caller to restore when the script ends.
+    fn enter_version(&mut self, content: &str) -> Result<Vec<&'static Rename>> {
+        let declared = statements(content).into_iter().find_map(|(line_no, line)| {
+            compat::directive(line).map(|version| (version, Span::with_length(line_no, 1, 0, line.len() as u32)))
+        });
+        let renames = match declared {
+            Some((version, span)) => compat::renames_for(version, span)?,
+            None => Vec::new(),
+        };
+        let mut noted = HashSet::new();
+        for (line_no, line) in statements(content) {
+            for token in Tokenizer::new(line) {
+                if token.kind != TokenKind::Symbol || self.registry.contains(token.text) {
+                    continue;
+                }
+                if let Some(rename) = renames.iter().find(|r| r.old == token.text) {
+                    if noted.insert(rename.old) {
+                        eprintln!("{}", compat::note(rename, line_no + token.span.line() - 1));
+                    }
                 }
-                return Err(e);
             }
         }
+        Ok(std::mem::replace(&mut self.renames, renames))
+    }
 
-        self.current_filename = None;
+    /// Register a file's executable lines and function definitions
+    /// with the active coverage record.
+    fn register_coverage_file(&mut self, source: &str) {
+        let (Some(coverage), Some(file)) = (&mut self.coverage, &self.current_filename) else {
+            return;
+        };
+        coverage.reset_line();
+
+        let tokens: Vec<Token<'_>> = statements(source)
+            .into_iter()
+            .flat_map(|(line_no, line)| {
+                Tokenizer::new(line).map(move |mut t| {
+                    let s = t.span;
+                    t.span = Span::with_length(line_no + s.line() - 1, s.column(), s.offset(), s.length());
+                    t
+                })
+            })
+            .collect();
+
+        coverage.register_file(file, tokens.iter().map(|t| t.span.line()));
+        for pair in tokens.windows(2) {
+            if matches!(pair[0].text, "⊕" | "fn" | "func" | "def") {
+                coverage.register_function(file, pair[1].text, pair[0].span.line());
+            }
+        }
+    }
+
+    /// Start collecting coverage, discarding any previous record.
+    pub fn enable_coverage(&mut self) {
+        self.coverage = Some(Coverage::new());
+    }
+
+    /// The coverage collected so far, if enabled.
+    #[must_use]
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    /// Stop collecting coverage and return the record.
+    pub fn take_coverage(&mut self) -> Option<Coverage> {
+        self.coverage.take()
+    }
+
+    /// Start timing the dispatch path, discarding any previous profile.
+    pub fn enable_phase_profile(&mut self) {
+        self.profile = Some(PhaseProfile::new());
+    }
+
+    /// The dispatch-path timings collected so far, if enabled.
+    #[must_use]
+    pub fn phase_profile(&self) -> Option<&PhaseProfile> {
+        self.profile.as_ref()
+    }
+
+    /// Stop timing the dispatch path and return the profile.
+    pub fn take_phase_profile(&mut self) -> Option<PhaseProfile> {
+        self.profile.take()
+    }
+
+    /// Start timing each op and user function, discarding any previous
+    /// op profile.
+    pub fn enable_op_profile(&mut self) {
+        self.op_profile = Some(OpProfile::new());
+    }
+
+    /// The per-op and per-function timings collected so far, if enabled.
+    #[must_use]
+    pub fn op_profile(&self) -> Option<&OpProfile> {
+        self.op_profile.as_ref()
+    }
+
+    /// Stop timing ops and functions and return the profile.
+    pub fn take_op_profile(&mut self) -> Option<OpProfile> {
+        self.op_profile.take()
+    }
+
+    /// Charge a call of `function` started at `started` that is
+    /// returning, unless an outer call of it is still running and will
+    /// cover this time.
+    fn profile_return(&mut self, function: &str, started: Option<Instant>) {
+        let (Some(profile), Some(started)) = (&mut self.op_profile, started) else {
+            return;
+        };
+        if !self.call_stack.iter().any(|f| f.function == function) {
+            profile.add_function_time(function, started.elapsed());
+        }
+    }
+
+    /// Start a phase timer when profiling is enabled.
+    fn phase_start(&self) -> Option<Instant> {
+        self.profile.is_some().then(Instant::now)
+    }
+
+    /// Charge the time since `start` to `phase`.
+    fn phase_end(&mut self, phase: Phase, start: Option<Instant>) {
+        if let (Some(profile), Some(start)) = (&mut self.profile, start) {
+            profile.add(phase, start.elapsed());
+        }
+    }
+
+    /// Attach a debugger frontend, replacing any attached one. Breakpoints
+    /// are kept.
+    pub fn attach_debugger(&mut self, frontend: impl DebugFrontend + 'static) {
+        self.debugger.attach(Box::new(frontend));
+    }
+
+    /// Detach the debugger frontend; returns whether one was attached.
+    pub fn detach_debugger(&mut self) -> bool {
+        self.debugger.detach().is_some()
+    }
+
+    /// Check whether a debugger frontend is attached.
+    #[must_use]
+    pub fn has_debugger(&self) -> bool {
+        self.debugger.is_attached()
+    }
+
+    /// Pause at `breakpoint` from now on; returns false if it was
+    /// already set.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> bool {
+        self.debugger.add(breakpoint)
+    }
+
+    /// Remove a breakpoint; returns whether it was set.
+    pub fn remove_breakpoint(&mut self, breakpoint: &Breakpoint) -> bool {
+        self.debugger.remove(breakpoint)
+    }
+
+    /// Remove every breakpoint.
+    pub fn clear_breakpoints(&mut self) {
+        self.debugger.clear();
+    }
+
+    /// The breakpoints set, ops before lines.
+    #[must_use]
+    pub fn breakpoints(&self) -> Vec<&Breakpoint> {
+        self.debugger.breakpoints().collect()
+    }
+
+    /// Pause before the next token that runs, if a debugger is attached.
+    pub fn step(&mut self) {
+        self.debugger.set_stepping(true);
+    }
+
+    /// The calls and loops now running, outermost first.
+    #[must_use]
+    pub fn frames(&self) -> Vec<DebugFrame> {
+        let loop_frame = |f: &LoopFrame| DebugFrame::Loop { loop_type: f.loop_type, iteration: f.iteration };
+        let mut frames = Vec::with_capacity(self.call_stack.len() + self.loop_stack.len());
+        let mut loops = self.loop_stack.iter();
+        let mut outer = 0;
+        for call in &self.call_stack {
+            // The loops that were running when the call was made
+            frames.extend(loops.by_ref().take(call.loop_depth.saturating_sub(outer)).map(loop_frame));
+            outer = outer.max(call.loop_depth);
+            frames.push(DebugFrame::Call { function: call.function.clone() });
+        }
+        frames.extend(loops.map(loop_frame));
+        frames
+    }
+
+    /// Hand a pause before `token` to the debugger frontend, if the token
+    /// should pause.
+    fn debug_pause(&mut self, token: &OwnedToken) -> Result<()> {
+        let Some(reason) = self.debugger.check(&token.text, token.kind == TokenKind::Symbol, token.span) else {
+            return Ok(());
+        };
+        let Some(mut frontend) = self.debugger.take_frontend() else {
+            return Ok(());
+        };
+        let pause = Pause { reason, token: token.text.clone(), span: token.span };
+        let action = frontend.paused(self, &pause);
+        self.debugger.resume(frontend, action);
+        if action == DebugAction::Abort {
+            return Err(WofError::interrupted(format!("by the debugger before '{}' at {}", token.text, token.span)));
+        }
         Ok(())
     }
 
     /// Collect labels from source (first pass for file execution).
     fn collect_labels(&mut self, source: &str) {
-        for line in source.lines() {
+        for (_, line) in statements(source) {
             let trimmed = line.trim();
             // Look for label definitions (:name) followed by code
             if let Some(label_part) = trimmed.strip_prefix(':') {
@@ -730,6 +1574,10 @@ impl Interpreter {
 
     /// Dispatch an owned token.
     fn dispatch_owned_token(&mut self, token: &OwnedToken) -> Result<()> {
+        if self.cancel.reset() {
+            return Err(WofError::interrupted(format!("before '{}' at {}", token.text, token.span)));
+        }
+
         // If we're collecting a loop body, handle that first
         if self.collecting_loop.is_some() {
             return self.handle_loop_collect_mode(token);
@@ -751,33 +1599,73 @@ impl Interpreter {
             return Ok(());
         }
 
-        match token.kind {
-            TokenKind::Integer => {
-                let value: i64 = token.text.parse().map_err(|e: std::num::ParseIntError| {
-                    WofError::parse(e.to_string(), token.span)
-                })?;
-                self.stack.push(WofValue::integer(value));
+        // A then-block's outcome only matters to an immediately following 或
+        let then_ran = self.then_ran.take();
+        if let Some((block_type, run)) = self.pending_branch.take() {
+            return self.open_branch(token, block_type, run);
+        }
+        if token.kind == TokenKind::Symbol && matches!(token.text.as_str(), "或" | "else") {
+            let ran = then_ran
+                .ok_or_else(|| WofError::runtime_at("或 (else) must follow a 若 ⺆ ... ⺘ block", token.span))?;
+            self.pending_branch = Some((BlockType::Else, !ran));
+            return Ok(());
+        }
+
+        if self.debugger.is_armed() {
+            self.debug_pause(token)?;
+        }
+        if let (Some(coverage), Some(file)) = (&mut self.coverage, &self.current_filename) {
+            coverage.record_line(file, token.span.line());
+        }
+
+        let before = self.stack.len();
+        match token.kind {
+            TokenKind::Integer => {
+                let convert = self.phase_start();
+                // Literals past the i64 range become bigints
+                let value = match token.text.parse::<i64>() {
+                    Ok(n) => WofValue::integer(n),
+                    Err(e) => token
+                        .text
+                        .parse::<BigInt>()
+                        .map(WofValue::bigint)
+                        .map_err(|_| WofError::parse(e.to_string(), token.span))?,
+                };
+                self.stack.push(value);
+                self.phase_end(Phase::Convert, convert);
+                self.trace.literal(&token.text, self.stack.as_slice());
             }
             TokenKind::Float => {
+                let convert = self.phase_start();
                 let value: f64 = token.text.parse().map_err(|e: std::num::ParseFloatError| {
                     WofError::parse(e.to_string(), token.span)
                 })?;
                 self.stack.push(WofValue::double(value));
+                self.phase_end(Phase::Convert, convert);
+                self.trace.literal(&token.text, self.stack.as_slice());
             }
             TokenKind::String => {
+                let convert = self.phase_start();
                 let value = crate::tokenizer::parse_string_literal(&token.text);
                 self.stack.push(WofValue::string(value));
+                self.phase_end(Phase::Convert, convert);
+                self.trace.literal(&token.text, self.stack.as_slice());
+            }
+            TokenKind::Interpolated => {
+                let value = self.interpolate(&token.text, token.span)?;
+                self.stack.push(WofValue::string(value));
+                self.trace.literal(&token.text, self.stack.as_slice());
             }
             TokenKind::Symbol => {
-                self.dispatch_symbol(&token.text, token.span)
-                    .map_err(|e| {
-                        // Enrich spanless errors with the token's span
-                        if e.span().is_none() {
-                            WofError::runtime_at(e.to_string(), token.span)
-                        } else {
-                            e
-                        }
-                    })?;
+                // Bound symbols go straight to their op; the rest are
+                // resolved by name. Enrich spanless errors with the span.
+                let bound = token.op.and_then(|id| self.registry.bound(id, &token.text)).cloned();
+                match bound {
+                    Some(op) => self.call_op(&token.text, &op, token.span, self.phase_start()),
+                    None => self.dispatch_symbol(&token.text, token.span),
+                }
+                .map_err(|e| located(e, token.span))?;
+                self.trace.symbol(&token.text, before, self.stack.as_slice());
             }
             TokenKind::Label => {
                 // Label definition (:name) - register in label table
@@ -795,17 +1683,403 @@ impl Interpreter {
                 // Label reference (@name) - for jumps
                 let name = token.text.trim_start_matches('@');
                 self.stack.push(WofValue::symbol(format!("@{name}")));
+                self.trace.symbol(&token.text, before, self.stack.as_slice());
+            }
+            TokenKind::Infix => {
+                // `=( expr )`: compile the inner expression, one column in
+                let inner = token
+                    .text
+                    .strip_prefix("=(")
+                    .and_then(|t| t.strip_suffix(')'))
+                    .ok_or_else(|| WofError::parse("unclosed infix expression", token.span))?;
+                let s = token.span;
+                let origin = Span::with_length(s.line(), s.column() + 2, s.offset() + 2, 0);
+                self.exec_infix_at(inner, origin)?;
             }
             TokenKind::Eof => {}
         }
         Ok(())
     }
 
+    /// Install an op hook under `name`, replacing any existing hook of
+    /// that name.
+    pub fn add_hook(&mut self, name: impl Into<String>, hook: impl OpHook + 'static) {
+        self.hooks.add(name.into(), Box::new(hook));
+    }
+
+    /// Remove the op hook installed under `name`.
+    ///
+    /// Returns `true` if a hook was removed.
+    pub fn remove_hook(&mut self, name: &str) -> bool {
+        self.hooks.remove(name)
+    }
+
+    /// Check whether an op hook is installed under `name`.
+    #[must_use]
+    pub fn has_hook(&self, name: &str) -> bool {
+        self.hooks.contains(name)
+    }
+
+    /// Plugin state of type `T` owned by this interpreter, created with
+    /// `T::default()` on first use.
+    ///
+    /// Ops that need to remember something between calls (oscillator
+    /// phases, named tables) keep it here instead of in globals, so two
+    /// interpreters never share it.
+    pub fn state_mut<T: Any + Default + Send + Sync>(&mut self) -> &mut T {
+        self.state
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::<T>::default())
+            .downcast_mut()
+            .expect("state entry is keyed by its own type")
+    }
+
+    /// Plugin state of type `T`, if any has been created.
+    #[must_use]
+    pub fn state<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.state.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    /// A handle that stops this interpreter's current computation when
+    /// cancelled, for example from a Ctrl-C handler.
+    #[must_use]
+    pub fn cancel_token(&self) -> CancelToken {
+        self.cancel.clone()
+    }
+
+    /// Whether cancellation has been requested. Long-running ops poll
+    /// this between units of work and stop with [`Interpreter::interrupt`].
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Acknowledge a cancellation request and build the error reporting
+    /// how far the op got, such as `"after 12 of 100 games"`.
+    pub fn interrupt(&self, progress: impl Into<String>) -> WofError {
+        self.cancel.reset();
+        WofError::interrupted(progress)
+    }
+
+    /// The file access policy that file ops check paths against.
+    #[must_use]
+    pub fn sandbox(&self) -> &Sandbox {
+        &self.sandbox
+    }
+
+    /// Replace the file access policy.
+    pub fn set_sandbox(&mut self, sandbox: Sandbox) {
+        self.sandbox = sandbox;
+    }
+
+    /// Send progress reports to `sink`, replacing any previous sink.
+    pub fn set_progress_sink(&mut self, sink: impl ProgressSink + 'static) {
+        self.progress_sink = Some(Box::new(sink));
+    }
+
+    /// Stop reporting progress. Returns `true` if a sink was installed.
+    pub fn clear_progress_sink(&mut self) -> bool {
+        self.progress_sink.take().is_some()
+    }
+
+    /// Report that `done` of `total` units of `message` are complete.
+    ///
+    /// Long-running ops call this as they go; it does nothing unless a
+    /// sink is installed, so reporting often is cheap.
+    pub fn progress(&mut self, done: usize, total: usize, message: &str) {
+        if let Some(sink) = &mut self.progress_sink {
+            sink.report(&Progress { done, total, message: message.to_string() });
+        }
+    }
+
+    /// Reconstruct the infix expression that produced the top of stack.
+    ///
+    /// Returns the plain value when its provenance is unknown (for
+    /// example after a plugin op), and `None` on an empty stack.
+    #[must_use]
+    pub fn explain(&self) -> Option<String> {
+        self.trace.explain(self.stack.as_slice())
+    }
+
+    /// Take the tokens of a block whose `⺆` was just consumed, up to
+    /// and excluding the matching `⺘`.
+    ///
+    /// Returns `None` (leaving the buffer drained) if the block is not
+    /// closed on this line.
+    fn take_block(&mut self) -> Option<Vec<OwnedToken>> {
+        let mut body = Vec::new();
+        let mut depth = 0usize;
+        while let Some(token) = self.token_buffer.pop_front() {
+            match token.text.as_str() {
+                "⺆" => depth += 1,
+                "⺘" if depth == 0 => return Some(body),
+                "⺘" => depth -= 1,
+                _ => {}
+            }
+            body.push(token);
+        }
+        None
+    }
+
+    /// Take the string literals immediately following a definition form.
+    fn take_string_args(&mut self) -> Vec<String> {
+        let mut parts = Vec::new();
+        while let Some(t) = self.token_buffer.front() {
+            if t.kind != TokenKind::String {
+                break;
+            }
+            parts.push(crate::tokenizer::parse_string_literal(&t.text));
+            self.token_buffer.pop_front();
+        }
+        parts
+    }
+
+    /// Execute `tokens` as a line of their own, replacing anything left
+    /// in the token buffer.
+    ///
+    /// A return out of the function they run in ends them early, leaving
+    /// that function's caller to carry on from the token buffer.
+    pub(crate) fn run_tokens(&mut self, tokens: Vec<OwnedToken>) -> Result<()> {
+        let calls = self.call_stack.len();
+        self.token_buffer = tokens.into();
+        while self.call_stack.len() >= calls {
+            let Some(token) = self.token_buffer.pop_front() else { break };
+            self.dispatch_owned_token(&token)?;
+        }
+        Ok(())
+    }
+
+    /// How many function calls are running.
+    pub(crate) fn call_depth(&self) -> usize {
+        self.call_stack.len()
+    }
+
+    /// Run `body` `runs` times, print timing statistics and push the
+    /// mean time per run in milliseconds.
+    ///
+    /// Every run starts from the same stack, so the body may consume
+    /// its inputs; whatever it leaves behind is discarded. Functions the
+    /// body calls run within its timing.
+    fn bench(&mut self, body: &[OwnedToken], runs: usize) -> Result<()> {
+        if runs == 0 {
+            return Err(WofError::InvalidArgument("bench: N must be positive".into()));
+        }
+        // `Instant::now` panics on wasm32-unknown-unknown, which has no clock.
+        if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+            return Err(WofError::Runtime("bench: no clock on this target".into()));
+        }
+
+        let snapshot = self.stack.clone();
+        let calls = self.call_stack.len();
+        let mut times = Vec::with_capacity(runs);
+        for _ in 0..runs {
+            self.stack = snapshot.clone();
+            let tokens = body.to_vec();
+            let start = Instant::now();
+            let result = self.run_guarded(tokens);
+            times.push(start.elapsed());
+            if let Err((e, at)) = result {
+                self.stack = snapshot;
+                return Err(located(e, at));
+            }
+            // A return ends the benchmark along with the function.
+            if self.call_stack.len() < calls {
+                return Ok(());
+            }
+        }
+        self.stack = snapshot;
+
+        times.sort_unstable();
+        let total: Duration = times.iter().sum();
+        let mean = total.div_f64(runs as f64);
+        let p95 = times[((runs * 95).div_ceil(100)).saturating_sub(1)];
+        let ops_per_sec = if mean.is_zero() { f64::INFINITY } else { 1.0 / mean.as_secs_f64() };
+
+        writeln!(
+            self.out,
+            "[bench] {runs} runs: min {:?}  mean {mean:?}  p95 {p95:?}  ({ops_per_sec:.0} ops/sec)",
+            times[0]
+        );
+        self.stack.push(WofValue::double(mean.as_secs_f64() * 1000.0));
+        Ok(())
+    }
+
+    /// Run a `jit` block: natively when it is a numeric kernel and the
+    /// `jit` feature is on, otherwise on the interpreter.
+    ///
+    /// Blocks are always interpreted while hooks, coverage or a profile
+    /// are observing ops, since a kernel runs no op handlers.
+    fn run_jit(&mut self, body: Vec<OwnedToken>) -> Result<()> {
+        #[cfg(feature = "jit")]
+        if self.hooks.is_empty() && self.coverage.is_none() && self.profile.is_none() && self.op_profile.is_none() {
+            match crate::jit::run(self, &body) {
+                crate::jit::Outcome::Done => return Ok(()),
+                crate::jit::Outcome::Interrupted => {
+                    self.cancel.reset();
+                    return Err(WofError::interrupted("in a jit block (stack unchanged)"));
+                }
+                crate::jit::Outcome::Fallback => {}
+            }
+        }
+        let rest = std::mem::take(&mut self.token_buffer);
+        let calls = self.call_stack.len();
+        let result = self.run_tokens(body);
+        if self.call_stack.len() >= calls {
+            self.token_buffer = rest;
+        }
+        result
+    }
+
+    /// Run `source` to completion from inside an op.
+    ///
+    /// The rest of the current line stays queued and resumes once the
+    /// source has run, so ops can execute code they build (`eval_list`).
+    pub fn run_source(&mut self, source: &str) -> Result<()> {
+        let mut tokens = Tokenizer::new(source)
+            .map(|t| OwnedToken { kind: t.kind, text: t.text.to_string(), span: Span::synthetic(), op: None })
+            .collect();
+        self.expand_macros(&mut tokens)?;
+        self.run_nested(tokens.into())
+    }
+
+    /// Run `tokens` to completion, then resume the rest of the current
+    /// line; calls they leave open are abandoned.
+    ///
+    /// If `tokens` return from the function they run in, the rest of the
+    /// line is dropped with the rest of that function, and its caller's
+    /// tokens, now in the token buffer, run next.
+    pub(crate) fn run_nested(&mut self, tokens: Vec<OwnedToken>) -> Result<()> {
+        let rest = std::mem::take(&mut self.token_buffer);
+        let depth = self.call_stack.len();
+        let result = self.run_tokens(tokens);
+        if self.call_stack.len() < depth {
+            return result;
+        }
+        self.unwind_calls(depth);
+        self.token_buffer = rest;
+        result
+    }
+
+    /// Run `tokens` as [`run_nested`](Self::run_nested) does, but on
+    /// failure also close the blocks they opened and return the span of
+    /// the token that failed alongside the error.
+    pub(crate) fn run_guarded(&mut self, tokens: Vec<OwnedToken>) -> std::result::Result<(), (WofError, Span)> {
+        let rest = std::mem::take(&mut self.token_buffer);
+        let calls = self.call_stack.len();
+        let blocks = self.block_stack.depth();
+        self.token_buffer = tokens.into();
+        let mut result = Ok(());
+        while self.call_stack.len() >= calls {
+            let Some(token) = self.token_buffer.pop_front() else { break };
+            if let Err(e) = self.dispatch_owned_token(&token) {
+                result = Err((e, token.span));
+                break;
+            }
+        }
+        match result {
+            Err(_) => self.abandon(calls, blocks),
+            Ok(()) if self.call_stack.len() < calls => return result,
+            Ok(()) => self.unwind_calls(calls),
+        }
+        self.token_buffer = rest;
+        result
+    }
+
+    /// Give up on code that failed: return from the calls above `calls`
+    /// and close the blocks above `blocks`, along with any conditional
+    /// waiting for its branch.
+    fn abandon(&mut self, calls: usize, blocks: usize) {
+        self.unwind_calls(calls);
+        self.unwind_blocks(blocks);
+        self.pending_branch = None;
+        self.then_ran = None;
+    }
+
+    /// Evaluate an infix expression such as `3*(2+4)^2`.
+    ///
+    /// The expression is compiled to postfix tokens and executed in place,
+    /// so it sees (and leaves its result on) the current stack.
+    pub fn exec_infix(&mut self, expr: &str) -> Result<()> {
+        self.exec_infix_at(expr, Span::synthetic())
+    }
+
+    fn exec_infix_at(&mut self, expr: &str, origin: Span) -> Result<()> {
+        let tokens = crate::infix::compile(expr, origin)?;
+        self.run_nested(tokens)
+    }
+
+    /// Build the string for a `$"..."` literal.
+    ///
+    /// Each `{expr}` segment is run as Woflang against the current stack
+    /// and scope, and the value it leaves on top is formatted in its
+    /// place; the stack is then put back, so segments never consume or
+    /// leave values. `{{` and `}}` are literal braces, and quotes inside a
+    /// segment are written `\"`.
+    fn interpolate(&mut self, text: &str, span: Span) -> Result<String> {
+        let inner = text
+            .strip_prefix("$\"")
+            .and_then(|t| t.strip_suffix('"'))
+            .ok_or_else(|| WofError::parse("unterminated interpolated string", span))?;
+        let literal = |raw: &str| crate::tokenizer::parse_string_literal(raw);
+
+        let mut out = String::with_capacity(inner.len());
+        let mut raw = String::new();
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    raw.push(c);
+                    raw.extend(chars.next());
+                }
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    raw.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    raw.push('}');
+                }
+                '{' => {
+                    let (expr, rest) = chars
+                        .as_str()
+                        .split_once('}')
+                        .ok_or_else(|| WofError::parse("unclosed '{' in interpolated string", span))?;
+                    chars = rest.chars();
+                    out.push_str(&literal(&raw));
+                    raw.clear();
+                    out.push_str(&self.interpolate_segment(&literal(expr), span)?);
+                }
+                '}' => return Err(WofError::parse("unmatched '}' in interpolated string", span)),
+                _ => raw.push(c),
+            }
+        }
+        out.push_str(&literal(&raw));
+        Ok(out)
+    }
+
+    /// Run one `{expr}` segment and format the value it leaves on top.
+    fn interpolate_segment(&mut self, expr: &str, span: Span) -> Result<String> {
+        let tokens = Tokenizer::new(expr)
+            .map(|t| OwnedToken { kind: t.kind, text: t.text.to_string(), span, op: None })
+            .collect();
+        let saved = self.stack.as_slice().to_vec();
+        let rest = std::mem::take(&mut self.token_buffer);
+        let result = self.run_tokens(tokens);
+        self.token_buffer = rest;
+        let value = self.stack.peek().ok().map(ToString::to_string);
+        self.stack.clear();
+        self.stack.push_all(saved);
+        result?;
+        value.ok_or_else(|| {
+            WofError::Runtime(format!("interpolation {{{}}} left nothing on the stack", expr.trim()))
+        })
+    }
+
     /// Handle tokens while collecting a loop body.
     fn handle_loop_collect_mode(&mut self, token: &OwnedToken) -> Result<()> {
         match token.text.as_str() {
-            "⺆" | "⟳" | "loop" => {
-                // Nested block/loop - increase depth
+            "⺆" => {
+                // Nested block - increase depth
                 self.loop_collect_depth += 1;
                 self.loop_body_buffer.push(token.clone());
             }
@@ -814,7 +2088,18 @@ impl Interpreter {
                     // End of loop body - execute it
                     let loop_type = self.collecting_loop.take().unwrap();
                     let body = std::mem::take(&mut self.loop_body_buffer);
-                    self.execute_loop(loop_type, body)?;
+                    if loop_type == LoopType::While && self.while_condition.is_none() {
+                        // That was the condition; the body block follows
+                        if !matches!(self.token_buffer.front(), Some(t) if t.text == "⺆") {
+                            return Err(WofError::Runtime("当 requires: 当 ⺆ cond ⺘ ⺆ body ⺘".into()));
+                        }
+                        self.token_buffer.pop_front();
+                        self.while_condition = Some(body);
+                        self.collecting_loop = Some(LoopType::While);
+                        return Ok(());
+                    }
+                    let condition = self.while_condition.take().unwrap_or_default();
+                    self.execute_loop(loop_type, &condition, body)?;
                 } else {
                     // End of nested block
                     self.loop_collect_depth -= 1;
@@ -830,7 +2115,16 @@ impl Interpreter {
     }
 
     /// Execute a loop with the given body.
-    fn execute_loop(&mut self, loop_type: LoopType, body: Vec<OwnedToken>) -> Result<()> {
+    ///
+    /// `condition` is run before each iteration of a `While` loop, which
+    /// stops when it leaves a falsy value; other loops ignore it.
+    fn execute_loop(&mut self, loop_type: LoopType, condition: &[OwnedToken], body: Vec<OwnedToken>) -> Result<()> {
+        // A zero or negative repeat count runs the body no times, rather
+        // than falling through to the unlimited `max_iterations == 0` case.
+        if matches!(loop_type, LoopType::Repeat(n) if n <= 0) {
+            return Ok(());
+        }
+
         let max_iterations = match loop_type {
             LoopType::Infinite => 0, // 0 = no limit
             LoopType::Repeat(n) => n,
@@ -849,52 +2143,69 @@ impl Interpreter {
             max_iterations,
         });
 
-        // Create scope for loop
+        // The body runs through the token buffer, so forms that look ahead
+        // (nested loops, 若) work inside it; the rest of the line waits.
+        let rest = std::mem::take(&mut self.token_buffer);
+        let depth = self.block_stack.depth();
         self.push_scope(BlockType::Loop);
+        let result = self.run_loop(loop_type, condition, &body);
+
+        // Unwind so an interrupted loop leaves no scope behind
+        self.unwind_blocks(depth);
+        self.loop_stack.pop();
+        self.token_buffer = rest;
+        self.break_signal = false;
+        self.continue_signal = false;
+        self.pending_branch = None;
+        self.then_ran = None;
+        result
+    }
 
-        // Execute loop iterations
+    /// Iterate a loop whose frame and scope are already pushed.
+    fn run_loop(&mut self, loop_type: LoopType, condition: &[OwnedToken], body: &[OwnedToken]) -> Result<()> {
+        let depth = self.block_stack.depth();
         loop {
             // Check iteration limit for repeat loops
             if let Some(frame) = self.loop_stack.last_mut() {
                 if frame.max_iterations > 0 && frame.iteration >= frame.max_iterations {
-                    break;
+                    return Ok(());
                 }
                 frame.iteration += 1;
             }
 
-            // Execute loop body
-            for token in &body {
-                self.dispatch_owned_token(token)?;
-                
-                // Check for break
-                if self.break_signal {
-                    self.break_signal = false;
-                    // Exit the loop
-                    self.loop_stack.pop();
-                    self.pop_scope();
+            if loop_type == LoopType::While {
+                self.run_loop_tokens(condition)?;
+                if !self.stack.pop()?.is_truthy() {
                     return Ok(());
                 }
-                
-                // Check for continue
-                if self.continue_signal {
-                    self.continue_signal = false;
-                    break; // Break inner loop, continue outer
-                }
             }
 
+            // Execute loop body, then close blocks a break or continue left open
+            self.run_loop_tokens(body)?;
+            self.unwind_blocks(depth);
+            if self.break_signal {
+                return Ok(());
+            }
+            self.continue_signal = false;
+
             // Safety limit for infinite loops (prevent runaway in REPL)
             if let Some(frame) = self.loop_stack.last() {
                 if frame.loop_type == LoopType::Infinite && frame.iteration > 1_000_000 {
-                    self.loop_stack.pop();
-                    self.pop_scope();
                     return Err(WofError::Runtime("infinite loop safety limit reached (1M iterations)".into()));
                 }
             }
         }
+    }
 
-        // Normal loop completion
-        self.loop_stack.pop();
-        self.pop_scope();
+    /// Run `tokens` from the token buffer, stopping at a break or continue.
+    fn run_loop_tokens(&mut self, tokens: &[OwnedToken]) -> Result<()> {
+        self.token_buffer = tokens.iter().cloned().collect();
+        while let Some(token) = self.token_buffer.pop_front() {
+            self.dispatch_owned_token(&token)?;
+            if self.break_signal || self.continue_signal {
+                break;
+            }
+        }
         Ok(())
     }
 
@@ -909,9 +2220,12 @@ impl Interpreter {
             "⺘" => {
                 if self.function_def_depth == 0 {
                     // End of function definition
-                    let name = self.defining_function.take().unwrap();
+                    let (name, arity) = self
+                        .defining_function
+                        .take()
+                        .ok_or_else(|| WofError::Runtime("⺘ without a function definition".into()))?;
                     let body = std::mem::take(&mut self.function_body_buffer);
-                    let func = FunctionDef::new(name, body, token.span);
+                    let func = FunctionDef::new(name, body, token.span).with_arity(arity);
                     self.define_function(func);
                 } else {
                     // End of nested block inside function
@@ -927,42 +2241,97 @@ impl Interpreter {
         Ok(())
     }
 
-    /// Handle tokens while in skip mode (skipping else branches etc).
+    /// Handle tokens while in skip mode (skipping a branch not taken).
+    ///
+    /// Only block delimiters matter: the branch ends at the `⺘` that
+    /// closes its own `⺆`, however deeply the `若`/`或` chains and loops
+    /// inside it are nested.
     fn handle_skip_mode(&mut self, token: &OwnedToken) -> Result<()> {
         match token.text.as_str() {
-            "⺆" | "若" | "loop" | "⟳" => {
-                // Nested block - increase skip depth
-                self.skip_depth += 1;
-            }
+            "⺆" => self.skip_depth += 1,
             "⺘" => {
-                // Block close - decrease skip depth
-                self.skip_depth = self.skip_depth.saturating_sub(1);
-            }
-            "或" if self.skip_depth == 1 => {
-                // We hit the else branch at our skip level - stop skipping
-                self.skip_depth = 0;
-            }
-            _ => {
-                // Skip this token
+                self.skip_depth -= 1;
+                if self.skip_depth == 0 && self.close_block() == Some(BlockType::Then) {
+                    self.then_ran = Some(false);
+                }
             }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Open the block following `若` or `或`, skipping it unless `run`.
+    fn open_branch(&mut self, token: &OwnedToken, block_type: BlockType, run: bool) -> Result<()> {
+        if token.text != "⺆" {
+            let form = if block_type == BlockType::Then { "若" } else { "或" };
+            return Err(WofError::runtime_at(
+                format!("{form} requires: cond 若 ⺆ then ⺘ 或 ⺆ else ⺘"),
+                token.span,
+            ));
+        }
+        self.push_scope(block_type);
+        if !run {
+            self.skip_depth = 1;
         }
         Ok(())
     }
 
+    /// Run the registered op `name`, found by a lookup that started at
+    /// `lookup`.
+    pub(crate) fn call_op(
+        &mut self,
+        name: &str,
+        op: &BoxedOp<Self>,
+        span: Span,
+        lookup: Option<Instant>,
+    ) -> Result<()> {
+        self.phase_end(Phase::Lookup, lookup);
+        if let Some(coverage) = &mut self.coverage {
+            coverage.record_op(name);
+        }
+        let handler = self.phase_start();
+        let timed = self.op_profile.is_some().then(Instant::now);
+        let result = if self.hooks.is_empty() {
+            op(self)
+        } else {
+            self.hooks.before(name, &self.stack);
+            let result = op(self);
+            self.hooks.after(name, &self.stack, &result, &self.out);
+            result
+        };
+        if let (Some(profile), Some(start)) = (&mut self.op_profile, timed) {
+            profile.add_op(name, start.elapsed());
+        }
+        self.phase_end(Phase::Handler, handler);
+        result.map_err(|e| located(e, span))
+    }
+
     /// Dispatch a symbol (operation or identifier).
-    fn dispatch_symbol(&mut self, name: &str, span: Span) -> Result<()> {
+    pub(crate) fn dispatch_symbol(&mut self, name: &str, span: Span) -> Result<()> {
+        let lookup = self.phase_start();
+
         // ═══════════════════════════════════════════════════════════════
-        // FUNCTION DEFINITION: ⊕name ⺆ ... ⺘
+        // FUNCTION DEFINITION: ⊕name [arity] ⺆ ... ⺘
         // ═══════════════════════════════════════════════════════════════
         if name == "⊕" || name == "fn" || name == "func" || name == "def" {
-            // Next token is function name, then ⺆
+            // Next token is function name, then an optional arity, then ⺆
             if let Some(next) = self.token_buffer.pop_front() {
                 if next.kind == TokenKind::Symbol {
                     let func_name = next.text.clone();
+                    let arity = match self.token_buffer.front() {
+                        Some(t) if t.kind == TokenKind::Integer => {
+                            let arity = t.text.parse().map_err(|_| {
+                                WofError::Runtime(format!("⊕ {func_name}: arity must be a non-negative integer"))
+                            })?;
+                            self.token_buffer.pop_front();
+                            arity
+                        }
+                        _ => 0,
+                    };
                     // Expect ⺆ next
                     if let Some(block_start) = self.token_buffer.pop_front() {
                         if block_start.text == "⺆" {
-                            self.defining_function = Some(func_name);
+                            self.defining_function = Some((func_name, arity));
                             self.function_body_buffer.clear();
                             self.function_def_depth = 0;
                             return Ok(());
@@ -972,7 +2341,7 @@ impl Interpreter {
                 }
                 self.token_buffer.push_front(next);
             }
-            return Err(WofError::Runtime("⊕ requires: ⊕ name ⺆ body ⺘".into()));
+            return Err(WofError::Runtime("⊕ requires: ⊕ name [arity] ⺆ body ⺘".into()));
         }
 
         // ═══════════════════════════════════════════════════════════════
@@ -991,7 +2360,7 @@ impl Interpreter {
         // ═══════════════════════════════════════════════════════════════
         // RETURN: 至
         // ═══════════════════════════════════════════════════════════════
-        if name == "至" || name == "return" || name == "ret" {
+        if is_return(name) {
             return self.return_from_function();
         }
 
@@ -1012,6 +2381,21 @@ impl Interpreter {
             return Err(WofError::Runtime("⟳ requires: ⟳ ⺆ body ⺘".into()));
         }
 
+        // ═══════════════════════════════════════════════════════════════
+        // WHILE LOOP: 当 ⺆ cond ⺘ ⺆ ... ⺘
+        // ═══════════════════════════════════════════════════════════════
+        if name == "当" || name == "while" {
+            if matches!(self.token_buffer.front(), Some(t) if t.text == "⺆") {
+                self.token_buffer.pop_front();
+                self.collecting_loop = Some(LoopType::While);
+                self.while_condition = None;
+                self.loop_body_buffer.clear();
+                self.loop_collect_depth = 0;
+                return Ok(());
+            }
+            return Err(WofError::Runtime("当 requires: 当 ⺆ cond ⺘ ⺆ body ⺘".into()));
+        }
+
         // ═══════════════════════════════════════════════════════════════
         // REPEAT N TIMES: N ⨯ ⺆ ... ⺘  or  ⨯ ⺆ ... ⺘ (N from stack)
         // ═══════════════════════════════════════════════════════════════
@@ -1032,6 +2416,252 @@ impl Interpreter {
             return Err(WofError::Runtime("⨯ requires: N ⨯ ⺆ body ⺘".into()));
         }
 
+        // ═══════════════════════════════════════════════════════════════
+        // BENCHMARK: bench ⺆ ... ⺘ N
+        // ═══════════════════════════════════════════════════════════════
+        if name == "bench" {
+            let usage = || WofError::Runtime("bench requires: bench ⺆ body ⺘ N".into());
+            if !matches!(self.token_buffer.front(), Some(t) if t.text == "⺆") {
+                return Err(usage());
+            }
+            self.token_buffer.pop_front();
+            let body = self.take_block().ok_or_else(usage)?;
+            let runs = match self.token_buffer.pop_front() {
+                Some(t) if t.kind == TokenKind::Integer => t
+                    .text
+                    .parse::<usize>()
+                    .map_err(|_| WofError::InvalidArgument("bench: N must be positive".into()))?,
+                _ => return Err(usage()),
+            };
+            return self.bench(&body, runs);
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // BACKGROUND JOB: spawn ⺆ ... ⺘ (pushes the job id)
+        // ═══════════════════════════════════════════════════════════════
+        if name == "spawn" {
+            let usage = || WofError::Runtime("spawn requires: spawn ⺆ body ⺘".into());
+            if !matches!(self.token_buffer.front(), Some(t) if t.text == "⺆") {
+                return Err(usage());
+            }
+            self.token_buffer.pop_front();
+            let body = self.take_block().ok_or_else(usage)?;
+            let id = self.spawn_job(body)?;
+            self.stack.push(WofValue::integer(id as i64));
+            return Ok(());
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // NATIVE KERNEL: jit ⺆ ... ⺘
+        // ═══════════════════════════════════════════════════════════════
+        if name == "jit" {
+            let usage = || WofError::Runtime("jit requires: jit ⺆ body ⺘".into());
+            if !matches!(self.token_buffer.front(), Some(t) if t.text == "⺆") {
+                return Err(usage());
+            }
+            self.token_buffer.pop_front();
+            let body = self.take_block().ok_or_else(usage)?;
+            return self.run_jit(body);
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // DYNAMIC VARIABLE: parameterize "name" value ⺆ ... ⺘
+        // ═══════════════════════════════════════════════════════════════
+        if name == "parameterize" {
+            let usage = || WofError::Runtime("parameterize requires: parameterize \"name\" value ⺆ body ⺘".into());
+            let param = match self.token_buffer.pop_front() {
+                Some(t) if t.kind == TokenKind::String => crate::tokenizer::parse_string_literal(&t.text),
+                _ => return Err(usage()),
+            };
+            let mut value_tokens = Vec::new();
+            loop {
+                match self.token_buffer.pop_front() {
+                    Some(t) if t.text == "⺆" => break,
+                    Some(t) => value_tokens.push(t),
+                    None => return Err(usage()),
+                }
+            }
+            let body = self.take_block().ok_or_else(usage)?;
+            let (depth, calls) = (self.stack.len(), self.call_stack.len());
+            self.run_nested(value_tokens)?;
+            if self.call_stack.len() < calls {
+                return Ok(());
+            }
+            if self.stack.len() != depth + 1 {
+                return Err(WofError::Runtime(format!("parameterize {param}: the value must be a single value")));
+            }
+            let value = self.stack.pop()?;
+            return self.parameterize(&param, value, |interp| interp.run_nested(body));
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // RESOURCE NAMESPACE: ns "name" ⺆ ... ⺘, global ⺆ ... ⺘
+        // ═══════════════════════════════════════════════════════════════
+        if name == "ns" {
+            let usage = || WofError::Runtime("ns requires: ns \"name\" ⺆ body ⺘".into());
+            let ns = match self.token_buffer.pop_front() {
+                Some(t) if t.kind == TokenKind::String => crate::tokenizer::parse_string_literal(&t.text),
+                _ => return Err(usage()),
+            };
+            if !matches!(self.token_buffer.pop_front(), Some(t) if t.text == "⺆") {
+                return Err(usage());
+            }
+            let body = self.take_block().ok_or_else(usage)?;
+            return self.in_namespace(&ns, |interp| interp.run_nested(body));
+        }
+        if name == "global" {
+            let usage = || WofError::Runtime("global requires: global ⺆ body ⺘".into());
+            if !matches!(self.token_buffer.pop_front(), Some(t) if t.text == "⺆") {
+                return Err(usage());
+            }
+            let body = self.take_block().ok_or_else(usage)?;
+            return self.in_global(|interp| interp.run_nested(body));
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // SCOPED RESOURCE: with_file "path" ⺆ ... ⺘
+        // ═══════════════════════════════════════════════════════════════
+        if let Some(kind) = name.strip_prefix("with_").filter(|kind| self.has_resource(kind)) {
+            let usage = || WofError::Runtime(format!("{name} requires: {name} args ⺆ body ⺘"));
+            let mut arg_tokens = Vec::new();
+            loop {
+                match self.token_buffer.pop_front() {
+                    Some(t) if t.text == "⺆" => break,
+                    Some(t) => arg_tokens.push(t),
+                    None => return Err(usage()),
+                }
+            }
+            let body = self.take_block().ok_or_else(usage)?;
+            let (depth, calls) = (self.stack.len(), self.call_stack.len());
+            self.run_nested(arg_tokens)?;
+            if self.call_stack.len() < calls {
+                return Ok(());
+            }
+            let mut args = self.stack.pop_n(self.stack.len().saturating_sub(depth))?;
+            args.reverse();
+            return self.with_resource(kind, args, |interp, handle| {
+                interp.stack.push(handle);
+                interp.run_nested(body)
+            });
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // RECORD DEFINITION: record "Name" "field" ...
+        // ═══════════════════════════════════════════════════════════════
+        if name == "record" {
+            let parts = self.take_string_args();
+            let Some((type_name, fields)) = parts.split_first() else {
+                return Err(WofError::Runtime(
+                    "record requires: record \"Name\" \"field\" ...".into(),
+                ));
+            };
+            return self.define_record(type_name, fields);
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // ENUM DEFINITION: enum "Name" "Variant/arity" ...
+        // ═══════════════════════════════════════════════════════════════
+        if name == "enum" {
+            let parts = self.take_string_args();
+            let Some((type_name, variants)) = parts.split_first() else {
+                return Err(WofError::Runtime(
+                    "enum requires: enum \"Name\" \"Variant/arity\" ...".into(),
+                ));
+            };
+            return self.define_enum(type_name, variants);
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // SCHEMA DEFINITION: schema "Name" "key:type(min..max)" ...
+        // ═══════════════════════════════════════════════════════════════
+        if name == "schema" {
+            let parts = self.take_string_args();
+            let Some((schema_name, fields)) = parts.split_first() else {
+                return Err(WofError::Runtime(
+                    "schema requires: schema \"Name\" \"key:type\" ...".into(),
+                ));
+            };
+            return self.define_schema(schema_name, fields);
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // TAG MATCH: v match_tag ⺆ Tag ⺆ ... ⺘ _ ⺆ ... ⺘ ⺘
+        // ═══════════════════════════════════════════════════════════════
+        if name == "match_tag" {
+            let usage = || WofError::Runtime("match_tag requires: match_tag ⺆ Tag ⺆ body ⺘ ... ⺘".into());
+            if !matches!(self.token_buffer.front(), Some(t) if t.text == "⺆") {
+                return Err(usage());
+            }
+            self.token_buffer.pop_front();
+            let arms = self.take_block().and_then(split_arms).ok_or_else(usage)?;
+            let value = self.stack.pop()?;
+            let tagged = value.as_tagged()?;
+            let Some((_, body)) = arms
+                .into_iter()
+                .find(|(pattern, _)| pattern == "_" || tagged.matches(pattern))
+            else {
+                return Err(WofError::Runtime(format!("match_tag: no arm for {tagged}")));
+            };
+            self.stack.push_all(tagged.payload().iter().cloned());
+            // Splice the arm in place so nested blocks see their own tokens.
+            for token in body.into_iter().rev() {
+                self.token_buffer.push_front(token);
+            }
+            return Ok(());
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // OPTION/RESULT: map_ok ⺆ ... ⺘, and_then ⺆ ... ⺘, try_prop
+        // ═══════════════════════════════════════════════════════════════
+        if name == "map_ok" || name == "and_then" {
+            let usage = || WofError::Runtime(format!("{name} requires: {name} ⺆ body ⺘"));
+            if !matches!(self.token_buffer.pop_front(), Some(t) if t.text == "⺆") {
+                return Err(usage());
+            }
+            let body = self.take_block().ok_or_else(usage)?;
+            return if name == "map_ok" { self.map_ok(body) } else { self.and_then(body) };
+        }
+        if name == "try_prop" {
+            return self.try_prop();
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // ERROR HANDLING: try ⺆ ... ⺘ catch ⺆ ... ⺘
+        // ═══════════════════════════════════════════════════════════════
+        if name == "try" {
+            let usage = || WofError::Runtime("try requires: try ⺆ body ⺘ catch ⺆ handler ⺘".into());
+            if !matches!(self.token_buffer.pop_front(), Some(t) if t.text == "⺆") {
+                return Err(usage());
+            }
+            let body = self.take_block().ok_or_else(usage)?;
+            if !matches!(self.token_buffer.pop_front(), Some(t) if t.text == "catch")
+                || !matches!(self.token_buffer.pop_front(), Some(t) if t.text == "⺆")
+            {
+                return Err(usage());
+            }
+            let handler = self.take_block().ok_or_else(usage)?;
+            return self.try_catch(body, handler);
+        }
+        if name == "catch" {
+            return Err(WofError::Runtime("catch without a preceding try ⺆ ... ⺘".into()));
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // TEST CASE: test_case "name" ⺆ ... ⺘
+        // ═══════════════════════════════════════════════════════════════
+        if name == "test_case" {
+            let usage = || WofError::Runtime("test_case requires: test_case \"name\" ⺆ body ⺘".into());
+            let case = match self.token_buffer.pop_front() {
+                Some(t) if t.kind == TokenKind::String => crate::tokenizer::parse_string_literal(&t.text),
+                _ => return Err(usage()),
+            };
+            if !matches!(self.token_buffer.pop_front(), Some(t) if t.text == "⺆") {
+                return Err(usage());
+            }
+            let body = self.take_block().ok_or_else(usage)?;
+            return self.test_case(case, body);
+        }
+
         // ═══════════════════════════════════════════════════════════════
         // BREAK: 🛑 (exit innermost loop)
         // ═══════════════════════════════════════════════════════════════
@@ -1105,20 +2735,7 @@ impl Interpreter {
         // ═══════════════════════════════════════════════════════════════
         if name == "若" || name == "if" {
             let condition = self.stack.pop()?;
-            let is_true = condition.is_truthy();
-            
-            if is_true {
-                self.push_scope(BlockType::If);
-            } else {
-                self.skip_depth = 1;
-            }
-            return Ok(());
-        }
-
-        // Check for else: 或
-        if name == "或" || name == "else" {
-            // If we're here, we executed the then branch - skip the else
-            self.skip_depth = 1;
+            self.pending_branch = Some((BlockType::Then, condition.is_truthy()));
             return Ok(());
         }
 
@@ -1131,7 +2748,9 @@ impl Interpreter {
         }
 
         if name == "⺘" {
-            self.pop_scope();
+            if self.close_block() == Some(BlockType::Then) {
+                self.then_ran = Some(true);
+            }
             return Ok(());
         }
 
@@ -1159,9 +2778,10 @@ impl Interpreter {
         // ═══════════════════════════════════════════════════════════════
         if name == ":labels" {
             if self.labels.is_empty() {
-                println!("No labels defined");
+                self.out.println("No labels defined");
             } else {
-                println!("Labels: {}", self.labels.keys().cloned().collect::<Vec<_>>().join(", "));
+                let names = self.labels.keys().cloned().collect::<Vec<_>>().join(", ");
+                writeln!(self.out, "Labels: {names}");
             }
             return Ok(());
         }
@@ -1170,13 +2790,7 @@ impl Interpreter {
         // REGISTERED OPERATIONS
         // ═══════════════════════════════════════════════════════════════
         if let Some(op) = self.registry.get_cloned(name) {
-            return op(self).map_err(|e| {
-                if e.span().is_none() {
-                    WofError::runtime_at(e.to_string(), span)
-                } else {
-                    e
-                }
-            });
+            return self.call_op(name, &op, span, lookup);
         }
 
         // ═══════════════════════════════════════════════════════════════
@@ -1201,6 +2815,33 @@ impl Interpreter {
     }
 }
 
+/// Split the body of a `match_tag` block into `(pattern, arm)` pairs.
+///
+/// Returns `None` unless every pattern is followed by a closed `⺆ ... ⺘`.
+fn split_arms(tokens: Vec<OwnedToken>) -> Option<Vec<(String, Vec<OwnedToken>)>> {
+    let mut arms = Vec::new();
+    let mut tokens = tokens.into_iter();
+    while let Some(pattern) = tokens.next() {
+        if tokens.next()?.text != "⺆" {
+            return None;
+        }
+        let mut body = Vec::new();
+        let mut depth = 0usize;
+        loop {
+            let token = tokens.next()?;
+            match token.text.as_str() {
+                "⺆" => depth += 1,
+                "⺘" if depth == 0 => break,
+                "⺘" => depth -= 1,
+                _ => {}
+            }
+            body.push(token);
+        }
+        arms.push((pattern.text, body));
+    }
+    Some(arms)
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // InterpreterContext IMPLEMENTATION
 // ═══════════════════════════════════════════════════════════════════════
@@ -1240,6 +2881,22 @@ impl InterpreterContext for Interpreter {
     fn clear(&mut self) {
         self.stack.clear();
     }
+
+    fn out(&self) -> Out {
+        self.out.clone()
+    }
+}
+
+/// Attach `span` to an error that has no location of its own, keeping
+/// the error itself so its [kind](WofError::kind) survives.
+///
+/// Interruptions are left alone so callers can still tell them apart.
+pub(crate) fn located(e: WofError, span: Span) -> WofError {
+    if e.span().is_some() || matches!(e, WofError::Interrupted(_)) {
+        e
+    } else {
+        WofError::Located { error: Box::new(e), span }
+    }
 }
 
 #[cfg(test)]
@@ -1299,6 +2956,17 @@ mod tests {
         assert_eq!(interp.stack.pop_integer().unwrap(), 2);
     }
 
+    #[test]
+    fn big_literals_become_bigints() {
+        let mut interp = make_interp();
+        interp.exec_line("9223372036854775807 -9223372036854775809").unwrap();
+
+        let big = interp.stack.pop().unwrap();
+        assert_eq!(big.value_type(), woflang_core::WofType::BigInt);
+        assert_eq!(big.to_string(), "-9223372036854775809");
+        assert_eq!(interp.stack.pop_integer().unwrap(), i64::MAX);
+    }
+
     #[test]
     fn unknown_symbol_pushed() {
         let mut interp = make_interp();
@@ -1317,6 +2985,128 @@ mod tests {
         assert_eq!(val.as_str().unwrap(), "hello world");
     }
 
+    #[test]
+    fn interpolated_strings_format_segments_without_consuming() {
+        let mut interp = make_interp();
+        interp.define_var("x", WofValue::integer(7));
+        interp.exec_line(r#"3 4 $"x={x}, got {+} {{ok}} \"{ \"q\" }\"" "#).unwrap();
+        assert_eq!(interp.stack.pop().unwrap().as_str().unwrap(), r#"x=7, got 7.0 {ok} "q""#);
+        assert_eq!(interp.stack.len(), 2);
+
+        assert!(interp.exec_line(r#"$"{drop drop drop}""#).is_err());
+        assert_eq!(interp.stack.len(), 2);
+        assert!(interp.exec_line(r#"$"open {x""#).is_err());
+        interp.stack.clear();
+        assert!(interp.exec_line(r#"$"{}""#).is_err());
+    }
+
+    #[test]
+    fn raw_strings_span_lines_in_scripts() {
+        let mut interp = make_interp();
+        let script = "1\n\"\"\"Room:\n  exits and \"doors\"\n\"\"\" 2\n:x 3\nbad_line =(";
+        let err = interp.exec_source(script).unwrap_err();
+        assert!(err.to_string().contains("6:"), "{err}");
+        let values: Vec<String> = interp.stack.iter().map(ToString::to_string).collect();
+        assert_eq!(values, ["1", "Room:\n  exits and \"doors\"\n", "2", "3", "bad_line"]);
+
+        assert_eq!(statements("a\n\"\"\"b\nc\"\"\"\r\nd"), [(1, "a"), (2, "\"\"\"b\nc\"\"\""), (4, "d")]);
+    }
+
+    #[test]
+    fn nested_if_else_takes_one_branch_per_level() {
+        let mut interp = make_interp();
+        let depth = interp.block_depth();
+        for (outer, inner, expected) in [(0, 1, "c"), (1, 0, "b"), (1, 1, "a")] {
+            let line = format!("{outer} 若 ⺆ {inner} 若 ⺆ \"a\" ⺘ 或 ⺆ \"b\" ⺘ ⺘ 或 ⺆ \"c\" ⺘ \"end\"");
+            interp.exec_line(&line).unwrap();
+            let values: Vec<String> = interp.stack.iter().map(ToString::to_string).collect();
+            assert_eq!(values, [expected, "end"], "{line}");
+            interp.stack.clear();
+        }
+        assert_eq!(interp.block_depth(), depth);
+
+        interp.exec_source("0 若 ⺆\n  1 若 ⺆ 2 ⺘ 或 ⺆ 3 ⺘\n⺘\n或 ⺆\n  4\n⺘\n5 若 ⺆ 6 ⺘").unwrap();
+        let values: Vec<String> = interp.stack.iter().map(ToString::to_string).collect();
+        assert_eq!(values, ["4", "6"]);
+
+        interp.exec_line("⊕ pick ⺆ 若 ⺆ \"early\" 至 ⺘ \"late\" ⺘").unwrap();
+        interp.exec_line("1 巡 pick 0 巡 pick").unwrap();
+        assert_eq!(interp.stack.pop().unwrap().to_string(), "late");
+        assert_eq!(interp.stack.pop().unwrap().to_string(), "early");
+        assert_eq!(interp.block_depth(), depth);
+
+        assert!(interp.exec_line("1 若 2").is_err());
+        assert!(interp.exec_line("或 ⺆ 1 ⺘").is_err());
+    }
+
+    #[test]
+    fn branches_leave_no_blocks_behind() {
+        let mut interp = make_interp();
+        let blocks = interp.blocks.len();
+        interp.exec_line("0 1000 ⨯ ⺆ 1 若 ⺆ 0 若 ⺆ 1 ⺘ 或 ⺆ 1 + ⺘ ⺘ 或 ⺆ ⺘ ⺘").unwrap();
+        assert_eq!(interp.stack.pop().unwrap().to_string(), "1000.0");
+        interp.exec_line("⊕ odd 1 ⺆ dup 若 ⺆ 1 - 若 ⺆ ⺘ 或 ⺆ ⺘ ⺘ 或 ⺆ ⺘ ⺘").unwrap();
+        interp.exec_line("1 巡 odd 1 巡 odd 0 巡 odd").unwrap();
+        assert_eq!(interp.blocks.len(), blocks);
+        assert!(interp.exec_line("1 若 ⺆ 2 若 ⺘").is_err());
+        assert_eq!(interp.blocks.len(), blocks);
+    }
+
+    #[test]
+    fn recursion_checks_arity_depth_and_tail_calls() {
+        let mut interp = make_interp();
+        interp.max_call_depth = 50;
+        let depth = interp.block_depth();
+
+        // Tail calls reuse their frame, so this runs far past the limit.
+        interp.exec_line("⊕ down 1 ⺆ dup 若 ⺆ 1 - down 至 ⺘ ⺘").unwrap();
+        interp.exec_line("200 down").unwrap();
+        assert_eq!(interp.stack.pop().unwrap().to_string(), "0.0");
+        assert!(!interp.in_function_call());
+
+        // The same recursion without the return stacks a frame per call.
+        interp.exec_line("⊕ deep 1 ⺆ dup 若 ⺆ 1 - deep ⺘ ⺘").unwrap();
+        interp.exec_line("40 deep").unwrap();
+        interp.stack.clear();
+        let err = interp.exec_line("200 deep").unwrap_err();
+        assert!(err.to_string().contains("maximum call depth (50)"), "{err}");
+        assert!(!interp.in_function_call());
+        assert_eq!(interp.block_depth(), depth);
+
+        interp.stack.clear();
+        let err = interp.exec_line("down").unwrap_err();
+        assert!(err.to_string().contains("'down' takes 1 argument(s)"), "{err}");
+        assert_eq!(interp.get_function("deep").map(|f| f.arity), Some(1));
+        assert!(interp.exec_line("⊕ bad -1 ⺆ ⺘").is_err());
+    }
+
+    #[test]
+    fn tail_recursion_keeps_no_closed_blocks() {
+        let mut interp = make_interp();
+        let blocks = interp.blocks.len();
+        interp.exec_line("⊕ down 1 ⺆ dup 若 ⺆ 1 - down 至 ⺘ ⺘").unwrap();
+        let started = Instant::now();
+        interp.exec_line("100000 down").unwrap();
+        assert!(started.elapsed() < Duration::from_secs(30), "took {:?}", started.elapsed());
+        assert_eq!(interp.stack.pop().unwrap().to_string(), "0.0");
+        assert_eq!(interp.blocks.len(), blocks);
+    }
+
+    #[test]
+    fn while_loop_rechecks_its_condition() {
+        let mut interp = make_interp();
+        let depth = interp.block_depth();
+        interp.exec_line("3 当 ⺆ dup ⺘ ⺆ dup 1 - ⺘ \"done\"").unwrap();
+        let values: Vec<String> = interp.stack.iter().map(ToString::to_string).collect();
+        assert_eq!(values, ["3", "2.0", "1.0", "0.0", "done"]);
+
+        interp.stack.clear();
+        interp.exec_source("2 while ⺆ dup ⺘ ⺆\n  2 ⨯ ⺆ 7 ⺘ drop drop\n  1 -\n⺘").unwrap();
+        assert_eq!(interp.stack.len(), 1);
+        assert_eq!((interp.block_depth(), interp.loop_depth()), (depth, 0));
+        assert!(interp.exec_line("当 ⺆ 1 ⺘ 2").is_err());
+    }
+
     #[test]
     fn empty_line_noop() {
         let mut interp = make_interp();
@@ -1325,4 +3115,183 @@ mod tests {
 
         assert!(interp.stack.is_empty());
     }
+
+    #[test]
+    fn bench_restores_stack_and_pushes_mean() {
+        let mut interp = make_interp();
+        interp.exec_line("7 bench ⺆ 1 + ⺆ dup drop ⺘ ⺘ 50").unwrap();
+
+        assert_eq!(interp.stack.len(), 2);
+        assert!(interp.stack.pop_numeric().unwrap() >= 0.0);
+        assert!((interp.stack.pop_numeric().unwrap() - 7.0).abs() < f64::EPSILON);
+
+        assert!(interp.exec_line("bench ⺆ 1 ⺘").is_err());
+        assert!(interp.exec_line("bench ⺆ 1 ⺘ 0").is_err());
+    }
+
+    #[test]
+    fn bench_times_user_functions() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ big ⺆ 1000 + ⺘ 7 bench ⺆ big ⺘ 3").unwrap();
+        assert_eq!(interp.stack.len(), 2);
+        assert!(interp.stack.pop_numeric().unwrap() < 1000.0);
+        assert!((interp.stack.pop_numeric().unwrap() - 7.0).abs() < f64::EPSILON);
+
+        // A failing call is abandoned along with the run.
+        interp.exec_line("⊕ bad ⺆ + ⺘").unwrap();
+        assert!(interp.exec_line("bench ⺆ bad ⺘ 2").is_err());
+        assert!(!interp.in_function_call());
+        interp.exec_line("1 big").unwrap();
+        assert!((interp.stack.pop_numeric().unwrap() - 1001.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn infix_calls_user_functions_in_place() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ dbl ⺆ dup + ⺘ =(dbl(5) + 100) =(dbl(dbl(5)) - 1) 7").unwrap();
+        let values: Vec<i64> = interp.stack.as_slice().iter().map(|v| v.as_integer().unwrap()).collect();
+        assert_eq!(values, [110, 19, 7]);
+    }
+
+    #[test]
+    fn jit_block_runs_with_or_without_the_feature() {
+        let mut interp = make_interp();
+        interp.exec_line("3 jit ⺆ dup ⺘ 1").unwrap();
+        assert_eq!(interp.stack().len(), 3);
+        assert!(interp.exec_line("jit 1").is_err());
+        assert!(interp.exec_line("jit ⺆ 1").is_err());
+    }
+
+    #[test]
+    fn bound_symbols_follow_reregistration_and_removal() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ twice ⺆ dup + ⺘").unwrap();
+        assert!(interp.get_function("twice").unwrap().body.iter().all(|t| t.op.is_some()));
+
+        interp.register("+", |ctx| {
+            ctx.stack_mut().drop()?;
+            Ok(())
+        });
+        interp.exec_line("5 twice").unwrap();
+        assert_eq!(interp.stack.pop_numeric().unwrap(), 5.0);
+
+        interp.registry_mut().remove("+");
+        interp.exec_line("5 twice").unwrap();
+        assert_eq!(interp.stack.pop().unwrap().to_string(), "+");
+    }
+
+    #[test]
+    fn strict_mode_rejects_unknown_symbols_before_running() {
+        let mut interp = make_interp();
+        interp.strict = true;
+        let err = interp.exec_line("1 2 frobnicate +").unwrap_err();
+        assert!(err.to_string().contains("unknown symbol 'frobnicate'"), "{err}");
+        assert!(interp.stack.is_empty());
+
+        interp.exec_line("⊕ sq ⺆ dup + ⺘ 3 sq 字 x x drop").unwrap();
+        interp.exec_line("⊕ f ⺆ 字 n n n + ⺘ x f").unwrap();
+        assert_eq!(interp.stack.pop_numeric().unwrap(), 12.0);
+        interp.exec_source("⊕ a ⺆ b 1 + ⺘\n⊕ b ⺆ 1 ⺘\na").unwrap();
+        assert_eq!(interp.stack.pop_numeric().unwrap(), 2.0);
+
+        interp.strict = false;
+        interp.exec_line("frobnicate").unwrap();
+        assert_eq!(interp.stack.pop().unwrap().to_string(), "frobnicate");
+    }
+
+    #[test]
+    fn wof_version_renames_old_ops_and_refuses_newer_scripts() {
+        let mut interp = make_interp();
+        interp.strict = true;
+        interp.exec_source("@wof-version 0.0.3\n3 stack_dup +\n⊕ f ⺆ stack_swap - ⺘\n1 f").unwrap();
+        assert_eq!(interp.stack.pop_numeric().unwrap(), -5.0);
+        // The renames last only as long as the script
+        assert!(interp.exec_line("stack_dup").is_err());
+        assert!(interp.exec_source("@wof-version 0.1\n1 stack_dup").is_err());
+
+        let err = interp.exec_source("1 2 +\n@wof-version 9.0.0\n4").unwrap_err();
+        assert!(err.to_string().contains("requires woflang 9.0.0"), "{err}");
+        assert!(interp.stack.is_empty());
+    }
+
+    #[test]
+    fn exec_line_in_reuses_pooled_buffers() {
+        let pool = BufferPool::new();
+        let mut interp = make_interp();
+        interp.exec_line_in(&pool, "1 2 +").unwrap();
+        let warm = pool.stats().allocated;
+        interp.exec_line_in(&pool, "3 +").unwrap();
+
+        assert_eq!(pool.stats().allocated, warm);
+        assert!((interp.stack.pop_numeric().unwrap() - 6.0).abs() < f64::EPSILON);
+
+        assert!(interp.exec_line_in(&pool, "5 + 9 9").is_err());
+        assert!(pool.pooled() >= 4);
+
+        // Expanded lines come from the pool too, so it stops growing.
+        assert!(interp.expand_bindings);
+        interp.exec_line_in(&pool, "1 2 + drop").unwrap();
+        let (warm, pooled) = (pool.stats().allocated, pool.pooled());
+        for _ in 0..100 {
+            interp.exec_line_in(&pool, "1 2 + drop").unwrap();
+        }
+        assert_eq!((pool.stats().allocated, pool.pooled()), (warm, pooled));
+        pool.reset();
+        assert_eq!(pool.pooled(), 0);
+    }
+
+    #[test]
+    fn fork_copies_program_state_but_not_changes() {
+        let mut interp = make_interp();
+        interp.exec_line("5 字 x ⊕ inc ⺆ 1 + ⺘ 2").unwrap();
+        let mut fork = interp.fork();
+        fork.exec_line("x inc 字 x 9").unwrap();
+        assert_eq!(fork.stack.len(), 2);
+        assert_eq!(fork.get_var("x").unwrap().as_integer().unwrap(), 6);
+
+        assert_eq!(interp.stack.len(), 1);
+        assert_eq!(interp.get_var("x").unwrap().as_integer().unwrap(), 5);
+    }
+
+    #[test]
+    fn function_listing_is_sorted() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ tri ⺆ 3 ⺘ ⊕ bi ⺆ 2 ⺘ ⊕ uni ⺆ 1 ⺘ ⊕ all ⺆ 0 ⺘").unwrap();
+        assert_eq!(interp.function_names(), ["all", "bi", "tri", "uni"]);
+    }
+
+    #[test]
+    fn cancellation_stops_the_line_and_keeps_the_stack() {
+        let mut interp = make_interp();
+        interp.register("trip", |ctx| {
+            ctx.cancel_token().cancel();
+            Ok(())
+        });
+
+        let err = interp.exec_line("1 trip 2 3").unwrap_err();
+        assert!(matches!(err, WofError::Interrupted(_)), "{err}");
+        assert_eq!(err.to_string(), "interrupted before '2' at 1:8");
+        assert_eq!(interp.stack.len(), 1);
+        assert!(!interp.is_cancelled());
+
+        interp.exec_line("4").unwrap();
+        assert_eq!(interp.stack.len(), 2);
+
+        let depth = interp.block_depth();
+        assert!(interp.exec_line("3 ⨯ ⺆ trip 5 ⺘").is_err());
+        assert_eq!((interp.block_depth(), interp.loop_depth()), (depth, 0));
+    }
+
+    #[test]
+    fn state_is_per_type_and_per_interpreter() {
+        #[derive(Default)]
+        struct Counter(u32);
+
+        let mut interp = make_interp();
+        assert!(interp.state::<Counter>().is_none());
+        interp.state_mut::<Counter>().0 += 2;
+        interp.state_mut::<Counter>().0 += 1;
+        assert_eq!(interp.state::<Counter>().unwrap().0, 3);
+        assert!(make_interp().state::<Counter>().is_none());
+    }
 }
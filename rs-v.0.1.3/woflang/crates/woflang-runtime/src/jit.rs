@@ -0,0 +1,787 @@
+//! Native compilation of numeric kernels (feature `jit`).
+//!
+//! `jit ⺆ body ⺘` runs its block as machine code when the block is a
+//! numeric kernel: integer and float literals, `+ - * /` (also `×` and
+//! `÷`), `dup drop swap over rot`, and `N ⨯ ⺆ ... ⺘` loops whose body
+//! leaves the stack shape unchanged. The block is compiled with Cranelift
+//! the first time it runs against a given stack shape, and the code is
+//! cached on the interpreter:
+//!
+//! ```text
+//! wof> 0 jit ⺆ 1000000 ⨯ ⺆ 3 + 2 * ⺘ ⺘
+//! ```
+//!
+//! Anything else (other ops, functions, variables, strings, values with
+//! units) has to call into Rust handlers, so such a block runs on the
+//! interpreter instead, with the same result. Kernels follow the
+//! arithmetic ops exactly: integers wrap, mixing in a float promotes to
+//! float and `/` always yields a float. A kernel never stops part way: on
+//! division by zero it gives up and the interpreter reruns the block to
+//! report the error with its usual stack effect. Loops poll the cancel
+//! token every iteration; an interrupted kernel leaves the stack as it
+//! was before the block.
+
+// Calling generated machine code requires unsafe blocks.
+#![allow(unsafe_code)]
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use cranelift_codegen::ir::condcodes::{FloatCC, IntCC};
+use cranelift_codegen::ir::{types, AbiParam, Block, InstBuilder, MemFlags, Type, Value};
+use cranelift_codegen::settings::{self, Configurable};
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext, Variable};
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::{Linkage, Module};
+use woflang_core::{InterpreterContext, WofStack, WofValue};
+
+use crate::{Interpreter, OwnedToken, TokenKind};
+
+/// Kernel exit status: finished normally.
+const OK: i32 = 0;
+/// Kernel exit status: a division by zero, left to the interpreter.
+const DIV_BY_ZERO: i32 = 1;
+/// Kernel exit status: the cancel token was set.
+const CANCELLED: i32 = 2;
+
+/// What happened to a block offered to the JIT.
+pub enum Outcome {
+    /// The kernel ran and the stack holds its results.
+    Done,
+    /// The kernel saw the cancel token; the stack is unchanged.
+    Interrupted,
+    /// The block is not a kernel (or bailed out); interpret it.
+    Fallback,
+}
+
+/// Run `body` natively if it is a numeric kernel.
+pub fn run(interp: &mut Interpreter, body: &[OwnedToken]) -> Outcome {
+    let key: Vec<&str> = body.iter().map(|t| t.text.as_str()).collect();
+    let key = key.join(" ");
+    let below: Vec<Option<Ty>> = interp.stack().as_slice().iter().map(Ty::of).collect();
+
+    let cached = interp.state::<JitCache>().and_then(|cache| cache.find(&key, &below));
+    let kernel = if let Some(kernel) = cached {
+        kernel
+    } else {
+        let registry = interp.registry();
+        let Some(ops) = parse(body, &|name| registry.contains(name)) else {
+            return Outcome::Fallback;
+        };
+        let Some(shape) = Shape::of(&ops, &below) else {
+            return Outcome::Fallback;
+        };
+        let Ok(kernel) = compile(&ops, shape) else {
+            return Outcome::Fallback;
+        };
+        let kernel = Arc::new(kernel);
+        interp.state_mut::<JitCache>().kernels.entry(key).or_default().push(Arc::clone(&kernel));
+        kernel
+    };
+
+    let cancel = interp.cancel_token();
+    match kernel.call(interp.stack_mut(), cancel.flag()) {
+        OK => Outcome::Done,
+        CANCELLED => Outcome::Interrupted,
+        _ => Outcome::Fallback,
+    }
+}
+
+/// Compiled kernels of one interpreter, by block text.
+#[derive(Default)]
+pub struct JitCache {
+    /// Each block has one kernel per stack shape it was run against.
+    kernels: HashMap<String, Vec<Arc<Kernel>>>,
+}
+
+impl JitCache {
+    fn find(&self, key: &str, below: &[Option<Ty>]) -> Option<Arc<Kernel>> {
+        self.kernels.get(key)?.iter().find(|k| k.accepts(below)).cloned()
+    }
+}
+
+/// The static type of a kernel stack slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Ty {
+    Int,
+    Float,
+}
+
+impl Ty {
+    /// The slot type for `value`, if a kernel can hold it.
+    fn of(value: &WofValue) -> Option<Self> {
+        if value.unit().is_some() {
+            None
+        } else if value.is_integer() {
+            Some(Self::Int)
+        } else if value.is_double() {
+            Some(Self::Float)
+        } else {
+            None
+        }
+    }
+
+    const fn clif(self) -> Type {
+        match self {
+            Self::Int => types::I64,
+            Self::Float => types::F64,
+        }
+    }
+
+    fn load(self, value: &WofValue) -> u64 {
+        match self {
+            Self::Int => value.try_integer().unwrap_or_default() as u64,
+            Self::Float => value.try_double().unwrap_or_default().to_bits(),
+        }
+    }
+
+    fn store(self, bits: u64) -> WofValue {
+        match self {
+            Self::Int => WofValue::integer(bits as i64),
+            Self::Float => WofValue::double(f64::from_bits(bits)),
+        }
+    }
+
+    /// Result type of `+ - *` on `a` and `b`.
+    fn join(a: Self, b: Self) -> Self {
+        if a == Self::Int && b == Self::Int {
+            Self::Int
+        } else {
+            Self::Float
+        }
+    }
+}
+
+/// One kernel instruction.
+#[derive(Debug, Clone, PartialEq)]
+enum Op {
+    Int(i64),
+    Float(f64),
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Dup,
+    Drop,
+    Swap,
+    Over,
+    Rot,
+    /// Pop a count and run the body that many times.
+    Repeat(Vec<Self>),
+}
+
+impl Op {
+    /// Values popped and pushed, not counting a `Repeat` body.
+    const fn effect(&self) -> (usize, usize) {
+        match self {
+            Self::Int(_) | Self::Float(_) => (0, 1),
+            Self::Add | Self::Sub | Self::Mul | Self::Div => (2, 1),
+            Self::Dup => (1, 2),
+            Self::Drop | Self::Repeat(_) => (1, 0),
+            Self::Swap => (2, 2),
+            Self::Over => (2, 3),
+            Self::Rot => (3, 3),
+        }
+    }
+}
+
+/// Translate a block into kernel ops, or `None` if any token needs the
+/// interpreter. `is_op` says whether a name is a registered op, so that a
+/// session without the arithmetic ops keeps its symbol-pushing behaviour.
+fn parse(tokens: &[OwnedToken], is_op: &impl Fn(&str) -> bool) -> Option<Vec<Op>> {
+    let mut ops = Vec::new();
+    let mut rest = tokens;
+    while let Some((token, tail)) = rest.split_first() {
+        rest = tail;
+        let op = match token.kind {
+            TokenKind::Integer => Op::Int(token.text.parse().ok()?),
+            TokenKind::Float => Op::Float(token.text.parse().ok()?),
+            TokenKind::Symbol => match token.text.as_str() {
+                "⨯" | "times" | "repeat" => {
+                    let (open, tail) = rest.split_first()?;
+                    if open.text != "⺆" {
+                        return None;
+                    }
+                    let len = block_len(tail)?;
+                    let body = parse(tail.get(..len)?, is_op)?;
+                    rest = tail.get(len + 1..)?;
+                    Op::Repeat(body)
+                }
+                name if !is_op(name) => return None,
+                "+" => Op::Add,
+                "-" => Op::Sub,
+                "*" | "×" => Op::Mul,
+                "/" | "÷" => Op::Div,
+                "dup" => Op::Dup,
+                "drop" => Op::Drop,
+                "swap" => Op::Swap,
+                "over" => Op::Over,
+                "rot" => Op::Rot,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        ops.push(op);
+    }
+    Some(ops)
+}
+
+/// Number of tokens before the `⺘` closing an already opened block.
+fn block_len(tokens: &[OwnedToken]) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.text.as_str() {
+            "⺆" => depth += 1,
+            "⺘" if depth == 0 => return Some(i),
+            "⺘" => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// How many values below its own pushes `ops` reads.
+fn reach(ops: &[Op]) -> usize {
+    let mut height = 0isize;
+    let mut lowest = 0isize;
+    for op in ops {
+        let (pops, pushes) = op.effect();
+        height -= pops as isize;
+        lowest = lowest.min(height);
+        if let Op::Repeat(body) = op {
+            lowest = lowest.min(height - reach(body) as isize);
+        }
+        height += pushes as isize;
+    }
+    lowest.unsigned_abs()
+}
+
+/// The stack types a kernel takes and leaves, bottom first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Shape {
+    inputs: Vec<Ty>,
+    outputs: Vec<Ty>,
+}
+
+impl Shape {
+    /// Type-check `ops` against the types of the stack below them, or
+    /// `None` if they read a value a kernel cannot hold or run a loop
+    /// whose body changes the stack shape.
+    fn of(ops: &[Op], below: &[Option<Ty>]) -> Option<Self> {
+        let mut checker = Checker { stack: Vec::new(), inputs: Vec::new(), below };
+        checker.run(ops)?;
+        Some(Self { inputs: checker.inputs, outputs: checker.stack })
+    }
+}
+
+struct Checker<'a> {
+    stack: Vec<Ty>,
+    inputs: Vec<Ty>,
+    below: &'a [Option<Ty>],
+}
+
+impl Checker<'_> {
+    /// Take inputs from the real stack until `n` values are available.
+    fn need(&mut self, n: usize) -> Option<()> {
+        while self.stack.len() < n {
+            let index = self.below.len().checked_sub(self.inputs.len() + 1)?;
+            let ty = (*self.below.get(index)?)?;
+            self.inputs.insert(0, ty);
+            self.stack.insert(0, ty);
+        }
+        Some(())
+    }
+
+    fn pop(&mut self) -> Option<Ty> {
+        self.stack.pop()
+    }
+
+    fn run(&mut self, ops: &[Op]) -> Option<()> {
+        for op in ops {
+            self.need(op.effect().0)?;
+            match op {
+                Op::Int(_) => self.stack.push(Ty::Int),
+                Op::Float(_) => self.stack.push(Ty::Float),
+                Op::Add | Op::Sub | Op::Mul => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    self.stack.push(Ty::join(a, b));
+                }
+                Op::Div => {
+                    self.pop()?;
+                    self.pop()?;
+                    self.stack.push(Ty::Float);
+                }
+                Op::Dup => self.stack.push(*self.stack.last()?),
+                Op::Drop => {
+                    self.pop()?;
+                }
+                Op::Swap => {
+                    let len = self.stack.len();
+                    self.stack.swap(len - 2, len - 1);
+                }
+                Op::Over => self.stack.push(*self.stack.get(self.stack.len() - 2)?),
+                Op::Rot => {
+                    let a = self.stack.remove(self.stack.len() - 3);
+                    self.stack.push(a);
+                }
+                Op::Repeat(body) => {
+                    if self.pop()? != Ty::Int {
+                        return None;
+                    }
+                    self.need(reach(body))?;
+                    let (entry, taken) = (self.stack.clone(), self.inputs.len());
+                    self.run(body)?;
+                    // Every iteration must see the same shape and read no
+                    // inputs beyond those taken before the loop.
+                    if self.stack != entry || self.inputs.len() != taken {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(())
+    }
+}
+
+type KernelFn = unsafe extern "C" fn(*mut u64, *const AtomicBool) -> i32;
+
+/// A compiled block for one input shape.
+struct Kernel {
+    /// Owns the code; only kept to free it on drop.
+    module: Option<JITModule>,
+    entry: KernelFn,
+    shape: Shape,
+}
+
+// SAFETY: the module is never used after compilation except to free its
+// memory on drop, and the entry point is immutable code with no state of
+// its own, so a kernel can move to and be shared with any thread.
+unsafe impl Send for Kernel {}
+unsafe impl Sync for Kernel {}
+
+impl Drop for Kernel {
+    fn drop(&mut self) {
+        if let Some(module) = self.module.take() {
+            // SAFETY: the entry point is private to this kernel, which is
+            // being dropped, so no code from the module can run again.
+            unsafe { module.free_memory() };
+        }
+    }
+}
+
+impl Kernel {
+    /// Whether this kernel was compiled for the top of `below`.
+    fn accepts(&self, below: &[Option<Ty>]) -> bool {
+        let inputs = &self.shape.inputs;
+        below.len() >= inputs.len()
+            && below.get(below.len() - inputs.len()..).is_some_and(|top| {
+                top.iter().zip(inputs).all(|(have, want)| *have == Some(*want))
+            })
+    }
+
+    /// Run on `stack`, replacing the inputs with the outputs on success.
+    fn call(&self, stack: &mut WofStack, cancel: &AtomicBool) -> i32 {
+        let Shape { inputs, outputs } = &self.shape;
+        let base = stack.len() - inputs.len();
+        let mut slots = vec![0u64; inputs.len().max(outputs.len())];
+        for ((slot, ty), value) in slots.iter_mut().zip(inputs).zip(stack.as_slice().iter().skip(base)) {
+            *slot = ty.load(value);
+        }
+        // SAFETY: the kernel reads its inputs from and writes its outputs
+        // to `slots`, which is large enough for both, and only reads the
+        // cancel flag.
+        let status = unsafe { (self.entry)(slots.as_mut_ptr(), cancel) };
+        if status == OK {
+            for _ in 0..inputs.len() {
+                let _ = stack.pop();
+            }
+            stack.push_all(slots.iter().zip(outputs).map(|(&bits, ty)| ty.store(bits)));
+        }
+        status
+    }
+}
+
+/// Generate machine code for `ops`, which must type-check as `shape`.
+fn compile(ops: &[Op], shape: Shape) -> Result<Kernel, String> {
+    let mut flags = settings::builder();
+    flags.set("opt_level", "speed").map_err(|e| e.to_string())?;
+    let isa = cranelift_native::builder()
+        .map_err(str::to_string)?
+        .finish(settings::Flags::new(flags))
+        .map_err(|e| e.to_string())?;
+    let mut module = JITModule::new(JITBuilder::with_isa(isa, cranelift_module::default_libcall_names()));
+
+    let ptr = module.target_config().pointer_type();
+    let mut ctx = module.make_context();
+    ctx.func.signature.params.extend([AbiParam::new(ptr), AbiParam::new(ptr)]);
+    ctx.func.signature.returns.push(AbiParam::new(types::I32));
+
+    let mut builder_ctx = FunctionBuilderContext::new();
+    let mut builder = FunctionBuilder::new(&mut ctx.func, &mut builder_ctx);
+    let entry = builder.create_block();
+    builder.append_block_params_for_function_params(entry);
+    builder.switch_to_block(entry);
+    builder.seal_block(entry);
+    let &[slots, cancel] = builder.block_params(entry) else {
+        return Err("kernel signature mismatch".into());
+    };
+
+    let mut emitter = Emitter { builder, cancel, stack: Vec::new(), vars: HashMap::new(), next_var: 0 };
+    for (i, &ty) in shape.inputs.iter().enumerate() {
+        let value = emitter.builder.ins().load(ty.clif(), MemFlags::trusted(), slots, slot_offset(i));
+        emitter.push(ty, value);
+    }
+    emitter.emit(ops);
+    for i in 0..shape.outputs.len() {
+        let value = emitter.get(i);
+        emitter.builder.ins().store(MemFlags::trusted(), value, slots, slot_offset(i));
+    }
+    let ok = emitter.builder.ins().iconst(types::I32, i64::from(OK));
+    emitter.builder.ins().return_(&[ok]);
+    emitter.builder.finalize();
+
+    let id = module
+        .declare_function("kernel", Linkage::Local, &ctx.func.signature)
+        .map_err(|e| e.to_string())?;
+    module.define_function(id, &mut ctx).map_err(|e| e.to_string())?;
+    module.clear_context(&mut ctx);
+    module.finalize_definitions().map_err(|e| e.to_string())?;
+    let code = module.get_finalized_function(id);
+    // SAFETY: `code` was just compiled from a function with exactly the
+    // `KernelFn` signature, and lives as long as `module`.
+    let entry = unsafe { std::mem::transmute::<*const u8, KernelFn>(code) };
+    Ok(Kernel { module: Some(module), entry, shape })
+}
+
+const fn slot_offset(i: usize) -> i32 {
+    (i * 8) as i32
+}
+
+/// Emits Cranelift IR for kernel ops, keeping each stack slot in a
+/// variable per position and type.
+struct Emitter<'a> {
+    builder: FunctionBuilder<'a>,
+    cancel: Value,
+    stack: Vec<Ty>,
+    vars: HashMap<(usize, Ty), Variable>,
+    next_var: u32,
+}
+
+impl Emitter<'_> {
+    fn fresh_var(&mut self, ty: Type) -> Variable {
+        let var = Variable::from_u32(self.next_var);
+        self.next_var += 1;
+        self.builder.declare_var(var, ty);
+        var
+    }
+
+    fn var(&mut self, pos: usize, ty: Ty) -> Variable {
+        if let Some(&var) = self.vars.get(&(pos, ty)) {
+            return var;
+        }
+        let var = self.fresh_var(ty.clif());
+        self.vars.insert((pos, ty), var);
+        var
+    }
+
+    fn get(&mut self, pos: usize) -> Value {
+        let ty = self.stack[pos];
+        let var = self.var(pos, ty);
+        self.builder.use_var(var)
+    }
+
+    fn set(&mut self, pos: usize, ty: Ty, value: Value) {
+        self.stack[pos] = ty;
+        let var = self.var(pos, ty);
+        self.builder.def_var(var, value);
+    }
+
+    fn push(&mut self, ty: Ty, value: Value) {
+        self.stack.push(ty);
+        self.set(self.stack.len() - 1, ty, value);
+    }
+
+    fn pop(&mut self) -> (Ty, Value) {
+        let top = self.stack.len() - 1;
+        let value = self.get(top);
+        (self.stack.pop().unwrap_or(Ty::Int), value)
+    }
+
+    fn promote(&mut self, (ty, value): (Ty, Value)) -> Value {
+        match ty {
+            Ty::Int => self.builder.ins().fcvt_from_sint(types::F64, value),
+            Ty::Float => value,
+        }
+    }
+
+    /// Leave the kernel with `status` if `cond` is non-zero.
+    fn exit_if(&mut self, cond: Value, status: i32) {
+        let exit = self.builder.create_block();
+        let next = self.builder.create_block();
+        self.builder.ins().brif(cond, exit, &[], next, &[]);
+        self.builder.switch_to_block(exit);
+        self.builder.seal_block(exit);
+        let status = self.builder.ins().iconst(types::I32, i64::from(status));
+        self.builder.ins().return_(&[status]);
+        self.builder.switch_to_block(next);
+        self.builder.seal_block(next);
+    }
+
+    fn emit(&mut self, ops: &[Op]) {
+        for op in ops {
+            match op {
+                Op::Int(n) => {
+                    let value = self.builder.ins().iconst(types::I64, *n);
+                    self.push(Ty::Int, value);
+                }
+                Op::Float(x) => {
+                    let value = self.builder.ins().f64const(*x);
+                    self.push(Ty::Float, value);
+                }
+                Op::Add | Op::Sub | Op::Mul => {
+                    let b = self.pop();
+                    let a = self.pop();
+                    let ins = self.builder.ins();
+                    if Ty::join(a.0, b.0) == Ty::Int {
+                        let value = match op {
+                            Op::Add => ins.iadd(a.1, b.1),
+                            Op::Sub => ins.isub(a.1, b.1),
+                            _ => ins.imul(a.1, b.1),
+                        };
+                        self.push(Ty::Int, value);
+                    } else {
+                        let (a, b) = (self.promote(a), self.promote(b));
+                        let ins = self.builder.ins();
+                        let value = match op {
+                            Op::Add => ins.fadd(a, b),
+                            Op::Sub => ins.fsub(a, b),
+                            _ => ins.fmul(a, b),
+                        };
+                        self.push(Ty::Float, value);
+                    }
+                }
+                Op::Div => {
+                    let b = self.pop();
+                    let a = self.pop();
+                    let (a, b) = (self.promote(a), self.promote(b));
+                    let zero = self.builder.ins().f64const(0.0);
+                    let is_zero = self.builder.ins().fcmp(FloatCC::Equal, b, zero);
+                    self.exit_if(is_zero, DIV_BY_ZERO);
+                    let value = self.builder.ins().fdiv(a, b);
+                    self.push(Ty::Float, value);
+                }
+                Op::Dup => {
+                    let top = self.stack.len() - 1;
+                    let value = self.get(top);
+                    self.push(self.stack[top], value);
+                }
+                Op::Drop => {
+                    self.pop();
+                }
+                Op::Swap => {
+                    let (b, a) = (self.pop(), self.pop());
+                    self.push(b.0, b.1);
+                    self.push(a.0, a.1);
+                }
+                Op::Over => {
+                    let below = self.stack.len() - 2;
+                    let value = self.get(below);
+                    self.push(self.stack[below], value);
+                }
+                Op::Rot => {
+                    let (c, b, a) = (self.pop(), self.pop(), self.pop());
+                    self.push(b.0, b.1);
+                    self.push(c.0, c.1);
+                    self.push(a.0, a.1);
+                }
+                Op::Repeat(body) => {
+                    let (_, count) = self.pop();
+                    self.emit_repeat(count, body);
+                }
+            }
+        }
+    }
+
+    fn emit_repeat(&mut self, count: Value, body: &[Op]) {
+        let counter = self.fresh_var(types::I64);
+        self.builder.def_var(counter, count);
+        let header: Block = self.builder.create_block();
+        let looped = self.builder.create_block();
+        let exit = self.builder.create_block();
+        self.builder.ins().jump(header, &[]);
+
+        self.builder.switch_to_block(header);
+        let left = self.builder.use_var(counter);
+        let more = self.builder.ins().icmp_imm(IntCC::SignedGreaterThan, left, 0);
+        self.builder.ins().brif(more, looped, &[], exit, &[]);
+
+        self.builder.switch_to_block(looped);
+        self.builder.seal_block(looped);
+        let cancelled = self.builder.ins().atomic_load(types::I8, MemFlags::trusted(), self.cancel);
+        self.exit_if(cancelled, CANCELLED);
+        self.emit(body);
+        let left = self.builder.use_var(counter);
+        let left = self.builder.ins().iadd_imm(left, -1);
+        self.builder.def_var(counter, left);
+        self.builder.ins().jump(header, &[]);
+        self.builder.seal_block(header);
+
+        self.builder.switch_to_block(exit);
+        self.builder.seal_block(exit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::Tokenizer;
+    use woflang_core::{Result, WofError};
+
+    /// An interpreter with the stack ops and arithmetic semantics of
+    /// `woflang-ops`, which the runtime cannot depend on.
+    fn interp() -> Interpreter {
+        fn arith(interp: &mut Interpreter, int: fn(i64, i64) -> i64, float: fn(f64, f64) -> f64) -> Result<()> {
+            let b = interp.stack_mut().pop()?;
+            let a = interp.stack_mut().pop()?;
+            let result = match (a.try_integer(), b.try_integer()) {
+                (Some(a), Some(b)) => WofValue::integer(int(a, b)),
+                _ => WofValue::double(float(a.as_numeric()?, b.as_numeric()?)),
+            };
+            interp.push(result);
+            Ok(())
+        }
+        let mut interp = Interpreter::new();
+        interp.register("+", |i| arith(i, i64::wrapping_add, |a, b| a + b));
+        interp.register("-", |i| arith(i, i64::wrapping_sub, |a, b| a - b));
+        interp.register("*", |i| arith(i, i64::wrapping_mul, |a, b| a * b));
+        interp.register("/", |i| {
+            let b = i.stack_mut().pop_numeric()?;
+            let a = i.stack_mut().pop_numeric()?;
+            if b == 0.0 {
+                return Err(WofError::DivisionByZero);
+            }
+            i.push(WofValue::double(a / b));
+            Ok(())
+        });
+        interp.register("dup", |i| i.stack_mut().dup());
+        interp.register("drop", |i| i.stack_mut().drop());
+        interp.register("swap", |i| i.stack_mut().swap());
+        interp.register("over", |i| i.stack_mut().over());
+        interp.register("rot", |i| i.stack_mut().rot());
+        interp.register("halve", |i| {
+            let x = i.stack_mut().pop_numeric()?;
+            i.push(WofValue::double(x / 2.0));
+            Ok(())
+        });
+        interp
+    }
+
+    fn kernels(interp: &Interpreter) -> usize {
+        interp.state::<JitCache>().map_or(0, |c| c.kernels.values().map(Vec::len).sum())
+    }
+
+    /// Run `setup body` interpreted and `setup jit ⺆ body ⺘`, returning
+    /// both outcomes (errors without their location) as strings.
+    fn both(setup: &str, body: &str) -> (String, String, Interpreter) {
+        let run = |interp: &mut Interpreter, line: &str| match interp.exec_line(line) {
+            Ok(()) => format!("{:?}", interp.stack().as_slice()),
+            Err(e) => {
+                let e = e.to_string();
+                format!("{} {:?}", e.split_once(": ").map_or(e.as_str(), |(_, m)| m), interp.stack().as_slice())
+            }
+        };
+        let plain = run(&mut interp(), &format!("{setup} {body}"));
+        let mut jitted = interp();
+        let native = run(&mut jitted, &format!("{setup} jit ⺆ {body} ⺘"));
+        (plain, native, jitted)
+    }
+
+    #[test]
+    fn kernels_match_the_interpreter() {
+        for (setup, body) in [
+            ("0", "1000 ⨯ ⺆ 3 + 2 * ⺘"),
+            ("1.5 2.0", "10 ⨯ ⺆ over * swap 0.25 + swap ⺘ /"),
+            ("9223372036854775807", "1 + dup 2 *"),
+            ("1 2 3", "rot rot 4 ⨯ ⺆ rot ⺘ - -"),
+            ("5", "-3 ⨯ ⺆ 1 + ⺘ 4 ⨯ ⺆ dup * ⺘"),
+            ("7", "0 /"),
+        ] {
+            let (plain, native, jitted) = both(setup, body);
+            assert_eq!(plain, native, "{setup} {body}");
+            assert_eq!(kernels(&jitted), 1, "{setup} {body}");
+        }
+    }
+
+    #[test]
+    fn nested_loops_compile() {
+        let mut interp = interp();
+        interp.exec_line("5 jit ⺆ 2 ⨯ ⺆ 2 ⨯ ⺆ dup * ⺘ ⺘ ⺘").unwrap();
+        assert_eq!(interp.stack().as_slice(), [WofValue::integer(152_587_890_625)]);
+    }
+
+    #[test]
+    fn other_blocks_fall_back_to_the_interpreter() {
+        for (setup, body) in [("16", "halve 1 +"), ("\"x\"", "dup"), ("3", "⨯ ⺆ 1 ⺘"), ("", "1 +")] {
+            let (plain, native, jitted) = both(setup, body);
+            assert_eq!(plain, native, "{setup} {body}");
+            assert_eq!(kernels(&jitted), 0, "{setup} {body}");
+        }
+    }
+
+    #[test]
+    fn kernels_are_cached_per_stack_shape() {
+        let mut interp = interp();
+        interp.exec_line("1 jit ⺆ 2 * ⺘ jit ⺆ 2 * ⺘ 0.5 jit ⺆ 2 * ⺘").unwrap();
+        assert_eq!(kernels(&interp), 2);
+        assert_eq!(format!("{:?}", interp.stack().as_slice()), format!("{:?}", [WofValue::integer(4), WofValue::double(1.0)]));
+    }
+
+    #[test]
+    fn loops_stop_on_cancel_and_keep_the_stack() {
+        let mut interp = interp();
+        let token = interp.cancel_token();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            token.cancel();
+        });
+        let err = interp.exec_line("7 jit ⺆ 100000000000 ⨯ ⺆ 1 + ⺘ ⺘").unwrap_err();
+        canceller.join().unwrap();
+        assert!(matches!(err, WofError::Interrupted(_)), "{err}");
+        assert_eq!(interp.stack().as_slice(), [WofValue::integer(7)]);
+        interp.exec_line("1 +").unwrap();
+        assert_eq!(interp.stack().as_slice(), [WofValue::integer(8)]);
+    }
+
+    fn ops(src: &str) -> Option<Vec<Op>> {
+        let tokens: Vec<OwnedToken> = Tokenizer::new(src).tokenize_all().into_iter().map(OwnedToken::from).collect();
+        parse(&tokens, &|_| true)
+    }
+
+    #[test]
+    fn parses_kernels_and_rejects_the_rest() {
+        assert_eq!(
+            ops("2 ⨯ ⺆ 1.5 × ⺘ dup").unwrap(),
+            vec![Op::Int(2), Op::Repeat(vec![Op::Float(1.5), Op::Mul]), Op::Dup]
+        );
+        assert!(ops("1 sqrt").is_none());
+        assert!(ops("\"a\" dup").is_none());
+        assert!(ops("3 ⨯ ⺆ 1 +").is_none());
+    }
+
+    #[test]
+    fn shapes_track_inputs_and_loop_stability() {
+        let int = Some(Ty::Int);
+        let shape = Shape::of(&ops("10 ⨯ ⺆ 1 + ⺘ 2 /").unwrap(), &[int, int]).unwrap();
+        assert_eq!(shape, Shape { inputs: vec![Ty::Int], outputs: vec![Ty::Float] });
+        // The body turns an integer into a float, so the loop is unstable.
+        assert!(Shape::of(&ops("3 ⨯ ⺆ 0.5 + ⺘").unwrap(), &[int]).is_none());
+        // The body grows the stack.
+        assert!(Shape::of(&ops("3 ⨯ ⺆ 1 ⺘").unwrap(), &[]).is_none());
+        assert!(Shape::of(&ops("+").unwrap(), &[int]).is_none());
+        assert!(Shape::of(&ops("dup").unwrap(), &[None]).is_none());
+    }
+}
@@ -0,0 +1,122 @@
+//! Background jobs: `spawn ⺆ body ⺘`.
+//!
+//! A spawned block runs on its own thread in a [fork](Interpreter::fork)
+//! of the interpreter, starting from an empty stack. The spawning
+//! interpreter gets a job id back immediately and collects the worker's
+//! final stack later:
+//!
+//! ```text
+//! spawn ⺆ 1000000 ⨯ ⺆ 1 + ⺘ ⺘   # pushes job id 1
+//! jobs                          # lists running and finished jobs
+//! 1 join                        # pushes the worker's stack
+//! ```
+//!
+//! Jobs see the functions and variables defined before they were
+//! spawned, but nothing they define or change is visible to the parent.
+
+use std::collections::BTreeMap;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use woflang_core::{InterpreterContext, Result, WofError, WofValue};
+
+use crate::{Interpreter, OwnedToken};
+
+/// A job's status, as listed by `jobs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobStatus {
+    /// Id passed to `join`.
+    pub id: usize,
+    /// The spawned block, as source text.
+    pub source: String,
+    /// Time since the job was spawned.
+    pub elapsed: Duration,
+    /// Whether the worker has finished (successfully or not).
+    pub finished: bool,
+}
+
+struct Job {
+    source: String,
+    started: Instant,
+    handle: JoinHandle<Result<Vec<WofValue>>>,
+}
+
+/// Jobs not yet joined, keyed by id.
+#[derive(Default)]
+struct Jobs {
+    next_id: usize,
+    running: BTreeMap<usize, Job>,
+}
+
+impl Interpreter {
+    /// Run `body` on a worker thread in a fork of this interpreter with
+    /// an empty stack, returning the new job's id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no thread can be started, as on targets
+    /// without threads such as `wasm32-unknown-unknown`.
+    pub fn spawn_job(&mut self, body: Vec<OwnedToken>) -> Result<usize> {
+        let source = body.iter().map(|t| t.text.as_str()).collect::<Vec<_>>().join(" ");
+        let mut worker = self.fork();
+        worker.stack_mut().clear();
+        let handle = thread::Builder::new()
+            .spawn(move || {
+                worker.run_tokens(body)?;
+                Ok(worker.stack().as_slice().to_vec())
+            })
+            .map_err(|e| WofError::Runtime(format!("spawn: cannot start a thread: {e}")))?;
+
+        let jobs = self.state_mut::<Jobs>();
+        jobs.next_id += 1;
+        let id = jobs.next_id;
+        jobs.running.insert(id, Job { source, started: Instant::now(), handle });
+        Ok(id)
+    }
+
+    /// The jobs that have not been joined yet, oldest first.
+    #[must_use]
+    pub fn jobs(&self) -> Vec<JobStatus> {
+        let Some(jobs) = self.state::<Jobs>() else {
+            return Vec::new();
+        };
+        jobs.running
+            .iter()
+            .map(|(&id, job)| JobStatus {
+                id,
+                source: job.source.clone(),
+                elapsed: job.started.elapsed(),
+                finished: job.handle.is_finished(),
+            })
+            .collect()
+    }
+
+    /// Wait for job `id` to finish and return its final stack, bottom
+    /// first. A job can only be joined once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such job, or if the job failed or
+    /// panicked.
+    pub fn join_job(&mut self, id: usize) -> Result<Vec<WofValue>> {
+        let Job { handle, .. } = self
+            .state_mut::<Jobs>()
+            .running
+            .remove(&id)
+            .ok_or_else(|| WofError::Runtime(format!("join: no job {id}")))?;
+        let outcome = handle.join();
+        match outcome {
+            Ok(Ok(stack)) => Ok(stack),
+            Ok(Err(e)) => Err(WofError::Runtime(format!("job {id} failed: {e}"))),
+            Err(payload) => {
+                let reason = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| (*s).to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".into());
+                Err(WofError::Runtime(format!("job {id} panicked: {reason}")))
+            }
+        }
+    }
+}
+
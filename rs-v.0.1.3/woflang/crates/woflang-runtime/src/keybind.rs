@@ -53,6 +53,7 @@ impl KeyBindings {
         kb.bind("call", "巡");
         kb.bind("loop", "⟳");
         kb.bind("times", "⨯");
+        kb.bind("while", "当");
         kb.bind("break", "🛑");
         kb.bind("continue", "↻");
         
@@ -180,42 +181,39 @@ impl KeyBindings {
     /// Expand all aliases in a line of code.
     ///
     /// Performs a single pass, replacing each token that matches an alias.
+    /// String literals are copied unchanged.
     #[must_use]
     pub fn expand_line(&self, line: &str) -> String {
         let mut result = String::with_capacity(line.len());
-        let mut chars = line.chars().peekable();
-        
-        while chars.peek().is_some() {
-            // Skip whitespace
-            if chars.peek().map_or(false, |c| c.is_whitespace()) {
-                result.push(chars.next().unwrap());
-                continue;
-            }
-            
-            // Try to match an alias
-            let start = result.len();
-            let mut token = String::new();
-            
-            // Collect alphanumeric token
-            while chars.peek().map_or(false, |c| c.is_alphanumeric() || *c == '_') {
-                token.push(chars.next().unwrap());
-            }
-            
-            if token.is_empty() {
-                // Not alphanumeric, just copy the char
-                if let Some(c) = chars.next() {
-                    result.push(c);
-                }
-            } else if let Some(glyph) = self.resolve(&token) {
-                // Found a binding, expand it
-                result.push_str(glyph);
+        self.expand_line_into(line, &mut result);
+        result
+    }
+
+    /// Expand all aliases in a line of code as [`expand_line`](Self::expand_line)
+    /// does, appending the result to `result`.
+    pub fn expand_line_into(&self, line: &str, result: &mut String) {
+        let mut rest = line;
+
+        while let Some(c) = rest.chars().next() {
+            let len = if c == '"' {
+                // Copy string literals through untouched
+                let len = crate::tokenizer::string_literal_len(rest);
+                result.push_str(&rest[..len]);
+                len
+            } else if c.is_alphanumeric() || c == '_' {
+                // Expand the alphanumeric token if it is bound
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                let token = &rest[..len];
+                result.push_str(self.resolve(token).unwrap_or(token));
+                len
             } else {
-                // No binding, keep original
-                result.push_str(&token);
-            }
+                result.push(c);
+                c.len_utf8()
+            };
+            rest = &rest[len..];
         }
-        
-        result
     }
 
     // ═══════════════════════════════════════════════════════════════
@@ -334,6 +332,13 @@ mod tests {
         assert_eq!(kb.expand_line("unknown"), "unknown");
     }
 
+    #[test]
+    fn expand_skips_string_literals() {
+        let kb = KeyBindings::with_defaults();
+        assert_eq!(kb.expand_line(r#"1 and "rock and roll" not"#), r#"1 ∧ "rock and roll" ¬"#);
+        assert_eq!(kb.expand_line("\"\"\"in \" and\"\"\" or"), "\"\"\"in \" and\"\"\" ∨");
+    }
+
     #[test]
     fn expand_preserves_structure() {
         let mut kb = KeyBindings::new();
@@ -3,9 +3,47 @@
 //! This crate provides the runtime interpreter for the Woflang stack-based
 //! programming language. It handles:
 //!
-//! - **Tokenization**: Converting source text into tokens
+//! - **Tokenization**: Converting source text into tokens, whole or
+//!   as it streams in
+//! - **Infix**: Compiling infix expressions to postfix tokens
 //! - **Interpretation**: Executing tokens against the stack machine
-//! - **Plugin System**: Extensible operation registration
+//! - **Bytecode**: Compiling source to a [`Program`](woflang_core::Program)
+//!   and running it on a VM loop
+//! - **Plugin System**: Extensible operation registration, and versioned
+//!   dynamic plugins declared with `declare_plugin!`
+//! - **Op Hooks**: Observers around every registered operation
+//! - **Buffer pools**: Reusable token buffers for batch execution
+//! - **Cancellation**: Stopping long computations cleanly on Ctrl-C
+//! - **Progress**: Reports from long-running ops to a progress bar or embedder
+//! - **Streaming**: A line's stack deltas, printed text and errors as
+//!   events, for front-ends that render as it runs
+//! - **Macros**: Rewriting tokens as lines are read (`macro name ⺆ ... ⺘`)
+//! - **Parameters**: Dynamic variables set for the length of a block
+//!   (`parameterize "name" value ⺆ ... ⺘`)
+//! - **Namespaces**: Keeping a script's named graphs, machines and LFOs
+//!   apart from a library's (`ns "name" ⺆ ... ⺘`)
+//! - **Schemas**: Checking the shape of map and list data
+//!   (`schema "Name" "key:type" ...`)
+//! - **Error Handling**: Catching errors as `Error` records
+//!   (`try ⺆ ... ⺘ catch ⺆ ... ⺘`)
+//! - **Test Cases**: Recording assertion results per named case
+//!   (`test_case "name" ⺆ ... ⺘`)
+//! - **Options and Results**: Unwrapping `Some`/`Ok` values and passing
+//!   `None`/`Err` through (`map_ok`, `and_then`, `try_prop`)
+//! - **Resources**: Files and other handles closed when a block ends
+//!   (`with_file "path" ⺆ ... ⺘`)
+//! - **Jobs**: Running blocks on worker threads (`spawn ⺆ ... ⺘`)
+//! - **JIT**: Compiling numeric blocks to native code (`jit ⺆ ... ⺘`,
+//!   feature-gated)
+//! - **Debugger**: Breakpoints on ops or lines, stepping and frame
+//!   inspection through a pluggable frontend
+//! - **Profiling**: Time per dispatch phase, or call counts and time per
+//!   operation and user function
+//! - **Snapshots**: Copies of the stack and variables to go back to,
+//!   behind `checkpoint`/`rollback` and the REPL's undo
+//! - **Differential Testing**: Comparing execution backends over a corpus
+//! - **Language Versions**: Running scripts that declare an older
+//!   `@wof-version` under renamed ops, and refusing newer ones
 //!
 //! ## Architecture
 //!
@@ -42,19 +80,63 @@
 #![warn(missing_docs)]
 #![allow(clippy::module_name_repetitions)]
 
+mod bytecode;
+mod cancel;
+mod catch;
+mod compat;
+mod coverage;
+mod debugger;
+pub mod difftest;
+mod explain;
+mod hooks;
+pub mod infix;
 mod interpreter;
+#[cfg(feature = "jit")]
+mod jit;
+mod jobs;
 mod keybind;
+mod macros;
+mod manifest;
+mod namespace;
+mod option;
+mod params;
 #[cfg(feature = "dynamic-plugins")]
 mod plugin;
+mod pool;
+mod profile;
+mod progress;
+mod record;
 mod registry;
+mod resources;
+mod sandbox;
+mod schema;
+pub mod stream;
+mod tagged;
+mod testing;
 mod tokenizer;
 
-pub use interpreter::{FunctionDef, Interpreter, LoopType, OwnedToken};
+pub use cancel::CancelToken;
+pub use catch::ERROR_FIELDS;
+pub use compat::{Rename, LANGUAGE_VERSION, RENAMED_OPS};
+pub use coverage::Coverage;
+pub use debugger::{Breakpoint, DebugAction, DebugFrame, DebugFrontend, Pause, PauseReason};
+pub use hooks::OpHook;
+pub use interpreter::{is_special_form, FunctionDef, Interpreter, LoopType, OwnedToken, Snapshot, DEFAULT_MAX_CALL_DEPTH};
+pub use jobs::JobStatus;
 pub use keybind::KeyBindings;
+pub use manifest::{PluginManifest, PluginOpDoc, PLUGIN_ABI_VERSION};
 #[cfg(feature = "dynamic-plugins")]
-pub use plugin::PluginLoader;
-pub use registry::{OpFn, Registry};
-pub use tokenizer::{Token, TokenKind, Tokenizer};
+pub use plugin::{plugin_libraries, ManifestFn, PluginInfo, PluginLoader, RegisterPluginFn};
+pub use params::{ParamGet, ParamSet};
+pub use pool::{BufferPool, PoolStats};
+pub use profile::{CallStats, OpProfile, Phase, PhaseProfile};
+pub use progress::{Progress, ProgressSink};
+pub use resources::{ResourceClose, ResourceOpen};
+pub use registry::{OpFn, OpId, OpMeta, Registry};
+pub use sandbox::Sandbox;
+pub use stream::{Event, EventSink};
+pub use testing::{TestCase, TestReport};
+pub use tokenizer::{is_incomplete, IncrementalTokenizer, Token, TokenKind, Tokenizer};
 
 /// Re-export core types for convenience.
 pub mod core {
@@ -0,0 +1,242 @@
+//! Macros: `macro name ⺆ body ⺘`.
+//!
+//! A macro is a function that runs while a line is being read rather than
+//! while it executes. Wherever its name appears, the body is called with
+//! the tokens that follow it, up to the end of the enclosing block or
+//! else of the line, as a list of strings on the stack. It must leave a
+//! list whose items are spliced back in their place:
+//!
+//! ```text
+//! macro unless ⺆ 0 "not 若 ⺆" list_set ⺘
+//! x 0 > unless ⺆ "not positive" print ⺘
+//! ```
+//!
+//! Token strings keep their source spelling, so a string literal arrives
+//! as `"\"hi\""`. An item may hold several tokens (`"not 若 ⺆"`) and
+//! non-string items are spelled as they display. The replacement is read
+//! again, so macros can expand to other macros, up to 10 000 expansions
+//! per line.
+//!
+//! Definitions also happen as the line is read, so a macro can be used
+//! later on the line that defines it; a definition must fit on one line.
+//! Bytecode compiled with [`compile_line`](Interpreter::compile_line) is
+//! not expanded, while [`compile_source`](Interpreter::compile_source) is.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use woflang_core::{InterpreterContext, Result, Span, WofError, WofType, WofValue};
+
+use crate::{Interpreter, OwnedToken, TokenKind, Tokenizer};
+
+/// Macro expansions allowed while reading one line, so a macro that
+/// keeps reproducing itself fails instead of hanging.
+const MAX_EXPANSIONS: usize = 10_000;
+
+/// Macro bodies by name.
+#[derive(Default)]
+struct Macros {
+    defs: BTreeMap<String, Vec<OwnedToken>>,
+}
+
+impl Interpreter {
+    /// Check whether a macro named `name` exists.
+    #[must_use]
+    pub fn has_macro(&self, name: &str) -> bool {
+        self.state::<Macros>().is_some_and(|m| m.defs.contains_key(name))
+    }
+
+    /// The names of all macros, sorted.
+    #[must_use]
+    pub fn macro_names(&self) -> Vec<&str> {
+        self.state::<Macros>()
+            .map(|m| m.defs.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Define the macros in `tokens` and expand the uses of macros,
+    /// leaving tokens ready to bind and run.
+    pub(crate) fn expand_macros(&mut self, tokens: &mut VecDeque<OwnedToken>) -> Result<()> {
+        let is_macro = |t: &OwnedToken| t.kind == TokenKind::Symbol && (t.text == "macro" || self.has_macro(&t.text));
+        if !tokens.iter().any(is_macro) {
+            return Ok(());
+        }
+
+        let mut input = std::mem::take(tokens);
+        let mut expansions = 0;
+        while let Some(token) = input.pop_front() {
+            if token.kind != TokenKind::Symbol {
+                tokens.push_back(token);
+            } else if token.text == "macro" {
+                self.define_macro(&mut input, token.span)?;
+            } else if let Some(body) = self.macro_body(&token.text) {
+                expansions += 1;
+                if expansions > MAX_EXPANSIONS {
+                    return Err(WofError::runtime_at(
+                        format!("macro {}: more than {MAX_EXPANSIONS} expansions", token.text),
+                        token.span,
+                    ));
+                }
+                let following = input.drain(..block_end(&input)).map(|t| WofValue::string(t.text)).collect();
+                let replacement = self.call_macro(&token, body, following)?;
+                for t in replacement.into_iter().rev() {
+                    input.push_front(t);
+                }
+            } else {
+                tokens.push_back(token);
+            }
+        }
+        Ok(())
+    }
+
+    /// The body of macro `name`, if there is one.
+    fn macro_body(&self, name: &str) -> Option<Vec<OwnedToken>> {
+        self.state::<Macros>()?.defs.get(name).cloned()
+    }
+
+    /// Define a macro from the tokens after `macro`: `name ⺆ body ⺘`.
+    fn define_macro(&mut self, input: &mut VecDeque<OwnedToken>, span: Span) -> Result<()> {
+        let usage = || WofError::runtime_at("macro requires: macro name ⺆ body ⺘", span);
+        let name = match input.pop_front() {
+            Some(t) if t.kind == TokenKind::Symbol && t.text != "⺆" => t.text,
+            _ => return Err(usage()),
+        };
+        if !matches!(input.pop_front(), Some(t) if t.text == "⺆") {
+            return Err(usage());
+        }
+        let mut body = VecDeque::new();
+        let mut depth = 0usize;
+        loop {
+            let token = input.pop_front().ok_or_else(usage)?;
+            match token.text.as_str() {
+                "⺆" => depth += 1,
+                "⺘" if depth == 0 => break,
+                "⺘" => depth -= 1,
+                _ => {}
+            }
+            body.push_back(token);
+        }
+        // Macros used in the body expand now, as anywhere else.
+        self.expand_macros(&mut body)?;
+        self.state_mut::<Macros>().defs.insert(name, body.into());
+        Ok(())
+    }
+
+    /// Run a macro body on the tokens following its use and read back
+    /// the tokens it leaves, attributed to the use.
+    fn call_macro(&mut self, token: &OwnedToken, body: Vec<OwnedToken>, following: Vec<WofValue>) -> Result<Vec<OwnedToken>> {
+        let name = &token.text;
+        let depth = self.stack().len();
+        self.push(WofValue::list(following));
+        let ran = self.run_nested(body);
+        let extra = self.stack().len().saturating_sub(depth);
+        let left = self.stack_mut().pop_n(extra);
+
+        let invalid = || WofError::runtime_at(format!("macro {name}: must leave just a list of tokens"), token.span);
+        ran.map_err(|e| WofError::runtime_at(format!("macro {name}: {e}"), token.span))?;
+        let items = match left?.as_slice() {
+            [list] => list.clone().into_list().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        };
+        let mut source = String::new();
+        for item in &items {
+            if item.value_type() == WofType::List {
+                return Err(invalid());
+            }
+            match item.as_str() {
+                Ok(text) => source.push_str(text),
+                Err(_) => source.push_str(&item.to_string()),
+            }
+            source.push(' ');
+        }
+        Ok(Tokenizer::new(&source)
+            .map(|t| OwnedToken { kind: t.kind, text: t.text.to_string(), span: token.span, op: None })
+            .collect())
+    }
+}
+
+/// How many of `tokens` come before the `⺘` closing the block they are in.
+fn block_end(tokens: &VecDeque<OwnedToken>) -> usize {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.text.as_str() {
+            "⺆" => depth += 1,
+            "⺘" if depth == 0 => return i,
+            "⺘" => depth -= 1,
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        interp.register("drop", |ctx| ctx.stack_mut().drop());
+        interp.register("+", |ctx| {
+            let b = ctx.stack_mut().pop_numeric()?;
+            let a = ctx.stack_mut().pop_numeric()?;
+            ctx.push(WofValue::double(a + b));
+            Ok(())
+        });
+        // Stack: list → list, reversed
+        interp.register("reverse", |ctx| {
+            let mut items = ctx.pop()?.into_list()?;
+            items.reverse();
+            ctx.push(WofValue::list(items));
+            Ok(())
+        });
+        // Stack: x → [x]
+        interp.register("wrap", |ctx| {
+            let x = ctx.pop()?;
+            ctx.push(WofValue::list(vec![x]));
+            Ok(())
+        });
+        interp
+    }
+
+    fn drain(interp: &mut Interpreter) -> Vec<String> {
+        let values = interp.stack().as_slice().iter().map(ToString::to_string).collect();
+        interp.stack_mut().clear();
+        values
+    }
+
+    #[test]
+    fn macros_rewrite_the_rest_of_the_line() {
+        let mut interp = make_interp();
+        interp.exec_line("macro backwards ⺆ reverse ⺘").unwrap();
+        assert!(interp.has_macro("backwards"));
+        interp.exec_line("backwards \"a\" 2 1").unwrap();
+        assert_eq!(drain(&mut interp), ["1", "2", "a"]);
+
+        // Defined and used on one line.
+        interp.exec_line("macro add ⺆ drop \"+\" wrap ⺘ 1 2 add ignored").unwrap();
+        assert_eq!(drain(&mut interp), ["3.0"]);
+        // Replacements are expanded in turn.
+        interp.exec_line("macro add_later ⺆ drop \"add ignored\" wrap ⺘ 4 5 add_later").unwrap();
+        assert_eq!(drain(&mut interp), ["9.0"]);
+        assert_eq!(interp.macro_names(), ["add", "add_later", "backwards"]);
+
+        // Inside a block a macro gets the rest of the block.
+        interp.exec_line("⊕ f ⺆ backwards 1 2 ⺘ f 3").unwrap();
+        assert_eq!(drain(&mut interp), ["2", "1", "3"]);
+    }
+
+    #[test]
+    fn expansion_errors() {
+        let mut interp = make_interp();
+        assert!(interp.exec_line("macro ⺆ ⺘").is_err());
+        assert!(interp.exec_line("macro m ⺆ drop").is_err());
+
+        interp.exec_line("macro junk ⺆ 1 ⺘ macro bad ⺆ 1 + ⺘ macro forever ⺆ drop \"forever\" wrap ⺘").unwrap();
+        let err = interp.exec_line("junk 1 2").unwrap_err();
+        assert!(err.to_string().contains("must leave just a list"), "{err}");
+        let err = interp.exec_line("bad").unwrap_err();
+        assert!(err.to_string().contains("macro bad"), "{err}");
+        let err = interp.exec_line("forever").unwrap_err();
+        assert!(err.to_string().contains("expansions"), "{err}");
+        assert!(interp.stack().is_empty());
+    }
+}
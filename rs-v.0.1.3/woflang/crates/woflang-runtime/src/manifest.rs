@@ -0,0 +1,139 @@
+//! The dynamic plugin ABI: what a plugin library exports.
+//!
+//! A plugin is a `cdylib` crate depending on `woflang-runtime` that
+//! declares itself with [`declare_plugin!`](crate::declare_plugin):
+//!
+//! ```ignore
+//! use woflang_core::{InterpreterContext, WofValue};
+//! use woflang_runtime::Interpreter;
+//!
+//! fn register(interp: &mut Interpreter) {
+//!     interp.register("answer", |interp| {
+//!         interp.push(WofValue::integer(42));
+//!         Ok(())
+//!     });
+//! }
+//!
+//! woflang_runtime::declare_plugin! {
+//!     name: "answer",
+//!     version: env!("CARGO_PKG_VERSION"),
+//!     core: "10.1",
+//!     ops: [("answer", "( -- n )", "Push the answer")],
+//!     register: register,
+//! }
+//! ```
+//!
+//! The macro exports three symbols, which the loader reads in order:
+//!
+//! | Symbol                     | Signature                   | Purpose |
+//! |----------------------------|-----------------------------|---------|
+//! | `woflang_plugin_abi`       | `extern "C" fn() -> u32`    | [`PLUGIN_ABI_VERSION`] the plugin was built for |
+//! | `woflang_plugin_manifest`  | `fn() -> PluginManifest`    | Name, version, core requirement and op docs |
+//! | `woflang_register_plugin`  | `fn(&mut Interpreter)`      | Register the plugin's ops |
+//!
+//! Only the first has a C signature; the others pass Rust types, so
+//! plugins must be built with the same compiler as the host. The ABI
+//! version guards the shape of these symbols, and the core requirement
+//! guards the [`Interpreter`](crate::Interpreter) they are handed.
+
+/// The plugin ABI version this runtime loads. Bumped whenever the
+/// exported symbols or [`PluginManifest`] change shape.
+pub const PLUGIN_ABI_VERSION: u32 = 2;
+
+/// What a plugin says about itself, returned by its
+/// `woflang_plugin_manifest` export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginManifest {
+    /// Plugin name, unique among loaded plugins.
+    pub name: &'static str,
+    /// Plugin version, for listings.
+    pub version: &'static str,
+    /// The runtime versions the plugin works with, as a caret
+    /// requirement: `"10.1"` accepts 10.1.0 and later 10.x releases.
+    pub core_version: &'static str,
+    /// Reference docs for the ops the plugin registers.
+    pub ops: &'static [PluginOpDoc],
+}
+
+/// Reference docs for one plugin op.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginOpDoc {
+    /// Op name.
+    pub name: &'static str,
+    /// Stack effect in `( before -- after )` notation.
+    pub stack_effect: &'static str,
+    /// One-line description.
+    pub help: &'static str,
+}
+
+/// Export the plugin ABI symbols for a plugin library. See the
+/// [`PluginManifest`] docs for the fields.
+#[macro_export]
+#[allow(edition_2024_expr_fragment_specifier)]
+macro_rules! declare_plugin {
+    (
+        name: $name:expr,
+        version: $version:expr,
+        core: $core:expr,
+        ops: [$(($op:expr, $effect:expr, $help:expr)),* $(,)?],
+        register: $register:path $(,)?
+    ) => {
+        #[no_mangle]
+        pub extern "C" fn woflang_plugin_abi() -> u32 {
+            $crate::PLUGIN_ABI_VERSION
+        }
+
+        #[no_mangle]
+        pub fn woflang_plugin_manifest() -> $crate::PluginManifest {
+            $crate::PluginManifest {
+                name: $name,
+                version: $version,
+                core_version: $core,
+                ops: &[$($crate::PluginOpDoc { name: $op, stack_effect: $effect, help: $help }),*],
+            }
+        }
+
+        #[no_mangle]
+        pub fn woflang_register_plugin(interp: &mut $crate::Interpreter) {
+            $register(interp);
+        }
+    };
+}
+
+/// Whether `version` (`major.minor.patch`) meets the caret requirement
+/// `required` (`major[.minor[.patch]]`); `None` if either is malformed.
+#[cfg_attr(not(feature = "dynamic-plugins"), allow(dead_code))]
+pub(crate) fn satisfies(required: &str, version: &str) -> Option<bool> {
+    fn parts(text: &str) -> Option<Vec<u64>> {
+        text.trim().split('.').map(|part| part.parse().ok()).collect()
+    }
+    let required = parts(required).filter(|parts| (1..=3).contains(&parts.len()))?;
+    let mut version = parts(version).filter(|parts| parts.len() == 3)?;
+    // Below 1.0 the minor version is the breaking one, as in Cargo.
+    let fixed = if required.first() == Some(&0) { 2 } else { 1 };
+    let fixed = fixed.min(required.len());
+    if version[..fixed] != required[..fixed] {
+        return Some(false);
+    }
+    version.truncate(required.len());
+    Some(version >= required)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caret_requirements() {
+        assert_eq!(satisfies("10", "10.4.2"), Some(true));
+        assert_eq!(satisfies("10.1", "10.1.0"), Some(true));
+        assert_eq!(satisfies("10.1", "10.3.0"), Some(true));
+        assert_eq!(satisfies("10.2", "10.1.9"), Some(false));
+        assert_eq!(satisfies("10.1.2", "10.1.1"), Some(false));
+        assert_eq!(satisfies("10", "11.0.0"), Some(false));
+        assert_eq!(satisfies("0.3", "0.3.5"), Some(true));
+        assert_eq!(satisfies("0.3", "0.4.0"), Some(false));
+        assert_eq!(satisfies("ten", "10.1.1"), None);
+        assert_eq!(satisfies("10", "10.1"), None);
+    }
+}
@@ -0,0 +1,141 @@
+//! Resource namespaces: `ns "mylib" ⺆ ... ⺘`.
+//!
+//! Ops that keep named objects, such as graphs, state machines and LFOs,
+//! store them per interpreter under [`Interpreter::resource_name`]. At
+//! the top level that is the name as written; inside `ns` it is prefixed
+//! with the namespace, so a library's `"g"` and a script's `"g"` are
+//! different graphs:
+//!
+//! ```
+//! use woflang_runtime::Interpreter;
+//!
+//! let mut interp = Interpreter::new();
+//! assert_eq!(interp.resource_name("g"), "g");
+//! interp
+//!     .in_namespace("mylib", |interp| {
+//!         assert_eq!(interp.resource_name("g"), "mylib::g");
+//!         assert_eq!(interp.resource_name("::g"), "g");
+//!         interp.in_namespace("util", |interp| {
+//!             assert_eq!(interp.resource_name("g"), "mylib::util::g");
+//!             Ok(())
+//!         })
+//!     })
+//!     .unwrap();
+//! ```
+//!
+//! Namespaces nest, and apply to everything run inside the block,
+//! including the functions it calls. Two escapes reach the top level: a
+//! name starting with `::`, and a `global ⺆ ... ⺘` block, which runs
+//! with no namespace. From the top level, `"mylib::g"` names the
+//! library's graph directly.
+
+use woflang_core::{Result, WofError};
+
+use crate::Interpreter;
+
+/// Separates the parts of a qualified name.
+const SEPARATOR: &str = "::";
+
+/// The namespace in effect, empty at the top level.
+#[derive(Debug, Default)]
+struct Namespace(String);
+
+impl Interpreter {
+    /// The namespace in effect, such as `"mylib::util"`; empty at the top
+    /// level.
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        self.state::<Namespace>().map_or("", |ns| ns.0.as_str())
+    }
+
+    /// The key a named object is stored under: `name` qualified by the
+    /// current namespace, or without its leading `::` if it has one.
+    #[must_use]
+    pub fn resource_name(&self, name: &str) -> String {
+        if let Some(global) = name.strip_prefix(SEPARATOR) {
+            return global.to_string();
+        }
+        match self.namespace() {
+            "" => name.to_string(),
+            ns => format!("{ns}{SEPARATOR}{name}"),
+        }
+    }
+
+    /// Run `body` inside namespace `name`, nested in the current one,
+    /// then return to the current namespace whether or not `body`
+    /// succeeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` is empty or contains `::`, or the error
+    /// from `body`.
+    pub fn in_namespace<T>(&mut self, name: &str, body: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if name.is_empty() || name.contains(SEPARATOR) {
+            return Err(WofError::Runtime(format!("ns: invalid namespace name '{name}'")));
+        }
+        let inner = self.resource_name(name);
+        self.with_namespace(inner, body)
+    }
+
+    /// Run `body` at the top level, outside any namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `body`.
+    pub fn in_global<T>(&mut self, body: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        self.with_namespace(String::new(), body)
+    }
+
+    fn with_namespace<T>(&mut self, ns: String, body: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let outer = std::mem::replace(&mut self.state_mut::<Namespace>().0, ns);
+        let result = body(self);
+        self.state_mut::<Namespace>().0 = outer;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use woflang_core::{InterpreterContext, WofValue};
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        interp.register("here", |interp| {
+            let name = interp.resource_name("g");
+            interp.push(WofValue::string(name));
+            Ok(())
+        });
+        interp.register("fail", |_| Err(WofError::Runtime("fail".into())));
+        interp
+    }
+
+    fn names(interp: &mut Interpreter) -> Vec<String> {
+        let mut names: Vec<String> = std::iter::from_fn(|| interp.stack_mut().pop_string().ok()).collect();
+        names.reverse();
+        names
+    }
+
+    #[test]
+    fn blocks_qualify_names_and_global_escapes() {
+        let mut interp = make_interp();
+        interp.exec_line(r#"here ns "a" ⺆ here ns "b" ⺆ here global ⺆ here ⺘ ⺘ here ⺘ here"#).unwrap();
+        assert_eq!(names(&mut interp), ["g", "a::g", "a::b::g", "g", "a::g", "g"]);
+
+        // The namespace is left even if the block fails.
+        assert!(interp.exec_line(r#"ns "a" ⺆ fail ⺘"#).is_err());
+        assert_eq!(interp.namespace(), "");
+        assert!(interp.exec_line(r#"ns "a::b" ⺆ ⺘"#).is_err());
+        assert!(interp.exec_line("ns ⺆ ⺘").is_err());
+        assert!(interp.exec_line(r#"ns "a" here"#).is_err());
+    }
+
+    #[test]
+    fn returns_leave_the_block_and_function() {
+        let mut interp = make_interp();
+        interp.exec_line(r#"⊕ f ⺆ ns "a" ⺆ here 至 ⺘ 99 ⺘ ⊕ g ⺆ ns "b" ⺆ global ⺆ here 至 ⺘ 99 ⺘ 99 ⺘"#).unwrap();
+        interp.exec_line("f g here").unwrap();
+        assert_eq!(names(&mut interp), ["a::g", "g", "g"]);
+        assert_eq!(interp.namespace(), "");
+    }
+}
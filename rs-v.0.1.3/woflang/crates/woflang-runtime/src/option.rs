@@ -0,0 +1,146 @@
+//! Combinators for option and result values: `map_ok`, `and_then` and
+//! `try_prop`.
+//!
+//! The values themselves are `Option.Some`/`Option.None` and
+//! `Result.Ok`/`Result.Err` tagged values, built by the standard ops
+//! `some`, `none`, `ok` and `err`. Each form passes `None` and `Err`
+//! through untouched and unwraps `Some` and `Ok`:
+//!
+//! | Form               | Stack Effect      | On `Some(x)` / `Ok(x)` |
+//! |--------------------|-------------------|------------------------|
+//! | `map_ok ⺆ ... ⺘`   | ( opt -- opt' )   | Run the block on `x` and rewrap its value |
+//! | `and_then ⺆ ... ⺘` | ( opt -- opt' )   | Run the block on `x`; it must leave an option or result |
+//! | `try_prop`         | ( opt -- x )      | Push `x`; on `None`/`Err`, return it from the current function |
+
+use woflang_core::{InterpreterContext, Result, WofError, WofTagged, WofValue};
+
+use crate::{Interpreter, OwnedToken};
+
+impl Interpreter {
+    /// Run `body` on the value inside the option or result on top of the
+    /// stack and rewrap the single value it leaves.
+    pub(crate) fn map_ok(&mut self, body: Vec<OwnedToken>) -> Result<()> {
+        let Some((outer, result)) = self.run_on_success("map_ok", body)? else { return Ok(()) };
+        let rewrapped = WofTagged::new(outer.enum_name().into(), outer.tag().into(), vec![result]);
+        self.push(WofValue::tagged(rewrapped));
+        Ok(())
+    }
+
+    /// Run `body` on the value inside the option or result on top of the
+    /// stack, keeping the option or result it leaves.
+    pub(crate) fn and_then(&mut self, body: Vec<OwnedToken>) -> Result<()> {
+        let Some((_, result)) = self.run_on_success("and_then", body)? else { return Ok(()) };
+        result.as_tagged()?.success()?;
+        self.push(result);
+        Ok(())
+    }
+
+    /// Replace the option or result on top of the stack with its value,
+    /// or leave it and return from the current function if it holds none.
+    pub(crate) fn try_prop(&mut self) -> Result<()> {
+        let value = self.stack_mut().pop()?;
+        if let Some(inner) = value.as_tagged()?.success()? {
+            self.push(inner.clone());
+            return Ok(());
+        }
+        self.push(value);
+        self.return_from_function()
+    }
+
+    /// Pop an option or result. For `Some(x)` or `Ok(x)`, run `body` on
+    /// `x` and return the wrapper with the one value `body` left; for
+    /// `None` or `Err`, push it back and return `None`. Also return
+    /// `None` if `body` returned from the current function.
+    fn run_on_success(&mut self, form: &str, body: Vec<OwnedToken>) -> Result<Option<(WofTagged, WofValue)>> {
+        let value = self.stack_mut().pop()?;
+        let outer = value.as_tagged()?;
+        let Some(inner) = outer.success()?.cloned() else {
+            self.push(value);
+            return Ok(None);
+        };
+        let outer = outer.clone();
+        let (depth, calls) = (self.stack().len(), self.call_depth());
+        self.push(inner);
+        self.run_nested(body)?;
+        if self.call_depth() < calls {
+            return Ok(None);
+        }
+        if self.stack().len() != depth + 1 {
+            return Err(WofError::Runtime(format!("{form}: the block must leave a single value")));
+        }
+        Ok(Some((outer, self.stack_mut().pop()?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interpreter;
+    use woflang_core::{InterpreterContext, WofTagged, WofValue};
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        interp.register("ok", |interp| {
+            let value = interp.stack_mut().pop()?;
+            interp.push(WofValue::tagged(WofTagged::ok(value)));
+            Ok(())
+        });
+        interp.register("err", |interp| {
+            let value = interp.stack_mut().pop()?;
+            interp.push(WofValue::tagged(WofTagged::err(value)));
+            Ok(())
+        });
+        interp.register("none", |interp| {
+            interp.push(WofValue::tagged(WofTagged::none()));
+            Ok(())
+        });
+        interp.register("inc", |interp| {
+            let n = interp.stack_mut().pop_integer()?;
+            interp.push(WofValue::integer(n + 1));
+            Ok(())
+        });
+        interp
+    }
+
+    fn top(interp: &mut Interpreter) -> String {
+        interp.stack_mut().pop().unwrap().to_string()
+    }
+
+    #[test]
+    fn combinators_unwrap_ok_and_pass_err_through() {
+        let mut interp = make_interp();
+        interp.exec_line("1 ok map_ok ⺆ inc ⺘ 1 err map_ok ⺆ inc ⺘ none map_ok ⺆ inc ⺘").unwrap();
+        assert_eq!(top(&mut interp), "Option.None");
+        assert_eq!(top(&mut interp), "Result.Err(1)");
+        assert_eq!(top(&mut interp), "Result.Ok(2)");
+
+        interp.exec_line("1 ok and_then ⺆ inc err ⺘ 1 ok and_then ⺆ inc ok ⺘").unwrap();
+        assert_eq!(top(&mut interp), "Result.Ok(2)");
+        assert_eq!(top(&mut interp), "Result.Err(2)");
+
+        assert!(interp.exec_line("1 ok and_then ⺆ inc ⺘").is_err());
+        assert!(interp.exec_line("1 ok map_ok ⺆ drop ⺘").is_err());
+        assert!(interp.exec_line("1 map_ok ⺆ inc ⺘").is_err());
+        assert!(interp.exec_line("1 ok map_ok inc").is_err());
+    }
+
+    #[test]
+    fn try_prop_returns_err_from_the_function() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ bump ⺆ try_prop inc ok ⺘").unwrap();
+        interp.exec_line("5 ok bump \"no\" err bump").unwrap();
+        assert_eq!(top(&mut interp), "Result.Err(no)");
+        assert_eq!(top(&mut interp), "Result.Ok(6)");
+
+        // A return from inside a block leaves the function as it is.
+        interp.exec_line("⊕ f ⺆ map_ok ⺆ 至 ⺘ 99 ⺘ ⊕ g ⺆ and_then ⺆ inc 至 ⺘ 99 ⺘").unwrap();
+        interp.exec_line("1 ok f 2 ok g 5").unwrap();
+        assert_eq!(top(&mut interp), "5");
+        assert_eq!(top(&mut interp), "3");
+        assert_eq!(top(&mut interp), "1");
+
+        // At the top level it ends the line.
+        interp.exec_line("none try_prop 9").unwrap();
+        assert_eq!(top(&mut interp), "Option.None");
+        assert!(interp.stack().is_empty());
+    }
+}
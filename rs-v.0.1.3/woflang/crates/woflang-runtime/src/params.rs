@@ -0,0 +1,202 @@
+//! Dynamic variables: `parameterize "precision" 4 ⺆ ... ⺘`.
+//!
+//! A dynamic variable (a *parameter*) is a named setting that ops
+//! consult, such as display precision, the analog mode or the random
+//! seed. Whoever owns the setting defines the parameter with a getter and
+//! a setter, so the value can live wherever the owner keeps it:
+//!
+//! ```
+//! use woflang_runtime::Interpreter;
+//! use woflang_core::WofValue;
+//!
+//! #[derive(Default)]
+//! struct Verbose(bool);
+//!
+//! let mut interp = Interpreter::new();
+//! interp.define_param(
+//!     "verbose",
+//!     |interp| WofValue::boolean(interp.state::<Verbose>().is_some_and(|v| v.0)),
+//!     |interp, value| {
+//!         interp.state_mut::<Verbose>().0 = value.is_truthy();
+//!         Ok(())
+//!     },
+//! );
+//!
+//! interp.exec_line("parameterize \"verbose\" 1 ⺆ ⺘").unwrap();
+//! assert!(!interp.param("verbose").unwrap().is_truthy());
+//! ```
+//!
+//! `parameterize` runs the tokens between the name and the block to get
+//! the new value, sets it for the block, and restores the previous value
+//! afterwards, even if the block fails.
+
+use std::collections::BTreeMap;
+
+use woflang_core::{Result, WofError, WofType, WofValue};
+
+use crate::Interpreter;
+
+/// Read a parameter's current value.
+pub type ParamGet = fn(&Interpreter) -> WofValue;
+
+/// Set a parameter, rejecting values it cannot take.
+pub type ParamSet = fn(&mut Interpreter, WofValue) -> Result<()>;
+
+/// Parameter accessors by name.
+#[derive(Default)]
+struct Params {
+    defs: BTreeMap<String, (ParamGet, ParamSet)>,
+}
+
+impl Interpreter {
+    /// Define the parameter `name`, replacing any existing definition.
+    pub fn define_param(&mut self, name: impl Into<String>, get: ParamGet, set: ParamSet) {
+        self.state_mut::<Params>().defs.insert(name.into(), (get, set));
+    }
+
+    /// The names of all parameters, sorted.
+    #[must_use]
+    pub fn param_names(&self) -> Vec<&str> {
+        self.state::<Params>()
+            .map(|p| p.defs.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    fn accessors(&self, name: &str) -> Result<(ParamGet, ParamSet)> {
+        self.state::<Params>()
+            .and_then(|p| p.defs.get(name).copied())
+            .ok_or_else(|| WofError::Runtime(format!("no parameter named '{name}'")))
+    }
+
+    /// The current value of parameter `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such parameter.
+    pub fn param(&self, name: &str) -> Result<WofValue> {
+        let (get, _) = self.accessors(name)?;
+        Ok(get(self))
+    }
+
+    /// Set parameter `name` until it is set again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such parameter or it rejects
+    /// `value`.
+    pub fn set_param(&mut self, name: &str, value: WofValue) -> Result<()> {
+        let (_, set) = self.accessors(name)?;
+        set(self, value)
+    }
+
+    /// Run `body` with parameter `name` set to `value`, then restore its
+    /// previous value whether or not `body` succeeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameter cannot be set, or the error from
+    /// `body`.
+    pub fn parameterize<T>(
+        &mut self,
+        name: &str,
+        value: WofValue,
+        body: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let (get, set) = self.accessors(name)?;
+        let previous = get(self);
+        set(self, value).map_err(|e| WofError::Runtime(format!("parameterize {name}: {e}")))?;
+        let result = body(self);
+        let restored = set(self, previous);
+        let value = result?;
+        restored?;
+        Ok(value)
+    }
+
+    /// Format `value` for printing, rounding doubles to the `precision`
+    /// parameter's number of places when it is defined and set.
+    #[must_use]
+    pub fn display(&self, value: &WofValue) -> String {
+        let places = self.param("precision").ok().and_then(|p| p.as_integer().ok());
+        match (places.and_then(|p| usize::try_from(p).ok()), value.value_type()) {
+            (Some(places), WofType::Double) => {
+                let x = value.as_double().unwrap_or_default();
+                match value.unit() {
+                    Some(unit) => format!("{x:.places$} {}", unit.name),
+                    None => format!("{x:.places$}"),
+                }
+            }
+            _ => value.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use woflang_core::InterpreterContext;
+
+    #[derive(Default)]
+    struct Level(i64);
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        interp.define_param(
+            "level",
+            |interp| WofValue::integer(interp.state::<Level>().map_or(0, |l| l.0)),
+            |interp, value| {
+                let level = value.as_integer()?;
+                if level < 0 {
+                    return Err(WofError::InvalidArgument("level must not be negative".into()));
+                }
+                interp.state_mut::<Level>().0 = level;
+                Ok(())
+            },
+        );
+        interp.register("level", |interp| {
+            let level = interp.param("level")?;
+            interp.stack_mut().push(level);
+            Ok(())
+        });
+        interp.register("fail", |_| Err(WofError::Runtime("fail".into())));
+        interp
+    }
+
+    fn drain(interp: &mut Interpreter) -> Vec<i64> {
+        let values = interp.stack().as_slice().iter().map(|v| v.as_integer().unwrap()).collect();
+        interp.stack_mut().clear();
+        values
+    }
+
+    #[test]
+    fn blocks_see_the_override() {
+        let mut interp = make_interp();
+        interp.set_param("level", WofValue::integer(1)).unwrap();
+        interp
+            .exec_line("level parameterize \"level\" 2 ⺆ level parameterize \"level\" 3 ⺆ level ⺘ level ⺘ level")
+            .unwrap();
+        assert_eq!(drain(&mut interp), [1, 2, 3, 2, 1]);
+        assert_eq!(interp.param_names(), ["level"]);
+    }
+
+    #[test]
+    fn returns_leave_the_block_and_function() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ f ⺆ parameterize \"level\" 2 ⺆ level 至 ⺘ 99 ⺘").unwrap();
+        interp.exec_line("f level").unwrap();
+        assert_eq!(drain(&mut interp), [2, 0]);
+    }
+
+    #[test]
+    fn values_are_restored_after_errors() {
+        let mut interp = make_interp();
+        assert!(interp.exec_line("parameterize \"level\" 5 ⺆ fail ⺘").is_err());
+        assert_eq!(interp.param("level").unwrap().as_integer().unwrap(), 0);
+
+        assert!(interp.exec_line("parameterize \"level\" -1 ⺆ ⺘").is_err());
+        assert!(interp.exec_line("parameterize \"nope\" 1 ⺆ ⺘").is_err());
+        assert!(interp.exec_line("parameterize \"level\" ⺆ ⺘").is_err());
+        assert!(interp.exec_line("parameterize \"level\" 1 ⺆").is_err());
+        assert!(interp.set_param("nope", WofValue::nil()).is_err());
+        assert_eq!(interp.param("level").unwrap().as_integer().unwrap(), 0);
+    }
+}
@@ -6,15 +6,28 @@
 //!
 //! # Plugin ABI
 //!
-//! Plugins must export a `register_plugin` function with the following
-//! signature:
+//! Plugins declare themselves with
+//! [`declare_plugin!`](crate::declare_plugin), which exports an ABI
+//! version, a [`PluginManifest`] and a registration function. Before
+//! registering anything the loader checks that:
 //!
-//! ```c
-//! extern "C" void register_plugin(void* interpreter);
-//! ```
+//! - the plugin was built for [`PLUGIN_ABI_VERSION`],
+//! - its core requirement accepts this runtime's version, and
+//! - no plugin of the same name is loaded.
 //!
-//! The interpreter pointer is transmuted to a mutable reference to
-//! [`Interpreter`] for registration.
+//! The loader records which ops each plugin registered, attaches the
+//! manifest's docs to them, and removes them again when the plugin is
+//! unloaded or reloaded.
+//!
+//! # Hot reload
+//!
+//! With [`PluginLoader::enable_hot_reload`], the loader maps a private
+//! copy of each library instead of the file itself, so the plugin can be
+//! rebuilt in place while it is loaded (Windows locks mapped files) and
+//! the system loader never hands back the old code for the same path.
+//! [`PluginLoader::reload_plugin`] opens and checks the rebuilt library
+//! before unloading the old one, so a broken build leaves the old ops
+//! in place.
 //!
 //! # Safety
 //!
@@ -23,29 +36,84 @@
 //! - Violate memory safety guarantees
 //! - Cause undefined behavior if ABI mismatches occur
 //!
-//! Only load plugins from trusted sources.
+//! Only load plugins from trusted sources. Unloading a plugin removes its
+//! ops but keeps its library mapped until the loader is dropped, so
+//! anything it left behind (op handlers copied into a forked interpreter,
+//! hooks, state) stays valid while the loader lives.
 
 // Dynamic loading via libloading requires unsafe blocks.
 // This is an unavoidable consequence of FFI with unknown code.
 #![allow(unsafe_code)]
 
-use crate::Interpreter;
+use crate::manifest::{satisfies, PluginManifest, PLUGIN_ABI_VERSION};
+use crate::{Interpreter, OpMeta};
 use libloading::{Library, Symbol};
-use std::path::Path;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use woflang_core::{Result, WofError};
 
-/// Type signature for plugin registration functions.
+/// The version plugins' core requirements are checked against.
+const RUNTIME_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Numbers the private library copies made by every loader.
+static COPIES: AtomicUsize = AtomicUsize::new(0);
+
+/// Type of a plugin's `woflang_plugin_abi` export.
+type AbiFn = unsafe extern "C" fn() -> u32;
+
+/// Type of a plugin's `woflang_plugin_manifest` export.
+pub type ManifestFn = fn() -> PluginManifest;
+
+/// Type of a plugin's `woflang_register_plugin` export.
 ///
 /// Plugins receive a mutable reference to the interpreter and should
 /// use it to register their operations via `interpreter.register(...)`.
-pub type RegisterPluginFn = unsafe extern "C" fn(&mut Interpreter);
+pub type RegisterPluginFn = fn(&mut Interpreter);
+
+/// A loaded plugin, as listed by [`PluginLoader::plugins`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginInfo {
+    /// Plugin name from its manifest.
+    pub name: String,
+    /// Plugin version from its manifest.
+    pub version: String,
+    /// The runtime versions the plugin accepts.
+    pub core_version: String,
+    /// The library it was loaded from.
+    pub path: PathBuf,
+    /// The ops it registered, or replaced, sorted.
+    pub ops: Vec<String>,
+}
+
+struct LoadedPlugin {
+    info: PluginInfo,
+    // Dropped after the ops are removed, which unmaps the plugin's code.
+    library: Library,
+}
+
+/// A plugin library that has been opened and checked but not registered.
+struct Opened {
+    library: Library,
+    manifest: PluginManifest,
+    register: RegisterPluginFn,
+}
 
 /// Manages loaded plugin libraries.
 ///
-/// The loader keeps plugin libraries alive for the duration of the
-/// interpreter's lifetime. Dropping the loader will unload all plugins.
+/// The loader keeps plugin libraries alive until they are unloaded or
+/// the loader is dropped, so it must outlive the interpreter it loads
+/// into: declare it first.
 pub struct PluginLoader {
-    libraries: Vec<Library>,
+    plugins: Vec<LoadedPlugin>,
+    hot_reload: bool,
+    /// Libraries of unloaded plugins, kept mapped until the loader is
+    /// dropped since forked interpreters may still hold their ops.
+    retired: Vec<Library>,
+    /// Private library copies, removed when the loader is dropped.
+    copies: Vec<PathBuf>,
 }
 
 impl Default for PluginLoader {
@@ -54,57 +122,232 @@ impl Default for PluginLoader {
     }
 }
 
+/// Look up an export of `library`, naming the plugin in the error.
+///
+/// # Safety
+///
+/// `T` must be the type the plugin exported the symbol with.
+unsafe fn export<'lib, T>(library: &'lib Library, path: &Path, symbol: &str) -> Result<Symbol<'lib, T>> {
+    // SAFETY: The caller vouches for `T`.
+    unsafe { library.get(symbol.as_bytes()) }.map_err(|e| {
+        WofError::plugin(format!(
+            "{} is not a woflang plugin (no '{symbol}'; build it with declare_plugin!): {e}",
+            path.display()
+        ))
+    })
+}
+
 impl PluginLoader {
     /// Create a new empty plugin loader.
     #[must_use]
     pub const fn new() -> Self {
         Self {
-            libraries: Vec::new(),
+            plugins: Vec::new(),
+            hot_reload: false,
+            retired: Vec::new(),
+            copies: Vec::new(),
         }
     }
 
+    /// Load plugins from private copies of their libraries, so plugins
+    /// can be rebuilt and reloaded while the interpreter runs. Affects
+    /// plugins loaded afterwards.
+    pub fn enable_hot_reload(&mut self) {
+        self.hot_reload = true;
+    }
+
     /// Load a single plugin from a shared library path.
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - The library cannot be loaded
-    /// - The library does not export `register_plugin`
+    /// - The library does not export the plugin ABI symbols
+    /// - It was built for another ABI version, or requires another
+    ///   runtime version
+    /// - A plugin with the same name is already loaded
     ///
     /// # Safety
     ///
     /// This function loads and executes code from the specified path.
     /// Only load plugins from trusted sources.
-    pub fn load_plugin(&mut self, path: impl AsRef<Path>, interp: &mut Interpreter) -> Result<()> {
+    pub fn load_plugin(&mut self, path: impl AsRef<Path>, interp: &mut Interpreter) -> Result<&PluginInfo> {
         let path = path.as_ref();
+        let opened = self.open(path, self.hot_reload)?;
+        self.check(&opened.manifest, path, None)?;
+        Ok(self.install(opened, path, interp))
+    }
+
+    /// Open the library at `path`, or a fresh copy of it, and read its
+    /// exports.
+    fn open(&mut self, path: &Path, copy: bool) -> Result<Opened> {
+        let mapped = if copy { self.copy(path)? } else { path.to_path_buf() };
 
         // SAFETY: Loading arbitrary shared libraries is inherently unsafe.
         // We're trusting the plugin to maintain memory safety.
-        let library = unsafe { Library::new(path) }
+        let library = unsafe { Library::new(&mapped) }
             .map_err(|e| WofError::plugin(format!("failed to load {}: {e}", path.display())))?;
 
-        // Look up the registration function
-        // SAFETY: We're trusting the plugin exports the correct function signature
-        let register_fn: Symbol<RegisterPluginFn> = unsafe { library.get(b"register_plugin") }
-            .map_err(|e| {
-                WofError::plugin(format!(
-                    "plugin {} missing 'register_plugin': {e}",
-                    path.display()
-                ))
-            })?;
+        // SAFETY: The ABI version has a C signature in every version of
+        // the ABI, so it is safe to read whatever the plugin was built for.
+        let abi = unsafe { export::<AbiFn>(&library, path, "woflang_plugin_abi")?() };
+        if abi != PLUGIN_ABI_VERSION {
+            return Err(WofError::plugin(format!(
+                "{} was built for plugin ABI {abi}; this runtime loads ABI {PLUGIN_ABI_VERSION}",
+                path.display()
+            )));
+        }
+
+        // SAFETY: The ABI version matches, so the exports have these types.
+        let manifest = unsafe { export::<ManifestFn>(&library, path, "woflang_plugin_manifest")?() };
+        let register = unsafe { *export::<RegisterPluginFn>(&library, path, "woflang_register_plugin")? };
+        Ok(Opened { library, manifest, register })
+    }
+
+    /// Copy the library at `path` to a new file in the temp directory.
+    fn copy(&mut self, path: &Path) -> Result<PathBuf> {
+        let dir = shadow_dir();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let copy = dir.join(format!("{}-{name}", COPIES.fetch_add(1, Ordering::Relaxed)));
+        fs::create_dir_all(&dir)
+            .and_then(|()| fs::copy(path, &copy))
+            .map_err(|e| WofError::plugin(format!("failed to load {}: {e}", path.display())))?;
+        self.copies.push(copy.clone());
+        Ok(copy)
+    }
+
+    /// Register an opened plugin's ops with `interp` and add it to the
+    /// loaded plugins.
+    fn install(&mut self, opened: Opened, path: &Path, interp: &mut Interpreter) -> &PluginInfo {
+        let Opened { library, manifest, register } = opened;
+
+        // Register, then see which handlers are new.
+        let before: HashMap<String, _> = interp
+            .registry()
+            .names()
+            .filter_map(|name| Some((name.to_string(), interp.registry().get_cloned(name)?)))
+            .collect();
+        register(interp);
+        let mut ops: Vec<String> = interp
+            .registry()
+            .names()
+            .filter(|&name| {
+                let after = interp.registry().get(name);
+                !matches!((before.get(name), after), (Some(old), Some(new)) if Arc::ptr_eq(old, new))
+            })
+            .map(str::to_string)
+            .collect();
+        ops.sort_unstable();
 
-        // Call the registration function
-        // SAFETY: Trusting the plugin to not violate memory safety
-        unsafe {
-            register_fn(interp);
+        let source = format!("{} {}", manifest.name, manifest.version);
+        for doc in manifest.ops.iter().filter(|doc| ops.iter().any(|op| op == doc.name)) {
+            let meta = OpMeta::new(manifest.name, doc.stack_effect, doc.help).with_source(source.as_str());
+            interp.describe(doc.name, meta);
         }
 
-        // Keep the library alive
-        self.libraries.push(library);
+        let info = PluginInfo {
+            name: manifest.name.to_string(),
+            version: manifest.version.to_string(),
+            core_version: manifest.core_version.to_string(),
+            path: path.to_path_buf(),
+            ops,
+        };
+        self.plugins.push(LoadedPlugin { info, library });
+        &self.plugins[self.plugins.len() - 1].info
+    }
 
+    /// Check that a plugin with `manifest` can be loaded alongside the
+    /// plugins already loaded, apart from `replacing`.
+    fn check(&self, manifest: &PluginManifest, path: &Path, replacing: Option<&str>) -> Result<()> {
+        let name = manifest.name;
+        if name.is_empty() {
+            return Err(WofError::plugin(format!("{}: the plugin manifest has no name", path.display())));
+        }
+        match satisfies(manifest.core_version, RUNTIME_VERSION) {
+            Some(true) => {}
+            Some(false) => {
+                return Err(WofError::plugin(format!(
+                    "plugin '{name}' requires woflang {}; this is {RUNTIME_VERSION}",
+                    manifest.core_version
+                )))
+            }
+            None => {
+                return Err(WofError::plugin(format!(
+                    "plugin '{name}' has a malformed core version '{}'",
+                    manifest.core_version
+                )))
+            }
+        }
+        if let Some(loaded) = self.plugins.iter().find(|p| p.info.name == name && replacing != Some(name)) {
+            return Err(WofError::plugin(format!(
+                "plugin '{name}' is already loaded from {}",
+                loaded.info.path.display()
+            )));
+        }
         Ok(())
     }
 
+    /// Unload the plugin `name`, removing the ops it registered from
+    /// `interp`. An op the plugin replaced is removed, not restored. The
+    /// library stays mapped until the loader is dropped, so interpreters
+    /// forked from `interp` can still run the ops they copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no plugin of that name is loaded.
+    pub fn unload_plugin(&mut self, name: &str, interp: &mut Interpreter) -> Result<PluginInfo> {
+        let index = self
+            .plugins
+            .iter()
+            .position(|p| p.info.name == name)
+            .ok_or_else(|| WofError::plugin(format!("no plugin named '{name}' is loaded")))?;
+        let LoadedPlugin { info, library } = self.plugins.remove(index);
+        for op in &info.ops {
+            interp.registry_mut().remove(op);
+        }
+        self.retired.push(library);
+        Ok(info)
+    }
+
+    /// Load the plugin `name` again from the same path, picking up a
+    /// rebuilt library. The new library is mapped from a fresh copy and
+    /// checked before the old plugin's ops are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no plugin of that name is loaded, or if the
+    /// library at its path cannot be loaded, in which case the old plugin
+    /// stays loaded.
+    pub fn reload_plugin(&mut self, name: &str, interp: &mut Interpreter) -> Result<&PluginInfo> {
+        let path = self
+            .plugins
+            .iter()
+            .find(|p| p.info.name == name)
+            .map(|p| p.info.path.clone())
+            .ok_or_else(|| WofError::plugin(format!("no plugin named '{name}' is loaded")))?;
+        let opened = self.open(&path, true)?;
+        self.check(&opened.manifest, &path, Some(name))?;
+        self.unload_plugin(name, interp)?;
+        Ok(self.install(opened, &path, interp))
+    }
+
+    /// Reload the plugin loaded from `path`, or load it if none was.
+    ///
+    /// # Errors
+    ///
+    /// As for [`load_plugin`](Self::load_plugin) and
+    /// [`reload_plugin`](Self::reload_plugin).
+    pub fn load_or_reload(&mut self, path: impl AsRef<Path>, interp: &mut Interpreter) -> Result<&PluginInfo> {
+        let path = path.as_ref();
+        match self.plugins.iter().find(|p| p.info.path == path) {
+            Some(plugin) => {
+                let name = plugin.info.name.clone();
+                self.reload_plugin(&name, interp)
+            }
+            None => self.load_plugin(path, interp),
+        }
+    }
+
     /// Load all plugins from a directory.
     ///
     /// Loads all files matching the platform's shared library extension:
@@ -127,16 +370,9 @@ impl PluginLoader {
             return Ok(loaded);
         }
 
-        let entries = std::fs::read_dir(dir).map_err(|e| WofError::io(e.to_string()))?;
-
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if !is_shared_library(&path) {
-                continue;
-            }
-
+        for path in plugin_libraries(dir)? {
             match self.load_plugin(&path, interp) {
-                Ok(()) => {
+                Ok(_) => {
                     loaded.push(path.display().to_string());
                 }
                 Err(e) => {
@@ -155,13 +391,50 @@ impl PluginLoader {
         Ok(loaded)
     }
 
+    /// The loaded plugins, in load order.
+    pub fn plugins(&self) -> impl Iterator<Item = &PluginInfo> {
+        self.plugins.iter().map(|p| &p.info)
+    }
+
     /// Get the number of loaded plugins.
     #[must_use]
     pub fn count(&self) -> usize {
-        self.libraries.len()
+        self.plugins.len()
+    }
+}
+
+impl Drop for PluginLoader {
+    fn drop(&mut self) {
+        // Unmap the libraries before deleting the files they came from.
+        self.plugins.clear();
+        self.retired.clear();
+        for copy in &self.copies {
+            let _ = fs::remove_file(copy);
+        }
+        if !self.copies.is_empty() {
+            let _ = fs::remove_dir(shadow_dir());
+        }
     }
 }
 
+/// Where [`PluginLoader::enable_hot_reload`] copies libraries to.
+fn shadow_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("woflang-plugins-{}", std::process::id()))
+}
+
+/// The shared libraries in `dir`, sorted by path.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read.
+pub fn plugin_libraries(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+    let entries = fs::read_dir(dir).map_err(|e| WofError::io(e.to_string()))?;
+    let mut libraries: Vec<PathBuf> =
+        entries.flatten().map(|entry| entry.path()).filter(|path| is_shared_library(path)).collect();
+    libraries.sort();
+    Ok(libraries)
+}
+
 /// Check if a path is a shared library based on extension.
 fn is_shared_library(path: &Path) -> bool {
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
@@ -206,9 +479,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn lists_libraries_in_a_directory() {
+        let dir = std::env::temp_dir().join(format!("woflang-plugin-dir-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let ext = if cfg!(target_os = "windows") {
+            "dll"
+        } else if cfg!(target_os = "macos") {
+            "dylib"
+        } else {
+            "so"
+        };
+        for name in [format!("b.{ext}"), format!("a.{ext}"), "notes.txt".to_string()] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let libraries = plugin_libraries(&dir).unwrap();
+        assert_eq!(libraries, [dir.join(format!("a.{ext}")), dir.join(format!("b.{ext}"))]);
+
+        // Neither file is a plugin, and a failed load leaves nothing behind.
+        let mut loader = PluginLoader::new();
+        loader.enable_hot_reload();
+        let mut interp = Interpreter::new();
+        assert!(loader.load_or_reload(&libraries[0], &mut interp).is_err());
+        assert_eq!(loader.count(), 0);
+        drop(loader);
+        assert!(!shadow_dir().exists());
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(plugin_libraries(&dir).is_err());
+    }
+
     #[test]
     fn empty_loader() {
         let loader = PluginLoader::new();
         assert_eq!(loader.count(), 0);
     }
+
+    #[test]
+    fn rejects_incompatible_manifests() {
+        let mut loader = PluginLoader::new();
+        let path = Path::new("plugin.so");
+        let manifest = PluginManifest { name: "p", version: "1.0.0", core_version: "10", ops: &[] };
+        let major = RUNTIME_VERSION.split('.').next().unwrap();
+        assert!(loader.check(&PluginManifest { core_version: major, ..manifest }, path, None).is_ok());
+
+        let newer = format!("{}", major.parse::<u64>().unwrap() + 1);
+        let err = loader.check(&PluginManifest { core_version: newer.leak(), ..manifest }, path, None).unwrap_err();
+        assert!(err.to_string().contains("requires woflang"), "{err}");
+        assert!(loader.check(&PluginManifest { core_version: "ten", ..manifest }, path, None).is_err());
+        assert!(loader.check(&PluginManifest { name: "", ..manifest }, path, None).is_err());
+
+        let mut interp = Interpreter::new();
+        assert!(loader.load_plugin("Cargo.toml", &mut interp).is_err());
+        assert!(loader.unload_plugin("p", &mut interp).is_err());
+        assert!(loader.reload_plugin("p", &mut interp).is_err());
+    }
 }
@@ -0,0 +1,107 @@
+//! Reusable token buffers for batch execution.
+//!
+//! Executing a line allocates a handful of short-lived strings: the
+//! expanded line itself and the text of every token. When the same
+//! interpreter runs thousands of lines in a batch, that is a steady stream
+//! of small heap allocations and frees. A [`BufferPool`] keeps those
+//! buffers alive across lines and hands them back out, so after warm-up a
+//! batch runs without touching the allocator for token storage. The pool
+//! keeps at most [`MAX_POOLED`] idle buffers and frees any beyond that.
+//! Everything the pool holds is released at once by [`BufferPool::reset`]
+//! or when it is dropped.
+//!
+//! Only token text is pooled. Values pushed on the stack are allocated as
+//! usual and live as long as something refers to them.
+//!
+//! ```
+//! use woflang_runtime::{BufferPool, Interpreter};
+//!
+//! let pool = BufferPool::new();
+//! let mut interp = Interpreter::new();
+//! for i in 0..100 {
+//!     interp.exec_line_in(&pool, &format!("{i} \"item\"")).unwrap();
+//! }
+//! assert!(pool.stats().reused > 0);
+//! ```
+//!
+//! A pool is not `Sync`; parallel workers each use their own.
+
+use std::cell::{Cell, RefCell};
+
+/// Most idle buffers a pool keeps.
+const MAX_POOLED: usize = 1024;
+
+/// Allocation counters for a [`BufferPool`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Buffers that had to be freshly allocated.
+    pub allocated: usize,
+    /// Buffers served from the pool's free list.
+    pub reused: usize,
+}
+
+/// A pool of reusable text buffers scoped to one batch run.
+#[derive(Debug, Default)]
+pub struct BufferPool {
+    free: RefCell<Vec<String>>,
+    stats: Cell<PoolStats>,
+}
+
+impl BufferPool {
+    /// Create an empty pool.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a pool with `buffers` preallocated buffers of `len` bytes.
+    #[must_use]
+    pub fn with_capacity(buffers: usize, len: usize) -> Self {
+        let free = (0..buffers).map(|_| String::with_capacity(len)).collect();
+        Self { free: RefCell::new(free), stats: Cell::default() }
+    }
+
+    /// Copy `text` into a buffer from the pool.
+    pub(crate) fn alloc_str(&self, text: &str) -> String {
+        let mut stats = self.stats.get();
+        let pooled = self.free.borrow_mut().pop();
+        let buf = if let Some(mut buf) = pooled {
+            stats.reused += 1;
+            buf.clear();
+            buf.push_str(text);
+            buf
+        } else {
+            stats.allocated += 1;
+            text.to_string()
+        };
+        self.stats.set(stats);
+        buf
+    }
+
+    /// Return a buffer to the pool for reuse, or free it if the pool is
+    /// full.
+    pub(crate) fn recycle(&self, buf: String) {
+        let mut free = self.free.borrow_mut();
+        if buf.capacity() > 0 && free.len() < MAX_POOLED {
+            free.push(buf);
+        }
+    }
+
+    /// Number of idle buffers currently held.
+    #[must_use]
+    pub fn pooled(&self) -> usize {
+        self.free.borrow().len()
+    }
+
+    /// Allocation counters since creation or the last reset.
+    #[must_use]
+    pub fn stats(&self) -> PoolStats {
+        self.stats.get()
+    }
+
+    /// Free every buffer the pool holds.
+    pub fn reset(&self) {
+        *self.free.borrow_mut() = Vec::new();
+        self.stats.set(PoolStats::default());
+    }
+}
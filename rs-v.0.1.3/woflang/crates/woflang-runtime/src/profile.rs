@@ -0,0 +1,314 @@
+//! Profiling: where interpreter time goes.
+//!
+//! Two opt-in profiles can be enabled on an [`Interpreter`](crate::Interpreter).
+//!
+//! The phase profile ([`PhaseProfile`]) accumulates the time spent in each
+//! phase of executing a line:
+//!
+//! | Phase      | Covers |
+//! |------------|--------|
+//! | `tokenize` | Splitting the line into buffered tokens |
+//! | `lookup`   | Resolving a symbol to its registered op, including the special-form checks before the registry |
+//! | `convert`  | Parsing literals into values and pushing them |
+//! | `handler`  | Running op handlers |
+//!
+//! Control flow, user functions and variables are not attributed; the
+//! caller can derive them as its own wall time minus [`PhaseProfile::attributed`].
+//! An op that itself executes code (such as `eval`) counts that code in
+//! both its own phases and its handler time.
+//!
+//! The op profile ([`OpProfile`]) counts calls and wall time per
+//! registered operation and per user function:
+//!
+//! ```
+//! use woflang_runtime::Interpreter;
+//! use woflang_core::{InterpreterContext, WofValue};
+//!
+//! let mut interp = Interpreter::new();
+//! interp.register("one", |interp| {
+//!     interp.push(WofValue::integer(1));
+//!     Ok(())
+//! });
+//! interp.enable_op_profile();
+//! interp.exec_line("⊕ two ⺆ one one ⺘ two two").unwrap();
+//!
+//! let profile = interp.take_op_profile().unwrap();
+//! assert_eq!(profile.op("one").unwrap().calls, 4);
+//! assert_eq!(profile.function("two").unwrap().calls, 2);
+//! ```
+//!
+//! A function's time runs from its call to its return, so it includes the
+//! ops and functions it calls; recursive calls are counted but their time
+//! is charged once, to the outermost call. Likewise an op's time includes
+//! any code it runs.
+
+use core::fmt;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// A phase of the dispatch path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Tokenizing source text.
+    Tokenize,
+    /// Finding the op for a symbol.
+    Lookup,
+    /// Converting literals to values.
+    Convert,
+    /// Running op handlers.
+    Handler,
+}
+
+/// Accumulated time per [`Phase`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseProfile {
+    tokenize: Duration,
+    lookup: Duration,
+    convert: Duration,
+    handler: Duration,
+    ops: u64,
+}
+
+impl PhaseProfile {
+    /// Create an empty profile.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `elapsed` to `phase`. Each handler timing counts one op.
+    pub fn add(&mut self, phase: Phase, elapsed: Duration) {
+        match phase {
+            Phase::Tokenize => self.tokenize += elapsed,
+            Phase::Lookup => self.lookup += elapsed,
+            Phase::Convert => self.convert += elapsed,
+            Phase::Handler => {
+                self.handler += elapsed;
+                self.ops += 1;
+            }
+        }
+    }
+
+    /// Time spent in `phase`.
+    #[must_use]
+    pub const fn get(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Tokenize => self.tokenize,
+            Phase::Lookup => self.lookup,
+            Phase::Convert => self.convert,
+            Phase::Handler => self.handler,
+        }
+    }
+
+    /// Number of op handlers that ran.
+    #[must_use]
+    pub const fn ops(&self) -> u64 {
+        self.ops
+    }
+
+    /// Total time attributed to any phase.
+    #[must_use]
+    pub fn attributed(&self) -> Duration {
+        self.tokenize + self.lookup + self.convert + self.handler
+    }
+}
+
+impl fmt::Display for PhaseProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let us = |d: Duration| d.as_secs_f64() * 1e6;
+        write!(
+            f,
+            "tokenize {:.2} µs, lookup {:.2} µs, convert {:.2} µs, handler {:.2} µs ({} ops)",
+            us(self.tokenize),
+            us(self.lookup),
+            us(self.convert),
+            us(self.handler),
+            self.ops
+        )
+    }
+}
+
+/// Calls and cumulative wall time of one op or function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CallStats {
+    /// Times it was called.
+    pub calls: u64,
+    /// Wall time spent in it.
+    pub total: Duration,
+}
+
+impl CallStats {
+    /// Mean time per call.
+    #[must_use]
+    pub fn mean(&self) -> Duration {
+        u32::try_from(self.calls).ok().filter(|&n| n > 0).map_or(Duration::ZERO, |n| self.total / n)
+    }
+}
+
+/// Call counts and wall time per registered operation and per user
+/// function.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpProfile {
+    ops: HashMap<String, CallStats>,
+    functions: HashMap<String, CallStats>,
+}
+
+impl OpProfile {
+    /// Create an empty profile.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a call of op `name` that took `elapsed`.
+    pub fn add_op(&mut self, name: &str, elapsed: Duration) {
+        let stats = self.ops.entry(name.to_string()).or_default();
+        stats.calls += 1;
+        stats.total += elapsed;
+    }
+
+    /// Count a call of function `name`; its time is added on return.
+    pub fn count_call(&mut self, name: &str) {
+        self.functions.entry(name.to_string()).or_default().calls += 1;
+    }
+
+    /// Add `elapsed` to function `name`.
+    pub fn add_function_time(&mut self, name: &str, elapsed: Duration) {
+        self.functions.entry(name.to_string()).or_default().total += elapsed;
+    }
+
+    /// The stats of op `name`, if it ran.
+    #[must_use]
+    pub fn op(&self, name: &str) -> Option<&CallStats> {
+        self.ops.get(name)
+    }
+
+    /// The stats of function `name`, if it was called.
+    #[must_use]
+    pub fn function(&self, name: &str) -> Option<&CallStats> {
+        self.functions.get(name)
+    }
+
+    /// Every op that ran, most total time first.
+    #[must_use]
+    pub fn ops(&self) -> Vec<(&str, CallStats)> {
+        by_total(&self.ops)
+    }
+
+    /// Every function called, most total time first.
+    #[must_use]
+    pub fn functions(&self) -> Vec<(&str, CallStats)> {
+        by_total(&self.functions)
+    }
+
+    /// Whether nothing has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty() && self.functions.is_empty()
+    }
+}
+
+/// Entries sorted by total time, then name.
+fn by_total(map: &HashMap<String, CallStats>) -> Vec<(&str, CallStats)> {
+    let mut entries: Vec<(&str, CallStats)> = map.iter().map(|(name, stats)| (name.as_str(), *stats)).collect();
+    entries.sort_by(|a, b| b.1.total.cmp(&a.1.total).then_with(|| a.0.cmp(b.0)));
+    entries
+}
+
+impl fmt::Display for OpProfile {
+    /// A table per section, most total time first.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "Nothing profiled yet");
+        }
+        for (title, entries) in [("Operations", self.ops()), ("Functions", self.functions())] {
+            if entries.is_empty() {
+                continue;
+            }
+            writeln!(f, "{title}:")?;
+            writeln!(f, "  {:>10}  {:>12}  {:>10}  name", "calls", "total ms", "mean µs")?;
+            for (name, stats) in entries {
+                writeln!(
+                    f,
+                    "  {:>10}  {:>12.3}  {:>10.2}  {name}",
+                    stats.calls,
+                    stats.total.as_secs_f64() * 1e3,
+                    stats.mean().as_secs_f64() * 1e6,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interpreter;
+    use woflang_core::{InterpreterContext, WofValue};
+
+    #[test]
+    fn attributes_time_to_phases() {
+        let mut interp = Interpreter::new();
+        interp.register("spin", |interp| {
+            std::thread::sleep(Duration::from_millis(2));
+            interp.push(WofValue::integer(1));
+            Ok(())
+        });
+        interp.exec_line("1 spin").unwrap();
+        assert!(interp.phase_profile().is_none());
+
+        interp.enable_phase_profile();
+        interp.exec_line(r#"1 2.5 "s" spin spin"#).unwrap();
+        let profile = interp.take_phase_profile().unwrap();
+        assert_eq!(profile.ops(), 2);
+        assert!(profile.get(Phase::Handler) >= Duration::from_millis(4));
+        assert!(profile.get(Phase::Tokenize) > Duration::ZERO);
+        assert!(profile.get(Phase::Convert) > Duration::ZERO);
+        assert!(profile.attributed() >= profile.get(Phase::Handler));
+        assert!(interp.phase_profile().is_none());
+    }
+
+    #[test]
+    fn counts_and_times_ops_and_functions() {
+        let mut interp = Interpreter::new();
+        interp.register("spin", |_| {
+            std::thread::sleep(Duration::from_millis(2));
+            Ok(())
+        });
+        interp.register("-", |interp| {
+            let n = interp.stack_mut().pop_integer()?;
+            interp.push(WofValue::integer(n - 1));
+            Ok(())
+        });
+        interp.register("dup", |interp| {
+            let top = interp.stack().peek()?.clone();
+            interp.push(top);
+            Ok(())
+        });
+        interp.exec_line("⊕ down ⺆ spin dup 若 ⺆ - down ⺘ ⺘").unwrap();
+        interp.exec_line("spin").unwrap();
+        assert!(interp.op_profile().is_none());
+
+        interp.enable_op_profile();
+        interp.exec_line("2 down").unwrap();
+        let profile = interp.op_profile().unwrap();
+        assert_eq!(profile.op("spin").unwrap().calls, 3);
+        assert!(profile.op("spin").unwrap().total >= Duration::from_millis(6));
+        assert_eq!(profile.op("-").unwrap().calls, 2);
+
+        // Three calls, each recursing into the next: timed once, outermost.
+        let down = *profile.function("down").unwrap();
+        assert_eq!(down.calls, 3);
+        assert!(down.total >= Duration::from_millis(6));
+        assert!(down.total < Duration::from_millis(6) * 3);
+        assert_eq!(profile.ops()[0].0, "spin");
+
+        let report = profile.to_string();
+        assert!(report.starts_with("Operations:\n"));
+        assert!(report.contains("Functions:\n"));
+        assert!(report.lines().any(|l| l.trim_start().starts_with("3 ") && l.ends_with(" down")));
+        assert!(interp.take_op_profile().is_some());
+        assert_eq!(OpProfile::new().to_string(), "Nothing profiled yet\n");
+    }
+}
@@ -0,0 +1,71 @@
+//! Progress reporting for long-running ops.
+//!
+//! Ops call [`Interpreter::progress`](crate::Interpreter::progress) as
+//! work proceeds. The interpreter forwards each report to its
+//! [`ProgressSink`], if one is installed, and otherwise ignores it. The
+//! CLI draws a terminal progress bar; an embedder can collect events:
+//!
+//! ```
+//! use std::sync::mpsc;
+//! use woflang_runtime::{Interpreter, Progress};
+//!
+//! let (tx, rx) = mpsc::channel();
+//! let mut interp = Interpreter::new();
+//! interp.set_progress_sink(move |p: &Progress| {
+//!     let _ = tx.send(p.clone());
+//! });
+//! interp.register("work", |ctx| {
+//!     for step in 1..=3 {
+//!         ctx.progress(step, 3, "steps");
+//!     }
+//!     Ok(())
+//! });
+//!
+//! interp.exec_line("work").unwrap();
+//! let events: Vec<Progress> = rx.try_iter().collect();
+//! assert_eq!(events.len(), 3);
+//! assert!(events[2].is_finished());
+//! ```
+
+/// One progress report from a running op.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    /// Units of work completed so far.
+    pub done: usize,
+    /// Total units of work, or 0 if unknown.
+    pub total: usize,
+    /// What is being counted, such as `"self-play games"`.
+    pub message: String,
+}
+
+impl Progress {
+    /// Completed fraction in `0.0..=1.0`, or 0 when the total is unknown.
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.done as f64 / self.total as f64).min(1.0)
+        }
+    }
+
+    /// Whether this report marks the end of the work.
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.total > 0 && self.done >= self.total
+    }
+}
+
+/// Receives the progress reports of an interpreter.
+///
+/// Implemented for closures taking `&Progress`.
+pub trait ProgressSink: Send + Sync {
+    /// Called for every report, in order.
+    fn report(&mut self, progress: &Progress);
+}
+
+impl<F: FnMut(&Progress) + Send + Sync> ProgressSink for F {
+    fn report(&mut self, progress: &Progress) {
+        self(progress);
+    }
+}
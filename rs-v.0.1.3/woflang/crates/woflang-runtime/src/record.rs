@@ -0,0 +1,114 @@
+//! Record definitions: `record "Point" "x" "y"`.
+//!
+//! Defining a record registers ops for building and using it:
+//!
+//! | Operation   | Stack Effect         | Description |
+//! |-------------|----------------------|-------------|
+//! | `Point_new` | ( x y -- point )     | Build from field values in order |
+//! | `Point?`    | ( v -- bool )        | Is `v` a `Point`? |
+//! | `.x`        | ( rec -- x )         | Read field `x` of any record |
+//! | `with_x`    | ( rec v -- rec' )    | Copy with field `x` replaced |
+//!
+//! Accessors are shared between record types, so `.x` works on every
+//! record with an `x` field.
+
+use std::sync::Arc;
+
+use woflang_core::{InterpreterContext, RecordType, Result, WofError, WofRecord, WofValue};
+
+use crate::Interpreter;
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.chars().any(|c| c.is_whitespace() || c == '"')
+}
+
+/// Register the ops for a record type.
+pub(crate) fn define(interp: &mut Interpreter, name: &str, fields: &[String]) -> Result<()> {
+    if !valid_name(name) {
+        return Err(WofError::InvalidArgument(format!("record: invalid type name '{name}'")));
+    }
+    for (i, field) in fields.iter().enumerate() {
+        if !valid_name(field) {
+            return Err(WofError::InvalidArgument(format!(
+                "record {name}: invalid field name '{field}'"
+            )));
+        }
+        if fields[..i].contains(field) {
+            return Err(WofError::InvalidArgument(format!(
+                "record {name}: duplicate field '{field}'"
+            )));
+        }
+    }
+
+    let ty = RecordType::new(name, fields);
+
+    let ctor = Arc::clone(&ty);
+    interp.register(format!("{name}_new"), move |interp| {
+        let mut values = interp.stack_mut().pop_n(ctor.arity())?;
+        values.reverse();
+        let record = WofRecord::new(Arc::clone(&ctor), values)
+            .ok_or_else(|| WofError::Runtime(format!("{}_new: wrong field count", ctor.name())))?;
+        interp.push(WofValue::record(record));
+        Ok(())
+    });
+
+    let test = Arc::clone(&ty);
+    interp.register(format!("{name}?"), move |interp| {
+        let value = interp.stack_mut().pop()?;
+        let is = value.as_record().is_ok_and(|r| **r.record_type() == *test);
+        interp.push(WofValue::boolean(is));
+        Ok(())
+    });
+
+    for field in fields {
+        let get = field.clone();
+        interp.register(format!(".{field}"), move |interp| {
+            let value = interp.stack_mut().pop()?;
+            let record = value.as_record()?;
+            let field = record.get(&get).ok_or_else(|| no_field(record, &get))?.clone();
+            interp.push(field);
+            Ok(())
+        });
+
+        let set = field.clone();
+        interp.register(format!("with_{field}"), move |interp| {
+            let new = interp.stack_mut().pop()?;
+            let value = interp.stack_mut().pop()?;
+            let record = value.as_record()?;
+            let updated = record.with(&set, new).ok_or_else(|| no_field(record, &set))?;
+            interp.push(WofValue::record(updated));
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+fn no_field(record: &WofRecord, field: &str) -> WofError {
+    WofError::Runtime(format!("{} has no field '{field}'", record.record_type().name()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interpreter;
+    use woflang_core::{InterpreterContext, WofValue};
+
+    #[test]
+    fn defines_constructor_accessors_and_updates() {
+        let mut interp = Interpreter::new();
+        interp.exec_line(r#"record "Point" "x" "y""#).unwrap();
+        interp.exec_line("3 4 Point_new").unwrap();
+        assert_eq!(interp.stack().peek().unwrap().to_string(), "Point{x: 3, y: 4}");
+
+        interp.exec_line("10 with_x .x").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::integer(10));
+
+        interp.exec_line(r#"record "Size" "w" "y" 1 2 Size_new .y 5 6 Point_new Point?"#).unwrap();
+        assert!(interp.stack_mut().pop_bool().unwrap());
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::integer(2));
+
+        assert!(interp.exec_line("1 2 Size_new .x").is_err());
+        assert!(interp.exec_line("7 .x").is_err());
+        assert!(interp.exec_line(r#"record "Bad" "a" "a""#).is_err());
+        assert!(interp.exec_line("record").is_err());
+    }
+}
@@ -9,8 +9,22 @@
 //! Operations are stored as boxed trait objects to allow heterogeneous
 //! handler types while maintaining a uniform dispatch interface. The
 //! registry uses a `HashMap` for O(1) lookup during interpretation.
+//!
+//! Each name owns a slot, identified by an [`OpId`], that survives
+//! re-registration. The interpreter binds symbols to slots when it
+//! compiles a line, so dispatching a bound symbol is a vector index
+//! instead of a name lookup.
+//!
+//! Alongside the handlers, each name may carry an [`OpMeta`] describing
+//! it for humans: category, stack effect, help text and an example.
+//! Metadata is optional and never consulted during dispatch.
+//!
+//! Names bound to one handler (`⊞`, `a+`, `analog_add`) form an alias
+//! group, recorded by [`Registry::register_group`] or [`Registry::group`],
+//! so tools can list every spelling of an op and map glyphs to ASCII.
 
 use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::sync::Arc;
 use woflang_core::{InterpreterContext, Result};
 
@@ -27,13 +41,82 @@ pub type OpFn<Ctx> = fn(&mut Ctx) -> Result<()>;
 /// which is essential for avoiding borrow conflicts during dispatch.
 pub type BoxedOp<Ctx> = Arc<dyn Fn(&mut Ctx) -> Result<()> + Send + Sync>;
 
+/// The slot of a registered operation.
+///
+/// A name keeps its slot when it is registered again; a removed name's
+/// slot is left empty and never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OpId(u32);
+
+/// A registered handler and the name it is registered under.
+struct Slot<Ctx: InterpreterContext> {
+    name: String,
+    handler: BoxedOp<Ctx>,
+}
+
+// Not derived, for the same reason as `Registry`'s `Clone`.
+impl<Ctx: InterpreterContext> Clone for Slot<Ctx> {
+    fn clone(&self) -> Self {
+        Self { name: self.name.clone(), handler: Arc::clone(&self.handler) }
+    }
+}
+
+/// Human-facing description of a registered operation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpMeta {
+    /// Grouping used by reference docs, such as `"arithmetic"`.
+    pub category: String,
+    /// Stack effect in `( before -- after )` notation.
+    pub stack_effect: String,
+    /// One-line description.
+    pub help: String,
+    /// A runnable snippet that exercises the op.
+    pub example: Option<String>,
+    /// The crate or plugin that registered the op.
+    pub source: Option<String>,
+}
+
+impl OpMeta {
+    /// Create metadata with a category, stack effect and help text.
+    #[must_use]
+    pub fn new(
+        category: impl Into<String>,
+        stack_effect: impl Into<String>,
+        help: impl Into<String>,
+    ) -> Self {
+        Self {
+            category: category.into(),
+            stack_effect: stack_effect.into(),
+            help: help.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the runnable example.
+    #[must_use]
+    pub fn with_example(mut self, example: impl Into<String>) -> Self {
+        self.example = Some(example.into());
+        self
+    }
+
+    /// Set the registering crate or plugin.
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+}
+
 /// Operation registry mapping names to handlers.
 ///
 /// The registry is generic over the interpreter context type, allowing
 /// reuse with different interpreter implementations.
 pub struct Registry<Ctx: InterpreterContext> {
-    ops: HashMap<String, BoxedOp<Ctx>>,
+    ops: HashMap<String, OpId>,
+    slots: Vec<Option<Slot<Ctx>>>,
     aliases: HashMap<String, String>,
+    meta: HashMap<String, OpMeta>,
+    groups: HashMap<String, Arc<[String]>>,
 }
 
 impl<Ctx: InterpreterContext> Default for Registry<Ctx> {
@@ -42,6 +125,20 @@ impl<Ctx: InterpreterContext> Default for Registry<Ctx> {
     }
 }
 
+// Not derived: that would require `Ctx: Clone`, but only the handlers
+// (shared `Arc`s) are cloned.
+impl<Ctx: InterpreterContext> Clone for Registry<Ctx> {
+    fn clone(&self) -> Self {
+        Self {
+            ops: self.ops.clone(),
+            slots: self.slots.clone(),
+            aliases: self.aliases.clone(),
+            meta: self.meta.clone(),
+            groups: self.groups.clone(),
+        }
+    }
+}
+
 impl<Ctx: InterpreterContext> Registry<Ctx> {
     /// Create a new empty registry.
     #[inline]
@@ -49,7 +146,10 @@ impl<Ctx: InterpreterContext> Registry<Ctx> {
     pub fn new() -> Self {
         Self {
             ops: HashMap::new(),
+            slots: Vec::new(),
             aliases: HashMap::new(),
+            meta: HashMap::new(),
+            groups: HashMap::new(),
         }
     }
 
@@ -59,13 +159,18 @@ impl<Ctx: InterpreterContext> Registry<Ctx> {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             ops: HashMap::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
             aliases: HashMap::new(),
+            meta: HashMap::new(),
+            groups: HashMap::new(),
         }
     }
 
     /// Register an operation handler.
     ///
-    /// If an operation with the same name already exists, it is replaced.
+    /// If an operation with the same name already exists, it is replaced;
+    /// its metadata and alias group membership are dropped, since they
+    /// described the old handler.
     ///
     /// # Examples
     ///
@@ -80,7 +185,32 @@ impl<Ctx: InterpreterContext> Registry<Ctx> {
     where
         F: Fn(&mut Ctx) -> Result<()> + Send + Sync + 'static,
     {
-        self.ops.insert(name.into(), Arc::new(handler));
+        let name = name.into();
+        self.meta.remove(&name);
+        self.ungroup(&name);
+        self.insert(name, Arc::new(handler));
+    }
+
+    /// Register an operation handler together with its metadata.
+    ///
+    /// The same as [`register`](Self::register) followed by
+    /// [`describe`](Self::describe), so the description is written where
+    /// the op is:
+    ///
+    /// ```ignore
+    /// registry.register_with("double", OpMeta::new("math", "( a -- 2a )", "Double"), |interp| {
+    ///     let val = interp.stack_mut().pop_numeric()?;
+    ///     interp.stack_mut().push(WofValue::double(val * 2.0));
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn register_with<F>(&mut self, name: impl Into<String>, meta: OpMeta, handler: F)
+    where
+        F: Fn(&mut Ctx) -> Result<()> + Send + Sync + 'static,
+    {
+        let name = name.into();
+        self.register(name.clone(), handler);
+        self.describe(name, meta);
     }
 
     /// Register an operation with a function pointer (zero-overhead).
@@ -88,7 +218,87 @@ impl<Ctx: InterpreterContext> Registry<Ctx> {
     where
         Ctx: 'static,
     {
-        self.ops.insert(name.into(), Arc::new(handler));
+        let name = name.into();
+        self.meta.remove(&name);
+        self.ungroup(&name);
+        self.insert(name, Arc::new(handler));
+    }
+
+    /// Put `handler` in `name`'s slot, allocating one for a new name.
+    fn insert(&mut self, name: String, handler: BoxedOp<Ctx>) {
+        let slot = Slot { name: name.clone(), handler };
+        if let Some(&OpId(id)) = self.ops.get(&name) {
+            if let Some(existing) = self.slots.get_mut(id as usize) {
+                *existing = Some(slot);
+                return;
+            }
+        }
+        let id = OpId(u32::try_from(self.slots.len()).unwrap_or(u32::MAX));
+        self.slots.push(Some(slot));
+        self.ops.insert(name, id);
+    }
+
+    /// Register one handler under several names and record them as an
+    /// alias group, first name canonical.
+    pub fn register_group<F>(&mut self, names: &[&str], handler: F)
+    where
+        F: Fn(&mut Ctx) -> Result<()> + Send + Sync + 'static,
+    {
+        let handler: BoxedOp<Ctx> = Arc::new(handler);
+        for &name in names {
+            self.meta.remove(name);
+            self.insert(name.to_string(), Arc::clone(&handler));
+        }
+        self.group(names);
+    }
+
+    /// Record already registered `names` as bound to the same handler.
+    ///
+    /// Each name leaves whatever group it was in before.
+    pub fn group(&mut self, names: &[&str]) {
+        for &name in names {
+            self.ungroup(name);
+        }
+        let group: Arc<[String]> = names.iter().map(|&n| n.to_string()).collect();
+        for &name in names {
+            self.groups.insert(name.to_string(), Arc::clone(&group));
+        }
+    }
+
+    /// Take `name` out of its alias group, leaving the rest grouped.
+    fn ungroup(&mut self, name: &str) {
+        let Some(group) = self.groups.remove(name) else {
+            return;
+        };
+        let rest: Arc<[String]> = group.iter().filter(|n| *n != name).cloned().collect();
+        for other in rest.iter() {
+            self.groups.insert(other.clone(), Arc::clone(&rest));
+        }
+    }
+
+    /// Every name bound to the same handler as `name`, including `name`:
+    /// its alias group in order, then any [`alias`](Self::alias) names
+    /// pointing into the group. Empty if `name` is not registered.
+    #[must_use]
+    pub fn aliases_of(&self, name: &str) -> Vec<&str> {
+        let resolved = self.aliases.get(name).map_or(name, String::as_str);
+        let Some((resolved, _)) = self.ops.get_key_value(resolved) else {
+            return Vec::new();
+        };
+        let resolved = resolved.as_str();
+        let mut names: Vec<&str> = self
+            .groups
+            .get(resolved)
+            .map_or_else(|| vec![resolved], |group| group.iter().map(String::as_str).collect());
+        let mut pointing: Vec<&str> = self
+            .aliases
+            .iter()
+            .filter(|(_, target)| names.contains(&target.as_str()))
+            .map(|(alias, _)| alias.as_str())
+            .collect();
+        pointing.sort_unstable();
+        names.extend(pointing);
+        names
     }
 
     /// Register an alias for an existing operation.
@@ -98,6 +308,68 @@ impl<Ctx: InterpreterContext> Registry<Ctx> {
         self.aliases.insert(alias.into(), target.into());
     }
 
+    /// Attach metadata to an operation name.
+    ///
+    /// The name need not be registered yet; metadata for a name that is
+    /// never registered is ignored by [`described`](Self::described).
+    pub fn describe(&mut self, name: impl Into<String>, meta: OpMeta) {
+        self.meta.insert(name.into(), meta);
+    }
+
+    /// Look up the metadata for an operation, resolving aliases.
+    #[must_use]
+    pub fn meta(&self, name: &str) -> Option<&OpMeta> {
+        self.meta
+            .get(name)
+            .or_else(|| self.aliases.get(name).and_then(|target| self.meta.get(target)))
+    }
+
+    /// A short help card for the operation `name`: its other names,
+    /// category and source, stack effect, description and example.
+    ///
+    /// ```text
+    /// sqrt  (also √)  [math, woflang-ops]
+    ///   ( a -- b )  Square root
+    ///   Example: 16 sqrt
+    /// ```
+    ///
+    /// Returns `None` if `name` is not registered.
+    #[must_use]
+    pub fn help_card(&self, name: &str) -> Option<String> {
+        if !self.contains(name) {
+            return None;
+        }
+        let Some(meta) = self.meta(name) else {
+            return Some(format!("{name}\n  No description available.\n"));
+        };
+
+        let mut out = name.to_string();
+        let others: Vec<&str> = self.aliases_of(name).into_iter().filter(|&n| n != name).collect();
+        if !others.is_empty() {
+            let _ = write!(out, "  (also {})", others.join(", "));
+        }
+        let _ = write!(out, "  [{}", meta.category);
+        if let Some(source) = &meta.source {
+            let _ = write!(out, ", {source}");
+        }
+        out.push_str("]\n");
+        if meta.stack_effect.is_empty() && meta.help.is_empty() {
+            out.push_str("  No description available.\n");
+        } else {
+            let _ = writeln!(out, "  {}  {}", meta.stack_effect, meta.help);
+        }
+        if let Some(example) = &meta.example {
+            let _ = writeln!(out, "  Example: {example}");
+        }
+        Some(out)
+    }
+
+    /// Iterate over registered operations that have metadata, in
+    /// registration order.
+    pub fn described(&self) -> impl Iterator<Item = (&str, &OpMeta)> {
+        self.names().filter_map(|name| Some((name, self.meta.get(name)?)))
+    }
+
     /// Look up an operation by name.
     ///
     /// Returns `None` if the operation is not registered.
@@ -105,7 +377,29 @@ impl<Ctx: InterpreterContext> Registry<Ctx> {
     pub fn get(&self, name: &str) -> Option<&BoxedOp<Ctx>> {
         // Check for alias first
         let resolved = self.aliases.get(name).map_or(name, String::as_str);
-        self.ops.get(resolved)
+        self.ops.get(resolved).and_then(|&id| self.bound(id, resolved))
+    }
+
+    /// The slot of the op registered directly under `name`.
+    ///
+    /// Aliases are not followed, since they may be retargeted after a
+    /// symbol is bound.
+    #[must_use]
+    pub fn id_of(&self, name: &str) -> Option<OpId> {
+        self.ops.get(name).copied()
+    }
+
+    /// The handler in slot `id`, if it is still registered under `name`.
+    ///
+    /// Checking the name makes a stale binding (the op was removed, or
+    /// the id came from a fork whose registry has since diverged) miss
+    /// instead of calling the wrong handler.
+    #[must_use]
+    pub fn bound(&self, id: OpId, name: &str) -> Option<&BoxedOp<Ctx>> {
+        match self.slots.get(id.0 as usize) {
+            Some(Some(slot)) if slot.name == name => Some(&slot.handler),
+            _ => None,
+        }
     }
 
     /// Look up an operation by name and clone it.
@@ -136,14 +430,41 @@ impl<Ctx: InterpreterContext> Registry<Ctx> {
         self.ops.is_empty()
     }
 
-    /// Iterate over all registered operation names.
+    /// Iterate over all registered operation names, in the order they
+    /// were first registered.
     pub fn names(&self) -> impl Iterator<Item = &str> {
-        self.ops.keys().map(String::as_str)
+        self.slots.iter().flatten().map(|slot| slot.name.as_str())
+    }
+
+    /// Every callable name starting with `prefix`, registered and
+    /// [`alias`](Self::alias) names alike, sorted: what a REPL offers
+    /// for tab completion. Aliases of removed ops are left out.
+    #[must_use]
+    pub fn completions(&self, prefix: &str) -> Vec<&str> {
+        let aliases = self.aliases.keys().filter(|alias| self.contains(alias));
+        let mut names: Vec<&str> = self
+            .ops
+            .keys()
+            .chain(aliases)
+            .map(String::as_str)
+            .filter(|name| name.starts_with(prefix))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
     }
 
     /// Remove an operation from the registry.
     pub fn remove(&mut self, name: &str) -> bool {
-        self.ops.remove(name).is_some()
+        self.meta.remove(name);
+        self.ungroup(name);
+        let Some(OpId(id)) = self.ops.remove(name) else {
+            return false;
+        };
+        if let Some(slot) = self.slots.get_mut(id as usize) {
+            *slot = None;
+        }
+        true
     }
 
     /// Merge another registry into this one.
@@ -151,8 +472,13 @@ impl<Ctx: InterpreterContext> Registry<Ctx> {
     /// Operations from `other` will overwrite existing operations
     /// with the same name.
     pub fn merge(&mut self, other: Self) {
-        self.ops.extend(other.ops);
+        for slot in other.slots.into_iter().flatten() {
+            self.ungroup(&slot.name);
+            self.insert(slot.name, slot.handler);
+        }
         self.aliases.extend(other.aliases);
+        self.meta.extend(other.meta);
+        self.groups.extend(other.groups);
     }
 }
 
@@ -161,7 +487,7 @@ impl<Ctx: InterpreterContext> std::fmt::Debug for Registry<Ctx> {
         f.debug_struct("Registry")
             .field("ops_count", &self.ops.len())
             .field("aliases_count", &self.aliases.len())
-            .field("ops", &self.ops.keys().collect::<Vec<_>>())
+            .field("ops", &self.names().collect::<Vec<_>>())
             .finish()
     }
 }
@@ -235,4 +561,121 @@ mod tests {
         assert!(registry.contains("dup"));
         assert!(registry.get("dup").is_some());
     }
+
+    #[test]
+    fn metadata_lookup() {
+        let mut registry: Registry<TestCtx> = Registry::new();
+        registry.register("duplicate", |ctx| ctx.stack_mut().dup());
+        registry.alias("dup", "duplicate");
+        registry.describe(
+            "duplicate",
+            OpMeta::new("stack", "( a -- a a )", "Duplicate top").with_example("1 dup"),
+        );
+        registry.describe("missing", OpMeta::new("stack", "( -- )", "Not registered"));
+
+        assert_eq!(registry.meta("dup").unwrap().help, "Duplicate top");
+        assert_eq!(registry.described().count(), 1);
+        registry.register("duplicate", |ctx| ctx.stack_mut().dup());
+        assert!(registry.meta("duplicate").is_none());
+        assert!(registry.remove("duplicate"));
+        assert!(registry.meta("duplicate").is_none());
+    }
+
+    #[test]
+    fn register_with_metadata_and_help_cards() {
+        let mut registry: Registry<TestCtx> = Registry::new();
+        registry.register_with(
+            "twice",
+            OpMeta::new("math", "( a -- 2a )", "Double").with_example("3 twice").with_source("test"),
+            |_| Ok(()),
+        );
+        registry.alias("x2", "twice");
+        registry.register("bare", |_| Ok(()));
+
+        assert_eq!(registry.meta("twice").unwrap().help, "Double");
+        assert_eq!(
+            registry.help_card("twice").unwrap(),
+            "twice  (also x2)  [math, test]\n  ( a -- 2a )  Double\n  Example: 3 twice\n"
+        );
+        assert!(registry.help_card("x2").unwrap().starts_with("x2  (also twice)  [math, test]\n"));
+        assert_eq!(registry.help_card("bare").unwrap(), "bare\n  No description available.\n");
+        assert!(registry.help_card("nope").is_none());
+    }
+
+    #[test]
+    fn slots_survive_reregistration() {
+        let mut registry: Registry<TestCtx> = Registry::new();
+        registry.register("one", |ctx| {
+            ctx.push(WofValue::integer(1));
+            Ok(())
+        });
+        registry.alias("uno", "one");
+        let id = registry.id_of("one").unwrap();
+        assert!(registry.id_of("uno").is_none());
+        assert!(registry.bound(id, "one").is_some());
+        assert!(registry.bound(id, "two").is_none());
+
+        registry.register("one", |ctx| {
+            ctx.push(WofValue::integer(11));
+            Ok(())
+        });
+        assert_eq!(registry.id_of("one"), Some(id));
+        let mut ctx = TestCtx { stack: WofStack::new() };
+        registry.bound(id, "one").unwrap()(&mut ctx).unwrap();
+        assert_eq!(ctx.stack.pop_integer().unwrap(), 11);
+
+        assert!(registry.remove("one"));
+        assert!(registry.bound(id, "one").is_none());
+        registry.register("one", |_| Ok(()));
+        assert_ne!(registry.id_of("one"), Some(id));
+    }
+
+    #[test]
+    fn listings_follow_registration_order() {
+        let mut registry: Registry<TestCtx> = Registry::new();
+        for name in ["zeta", "alpha", "mu", "beta"] {
+            registry.register(name, |_| Ok(()));
+        }
+        registry.describe("mu", OpMeta::new("test", "( -- )", "Mu"));
+        registry.describe("zeta", OpMeta::new("test", "( -- )", "Zeta"));
+        registry.register("alpha", |_| Ok(()));
+        assert!(registry.remove("beta"));
+        registry.register("beta", |_| Ok(()));
+
+        assert_eq!(registry.names().collect::<Vec<_>>(), ["zeta", "alpha", "mu", "beta"]);
+        let described: Vec<&str> = registry.described().map(|(name, _)| name).collect();
+        assert_eq!(described, ["zeta", "mu"]);
+    }
+
+    #[test]
+    fn completions_cover_aliases() {
+        let mut registry: Registry<TestCtx> = Registry::new();
+        for name in ["drop", "dup", "swap"] {
+            registry.register(name, |_| Ok(()));
+        }
+        registry.alias("dupe", "dup");
+        registry.alias("dump", "missing");
+
+        assert_eq!(registry.completions("du"), ["dup", "dupe"]);
+        assert_eq!(registry.completions("").len(), 4);
+        assert!(registry.completions("x").is_empty());
+    }
+
+    #[test]
+    fn alias_groups() {
+        let mut registry: Registry<TestCtx> = Registry::new();
+        registry.register_group(&["⊞", "a+", "analog_add"], |_| Ok(()));
+        registry.alias("plus", "a+");
+
+        assert_eq!(registry.aliases_of("a+"), ["⊞", "a+", "analog_add", "plus"]);
+        assert_eq!(registry.aliases_of("plus"), registry.aliases_of("⊞"));
+        assert!(registry.aliases_of("missing").is_empty());
+
+        registry.register("a+", |_| Ok(()));
+        assert_eq!(registry.aliases_of("⊞"), ["⊞", "analog_add"]);
+        assert_eq!(registry.aliases_of("a+"), ["a+", "plus"]);
+
+        registry.register("solo", |_| Ok(()));
+        assert_eq!(registry.aliases_of("solo"), ["solo"]);
+    }
 }
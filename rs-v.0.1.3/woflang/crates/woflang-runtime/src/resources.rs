@@ -0,0 +1,179 @@
+//! Scoped resources: `with_file "path" ⺆ ... ⺘`.
+//!
+//! A resource is something a script opens and must release, such as a
+//! file handle or a scratch graph. Whoever owns the resource defines its
+//! kind with an opener and a closer, and `with_<kind>` then guarantees
+//! the close:
+//!
+//! ```
+//! use woflang_runtime::Interpreter;
+//! use woflang_core::{InterpreterContext, WofValue};
+//!
+//! #[derive(Default)]
+//! struct Open(i64);
+//!
+//! let mut interp = Interpreter::new();
+//! interp.define_resource(
+//!     "lock",
+//!     |interp, _args| {
+//!         interp.state_mut::<Open>().0 += 1;
+//!         Ok(WofValue::string("lock"))
+//!     },
+//!     |interp, _handle| {
+//!         interp.state_mut::<Open>().0 -= 1;
+//!         Ok(())
+//!     },
+//! );
+//!
+//! interp.exec_line("with_lock ⺆ ⺘").unwrap();
+//! assert_eq!(interp.stack_mut().pop_string().unwrap(), "lock");
+//! assert_eq!(interp.state::<Open>().unwrap().0, 0);
+//! ```
+//!
+//! `with_<kind>` runs the tokens between its name and the block, passing
+//! every value they push to the opener. The handle it returns is pushed
+//! for the block, and the closer receives it once the block ends, even
+//! if the block fails.
+
+use std::collections::BTreeMap;
+
+use woflang_core::{Result, WofError, WofValue};
+
+use crate::Interpreter;
+
+/// Open a resource from the values given to `with_<kind>`, returning its
+/// handle.
+pub type ResourceOpen = fn(&mut Interpreter, Vec<WofValue>) -> Result<WofValue>;
+
+/// Release a resource given its handle.
+pub type ResourceClose = fn(&mut Interpreter, WofValue) -> Result<()>;
+
+/// Resource accessors by kind.
+#[derive(Default)]
+struct Resources {
+    defs: BTreeMap<String, (ResourceOpen, ResourceClose)>,
+}
+
+impl Interpreter {
+    /// Define the resource kind `kind`, making `with_<kind>` available and
+    /// replacing any existing definition.
+    pub fn define_resource(&mut self, kind: impl Into<String>, open: ResourceOpen, close: ResourceClose) {
+        self.state_mut::<Resources>().defs.insert(kind.into(), (open, close));
+    }
+
+    /// Check whether a resource kind named `kind` exists.
+    #[must_use]
+    pub fn has_resource(&self, kind: &str) -> bool {
+        self.state::<Resources>().is_some_and(|r| r.defs.contains_key(kind))
+    }
+
+    /// The names of all resource kinds, sorted.
+    #[must_use]
+    pub fn resource_kinds(&self) -> Vec<&str> {
+        self.state::<Resources>()
+            .map(|r| r.defs.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Open a `kind` resource from `args`, run `body` with its handle, then
+    /// close it whether or not `body` succeeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such kind or the resource cannot be
+    /// opened, the error from `body`, or else the error from closing.
+    pub fn with_resource<T>(
+        &mut self,
+        kind: &str,
+        args: Vec<WofValue>,
+        body: impl FnOnce(&mut Self, WofValue) -> Result<T>,
+    ) -> Result<T> {
+        let (open, close) = self
+            .state::<Resources>()
+            .and_then(|r| r.defs.get(kind).copied())
+            .ok_or_else(|| WofError::Runtime(format!("no resource kind named '{kind}'")))?;
+        let handle = open(self, args).map_err(|e| WofError::Runtime(format!("with_{kind}: {e}")))?;
+        let result = body(self, handle.clone());
+        let closed = close(self, handle).map_err(|e| WofError::Runtime(format!("with_{kind}: closing: {e}")));
+        let value = result?;
+        closed?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use woflang_core::InterpreterContext;
+
+    /// Handles currently open, and every handle ever closed.
+    #[derive(Default)]
+    struct Log {
+        open: Vec<i64>,
+        closed: Vec<i64>,
+    }
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        interp.define_resource(
+            "slot",
+            |interp, args| {
+                let id = match args.as_slice() {
+                    [] => 0,
+                    [id] => id.as_integer()?,
+                    _ => return Err(WofError::Runtime("takes at most one id".into())),
+                };
+                interp.state_mut::<Log>().open.push(id);
+                Ok(WofValue::integer(id))
+            },
+            |interp, handle| {
+                let id = handle.as_integer()?;
+                let log = interp.state_mut::<Log>();
+                log.open.retain(|&open| open != id);
+                log.closed.push(id);
+                Ok(())
+            },
+        );
+        interp.register("drop", |interp| interp.stack_mut().drop());
+        interp.register("fail", |_| Err(WofError::Runtime("fail".into())));
+        interp
+    }
+
+    fn log(interp: &Interpreter) -> (&[i64], &[i64]) {
+        let log = interp.state::<Log>().unwrap();
+        (&log.open, &log.closed)
+    }
+
+    #[test]
+    fn blocks_get_the_handle_and_close_it() {
+        let mut interp = make_interp();
+        interp.exec_line("with_slot 1 ⺆ with_slot 2 ⺆ ⺘ ⺘ with_slot ⺆ drop ⺘").unwrap();
+        assert_eq!(interp.stack().as_slice().iter().map(|v| v.as_integer().unwrap()).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(log(&interp), (&[][..], &[2, 1, 0][..]));
+        assert!(interp.has_resource("slot"));
+        assert_eq!(interp.resource_kinds(), ["slot"]);
+    }
+
+    #[test]
+    fn returns_leave_the_block_and_close_the_resource() {
+        let mut interp = make_interp();
+        interp.exec_line("⊕ f ⺆ with_slot 4 ⺆ 至 ⺘ 99 ⺘").unwrap();
+        interp.exec_line("f 5").unwrap();
+        assert_eq!(interp.stack().as_slice().iter().map(|v| v.as_integer().unwrap()).collect::<Vec<_>>(), [4, 5]);
+        assert_eq!(log(&interp), (&[][..], &[4][..]));
+    }
+
+    #[test]
+    fn resources_are_closed_after_errors() {
+        let mut interp = make_interp();
+        assert!(interp.exec_line("with_slot 3 ⺆ fail ⺘").is_err());
+        assert_eq!(log(&interp), (&[][..], &[3][..]));
+
+        // Nothing is opened, so nothing is closed.
+        assert!(interp.exec_line("with_slot 1 2 ⺆ ⺘").is_err());
+        assert!(interp.exec_line("with_slot fail ⺆ ⺘").is_err());
+        assert!(interp.exec_line("with_slot 4 ⺆").is_err());
+        assert_eq!(log(&interp), (&[][..], &[3][..]));
+        assert!(interp.with_resource("nope", Vec::new(), |_, _| Ok(())).is_err());
+    }
+}
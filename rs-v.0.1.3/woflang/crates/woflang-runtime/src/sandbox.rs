@@ -0,0 +1,151 @@
+//! File access policy for scripts.
+//!
+//! Every interpreter owns a [`Sandbox`] that file ops consult before
+//! touching the filesystem. The default allows everything; an embedder
+//! (or the CLI's `--allow-path` and `--read-only` flags) narrows it to a
+//! set of root directories and can forbid writes altogether:
+//!
+//! ```
+//! use woflang_runtime::Sandbox;
+//!
+//! let dir = std::env::temp_dir();
+//! let mut sandbox = Sandbox::default();
+//! sandbox.allow(&dir).unwrap();
+//! sandbox.read_only = true;
+//!
+//! assert!(sandbox.check_read(dir.join("data.csv")).is_ok());
+//! assert!(sandbox.check_write(dir.join("out.txt")).is_err());
+//! ```
+//!
+//! Paths are resolved before they are checked, so `..` components and
+//! symbolic links cannot climb out of an allowed root.
+
+use std::path::{Component, Path, PathBuf};
+
+use woflang_core::{Result, WofError};
+
+/// Where scripts may read and write files.
+#[derive(Debug, Clone, Default)]
+pub struct Sandbox {
+    /// Canonical directories scripts may use; empty means anywhere.
+    roots: Vec<PathBuf>,
+    /// Refuse every write.
+    pub read_only: bool,
+}
+
+impl Sandbox {
+    /// Add a directory scripts may use, along with everything below it.
+    ///
+    /// The first root turns the sandbox from "anywhere" into "only the
+    /// allowed roots". Fails if the directory does not exist.
+    pub fn allow(&mut self, root: impl AsRef<Path>) -> Result<()> {
+        let root = root.as_ref();
+        let canonical = root
+            .canonicalize()
+            .map_err(|e| WofError::Io(format!("cannot allow {}: {e}", root.display())))?;
+        self.roots.push(canonical);
+        Ok(())
+    }
+
+    /// The allowed roots; empty when every path is allowed.
+    #[must_use]
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Resolve `path` for reading, or explain why it is off limits.
+    pub fn check_read(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        self.check(path.as_ref())
+    }
+
+    /// Resolve `path` for writing, or explain why it is off limits.
+    pub fn check_write(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let path = path.as_ref();
+        if self.read_only {
+            return Err(WofError::Io(format!("{}: the sandbox is read-only", path.display())));
+        }
+        self.check(path)
+    }
+
+    fn check(&self, path: &Path) -> Result<PathBuf> {
+        let resolved = resolve(path)?;
+        if self.roots.is_empty() || self.roots.iter().any(|root| resolved.starts_with(root)) {
+            Ok(resolved)
+        } else {
+            Err(WofError::Io(format!("{}: outside the sandbox", path.display())))
+        }
+    }
+}
+
+/// Make `path` absolute with links and `..` resolved. A path that does
+/// not exist yet resolves through its nearest existing ancestor.
+fn resolve(path: &Path) -> Result<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().map_err(WofError::from)?.join(path)
+    };
+    let mut existing = absolute.as_path();
+    let mut missing = Vec::new();
+    let base = loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            break canonical;
+        }
+        let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+            return Err(WofError::Io(format!("{}: cannot resolve path", path.display())));
+        };
+        missing.push(name);
+        existing = parent;
+    };
+    let mut resolved = base;
+    for name in missing.into_iter().rev() {
+        match Path::new(name).components().next() {
+            Some(Component::Normal(part)) => resolved.push(part),
+            _ => return Err(WofError::Io(format!("{}: cannot resolve path", path.display()))),
+        }
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("woflang-sandbox-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("inner")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn roots_confine_paths() {
+        let dir = scratch("roots");
+        let mut sandbox = Sandbox::default();
+        assert!(sandbox.check_write(dir.join("anything")).is_ok());
+
+        sandbox.allow(dir.join("inner")).unwrap();
+        assert!(sandbox.check_read(dir.join("inner/new/file.txt")).is_ok());
+        assert!(sandbox.check_read(dir.join("inner/../outside.txt")).is_err());
+        assert!(sandbox.check_read(dir.join("outside.txt")).is_err());
+        assert!(sandbox.allow(dir.join("missing")).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn links_cannot_escape() {
+        let dir = scratch("links");
+        std::os::unix::fs::symlink(&dir, dir.join("inner/up")).unwrap();
+        let mut sandbox = Sandbox::default();
+        sandbox.allow(dir.join("inner")).unwrap();
+        assert!(sandbox.check_read(dir.join("inner/up/secret")).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn read_only_refuses_writes() {
+        let sandbox = Sandbox { read_only: true, ..Sandbox::default() };
+        assert!(sandbox.check_read("Cargo.toml").is_ok());
+        assert!(sandbox.check_write("Cargo.toml").is_err());
+    }
+}
@@ -0,0 +1,330 @@
+//! Schema definitions: `schema "Person" "name:string(1..)" "age?:int(0..150)"`.
+//!
+//! A schema describes the keys a map must have, such as a parsed JSON
+//! object or a CSV row. Each field is `key:type`, with `?` after the key
+//! if it may be missing (or nil), and an optional inclusive `(min..max)`
+//! range, either end of which may be left out:
+//!
+//! | Type            | Accepts | Range bounds |
+//! |-----------------|---------|--------------|
+//! | `any`           | Any value (the default for a bare `key`) | - |
+//! | `int`           | Integers and bigints | The value |
+//! | `float`         | Doubles | The value |
+//! | `number`        | Any of the above | The value |
+//! | `bool`          | `0` or `1`, as JSON `true`/`false` parse | - |
+//! | `string`        | Strings | Length in characters |
+//! | `list`          | Lists | Length |
+//! | `list<T>`       | Lists whose elements are all `T` | Length |
+//! | `map`           | Maps | Number of entries |
+//! | `Name`          | Maps matching schema `Name` | - |
+//!
+//! Keys a schema does not mention are allowed. Schemas are looked up
+//! when a value is checked, so they may refer to each other, or to
+//! themselves, in any order, and are kept per namespace like other
+//! named objects (see [`Interpreter::resource_name`]).
+//!
+//! [`Interpreter::validate`] checks a value against a schema name or any
+//! type above and returns what is wrong with it:
+//!
+//! ```
+//! use woflang_core::{WofMap, WofValue};
+//! use woflang_runtime::Interpreter;
+//!
+//! let mut interp = Interpreter::new();
+//! interp.exec_line(r#"schema "Point" "x:number" "y:number" "label?:string(..8)""#).unwrap();
+//!
+//! let mut point = WofMap::new();
+//! point.insert(WofValue::string("x"), WofValue::integer(3));
+//! point.insert(WofValue::string("label"), WofValue::string("far too long"));
+//! let errors = interp.validate(&WofValue::map(point), "Point").unwrap();
+//! assert_eq!(errors, ["y: missing required key", "label: length 12 is above the maximum 8"]);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use woflang_core::{Result, WofError, WofType, WofValue};
+
+use crate::Interpreter;
+
+/// What a field's value must be.
+#[derive(Debug, Clone, PartialEq)]
+enum Type {
+    Any,
+    Int,
+    Float,
+    Number,
+    Bool,
+    String,
+    List(Option<Box<Type>>),
+    Map,
+    /// A schema, by its namespaced name.
+    Named(String),
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "any"),
+            Self::Int => write!(f, "int"),
+            Self::Float => write!(f, "float"),
+            Self::Number => write!(f, "number"),
+            Self::Bool => write!(f, "bool"),
+            Self::String => write!(f, "string"),
+            Self::List(None) => write!(f, "list"),
+            Self::List(Some(element)) => write!(f, "list<{element}>"),
+            Self::Map => write!(f, "map"),
+            Self::Named(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Inclusive bounds on a value or length.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Range {
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    key: String,
+    optional: bool,
+    ty: Type,
+    range: Option<Range>,
+}
+
+/// Defined schemas, by namespaced name.
+#[derive(Debug, Default)]
+struct Schemas(HashMap<String, Arc<[Field]>>);
+
+/// Parse a type name, qualifying schema names for the current namespace.
+fn parse_type(interp: &Interpreter, text: &str) -> Result<Type> {
+    let ty = match text {
+        "any" => Type::Any,
+        "int" => Type::Int,
+        "float" => Type::Float,
+        "number" => Type::Number,
+        "bool" => Type::Bool,
+        "string" => Type::String,
+        "list" => Type::List(None),
+        "map" => Type::Map,
+        _ => {
+            if let Some(element) = text.strip_prefix("list<").and_then(|rest| rest.strip_suffix('>')) {
+                return Ok(Type::List(Some(Box::new(parse_type(interp, element)?))));
+            }
+            if text.is_empty() || text.chars().any(|c| c.is_whitespace() || "\"<>()".contains(c)) {
+                return Err(WofError::InvalidArgument(format!("schema: invalid type '{text}'")));
+            }
+            Type::Named(interp.resource_name(text))
+        }
+    };
+    Ok(ty)
+}
+
+/// Parse the inside of a `(min..max)` suffix.
+fn parse_range(text: &str) -> Option<Range> {
+    let (min, max) = text.split_once("..")?;
+    let bound = |s: &str| if s.is_empty() { Ok(None) } else { s.parse().map(Some) };
+    Some(Range { min: bound(min).ok()?, max: bound(max).ok()? })
+}
+
+/// Parse a `key[?][:type[(min..max)]]` field spec.
+fn parse_field(interp: &Interpreter, schema: &str, spec: &str) -> Result<Field> {
+    let bad = |why: &str| WofError::InvalidArgument(format!("schema {schema}: {why} in '{spec}'"));
+    let (key, ty) = spec.split_once(':').unwrap_or((spec, "any"));
+    let (key, optional) = key.strip_suffix('?').map_or((key, false), |key| (key, true));
+    if key.is_empty() {
+        return Err(bad("missing key"));
+    }
+    let (ty, range) = match ty.strip_suffix(')').and_then(|ty| ty.split_once('(')) {
+        Some((ty, range)) => (ty, Some(parse_range(range).ok_or_else(|| bad("bad range"))?)),
+        None => (ty, None),
+    };
+    let ty = parse_type(interp, ty)?;
+    if range.is_some() && matches!(ty, Type::Any | Type::Bool | Type::Named(_)) {
+        return Err(bad(&format!("{ty} takes no range")));
+    }
+    Ok(Field { key: key.to_string(), optional, ty, range })
+}
+
+/// Define schema `name` from its field specs.
+pub(crate) fn define(interp: &mut Interpreter, name: &str, specs: &[String]) -> Result<()> {
+    if !matches!(parse_type(interp, name), Ok(Type::Named(_))) {
+        return Err(WofError::InvalidArgument(format!("schema: invalid name '{name}'")));
+    }
+    let mut fields: Vec<Field> = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let field = parse_field(interp, name, spec)?;
+        if fields.iter().any(|f| f.key == field.key) {
+            return Err(WofError::InvalidArgument(format!("schema {name}: duplicate key '{}'", field.key)));
+        }
+        fields.push(field);
+    }
+    let name = interp.resource_name(name);
+    interp.state_mut::<Schemas>().0.insert(name, fields.into());
+    Ok(())
+}
+
+/// Collects the problems found while walking a value.
+struct Checker<'a> {
+    schemas: Option<&'a Schemas>,
+    errors: Vec<String>,
+}
+
+impl Checker<'_> {
+    fn report(&mut self, path: &str, problem: impl fmt::Display) {
+        if path.is_empty() {
+            self.errors.push(problem.to_string());
+        } else {
+            self.errors.push(format!("{path}: {problem}"));
+        }
+    }
+
+    fn check(&mut self, value: &WofValue, ty: &Type, range: Option<Range>, path: &str) -> Result<()> {
+        let matches = match ty {
+            Type::Any => true,
+            Type::Int => matches!(value.value_type(), WofType::Integer | WofType::BigInt),
+            Type::Float => value.value_type() == WofType::Double,
+            Type::Number => value.value_type().is_numeric(),
+            Type::Bool => value.value_type() == WofType::Integer && matches!(value.as_integer(), Ok(0 | 1)),
+            Type::String => value.value_type() == WofType::String,
+            Type::List(_) => value.value_type() == WofType::List,
+            Type::Map | Type::Named(_) => value.value_type() == WofType::Map,
+        };
+        if !matches {
+            self.report(path, format!("expected {ty}, got {}", value.value_type()));
+            return Ok(());
+        }
+
+        if let Some(range) = range {
+            let (measure, what) = match ty {
+                Type::String => (value.as_str()?.chars().count() as f64, "length "),
+                Type::List(_) => (value.as_list()?.len() as f64, "length "),
+                Type::Map => (value.as_map()?.len() as f64, "size "),
+                _ => (value.as_double()?, ""),
+            };
+            if let Some(min) = range.min.filter(|&min| measure < min) {
+                self.report(path, format!("{what}{measure} is below the minimum {min}"));
+            }
+            if let Some(max) = range.max.filter(|&max| measure > max) {
+                self.report(path, format!("{what}{measure} is above the maximum {max}"));
+            }
+        }
+
+        match ty {
+            Type::List(Some(element)) => {
+                for (i, item) in value.as_list()?.iter().enumerate() {
+                    self.check(item, element, None, &format!("{path}[{i}]"))?;
+                }
+            }
+            Type::Named(name) => {
+                let fields = self
+                    .schemas
+                    .and_then(|schemas| schemas.0.get(name))
+                    .cloned()
+                    .ok_or_else(|| WofError::Runtime(format!("validate: unknown schema '{name}'")))?;
+                let map = value.as_map()?;
+                for field in fields.iter() {
+                    let path = if path.is_empty() { field.key.clone() } else { format!("{path}.{}", field.key) };
+                    match map.get(&WofValue::string(&field.key)).filter(|v| !v.is_nil()) {
+                        Some(v) => self.check(v, &field.ty, field.range, &path)?,
+                        None if field.optional => {}
+                        None => self.report(&path, "missing required key"),
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl Interpreter {
+    /// Define a schema from field specs such as `"age?:int(0..150)"`,
+    /// replacing any earlier one of the same name in the current
+    /// namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an invalid name, field spec or range, or a
+    /// repeated key.
+    pub fn define_schema(&mut self, name: &str, fields: &[String]) -> Result<()> {
+        define(self, name, fields)
+    }
+
+    /// Check `value` against `ty`, a schema name or a built-in type such
+    /// as `list<Person>`, returning a description of each problem found,
+    /// prefixed with the path to it (`people[2].age: ...`). An empty list
+    /// means the value is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `ty` is malformed or names a schema that is
+    /// not defined.
+    pub fn validate(&self, value: &WofValue, ty: &str) -> Result<Vec<String>> {
+        let ty = parse_type(self, ty)?;
+        let mut checker = Checker { schemas: self.state::<Schemas>(), errors: Vec::new() };
+        checker.check(value, &ty, None, "")?;
+        Ok(checker.errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interpreter;
+    use woflang_core::{WofMap, WofValue};
+
+    fn map(entries: &[(&str, WofValue)]) -> WofValue {
+        let mut map = WofMap::new();
+        for (key, value) in entries {
+            map.insert(WofValue::string(key), value.clone());
+        }
+        WofValue::map(map)
+    }
+
+    #[test]
+    fn reports_each_problem_with_its_path() {
+        let mut interp = Interpreter::new();
+        interp
+            .exec_line(r#"schema "Person" "name:string(1..)" "age?:int(0..150)" "tags:list<string>" "boss?:Person""#)
+            .unwrap();
+
+        let tags = WofValue::list(vec![WofValue::string("a")]);
+        let alice = map(&[("name", WofValue::string("Alice")), ("tags", tags.clone()), ("extra", WofValue::nil())]);
+        assert!(interp.validate(&alice, "Person").unwrap().is_empty());
+
+        let bob = map(&[
+            ("name", WofValue::string("")),
+            ("age", WofValue::integer(200)),
+            ("tags", WofValue::list(vec![WofValue::integer(1)])),
+            ("boss", map(&[("tags", tags)])),
+        ]);
+        assert_eq!(
+            interp.validate(&WofValue::list(vec![alice, bob]), "list<Person>").unwrap(),
+            [
+                "[1].name: length 0 is below the minimum 1",
+                "[1].age: 200 is above the maximum 150",
+                "[1].tags[0]: expected string, got integer",
+                "[1].boss.name: missing required key",
+            ]
+        );
+        assert_eq!(interp.validate(&WofValue::integer(2), "Person").unwrap(), ["expected Person, got integer"]);
+
+        assert!(interp.validate(&map(&[]), "Nobody").is_err());
+        assert!(interp.exec_line(r#"schema "Bad" "a:int" "a:string""#).is_err());
+        assert!(interp.exec_line(r#"schema "Bad" "a:int(x..)""#).is_err());
+        assert!(interp.exec_line(r#"schema "Bad" "a:bool(0..1)""#).is_err());
+        assert!(interp.exec_line(r#"schema "list" "a""#).is_err());
+    }
+
+    #[test]
+    fn schemas_are_namespaced() {
+        let mut interp = Interpreter::new();
+        interp.exec_line(r#"ns "lib" ⺆ schema "Id" "id:int" schema "Ref" "to:Id" ⺘"#).unwrap();
+        let value = map(&[("to", map(&[("id", WofValue::integer(1))]))]);
+        assert!(interp.validate(&value, "lib::Ref").unwrap().is_empty());
+        assert!(interp.validate(&value, "Ref").is_err());
+    }
+}
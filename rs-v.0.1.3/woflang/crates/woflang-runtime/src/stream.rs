@@ -0,0 +1,165 @@
+//! Streaming evaluation: a line's effects as a sequence of events.
+//!
+//! [`Interpreter::exec_line_stream`](crate::Interpreter::exec_line_stream)
+//! runs a line like `exec_line`, but reports what happens as it happens
+//! instead of leaving the caller to inspect the final stack. Stack changes
+//! arrive as deltas after every token, text ops print to their
+//! [`Out`](woflang_core::Out) arrives as it is printed, and a failure
+//! ends the stream with an error:
+//!
+//! ```
+//! use std::sync::mpsc;
+//! use woflang_core::{InterpreterContext, WofValue};
+//! use woflang_runtime::{Event, Interpreter};
+//!
+//! let (tx, rx) = mpsc::channel();
+//! let mut interp = Interpreter::new();
+//! interp.register("add", |ctx| {
+//!     let b = ctx.stack_mut().pop_integer()?;
+//!     let a = ctx.stack_mut().pop_integer()?;
+//!     ctx.stack_mut().push(WofValue::integer(a + b));
+//!     Ok(())
+//! });
+//!
+//! interp.exec_line_stream("2 3 add", move |e: &Event| {
+//!     let _ = tx.send(e.clone());
+//! }).unwrap();
+//! let events: Vec<String> = rx.try_iter().map(|e| e.to_string()).collect();
+//! assert_eq!(events, ["push 2", "push 3", "pop 2", "push 5"]);
+//! ```
+//!
+//! A front-end replays the deltas onto its own copy of the stack, so it
+//! can draw results progressively and keep op output apart from its own
+//! rendering. Deltas are computed between the tokens of the line (and of
+//! the functions it calls), so a loop or block reports its net effect
+//! when it finishes, after anything it printed.
+
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use woflang_core::{Out, WofError, WofValue};
+
+/// Something a streamed line did.
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// A value was pushed on top of the stack.
+    Pushed(WofValue),
+    /// This many values were removed from the top of the stack.
+    Popped(usize),
+    /// Text an op printed, newlines included.
+    Printed(String),
+    /// The line failed; nothing after it runs.
+    Error(WofError),
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pushed(value) => write!(f, "push {value}"),
+            Self::Popped(n) => write!(f, "pop {n}"),
+            Self::Printed(text) => write!(f, "print {text:?}"),
+            Self::Error(e) => write!(f, "error {e}"),
+        }
+    }
+}
+
+/// Receives the events of a streamed line.
+///
+/// Implemented for closures taking `&Event`.
+pub trait EventSink: Send + Sync {
+    /// Called for every event, in order.
+    fn event(&mut self, event: &Event);
+}
+
+impl<F: FnMut(&Event) + Send + Sync> EventSink for F {
+    fn event(&mut self, event: &Event) {
+        self(event);
+    }
+}
+
+/// A sink and the stack as last reported to it.
+pub(crate) struct Stream {
+    sink: Arc<Mutex<Box<dyn EventSink>>>,
+    shown: Vec<WofValue>,
+}
+
+impl Stream {
+    pub(crate) fn new(sink: Box<dyn EventSink>, stack: &[WofValue]) -> Self {
+        Self { sink: Arc::new(Mutex::new(sink)), shown: stack.to_vec() }
+    }
+
+    /// An output sending what is printed to the sink.
+    pub(crate) fn printer(&self) -> Out {
+        let sink = Arc::clone(&self.sink);
+        Out::new(move |text: &str| {
+            sink.lock().unwrap_or_else(PoisonError::into_inner).event(&Event::Printed(text.to_string()));
+        })
+    }
+
+    /// Report how `stack` differs from the stack last reported: the
+    /// values above the longest common base are popped and the new ones
+    /// pushed.
+    pub(crate) fn stack_changed(&mut self, stack: &[WofValue]) {
+        let kept = self.shown.iter().zip(stack).take_while(|(a, b)| a == b).count();
+        if kept == self.shown.len() && kept == stack.len() {
+            return;
+        }
+        let mut sink = self.sink.lock().unwrap_or_else(PoisonError::into_inner);
+        if kept < self.shown.len() {
+            sink.event(&Event::Popped(self.shown.len() - kept));
+            self.shown.truncate(kept);
+        }
+        for value in &stack[kept..] {
+            sink.event(&Event::Pushed(value.clone()));
+            self.shown.push(value.clone());
+        }
+    }
+
+    pub(crate) fn send(&self, event: &Event) {
+        self.sink.lock().unwrap_or_else(PoisonError::into_inner).event(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interpreter;
+    use std::sync::{Arc, Mutex};
+    use woflang_core::{InterpreterContext, WofError};
+
+    fn stream(interp: &mut Interpreter, line: &str) -> Vec<String> {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&events);
+        let _ = interp.exec_line_stream(line, move |e: &super::Event| seen.lock().unwrap().push(e.to_string()));
+        let events = events.lock().unwrap().clone();
+        events
+    }
+
+    #[test]
+    fn streams_stack_deltas_prints_and_errors() {
+        let mut interp = Interpreter::new();
+        interp.register("swap", |ctx| ctx.stack_mut().swap());
+        interp.register("say", |ctx| {
+            let value = ctx.stack_mut().pop()?;
+            writeln!(ctx.out(), "{value}");
+            Ok(())
+        });
+        interp.register("boom", |_| Err(WofError::runtime("boom")));
+        interp.exec_line("1").unwrap();
+
+        assert_eq!(stream(&mut interp, "2 3 swap"), ["push 2", "push 3", "pop 2", "push 3", "push 2"]);
+        let printed = "print \"7\\n\"";
+        assert_eq!(stream(&mut interp, "3 ⨯ ⺆ 7 say ⺘"), ["push 3", "pop 1", printed, printed, printed]);
+        assert_eq!(stream(&mut interp, "4 say 5"), ["push 4", "print \"4\\n\"", "pop 1", "push 5"]);
+
+        let events = stream(&mut interp, "6 boom 7");
+        assert_eq!(events[0], "push 6");
+        assert_eq!(events.len(), 2, "{events:?}");
+        assert!(events[1].starts_with("error ") && events[1].contains("boom"), "{events:?}");
+        assert_eq!(interp.stack().len(), 5);
+
+        // Outside a stream ops print to the interpreter's output
+        let buffer = woflang_core::Buffer::new();
+        interp.set_output(buffer.clone());
+        interp.exec_line("say").unwrap();
+        assert_eq!(buffer.take(), "6\n");
+    }
+}
@@ -0,0 +1,187 @@
+//! Enum definitions: `enum "Shape" "Circle/1" "Square/1" "Empty"`.
+//!
+//! Each variant may declare a payload arity after a slash (default 0).
+//! Defining an enum registers:
+//!
+//! | Operation      | Stack Effect          | Description |
+//! |----------------|-----------------------|-------------|
+//! | `Circle`       | ( r -- shape )        | Build the variant from its payload |
+//! | `Shape.Circle` | ( r -- shape )        | Same, qualified by the enum name |
+//! | `Shape?`       | ( v -- bool )         | Is `v` a `Shape`? |
+//! | `tag_of`       | ( v -- "Circle" )     | Variant name of any tagged value |
+//! | `untag`        | ( v -- payload... )   | Push the payload in constructor order |
+//!
+//! Values are taken apart with `match_tag`, which pushes the payload and
+//! runs the first arm naming the variant, or the `_` arm:
+//!
+//! ```text
+//! shape match_tag ⺆
+//!     Circle ⺆ dup * 3.14159 * ⺘
+//!     Square ⺆ dup * ⺘
+//!     _ ⺆ 0 ⺘
+//! ⺘
+//! ```
+//!
+//! Declaring a variant whose constructor name is already an op, such as
+//! a builtin or another enum's variant, is an error rather than replacing
+//! it; redefining the same enum is allowed.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use woflang_core::{InterpreterContext, Result, WofError, WofTagged, WofValue};
+
+use crate::Interpreter;
+
+/// The enum each registered constructor and `Name?` op belongs to.
+#[derive(Default)]
+struct Owners(HashMap<String, String>);
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.chars().any(|c| c.is_whitespace() || c == '"' || c == '.')
+}
+
+/// Split a `"Name/arity"` variant spec.
+fn parse_variant<'a>(enum_name: &str, spec: &'a str) -> Result<(&'a str, usize)> {
+    let (tag, arity) = match spec.split_once('/') {
+        Some((tag, arity)) => {
+            let arity = arity.parse().map_err(|_| {
+                WofError::InvalidArgument(format!("enum {enum_name}: bad arity in '{spec}'"))
+            })?;
+            (tag, arity)
+        }
+        None => (spec, 0),
+    };
+    if !valid_name(tag) {
+        return Err(WofError::InvalidArgument(format!(
+            "enum {enum_name}: invalid variant name '{tag}'"
+        )));
+    }
+    Ok((tag, arity))
+}
+
+/// Register the ops for an enum type.
+pub(crate) fn define(interp: &mut Interpreter, name: &str, variants: &[String]) -> Result<()> {
+    if !valid_name(name) {
+        return Err(WofError::InvalidArgument(format!("enum: invalid type name '{name}'")));
+    }
+    if variants.is_empty() {
+        return Err(WofError::InvalidArgument(format!("enum {name}: no variants")));
+    }
+    let mut parsed: Vec<(&str, usize)> = Vec::with_capacity(variants.len());
+    for spec in variants {
+        let (tag, arity) = parse_variant(name, spec)?;
+        if parsed.iter().any(|(t, _)| *t == tag) {
+            return Err(WofError::InvalidArgument(format!(
+                "enum {name}: duplicate variant '{tag}'"
+            )));
+        }
+        parsed.push((tag, arity));
+    }
+
+    let ops: Vec<String> = parsed
+        .iter()
+        .flat_map(|&(tag, _)| [tag.to_string(), format!("{name}.{tag}")])
+        .chain([format!("{name}?")])
+        .collect();
+    for op in &ops {
+        let owner = interp.state::<Owners>().and_then(|owners| owners.0.get(op));
+        if interp.registry().contains(op) && owner.map(String::as_str) != Some(name) {
+            return Err(WofError::InvalidArgument(format!(
+                "enum {name}: '{op}' is already an op; rename the variant"
+            )));
+        }
+    }
+    let owners = &mut interp.state_mut::<Owners>().0;
+    owners.extend(ops.into_iter().map(|op| (op, name.to_string())));
+
+    let enum_name: Arc<str> = Arc::from(name);
+    for (tag, arity) in parsed {
+        let tag: Arc<str> = Arc::from(tag);
+        let (ctor_enum, ctor_tag) = (Arc::clone(&enum_name), Arc::clone(&tag));
+        let ctor = move |interp: &mut Interpreter| {
+            let mut payload = interp.stack_mut().pop_n(arity)?;
+            payload.reverse();
+            let value = WofTagged::new(Arc::clone(&ctor_enum), Arc::clone(&ctor_tag), payload);
+            interp.push(WofValue::tagged(value));
+            Ok(())
+        };
+        interp.register(format!("{name}.{tag}"), ctor.clone());
+        interp.register(tag.to_string(), ctor);
+    }
+
+    let test = Arc::clone(&enum_name);
+    interp.register(format!("{name}?"), move |interp| {
+        let value = interp.stack_mut().pop()?;
+        let is = value.as_tagged().is_ok_and(|t| t.enum_name() == &*test);
+        interp.push(WofValue::boolean(is));
+        Ok(())
+    });
+
+    interp.register("tag_of", |interp| {
+        let value = interp.stack_mut().pop()?;
+        let tag = value.as_tagged()?.tag().to_string();
+        interp.push(WofValue::string(tag));
+        Ok(())
+    });
+    interp.register("untag", |interp| {
+        let value = interp.stack_mut().pop()?;
+        interp.stack_mut().push_all(value.as_tagged()?.payload().iter().cloned());
+        Ok(())
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interpreter;
+    use woflang_core::{InterpreterContext, WofValue};
+
+    #[test]
+    fn constructs_and_matches_variants() {
+        let mut interp = Interpreter::new();
+        interp.exec_line(r#"enum "Shape" "Circle/1" "Rect/2" "Empty""#).unwrap();
+        interp.exec_line("2 3 Rect").unwrap();
+        assert_eq!(interp.stack().peek().unwrap().to_string(), "Shape.Rect(2, 3)");
+
+        // The Rect arm leaves its payload and runs a nested match.
+        interp
+            .exec_line("match_tag ⺆ Circle ⺆ 0 ⺘ Rect ⺆ Empty match_tag ⺆ Empty ⺆ 1 ⺘ ⺘ ⺘ ⺘ 4")
+            .unwrap();
+        let rest = interp.stack_mut().pop_n(4).unwrap();
+        assert_eq!(rest, [4, 1, 3, 2].map(WofValue::integer));
+
+        interp.exec_line("Shape.Empty match_tag ⺆ Circle ⺆ ⺘ _ ⺆ 9 ⺘ ⺘").unwrap();
+        assert_eq!(interp.stack_mut().pop().unwrap(), WofValue::integer(9));
+
+        interp.exec_line("5 Circle Shape? 5 Shape.Circle tag_of").unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "Circle");
+        assert!(interp.stack_mut().pop_bool().unwrap());
+
+        assert!(interp.exec_line("Empty match_tag ⺆ Circle ⺆ ⺘ ⺘").is_err());
+        assert!(interp.exec_line("3 match_tag ⺆ _ ⺆ ⺘ ⺘").is_err());
+        assert!(interp.exec_line(r#"enum "Bad" "A" "A""#).is_err());
+        assert!(interp.exec_line(r#"enum "Bad" "A/x""#).is_err());
+    }
+
+    #[test]
+    fn refuses_variants_that_would_replace_ops() {
+        let mut interp = Interpreter::new();
+        interp.register("dup", |interp| {
+            let top = interp.stack().peek()?.clone();
+            interp.push(top);
+            Ok(())
+        });
+        let err = interp.exec_line(r#"enum "Op" "Push" "dup""#).unwrap_err();
+        assert!(err.to_string().contains("'dup' is already an op"), "{err}");
+        assert!(!interp.registry().contains("Push"));
+        interp.exec_line("1 dup").unwrap();
+        assert_eq!(interp.stack().len(), 2);
+
+        interp.exec_line(r#"enum "Shape" "Circle/1""#).unwrap();
+        interp.exec_line(r#"enum "Shape" "Circle/1" "Square/1""#).unwrap();
+        assert!(interp.exec_line(r#"enum "Figure" "Circle/1""#).is_err());
+        interp.exec_line("2 Circle tag_of").unwrap();
+        assert_eq!(interp.stack_mut().pop_string().unwrap(), "Circle");
+    }
+}
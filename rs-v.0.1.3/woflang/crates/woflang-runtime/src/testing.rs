@@ -0,0 +1,171 @@
+//! Test cases in scripts: `test_case "name" ⺆ ... ⺘`.
+//!
+//! A test case runs its block and then puts the stack back as it was, so
+//! cases are independent. Its outcome goes into the interpreter's
+//! [`TestReport`] rather than failing the line:
+//!
+//! - assertions made through [`Interpreter::check`] record a failure and
+//!   let the case carry on, so one run reports every broken check;
+//! - an error ends the case, which is recorded as failed.
+//!
+//! ```text
+//! test_case "addition" ⺆ 2 3 + 5 assert_eq ⺘
+//! test_case "halving"  ⺆ 1 2 / 0.5 0.001 assert_near 0 assert_stack_depth ⺘
+//! ```
+//!
+//! Outside a test case, [`check`](Interpreter::check) fails the line as
+//! an assertion always has.
+
+use woflang_core::{InterpreterContext, Result, WofError};
+
+use crate::interpreter::located;
+use crate::{Interpreter, OwnedToken};
+
+/// The outcome of one test case.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestCase {
+    /// The name given to `test_case`.
+    pub name: String,
+    /// Assertions checked.
+    pub checks: usize,
+    /// Messages of the assertions that failed.
+    pub failures: Vec<String>,
+    /// The error that ended the case early, if any.
+    pub error: Option<String>,
+}
+
+impl TestCase {
+    /// Whether every check passed and the case ran to the end.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty() && self.error.is_none()
+    }
+}
+
+/// The test cases run so far, in order.
+#[derive(Debug, Clone, Default)]
+pub struct TestReport {
+    cases: Vec<TestCase>,
+    /// Index of the case running now.
+    current: Option<usize>,
+}
+
+impl TestReport {
+    /// Every case run, in order.
+    #[must_use]
+    pub fn cases(&self) -> &[TestCase] {
+        &self.cases
+    }
+
+    /// Number of cases that passed.
+    #[must_use]
+    pub fn passed(&self) -> usize {
+        self.cases.iter().filter(|case| case.passed()).count()
+    }
+
+    /// Number of cases that failed.
+    #[must_use]
+    pub fn failed(&self) -> usize {
+        self.cases.len() - self.passed()
+    }
+}
+
+impl Interpreter {
+    /// Record an assertion. Inside a test case a failure is added to the
+    /// case's report and running continues; outside one it is returned
+    /// as an error. `failure` describes the failed assertion.
+    pub fn check(&mut self, passed: bool, failure: impl FnOnce() -> String) -> Result<()> {
+        let report = self.state_mut::<TestReport>();
+        let Some(case) = report.current.and_then(|i| report.cases.get_mut(i)) else {
+            return if passed { Ok(()) } else { Err(WofError::Runtime(failure())) };
+        };
+        case.checks += 1;
+        if !passed {
+            case.failures.push(failure());
+        }
+        Ok(())
+    }
+
+    /// The test cases run so far, if any.
+    #[must_use]
+    pub fn test_report(&self) -> Option<&TestReport> {
+        self.state::<TestReport>().filter(|report| !report.cases.is_empty())
+    }
+
+    /// Take the test report, starting a fresh one.
+    pub fn take_test_report(&mut self) -> TestReport {
+        std::mem::take(self.state_mut::<TestReport>())
+    }
+
+    /// Run `body` as the test case `name`, restoring the stack after.
+    pub(crate) fn test_case(&mut self, name: String, body: Vec<OwnedToken>) -> Result<()> {
+        let report = self.state_mut::<TestReport>();
+        if report.current.is_some() {
+            return Err(WofError::Runtime(format!("test_case {name}: test cases cannot be nested")));
+        }
+        report.current = Some(report.cases.len());
+        report.cases.push(TestCase { name, ..TestCase::default() });
+
+        let snapshot = self.stack().clone();
+        let result = self.run_guarded(body);
+        *self.stack_mut() = snapshot;
+
+        let report = self.state_mut::<TestReport>();
+        let case = report.current.take().and_then(|i| report.cases.get_mut(i));
+        match result {
+            Ok(()) => Ok(()),
+            Err((e @ WofError::Interrupted(_), _)) => Err(e),
+            Err((e, at)) => {
+                if let Some(case) = case {
+                    case.error = Some(located(e, at).to_string());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interpreter;
+    use woflang_core::{InterpreterContext, WofError};
+
+    fn make_interp() -> Interpreter {
+        let mut interp = Interpreter::new();
+        interp.register("assert_eq", |interp| {
+            let b = interp.stack_mut().pop()?;
+            let a = interp.stack_mut().pop()?;
+            interp.check(a == b, || format!("{a} != {b}"))
+        });
+        interp.register("boom", |_| Err(WofError::Runtime("boom".into())));
+        interp
+    }
+
+    #[test]
+    fn records_checks_and_errors_per_case() {
+        let mut interp = make_interp();
+        assert!(interp.test_report().is_none());
+        interp
+            .exec_source(
+                "7\ntest_case \"good\" ⺆ 1 1 assert_eq 2 ⺘\n\
+                 test_case \"bad\" ⺆ 1 2 assert_eq 3 3 assert_eq 4 5 assert_eq ⺘\n\
+                 test_case \"broken\" ⺆ 1 boom 1 1 assert_eq ⺘",
+            )
+            .unwrap();
+        assert_eq!(interp.stack().len(), 1);
+
+        let report = interp.take_test_report();
+        let summary: Vec<_> = report.cases().iter().map(|c| (c.name.as_str(), c.checks, c.passed())).collect();
+        assert_eq!(summary, [("good", 1, true), ("bad", 3, false), ("broken", 0, false)]);
+        assert_eq!(report.cases()[1].failures, ["1 != 2", "4 != 5"]);
+        assert_eq!(report.cases()[2].error.as_deref(), Some("error at 4:24: runtime error: boom"));
+        assert_eq!((report.passed(), report.failed()), (1, 2));
+        assert!(interp.test_report().is_none());
+
+        // Outside a test case a failed check fails the line
+        assert!(interp.exec_line("1 2 assert_eq").is_err());
+        assert!(interp.exec_line("test_case \"outer\" ⺆ test_case \"inner\" ⺆ ⺘ ⺘").is_ok());
+        assert!(interp.take_test_report().cases()[0].error.as_deref().is_some_and(|e| e.contains("nested")));
+        assert!(interp.exec_line("test_case ⺆ 1 ⺘").is_err());
+    }
+}
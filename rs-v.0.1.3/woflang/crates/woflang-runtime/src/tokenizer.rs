@@ -4,10 +4,14 @@
 //! It supports:
 //!
 //! - Integers and floating-point literals
-//! - Quoted strings
+//! - Quoted strings, interpolated strings (`$"x is {x}"`) and raw
+//!   multi-line strings (`"""..."""`)
 //! - Symbols and operators (including Unicode glyphs)
 //! - Comments (lines starting with `#`)
+//! - Infix sugar (`=( 3*(2+4)^2 )`)
 //! - Source location tracking (line:column)
+//! - Chunked input via [`IncrementalTokenizer`], and [`is_incomplete`] to
+//!   tell when a REPL should read another line
 //!
 //! ## Performance
 //!
@@ -19,6 +23,11 @@ use std::iter::Peekable;
 use std::str::CharIndices;
 use woflang_core::Span;
 
+use crate::OwnedToken;
+
+/// Delimiter of raw strings, which may span lines and take no escapes.
+const RAW_QUOTE: &str = "\"\"\"";
+
 /// Token kinds recognized by the Woflang tokenizer.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TokenKind {
@@ -26,14 +35,18 @@ pub enum TokenKind {
     Integer,
     /// Floating-point literal (e.g., `3.14`, `-0.5`).
     Float,
-    /// Quoted string literal (e.g., `"hello"`).
+    /// Quoted string literal (e.g., `"hello"`, or raw `"""a "b" c"""`).
     String,
+    /// Interpolated string literal (e.g., `$"x is {x}"`).
+    Interpolated,
     /// Identifier or symbol (e.g., `+`, `dup`, `|0⟩`).
     Symbol,
     /// Label definition (e.g., `:label`).
     Label,
     /// Label reference (e.g., `@label`).
     LabelRef,
+    /// Infix expression sugar (e.g., `=(3 * (2 + 4))`).
+    Infix,
     /// End of input.
     Eof,
 }
@@ -209,6 +222,52 @@ impl<'a> Tokenizer<'a> {
         Token::new(TokenKind::String, &self.source[start..end], span)
     }
 
+    /// Tokenize a raw string `"""..."""`, consuming up to the closing
+    /// `"""` (or the end of input).
+    fn tokenize_raw_string(&mut self, start: usize, start_line: u32, start_col: u32) -> Token<'a> {
+        for _ in 0..RAW_QUOTE.len() {
+            self.advance();
+        }
+        while let Some(&(i, _)) = self.chars.peek() {
+            if self.source[i..].starts_with(RAW_QUOTE) {
+                for _ in 0..RAW_QUOTE.len() {
+                    self.advance();
+                }
+                break;
+            }
+            self.advance();
+        }
+
+        let end = self.chars.peek().map_or(self.source.len(), |&(i, _)| i);
+        let span = Span::with_length(start_line, start_col, start as u32, (end - start) as u32);
+        Token::new(TokenKind::String, &self.source[start..end], span)
+    }
+
+    /// Tokenize infix sugar `=( ... )`, consuming up to the matching `)`.
+    fn tokenize_infix(&mut self, start: usize, start_line: u32, start_col: u32) -> Token<'a> {
+        // Skip `=(`
+        self.advance();
+        self.advance();
+
+        let mut depth = 1usize;
+        while let Some((_, c)) = self.advance() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let end = self.chars.peek().map_or(self.source.len(), |&(i, _)| i);
+        let span = Span::with_length(start_line, start_col, start as u32, (end - start) as u32);
+        Token::new(TokenKind::Infix, &self.source[start..end], span)
+    }
+
     /// Tokenize a symbol (identifier or operator).
     fn tokenize_symbol(&mut self, start: usize, start_line: u32, start_col: u32) -> Token<'a> {
         // Check for label definition (:name) or label reference (@name)
@@ -261,17 +320,36 @@ impl<'a> Tokenizer<'a> {
             return self.next_token();
         }
 
+        // Raw string literal
+        if self.source[start..].starts_with(RAW_QUOTE) {
+            return self.tokenize_raw_string(start, start_line, start_col);
+        }
+
         // String literal
         if c == '"' {
             return self.tokenize_string(start, start_line, start_col);
         }
 
-        // Number literal
         let next = {
             let mut lookahead = self.chars.clone();
             lookahead.next();
             lookahead.peek().map(|&(_, c)| c)
         };
+
+        // Interpolated string: `$` then an ordinary string literal
+        if c == '$' && next == Some('"') {
+            self.advance();
+            let mut token = self.tokenize_string(start, start_line, start_col);
+            token.kind = TokenKind::Interpolated;
+            return token;
+        }
+
+        // Infix sugar
+        if c == '=' && next == Some('(') {
+            return self.tokenize_infix(start, start_line, start_col);
+        }
+
+        // Number literal
         if Self::is_number_start(c, next) {
             return self.tokenize_number(start, start_line, start_col);
         }
@@ -308,9 +386,231 @@ impl<'a> Iterator for Tokenizer<'a> {
     }
 }
 
+/// Tokenizer for source that arrives in chunks (pipes, editors).
+///
+/// Text passed to [`feed`](Self::feed) is buffered until the tokens it
+/// holds are certain: a token is emitted once whitespace follows it, or
+/// once its closing quote or parenthesis arrives, so an unterminated
+/// string or `=( ... )` carries over to the next chunk. Spans are relative
+/// to the whole stream, and the tokens match what [`Tokenizer`] produces
+/// for the concatenated input however it was split.
+///
+/// ```
+/// use woflang_runtime::IncrementalTokenizer;
+///
+/// let mut stream = IncrementalTokenizer::new();
+/// assert!(stream.feed("1 \"two ").iter().map(|t| &t.text).eq(["1"]));
+/// assert!(stream.feed("words\" ⺆ 3").iter().map(|t| &t.text).eq([r#""two words""#, "⺆"]));
+/// assert!(!stream.is_complete());
+/// assert!(stream.feed(" ⺘\n").iter().map(|t| &t.text).eq(["3", "⺘"]));
+/// assert!(stream.is_complete());
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalTokenizer {
+    pending: String,
+    line: u32,
+    column: u32,
+    offset: usize,
+    depth: usize,
+}
+
+impl Default for IncrementalTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IncrementalTokenizer {
+    /// Create a tokenizer positioned at the start of a stream.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { pending: String::new(), line: 1, column: 1, offset: 0, depth: 0 }
+    }
+
+    /// Append a chunk and return the tokens it completes.
+    pub fn feed(&mut self, chunk: &str) -> Vec<OwnedToken> {
+        self.pending.push_str(chunk);
+        self.take(false)
+    }
+
+    /// End the stream, returning whatever is still buffered.
+    ///
+    /// An unterminated string or infix expression comes back as written,
+    /// as [`Tokenizer`] would return it at end of input.
+    pub fn finish(&mut self) -> Vec<OwnedToken> {
+        self.take(true)
+    }
+
+    /// Number of `⺆` blocks opened and not yet closed.
+    #[must_use]
+    pub const fn block_depth(&self) -> usize {
+        self.depth
+    }
+
+    /// True when no token is partly buffered and every block is closed,
+    /// i.e. the input so far is a whole statement.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.depth == 0 && Tokenizer::new(&self.pending).next().is_none()
+    }
+
+    /// Emit buffered tokens that are certain (all of them when `flush`),
+    /// keeping the rest for the next chunk.
+    fn take(&mut self, flush: bool) -> Vec<OwnedToken> {
+        let source = std::mem::take(&mut self.pending);
+        // Scanning restarts after whitespace, so nothing that ends before
+        // the last whitespace can change when more text arrives.
+        let last_space = source.rfind(char::is_whitespace);
+        let mut tokens = Vec::new();
+        let mut cut = 0;
+        for token in Tokenizer::new(&source) {
+            let end = token.offset() + token.text.len();
+            // A trailing `""` may yet open a raw string.
+            let closed = is_closed(&token) && !(token.text == "\"\"" && end == source.len());
+            if !(flush || last_space.is_some_and(|w| end <= w) || closed) {
+                break;
+            }
+            match token.text {
+                "⺆" => self.depth += 1,
+                "⺘" => self.depth = self.depth.saturating_sub(1),
+                _ => {}
+            }
+            let s = token.span;
+            let column = if s.line() == 1 { s.column() + self.column - 1 } else { s.column() };
+            let mut owned = OwnedToken::from(token);
+            owned.span = Span::with_length(
+                s.line() + self.line - 1,
+                column,
+                (s.offset() as usize + self.offset) as u32,
+                s.length(),
+            );
+            tokens.push(owned);
+            cut = end;
+        }
+        if flush {
+            cut = source.len();
+        }
+        for c in source[..cut].chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.offset += cut;
+        self.pending = source[cut..].to_string();
+        tokens
+    }
+}
+
+/// Whether a string or infix token reached its closing delimiter.
+pub(crate) fn is_closed(token: &Token<'_>) -> bool {
+    match token.kind {
+        TokenKind::String if token.text.starts_with(RAW_QUOTE) => {
+            token.text.len() >= 2 * RAW_QUOTE.len() && token.text.ends_with(RAW_QUOTE)
+        }
+        TokenKind::String | TokenKind::Interpolated => {
+            let open = token.text.find('"').map_or(0, |i| i + 1);
+            let mut chars = token.text[open..].chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => return true,
+                    _ => {}
+                }
+            }
+            false
+        }
+        TokenKind::Infix => {
+            let mut depth = 0usize;
+            for c in token.text.chars().skip(1) {
+                match c {
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            false
+        }
+        _ => false,
+    }
+}
+
+/// Byte length of the string literal at the start of `text` (which
+/// begins with `"`), including its closing quote if present.
+pub(crate) fn string_literal_len(text: &str) -> usize {
+    let token = Tokenizer::new(text).next_token();
+    if token.kind == TokenKind::String {
+        token.text.len()
+    } else {
+        text.len()
+    }
+}
+
+/// Forms that run the block written after them.
+const BLOCK_FORMS: &[&str] =
+    &["⟳", "loop", "当", "while", "⨯", "times", "repeat", "bench", "spawn", "jit", "若", "if", "或", "else"];
+
+/// Forms that name a function, then take an optional arity and its body.
+const DEFINE_FORMS: &[&str] = &["⊕", "fn", "func", "def"];
+
+/// Forms whose first block is followed by a second block or a count.
+const TWO_PART_FORMS: &[&str] = &["当", "while", "bench"];
+
+/// Whether `source` stops partway through a construct, so a REPL should
+/// read another line before running it: a `⺆` block or a string is still
+/// open, a block form (`⨯`, `若`, `⊕ name`, ...) is still waiting for its
+/// block, or `当 ⺆ cond ⺘` or `bench ⺆ body ⺘` for what follows.
+#[must_use]
+pub fn is_incomplete(source: &str) -> bool {
+    let mut stream = IncrementalTokenizer::new();
+    stream.feed(source);
+    stream.feed("\n");
+    if !stream.is_complete() {
+        return true;
+    }
+    let tokens: Vec<Token<'_>> = Tokenizer::new(source).collect();
+    let defines = |t: &Token<'_>| DEFINE_FORMS.contains(&t.text);
+    let symbol = |t: &Token<'_>| t.kind == TokenKind::Symbol;
+    let waiting = match tokens.as_slice() {
+        [.., def, name, arity] if defines(def) && symbol(name) && arity.kind == TokenKind::Integer => true,
+        [.., def, name] if defines(def) && symbol(name) => true,
+        [.., last] => BLOCK_FORMS.contains(&last.text) || defines(last),
+        [] => false,
+    };
+    if waiting || !tokens.last().is_some_and(|t| t.text == "⺘") {
+        return waiting;
+    }
+    // Find the block the last `⺘` closes and look before it.
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().rev() {
+        match token.text {
+            "⺘" => depth += 1,
+            "⺆" if depth == 1 => return i > 0 && TWO_PART_FORMS.contains(&tokens[i - 1].text),
+            "⺆" => depth -= 1,
+            _ => {}
+        }
+    }
+    false
+}
+
 /// Parse a token's text into a string value (handling escapes).
+///
+/// Raw strings (`"""..."""`) are returned as written, without escape
+/// processing.
 #[must_use]
 pub fn parse_string_literal(text: &str) -> String {
+    if let Some(raw) = text.strip_prefix(RAW_QUOTE) {
+        return raw.strip_suffix(RAW_QUOTE).unwrap_or(raw).to_string();
+    }
     // Strip quotes
     let inner = if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
         &text[1..text.len() - 1]
@@ -375,6 +675,32 @@ mod tests {
         assert_eq!(tokens[0].text, r#""hello""#);
     }
 
+    #[test]
+    fn tokenize_interpolated_strings() {
+        let tokens: Vec<_> = Tokenizer::new(r#"$"n = {n}" $ "plain" $x"#).collect();
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[0].kind, TokenKind::Interpolated);
+        assert_eq!(tokens[0].text, r#"$"n = {n}""#);
+        assert_eq!(tokens[1].kind, TokenKind::Symbol);
+        assert_eq!(tokens[2].kind, TokenKind::String);
+        assert_eq!(tokens[3].text, "$x");
+    }
+
+    #[test]
+    fn tokenize_raw_strings() {
+        let source = "1 \"\"\"Room:\n  a \"door\" \\n\n\"\"\" 2 \"\"";
+        let tokens: Vec<_> = Tokenizer::new(source).collect();
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[1].kind, TokenKind::String);
+        assert_eq!(parse_string_literal(tokens[1].text), "Room:\n  a \"door\" \\n\n");
+        assert_eq!((tokens[2].text, tokens[2].span.line(), tokens[2].span.column()), ("2", 3, 5));
+        assert_eq!(parse_string_literal(tokens[3].text), "");
+
+        let open: Vec<_> = Tokenizer::new("\"\"\"never closed\" x").collect();
+        assert_eq!(open.len(), 1);
+        assert!(!is_closed(&open[0]));
+    }
+
     #[test]
     fn tokenize_symbols() {
         let tokens: Vec<_> = Tokenizer::new("+ - dup |0⟩ π").collect();
@@ -426,6 +752,16 @@ mod tests {
         assert_eq!(tokens[3].text, "→");
     }
 
+    #[test]
+    fn tokenize_infix_sugar() {
+        let tokens: Vec<_> = Tokenizer::new("1 =(3 * (2 + 4)) + = ==").collect();
+        assert_eq!(tokens.len(), 5);
+        assert_eq!(tokens[1].kind, TokenKind::Infix);
+        assert_eq!(tokens[1].text, "=(3 * (2 + 4))");
+        assert_eq!(tokens[2].text, "+");
+        assert_eq!(tokens[3].kind, TokenKind::Symbol);
+    }
+
     #[test]
     fn multiline_tracking() {
         let tokens: Vec<_> = Tokenizer::new("a\nb\nc").collect();
@@ -442,4 +778,50 @@ mod tests {
         assert_eq!(tokens[1].span.column(), 5);
         assert_eq!(tokens[2].span.column(), 9);
     }
+
+    #[test]
+    fn incremental_matches_whole_text_at_every_split() {
+        let source = "1 -2.5 \"a \\\" b\" \"\"\"raw \" \n text\"\"\" $\"{x} y\" =(3 * (2 + 4)) ⺆ dup ⺘ # note\n:top @top x\"y\" 3.";
+        let key = |t: &OwnedToken| (t.kind, t.text.clone(), t.span);
+        let whole: Vec<_> = Tokenizer::new(source).map(|t| key(&t.into())).collect();
+        let splits = source.char_indices().map(|(i, _)| i).chain([source.len()]);
+        for a in splits {
+            for b in source.char_indices().map(|(i, _)| i).filter(|&b| b >= a) {
+                let mut stream = IncrementalTokenizer::new();
+                let mut tokens = stream.feed(&source[..a]);
+                tokens.extend(stream.feed(&source[a..b]));
+                tokens.extend(stream.feed(&source[b..]));
+                tokens.extend(stream.finish());
+                assert_eq!(tokens.iter().map(key).collect::<Vec<_>>(), whole, "split at {a} and {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn incomplete_until_constructs_close() {
+        for source in ["⊕ sq ⺆", "⊕ sq", "⊕ add 2", "3 ⨯", "1 若 ⺆ 2 ⺘ 或", "当 ⺆ x 0 > ⺘", "bench ⺆ 1 ⺘", "\"open", "\"\"\"raw\n"]
+        {
+            assert!(is_incomplete(source), "{source}");
+        }
+        for source in ["", "1 2 +", "⊕ sq ⺆\ndup *\n⺘", "3 ⨯ ⺆ 1 ⺘", "当 ⺆ 1 ⺘ ⺆ 2 ⺘", "bench ⺆ 1 ⺘ 10", "1 ⺘", "# ⺆"] {
+            assert!(!is_incomplete(source), "{source}");
+        }
+    }
+
+    #[test]
+    fn incremental_holds_open_strings_and_blocks() {
+        let mut stream = IncrementalTokenizer::new();
+        assert!(stream.feed("\"unterminated ").is_empty());
+        assert!(!stream.is_complete());
+        assert_eq!(stream.feed("string\"\n⺆ ⺆")[0].text, "\"unterminated string\"");
+        assert_eq!(stream.block_depth(), 1);
+        assert_eq!(stream.feed(" ⺘ ⺘ ").len(), 3);
+        assert!(stream.is_complete());
+
+        let tail = stream.feed("\n\"open");
+        assert!(tail.is_empty());
+        let rest = stream.finish();
+        assert_eq!(rest[0].text, "\"open");
+        assert_eq!(rest[0].span.line(), 3);
+    }
 }
@@ -0,0 +1,106 @@
+//! # Woflang in the browser
+//!
+//! A [`Playground`] is an interpreter with the standard ops and library
+//! whose printed output is captured instead of going to a console, so a
+//! web page can run Woflang client-side:
+//!
+//! ```js
+//! import init, { Playground } from "./pkg/woflang_wasm.js";
+//!
+//! await init();
+//! const wof = new Playground();
+//! wof.exec_line("2 3 + dup print");  // "5\n"
+//! wof.stack();                       // ["5"]
+//! ```
+//!
+//! Build with `wasm-pack build --target web` from this directory. The
+//! crate uses `woflang` without default features: no plugins, no file
+//! ops and no dynamic loading. `spawn` and `bench` fail with an error,
+//! since `wasm32-unknown-unknown` has no threads and no clock.
+
+#![deny(unsafe_code)]
+#![warn(missing_docs)]
+
+use wasm_bindgen::prelude::*;
+use woflang::core::Buffer;
+use woflang::{Builder, Interpreter, InterpreterContext};
+
+/// An interpreter session for a web page.
+#[wasm_bindgen]
+pub struct Playground {
+    interp: Interpreter,
+    output: Buffer,
+}
+
+#[wasm_bindgen]
+impl Playground {
+    /// Create a session with the standard ops and library loaded.
+    ///
+    /// # Errors
+    ///
+    /// Throws if the standard library does not load.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Result<Playground, JsError> {
+        let mut interp = Builder::new().build().map_err(to_js)?;
+        let output = Buffer::new();
+        interp.set_output(output.clone());
+        Ok(Self { interp, output })
+    }
+
+    /// Run one line and return what it printed.
+    ///
+    /// # Errors
+    ///
+    /// Throws the Woflang error if the line fails; anything printed
+    /// before the failure is left for [`take_output`](Self::take_output).
+    pub fn exec_line(&mut self, line: &str) -> Result<String, JsError> {
+        self.interp.exec_line(line).map_err(to_js)?;
+        Ok(self.output.take())
+    }
+
+    /// Run a multi-line program, such as the contents of an editor, and
+    /// return what it printed.
+    ///
+    /// # Errors
+    ///
+    /// As for [`exec_line`](Self::exec_line).
+    pub fn exec(&mut self, source: &str) -> Result<String, JsError> {
+        self.interp.exec_source(source).map_err(to_js)?;
+        Ok(self.output.take())
+    }
+
+    /// Printed text not yet returned, such as the output of a line that
+    /// then failed.
+    pub fn take_output(&self) -> String {
+        self.output.take()
+    }
+
+    /// The stack, bottom first, as the REPL displays each value.
+    pub fn stack(&self) -> Vec<String> {
+        self.interp.stack().as_slice().iter().map(ToString::to_string).collect()
+    }
+
+    /// The number of values on the stack.
+    pub fn depth(&self) -> usize {
+        self.interp.stack().len()
+    }
+
+    /// Empty the stack.
+    pub fn clear(&mut self) {
+        self.interp.stack_mut().clear();
+    }
+
+    /// Op names starting with `prefix`, sorted, for autocompletion.
+    pub fn completions(&self, prefix: &str) -> Vec<String> {
+        self.interp.registry().completions(prefix).into_iter().map(str::to_string).collect()
+    }
+
+    /// The help card for op `name`, if it has one.
+    pub fn help(&self, name: &str) -> Option<String> {
+        self.interp.help_card(name)
+    }
+}
+
+fn to_js(error: woflang::WofError) -> JsError {
+    JsError::new(&error.to_string())
+}
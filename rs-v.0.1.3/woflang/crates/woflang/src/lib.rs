@@ -0,0 +1,185 @@
+//! # Woflang
+//!
+//! Everything an embedder needs in one dependency: the interpreter, the
+//! value types, the standard ops and library, and the plugin modules,
+//! with their Cargo features lined up behind one set of flags.
+//!
+//! ```
+//! use woflang::prelude::*;
+//!
+//! let mut interp = woflang::Builder::new().build().unwrap();
+//! interp.exec_line("3 sq 1 +").unwrap();
+//!
+//! assert_eq!(interp.stack().peek().unwrap(), &WofValue::integer(10));
+//! ```
+//!
+//! ## Features
+//!
+//! | Feature           | Enables |
+//! |-------------------|---------|
+//! | `plugins`         | The math, util, crypto and logic plugins (default) |
+//! | `files`           | File ops in the standard ops (default) |
+//! | `crypto`          | Cryptographic primitives in the standard ops |
+//! | `quantum`         | Quantum ops and the quantum plugin |
+//! | `analog`          | The analog plugin and `analog` re-export |
+//! | `graph`, `games`, `science`, ... | The plugin module of the same name |
+//! | `full`            | Every plugin module and optional op set |
+//! | `jit`             | Native compilation of `jit ⺆ ... ⺘` blocks |
+//! | `dynamic-plugins` | Loading `.so`/`.dll` plugins at runtime |
+//!
+//! With no default features the crate builds for `wasm32-unknown-unknown`;
+//! the `woflang-wasm` crate wraps it for the browser.
+//!
+//! The underlying crates are re-exported as [`core`], [`runtime`],
+//! [`ops`] and (with `plugins`) `plugins` for anything the prelude
+//! does not cover.
+
+#![deny(unsafe_code)]
+#![warn(missing_docs)]
+
+pub use woflang_core as core;
+pub use woflang_ops as ops;
+pub use woflang_runtime as runtime;
+
+#[cfg(feature = "analog")]
+pub use woflang_analog as analog;
+#[cfg(feature = "plugins")]
+pub use woflang_plugins as plugins;
+
+pub use woflang_core::{InterpreterContext, Result, Span, WofError, WofStack, WofType, WofValue};
+pub use woflang_runtime::{CancelToken, Interpreter, OpMeta, Registry};
+
+/// The types most embedders use, for a glob import.
+pub mod prelude {
+    pub use crate::{
+        Builder, Interpreter, InterpreterContext, Result, WofError, WofType, WofValue,
+    };
+}
+
+/// Register the standard ops and, with the `plugins` feature, every
+/// enabled plugin module with `interp`.
+pub fn register_all(interp: &mut Interpreter) {
+    woflang_ops::register_all(interp);
+    #[cfg(feature = "plugins")]
+    woflang_plugins::register_all(interp);
+}
+
+/// Configures and creates an [`Interpreter`].
+///
+/// By default the interpreter gets the standard ops, the enabled plugins
+/// and the self-hosted standard library, the same setup as the `woflang`
+/// command.
+#[derive(Debug, Clone)]
+#[must_use]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Builder {
+    ops: bool,
+    #[cfg(feature = "plugins")]
+    plugins: bool,
+    stdlib: bool,
+    strict: bool,
+    debug: bool,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    /// A builder with the default setup.
+    pub const fn new() -> Self {
+        Self {
+            ops: true,
+            #[cfg(feature = "plugins")]
+            plugins: true,
+            stdlib: true,
+            strict: false,
+            debug: false,
+        }
+    }
+
+    /// Whether to register the standard ops. The standard library calls
+    /// them, so turning them off usually means turning it off too.
+    pub const fn ops(mut self, on: bool) -> Self {
+        self.ops = on;
+        self
+    }
+
+    /// Whether to register the plugin modules enabled at compile time.
+    #[cfg(feature = "plugins")]
+    pub const fn plugins(mut self, on: bool) -> Self {
+        self.plugins = on;
+        self
+    }
+
+    /// Whether to define the self-hosted standard library functions.
+    pub const fn stdlib(mut self, on: bool) -> Self {
+        self.stdlib = on;
+        self
+    }
+
+    /// Reject unknown symbols at compile time (see
+    /// [`Interpreter::strict`]).
+    pub const fn strict(mut self, on: bool) -> Self {
+        self.strict = on;
+        self
+    }
+
+    /// Print each executed token.
+    pub const fn debug(mut self, on: bool) -> Self {
+        self.debug = on;
+        self
+    }
+
+    /// Create the interpreter.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the standard library does not load.
+    pub fn build(self) -> Result<Interpreter> {
+        let mut interp = Interpreter::new();
+        interp.debug = self.debug;
+        if self.ops {
+            woflang_ops::register_all(&mut interp);
+        }
+        #[cfg(feature = "plugins")]
+        if self.plugins {
+            woflang_plugins::register_all(&mut interp);
+        }
+        if self.stdlib {
+            woflang_ops::stdlib::load(&mut interp)?;
+        }
+        interp.strict = self.strict;
+        Ok(interp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prelude::*;
+
+    #[test]
+    fn builder_controls_setup() {
+        let mut full = Builder::new().build().unwrap();
+        assert!(full.has_function("sq"));
+        full.exec_line("2 3 +").unwrap();
+        assert_eq!(full.stack().peek().unwrap(), &WofValue::integer(5));
+
+        let bare = Builder::new().ops(false).stdlib(false).build().unwrap();
+        assert!(!bare.has_function("sq"));
+        assert!(!bare.registry().contains("+"));
+
+        let mut strict = Builder::new().strict(true).build().unwrap();
+        assert!(strict.exec_line("1 nosuchop").is_err());
+    }
+
+    #[test]
+    fn register_all_matches_the_builder() {
+        let mut interp = Interpreter::new();
+        crate::register_all(&mut interp);
+        let built = Builder::new().stdlib(false).build().unwrap();
+        assert_eq!(interp.registry().len(), built.registry().len());
+    }
+}